@@ -0,0 +1,48 @@
+//! Verifier throughput over a corpus of ActionScript sources.
+//!
+//! The corpus defaults to the embedded core library and may be given
+//! through the `WHACK_BENCH_CORPUS` environment variable:
+//!
+//! ```text
+//! WHACK_BENCH_CORPUS=path/to/src cargo bench -p whackengine-verifier
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use whackengine_verifier::ns::*;
+
+fn run(corpus: Option<&str>) -> CorpusBenchmark {
+    match corpus {
+        Some(path) => bench_corpus(path),
+        None => {
+            // The core library itself, verified without another copy of it
+            let compiler_options = Rc::new(CompilerOptions {
+                core_library: CoreLibrarySource::Omitted,
+                ..default()
+            });
+            let mut sources = MemorySourceProvider::new();
+            for (path, text) in CoreLibrary::embedded_sources() {
+                sources.insert(&path, &text);
+            }
+            bench_sources(&compiler_options, &sources)
+        },
+    }
+}
+
+fn verifier_throughput(c: &mut Criterion) {
+    let corpus = std::env::var("WHACK_BENCH_CORPUS").ok();
+    let sample = run(corpus.as_deref());
+    if sample.invalidated {
+        eprintln!("warning: the corpus has errors; figures are not comparable");
+    }
+    print!("{}", sample.format_human());
+
+    let mut group = c.benchmark_group("verifier");
+    group.throughput(Throughput::Elements(sample.statements as u64));
+    group.bench_function("statements", |b| b.iter(|| run(corpus.as_deref())));
+    group.throughput(Throughput::Elements(sample.files as u64));
+    group.bench_function("files", |b| b.iter(|| run(corpus.as_deref())));
+    group.finish();
+}
+
+criterion_group!(benches, verifier_throughput);
+criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,34 @@
+package {
+    [Whack::External(slots="3")]
+    public dynamic class Array {
+        public static const CASEINSENSITIVE: uint = 1;
+        public static const DESCENDING: uint = 2;
+        public static const UNIQUESORT: uint = 4;
+        public static const RETURNINDEXEDARRAY: uint = 8;
+        public static const NUMERIC: uint = 16;
+
+        public native function Array(...values);
+
+        public native function get length(): uint;
+        public native function set length(value: uint): void;
+
+        public native function concat(...args): Array;
+        public native function every(callback: Function, thisObject: * = null): Boolean;
+        public native function filter(callback: Function, thisObject: * = null): Array;
+        public native function forEach(callback: Function, thisObject: * = null): void;
+        public native function indexOf(searchElement: *, fromIndex: int = 0): int;
+        public native function join(sep: * = undefined): String;
+        public native function lastIndexOf(searchElement: *, fromIndex: int = 0x7FFFFFFF): int;
+        public native function map(callback: Function, thisObject: * = null): Array;
+        public native function pop(): *;
+        public native function push(...args): uint;
+        public native function reverse(): Array;
+        public native function shift(): *;
+        public native function slice(startIndex: int = 0, endIndex: int = 16777215): Array;
+        public native function some(callback: Function, thisObject: * = null): Boolean;
+        public native function sort(...args): *;
+        public native function sortOn(fieldName: *, options: * = 0): *;
+        public native function splice(startIndex: int, deleteCount: uint = 0xFFFFFFFF, ...values): Array;
+        public native function unshift(...args): uint;
+    }
+}
@@ -0,0 +1,6 @@
+package flash.utils {
+    [Whack::External(slots="3")]
+    public dynamic class Dictionary {
+        public native function Dictionary(weakKeys: Boolean = false);
+    }
+}
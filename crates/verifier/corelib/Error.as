@@ -0,0 +1,62 @@
+package {
+    [Whack::External(slots="4")]
+    public dynamic class Error {
+        public var message: String;
+        public var name: String;
+
+        public native function Error(message: String = "", id: int = 0);
+        public native function get errorID(): int;
+        public native function getStackTrace(): String;
+        public native function toString(): String;
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class ArgumentError extends Error {
+        public native function ArgumentError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class DefinitionError extends Error {
+        public native function DefinitionError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class EvalError extends Error {
+        public native function EvalError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class RangeError extends Error {
+        public native function RangeError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class ReferenceError extends Error {
+        public native function ReferenceError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class SecurityError extends Error {
+        public native function SecurityError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class SyntaxError extends Error {
+        public native function SyntaxError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class TypeError extends Error {
+        public native function TypeError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class URIError extends Error {
+        public native function URIError(message: String = "", id: int = 0);
+    }
+
+    [Whack::External(slots="4")]
+    public dynamic class VerifyError extends Error {
+        public native function VerifyError(message: String = "", id: int = 0);
+    }
+}
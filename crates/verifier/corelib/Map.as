@@ -0,0 +1,15 @@
+package {
+    [Whack::External(slots="3")]
+    public final class Map.<K, V> {
+        public native function Map();
+
+        public native function get size(): uint;
+
+        public native function has(key: K): Boolean;
+        public native function remove(key: K): Boolean;
+        public native function clear(): void;
+        public native function keys(): Array;
+        public native function values(): Array;
+        public native function forEach(callback: Function, thisObject: Object = null): void;
+    }
+}
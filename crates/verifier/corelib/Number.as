@@ -0,0 +1,37 @@
+package {
+    [Whack::External(slots="2")]
+    public final class Number {
+        public static const MAX_VALUE: Number = 1.7976931348623157e+308;
+        public static const MIN_VALUE: Number = 4.9e-324;
+        public static const NaN: Number = 0 / 0;
+        public static const NEGATIVE_INFINITY: Number = -1 / 0;
+        public static const POSITIVE_INFINITY: Number = 1 / 0;
+
+        public native function Number(value: * = 0);
+        public native function toExponential(fractionDigits: uint = 0): String;
+        public native function toFixed(fractionDigits: uint = 0): String;
+        public native function toPrecision(precision: uint = 0): String;
+        public native function toString(radix: * = 10): String;
+        public native function valueOf(): Number;
+    }
+
+    [Whack::External(slots="2")]
+    public final class int {
+        public static const MAX_VALUE: int = 0x7FFFFFFF;
+        public static const MIN_VALUE: int = -0x80000000;
+
+        public native function int(value: * = 0);
+        public native function toString(radix: * = 10): String;
+        public native function valueOf(): int;
+    }
+
+    [Whack::External(slots="2")]
+    public final class uint {
+        public static const MAX_VALUE: uint = 0xFFFFFFFF;
+        public static const MIN_VALUE: uint = 0;
+
+        public native function uint(value: * = 0);
+        public native function toString(radix: * = 10): String;
+        public native function valueOf(): uint;
+    }
+}
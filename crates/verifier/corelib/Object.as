@@ -0,0 +1,57 @@
+package {
+    [Whack::External(slots="2")]
+    public dynamic class Object {
+        public native function Object();
+
+        public native function hasOwnProperty(name: *): Boolean;
+        public native function isPrototypeOf(value: *): Boolean;
+        public native function propertyIsEnumerable(name: *): Boolean;
+        public native function setPropertyIsEnumerable(name: *, isEnum: Boolean = true): void;
+        public native function toLocaleString(): String;
+        public native function toString(): String;
+        public native function valueOf(): *;
+    }
+
+    [Whack::External(slots="2")]
+    public final class Class {
+    }
+
+    [Whack::External(slots="2")]
+    public dynamic class Function {
+        public native function get length(): int;
+        public native function apply(thisArg: * = undefined, args: * = undefined): *;
+        public native function call(thisArg: * = undefined, ...args): *;
+    }
+
+    [Whack::External(slots="2")]
+    public final class Boolean {
+        public native function Boolean(value: * = false);
+        public native function toString(): String;
+        public native function valueOf(): Boolean;
+    }
+
+    [Whack::External(slots="4")]
+    public final class Namespace {
+        public native function Namespace(prefix: * = undefined, uri: * = undefined);
+        public native function get prefix(): *;
+        public native function get uri(): String;
+        public native function toString(): String;
+    }
+
+    [Whack::External(slots="4")]
+    public final class QName {
+        public native function QName(namespace: * = undefined, localName: * = undefined);
+        public native function get localName(): String;
+        public native function get uri(): *;
+        public native function toString(): String;
+    }
+
+    public native function trace(...args): void;
+    public native function isNaN(value: Number): Boolean;
+    public native function isFinite(value: Number): Boolean;
+    public native function parseInt(str: String, radix: uint = 0): Number;
+    public native function parseFloat(str: String): Number;
+
+    public const NaN: Number = 0 / 0;
+    public const Infinity: Number = 1 / 0;
+}
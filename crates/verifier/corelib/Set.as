@@ -0,0 +1,15 @@
+package {
+    [Whack::External(slots="3")]
+    public final class Set.<T> {
+        public native function Set();
+
+        public native function get size(): uint;
+
+        public native function add(value: T): Set.<T>;
+        public native function has(value: T): Boolean;
+        public native function remove(value: T): Boolean;
+        public native function clear(): void;
+        public native function values(): Array;
+        public native function forEach(callback: Function, thisObject: Object = null): void;
+    }
+}
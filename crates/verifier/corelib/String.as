@@ -0,0 +1,28 @@
+package {
+    [Whack::External(slots="2")]
+    public final class String {
+        public native function String(value: * = "");
+
+        public native function get length(): int;
+
+        public static native function fromCharCode(...charCodes): String;
+
+        public native function charAt(index: Number = 0): String;
+        public native function charCodeAt(index: Number = 0): Number;
+        public native function concat(...args): String;
+        public native function indexOf(searchString: String = "undefined", startIndex: Number = 0): int;
+        public native function lastIndexOf(searchString: String = "undefined", startIndex: Number = 0x7FFFFFFF): int;
+        public native function localeCompare(other: String = "undefined", ...values): int;
+        public native function match(pattern: * = undefined): Array;
+        public native function replace(pattern: * = undefined, replacement: * = undefined): String;
+        public native function search(pattern: * = undefined): int;
+        public native function slice(startIndex: Number = 0, endIndex: Number = 0x7FFFFFFF): String;
+        public native function split(delimiter: * = undefined, limit: * = 0xFFFFFFFF): Array;
+        public native function substr(startIndex: Number = 0, len: Number = 0x7FFFFFFF): String;
+        public native function substring(startIndex: Number = 0, endIndex: Number = 0x7FFFFFFF): String;
+        public native function toLowerCase(): String;
+        public native function toUpperCase(): String;
+        public native function toString(): String;
+        public native function valueOf(): String;
+    }
+}
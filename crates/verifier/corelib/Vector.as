@@ -0,0 +1,29 @@
+package __AS3__.vec {
+    [Whack::External(slots="3")]
+    public final dynamic class Vector.<T> {
+        public native function Vector(length: uint = 0, fixed: Boolean = false);
+
+        public native function get fixed(): Boolean;
+        public native function set fixed(value: Boolean): void;
+        public native function get length(): uint;
+        public native function set length(value: uint): void;
+
+        public native function concat(...args): Vector.<T>;
+        public native function every(callback: Function, thisObject: Object = null): Boolean;
+        public native function filter(callback: Function, thisObject: Object = null): Vector.<T>;
+        public native function forEach(callback: Function, thisObject: Object = null): void;
+        public native function indexOf(searchElement: T, fromIndex: int = 0): int;
+        public native function join(sep: String = ","): String;
+        public native function lastIndexOf(searchElement: T, fromIndex: int = 0x7FFFFFFF): int;
+        public native function map(callback: Function, thisObject: Object = null): Vector.<T>;
+        public native function pop(): T;
+        public native function push(...args): uint;
+        public native function reverse(): Vector.<T>;
+        public native function shift(): T;
+        public native function slice(startIndex: int = 0, endIndex: int = 16777215): Vector.<T>;
+        public native function some(callback: Function, thisObject: Object = null): Boolean;
+        public native function sort(compareFunction: *): Vector.<T>;
+        public native function splice(startIndex: int, deleteCount: uint = 0xFFFFFFFF, ...items): Vector.<T>;
+        public native function unshift(...args): uint;
+    }
+}
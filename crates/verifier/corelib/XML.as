@@ -0,0 +1,42 @@
+package {
+    [Whack::External(slots="2")]
+    public final dynamic class XML {
+        public static native function get ignoreComments(): Boolean;
+        public static native function set ignoreComments(value: Boolean): void;
+        public static native function get ignoreWhitespace(): Boolean;
+        public static native function set ignoreWhitespace(value: Boolean): void;
+        public static native function get prettyPrinting(): Boolean;
+        public static native function set prettyPrinting(value: Boolean): void;
+
+        public native function XML(value: * = undefined);
+
+        public native function appendChild(child: *): XML;
+        public native function attribute(attributeName: *): XMLList;
+        public native function attributes(): XMLList;
+        public native function child(propertyName: *): XMLList;
+        public native function children(): XMLList;
+        public native function copy(): XML;
+        public native function descendants(name: * = "*"): XMLList;
+        public native function elements(name: * = "*"): XMLList;
+        public native function localName(): Object;
+        public native function name(): Object;
+        public native function parent(): *;
+        public native function text(): XMLList;
+        public native function toString(): String;
+        public native function toXMLString(): String;
+    }
+
+    [Whack::External(slots="2")]
+    public final dynamic class XMLList {
+        public native function XMLList(value: * = undefined);
+
+        public native function attribute(attributeName: *): XMLList;
+        public native function children(): XMLList;
+        public native function copy(): XMLList;
+        public native function elements(name: * = "*"): XMLList;
+        public native function length(): int;
+        public native function text(): XMLList;
+        public native function toString(): String;
+        public native function toXMLString(): String;
+    }
+}
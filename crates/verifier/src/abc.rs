@@ -0,0 +1,29 @@
+mod abc_file;
+pub use abc_file::*;
+
+mod abc_reader;
+pub use abc_reader::*;
+
+mod abc_writer;
+pub use abc_writer::*;
+
+mod abc_opcodes;
+pub use abc_opcodes::*;
+
+mod abc_disassembler;
+pub use abc_disassembler::*;
+
+mod abc_stub_generator;
+pub use abc_stub_generator::*;
+
+mod swf_abc;
+pub use swf_abc::*;
+
+mod swf_inspection;
+pub use swf_inspection::*;
+
+mod abc_method_limits;
+pub use abc_method_limits::*;
+
+mod abc_pool_merger;
+pub use abc_pool_merger::*;
\ No newline at end of file
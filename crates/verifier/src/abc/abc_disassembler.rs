@@ -0,0 +1,210 @@
+use crate::ns::*;
+
+/// A decoded AVM2 instruction.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcInstruction {
+    /// Offset of the instruction into the method body code.
+    pub offset: usize,
+    pub opcode: u8,
+    pub name: &'static str,
+    /// Decoded operands. For `lookupswitch`, these are the default offset,
+    /// the case count and each case offset.
+    pub operands: Vec<i64>,
+}
+
+/// Produces textual listings of ABC files.
+///
+/// ```ignore
+/// let abc = AbcReader::read(&bytes)?;
+/// println!("{}", AbcDisassembler::disassemble(&abc)?);
+/// ```
+pub struct AbcDisassembler;
+
+impl AbcDisassembler {
+    /// Decodes the instructions of a method body.
+    pub fn decode(code: &[u8]) -> Result<Vec<AbcInstruction>, AbcReadError> {
+        let mut reader = AbcReader::new(code);
+        let mut r = vec![];
+        while !reader.is_at_end() {
+            let offset = reader.offset();
+            let opcode = reader.read_u8()?;
+            let Some((name, operand_kinds)) = abc_opcode_info(opcode) else {
+                return Err(AbcReadError::InvalidOpcode(opcode, offset));
+            };
+            let mut operands = vec![];
+            if opcode == 0x1B {
+                operands.push(reader.read_s24()? as i64);
+                let case_count = reader.read_u30()?;
+                operands.push(case_count as i64);
+                for _ in 0..=case_count {
+                    operands.push(reader.read_s24()? as i64);
+                }
+            }
+            for kind in operand_kinds.iter() {
+                operands.push(match kind {
+                    AbcOperand::U8 => reader.read_u8()? as i64,
+                    AbcOperand::S24 => reader.read_s24()? as i64,
+                    _ => reader.read_u30()? as i64,
+                });
+            }
+            r.push(AbcInstruction { offset, opcode, name, operands });
+        }
+        Ok(r)
+    }
+
+    pub fn disassemble(abc: &AbcFile) -> Result<String, AbcReadError> {
+        let pool = &abc.constant_pool;
+        let mut r = String::new();
+        r.push_str(&format!("abc {}.{}\n", abc.major_version, abc.minor_version));
+        r.push_str(&format!("; {} integers, {} uints, {} doubles, {} strings, {} namespaces, {} multinames\n",
+            pool.integers.len(), pool.unsigned_integers.len(), pool.doubles.len(),
+            pool.strings.len(), pool.namespaces.len(), pool.multinames.len()));
+
+        for (i, script) in abc.scripts.iter().enumerate() {
+            r.push_str(&format!("\nscript {i} init=method#{}\n", script.init));
+            Self::list_traits(&mut r, abc, &script.traits, 1);
+        }
+
+        for (i, (instance, class)) in abc.instances.iter().zip(abc.classes.iter()).enumerate() {
+            r.push_str(&format!("\nclass {i} {}", Self::multiname(pool, instance.name)));
+            if instance.super_name != 0 {
+                r.push_str(&format!(" extends {}", Self::multiname(pool, instance.super_name)));
+            }
+            if !instance.interfaces.is_empty() {
+                let list = instance.interfaces.iter().map(|i| Self::multiname(pool, *i)).collect::<Vec<_>>();
+                r.push_str(&format!(" implements {}", list.join(", ")));
+            }
+            r.push('\n');
+            r.push_str(&format!("  iinit=method#{} cinit=method#{}\n", instance.iinit, class.cinit));
+            r.push_str("  instance traits:\n");
+            Self::list_traits(&mut r, abc, &instance.traits, 2);
+            r.push_str("  static traits:\n");
+            Self::list_traits(&mut r, abc, &class.traits, 2);
+        }
+
+        for body in abc.method_bodies.iter() {
+            let method = abc.methods.get(body.method as usize);
+            let name = method.and_then(|m| pool.string(m.name)).unwrap_or("");
+            r.push_str(&format!("\nmethod#{} {name}\n", body.method));
+            r.push_str(&format!("  ; max_stack={} locals={} scope_depth={}..{}\n",
+                body.max_stack, body.local_count, body.init_scope_depth, body.max_scope_depth));
+            for instr in Self::decode(&body.code)?.iter() {
+                r.push_str(&format!("  {:>6}  {}\n", instr.offset, Self::format_instruction(abc, instr)));
+            }
+            for e in body.exceptions.iter() {
+                r.push_str(&format!("  ; try {}..{} catch {} -> {}\n",
+                    e.from, e.to,
+                    if e.exc_type == 0 { "*".into() } else { Self::multiname(pool, e.exc_type) },
+                    e.target));
+            }
+        }
+
+        Ok(r)
+    }
+
+    /// Formats an instruction with its operands resolved against the constant pool.
+    pub fn format_instruction(abc: &AbcFile, instr: &AbcInstruction) -> String {
+        let pool = &abc.constant_pool;
+        let mut r = instr.name.to_owned();
+        if instr.opcode == 0x1B {
+            let targets = instr.operands.iter().enumerate()
+                .filter(|(i, _)| *i != 1)
+                .map(|(_, o)| (instr.offset as i64 + o).to_string())
+                .collect::<Vec<_>>();
+            r.push_str(&format!(" {}", targets.join(", ")));
+            return r;
+        }
+        let Some((_, kinds)) = abc_opcode_info(instr.opcode) else {
+            return r;
+        };
+        let mut operands = vec![];
+        for (kind, value) in kinds.iter().zip(instr.operands.iter()) {
+            let i = *value as u32;
+            operands.push(match kind {
+                AbcOperand::U8 | AbcOperand::U30 => value.to_string(),
+                // Branch targets are relative to the end of the instruction.
+                AbcOperand::S24 => format!("@{}", instr.offset as i64 + 4 + value),
+                AbcOperand::Int => pool.integers.get((i as usize).wrapping_sub(1)).map(|v| v.to_string()).unwrap_or("0".into()),
+                AbcOperand::Uint => pool.unsigned_integers.get((i as usize).wrapping_sub(1)).map(|v| v.to_string()).unwrap_or("0".into()),
+                AbcOperand::Double => pool.doubles.get((i as usize).wrapping_sub(1)).map(|v| v.to_string()).unwrap_or("NaN".into()),
+                AbcOperand::String => format!("{:?}", pool.string(i).unwrap_or("")),
+                AbcOperand::Namespace => Self::namespace(pool, i),
+                AbcOperand::Multiname => Self::multiname(pool, i),
+                AbcOperand::Method => format!("method#{i}"),
+                AbcOperand::Class => format!("class#{i}"),
+                AbcOperand::Exception => format!("exception#{i}"),
+            });
+        }
+        if !operands.is_empty() {
+            r.push(' ');
+            r.push_str(&operands.join(", "));
+        }
+        r
+    }
+
+    pub fn namespace(pool: &AbcConstantPool, index: u32) -> String {
+        let Some(ns) = pool.namespace(index) else {
+            return "*".into();
+        };
+        let name = pool.string(ns.name).unwrap_or("");
+        match ns.kind {
+            AbcNamespaceInfo::PRIVATE_NS => format!("private({name})"),
+            AbcNamespaceInfo::PACKAGE_INTERNAL_NS => format!("internal({name})"),
+            AbcNamespaceInfo::PROTECTED_NAMESPACE => format!("protected({name})"),
+            AbcNamespaceInfo::STATIC_PROTECTED_NS => format!("static protected({name})"),
+            _ => name.to_owned(),
+        }
+    }
+
+    pub fn multiname(pool: &AbcConstantPool, index: u32) -> String {
+        let Some(mn) = pool.multiname(index) else {
+            return "*".into();
+        };
+        match mn {
+            AbcMultiname::QName { attribute, ns, name } => {
+                format!("{}{}::{}", if *attribute { "@" } else { "" }, Self::namespace(pool, *ns), pool.string(*name).unwrap_or("*"))
+            },
+            AbcMultiname::RTQName { attribute, name } => {
+                format!("{}(rt)::{}", if *attribute { "@" } else { "" }, pool.string(*name).unwrap_or("*"))
+            },
+            AbcMultiname::RTQNameL { attribute } => {
+                format!("{}(rt)::(rt)", if *attribute { "@" } else { "" })
+            },
+            AbcMultiname::Multiname { attribute, name, ns_set } => {
+                format!("{}[{}]::{}", if *attribute { "@" } else { "" }, Self::ns_set(pool, *ns_set), pool.string(*name).unwrap_or("*"))
+            },
+            AbcMultiname::MultinameL { attribute, ns_set } => {
+                format!("{}[{}]::(rt)", if *attribute { "@" } else { "" }, Self::ns_set(pool, *ns_set))
+            },
+            AbcMultiname::TypeName { name, params } => {
+                let params = params.iter().map(|p| Self::multiname(pool, *p)).collect::<Vec<_>>();
+                format!("{}.<{}>", Self::multiname(pool, *name), params.join(", "))
+            },
+        }
+    }
+
+    fn ns_set(pool: &AbcConstantPool, index: u32) -> String {
+        let Some(set) = (if index == 0 { None } else { pool.ns_sets.get(index as usize - 1) }) else {
+            return "".into();
+        };
+        set.iter().map(|ns| Self::namespace(pool, *ns)).collect::<Vec<_>>().join(", ")
+    }
+
+    fn list_traits(r: &mut String, abc: &AbcFile, traits: &[AbcTrait], indent: usize) {
+        let pool = &abc.constant_pool;
+        let indent = "  ".repeat(indent);
+        for t in traits.iter() {
+            let name = Self::multiname(pool, t.name);
+            let line = match &t.data {
+                AbcTraitData::Slot { slot_id, type_name, .. } => format!("var {name}: {} (slot {slot_id})", Self::multiname(pool, *type_name)),
+                AbcTraitData::Const { slot_id, type_name, .. } => format!("const {name}: {} (slot {slot_id})", Self::multiname(pool, *type_name)),
+                AbcTraitData::Method { disp_id, method } => format!("method {name} = method#{method} (disp {disp_id})"),
+                AbcTraitData::Getter { disp_id, method } => format!("getter {name} = method#{method} (disp {disp_id})"),
+                AbcTraitData::Setter { disp_id, method } => format!("setter {name} = method#{method} (disp {disp_id})"),
+                AbcTraitData::Class { slot_id, class } => format!("class {name} = class#{class} (slot {slot_id})"),
+                AbcTraitData::Function { slot_id, function } => format!("function {name} = method#{function} (slot {slot_id})"),
+            };
+            r.push_str(&format!("{indent}{line}\n"));
+        }
+    }
+}
\ No newline at end of file
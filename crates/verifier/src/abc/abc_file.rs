@@ -0,0 +1,205 @@
+/// Structured model of an ActionScript Byte Code (ABC) file.
+///
+/// Indices into the constant pool are kept as they are encoded,
+/// thus index zero refers to the implicit first entry
+/// ("any" name, empty string and so on).
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AbcFile {
+    pub minor_version: u16,
+    pub major_version: u16,
+    pub constant_pool: AbcConstantPool,
+    pub methods: Vec<AbcMethodInfo>,
+    pub metadata: Vec<AbcMetadataInfo>,
+    pub instances: Vec<AbcInstanceInfo>,
+    pub classes: Vec<AbcClassInfo>,
+    pub scripts: Vec<AbcScriptInfo>,
+    pub method_bodies: Vec<AbcMethodBody>,
+}
+
+/// Constant pool. Each list excludes the implicit entry at index zero.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AbcConstantPool {
+    pub integers: Vec<i32>,
+    pub unsigned_integers: Vec<u32>,
+    pub doubles: Vec<f64>,
+    pub strings: Vec<String>,
+    pub namespaces: Vec<AbcNamespaceInfo>,
+    pub ns_sets: Vec<Vec<u32>>,
+    pub multinames: Vec<AbcMultiname>,
+}
+
+impl AbcConstantPool {
+    pub fn string(&self, index: u32) -> Option<&str> {
+        if index == 0 { None } else { self.strings.get(index as usize - 1).map(|s| s.as_str()) }
+    }
+
+    pub fn namespace(&self, index: u32) -> Option<&AbcNamespaceInfo> {
+        if index == 0 { None } else { self.namespaces.get(index as usize - 1) }
+    }
+
+    pub fn multiname(&self, index: u32) -> Option<&AbcMultiname> {
+        if index == 0 { None } else { self.multinames.get(index as usize - 1) }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AbcNamespaceInfo {
+    pub kind: u8,
+    pub name: u32,
+}
+
+impl AbcNamespaceInfo {
+    pub const NAMESPACE: u8 = 0x08;
+    pub const PACKAGE_NAMESPACE: u8 = 0x16;
+    pub const PACKAGE_INTERNAL_NS: u8 = 0x17;
+    pub const PROTECTED_NAMESPACE: u8 = 0x18;
+    pub const EXPLICIT_NAMESPACE: u8 = 0x19;
+    pub const STATIC_PROTECTED_NS: u8 = 0x1A;
+    pub const PRIVATE_NS: u8 = 0x05;
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AbcMultiname {
+    QName { attribute: bool, ns: u32, name: u32 },
+    RTQName { attribute: bool, name: u32 },
+    RTQNameL { attribute: bool },
+    Multiname { attribute: bool, name: u32, ns_set: u32 },
+    MultinameL { attribute: bool, ns_set: u32 },
+    TypeName { name: u32, params: Vec<u32> },
+}
+
+impl AbcMultiname {
+    pub const QNAME: u8 = 0x07;
+    pub const QNAME_A: u8 = 0x0D;
+    pub const RTQNAME: u8 = 0x0F;
+    pub const RTQNAME_A: u8 = 0x10;
+    pub const RTQNAME_L: u8 = 0x11;
+    pub const RTQNAME_LA: u8 = 0x12;
+    pub const MULTINAME: u8 = 0x09;
+    pub const MULTINAME_A: u8 = 0x0E;
+    pub const MULTINAME_L: u8 = 0x1B;
+    pub const MULTINAME_LA: u8 = 0x1C;
+    pub const TYPE_NAME: u8 = 0x1D;
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcMethodInfo {
+    pub return_type: u32,
+    pub param_types: Vec<u32>,
+    pub name: u32,
+    pub flags: u8,
+    pub options: Vec<AbcOptionDetail>,
+    pub param_names: Vec<u32>,
+}
+
+impl AbcMethodInfo {
+    pub const NEED_ARGUMENTS: u8 = 0x01;
+    pub const NEED_ACTIVATION: u8 = 0x02;
+    pub const NEED_REST: u8 = 0x04;
+    pub const HAS_OPTIONAL: u8 = 0x08;
+    pub const SET_DXNS: u8 = 0x40;
+    pub const HAS_PARAM_NAMES: u8 = 0x80;
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcOptionDetail {
+    pub value: u32,
+    pub kind: u8,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcMetadataInfo {
+    pub name: u32,
+    pub items: Vec<(u32, u32)>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcInstanceInfo {
+    pub name: u32,
+    pub super_name: u32,
+    pub flags: u8,
+    pub protected_ns: Option<u32>,
+    pub interfaces: Vec<u32>,
+    pub iinit: u32,
+    pub traits: Vec<AbcTrait>,
+}
+
+impl AbcInstanceInfo {
+    pub const SEALED: u8 = 0x01;
+    pub const FINAL: u8 = 0x02;
+    pub const INTERFACE: u8 = 0x04;
+    pub const PROTECTED_NS: u8 = 0x08;
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcClassInfo {
+    pub cinit: u32,
+    pub traits: Vec<AbcTrait>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcScriptInfo {
+    pub init: u32,
+    pub traits: Vec<AbcTrait>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcTrait {
+    pub name: u32,
+    /// Upper four bits of the trait kind byte.
+    pub attributes: u8,
+    pub data: AbcTraitData,
+    pub metadata: Vec<u32>,
+}
+
+impl AbcTrait {
+    pub const ATTR_FINAL: u8 = 0x01;
+    pub const ATTR_OVERRIDE: u8 = 0x02;
+    pub const ATTR_METADATA: u8 = 0x04;
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AbcTraitData {
+    Slot { slot_id: u32, type_name: u32, value: Option<AbcOptionDetail> },
+    Const { slot_id: u32, type_name: u32, value: Option<AbcOptionDetail> },
+    Method { disp_id: u32, method: u32 },
+    Getter { disp_id: u32, method: u32 },
+    Setter { disp_id: u32, method: u32 },
+    Class { slot_id: u32, class: u32 },
+    Function { slot_id: u32, function: u32 },
+}
+
+impl AbcTraitData {
+    pub fn kind(&self) -> u8 {
+        match self {
+            Self::Slot { .. } => 0,
+            Self::Method { .. } => 1,
+            Self::Getter { .. } => 2,
+            Self::Setter { .. } => 3,
+            Self::Class { .. } => 4,
+            Self::Function { .. } => 5,
+            Self::Const { .. } => 6,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcMethodBody {
+    pub method: u32,
+    pub max_stack: u32,
+    pub local_count: u32,
+    pub init_scope_depth: u32,
+    pub max_scope_depth: u32,
+    pub code: Vec<u8>,
+    pub exceptions: Vec<AbcExceptionInfo>,
+    pub traits: Vec<AbcTrait>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcExceptionInfo {
+    pub from: u32,
+    pub to: u32,
+    pub target: u32,
+    pub exc_type: u32,
+    pub var_name: u32,
+}
\ No newline at end of file
@@ -0,0 +1,100 @@
+use crate::ns::*;
+
+/// Operand stack, scope stack and register counts of a method body.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AbcMethodLimits {
+    pub method: u32,
+    /// Name of the method as its trait, such as `com.example::Main/init`,
+    /// falling back to the name of its method info.
+    pub name: String,
+    pub max_stack: u32,
+    /// Scope stack entries pushed by the method itself, excluding those
+    /// of the enclosing scope.
+    pub max_scope_depth: u32,
+    pub local_count: u32,
+}
+
+impl AbcMethodLimits {
+    /// Limits of every method body of an ABC file.
+    pub fn collect(abc: &AbcFile) -> Vec<Self> {
+        let names = Self::method_names(abc);
+        abc.method_bodies.iter().map(|body| {
+            let name = names.get(&body.method).cloned()
+                .or_else(|| abc.methods.get(body.method as usize).and_then(|m| abc.constant_pool.string(m.name)).map(|n| n.to_owned()))
+                .unwrap_or(format!("method#{}", body.method));
+            Self {
+                method: body.method,
+                name,
+                max_stack: body.max_stack,
+                max_scope_depth: body.max_scope_depth.saturating_sub(body.init_scope_depth),
+                local_count: body.local_count,
+            }
+        }).collect()
+    }
+
+    fn method_names(abc: &AbcFile) -> HashMap<u32, String> {
+        let pool = &abc.constant_pool;
+        let mut r = HashMap::new();
+        for (i, script) in abc.scripts.iter().enumerate() {
+            r.insert(script.init, format!("script#{i}"));
+            Self::trait_names(pool, "", &script.traits, &mut r);
+        }
+        for (instance, class) in abc.instances.iter().zip(abc.classes.iter()) {
+            let class_name = AbcDisassembler::multiname(pool, instance.name);
+            r.insert(instance.iinit, format!("{class_name}/constructor"));
+            r.insert(class.cinit, format!("{class_name}/static initializer"));
+            Self::trait_names(pool, &format!("{class_name}/"), &instance.traits, &mut r);
+            Self::trait_names(pool, &format!("{class_name}/static "), &class.traits, &mut r);
+        }
+        r
+    }
+
+    fn trait_names(pool: &AbcConstantPool, prefix: &str, traits: &[AbcTrait], names: &mut HashMap<u32, String>) {
+        for t in traits.iter() {
+            let name = AbcDisassembler::multiname(pool, t.name);
+            match &t.data {
+                AbcTraitData::Method { method, .. } |
+                AbcTraitData::Function { function: method, .. } => {
+                    names.insert(*method, format!("{prefix}{name}"));
+                },
+                AbcTraitData::Getter { method, .. } => {
+                    names.insert(*method, format!("{prefix}get {name}"));
+                },
+                AbcTraitData::Setter { method, .. } => {
+                    names.insert(*method, format!("{prefix}set {name}"));
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+/// Limits past which a method body is reported by a `Compilation`
+/// through `CompilerEvent::MethodLimitExceeded`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MethodLimitThresholds {
+    pub max_stack: u32,
+    pub max_scope_depth: u32,
+    pub local_count: u32,
+}
+
+impl Default for MethodLimitThresholds {
+    fn default() -> Self {
+        Self {
+            max_stack: 256,
+            max_scope_depth: 32,
+            local_count: 256,
+        }
+    }
+}
+
+impl MethodLimitThresholds {
+    /// Limits a method exceeds, as `(limit name, value, threshold)`.
+    pub fn exceeded(&self, limits: &AbcMethodLimits) -> Vec<(&'static str, u32, u32)> {
+        [
+            ("max-stack", limits.max_stack, self.max_stack),
+            ("max-scope-depth", limits.max_scope_depth, self.max_scope_depth),
+            ("locals", limits.local_count, self.local_count),
+        ].into_iter().filter(|(_, value, threshold)| value > threshold).collect()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,200 @@
+/// Operand encoding of an AVM2 instruction.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AbcOperand {
+    U8,
+    U30,
+    S24,
+    /// Index into the integer pool.
+    Int,
+    /// Index into the unsigned integer pool.
+    Uint,
+    /// Index into the double pool.
+    Double,
+    /// Index into the string pool.
+    String,
+    /// Index into the namespace pool.
+    Namespace,
+    /// Index into the multiname pool.
+    Multiname,
+    /// Index into the method list.
+    Method,
+    /// Index into the class list.
+    Class,
+    /// Index into the exception list of the method body.
+    Exception,
+}
+
+/// Returns the mnemonic and operands of an AVM2 opcode. The `lookupswitch`
+/// operands are variable-length and are handled by the disassembler.
+pub fn abc_opcode_info(opcode: u8) -> Option<(&'static str, &'static [AbcOperand])> {
+    use AbcOperand::*;
+    Some(match opcode {
+        0x01 => ("bkpt", &[]),
+        0x02 => ("nop", &[]),
+        0x03 => ("throw", &[]),
+        0x04 => ("getsuper", &[Multiname]),
+        0x05 => ("setsuper", &[Multiname]),
+        0x06 => ("dxns", &[String]),
+        0x07 => ("dxnslate", &[]),
+        0x08 => ("kill", &[U30]),
+        0x09 => ("label", &[]),
+        0x0C => ("ifnlt", &[S24]),
+        0x0D => ("ifnle", &[S24]),
+        0x0E => ("ifngt", &[S24]),
+        0x0F => ("ifnge", &[S24]),
+        0x10 => ("jump", &[S24]),
+        0x11 => ("iftrue", &[S24]),
+        0x12 => ("iffalse", &[S24]),
+        0x13 => ("ifeq", &[S24]),
+        0x14 => ("ifne", &[S24]),
+        0x15 => ("iflt", &[S24]),
+        0x16 => ("ifle", &[S24]),
+        0x17 => ("ifgt", &[S24]),
+        0x18 => ("ifge", &[S24]),
+        0x19 => ("ifstricteq", &[S24]),
+        0x1A => ("ifstrictne", &[S24]),
+        0x1B => ("lookupswitch", &[]),
+        0x1C => ("pushwith", &[]),
+        0x1D => ("popscope", &[]),
+        0x1E => ("nextname", &[]),
+        0x1F => ("hasnext", &[]),
+        0x20 => ("pushnull", &[]),
+        0x21 => ("pushundefined", &[]),
+        0x23 => ("nextvalue", &[]),
+        0x24 => ("pushbyte", &[U8]),
+        0x25 => ("pushshort", &[U30]),
+        0x26 => ("pushtrue", &[]),
+        0x27 => ("pushfalse", &[]),
+        0x28 => ("pushnan", &[]),
+        0x29 => ("pop", &[]),
+        0x2A => ("dup", &[]),
+        0x2B => ("swap", &[]),
+        0x2C => ("pushstring", &[String]),
+        0x2D => ("pushint", &[Int]),
+        0x2E => ("pushuint", &[Uint]),
+        0x2F => ("pushdouble", &[Double]),
+        0x30 => ("pushscope", &[]),
+        0x31 => ("pushnamespace", &[Namespace]),
+        0x32 => ("hasnext2", &[U30, U30]),
+        0x35 => ("li8", &[]),
+        0x36 => ("li16", &[]),
+        0x37 => ("li32", &[]),
+        0x38 => ("lf32", &[]),
+        0x39 => ("lf64", &[]),
+        0x3A => ("si8", &[]),
+        0x3B => ("si16", &[]),
+        0x3C => ("si32", &[]),
+        0x3D => ("sf32", &[]),
+        0x3E => ("sf64", &[]),
+        0x40 => ("newfunction", &[Method]),
+        0x41 => ("call", &[U30]),
+        0x42 => ("construct", &[U30]),
+        0x43 => ("callmethod", &[U30, U30]),
+        0x44 => ("callstatic", &[Method, U30]),
+        0x45 => ("callsuper", &[Multiname, U30]),
+        0x46 => ("callproperty", &[Multiname, U30]),
+        0x47 => ("returnvoid", &[]),
+        0x48 => ("returnvalue", &[]),
+        0x49 => ("constructsuper", &[U30]),
+        0x4A => ("constructprop", &[Multiname, U30]),
+        0x4C => ("callproplex", &[Multiname, U30]),
+        0x4E => ("callsupervoid", &[Multiname, U30]),
+        0x4F => ("callpropvoid", &[Multiname, U30]),
+        0x50 => ("sxi1", &[]),
+        0x51 => ("sxi8", &[]),
+        0x52 => ("sxi16", &[]),
+        0x53 => ("applytype", &[U30]),
+        0x55 => ("newobject", &[U30]),
+        0x56 => ("newarray", &[U30]),
+        0x57 => ("newactivation", &[]),
+        0x58 => ("newclass", &[Class]),
+        0x59 => ("getdescendants", &[Multiname]),
+        0x5A => ("newcatch", &[Exception]),
+        0x5D => ("findpropstrict", &[Multiname]),
+        0x5E => ("findproperty", &[Multiname]),
+        0x5F => ("finddef", &[Multiname]),
+        0x60 => ("getlex", &[Multiname]),
+        0x61 => ("setproperty", &[Multiname]),
+        0x62 => ("getlocal", &[U30]),
+        0x63 => ("setlocal", &[U30]),
+        0x64 => ("getglobalscope", &[]),
+        0x65 => ("getscopeobject", &[U8]),
+        0x66 => ("getproperty", &[Multiname]),
+        0x68 => ("initproperty", &[Multiname]),
+        0x6A => ("deleteproperty", &[Multiname]),
+        0x6C => ("getslot", &[U30]),
+        0x6D => ("setslot", &[U30]),
+        0x6E => ("getglobalslot", &[U30]),
+        0x6F => ("setglobalslot", &[U30]),
+        0x70 => ("convert_s", &[]),
+        0x71 => ("esc_xelem", &[]),
+        0x72 => ("esc_xattr", &[]),
+        0x73 => ("convert_i", &[]),
+        0x74 => ("convert_u", &[]),
+        0x75 => ("convert_d", &[]),
+        0x76 => ("convert_b", &[]),
+        0x77 => ("convert_o", &[]),
+        0x78 => ("checkfilter", &[]),
+        0x80 => ("coerce", &[Multiname]),
+        0x81 => ("coerce_b", &[]),
+        0x82 => ("coerce_a", &[]),
+        0x83 => ("coerce_i", &[]),
+        0x84 => ("coerce_d", &[]),
+        0x85 => ("coerce_s", &[]),
+        0x86 => ("astype", &[Multiname]),
+        0x87 => ("astypelate", &[]),
+        0x88 => ("coerce_u", &[]),
+        0x89 => ("coerce_o", &[]),
+        0x90 => ("negate", &[]),
+        0x91 => ("increment", &[]),
+        0x92 => ("inclocal", &[U30]),
+        0x93 => ("decrement", &[]),
+        0x94 => ("declocal", &[U30]),
+        0x95 => ("typeof", &[]),
+        0x96 => ("not", &[]),
+        0x97 => ("bitnot", &[]),
+        0xA0 => ("add", &[]),
+        0xA1 => ("subtract", &[]),
+        0xA2 => ("multiply", &[]),
+        0xA3 => ("divide", &[]),
+        0xA4 => ("modulo", &[]),
+        0xA5 => ("lshift", &[]),
+        0xA6 => ("rshift", &[]),
+        0xA7 => ("urshift", &[]),
+        0xA8 => ("bitand", &[]),
+        0xA9 => ("bitor", &[]),
+        0xAA => ("bitxor", &[]),
+        0xAB => ("equals", &[]),
+        0xAC => ("strictequals", &[]),
+        0xAD => ("lessthan", &[]),
+        0xAE => ("lessequals", &[]),
+        0xAF => ("greaterthan", &[]),
+        0xB0 => ("greaterequals", &[]),
+        0xB1 => ("instanceof", &[]),
+        0xB2 => ("istype", &[Multiname]),
+        0xB3 => ("istypelate", &[]),
+        0xB4 => ("in", &[]),
+        0xC0 => ("increment_i", &[]),
+        0xC1 => ("decrement_i", &[]),
+        0xC2 => ("inclocal_i", &[U30]),
+        0xC3 => ("declocal_i", &[U30]),
+        0xC4 => ("negate_i", &[]),
+        0xC5 => ("add_i", &[]),
+        0xC6 => ("subtract_i", &[]),
+        0xC7 => ("multiply_i", &[]),
+        0xD0 => ("getlocal0", &[]),
+        0xD1 => ("getlocal1", &[]),
+        0xD2 => ("getlocal2", &[]),
+        0xD3 => ("getlocal3", &[]),
+        0xD4 => ("setlocal0", &[]),
+        0xD5 => ("setlocal1", &[]),
+        0xD6 => ("setlocal2", &[]),
+        0xD7 => ("setlocal3", &[]),
+        0xEF => ("debug", &[U8, String, U8, U30]),
+        0xF0 => ("debugline", &[U30]),
+        0xF1 => ("debugfile", &[String]),
+        _ => {
+            return None;
+        },
+    })
+}
\ No newline at end of file
@@ -0,0 +1,424 @@
+use crate::ns::*;
+
+/// Constant pool entry counts and encoded sizes before and after an
+/// `AbcPoolMerger` merge.
+#[derive(Clone, Default, Debug)]
+pub struct AbcPoolReport {
+    /// Entry counts of the separate pools, summed, in the order of
+    /// `AbcPoolReport::KINDS`.
+    pub entries_before: [usize; 7],
+    pub entries_after: [usize; 7],
+    /// Encoded sizes of the separate ABC files, summed.
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+}
+
+impl AbcPoolReport {
+    pub const KINDS: [&'static str; 7] = ["integers", "unsigned integers", "doubles", "strings", "namespaces", "namespace sets", "multinames"];
+
+    fn counts(pool: &AbcConstantPool) -> [usize; 7] {
+        [
+            pool.integers.len(),
+            pool.unsigned_integers.len(),
+            pool.doubles.len(),
+            pool.strings.len(),
+            pool.namespaces.len(),
+            pool.ns_sets.len(),
+            pool.multinames.len(),
+        ]
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = String::new();
+        for (i, kind) in Self::KINDS.iter().enumerate() {
+            r.push_str(&format!("{kind}: {} -> {}\n", self.entries_before[i], self.entries_after[i]));
+        }
+        let saved = self.bytes_before.saturating_sub(self.bytes_after);
+        r.push_str(&format!("Size: {} -> {} bytes ({saved} bytes saved)\n", self.bytes_before, self.bytes_after));
+        r
+    }
+}
+
+/// Merges ABC files, such as one per generated script, into a single ABC
+/// file sharing one constant pool, so that the strings, namespaces and
+/// multinames repeated by each script are stored once.
+///
+/// ```ignore
+/// let (abc, report) = AbcPoolMerger::merge(&per_script_abc_files)?;
+/// print!("{}", report.format_human());
+/// ```
+///
+/// Methods, meta-data, classes and scripts are concatenated in the order
+/// of the given files, and every index into them or into the constant
+/// pool is remapped, including instruction operands. Method bodies are
+/// re-encoded, adjusting branch and exception offsets.
+///
+/// A `Compilation` produces no ABC files until codegen emits whole
+/// programs, so the merger is not part of its pipeline yet.
+pub struct AbcPoolMerger {
+    pool: AbcConstantPool,
+    integers: HashMap<i32, u32>,
+    unsigned_integers: HashMap<u32, u32>,
+    doubles: HashMap<u64, u32>,
+    strings: HashMap<String, u32>,
+    namespaces: HashMap<AbcNamespaceInfo, u32>,
+    ns_sets: HashMap<Vec<u32>, u32>,
+    multinames: HashMap<AbcMultiname, u32>,
+}
+
+/// Remapping of the indices of one of the merged files.
+#[derive(Default)]
+struct AbcFileMapping {
+    integers: Vec<u32>,
+    unsigned_integers: Vec<u32>,
+    doubles: Vec<u32>,
+    strings: Vec<u32>,
+    namespaces: Vec<u32>,
+    ns_sets: Vec<u32>,
+    multinames: Vec<u32>,
+    method_offset: u32,
+    metadata_offset: u32,
+    class_offset: u32,
+}
+
+impl AbcFileMapping {
+    /// Maps a one-based pool index, zero being kept as is.
+    fn map(list: &[u32], index: u32) -> u32 {
+        if index == 0 { 0 } else { list.get(index as usize - 1).copied().unwrap_or(0) }
+    }
+}
+
+impl AbcPoolMerger {
+    pub const MERGED_FILE_NAME: &'static str = "merged.abc";
+    pub const REPORT_FILE_NAME: &'static str = "abc-pool-report.txt";
+
+    pub fn merge(files: &[AbcFile]) -> Result<(AbcFile, AbcPoolReport), AbcReadError> {
+        let mut merger = Self {
+            pool: AbcConstantPool::default(),
+            integers: HashMap::new(),
+            unsigned_integers: HashMap::new(),
+            doubles: HashMap::new(),
+            strings: HashMap::new(),
+            namespaces: HashMap::new(),
+            ns_sets: HashMap::new(),
+            multinames: HashMap::new(),
+        };
+        let mut report = AbcPoolReport::default();
+        let mut r = AbcFile {
+            minor_version: files.first().map(|f| f.minor_version).unwrap_or(16),
+            major_version: files.first().map(|f| f.major_version).unwrap_or(46),
+            ..AbcFile::default()
+        };
+        for file in files.iter() {
+            for (i, count) in AbcPoolReport::counts(&file.constant_pool).into_iter().enumerate() {
+                report.entries_before[i] += count;
+            }
+            report.bytes_before += AbcWriter::write(file).len();
+
+            let mapping = merger.map_pool(file, &r);
+            for method in file.methods.iter() {
+                r.methods.push(Self::map_method(&mapping, method));
+            }
+            for metadata in file.metadata.iter() {
+                r.metadata.push(AbcMetadataInfo {
+                    name: AbcFileMapping::map(&mapping.strings, metadata.name),
+                    items: metadata.items.iter().map(|(k, v)| (AbcFileMapping::map(&mapping.strings, *k), AbcFileMapping::map(&mapping.strings, *v))).collect(),
+                });
+            }
+            for instance in file.instances.iter() {
+                r.instances.push(AbcInstanceInfo {
+                    name: AbcFileMapping::map(&mapping.multinames, instance.name),
+                    super_name: AbcFileMapping::map(&mapping.multinames, instance.super_name),
+                    flags: instance.flags,
+                    protected_ns: instance.protected_ns.map(|ns| AbcFileMapping::map(&mapping.namespaces, ns)),
+                    interfaces: instance.interfaces.iter().map(|i| AbcFileMapping::map(&mapping.multinames, *i)).collect(),
+                    iinit: instance.iinit + mapping.method_offset,
+                    traits: Self::map_traits(&mapping, &instance.traits),
+                });
+            }
+            for class in file.classes.iter() {
+                r.classes.push(AbcClassInfo {
+                    cinit: class.cinit + mapping.method_offset,
+                    traits: Self::map_traits(&mapping, &class.traits),
+                });
+            }
+            for script in file.scripts.iter() {
+                r.scripts.push(AbcScriptInfo {
+                    init: script.init + mapping.method_offset,
+                    traits: Self::map_traits(&mapping, &script.traits),
+                });
+            }
+            for body in file.method_bodies.iter() {
+                let (code, exceptions) = Self::map_code(&mapping, body)?;
+                r.method_bodies.push(AbcMethodBody {
+                    method: body.method + mapping.method_offset,
+                    max_stack: body.max_stack,
+                    local_count: body.local_count,
+                    init_scope_depth: body.init_scope_depth,
+                    max_scope_depth: body.max_scope_depth,
+                    code,
+                    exceptions,
+                    traits: Self::map_traits(&mapping, &body.traits),
+                });
+            }
+        }
+        r.constant_pool = merger.pool;
+        report.entries_after = AbcPoolReport::counts(&r.constant_pool);
+        report.bytes_after = AbcWriter::write(&r).len();
+        Ok((r, report))
+    }
+
+    fn map_pool(&mut self, file: &AbcFile, merged: &AbcFile) -> AbcFileMapping {
+        let pool = &file.constant_pool;
+        let mut mapping = AbcFileMapping {
+            method_offset: merged.methods.len() as u32,
+            metadata_offset: merged.metadata.len() as u32,
+            class_offset: merged.classes.len() as u32,
+            ..AbcFileMapping::default()
+        };
+        for v in pool.integers.iter() {
+            let i = *self.integers.entry(*v).or_insert_with(|| {
+                self.pool.integers.push(*v);
+                self.pool.integers.len() as u32
+            });
+            mapping.integers.push(i);
+        }
+        for v in pool.unsigned_integers.iter() {
+            let i = *self.unsigned_integers.entry(*v).or_insert_with(|| {
+                self.pool.unsigned_integers.push(*v);
+                self.pool.unsigned_integers.len() as u32
+            });
+            mapping.unsigned_integers.push(i);
+        }
+        for v in pool.doubles.iter() {
+            let i = *self.doubles.entry(v.to_bits()).or_insert_with(|| {
+                self.pool.doubles.push(*v);
+                self.pool.doubles.len() as u32
+            });
+            mapping.doubles.push(i);
+        }
+        for v in pool.strings.iter() {
+            let i = *self.strings.entry(v.clone()).or_insert_with(|| {
+                self.pool.strings.push(v.clone());
+                self.pool.strings.len() as u32
+            });
+            mapping.strings.push(i);
+        }
+        for ns in pool.namespaces.iter() {
+            let ns = AbcNamespaceInfo { kind: ns.kind, name: AbcFileMapping::map(&mapping.strings, ns.name) };
+            // Private namespaces are distinct even with the same name.
+            if ns.kind == AbcNamespaceInfo::PRIVATE_NS {
+                self.pool.namespaces.push(ns);
+                mapping.namespaces.push(self.pool.namespaces.len() as u32);
+                continue;
+            }
+            let i = *self.namespaces.entry(ns.clone()).or_insert_with(|| {
+                self.pool.namespaces.push(ns);
+                self.pool.namespaces.len() as u32
+            });
+            mapping.namespaces.push(i);
+        }
+        for set in pool.ns_sets.iter() {
+            let set: Vec<u32> = set.iter().map(|ns| AbcFileMapping::map(&mapping.namespaces, *ns)).collect();
+            let i = *self.ns_sets.entry(set.clone()).or_insert_with(|| {
+                self.pool.ns_sets.push(set);
+                self.pool.ns_sets.len() as u32
+            });
+            mapping.ns_sets.push(i);
+        }
+        // Type names may refer to multinames that come after them.
+        mapping.multinames = vec![0; pool.multinames.len()];
+        for i in 0..pool.multinames.len() {
+            self.map_multiname(pool, &mut mapping, i as u32 + 1);
+        }
+        mapping
+    }
+
+    fn map_multiname(&mut self, pool: &AbcConstantPool, mapping: &mut AbcFileMapping, index: u32) -> u32 {
+        if index == 0 || index as usize > pool.multinames.len() {
+            return 0;
+        }
+        match mapping.multinames[index as usize - 1] {
+            0 => {},
+            // A type name referring to itself is mapped to no name.
+            u32::MAX => return 0,
+            i => return i,
+        }
+        let strings = &mapping.strings;
+        let mn = match &pool.multinames[index as usize - 1] {
+            AbcMultiname::QName { attribute, ns, name } => AbcMultiname::QName {
+                attribute: *attribute,
+                ns: AbcFileMapping::map(&mapping.namespaces, *ns),
+                name: AbcFileMapping::map(strings, *name),
+            },
+            AbcMultiname::RTQName { attribute, name } => AbcMultiname::RTQName { attribute: *attribute, name: AbcFileMapping::map(strings, *name) },
+            AbcMultiname::RTQNameL { attribute } => AbcMultiname::RTQNameL { attribute: *attribute },
+            AbcMultiname::Multiname { attribute, name, ns_set } => AbcMultiname::Multiname {
+                attribute: *attribute,
+                name: AbcFileMapping::map(strings, *name),
+                ns_set: AbcFileMapping::map(&mapping.ns_sets, *ns_set),
+            },
+            AbcMultiname::MultinameL { attribute, ns_set } => AbcMultiname::MultinameL { attribute: *attribute, ns_set: AbcFileMapping::map(&mapping.ns_sets, *ns_set) },
+            AbcMultiname::TypeName { name, params } => {
+                let (name, params) = (*name, params.clone());
+                // Guards against a type name referring to itself.
+                mapping.multinames[index as usize - 1] = u32::MAX;
+                AbcMultiname::TypeName {
+                    name: self.map_multiname(pool, mapping, name),
+                    params: params.iter().map(|p| self.map_multiname(pool, mapping, *p)).collect(),
+                }
+            },
+        };
+        let i = *self.multinames.entry(mn.clone()).or_insert_with(|| {
+            self.pool.multinames.push(mn);
+            self.pool.multinames.len() as u32
+        });
+        mapping.multinames[index as usize - 1] = i;
+        i
+    }
+
+    fn map_method(mapping: &AbcFileMapping, method: &AbcMethodInfo) -> AbcMethodInfo {
+        AbcMethodInfo {
+            return_type: AbcFileMapping::map(&mapping.multinames, method.return_type),
+            param_types: method.param_types.iter().map(|t| AbcFileMapping::map(&mapping.multinames, *t)).collect(),
+            name: AbcFileMapping::map(&mapping.strings, method.name),
+            flags: method.flags,
+            options: method.options.iter().map(|o| Self::map_option(mapping, o)).collect(),
+            param_names: method.param_names.iter().map(|n| AbcFileMapping::map(&mapping.strings, *n)).collect(),
+        }
+    }
+
+    fn map_option(mapping: &AbcFileMapping, option: &AbcOptionDetail) -> AbcOptionDetail {
+        let value = match option.kind {
+            0x01 => AbcFileMapping::map(&mapping.strings, option.value),
+            0x03 => AbcFileMapping::map(&mapping.integers, option.value),
+            0x04 => AbcFileMapping::map(&mapping.unsigned_integers, option.value),
+            0x06 => AbcFileMapping::map(&mapping.doubles, option.value),
+            AbcNamespaceInfo::NAMESPACE | AbcNamespaceInfo::PACKAGE_NAMESPACE | AbcNamespaceInfo::PACKAGE_INTERNAL_NS |
+            AbcNamespaceInfo::PROTECTED_NAMESPACE | AbcNamespaceInfo::EXPLICIT_NAMESPACE | AbcNamespaceInfo::STATIC_PROTECTED_NS |
+            AbcNamespaceInfo::PRIVATE_NS => AbcFileMapping::map(&mapping.namespaces, option.value),
+            // `true`, `false`, `null` and `undefined` have no pool entry.
+            _ => option.value,
+        };
+        AbcOptionDetail { value, kind: option.kind }
+    }
+
+    fn map_traits(mapping: &AbcFileMapping, traits: &[AbcTrait]) -> Vec<AbcTrait> {
+        traits.iter().map(|t| AbcTrait {
+            name: AbcFileMapping::map(&mapping.multinames, t.name),
+            attributes: t.attributes,
+            data: match &t.data {
+                AbcTraitData::Slot { slot_id, type_name, value } => AbcTraitData::Slot {
+                    slot_id: *slot_id,
+                    type_name: AbcFileMapping::map(&mapping.multinames, *type_name),
+                    value: value.as_ref().map(|v| Self::map_option(mapping, v)),
+                },
+                AbcTraitData::Const { slot_id, type_name, value } => AbcTraitData::Const {
+                    slot_id: *slot_id,
+                    type_name: AbcFileMapping::map(&mapping.multinames, *type_name),
+                    value: value.as_ref().map(|v| Self::map_option(mapping, v)),
+                },
+                AbcTraitData::Method { disp_id, method } => AbcTraitData::Method { disp_id: *disp_id, method: method + mapping.method_offset },
+                AbcTraitData::Getter { disp_id, method } => AbcTraitData::Getter { disp_id: *disp_id, method: method + mapping.method_offset },
+                AbcTraitData::Setter { disp_id, method } => AbcTraitData::Setter { disp_id: *disp_id, method: method + mapping.method_offset },
+                AbcTraitData::Class { slot_id, class } => AbcTraitData::Class { slot_id: *slot_id, class: class + mapping.class_offset },
+                AbcTraitData::Function { slot_id, function } => AbcTraitData::Function { slot_id: *slot_id, function: function + mapping.method_offset },
+            },
+            metadata: t.metadata.iter().map(|m| m + mapping.metadata_offset).collect(),
+        }).collect()
+    }
+
+    /// Re-encodes the code of a method body with remapped operands,
+    /// returning it with the exceptions at their new offsets.
+    fn map_code(mapping: &AbcFileMapping, body: &AbcMethodBody) -> Result<(Vec<u8>, Vec<AbcExceptionInfo>), AbcReadError> {
+        let instructions = AbcDisassembler::decode(&body.code)?;
+
+        // Remap operands first, as their lengths determine the new offsets.
+        let mut operands = vec![];
+        for instr in instructions.iter() {
+            if instr.opcode == 0x1B {
+                operands.push(instr.operands.clone());
+                continue;
+            }
+            let (_, kinds) = abc_opcode_info(instr.opcode).unwrap();
+            operands.push(kinds.iter().zip(instr.operands.iter()).map(|(kind, value)| {
+                let i = *value as u32;
+                match kind {
+                    AbcOperand::Int => AbcFileMapping::map(&mapping.integers, i) as i64,
+                    AbcOperand::Uint => AbcFileMapping::map(&mapping.unsigned_integers, i) as i64,
+                    AbcOperand::Double => AbcFileMapping::map(&mapping.doubles, i) as i64,
+                    AbcOperand::String => AbcFileMapping::map(&mapping.strings, i) as i64,
+                    AbcOperand::Namespace => AbcFileMapping::map(&mapping.namespaces, i) as i64,
+                    AbcOperand::Multiname => AbcFileMapping::map(&mapping.multinames, i) as i64,
+                    AbcOperand::Method => (i + mapping.method_offset) as i64,
+                    AbcOperand::Class => (i + mapping.class_offset) as i64,
+                    _ => *value,
+                }
+            }).collect::<Vec<_>>());
+        }
+
+        let mut offsets = HashMap::<usize, usize>::new();
+        let mut new_offset = 0;
+        for (instr, operands) in instructions.iter().zip(operands.iter()) {
+            offsets.insert(instr.offset, new_offset);
+            new_offset += Self::encode(instr.opcode, operands).len();
+        }
+        offsets.insert(body.code.len(), new_offset);
+        let map_offset = |offset: i64| offsets.get(&(offset as usize)).copied().unwrap_or(new_offset) as i64;
+
+        let mut code = vec![];
+        for (i, (instr, operands)) in instructions.iter().zip(operands.iter()).enumerate() {
+            let start = code.len() as i64;
+            let mut operands = operands.clone();
+            if instr.opcode == 0x1B {
+                // Offsets of `lookupswitch` are relative to its start.
+                for (j, o) in operands.iter_mut().enumerate() {
+                    if j != 1 {
+                        *o = map_offset(instr.offset as i64 + *o) - start;
+                    }
+                }
+            } else {
+                let (_, kinds) = abc_opcode_info(instr.opcode).unwrap();
+                let old_end = instructions.get(i + 1).map(|next| next.offset).unwrap_or(body.code.len()) as i64;
+                let new_end = start + Self::encode(instr.opcode, &operands).len() as i64;
+                for (kind, o) in kinds.iter().zip(operands.iter_mut()) {
+                    if *kind == AbcOperand::S24 {
+                        *o = map_offset(old_end + *o) - new_end;
+                    }
+                }
+            }
+            code.extend(Self::encode(instr.opcode, &operands));
+        }
+
+        let exceptions = body.exceptions.iter().map(|e| AbcExceptionInfo {
+            from: map_offset(e.from as i64) as u32,
+            to: map_offset(e.to as i64) as u32,
+            target: map_offset(e.target as i64) as u32,
+            exc_type: AbcFileMapping::map(&mapping.multinames, e.exc_type),
+            var_name: AbcFileMapping::map(&mapping.multinames, e.var_name),
+        }).collect();
+        Ok((code, exceptions))
+    }
+
+    fn encode(opcode: u8, operands: &[i64]) -> Vec<u8> {
+        let mut writer = AbcWriter::new();
+        writer.write_u8(opcode);
+        if opcode == 0x1B {
+            writer.write_s24(operands[0] as i32);
+            writer.write_u30(operands[1] as u32);
+            for o in operands[2..].iter() {
+                writer.write_s24(*o as i32);
+            }
+            return writer.into_bytes();
+        }
+        let (_, kinds) = abc_opcode_info(opcode).unwrap();
+        for (kind, o) in kinds.iter().zip(operands.iter()) {
+            match kind {
+                AbcOperand::U8 => writer.write_u8(*o as u8),
+                AbcOperand::S24 => writer.write_s24(*o as i32),
+                _ => writer.write_u30(*o as u32),
+            }
+        }
+        writer.into_bytes()
+    }
+}
\ No newline at end of file
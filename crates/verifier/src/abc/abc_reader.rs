@@ -0,0 +1,373 @@
+use crate::ns::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AbcReadError {
+    UnexpectedEof,
+    InvalidMultinameKind(u8),
+    InvalidTraitKind(u8),
+    InvalidUtf8,
+    /// Unknown opcode at a code offset.
+    InvalidOpcode(u8, usize),
+    /// The SWF is compressed or is not a SWF.
+    UnsupportedSwf,
+}
+
+/// Reads an ABC file from bytes.
+///
+/// ```ignore
+/// let abc = AbcReader::read(&std::fs::read("Main.abc")?)?;
+/// ```
+pub struct AbcReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> AbcReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    pub fn read(bytes: &[u8]) -> Result<AbcFile, AbcReadError> {
+        AbcReader::new(bytes).read_abc_file()
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, AbcReadError> {
+        let Some(b) = self.bytes.get(self.offset) else {
+            return Err(AbcReadError::UnexpectedEof);
+        };
+        self.offset += 1;
+        Ok(*b)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, AbcReadError> {
+        let lo = self.read_u8()? as u16;
+        let hi = self.read_u8()? as u16;
+        Ok(lo | (hi << 8))
+    }
+
+    pub fn read_s24(&mut self) -> Result<i32, AbcReadError> {
+        let b0 = self.read_u8()? as u32;
+        let b1 = self.read_u8()? as u32;
+        let b2 = self.read_u8()? as u32;
+        let v = b0 | (b1 << 8) | (b2 << 16);
+        // Sign-extend from 24 bits
+        Ok(((v << 8) as i32) >> 8)
+    }
+
+    /// Reads a fixed-width little-endian 32-bit unsigned integer, as the
+    /// `UI32` fields of SWF tags.
+    pub fn read_u32_le(&mut self) -> Result<u32, AbcReadError> {
+        let b = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Reads a variable-length encoded 32-bit unsigned integer.
+    pub fn read_u32(&mut self) -> Result<u32, AbcReadError> {
+        Ok(self.read_var_u32()?.0)
+    }
+
+    pub fn read_u30(&mut self) -> Result<u32, AbcReadError> {
+        Ok(self.read_u32()? & 0x3FFF_FFFF)
+    }
+
+    /// Reads a variable-length encoded 32-bit signed integer. Values
+    /// of one to four bytes are sign-extended from the highest of the
+    /// seven bits of their last byte; five-byte values carry all 32 bits.
+    pub fn read_s32(&mut self) -> Result<i32, AbcReadError> {
+        let (v, byte_count) = self.read_var_u32()?;
+        if byte_count < 5 {
+            let shift = 32 - 7 * byte_count;
+            Ok(((v << shift) as i32) >> shift)
+        } else {
+            Ok(v as i32)
+        }
+    }
+
+    /// Reads a variable-length encoded integer with the number of bytes
+    /// it took.
+    fn read_var_u32(&mut self) -> Result<(u32, u32), AbcReadError> {
+        let mut r: u32 = 0;
+        for i in 0..5 {
+            let b = self.read_u8()?;
+            r |= ((b & 0x7F) as u32) << (7 * i);
+            if b & 0x80 == 0 {
+                return Ok((r, i + 1));
+            }
+        }
+        Ok((r, 5))
+    }
+
+    pub fn read_d64(&mut self) -> Result<f64, AbcReadError> {
+        let mut b = [0u8; 8];
+        for i in 0..8 {
+            b[i] = self.read_u8()?;
+        }
+        Ok(f64::from_le_bytes(b))
+    }
+
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], AbcReadError> {
+        if length > self.remaining() {
+            return Err(AbcReadError::UnexpectedEof);
+        }
+        let r = &self.bytes[self.offset..(self.offset + length)];
+        self.offset += length;
+        Ok(r)
+    }
+
+    /// Number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.offset)
+    }
+
+    fn read_u30_list(&mut self, count: usize) -> Result<Vec<u32>, AbcReadError> {
+        // Counts are untrusted; each entry takes at least one byte.
+        let mut r = Vec::with_capacity(count.min(self.remaining()));
+        for _ in 0..count {
+            r.push(self.read_u30()?);
+        }
+        Ok(r)
+    }
+
+    pub fn read_abc_file(&mut self) -> Result<AbcFile, AbcReadError> {
+        let minor_version = self.read_u16()?;
+        let major_version = self.read_u16()?;
+        let constant_pool = self.read_constant_pool()?;
+
+        let mut methods = vec![];
+        for _ in 0..self.read_u30()? {
+            methods.push(self.read_method_info()?);
+        }
+
+        let mut metadata = vec![];
+        for _ in 0..self.read_u30()? {
+            let name = self.read_u30()?;
+            let item_count = self.read_u30()? as usize;
+            // Keys are encoded before values.
+            let keys = self.read_u30_list(item_count)?;
+            let values = self.read_u30_list(item_count)?;
+            metadata.push(AbcMetadataInfo { name, items: keys.into_iter().zip(values).collect() });
+        }
+
+        let class_count = self.read_u30()?;
+        let mut instances = vec![];
+        for _ in 0..class_count {
+            instances.push(self.read_instance_info()?);
+        }
+        let mut classes = vec![];
+        for _ in 0..class_count {
+            let cinit = self.read_u30()?;
+            let traits = self.read_traits()?;
+            classes.push(AbcClassInfo { cinit, traits });
+        }
+
+        let mut scripts = vec![];
+        for _ in 0..self.read_u30()? {
+            let init = self.read_u30()?;
+            let traits = self.read_traits()?;
+            scripts.push(AbcScriptInfo { init, traits });
+        }
+
+        let mut method_bodies = vec![];
+        for _ in 0..self.read_u30()? {
+            method_bodies.push(self.read_method_body()?);
+        }
+
+        Ok(AbcFile {
+            minor_version,
+            major_version,
+            constant_pool,
+            methods,
+            metadata,
+            instances,
+            classes,
+            scripts,
+            method_bodies,
+        })
+    }
+
+    fn read_constant_pool(&mut self) -> Result<AbcConstantPool, AbcReadError> {
+        let mut pool = AbcConstantPool::default();
+        for _ in 1..self.read_u30()?.max(1) {
+            pool.integers.push(self.read_s32()?);
+        }
+        for _ in 1..self.read_u30()?.max(1) {
+            pool.unsigned_integers.push(self.read_u32()?);
+        }
+        for _ in 1..self.read_u30()?.max(1) {
+            pool.doubles.push(self.read_d64()?);
+        }
+        for _ in 1..self.read_u30()?.max(1) {
+            let length = self.read_u30()? as usize;
+            let bytes = self.read_bytes(length)?;
+            pool.strings.push(String::from_utf8(bytes.to_vec()).map_err(|_| AbcReadError::InvalidUtf8)?);
+        }
+        for _ in 1..self.read_u30()?.max(1) {
+            let kind = self.read_u8()?;
+            let name = self.read_u30()?;
+            pool.namespaces.push(AbcNamespaceInfo { kind, name });
+        }
+        for _ in 1..self.read_u30()?.max(1) {
+            let count = self.read_u30()? as usize;
+            pool.ns_sets.push(self.read_u30_list(count)?);
+        }
+        for _ in 1..self.read_u30()?.max(1) {
+            pool.multinames.push(self.read_multiname()?);
+        }
+        Ok(pool)
+    }
+
+    fn read_multiname(&mut self) -> Result<AbcMultiname, AbcReadError> {
+        let kind = self.read_u8()?;
+        Ok(match kind {
+            AbcMultiname::QNAME | AbcMultiname::QNAME_A => {
+                let ns = self.read_u30()?;
+                let name = self.read_u30()?;
+                AbcMultiname::QName { attribute: kind == AbcMultiname::QNAME_A, ns, name }
+            },
+            AbcMultiname::RTQNAME | AbcMultiname::RTQNAME_A => {
+                let name = self.read_u30()?;
+                AbcMultiname::RTQName { attribute: kind == AbcMultiname::RTQNAME_A, name }
+            },
+            AbcMultiname::RTQNAME_L | AbcMultiname::RTQNAME_LA => {
+                AbcMultiname::RTQNameL { attribute: kind == AbcMultiname::RTQNAME_LA }
+            },
+            AbcMultiname::MULTINAME | AbcMultiname::MULTINAME_A => {
+                let name = self.read_u30()?;
+                let ns_set = self.read_u30()?;
+                AbcMultiname::Multiname { attribute: kind == AbcMultiname::MULTINAME_A, name, ns_set }
+            },
+            AbcMultiname::MULTINAME_L | AbcMultiname::MULTINAME_LA => {
+                let ns_set = self.read_u30()?;
+                AbcMultiname::MultinameL { attribute: kind == AbcMultiname::MULTINAME_LA, ns_set }
+            },
+            AbcMultiname::TYPE_NAME => {
+                let name = self.read_u30()?;
+                let count = self.read_u30()? as usize;
+                let params = self.read_u30_list(count)?;
+                AbcMultiname::TypeName { name, params }
+            },
+            _ => {
+                return Err(AbcReadError::InvalidMultinameKind(kind));
+            },
+        })
+    }
+
+    fn read_method_info(&mut self) -> Result<AbcMethodInfo, AbcReadError> {
+        let param_count = self.read_u30()? as usize;
+        let return_type = self.read_u30()?;
+        let param_types = self.read_u30_list(param_count)?;
+        let name = self.read_u30()?;
+        let flags = self.read_u8()?;
+        let mut options = vec![];
+        if flags & AbcMethodInfo::HAS_OPTIONAL != 0 {
+            for _ in 0..self.read_u30()? {
+                let value = self.read_u30()?;
+                let kind = self.read_u8()?;
+                options.push(AbcOptionDetail { value, kind });
+            }
+        }
+        let mut param_names = vec![];
+        if flags & AbcMethodInfo::HAS_PARAM_NAMES != 0 {
+            param_names = self.read_u30_list(param_count)?;
+        }
+        Ok(AbcMethodInfo { return_type, param_types, name, flags, options, param_names })
+    }
+
+    fn read_instance_info(&mut self) -> Result<AbcInstanceInfo, AbcReadError> {
+        let name = self.read_u30()?;
+        let super_name = self.read_u30()?;
+        let flags = self.read_u8()?;
+        let protected_ns = if flags & AbcInstanceInfo::PROTECTED_NS != 0 { Some(self.read_u30()?) } else { None };
+        let interface_count = self.read_u30()? as usize;
+        let interfaces = self.read_u30_list(interface_count)?;
+        let iinit = self.read_u30()?;
+        let traits = self.read_traits()?;
+        Ok(AbcInstanceInfo { name, super_name, flags, protected_ns, interfaces, iinit, traits })
+    }
+
+    fn read_traits(&mut self) -> Result<Vec<AbcTrait>, AbcReadError> {
+        let mut r = vec![];
+        for _ in 0..self.read_u30()? {
+            r.push(self.read_trait()?);
+        }
+        Ok(r)
+    }
+
+    fn read_trait(&mut self) -> Result<AbcTrait, AbcReadError> {
+        let name = self.read_u30()?;
+        let kind_byte = self.read_u8()?;
+        let kind = kind_byte & 0x0F;
+        let attributes = kind_byte >> 4;
+        let data = match kind {
+            0 | 6 => {
+                let slot_id = self.read_u30()?;
+                let type_name = self.read_u30()?;
+                let vindex = self.read_u30()?;
+                let value = if vindex != 0 { Some(AbcOptionDetail { value: vindex, kind: self.read_u8()? }) } else { None };
+                if kind == 0 {
+                    AbcTraitData::Slot { slot_id, type_name, value }
+                } else {
+                    AbcTraitData::Const { slot_id, type_name, value }
+                }
+            },
+            1 | 2 | 3 => {
+                let disp_id = self.read_u30()?;
+                let method = self.read_u30()?;
+                match kind {
+                    1 => AbcTraitData::Method { disp_id, method },
+                    2 => AbcTraitData::Getter { disp_id, method },
+                    _ => AbcTraitData::Setter { disp_id, method },
+                }
+            },
+            4 => {
+                let slot_id = self.read_u30()?;
+                let class = self.read_u30()?;
+                AbcTraitData::Class { slot_id, class }
+            },
+            5 => {
+                let slot_id = self.read_u30()?;
+                let function = self.read_u30()?;
+                AbcTraitData::Function { slot_id, function }
+            },
+            _ => {
+                return Err(AbcReadError::InvalidTraitKind(kind));
+            },
+        };
+        let mut metadata = vec![];
+        if attributes & AbcTrait::ATTR_METADATA != 0 {
+            let count = self.read_u30()? as usize;
+            metadata = self.read_u30_list(count)?;
+        }
+        Ok(AbcTrait { name, attributes, data, metadata })
+    }
+
+    fn read_method_body(&mut self) -> Result<AbcMethodBody, AbcReadError> {
+        let method = self.read_u30()?;
+        let max_stack = self.read_u30()?;
+        let local_count = self.read_u30()?;
+        let init_scope_depth = self.read_u30()?;
+        let max_scope_depth = self.read_u30()?;
+        let code_length = self.read_u30()? as usize;
+        let code = self.read_bytes(code_length)?.to_vec();
+        let mut exceptions = vec![];
+        for _ in 0..self.read_u30()? {
+            exceptions.push(AbcExceptionInfo {
+                from: self.read_u30()?,
+                to: self.read_u30()?,
+                target: self.read_u30()?,
+                exc_type: self.read_u30()?,
+                var_name: self.read_u30()?,
+            });
+        }
+        let traits = self.read_traits()?;
+        Ok(AbcMethodBody { method, max_stack, local_count, init_scope_depth, max_scope_depth, code, exceptions, traits })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,233 @@
+use crate::ns::*;
+use std::collections::BTreeSet;
+
+/// Generates ActionScript declarations for the public definitions of an
+/// ABC file, such as the library of a SWC or native extension, so that
+/// the verifier can resolve them as `native` definitions.
+///
+/// ```ignore
+/// let abc = AbcReader::read(&bytes)?;
+/// for (definition, source) in AbcStubGenerator::generate(&abc) {
+///     provider.insert(definition, &source);
+/// }
+/// ```
+///
+/// Constants without a value in the ABC file are declared as variables.
+pub struct AbcStubGenerator<'a> {
+    abc: &'a AbcFile,
+    package: String,
+    imports: BTreeSet<String>,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum StubMemberContext {
+    Class,
+    Static,
+    Interface,
+    Package,
+}
+
+impl<'a> AbcStubGenerator<'a> {
+    pub fn generate(abc: &AbcFile) -> Vec<(ProvidedDefinition, String)> {
+        let mut r = vec![];
+        for (i, instance) in abc.instances.iter().enumerate() {
+            let Some((package, name)) = Self::public_qname(abc, instance.name) else {
+                continue;
+            };
+            let mut generator = AbcStubGenerator { abc, package: package.clone(), imports: BTreeSet::new() };
+            let body = generator.class(instance, abc.classes.get(i), &name);
+            r.push((ProvidedDefinition::new(&package, &name), generator.finish(&body)));
+        }
+        for script in abc.scripts.iter() {
+            for t in script.traits.iter() {
+                if matches!(t.data, AbcTraitData::Class { .. }) {
+                    continue;
+                }
+                let Some((package, name)) = Self::public_qname(abc, t.name) else {
+                    continue;
+                };
+                let mut generator = AbcStubGenerator { abc, package: package.clone(), imports: BTreeSet::new() };
+                let Some(body) = generator.member(t, StubMemberContext::Package) else {
+                    continue;
+                };
+                r.push((ProvidedDefinition::new(&package, &name), generator.finish(&format!("    {body}\n"))));
+            }
+        }
+        r
+    }
+
+    /// Returns the package and local name of a QName in a package namespace.
+    pub(crate) fn public_qname(abc: &AbcFile, index: u32) -> Option<(String, String)> {
+        let pool = &abc.constant_pool;
+        let AbcMultiname::QName { ns, name, .. } = pool.multiname(index)? else {
+            return None;
+        };
+        let ns = pool.namespace(*ns)?;
+        if ns.kind != AbcNamespaceInfo::PACKAGE_NAMESPACE {
+            return None;
+        }
+        Some((pool.string(ns.name).unwrap_or("").to_owned(), pool.string(*name)?.to_owned()))
+    }
+
+    fn finish(&self, body: &str) -> String {
+        let mut r = if self.package.is_empty() { "package {\n".to_owned() } else { format!("package {} {{\n", self.package) };
+        for import in self.imports.iter() {
+            r.push_str(&format!("    import {import};\n"));
+        }
+        if !self.imports.is_empty() {
+            r.push('\n');
+        }
+        r.push_str(body);
+        r.push_str("}\n");
+        r
+    }
+
+    fn class(&mut self, instance: &AbcInstanceInfo, class: Option<&AbcClassInfo>, name: &str) -> String {
+        let is_interface = instance.flags & AbcInstanceInfo::INTERFACE != 0;
+        let mut r = String::from("    public ");
+        if is_interface {
+            r.push_str(&format!("interface {name}"));
+            let extends = instance.interfaces.iter().map(|i| self.type_name(*i)).collect::<Vec<_>>();
+            if !extends.is_empty() {
+                r.push_str(&format!(" extends {}", extends.join(", ")));
+            }
+        } else {
+            if instance.flags & AbcInstanceInfo::FINAL != 0 {
+                r.push_str("final ");
+            }
+            if instance.flags & AbcInstanceInfo::SEALED == 0 {
+                r.push_str("dynamic ");
+            }
+            r.push_str(&format!("class {name}"));
+            let super_name = self.type_name(instance.super_name);
+            if instance.super_name != 0 && super_name != "Object" {
+                r.push_str(&format!(" extends {super_name}"));
+            }
+            let implements = instance.interfaces.iter().map(|i| self.type_name(*i)).collect::<Vec<_>>();
+            if !implements.is_empty() {
+                r.push_str(&format!(" implements {}", implements.join(", ")));
+            }
+        }
+        r.push_str(" {\n");
+        if !is_interface {
+            if let Some(ctor) = self.abc.methods.get(instance.iinit as usize) {
+                r.push_str(&format!("        public native function {name}({});\n", self.parameters(ctor)));
+            }
+            for t in class.map(|c| c.traits.as_slice()).unwrap_or(&[]) {
+                if let Some(member) = self.member(t, StubMemberContext::Static) {
+                    r.push_str(&format!("        {member}\n"));
+                }
+            }
+        }
+        let context = if is_interface { StubMemberContext::Interface } else { StubMemberContext::Class };
+        for t in instance.traits.iter() {
+            if let Some(member) = self.member(t, context) {
+                r.push_str(&format!("        {member}\n"));
+            }
+        }
+        r.push_str("    }\n");
+        r
+    }
+
+    fn member(&mut self, t: &AbcTrait, context: StubMemberContext) -> Option<String> {
+        let pool = &self.abc.constant_pool;
+        let AbcMultiname::QName { ns, name, .. } = pool.multiname(t.name)? else {
+            return None;
+        };
+        let name = pool.string(*name)?.to_owned();
+        let ns_kind = pool.namespace(*ns)?.kind;
+        let mut attributes = String::new();
+        if context != StubMemberContext::Interface {
+            match ns_kind {
+                AbcNamespaceInfo::PACKAGE_NAMESPACE => attributes.push_str("public "),
+                AbcNamespaceInfo::PROTECTED_NAMESPACE | AbcNamespaceInfo::STATIC_PROTECTED_NS => attributes.push_str("protected "),
+                _ => return None,
+            }
+            if context == StubMemberContext::Static {
+                attributes.push_str("static ");
+            }
+            if t.attributes & AbcTrait::ATTR_OVERRIDE != 0 {
+                attributes.push_str("override ");
+            }
+            if t.attributes & AbcTrait::ATTR_FINAL != 0 && context == StubMemberContext::Class {
+                attributes.push_str("final ");
+            }
+        }
+        let native = if context == StubMemberContext::Interface { "" } else { "native " };
+        Some(match &t.data {
+            AbcTraitData::Slot { type_name, value, .. } | AbcTraitData::Const { type_name, value: value @ None, .. } => {
+                let value = value.as_ref().map(|v| format!(" = {}", self.value(v))).unwrap_or_default();
+                format!("{attributes}var {name}: {}{value};", self.type_name(*type_name))
+            },
+            AbcTraitData::Const { type_name, value: Some(value), .. } => {
+                format!("{attributes}const {name}: {} = {};", self.type_name(*type_name), self.value(value))
+            },
+            AbcTraitData::Method { method, .. } | AbcTraitData::Function { function: method, .. } => {
+                let method = self.abc.methods.get(*method as usize)?;
+                format!("{attributes}{native}function {name}({}): {};", self.parameters(method), self.type_name(method.return_type))
+            },
+            AbcTraitData::Getter { method, .. } => {
+                let method = self.abc.methods.get(*method as usize)?;
+                format!("{attributes}{native}function get {name}(): {};", self.type_name(method.return_type))
+            },
+            AbcTraitData::Setter { method, .. } => {
+                let method = self.abc.methods.get(*method as usize)?;
+                format!("{attributes}{native}function set {name}({}): void;", self.parameters(method))
+            },
+            AbcTraitData::Class { .. } => return None,
+        })
+    }
+
+    fn parameters(&mut self, method: &AbcMethodInfo) -> String {
+        let pool = &self.abc.constant_pool;
+        let required = method.param_types.len() - method.options.len().min(method.param_types.len());
+        let mut r = vec![];
+        for (i, t) in method.param_types.iter().enumerate() {
+            let name = method.param_names.get(i).and_then(|n| pool.string(*n)).map(|n| n.to_owned()).unwrap_or_else(|| format!("arg{i}"));
+            let mut param = format!("{name}: {}", self.type_name(*t));
+            if i >= required {
+                param.push_str(&format!(" = {}", self.value(&method.options[i - required])));
+            }
+            r.push(param);
+        }
+        if method.flags & AbcMethodInfo::NEED_REST != 0 {
+            r.push("...rest".into());
+        }
+        r.join(", ")
+    }
+
+    fn type_name(&mut self, index: u32) -> String {
+        let pool = &self.abc.constant_pool;
+        match pool.multiname(index) {
+            Some(AbcMultiname::QName { ns, name, .. }) => {
+                let local = pool.string(*name).unwrap_or("*").to_owned();
+                let package = pool.namespace(*ns).and_then(|ns| pool.string(ns.name)).unwrap_or("");
+                if !package.is_empty() && package != self.package && !package.starts_with("__AS3__") {
+                    self.imports.insert(format!("{package}.{local}"));
+                }
+                local
+            },
+            Some(AbcMultiname::TypeName { name, params }) => {
+                let (name, params) = (*name, params.clone());
+                let params = params.iter().map(|p| self.type_name(*p)).collect::<Vec<_>>();
+                format!("{}.<{}>", self.type_name(name), params.join(", "))
+            },
+            _ => "*".into(),
+        }
+    }
+
+    fn value(&self, option: &AbcOptionDetail) -> String {
+        let pool = &self.abc.constant_pool;
+        let index = option.value as usize;
+        match option.kind {
+            0x01 => pool.string(option.value).map(|s| format!("{s:?}")).unwrap_or("\"\"".into()),
+            0x03 => index.checked_sub(1).and_then(|i| pool.integers.get(i)).map(|v| v.to_string()).unwrap_or("0".into()),
+            0x04 => index.checked_sub(1).and_then(|i| pool.unsigned_integers.get(i)).map(|v| v.to_string()).unwrap_or("0".into()),
+            0x06 => index.checked_sub(1).and_then(|i| pool.doubles.get(i)).map(|v| if v.is_nan() { "NaN".into() } else { v.to_string() }).unwrap_or("NaN".into()),
+            0x0A => "false".into(),
+            0x0B => "true".into(),
+            0x0C => "null".into(),
+            _ => "undefined".into(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,279 @@
+use crate::ns::*;
+
+/// Serializes an `AbcFile` into bytes.
+///
+/// Reading and writing back an ABC file produces the same bytes,
+/// except for variable-length integers that were not encoded
+/// in their shortest form and empty constant pool lists
+/// encoded with a count of one.
+#[derive(Default)]
+pub struct AbcWriter {
+    bytes: Vec<u8>,
+}
+
+impl AbcWriter {
+    pub fn new() -> Self {
+        Self { bytes: vec![] }
+    }
+
+    pub fn write(abc: &AbcFile) -> Vec<u8> {
+        let mut writer = Self::new();
+        writer.write_abc_file(abc);
+        writer.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_s24(&mut self, value: i32) {
+        let v = value as u32;
+        self.write_u8((v & 0xFF) as u8);
+        self.write_u8(((v >> 8) & 0xFF) as u8);
+        self.write_u8(((v >> 16) & 0xFF) as u8);
+    }
+
+    pub fn write_u32(&mut self, mut value: u32) {
+        loop {
+            let b = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.write_u8(b);
+                break;
+            }
+            self.write_u8(b | 0x80);
+        }
+    }
+
+    pub fn write_u30(&mut self, value: u32) {
+        self.write_u32(value & 0x3FFF_FFFF);
+    }
+
+    pub fn write_s32(&mut self, value: i32) {
+        self.write_u32(value as u32);
+    }
+
+    pub fn write_d64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u30_list(&mut self, list: &[u32]) {
+        for v in list.iter() {
+            self.write_u30(*v);
+        }
+    }
+
+    fn write_pool_count(&mut self, count: usize) {
+        self.write_u30(if count == 0 { 0 } else { count as u32 + 1 });
+    }
+
+    pub fn write_abc_file(&mut self, abc: &AbcFile) {
+        self.write_u16(abc.minor_version);
+        self.write_u16(abc.major_version);
+        self.write_constant_pool(&abc.constant_pool);
+
+        self.write_u30(abc.methods.len() as u32);
+        for method in abc.methods.iter() {
+            self.write_method_info(method);
+        }
+
+        self.write_u30(abc.metadata.len() as u32);
+        for m in abc.metadata.iter() {
+            self.write_u30(m.name);
+            self.write_u30(m.items.len() as u32);
+            for (k, _) in m.items.iter() {
+                self.write_u30(*k);
+            }
+            for (_, v) in m.items.iter() {
+                self.write_u30(*v);
+            }
+        }
+
+        self.write_u30(abc.classes.len() as u32);
+        for instance in abc.instances.iter() {
+            self.write_instance_info(instance);
+        }
+        for class in abc.classes.iter() {
+            self.write_u30(class.cinit);
+            self.write_traits(&class.traits);
+        }
+
+        self.write_u30(abc.scripts.len() as u32);
+        for script in abc.scripts.iter() {
+            self.write_u30(script.init);
+            self.write_traits(&script.traits);
+        }
+
+        self.write_u30(abc.method_bodies.len() as u32);
+        for body in abc.method_bodies.iter() {
+            self.write_method_body(body);
+        }
+    }
+
+    fn write_constant_pool(&mut self, pool: &AbcConstantPool) {
+        self.write_pool_count(pool.integers.len());
+        for v in pool.integers.iter() {
+            self.write_s32(*v);
+        }
+        self.write_pool_count(pool.unsigned_integers.len());
+        for v in pool.unsigned_integers.iter() {
+            self.write_u32(*v);
+        }
+        self.write_pool_count(pool.doubles.len());
+        for v in pool.doubles.iter() {
+            self.write_d64(*v);
+        }
+        self.write_pool_count(pool.strings.len());
+        for s in pool.strings.iter() {
+            self.write_u30(s.len() as u32);
+            self.bytes.extend_from_slice(s.as_bytes());
+        }
+        self.write_pool_count(pool.namespaces.len());
+        for ns in pool.namespaces.iter() {
+            self.write_u8(ns.kind);
+            self.write_u30(ns.name);
+        }
+        self.write_pool_count(pool.ns_sets.len());
+        for ns_set in pool.ns_sets.iter() {
+            self.write_u30(ns_set.len() as u32);
+            self.write_u30_list(ns_set);
+        }
+        self.write_pool_count(pool.multinames.len());
+        for mn in pool.multinames.iter() {
+            self.write_multiname(mn);
+        }
+    }
+
+    fn write_multiname(&mut self, mn: &AbcMultiname) {
+        match mn {
+            AbcMultiname::QName { attribute, ns, name } => {
+                self.write_u8(if *attribute { AbcMultiname::QNAME_A } else { AbcMultiname::QNAME });
+                self.write_u30(*ns);
+                self.write_u30(*name);
+            },
+            AbcMultiname::RTQName { attribute, name } => {
+                self.write_u8(if *attribute { AbcMultiname::RTQNAME_A } else { AbcMultiname::RTQNAME });
+                self.write_u30(*name);
+            },
+            AbcMultiname::RTQNameL { attribute } => {
+                self.write_u8(if *attribute { AbcMultiname::RTQNAME_LA } else { AbcMultiname::RTQNAME_L });
+            },
+            AbcMultiname::Multiname { attribute, name, ns_set } => {
+                self.write_u8(if *attribute { AbcMultiname::MULTINAME_A } else { AbcMultiname::MULTINAME });
+                self.write_u30(*name);
+                self.write_u30(*ns_set);
+            },
+            AbcMultiname::MultinameL { attribute, ns_set } => {
+                self.write_u8(if *attribute { AbcMultiname::MULTINAME_LA } else { AbcMultiname::MULTINAME_L });
+                self.write_u30(*ns_set);
+            },
+            AbcMultiname::TypeName { name, params } => {
+                self.write_u8(AbcMultiname::TYPE_NAME);
+                self.write_u30(*name);
+                self.write_u30(params.len() as u32);
+                self.write_u30_list(params);
+            },
+        }
+    }
+
+    fn write_method_info(&mut self, method: &AbcMethodInfo) {
+        self.write_u30(method.param_types.len() as u32);
+        self.write_u30(method.return_type);
+        self.write_u30_list(&method.param_types);
+        self.write_u30(method.name);
+        self.write_u8(method.flags);
+        if method.flags & AbcMethodInfo::HAS_OPTIONAL != 0 {
+            self.write_u30(method.options.len() as u32);
+            for option in method.options.iter() {
+                self.write_u30(option.value);
+                self.write_u8(option.kind);
+            }
+        }
+        if method.flags & AbcMethodInfo::HAS_PARAM_NAMES != 0 {
+            self.write_u30_list(&method.param_names);
+        }
+    }
+
+    fn write_instance_info(&mut self, instance: &AbcInstanceInfo) {
+        self.write_u30(instance.name);
+        self.write_u30(instance.super_name);
+        self.write_u8(instance.flags);
+        if let Some(ns) = instance.protected_ns {
+            self.write_u30(ns);
+        }
+        self.write_u30(instance.interfaces.len() as u32);
+        self.write_u30_list(&instance.interfaces);
+        self.write_u30(instance.iinit);
+        self.write_traits(&instance.traits);
+    }
+
+    fn write_traits(&mut self, traits: &[AbcTrait]) {
+        self.write_u30(traits.len() as u32);
+        for t in traits.iter() {
+            self.write_trait(t);
+        }
+    }
+
+    fn write_trait(&mut self, t: &AbcTrait) {
+        self.write_u30(t.name);
+        self.write_u8((t.attributes << 4) | t.data.kind());
+        match &t.data {
+            AbcTraitData::Slot { slot_id, type_name, value } |
+            AbcTraitData::Const { slot_id, type_name, value } => {
+                self.write_u30(*slot_id);
+                self.write_u30(*type_name);
+                if let Some(value) = value {
+                    self.write_u30(value.value);
+                    self.write_u8(value.kind);
+                } else {
+                    self.write_u30(0);
+                }
+            },
+            AbcTraitData::Method { disp_id, method } |
+            AbcTraitData::Getter { disp_id, method } |
+            AbcTraitData::Setter { disp_id, method } => {
+                self.write_u30(*disp_id);
+                self.write_u30(*method);
+            },
+            AbcTraitData::Class { slot_id, class } => {
+                self.write_u30(*slot_id);
+                self.write_u30(*class);
+            },
+            AbcTraitData::Function { slot_id, function } => {
+                self.write_u30(*slot_id);
+                self.write_u30(*function);
+            },
+        }
+        if t.attributes & AbcTrait::ATTR_METADATA != 0 {
+            self.write_u30(t.metadata.len() as u32);
+            self.write_u30_list(&t.metadata);
+        }
+    }
+
+    fn write_method_body(&mut self, body: &AbcMethodBody) {
+        self.write_u30(body.method);
+        self.write_u30(body.max_stack);
+        self.write_u30(body.local_count);
+        self.write_u30(body.init_scope_depth);
+        self.write_u30(body.max_scope_depth);
+        self.write_u30(body.code.len() as u32);
+        self.bytes.extend_from_slice(&body.code);
+        self.write_u30(body.exceptions.len() as u32);
+        for e in body.exceptions.iter() {
+            self.write_u30(e.from);
+            self.write_u30(e.to);
+            self.write_u30(e.target);
+            self.write_u30(e.exc_type);
+            self.write_u30(e.var_name);
+        }
+        self.write_traits(&body.traits);
+    }
+}
\ No newline at end of file
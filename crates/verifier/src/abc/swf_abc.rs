@@ -0,0 +1,145 @@
+use crate::ns::*;
+use std::io::Read;
+
+/// An ABC block extracted from a SWF `DoABC` or `DoABC1` tag.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SwfAbcTag {
+    /// Flags of a `DoABC` tag; zero for `DoABC1`.
+    pub flags: u32,
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl SwfAbcTag {
+    pub const DO_ABC1: u16 = 72;
+    pub const DO_ABC: u16 = 82;
+
+    /// Flag indicating lazy initialization of the ABC block.
+    pub const LAZY_INITIALIZE: u32 = 1;
+
+    /// Inflates a zlib-compressed (`CWS`) SWF into an uncompressed (`FWS`)
+    /// SWF, returning uncompressed SWFs as they are.
+    pub fn decompress(swf: Vec<u8>) -> Result<Vec<u8>, AbcReadError> {
+        if swf.starts_with(b"FWS") {
+            return Ok(swf);
+        }
+        if swf.len() < 8 || !swf.starts_with(b"CWS") {
+            return Err(AbcReadError::UnsupportedSwf);
+        }
+        let mut r = b"FWS".to_vec();
+        r.extend_from_slice(&swf[3..8]);
+        flate2::read::ZlibDecoder::new(&swf[8..]).read_to_end(&mut r).map_err(|_| AbcReadError::UnsupportedSwf)?;
+        Ok(r)
+    }
+
+    /// Extracts every ABC block from an uncompressed (`FWS`) SWF, as
+    /// given by `decompress()`.
+    pub fn extract(swf: &[u8]) -> Result<Vec<SwfAbcTag>, AbcReadError> {
+        let mut reader = SwfTagReader::new(swf)?;
+        let mut r = vec![];
+        while let Some(tag) = reader.next_tag()? {
+            if tag.code == 0 {
+                break;
+            }
+            if let Some(tag) = Self::from_tag(tag.code, tag.body)? {
+                r.push(tag);
+            }
+        }
+        Ok(r)
+    }
+
+    /// Reads the body of a `DoABC` or `DoABC1` tag, returning `None`
+    /// for other tags.
+    pub fn from_tag(code: u16, body: &[u8]) -> Result<Option<SwfAbcTag>, AbcReadError> {
+        match code {
+            Self::DO_ABC => {
+                let mut reader = AbcReader::new(body);
+                let flags = reader.read_u32_le()?;
+                let mut name = vec![];
+                loop {
+                    let ch = reader.read_u8()?;
+                    if ch == 0 {
+                        break;
+                    }
+                    name.push(ch);
+                }
+                let name = String::from_utf8(name).map_err(|_| AbcReadError::InvalidUtf8)?;
+                let rest = body.len() - reader.offset();
+                let bytes = reader.read_bytes(rest)?.to_vec();
+                Ok(Some(SwfAbcTag { flags, name, bytes }))
+            },
+            Self::DO_ABC1 => Ok(Some(SwfAbcTag { flags: 0, name: String::new(), bytes: body.to_vec() })),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn read_abc(&self) -> Result<AbcFile, AbcReadError> {
+        AbcReader::read(&self.bytes)
+    }
+
+    /// Reads the ABC files of a library path entry: every ABC block of a
+    /// `.swf` file, such as the `library.swf` of a SWC, or a single ABC
+    /// file otherwise.
+    pub fn read_library(path: &str, bytes: Vec<u8>) -> Result<Vec<AbcFile>, AbcReadError> {
+        if path.ends_with(".swf") {
+            let swf = Self::decompress(bytes)?;
+            Self::extract(&swf)?.iter().map(|tag| tag.read_abc()).collect()
+        } else {
+            AbcReader::read(&bytes).map(|abc| vec![abc])
+        }
+    }
+}
+
+/// A tag read by `SwfTagReader`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SwfTag<'a> {
+    pub code: u16,
+    /// Offset of the tag header within the SWF.
+    pub offset: usize,
+    pub body: &'a [u8],
+}
+
+/// Reads the header and then the tags of an uncompressed (`FWS`) SWF.
+pub struct SwfTagReader<'a> {
+    reader: AbcReader<'a>,
+    pub version: u8,
+    pub frame_count: u16,
+}
+
+impl<'a> SwfTagReader<'a> {
+    /// Offset of the frame size rectangle, after the signature, version
+    /// and file length.
+    const HEADER_LENGTH: usize = 8;
+
+    pub fn new(swf: &'a [u8]) -> Result<Self, AbcReadError> {
+        if swf.len() < Self::HEADER_LENGTH || &swf[0..3] != b"FWS" {
+            return Err(AbcReadError::UnsupportedSwf);
+        }
+        let mut reader = AbcReader::new(&swf[Self::HEADER_LENGTH..]);
+
+        // Frame size rectangle, followed by frame rate and frame count.
+        let nbits = (reader.read_u8()? >> 3) as usize;
+        let rect_len = (5 + nbits * 4 + 7) / 8;
+        reader.read_bytes(rect_len - 1)?;
+        reader.read_u16()?;
+        let frame_count = reader.read_u16()?;
+        Ok(Self { reader, version: swf[3], frame_count })
+    }
+
+    /// Reads the next tag, or returns `None` at the end of the SWF.
+    pub fn next_tag(&mut self) -> Result<Option<SwfTag<'a>>, AbcReadError> {
+        if self.reader.is_at_end() {
+            return Ok(None);
+        }
+        let offset = Self::HEADER_LENGTH + self.reader.offset();
+        let code_and_length = self.reader.read_u16()?;
+        let code = code_and_length >> 6;
+        let mut length = (code_and_length & 0x3F) as usize;
+        // Long tag headers follow with a fixed-width length.
+        if length == 0x3F {
+            length = self.reader.read_u32_le()? as usize;
+        }
+        let body = self.reader.read_bytes(length)?;
+        Ok(Some(SwfTag { code, offset, body }))
+    }
+}
\ No newline at end of file
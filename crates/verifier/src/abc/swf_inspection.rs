@@ -0,0 +1,218 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// A tag of a SWF, as listed by `SwfInspection`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SwfTagInfo {
+    pub code: u16,
+    /// Offset of the tag header within the uncompressed SWF.
+    pub offset: usize,
+    pub length: usize,
+    /// Character ID of definition tags.
+    pub character_id: Option<u16>,
+}
+
+impl SwfTagInfo {
+    pub fn name(&self) -> String {
+        SwfInspection::tag_name(self.code).map(|n| n.to_owned()).unwrap_or(format!("Tag{}", self.code))
+    }
+}
+
+/// ABC block of a SWF along with the definitions its scripts export.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SwfAbcBlock {
+    pub name: String,
+    /// Qualified names of the script traits, such as `com.example::Main`.
+    pub definitions: Vec<String>,
+}
+
+/// Tags, symbols and ABC scripts of an existing SWF, for debugging
+/// generated output and auditing third-party libraries.
+///
+/// ```ignore
+/// let inspection = SwfInspection::inspect(&std::fs::read("library.swf")?)?;
+/// print!("{}", inspection.format_human());
+/// ```
+///
+/// The SWF must be uncompressed (`FWS`); callers decompress `CWS` SWFs
+/// beforehand.
+#[derive(Clone, Default, Debug)]
+pub struct SwfInspection {
+    pub version: u8,
+    pub frame_count: u16,
+    pub tags: Vec<SwfTagInfo>,
+    /// Classes bound to characters by `SymbolClass` tags.
+    pub symbol_classes: BTreeMap<u16, String>,
+    /// Names given to characters by `ExportAssets` tags.
+    pub exports: BTreeMap<u16, String>,
+    pub abc_blocks: Vec<SwfAbcBlock>,
+}
+
+impl SwfInspection {
+    const SYMBOL_CLASS: u16 = 76;
+    const EXPORT_ASSETS: u16 = 56;
+
+    pub fn inspect(swf: &[u8]) -> Result<Self, AbcReadError> {
+        let mut reader = SwfTagReader::new(swf)?;
+        let mut r = Self { version: reader.version, frame_count: reader.frame_count, ..Self::default() };
+        while let Some(SwfTag { code, offset, body }) = reader.next_tag()? {
+            let length = body.len();
+            let character_id = (Self::is_definition(code) && body.len() >= 2).then(|| u16::from_le_bytes([body[0], body[1]]));
+            r.tags.push(SwfTagInfo { code, offset, length, character_id });
+            match code {
+                0 => break,
+                Self::SYMBOL_CLASS => r.symbol_classes.extend(Self::read_symbols(body)?),
+                Self::EXPORT_ASSETS => r.exports.extend(Self::read_symbols(body)?),
+                _ => {},
+            }
+            if let Some(tag) = SwfAbcTag::from_tag(code, body)? {
+                let abc = tag.read_abc()?;
+                let definitions = abc.scripts.iter()
+                    .flat_map(|script| script.traits.iter())
+                    .map(|t| AbcDisassembler::multiname(&abc.constant_pool, t.name))
+                    .collect();
+                r.abc_blocks.push(SwfAbcBlock { name: tag.name, definitions });
+            }
+        }
+        Ok(r)
+    }
+
+    /// Definitions exported by more than one ABC block, with the names
+    /// of those blocks.
+    pub fn duplicate_definitions(&self) -> BTreeMap<String, Vec<String>> {
+        let mut blocks = BTreeMap::<String, Vec<String>>::new();
+        for block in self.abc_blocks.iter() {
+            for definition in block.definitions.iter() {
+                blocks.entry(definition.clone()).or_default().push(block.name.clone());
+            }
+        }
+        blocks.retain(|_, names| names.len() > 1);
+        blocks
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = format!("SWF version {}, {} frames, {} tags\n", self.version, self.frame_count, self.tags.len());
+        r.push_str("\nTags:\n");
+        for tag in self.tags.iter() {
+            match tag.character_id {
+                Some(id) => r.push_str(&format!("  {:>8}  {} (character {id}, {} bytes)\n", tag.offset, tag.name(), tag.length)),
+                None => r.push_str(&format!("  {:>8}  {} ({} bytes)\n", tag.offset, tag.name(), tag.length)),
+            }
+        }
+        if !self.symbol_classes.is_empty() {
+            r.push_str("\nSymbol classes:\n");
+            for (id, name) in self.symbol_classes.iter() {
+                r.push_str(&format!("  {id:>5}  {name}\n"));
+            }
+        }
+        if !self.exports.is_empty() {
+            r.push_str("\nExported assets:\n");
+            for (id, name) in self.exports.iter() {
+                r.push_str(&format!("  {id:>5}  {name}\n"));
+            }
+        }
+        for block in self.abc_blocks.iter() {
+            let name = if block.name.is_empty() { "<unnamed>" } else { block.name.as_str() };
+            r.push_str(&format!("\nABC {name}:\n"));
+            for definition in block.definitions.iter() {
+                r.push_str(&format!("  {definition}\n"));
+            }
+        }
+        let duplicates = self.duplicate_definitions();
+        if !duplicates.is_empty() {
+            r.push_str("\nDefinitions in more than one ABC block:\n");
+            for (definition, blocks) in duplicates.iter() {
+                r.push_str(&format!("  {definition} ({})\n", blocks.join(", ")));
+            }
+        }
+        r
+    }
+
+    /// Reads the `(character ID, name)` pairs of `SymbolClass` and
+    /// `ExportAssets` tags.
+    fn read_symbols(body: &[u8]) -> Result<Vec<(u16, String)>, AbcReadError> {
+        let mut reader = AbcReader::new(body);
+        let count = reader.read_u16()?;
+        let mut r = vec![];
+        for _ in 0..count {
+            let id = reader.read_u16()?;
+            let mut name = vec![];
+            loop {
+                let ch = reader.read_u8()?;
+                if ch == 0 {
+                    break;
+                }
+                name.push(ch);
+            }
+            r.push((id, String::from_utf8(name).map_err(|_| AbcReadError::InvalidUtf8)?));
+        }
+        Ok(r)
+    }
+
+    fn is_definition(code: u16) -> bool {
+        matches!(code, 2 | 6 | 7 | 10 | 11 | 14 | 20 | 21 | 22 | 32 | 33 | 34 | 35 | 36 | 37 | 39 | 46 | 48 | 60 | 75 | 83 | 84 | 87 | 90 | 91)
+    }
+
+    pub fn tag_name(code: u16) -> Option<&'static str> {
+        Some(match code {
+            0 => "End",
+            1 => "ShowFrame",
+            2 => "DefineShape",
+            4 => "PlaceObject",
+            5 => "RemoveObject",
+            6 => "DefineBits",
+            7 => "DefineButton",
+            8 => "JPEGTables",
+            9 => "SetBackgroundColor",
+            10 => "DefineFont",
+            11 => "DefineText",
+            12 => "DoAction",
+            13 => "DefineFontInfo",
+            14 => "DefineSound",
+            15 => "StartSound",
+            20 => "DefineBitsLossless",
+            21 => "DefineBitsJPEG2",
+            22 => "DefineShape2",
+            24 => "Protect",
+            26 => "PlaceObject2",
+            28 => "RemoveObject2",
+            32 => "DefineShape3",
+            33 => "DefineText2",
+            34 => "DefineButton2",
+            35 => "DefineBitsJPEG3",
+            36 => "DefineBitsLossless2",
+            37 => "DefineEditText",
+            39 => "DefineSprite",
+            41 => "ProductInfo",
+            43 => "FrameLabel",
+            46 => "DefineMorphShape",
+            48 => "DefineFont2",
+            56 => "ExportAssets",
+            57 => "ImportAssets",
+            58 => "EnableDebugger",
+            60 => "DefineVideoStream",
+            63 => "DebugID",
+            64 => "EnableDebugger2",
+            65 => "ScriptLimits",
+            69 => "FileAttributes",
+            70 => "PlaceObject3",
+            71 => "ImportAssets2",
+            72 => "DoABC1",
+            73 => "DefineFontAlignZones",
+            75 => "DefineFont3",
+            76 => "SymbolClass",
+            77 => "Metadata",
+            78 => "DefineScalingGrid",
+            82 => "DoABC",
+            83 => "DefineShape4",
+            84 => "DefineMorphShape2",
+            86 => "DefineSceneAndFrameLabelData",
+            87 => "DefineBinaryData",
+            88 => "DefineFontName",
+            90 => "DefineBitsJPEG4",
+            91 => "DefineFont4",
+            93 => "EnableTelemetry",
+            _ => return None,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+mod ast_builder;
+pub use ast_builder::*;
\ No newline at end of file
@@ -0,0 +1,117 @@
+use crate::ns::*;
+use std::cell::Cell;
+
+/// Failure to build synthetic code, with the parser diagnostics.
+#[derive(Clone, Debug)]
+pub struct AstBuildError {
+    pub messages: Vec<String>,
+}
+
+impl std::fmt::Display for AstBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.messages.join("\n"))
+    }
+}
+
+/// Builds directives and expressions for synthetic code, such as that of
+/// MXML lowering, meta-data driven generators and test utilities, from
+/// source text rather than from parser-internal structures.
+///
+/// ```ignore
+/// let builder = AstBuilder::nested_in(&cu, "Bindable");
+/// let name = AstBuilder::identifier("width")?;
+/// let exp = builder.expression(&format!("dispatchEvent(new PropertyChangeEvent({}))", AstBuilder::string_literal(&name)))?;
+/// let directives = builder.directives("var _width: Number = 0;")?;
+/// ```
+///
+/// Each fragment is parsed into its own synthetic compilation unit, named
+/// after the builder, so that the locations of the built nodes point into
+/// the fragment. A builder nested in a compilation unit attaches the
+/// units of successful fragments to it, so that diagnostics reported
+/// over the built nodes are listed with those of that unit.
+pub struct AstBuilder {
+    name: String,
+    parent: Option<Rc<CompilationUnit>>,
+    counter: Cell<usize>,
+}
+
+impl AstBuilder {
+    /// Words that cannot name a definition.
+    const RESERVED_WORDS: [&'static str; 44] = [
+        "as", "break", "case", "catch", "class", "const", "continue", "default",
+        "delete", "do", "else", "extends", "false", "finally", "for", "function",
+        "if", "implements", "import", "in", "instanceof", "interface", "internal", "is",
+        "native", "new", "null", "package", "private", "protected", "public", "return",
+        "super", "switch", "this", "throw", "true", "try", "typeof", "use",
+        "var", "void", "while", "with",
+    ];
+
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_owned(), parent: None, counter: Cell::new(0) }
+    }
+
+    pub fn nested_in(parent: &Rc<CompilationUnit>, name: &str) -> Self {
+        Self { name: name.to_owned(), parent: Some(parent.clone()), counter: Cell::new(0) }
+    }
+
+    pub fn expression(&self, source: &str) -> Result<Rc<Expression>, AstBuildError> {
+        let cu = self.compilation_unit(source);
+        let exp = ParserFacade(&cu, ParserOptions::default()).parse_expression();
+        self.finish(&cu)?;
+        Ok(exp)
+    }
+
+    /// Builds directives outside of a package, such as statements and
+    /// member definitions.
+    pub fn directives(&self, source: &str) -> Result<Vec<Rc<Directive>>, AstBuildError> {
+        Ok(self.program(source)?.directives.clone())
+    }
+
+    pub fn program(&self, source: &str) -> Result<Rc<Program>, AstBuildError> {
+        let cu = self.compilation_unit(source);
+        let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+        self.finish(&cu)?;
+        Ok(program)
+    }
+
+    /// Returns a name if it is a valid identifier for synthetic code,
+    /// which excludes reserved words.
+    pub fn identifier(name: &str) -> Result<String, AstBuildError> {
+        let mut chars = name.chars();
+        let valid = chars.next().map(|ch| ch.is_alphabetic() || ch == '_' || ch == '$').unwrap_or(false)
+            && chars.all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+            && !Self::RESERVED_WORDS.contains(&name);
+        if valid {
+            Ok(name.to_owned())
+        } else {
+            Err(AstBuildError { messages: vec![format!("'{name}' is not a valid identifier.")] })
+        }
+    }
+
+    /// Quotes and escapes a string as a string literal.
+    pub fn string_literal(value: &str) -> String {
+        json_string(value)
+    }
+
+    fn compilation_unit(&self, source: &str) -> Rc<CompilationUnit> {
+        let index = self.counter.get();
+        self.counter.set(index + 1);
+        let cu = CompilationUnit::new(Some(format!("<{}#{index}>", self.name)), source.to_owned());
+        if let Some(parent) = self.parent.as_ref() {
+            cu.set_compiler_options(parent.compiler_options());
+        }
+        cu
+    }
+
+    fn finish(&self, cu: &Rc<CompilationUnit>) -> Result<(), AstBuildError> {
+        if cu.invalidated() {
+            cu.sort_diagnostics();
+            let messages = cu.nested_diagnostics().iter().map(|d| WhackDiagnostic(d).format_english()).collect();
+            return Err(AstBuildError { messages });
+        }
+        if let Some(parent) = self.parent.as_ref() {
+            parent.add_nested_compilation_unit(cu.clone());
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod proto_schema;
+pub use proto_schema::*;
+
+mod proto_bindings;
+pub use proto_bindings::*;
\ No newline at end of file
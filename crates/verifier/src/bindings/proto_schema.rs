@@ -0,0 +1,264 @@
+/// A parsed `.proto` file, restricted to top-level messages and enums
+/// with scalar, enum, message and `repeated` fields.
+#[derive(Clone, Debug)]
+pub struct ProtoSchema {
+    pub file_path: String,
+    /// Dot-delimited package name, empty if not declared.
+    pub package: String,
+    pub messages: Vec<ProtoMessage>,
+    pub enums: Vec<ProtoEnum>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProtoMessage {
+    pub name: String,
+    pub fields: Vec<ProtoField>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProtoField {
+    pub name: String,
+    /// Scalar type name, or the possibly qualified name of a message or enum.
+    pub type_name: String,
+    pub number: u32,
+    pub repeated: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProtoEnum {
+    pub name: String,
+    pub values: Vec<(String, i32)>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProtoSchemaError {
+    pub file_path: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum ProtoToken {
+    Identifier(String),
+    Number(String),
+    String(String),
+    Punctuator(char),
+    Eof,
+}
+
+impl ProtoSchema {
+    /// Scalar types with a binding.
+    pub const SCALAR_TYPES: [&'static str; 13] = [
+        "double", "float", "int32", "sint32", "uint32", "int64", "uint64",
+        "fixed32", "sfixed32", "fixed64", "bool", "string", "bytes",
+    ];
+
+    pub fn parse(file_path: &str, text: &str) -> Result<ProtoSchema, ProtoSchemaError> {
+        let mut parser = ProtoParser {
+            file_path: file_path.to_owned(),
+            tokens: ProtoParser::tokenize(text),
+            index: 0,
+        };
+        let mut schema = ProtoSchema { file_path: file_path.to_owned(), package: String::new(), messages: vec![], enums: vec![] };
+        loop {
+            match parser.next() {
+                ProtoToken::Eof => break,
+                ProtoToken::Identifier(keyword) => match keyword.as_str() {
+                    "syntax" | "option" | "import" => parser.skip_statement(),
+                    "package" => {
+                        schema.package = parser.expect_identifier()?;
+                        parser.expect(';')?;
+                    },
+                    "message" => schema.messages.push(parser.parse_message()?),
+                    "enum" => schema.enums.push(parser.parse_enum()?),
+                    _ => return Err(parser.error(&format!("Unsupported declaration '{keyword}'"))),
+                },
+                ProtoToken::Punctuator(';') => {},
+                _ => return Err(parser.error("Expected a declaration")),
+            }
+        }
+        Ok(schema)
+    }
+}
+
+struct ProtoParser {
+    file_path: String,
+    /// Tokens with their line numbers.
+    tokens: Vec<(ProtoToken, usize)>,
+    index: usize,
+}
+
+impl ProtoParser {
+    fn tokenize(text: &str) -> Vec<(ProtoToken, usize)> {
+        let chars = text.chars().collect::<Vec<_>>();
+        let mut r = vec![];
+        let (mut i, mut line) = (0, 1);
+        while i < chars.len() {
+            let ch = chars[i];
+            if ch == '\n' {
+                line += 1;
+                i += 1;
+            } else if ch.is_whitespace() {
+                i += 1;
+            } else if ch == '/' && chars.get(i + 1) == Some(&'/') {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            } else if ch == '/' && chars.get(i + 1) == Some(&'*') {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    line += (chars[i] == '\n') as usize;
+                    i += 1;
+                }
+                i += 2;
+            } else if ch.is_alphabetic() || ch == '_' || ch == '.' {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                    i += 1;
+                }
+                r.push((ProtoToken::Identifier(chars[start..i].iter().collect::<String>().trim_start_matches('.').to_owned()), line));
+            } else if ch.is_ascii_digit() || ch == '-' {
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+                    i += 1;
+                }
+                r.push((ProtoToken::Number(chars[start..i].iter().collect()), line));
+            } else if ch == '"' || ch == '\'' {
+                let start = i + 1;
+                i += 1;
+                while i < chars.len() && chars[i] != ch {
+                    i += 1;
+                }
+                r.push((ProtoToken::String(chars[start..i.min(chars.len())].iter().collect()), line));
+                i += 1;
+            } else {
+                r.push((ProtoToken::Punctuator(ch), line));
+                i += 1;
+            }
+        }
+        r.push((ProtoToken::Eof, line));
+        r
+    }
+
+    fn peek(&self) -> &ProtoToken {
+        &self.tokens[self.index].0
+    }
+
+    fn next(&mut self) -> ProtoToken {
+        let token = self.tokens[self.index].0.clone();
+        if token != ProtoToken::Eof {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn error(&self, message: &str) -> ProtoSchemaError {
+        ProtoSchemaError {
+            file_path: self.file_path.clone(),
+            line: self.tokens[self.index.saturating_sub(1)].1,
+            message: message.to_owned(),
+        }
+    }
+
+    fn expect(&mut self, punctuator: char) -> Result<(), ProtoSchemaError> {
+        if self.next() != ProtoToken::Punctuator(punctuator) {
+            return Err(self.error(&format!("Expected '{punctuator}'")));
+        }
+        Ok(())
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, ProtoSchemaError> {
+        match self.next() {
+            ProtoToken::Identifier(name) => Ok(name),
+            _ => Err(self.error("Expected an identifier")),
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<i64, ProtoSchemaError> {
+        let ProtoToken::Number(n) = self.next() else {
+            return Err(self.error("Expected a number"));
+        };
+        let (negative, digits) = n.strip_prefix('-').map(|d| (true, d)).unwrap_or((false, n.as_str()));
+        let value = if let Some(hex) = digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+            i64::from_str_radix(hex, 16)
+        } else {
+            digits.parse::<i64>()
+        }.map_err(|_| self.error(&format!("Invalid number '{n}'")))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Skips past the next `;`.
+    fn skip_statement(&mut self) {
+        loop {
+            match self.next() {
+                ProtoToken::Punctuator(';') | ProtoToken::Eof => break,
+                _ => {},
+            }
+        }
+    }
+
+    fn parse_message(&mut self) -> Result<ProtoMessage, ProtoSchemaError> {
+        let name = self.expect_identifier()?;
+        self.expect('{')?;
+        let mut fields = vec![];
+        loop {
+            match self.next() {
+                ProtoToken::Punctuator('}') => break,
+                ProtoToken::Punctuator(';') => {},
+                ProtoToken::Identifier(word) => match word.as_str() {
+                    "option" | "reserved" | "extensions" => self.skip_statement(),
+                    "message" | "enum" => return Err(self.error("Nested definitions are not supported")),
+                    "oneof" | "map" | "group" | "extend" => return Err(self.error(&format!("'{word}' is not supported"))),
+                    _ => {
+                        let (repeated, type_name) = match word.as_str() {
+                            "repeated" => (true, self.expect_identifier()?),
+                            "optional" | "required" => (false, self.expect_identifier()?),
+                            _ => (false, word),
+                        };
+                        let field_name = self.expect_identifier()?;
+                        self.expect('=')?;
+                        let number = self.expect_number()?;
+                        if !(1..=536_870_911).contains(&number) {
+                            return Err(self.error(&format!("Field number {number} is out of range")));
+                        }
+                        // Field options, such as [packed = true]
+                        if *self.peek() == ProtoToken::Punctuator('[') {
+                            while !matches!(self.next(), ProtoToken::Punctuator(']') | ProtoToken::Eof) {}
+                        }
+                        self.expect(';')?;
+                        fields.push(ProtoField { name: field_name, type_name, number: number as u32, repeated });
+                    },
+                },
+                ProtoToken::Eof => return Err(self.error("Unterminated message")),
+                _ => return Err(self.error("Expected a field")),
+            }
+        }
+        Ok(ProtoMessage { name, fields })
+    }
+
+    fn parse_enum(&mut self) -> Result<ProtoEnum, ProtoSchemaError> {
+        let name = self.expect_identifier()?;
+        self.expect('{')?;
+        let mut values = vec![];
+        loop {
+            match self.next() {
+                ProtoToken::Punctuator('}') => break,
+                ProtoToken::Punctuator(';') => {},
+                ProtoToken::Identifier(word) if word == "option" || word == "reserved" => self.skip_statement(),
+                ProtoToken::Identifier(value_name) => {
+                    self.expect('=')?;
+                    let value = self.expect_number()?;
+                    if *self.peek() == ProtoToken::Punctuator('[') {
+                        while !matches!(self.next(), ProtoToken::Punctuator(']') | ProtoToken::Eof) {}
+                    }
+                    self.expect(';')?;
+                    values.push((value_name, value as i32));
+                },
+                ProtoToken::Eof => return Err(self.error("Unterminated enum")),
+                _ => return Err(self.error("Expected an enum value")),
+            }
+        }
+        Ok(ProtoEnum { name, values })
+    }
+}
\ No newline at end of file
@@ -1,2 +1,53 @@
 mod codegen_class_info;
-pub use codegen_class_info::*;
\ No newline at end of file
+pub use codegen_class_info::*;
+
+mod codegen_coverage;
+pub use codegen_coverage::*;
+
+mod codegen_debug_info;
+pub use codegen_debug_info::*;
+
+mod codegen_destructuring;
+pub use codegen_destructuring::*;
+
+mod codegen_json_type;
+pub use codegen_json_type::*;
+
+mod codegen_trace_calls;
+pub use codegen_trace_calls::*;
+
+mod codegen_module_split;
+pub use codegen_module_split::*;
+
+mod codegen_frame_script;
+pub use codegen_frame_script::*;
+
+mod codegen_renaming;
+pub use codegen_renaming::*;
+
+mod codegen_metadata_retention;
+pub use codegen_metadata_retention::*;
+
+mod codegen_tail_calls;
+pub use codegen_tail_calls::*;
+
+mod codegen_switch;
+pub use codegen_switch::*;
+
+mod codegen_inlining;
+pub use codegen_inlining::*;
+
+mod codegen_font_embed;
+pub use codegen_font_embed::*;
+
+mod codegen_bitmap_assets;
+pub use codegen_bitmap_assets::*;
+
+mod swf_tag;
+pub use swf_tag::*;
+
+mod codegen_method_limits;
+pub use codegen_method_limits::*;
+
+mod codegen_listing;
+pub use codegen_listing::*;
\ No newline at end of file
@@ -0,0 +1,280 @@
+use crate::ns::*;
+use std::io::Write;
+
+/// SWF tag storing an embedded bitmap.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BitmapTagKind {
+    /// A JPEG file, or a PNG or GIF file as is.
+    DefineBitsJpeg2,
+    /// A JPEG file with a zlib compressed alpha channel.
+    DefineBitsJpeg3,
+    /// Decoded pixels, compressed with zlib.
+    DefineBitsLossless2,
+}
+
+impl BitmapTagKind {
+    pub fn code(&self) -> u16 {
+        match self {
+            Self::DefineBitsJpeg2 => 21,
+            Self::DefineBitsJpeg3 => 35,
+            Self::DefineBitsLossless2 => 36,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        SwfInspection::tag_name(self.code()).unwrap()
+    }
+}
+
+/// A bitmap embedded by an `[Embed]` meta-data, as encoded by
+/// `CodegenBitmapAssets`.
+#[derive(Clone, Debug)]
+pub struct BitmapAsset {
+    /// Embedding definition, as `com.example::Assets.Logo`.
+    pub name: String,
+    pub file_path: String,
+    pub info: BitmapInfo,
+    pub original_size: usize,
+    pub tag: BitmapTagKind,
+    /// Size of the tag, including its record header and character ID,
+    /// or 0 if the character of an earlier asset is reused.
+    pub emitted_size: usize,
+    /// Index of an earlier asset of identical tag whose character
+    /// is reused.
+    pub shared_with: Option<usize>,
+    /// JPEG quality the bitmap was recompressed at.
+    pub recompressed_quality: Option<u8>,
+    /// Body of the tag following the character ID.
+    body: Rc<Vec<u8>>,
+}
+
+impl BitmapAsset {
+    /// Returns the tag defining the bitmap as the given character,
+    /// including its record header.
+    pub fn tag(&self, character_id: u16) -> Vec<u8> {
+        let mut body = character_id.to_le_bytes().to_vec();
+        body.extend_from_slice(&self.body);
+        SwfTag::record(self.tag.code(), &body)
+    }
+}
+
+/// Encodes the tags of embedded bitmaps and reports their sizes.
+///
+/// PNG and GIF bitmaps are decoded into `DefineBitsLossless2` tags and
+/// JPEG bitmaps are stored as is in `DefineBitsJPEG2` tags. With
+/// `compression`, lossless bitmaps are recompressed into JPEG, their
+/// alpha channel if any going into a `DefineBitsJPEG3` tag; with a
+/// `quality`, JPEG bitmaps are recompressed if that makes them smaller.
+/// With optimization, PNG and GIF bitmaps not recompressed are stored as
+/// is in `DefineBitsJPEG2` tags, which Flash Player 8 and later decode.
+/// Bitmaps of identical tags share a character.
+///
+/// ```ignore
+/// let assets = CodegenBitmapAssets::collect(&programs, &embedded_files, &compiler_options.source_path, true);
+/// std::fs::write(CodegenBitmapAssets::REPORT_FILE_NAME, assets.format_report())?;
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct CodegenBitmapAssets {
+    pub assets: Vec<BitmapAsset>,
+    optimize: bool,
+    /// Asset index per hash of the tag code and body.
+    hashes: HashMap<String, usize>,
+}
+
+impl CodegenBitmapAssets {
+    pub const REPORT_FILE_NAME: &'static str = "bitmap-assets.txt";
+
+    /// Quality of lossless bitmaps recompressed without a `quality`.
+    pub const DEFAULT_QUALITY: u8 = 80;
+
+    /// Size of the long record header and character ID of a tag.
+    const TAG_OVERHEAD: usize = 8;
+
+    pub fn new(optimize: bool) -> Self {
+        Self { optimize, ..Self::default() }
+    }
+
+    /// Encodes the bitmaps embedded by the given programs, as read into
+    /// `files`. Unreadable bitmaps are skipped.
+    pub fn collect(programs: &[Rc<Program>], files: &EmbeddedFiles, source_path: &[String], optimize: bool) -> Self {
+        let mut collector = BitmapEmbedCollector { package: String::new(), classes: vec![], embeds: vec![] };
+        for program in programs.iter() {
+            collector.visit_program(program);
+        }
+        let mut r = Self::new(optimize);
+        for (name, options, location) in collector.embeds {
+            let file_path = location.compilation_unit().file_path().unwrap_or_default();
+            if let Some((path, data)) = files.resolve(&options.source, &file_path, source_path) {
+                r.add(&name, &path, &options, &data);
+            }
+        }
+        r
+    }
+
+    /// Encodes a bitmap, returning `false` if it is not a PNG, JPEG
+    /// or GIF file or cannot be decoded.
+    pub fn add(&mut self, name: &str, file_path: &str, options: &BitmapEmbedOptions, data: &[u8]) -> bool {
+        let Some(info) = BitmapInfo::read(data) else {
+            return false;
+        };
+        let Some((tag, body, recompressed_quality)) = self.encode(&info, options, data) else {
+            return false;
+        };
+        let mut hashed = tag.code().to_le_bytes().to_vec();
+        hashed.extend_from_slice(&body);
+        let hash = ContentHash::sha256(&hashed);
+        let shared_with = self.hashes.get(&hash).cloned();
+        if shared_with.is_none() {
+            self.hashes.insert(hash, self.assets.len());
+        }
+        let body = match shared_with {
+            Some(first) => self.assets[first].body.clone(),
+            None => Rc::new(body),
+        };
+        self.assets.push(BitmapAsset {
+            name: name.to_owned(),
+            file_path: file_path.to_owned(),
+            info,
+            original_size: data.len(),
+            tag,
+            emitted_size: if shared_with.is_some() { 0 } else { body.len() + Self::TAG_OVERHEAD },
+            shared_with,
+            recompressed_quality,
+            body,
+        });
+        true
+    }
+
+    /// Returns the tag kind and body following the character ID of a
+    /// bitmap, with the quality it was recompressed at.
+    fn encode(&self, info: &BitmapInfo, options: &BitmapEmbedOptions, data: &[u8]) -> Option<(BitmapTagKind, Vec<u8>, Option<u8>)> {
+        if info.format == BitmapFormat::Jpeg {
+            if let Some(quality) = options.quality {
+                let image = image::load_from_memory(data).ok()?;
+                let jpeg = Self::encode_jpeg(&image, quality)?;
+                if jpeg.len() < data.len() {
+                    return Some((BitmapTagKind::DefineBitsJpeg2, jpeg, Some(quality)));
+                }
+            }
+            return Some((BitmapTagKind::DefineBitsJpeg2, data.to_vec(), None));
+        }
+        if options.compression {
+            let quality = options.quality.unwrap_or(Self::DEFAULT_QUALITY);
+            let image = image::load_from_memory(data).ok()?;
+            let jpeg = Self::encode_jpeg(&image, quality)?;
+            if !info.has_alpha {
+                return Some((BitmapTagKind::DefineBitsJpeg2, jpeg, Some(quality)));
+            }
+            let alpha: Vec<u8> = image.to_rgba8().pixels().map(|p| p.0[3]).collect();
+            let mut body = (jpeg.len() as u32).to_le_bytes().to_vec();
+            body.extend_from_slice(&jpeg);
+            body.extend_from_slice(&Self::zlib(&alpha));
+            return Some((BitmapTagKind::DefineBitsJpeg3, body, Some(quality)));
+        }
+        if self.optimize {
+            return Some((BitmapTagKind::DefineBitsJpeg2, data.to_vec(), None));
+        }
+        let image = image::load_from_memory(data).ok()?.to_rgba8();
+        let (width, height) = (u16::try_from(image.width()).ok()?, u16::try_from(image.height()).ok()?);
+        // Premultiplied ARGB pixels
+        let mut pixels = Vec::with_capacity(image.len());
+        for pixel in image.pixels() {
+            let [r, g, b, a] = pixel.0;
+            let premultiply = |c: u8| ((c as u16 * a as u16 + 127) / 255) as u8;
+            pixels.extend_from_slice(&[a, premultiply(r), premultiply(g), premultiply(b)]);
+        }
+        // Format 5 is 32-bit ARGB
+        let mut body = vec![5];
+        body.extend_from_slice(&width.to_le_bytes());
+        body.extend_from_slice(&height.to_le_bytes());
+        body.extend_from_slice(&Self::zlib(&pixels));
+        Some((BitmapTagKind::DefineBitsLossless2, body, None))
+    }
+
+    fn encode_jpeg(image: &image::DynamicImage, quality: u8) -> Option<Vec<u8>> {
+        let mut r = vec![];
+        let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut r, quality.max(1));
+        encoder.encode_image(&image.to_rgb8()).ok()?;
+        Some(r)
+    }
+
+    fn zlib(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::ZlibEncoder::new(vec![], flate2::Compression::best());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    pub fn format_report(&self) -> String {
+        let original: usize = self.assets.iter().map(|a| a.original_size).sum();
+        let emitted: usize = self.assets.iter().map(|a| a.emitted_size).sum();
+        let mut r = format!("{} embedded bitmaps, {original} bytes in sources, {emitted} bytes in output\n", self.assets.len());
+        for asset in self.assets.iter() {
+            let alpha = if asset.info.has_alpha { " with alpha" } else { "" };
+            r.push_str(&format!("\n{}\n  {} ({} {}x{}{alpha})\n", asset.name, asset.file_path, asset.info.format.name(), asset.info.width, asset.info.height));
+            if let Some(first) = asset.shared_with {
+                r.push_str(&format!("  {} bytes, shared with {}\n", asset.original_size, self.assets[first].name));
+                continue;
+            }
+            r.push_str(&format!("  {} bytes -> {} bytes as {}\n", asset.original_size, asset.emitted_size, asset.tag.name()));
+            if let Some(quality) = asset.recompressed_quality {
+                r.push_str(&format!("  recompressed as JPEG at quality {quality}\n"));
+            }
+        }
+        r
+    }
+}
+
+/// Collects the bitmap `[Embed]` meta-data of classes and variables
+/// with the names of their definitions.
+struct BitmapEmbedCollector {
+    package: String,
+    classes: Vec<String>,
+    embeds: Vec<(String, BitmapEmbedOptions, Location)>,
+}
+
+impl BitmapEmbedCollector {
+    fn qualified(&self, name: &str) -> String {
+        let mut names = self.classes.clone();
+        names.push(name.to_owned());
+        let local = names.join(".");
+        if self.package.is_empty() { local } else { format!("{}::{local}", self.package) }
+    }
+
+    fn collect(&mut self, attributes: &[Attribute], name: &str) {
+        for metadata in Attribute::find_metadata(attributes).iter() {
+            if let Some(options) = BitmapEmbedOptions::from_metadata(metadata) {
+                self.embeds.push((self.qualified(name), options, metadata.location.clone()));
+            }
+        }
+    }
+}
+
+impl Visitor for BitmapEmbedCollector {
+    fn visit_program(&mut self, program: &Rc<Program>) {
+        for pckgdefn in program.packages.iter() {
+            self.package = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+            self.visit_block(&pckgdefn.block);
+        }
+        self.package = String::new();
+        for drtv in program.directives.iter() {
+            self.visit_directive(drtv);
+        }
+    }
+
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                self.collect(&defn.attributes, &defn.name.0);
+                self.classes.push(defn.name.0.clone());
+                walk_directive(self, drtv);
+                self.classes.pop();
+            },
+            Directive::VariableDefinition(defn) => {
+                if let Some((name, _)) = defn.bindings.first().and_then(|binding| binding.destructuring.destructuring.to_identifier_name()) {
+                    self.collect(&defn.attributes, &name);
+                }
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+}
\ No newline at end of file
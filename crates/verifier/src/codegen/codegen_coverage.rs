@@ -0,0 +1,133 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CoverageProbeKind {
+    /// Entry of a statement.
+    Statement,
+    /// Entry of an arm of a branch point, such as the consequent (`arm` 0)
+    /// or the alternative (`arm` 1) of an `if` statement, or a `switch` case.
+    Branch {
+        branch: u32,
+        arm: u32,
+    },
+}
+
+/// A counter and the source span it is mapped to.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CoverageProbe {
+    pub id: u32,
+    pub kind: CoverageProbeKind,
+    pub file_path: String,
+    /// One-based line and zero-based column of the span start.
+    pub first_line: usize,
+    pub first_column: usize,
+    pub last_line: usize,
+    pub last_column: usize,
+}
+
+/// Instruments method body code with coverage counters when the
+/// `coverage` compiler option is set.
+///
+/// Each probe emits a call to the top-level function named
+/// [`CodegenCoverage::HIT_FUNCTION`] with the probe ID, which the test
+/// runtime implements by incrementing the respective counter. After code
+/// generation, [`CodegenCoverage::mapping_json`] maps probe IDs back to
+/// source spans for producing line and branch coverage reports.
+///
+/// ```ignore
+/// let mut coverage = CodegenCoverage::new(&compiler_options);
+/// coverage.emit_statement(&mut code, hit_function, &stmt.location(), &mut int_constant);
+/// std::fs::write("coverage.json", coverage.mapping_json())?;
+/// ```
+pub struct CodegenCoverage {
+    enabled: bool,
+    probes: Vec<CoverageProbe>,
+    branch_count: u32,
+}
+
+impl CodegenCoverage {
+    pub const FINDPROPSTRICT: u8 = 0x5D;
+    pub const PUSHSHORT: u8 = 0x25;
+    pub const PUSHINT: u8 = 0x2D;
+    pub const CALLPROPVOID: u8 = 0x4F;
+
+    pub const HIT_FUNCTION: &'static str = "__whack_coverage_hit";
+
+    pub fn new(compiler_options: &CompilerOptions) -> Self {
+        Self {
+            enabled: compiler_options.coverage,
+            probes: vec![],
+            branch_count: 0,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn probes(&self) -> &[CoverageProbe] {
+        &self.probes
+    }
+
+    /// Allocates a branch point, whose arms are then instrumented
+    /// through [`CodegenCoverage::emit_branch_arm`].
+    pub fn begin_branch(&mut self) -> u32 {
+        let branch = self.branch_count;
+        self.branch_count += 1;
+        branch
+    }
+
+    /// Emits a statement probe. `hit_function` is the multiname index of
+    /// [`CodegenCoverage::HIT_FUNCTION`] and `int_constant` returns the
+    /// constant pool index of an integer, used for IDs that do not fit
+    /// in `pushshort`.
+    pub fn emit_statement(&mut self, code: &mut AbcWriter, hit_function: u32, location: &Location, int_constant: &mut impl FnMut(i32) -> u32) {
+        self.emit_probe(code, hit_function, location, CoverageProbeKind::Statement, int_constant);
+    }
+
+    /// Emits a probe at the entry of a branch arm.
+    pub fn emit_branch_arm(&mut self, code: &mut AbcWriter, hit_function: u32, location: &Location, branch: u32, arm: u32, int_constant: &mut impl FnMut(i32) -> u32) {
+        self.emit_probe(code, hit_function, location, CoverageProbeKind::Branch { branch, arm }, int_constant);
+    }
+
+    fn emit_probe(&mut self, code: &mut AbcWriter, hit_function: u32, location: &Location, kind: CoverageProbeKind, int_constant: &mut impl FnMut(i32) -> u32) {
+        if !self.enabled {
+            return;
+        }
+        let id = self.probes.len() as u32;
+        self.probes.push(CoverageProbe {
+            id,
+            kind,
+            file_path: location.compilation_unit().file_path().unwrap_or_default(),
+            first_line: location.first_line_number(),
+            first_column: location.first_column(),
+            last_line: location.last_line_number(),
+            last_column: location.last_column(),
+        });
+        code.write_u8(Self::FINDPROPSTRICT);
+        code.write_u30(hit_function);
+        if id < 0x8000 {
+            code.write_u8(Self::PUSHSHORT);
+            code.write_u30(id);
+        } else {
+            code.write_u8(Self::PUSHINT);
+            code.write_u30(int_constant(id as i32));
+        }
+        code.write_u8(Self::CALLPROPVOID);
+        code.write_u30(hit_function);
+        code.write_u30(1);
+    }
+
+    /// Serializes the probes as JSON, in ID order.
+    pub fn mapping_json(&self) -> String {
+        let probes = self.probes.iter().map(|p| {
+            let kind = match p.kind {
+                CoverageProbeKind::Statement => "\"kind\":\"statement\"".to_owned(),
+                CoverageProbeKind::Branch { branch, arm } => format!("\"kind\":\"branch\",\"branch\":{branch},\"arm\":{arm}"),
+            };
+            format!("{{\"id\":{},{},\"file\":{},\"start\":[{},{}],\"end\":[{},{}]}}",
+                p.id, kind, json_string(&p.file_path), p.first_line, p.first_column, p.last_line, p.last_column)
+        }).collect::<Vec<_>>().join(",");
+        format!("{{\"version\":1,\"probes\":[{}]}}", probes)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::ns::*;
+
+/// Emits AVM2 debug instructions (`debugfile`, `debugline` and `debug`)
+/// into method body code when the `debug` compiler option is set.
+///
+/// Redundant `debugfile` and `debugline` instructions are skipped
+/// by tracking the last emitted file and line.
+pub struct CodegenDebugInfo {
+    enabled: bool,
+    last_file: Option<u32>,
+    last_line: Option<u32>,
+}
+
+impl CodegenDebugInfo {
+    pub const DEBUG: u8 = 0xEF;
+    pub const DEBUGLINE: u8 = 0xF0;
+    pub const DEBUGFILE: u8 = 0xF1;
+
+    /// `debug_type` operand of the `debug` instruction for local registers.
+    pub const DI_LOCAL: u8 = 1;
+
+    pub fn new(compiler_options: &CompilerOptions) -> Self {
+        Self {
+            enabled: compiler_options.debug,
+            last_file: None,
+            last_line: None,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Resets tracking at the start of a new method body.
+    pub fn begin_method_body(&mut self) {
+        self.last_file = None;
+        self.last_line = None;
+    }
+
+    /// Emits `debugfile`, given the constant pool index of the
+    /// string produced by [`CodegenDebugInfo::file_name`].
+    pub fn emit_file(&mut self, code: &mut AbcWriter, file_name: u32) {
+        if !self.enabled || self.last_file == Some(file_name) {
+            return;
+        }
+        code.write_u8(Self::DEBUGFILE);
+        code.write_u30(file_name);
+        self.last_file = Some(file_name);
+        self.last_line = None;
+    }
+
+    /// Emits `debugline` for a one-based line number.
+    pub fn emit_line(&mut self, code: &mut AbcWriter, line: u32) {
+        if !self.enabled || self.last_line == Some(line) {
+            return;
+        }
+        code.write_u8(Self::DEBUGLINE);
+        code.write_u30(line);
+        self.last_line = Some(line);
+    }
+
+    /// Emits `debugline` for the first line of a source location.
+    pub fn emit_location(&mut self, code: &mut AbcWriter, location: &Location) {
+        self.emit_line(code, location.first_line_number() as u32);
+    }
+
+    /// Names a local register, given the constant pool index of its name.
+    /// Register numbers above 255 cannot be described and are skipped.
+    pub fn emit_local(&mut self, code: &mut AbcWriter, name: u32, register: u32) {
+        if !self.enabled || register > 0xFF {
+            return;
+        }
+        code.write_u8(Self::DEBUG);
+        code.write_u8(Self::DI_LOCAL);
+        code.write_u30(name);
+        code.write_u8(register as u8);
+        code.write_u30(0);
+    }
+
+    /// Formats the `debugfile` name of a source file in the
+    /// `source_root;package_path;FileName.as` form understood by debuggers.
+    pub fn file_name(file_path: &str, package_name: &str) -> String {
+        let path = std::path::Path::new(file_path);
+        let file = path.file_name().map(|f| f.to_string_lossy().into_owned()).unwrap_or_default();
+        let package_path = package_name.replace('.', std::path::MAIN_SEPARATOR_STR);
+        let mut root = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        if !package_name.is_empty() {
+            for _ in package_name.split('.') {
+                root.pop();
+            }
+        }
+        format!("{};{};{}", root.to_string_lossy(), package_path, file)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+use crate::ns::*;
+
+/// An access step from the destructured value to a binding.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DestructuringAccess {
+    /// `value[index]` of an array pattern.
+    Index(usize),
+    /// `value.name` of an object pattern.
+    Property(String),
+    /// `value[key]` of an object pattern with a computed or string field name.
+    Computed(Rc<Expression>),
+    /// Asserts the value is non-null (`pattern!`).
+    NonNull,
+}
+
+#[derive(Clone, Debug)]
+pub enum DestructuringBinding {
+    /// An identifier pattern, mapped to its variable in the node mapping.
+    Identifier(Rc<Expression>),
+    /// A shorthand object field (`{x}`), whose field destructuring
+    /// resolution in the node mapping holds the variable.
+    Shorthand(Rc<InitializerField>),
+}
+
+/// A binding of a destructuring pattern, together with the accesses
+/// that read its value from the destructured value.
+#[derive(Clone, Debug)]
+pub struct DestructuringTarget {
+    pub binding: DestructuringBinding,
+    pub path: Vec<DestructuringAccess>,
+}
+
+/// Lowers destructuring patterns into member accesses.
+///
+/// ```ignore
+/// // var {x, y: [a, b]} = point;
+/// for target in CodegenDestructuring::lower(&pattern) {
+///     // emit `dup` followed by `getproperty` for each access,
+///     // then set the binding.
+/// }
+/// ```
+pub struct CodegenDestructuring;
+
+impl CodegenDestructuring {
+    pub fn lower(pattern: &Rc<Expression>) -> Vec<DestructuringTarget> {
+        let mut r = vec![];
+        Self::lower_pattern(pattern, &mut vec![], &mut r);
+        r
+    }
+
+    fn lower_pattern(pattern: &Rc<Expression>, path: &mut Vec<DestructuringAccess>, output: &mut Vec<DestructuringTarget>) {
+        match pattern.as_ref() {
+            Expression::QualifiedIdentifier(_) => {
+                output.push(DestructuringTarget { binding: DestructuringBinding::Identifier(pattern.clone()), path: path.clone() });
+            },
+            Expression::Unary(e) if e.operator == Operator::NonNull => {
+                path.push(DestructuringAccess::NonNull);
+                Self::lower_pattern(&e.expression, path, output);
+                path.pop();
+            },
+            Expression::ArrayLiteral(literal) => {
+                for (i, elem) in literal.elements.iter().enumerate() {
+                    if let Element::Expression(subpat) = elem {
+                        path.push(DestructuringAccess::Index(i));
+                        Self::lower_pattern(subpat, path, output);
+                        path.pop();
+                    }
+                }
+            },
+            Expression::ObjectInitializer(literal) => {
+                for field in literal.fields.iter() {
+                    let InitializerField::Field { name, non_null, value } = field.as_ref() else {
+                        continue;
+                    };
+                    let access = match &name.0 {
+                        FieldName::Identifier(id) => {
+                            let Some(id) = id.to_identifier_name_or_asterisk() else {
+                                continue;
+                            };
+                            DestructuringAccess::Property(id.0)
+                        },
+                        FieldName::Brackets(exp) | FieldName::StringLiteral(exp) | FieldName::NumericLiteral(exp) => {
+                            DestructuringAccess::Computed(exp.clone())
+                        },
+                    };
+                    path.push(access);
+                    if *non_null {
+                        path.push(DestructuringAccess::NonNull);
+                    }
+                    if let Some(subpat) = value {
+                        Self::lower_pattern(subpat, path, output);
+                    } else {
+                        output.push(DestructuringTarget { binding: DestructuringBinding::Shorthand(field.clone()), path: path.clone() });
+                    }
+                    if *non_null {
+                        path.pop();
+                    }
+                    path.pop();
+                }
+            },
+            _ => {},
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,317 @@
+use crate::ns::*;
+
+/// A font embed verified by `FontEmbedSubverifier`, with the tag
+/// defining it.
+#[derive(Clone)]
+pub struct EmbeddedFont {
+    pub location: Location,
+    /// Name the font is registered under.
+    pub font_name: String,
+    pub file_path: String,
+    /// Character defined by `tag`. Characters of fonts are numbered
+    /// from 1 in order of embedding.
+    pub character_id: u16,
+    /// `DefineFont4` or `DefineFont3` tag, including its record header.
+    pub tag: Vec<u8>,
+}
+
+/// Emits the SWF tags of `[Embed]` fonts.
+///
+/// CFF fonts embedded for the Flash Text Engine (`embedAsCFF`, the
+/// default) are emitted as `DefineFont4` tags carrying the font file.
+/// TrueType fonts are emitted as `DefineFont3` tags, their quadratic
+/// outlines mapping directly to SWF shapes, which classic text fields
+/// render. Transcoding TrueType outlines into CFF for `DefineFont4` is
+/// not supported yet.
+pub struct CodegenFontEmbed;
+
+impl CodegenFontEmbed {
+    pub const DEFINE_FONT3: u16 = 75;
+    pub const DEFINE_FONT4: u16 = 91;
+
+    const FLAG_HAS_LAYOUT: u8 = 0x80;
+    const FLAG_WIDE_OFFSETS: u8 = 8;
+    const FLAG_WIDE_CODES: u8 = 4;
+    const FLAG_HAS_FONT_DATA: u8 = 4;
+    const FLAG_ITALIC: u8 = 2;
+    const FLAG_BOLD: u8 = 1;
+
+    /// Size of the EM square of `DefineFont3` glyphs, in twips.
+    const EM_SQUARE: f64 = 1024.0 * 20.0;
+
+    /// Returns the `DefineFont4` tag, including its record header, of a
+    /// font read from `data`, or `None` if the font needs transcoding.
+    pub fn define_font4(character_id: u16, options: &FontEmbedOptions, font: &FontFile, data: &[u8]) -> Option<Vec<u8>> {
+        if !font.cff || options.embed_as_cff() != Some(true) {
+            return None;
+        }
+        let mut flags = Self::FLAG_HAS_FONT_DATA;
+        if options.is_italic() {
+            flags |= Self::FLAG_ITALIC;
+        }
+        if options.is_bold() {
+            flags |= Self::FLAG_BOLD;
+        }
+        let name = options.font_name.clone().or(font.family_name.clone()).unwrap_or_default();
+        let mut body = vec![];
+        body.extend_from_slice(&character_id.to_le_bytes());
+        body.push(flags);
+        body.extend_from_slice(name.as_bytes());
+        body.push(0);
+        body.extend_from_slice(data);
+        Some(SwfTag::record(Self::DEFINE_FONT4, &body))
+    }
+
+    /// Returns the `DefineFont3` tag, including its record header, of a
+    /// TrueType font read from `data`, with the glyphs of the given code
+    /// points that the font maps. Code points outside the BMP, which
+    /// `DefineFont3` cannot map, are left out.
+    pub fn define_font3(character_id: u16, options: &FontEmbedOptions, font: &FontFile, data: &[u8], ranges: &[(u32, u32)]) -> Result<Vec<u8>, FontFileError> {
+        let outlines = TrueTypeOutlines::parse(data)?;
+        let scale = Self::EM_SQUARE / outlines.units_per_em as f64;
+        let to_twips = |value: f64| (value * scale).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+
+        let mut code_points = vec![];
+        for &(first, last) in ranges.iter() {
+            for code_point in first..=last.min(0xFFFF) {
+                if font.covers(code_point) && code_points.len() < 0xFFFF {
+                    code_points.push(code_point as u16);
+                }
+            }
+        }
+        code_points.sort();
+        code_points.dedup();
+
+        let mut shapes = vec![];
+        let mut advances = vec![];
+        let mut bounds = vec![];
+        for code_point in code_points.iter() {
+            let glyph = outlines.glyph_index(*code_point as u32)?;
+            let (shape, glyph_bounds) = Self::glyph_shape(&outlines.contours(glyph)?, scale);
+            shapes.push(shape);
+            bounds.push(glyph_bounds);
+            advances.push(to_twips(outlines.advance(glyph)? as f64));
+        }
+
+        let shapes_length: usize = shapes.iter().map(|s| s.len()).sum();
+        let wide_offsets = (shapes.len() + 1) * 2 + shapes_length > 0xFFFF;
+        let offset_size = if wide_offsets { 4 } else { 2 };
+        let mut flags = Self::FLAG_HAS_LAYOUT | Self::FLAG_WIDE_CODES;
+        if wide_offsets {
+            flags |= Self::FLAG_WIDE_OFFSETS;
+        }
+        if options.is_italic() {
+            flags |= Self::FLAG_ITALIC;
+        }
+        if options.is_bold() {
+            flags |= Self::FLAG_BOLD;
+        }
+        let name = options.font_name.clone().or(font.family_name.clone()).unwrap_or_default();
+        let name: Vec<u8> = name.bytes().take(0xFF).collect();
+
+        let mut body = vec![];
+        body.extend_from_slice(&character_id.to_le_bytes());
+        body.push(flags);
+        // Language code
+        body.push(0);
+        body.push(name.len() as u8);
+        body.extend_from_slice(&name);
+        body.extend_from_slice(&(shapes.len() as u16).to_le_bytes());
+        // Offsets of the shapes and code table from the offset table
+        let mut offset = (shapes.len() + 1) * offset_size;
+        for shape in shapes.iter().map(Some).chain([None]) {
+            if wide_offsets {
+                body.extend_from_slice(&(offset as u32).to_le_bytes());
+            } else {
+                body.extend_from_slice(&(offset as u16).to_le_bytes());
+            }
+            offset += shape.map(|s| s.len()).unwrap_or(0);
+        }
+        for shape in shapes.iter() {
+            body.extend_from_slice(shape);
+        }
+        for code_point in code_points.iter() {
+            body.extend_from_slice(&code_point.to_le_bytes());
+        }
+
+        body.extend_from_slice(&to_twips(outlines.ascender as f64).to_le_bytes());
+        body.extend_from_slice(&to_twips(-(outlines.descender as f64)).to_le_bytes());
+        body.extend_from_slice(&to_twips(outlines.line_gap as f64).to_le_bytes());
+        for advance in advances.iter() {
+            body.extend_from_slice(&advance.to_le_bytes());
+        }
+        for glyph_bounds in bounds.iter() {
+            let mut writer = SwfBitWriter::default();
+            writer.write_rect(glyph_bounds);
+            body.extend_from_slice(&writer.finish());
+        }
+        // Kerning count
+        body.extend_from_slice(&0u16.to_le_bytes());
+        Ok(SwfTag::record(Self::DEFINE_FONT3, &body))
+    }
+
+    /// Builds the SWF shape of a glyph, with the y axis pointing down,
+    /// returning it with its bounds as `[x_min, x_max, y_min, y_max]`.
+    fn glyph_shape(contours: &[Vec<GlyphPoint>], scale: f64) -> (Vec<u8>, [i32; 4]) {
+        let limit = i16::MAX as f64;
+        let to_twips = |p: &GlyphPoint| ((p.x * scale).round().clamp(-limit, limit) as i32, (-p.y * scale).round().clamp(-limit, limit) as i32);
+
+        let mut writer = SwfBitWriter::default();
+        // One fill style and no line style
+        writer.write(1, 4);
+        writer.write(0, 4);
+        let mut position;
+        let mut bounds: Option<[i32; 4]> = None;
+        let mut extend = |(x, y): (i32, i32)| {
+            let b = bounds.get_or_insert([x, x, y, y]);
+            *b = [b[0].min(x), b[1].max(x), b[2].min(y), b[3].max(y)];
+        };
+        let mut first_contour = true;
+        for contour in contours.iter().filter(|c| !c.is_empty()) {
+            let points: Vec<((i32, i32), bool)> = contour.iter().map(|p| (to_twips(p), p.on_curve)).collect();
+            // Start on an on-curve point, or on the implied point between
+            // the first two control points
+            let start_index = points.iter().position(|(_, on_curve)| *on_curve);
+            let start = match start_index {
+                Some(i) => points[i].0,
+                None => Self::midpoint(points[0].0, points[points.len() - 1].0),
+            };
+            let rotated: Vec<((i32, i32), bool)> = match start_index {
+                Some(i) => points[i + 1..].iter().chain(points[..i].iter()).cloned().collect(),
+                None => points.clone(),
+            };
+
+            writer.write_move_to(start, first_contour);
+            first_contour = false;
+            position = start;
+            extend(start);
+            let mut control: Option<(i32, i32)> = None;
+            for (point, on_curve) in rotated.into_iter().chain([(start, true)]) {
+                match (control, on_curve) {
+                    (None, true) => {
+                        writer.write_straight_edge(position, point);
+                        position = point;
+                    },
+                    (None, false) => control = Some(point),
+                    (Some(c), true) => {
+                        writer.write_curved_edge(position, c, point);
+                        position = point;
+                        control = None;
+                    },
+                    (Some(c), false) => {
+                        let anchor = Self::midpoint(c, point);
+                        writer.write_curved_edge(position, c, anchor);
+                        position = anchor;
+                        control = Some(point);
+                    },
+                }
+                extend(point);
+            }
+        }
+        // End of shape
+        writer.write(0, 6);
+        (writer.finish(), bounds.unwrap_or_default())
+    }
+
+    fn midpoint(a: (i32, i32), b: (i32, i32)) -> (i32, i32) {
+        ((a.0 + b.0) / 2, (a.1 + b.1) / 2)
+    }
+}
+
+/// Writes the bit fields of SWF shapes, most significant bit first.
+#[derive(Default)]
+struct SwfBitWriter {
+    bytes: Vec<u8>,
+    /// Bits used in the last byte, or 0 if it is full.
+    bit: u8,
+}
+
+impl SwfBitWriter {
+    /// Most bits of the deltas of an edge record.
+    const MAX_EDGE_BITS: u32 = 17;
+
+    fn write(&mut self, value: u32, bits: u32) {
+        for i in (0..bits).rev() {
+            if self.bit == 0 {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 != 0 {
+                *self.bytes.last_mut().unwrap() |= 0x80 >> self.bit;
+            }
+            self.bit = (self.bit + 1) % 8;
+        }
+    }
+
+    fn write_signed(&mut self, value: i32, bits: u32) {
+        self.write(value as u32 & ((1u64 << bits) - 1) as u32, bits);
+    }
+
+    /// Number of bits holding each of the given values as
+    /// signed fields.
+    fn signed_bits(values: &[i32]) -> u32 {
+        values.iter().map(|v| 33 - if *v < 0 { v.leading_ones() } else { v.leading_zeros() }).max().unwrap_or(1)
+    }
+
+    /// Writes a `RECT` of the given `[x_min, x_max, y_min, y_max]`.
+    fn write_rect(&mut self, rect: &[i32; 4]) {
+        let bits = Self::signed_bits(rect);
+        self.write(bits, 5);
+        for value in rect.iter() {
+            self.write_signed(*value, bits);
+        }
+    }
+
+    /// Writes a style change record moving to a point, selecting the
+    /// fill style of glyphs for the first contour.
+    fn write_move_to(&mut self, (x, y): (i32, i32), select_fill: bool) {
+        self.write(0, 4);
+        self.write(select_fill as u32, 1);
+        self.write(1, 1);
+        let bits = Self::signed_bits(&[x, y]);
+        self.write(bits, 5);
+        self.write_signed(x, bits);
+        self.write_signed(y, bits);
+        if select_fill {
+            self.write(1, 1);
+        }
+    }
+
+    fn write_straight_edge(&mut self, from: (i32, i32), to: (i32, i32)) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        if (dx, dy) == (0, 0) {
+            return;
+        }
+        let bits = Self::signed_bits(&[dx, dy]).max(2);
+        if bits > Self::MAX_EDGE_BITS {
+            let middle = (from.0 + dx / 2, from.1 + dy / 2);
+            self.write_straight_edge(from, middle);
+            self.write_straight_edge(middle, to);
+            return;
+        }
+        self.write(0b11, 2);
+        self.write(bits - 2, 4);
+        // General line
+        self.write(1, 1);
+        self.write_signed(dx, bits);
+        self.write_signed(dy, bits);
+    }
+
+    fn write_curved_edge(&mut self, from: (i32, i32), control: (i32, i32), anchor: (i32, i32)) {
+        let deltas = [control.0 - from.0, control.1 - from.1, anchor.0 - control.0, anchor.1 - control.1];
+        let bits = Self::signed_bits(&deltas).max(2);
+        if bits > Self::MAX_EDGE_BITS {
+            // Too long a curve is flattened
+            self.write_straight_edge(from, anchor);
+            return;
+        }
+        self.write(0b10, 2);
+        self.write(bits - 2, 4);
+        for delta in deltas {
+            self.write_signed(delta, bits);
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
\ No newline at end of file
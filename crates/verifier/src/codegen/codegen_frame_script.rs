@@ -0,0 +1,62 @@
+use crate::ns::*;
+
+/// The top-level statements of the `CompilerOptions::frame_script` file,
+/// emitted as the script of the first frame of the timeline class in
+/// place of a document class.
+///
+/// ```ignore
+/// if let Some(script) = CodegenFrameScript::find(&host, &programs, &compiler_options) {
+///     // Declare script.definitions on the timeline class, then emit
+///     // script.statements into its frame 1 script
+/// }
+/// ```
+///
+/// The statements are those the verifier checked in the activation
+/// of the program, whose `this` is the timeline class.
+pub struct CodegenFrameScript {
+    pub program: Rc<Program>,
+    /// Activation the statements were verified in.
+    pub activation: Entity,
+    /// Statements in source order, including variable definitions,
+    /// whose initializers run in place.
+    pub statements: Vec<Rc<Directive>>,
+    /// Functions and variables of the script, declared as members of
+    /// the timeline instance.
+    pub definitions: Vec<Entity>,
+}
+
+impl CodegenFrameScript {
+    pub fn find(host: &Database, programs: &[Rc<Program>], compiler_options: &CompilerOptions) -> Option<Self> {
+        let options = compiler_options.frame_script.as_ref()?;
+        let program = programs.iter().find(|p| {
+            p.location.compilation_unit().file_path().map(|f| options.is_frame_script(&f)).unwrap_or(false)
+        })?;
+        let activation = host.node_mapping().get(program)?;
+        let mut statements = vec![];
+        let mut definitions = vec![];
+        for drtv in program.directives.iter() {
+            match drtv.as_ref() {
+                Directive::FunctionDefinition(_) => {
+                    definitions.extend(host.node_mapping().get(drtv));
+                },
+                Directive::VariableDefinition(defn) => {
+                    for binding in defn.bindings.iter() {
+                        definitions.extend(host.node_mapping().get(&binding.destructuring.destructuring).filter(|s| s.is::<VariableSlot>()));
+                    }
+                    statements.push(drtv.clone());
+                },
+                Directive::ImportDirective(_) |
+                Directive::UseNamespaceDirective(_) |
+                Directive::IncludeDirective(_) |
+                Directive::PackageConcatDirective(_) |
+                Directive::ClassDefinition(_) |
+                Directive::InterfaceDefinition(_) |
+                Directive::EnumDefinition(_) |
+                Directive::NamespaceDefinition(_) |
+                Directive::TypeDefinition(_) => {},
+                _ => statements.push(drtv.clone()),
+            }
+        }
+        Some(Self { program: program.clone(), activation, statements, definitions })
+    }
+}
\ No newline at end of file
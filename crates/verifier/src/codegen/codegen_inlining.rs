@@ -0,0 +1,96 @@
+use crate::ns::*;
+
+/// A method whose calls are inlined by `CodegenInlining`.
+#[derive(Clone)]
+pub struct InlinedMethod {
+    pub name: String,
+    pub common: Rc<FunctionCommon>,
+    /// Number of directives copied into each call site, an expression
+    /// body counting as one.
+    pub directive_count: usize,
+}
+
+/// Plans the inlining of calls to `[Inline]` methods, which the verifier
+/// restricts to static or final methods without closures or `try`
+/// statements. The body of such a method is copied into each call site,
+/// its parameters bound to new locals, when it holds at most
+/// `MAX_DIRECTIVES` directives and does not call itself; other calls are
+/// left as they are.
+pub struct CodegenInlining {
+    methods: HashMap<Entity, InlinedMethod>,
+}
+
+impl CodegenInlining {
+    /// Maximum number of directives of an inlined body, nested
+    /// directives included.
+    pub const MAX_DIRECTIVES: usize = 4;
+
+    /// Collects the inlined methods defined by the given programs.
+    pub fn collect(host: &Database, programs: &[Rc<Program>]) -> Self {
+        let mut collector = InlinedMethodCollector { host, methods: HashMap::new() };
+        for program in programs.iter() {
+            collector.visit_program(program);
+        }
+        Self { methods: collector.methods }
+    }
+
+    /// Returns the method whose body replaces a call expression.
+    pub fn inlined_call(&self, host: &Database, call: &CallExpression) -> Option<&InlinedMethod> {
+        self.methods.get(&referenced_entity(host, &call.base)?)
+    }
+}
+
+struct InlinedMethodCollector<'a> {
+    host: &'a Database,
+    methods: HashMap<Entity, InlinedMethod>,
+}
+
+impl<'a> InlinedMethodCollector<'a> {
+    fn collect_function_defn(&mut self, drtv: &Rc<Directive>, defn: &FunctionDefinition) {
+        if !Attribute::find_metadata(&defn.attributes).iter().any(|m| m.name.0 == "Inline") {
+            return;
+        }
+        let FunctionName::Identifier(name) = &defn.name else {
+            return;
+        };
+        let Some(method) = self.host.node_mapping().get(drtv).filter(|slot| slot.is::<MethodSlot>()) else {
+            return;
+        };
+        let directive_count = match &defn.common.body {
+            Some(FunctionBody::Block(block)) => {
+                let mut counter = DirectiveCounter(0);
+                counter.visit_block(block);
+                counter.0
+            },
+            Some(FunctionBody::Expression(_)) => 1,
+            None => return,
+        };
+        if directive_count > CodegenInlining::MAX_DIRECTIVES
+            || !InlineMetadataSubverifier::restrictions(&defn.common).is_empty()
+            || !CodegenTailCalls::self_calls(&name.0, &defn.common).is_empty() {
+            return;
+        }
+        self.methods.insert(method, InlinedMethod { name: name.0.clone(), common: defn.common.clone(), directive_count });
+    }
+}
+
+impl<'a> Visitor for InlinedMethodCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::FunctionDefinition(defn) = drtv.as_ref() {
+            self.collect_function_defn(drtv, defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
+
+struct DirectiveCounter(usize);
+
+impl Visitor for DirectiveCounter {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        // Blocks are not copied themselves
+        if !matches!(drtv.as_ref(), Directive::Block(_)) {
+            self.0 += 1;
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,229 @@
+use crate::ns::*;
+
+/// Type of a `[JsonType]` field as represented in JSON.
+#[derive(Clone, PartialEq, Debug)]
+pub enum JsonFieldType {
+    Number,
+    Int,
+    Uint,
+    String,
+    Boolean,
+    Array,
+    /// `Object` or `*`, copied without validation.
+    Any,
+    /// `Vector.<T>` of a non-vector element type, represented as an array.
+    Vector(Box<JsonFieldType>),
+    /// Another `[JsonType]` class, by local name.
+    JsonType(String),
+}
+
+impl JsonFieldType {
+    pub fn of(host: &Database, t: &Entity) -> Option<JsonFieldType> {
+        let t = t.escape_of_nullable_or_non_nullable();
+        Some(if t == host.number_type() {
+            Self::Number
+        } else if t == host.int_type() {
+            Self::Int
+        } else if t == host.uint_type() {
+            Self::Uint
+        } else if t == host.string_type() {
+            Self::String
+        } else if t == host.boolean_type() {
+            Self::Boolean
+        } else if t == host.array_type() {
+            Self::Array
+        } else if t == host.object_type() || t == host.any_type() {
+            Self::Any
+        } else if t.is::<TypeAfterSubstitution>() && t.origin() == host.vector_type() {
+            let element = Self::of(host, &t.substitute_types().get(0)?)?;
+            if matches!(element, Self::Vector(_)) {
+                return None;
+            }
+            Self::Vector(Box::new(element))
+        } else if t.is::<ClassType>() && CodegenJsonType::is_json_type(&t.metadata().iter().collect::<Vec<_>>()) {
+            Self::JsonType(t.name().local_name())
+        } else {
+            return None;
+        })
+    }
+
+    fn is_nullable(&self) -> bool {
+        !matches!(self, Self::Number | Self::Int | Self::Uint | Self::Boolean)
+    }
+
+    /// Condition holding for a JSON value of this type.
+    fn condition(&self, value: &str) -> String {
+        match self {
+            Self::Number => format!("typeof {value} == \"number\""),
+            Self::Int => format!("{value} is int"),
+            Self::Uint => format!("{value} is uint"),
+            Self::String => format!("{value} is String"),
+            Self::Boolean => format!("{value} is Boolean"),
+            Self::Array | Self::Vector(_) => format!("{value} is Array"),
+            Self::Any => format!("{value} !== undefined"),
+            Self::JsonType(_) => format!("typeof {value} == \"object\""),
+        }
+    }
+
+    fn from_json(&self, value: &str) -> String {
+        match self {
+            Self::JsonType(name) => format!("{name}.fromJSON({value})"),
+            _ => value.to_owned(),
+        }
+    }
+
+    fn to_json(&self, value: &str) -> String {
+        match self {
+            Self::JsonType(name) => format!("{name}.toJSON({value})"),
+            _ => value.to_owned(),
+        }
+    }
+
+    fn type_annotation(&self) -> String {
+        match self {
+            Self::Number => "Number".into(),
+            Self::Int => "int".into(),
+            Self::Uint => "uint".into(),
+            Self::String => "String".into(),
+            Self::Boolean => "Boolean".into(),
+            Self::Array => "Array".into(),
+            Self::Any => "*".into(),
+            Self::Vector(element) => format!("Vector.<{}>", element.type_annotation()),
+            Self::JsonType(name) => name.clone(),
+        }
+    }
+}
+
+/// A public, writable instance property of a `[JsonType]` class.
+#[derive(Clone, Debug)]
+pub struct JsonField {
+    pub name: String,
+    pub location: Location,
+    /// `None` if the property type has no JSON representation.
+    pub field_type: Option<JsonFieldType>,
+    pub static_type: Entity,
+}
+
+/// Generates the `fromJSON()` and `toJSON()` static helpers of classes
+/// marked with `[JsonType]` meta-data, when the `json_type_helpers`
+/// option is enabled.
+///
+/// `fromJSON()` validates the type of every field and throws a
+/// `TypeError` on mismatch. Fields are the public instance variables
+/// and getter/setter pairs not marked `[Transient]`.
+pub struct CodegenJsonType {
+    pub class: Entity,
+    pub fields: Vec<JsonField>,
+}
+
+impl CodegenJsonType {
+    pub const FROM_JSON: &'static str = "fromJSON";
+    pub const TO_JSON: &'static str = "toJSON";
+
+    pub fn is_json_type(metadata: &[Rc<Metadata>]) -> bool {
+        metadata.iter().any(|m| m.name.0 == "JsonType")
+    }
+
+    /// Collects the fields of a `[JsonType]` class definition.
+    pub fn new(host: &Database, drtv: &Rc<Directive>) -> Option<Self> {
+        let Directive::ClassDefinition(defn) = drtv.as_ref() else {
+            return None;
+        };
+        if !Self::is_json_type(&Attribute::find_metadata(&defn.attributes)) {
+            return None;
+        }
+        let class = host.node_mapping().get(drtv).filter(|c| c.is::<ClassType>())?;
+        let is_field = |attributes: &[Attribute]| {
+            attributes.iter().any(|a| matches!(a, Attribute::Public(_)))
+                && Attribute::find_static(attributes).is_none()
+                && !Attribute::find_metadata(attributes).iter().any(|m| m.name.0 == "Transient")
+        };
+        let mut names = vec![];
+        let mut setters = HashSet::<String>::new();
+        for drtv in defn.block.directives.iter() {
+            match drtv.as_ref() {
+                Directive::VariableDefinition(vardefn) if is_field(&vardefn.attributes) && vardefn.kind.0 != VariableDefinitionKind::Const => {
+                    names.extend(vardefn.bindings.iter().filter_map(|b| b.destructuring.destructuring.to_identifier_name()));
+                },
+                Directive::FunctionDefinition(fndefn) if is_field(&fndefn.attributes) => {
+                    match &fndefn.name {
+                        FunctionName::Getter(name) => names.push(name.clone()),
+                        FunctionName::Setter(name) => { setters.insert(name.0.clone()); },
+                        _ => {},
+                    }
+                },
+                _ => {},
+            }
+        }
+        let mut fields = vec![];
+        for (name, location) in names {
+            let is_accessor = class.prototype(host).get_in_any_public_ns(&name).ok().flatten().map(|p| p.is::<VirtualSlot>()).unwrap_or(false);
+            if is_accessor && !setters.contains(&name) {
+                continue;
+            }
+            let Ok(Some(property)) = class.prototype(host).get_in_any_public_ns(&name) else {
+                continue;
+            };
+            let static_type = property.static_type(host);
+            fields.push(JsonField { field_type: JsonFieldType::of(host, &static_type), name, location, static_type });
+        }
+        Some(Self { class, fields })
+    }
+
+    /// Source of `fromJSON()`, to be compiled as a static method of the class.
+    pub fn from_json_source(&self) -> String {
+        let class_name = self.class.name().local_name();
+        let mut r = format!("public static function {}(json: *): {class_name} {{\n", Self::FROM_JSON);
+        r.push_str(&format!("    if (json === null || typeof json != \"object\") throw new TypeError(\"Expected a JSON object for {class_name}\");\n"));
+        r.push_str(&format!("    var r: {class_name} = new {class_name}();\n"));
+        for field in self.fields.iter() {
+            let Some(t) = field.field_type.as_ref() else {
+                continue;
+            };
+            let (name, value) = (&field.name, format!("json.{}", field.name));
+            let mut condition = t.condition(&value);
+            if t.is_nullable() {
+                condition = format!("{value} === null || {condition}");
+            }
+            r.push_str(&format!("    if (!({condition})) throw new TypeError(\"Field '{name}' of {class_name} must be {}\");\n", t.type_annotation()));
+            if let JsonFieldType::Vector(element) = t {
+                r.push_str(&format!("    if ({value} === null) r.{name} = null; else {{\n"));
+                r.push_str(&format!("        r.{name} = new {}();\n", t.type_annotation()));
+                r.push_str(&format!("        for each (var {name}_element: * in {value}) {{\n"));
+                r.push_str(&format!("            if (!({})) throw new TypeError(\"Elements of field '{name}' of {class_name} must be {}\");\n", element.condition(&format!("{name}_element")), element.type_annotation()));
+                r.push_str(&format!("            r.{name}.push({});\n", element.from_json(&format!("{name}_element"))));
+                r.push_str("        }\n    }\n");
+            } else if matches!(t, JsonFieldType::JsonType(_)) {
+                r.push_str(&format!("    r.{name} = {value} === null ? null : {};\n", t.from_json(&value)));
+            } else {
+                r.push_str(&format!("    r.{name} = {value};\n"));
+            }
+        }
+        r.push_str("    return r;\n}\n");
+        r
+    }
+
+    /// Source of `toJSON()`, to be compiled as a static method of the class.
+    pub fn to_json_source(&self) -> String {
+        let class_name = self.class.name().local_name();
+        let mut r = format!("public static function {}(value: {class_name}): Object {{\n", Self::TO_JSON);
+        r.push_str("    if (value === null) return null;\n");
+        r.push_str("    var r: Object = {};\n");
+        for field in self.fields.iter() {
+            let Some(t) = field.field_type.as_ref() else {
+                continue;
+            };
+            let (name, value) = (&field.name, format!("value.{}", field.name));
+            if let JsonFieldType::Vector(element) = t {
+                r.push_str(&format!("    if ({value} === null) r.{name} = null; else {{\n"));
+                r.push_str(&format!("        r.{name} = [];\n"));
+                r.push_str(&format!("        for each (var {name}_element: {} in {value}) r.{name}.push({});\n", element.type_annotation(), element.to_json(&format!("{name}_element"))));
+                r.push_str("    }\n");
+            } else {
+                r.push_str(&format!("    r.{name} = {};\n", t.to_json(&value)));
+            }
+        }
+        r.push_str("    return r;\n}\n");
+        r
+    }
+}
\ No newline at end of file
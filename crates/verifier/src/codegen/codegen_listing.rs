@@ -0,0 +1,231 @@
+use crate::ns::*;
+
+/// Lists the methods code generation plans for a verified program with
+/// their estimated limits, in the format of `AbcDisassembler`, followed
+/// by the lowering of the constructs within each method, as dumped by
+/// `--emit=abc-asm` for source files.
+///
+/// ```ignore
+/// let inlining = CodegenInlining::collect(&host, &programs);
+/// let listing = CodegenListing::format(&host, &program, &inlining);
+/// // method#1 Main/f
+/// //   ; max_stack=2 locals=2 scope_depth=0..0
+/// //   3:13  destructuring {x, y: [a]}
+/// //           x = getproperty x
+/// //           a = getproperty y, getproperty 0
+/// ```
+///
+/// Methods are numbered and named as by `CodegenMethodLimits`. Each
+/// construct is listed under the innermost method containing it:
+///
+/// * Destructuring patterns, lowered as by `CodegenDestructuring`.
+/// * Function expressions, noting those that read `this`, which the
+///   verifier types as the enclosing `this`: the enclosing method saves
+///   its `this` into its activation, where the function reads it from.
+/// * The body of an async function, lowered into a state machine, and
+///   each `await`, which subscribes the next state to the awaited value
+///   through `then()` before returning.
+/// * Calls replaced by the body of an `[Inline]` method, as planned by
+///   `CodegenInlining`.
+/// * Self-recursive tail calls of functions converted into loops, as
+///   detected by `CodegenTailCalls`.
+/// * `switch` statements, lowered as planned by `CodegenSwitch`.
+pub struct CodegenListing<'a> {
+    host: &'a Database,
+    inlining: &'a CodegenInlining,
+    entries: Vec<(Location, Vec<String>)>,
+    /// Number of `await` operators visited per enclosing function.
+    awaits: Vec<usize>,
+}
+
+impl<'a> CodegenListing<'a> {
+    pub fn format(host: &'a Database, program: &Rc<Program>, inlining: &'a CodegenInlining) -> String {
+        let methods = CodegenMethodLimits::estimate(program);
+        let mut listing = Self { host, inlining, entries: vec![], awaits: vec![] };
+        listing.visit_program(program);
+
+        let mut method_entries: Vec<Vec<(Location, Vec<String>)>> = vec![vec![]; methods.len()];
+        for (location, lines) in listing.entries {
+            // A function expression starts where its own method does
+            let offset = location.first_offset();
+            let method = methods.iter().enumerate()
+                .filter(|(_, (method, _))| method.first_offset() < offset && offset < method.last_offset())
+                .max_by_key(|(_, (method, _))| method.first_offset())
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            method_entries[method].push((location, lines));
+        }
+
+        let mut r = String::new();
+        for (i, ((_, limits), entries)) in methods.iter().zip(method_entries.iter_mut()).enumerate() {
+            if i != 0 {
+                r.push('\n');
+            }
+            r.push_str(&format!("method#{} {}\n", limits.method, limits.name));
+            r.push_str(&format!("  ; max_stack={} locals={} scope_depth=0..{}\n", limits.max_stack, limits.local_count, limits.max_scope_depth));
+            entries.sort_by_key(|(location, _)| location.first_offset());
+            for (location, lines) in entries.iter() {
+                let position = format!("{}:{}", location.first_line_number(), location.first_column() + 1);
+                r.push_str(&format!("  {position}  {}\n", lines[0]));
+                for line in lines[1..].iter() {
+                    r.push_str(&format!("  {}  {line}\n", " ".repeat(position.len())));
+                }
+            }
+        }
+        r
+    }
+
+    fn add_destructuring(&mut self, pattern: &Rc<Expression>) {
+        if matches!(pattern.as_ref(), Expression::QualifiedIdentifier(_)) {
+            return;
+        }
+        let mut lines = vec![format!("destructuring {}", pattern.location().text())];
+        for target in CodegenDestructuring::lower(pattern) {
+            let name = match &target.binding {
+                DestructuringBinding::Identifier(exp) => exp.to_identifier_name().map(|(name, _)| name),
+                DestructuringBinding::Shorthand(field) => match field.as_ref() {
+                    InitializerField::Field { name: (FieldName::Identifier(id), _), .. } => id.to_identifier_name_or_asterisk().map(|(name, _)| name),
+                    _ => None,
+                },
+            };
+            let accesses = target.path.iter().map(|access| match access {
+                DestructuringAccess::Index(i) => format!("getproperty {i}"),
+                DestructuringAccess::Property(name) => format!("getproperty {name}"),
+                DestructuringAccess::Computed(_) => "getproperty (rt)".to_owned(),
+                DestructuringAccess::NonNull => "convert_nonnull".to_owned(),
+            }).collect::<Vec<_>>();
+            lines.push(format!("  {} = {}", name.unwrap_or_else(|| "*".to_owned()), accesses.join(", ")));
+        }
+        self.entries.push((pattern.location(), lines));
+    }
+
+    fn add_tail_calls(&mut self, defn: &FunctionDefinition) {
+        let FunctionName::Identifier(name) = &defn.name else {
+            return;
+        };
+        let calls = CodegenTailCalls::self_calls(&name.0, &defn.common);
+        if !CodegenTailCalls::can_convert_to_loop(&calls) {
+            return;
+        }
+        let parameter_count = defn.common.signature.parameters.len();
+        for call in calls.iter() {
+            self.entries.push((call.call.location(), vec![
+                "tail call converted into a loop".to_owned(),
+                format!("  ; rebinds {parameter_count} parameters and jumps to the start of {}", name.0),
+            ]));
+        }
+    }
+
+    fn add_switch(&mut self, swstmt: &SwitchStatement) {
+        let labels = swstmt.cases.iter().flat_map(|case| case.labels.iter()).filter_map(|label| match label {
+            CaseLabel::Case((exp, _)) => Some(exp),
+            CaseLabel::Default(_) => None,
+        }).collect::<Vec<_>>();
+        let int_values = labels.iter().map(|exp| CodegenSwitch::case_value(self.host, exp)).collect::<Vec<_>>();
+        let lowering = if int_values.iter().all(|v| v.is_some()) {
+            CodegenSwitch::plan(&int_values)
+        } else {
+            let string_values = labels.iter().map(|exp| CodegenSwitch::string_case_value(self.host, exp)).collect::<Vec<_>>();
+            CodegenSwitch::plan_strings(&string_values)
+        };
+        let lines = match lowering {
+            SwitchLowering::LookupSwitch { min, table } => vec![
+                format!("lookupswitch of {} entries from {min}", table.len()),
+                format!("  ; {} cases, {} entries taking the default case", labels.len(), table.iter().filter(|entry| entry.is_none()).count()),
+            ],
+            SwitchLowering::StringTable { keys } => vec![
+                format!("string table of {} keys", keys.len()),
+                "  ; getproperty on the table, then lookupswitch over the case index".to_owned(),
+            ],
+            SwitchLowering::Chained => vec![format!("chained ifstricteq of {} cases", labels.len())],
+        };
+        self.entries.push((swstmt.location.clone(), lines));
+    }
+}
+
+impl<'a> Visitor for CodegenListing<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::VariableDefinition(defn) => {
+                for binding in defn.bindings.iter() {
+                    self.add_destructuring(&binding.destructuring.destructuring);
+                }
+            },
+            Directive::FunctionDefinition(defn) => self.add_tail_calls(defn),
+            Directive::SwitchStatement(swstmt) => self.add_switch(swstmt),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Assignment(e) => {
+                if e.compound.is_none() && matches!(e.left.as_ref(), Expression::ArrayLiteral(_) | Expression::ObjectInitializer(_)) {
+                    self.add_destructuring(&e.left);
+                }
+            },
+            Expression::Call(e) => {
+                if let Some(method) = self.inlining.inlined_call(self.host, e) {
+                    self.entries.push((exp.location(), vec![
+                        format!("inline {}", method.name),
+                        format!("  ; binds {} parameters to locals and copies {} directives", method.common.signature.parameters.len(), method.directive_count),
+                    ]));
+                }
+            },
+            Expression::Unary(e) if e.operator == Operator::Await => {
+                if let Some(count) = self.awaits.last_mut() {
+                    *count += 1;
+                    let lines = vec!["await".to_owned(), format!("  callproperty then, 2; returnvoid; resumes in state {count}")];
+                    self.entries.push((exp.location(), lines));
+                }
+            },
+            Expression::Function(e) => {
+                let mut lines = vec!["newfunction".to_owned()];
+                let mut this_reads = ThisReads(false);
+                walk_function_common(&mut this_reads, &e.common);
+                if this_reads.0 {
+                    lines.push("  ; reads the enclosing this, saved into the activation".to_owned());
+                }
+                self.entries.push((exp.location(), lines));
+            },
+            _ => {},
+        }
+        walk_expression(self, exp);
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        for param in common.signature.parameters.iter() {
+            self.add_destructuring(&param.destructuring.destructuring);
+        }
+        // Awaits belong to the innermost function
+        self.awaits.push(0);
+        walk_function_common(self, common);
+        let count = self.awaits.pop().unwrap();
+        if !common.contains_await {
+            return;
+        }
+        let location = match &common.body {
+            Some(FunctionBody::Block(block)) => block.location.clone(),
+            Some(FunctionBody::Expression(exp)) => exp.location(),
+            None => return,
+        };
+        self.entries.push((location, vec![
+            format!("async state machine of {} states", count + 1),
+            "  ; returns a Promise resolved by the final state".to_owned(),
+        ]));
+    }
+}
+
+/// Finds `this` within a function expression, nested function
+/// expressions included, as they read the same `this`.
+struct ThisReads(bool);
+
+impl Visitor for ThisReads {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if matches!(exp.as_ref(), Expression::ThisLiteral(_)) {
+            self.0 = true;
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,71 @@
+use crate::ns::*;
+
+/// Selects the meta-data kept in compiled output for runtime reflection
+/// through `describeType()`, from `CompilerOptions::retained_metadata`
+/// and `[KeepMetadata]` meta-data. Other meta-data is stripped.
+///
+/// `[KeepMetadata]` on a class keeps every meta-data of the class and
+/// its members, while `[KeepMetadata(tags="Inject,PostConstruct")]`
+/// keeps only the listed tags. `[KeepMetadata]` itself is always
+/// stripped.
+///
+/// ```ignore
+/// let retention = CodegenMetadataRetention::new(&compiler_options);
+/// for metadata in retention.retained_metadata(&class) {
+///     // Emit metadata into the traits of the class
+/// }
+/// ```
+pub struct CodegenMetadataRetention {
+    retained: Vec<String>,
+}
+
+impl CodegenMetadataRetention {
+    pub const KEEP_METADATA: &'static str = "KeepMetadata";
+
+    /// Meta-data consumed by the compiler, which reflection needs not
+    /// see once stripped.
+    pub const COMPILER_METADATA: [&'static str; 14] = [
+        "API", "Bindable", "Embed", "Event", "Experimental", "Extension", "Inline",
+        "InternalAPI", "JsonType", "Options", "Pure", "ResourceBundle", "TailCall", "Throws",
+    ];
+
+    pub fn new(compiler_options: &CompilerOptions) -> Self {
+        Self { retained: compiler_options.retained_metadata.clone() }
+    }
+
+    pub fn is_retained(&self, entity: &Entity, name: &str) -> bool {
+        if name == Self::KEEP_METADATA {
+            return false;
+        }
+        if self.retained.iter().any(|n| n == name) {
+            return true;
+        }
+        match Self::keep_list(entity) {
+            Some(tags) => tags.is_empty() || tags.iter().any(|n| n == name),
+            None => false,
+        }
+    }
+
+    pub fn retained_metadata(&self, entity: &Entity) -> Vec<Rc<Metadata>> {
+        entity.metadata().iter().filter(|m| self.is_retained(entity, &m.name.0)).collect()
+    }
+
+    pub fn stripped_metadata(&self, entity: &Entity) -> Vec<Rc<Metadata>> {
+        entity.metadata().iter().filter(|m| m.name.0 != Self::KEEP_METADATA && !self.is_retained(entity, &m.name.0)).collect()
+    }
+
+    /// Returns the tags listed by `[KeepMetadata]` on a definition or its
+    /// class, empty if every tag is kept, or `None` without `[KeepMetadata]`.
+    fn keep_list(entity: &Entity) -> Option<Vec<String>> {
+        let find = |entity: &Entity| entity.metadata().iter().find(|m| m.name.0 == Self::KEEP_METADATA);
+        let class = entity.parent().filter(|p| p.is::<ClassType>() || p.is::<InterfaceType>());
+        let metadata = find(entity).or_else(|| class.as_ref().and_then(find))?;
+        let tags = metadata.entries.iter().flatten()
+            .find(|entry| entry.key.as_ref().map(|(k, _)| k == "tags").unwrap_or(false))
+            .map(|entry| match entry.value.as_ref() {
+                MetadataValue::String(val) => val.0.clone(),
+                MetadataValue::IdentifierString(val) => val.0.clone(),
+            });
+        Some(tags.map(|tags| tags.split(',').map(|t| t.trim().to_owned()).filter(|t| !t.is_empty()).collect()).unwrap_or_default())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,220 @@
+use crate::ns::*;
+
+/// Estimates the operand stack, scope stack and register counts of the
+/// methods code generation would emit for a program, so that pathological
+/// methods are spotted before the AVM2 verifier rejects them.
+///
+/// ```ignore
+/// for (location, limits) in CodegenMethodLimits::estimate(&program) {
+///     println!("{}: {} needs {} registers", location.line_number(), limits.name, limits.local_count);
+/// }
+/// ```
+///
+/// The estimates follow the usual code shapes: a register per parameter,
+/// local variable, `with` object, exception and `for..in` iteration, a
+/// scope per nested `with` and `catch`, and a stack slot per operand
+/// pending while an expression evaluates. Methods are named as
+/// `AbcMethodLimits::collect()` names them and numbered in source order.
+pub struct CodegenMethodLimits {
+    package: String,
+    classes: Vec<String>,
+    frames: Vec<MethodFrame>,
+    /// Name of the next function visited.
+    function_name: Option<String>,
+    /// Whether an expression is being visited, whose operands are
+    /// accounted for by the topmost one.
+    in_expression: bool,
+    results: Vec<(Location, AbcMethodLimits)>,
+}
+
+struct MethodFrame {
+    name: String,
+    location: Location,
+    local_count: u32,
+    scope_depth: u32,
+    max_scope_depth: u32,
+    max_stack: u32,
+}
+
+impl CodegenMethodLimits {
+    pub fn estimate(program: &Rc<Program>) -> Vec<(Location, AbcMethodLimits)> {
+        let mut estimator = Self { package: String::new(), classes: vec![], frames: vec![], function_name: None, in_expression: false, results: vec![] };
+        estimator.push_frame("script initializer".to_owned(), program.location.clone(), 0);
+        estimator.visit_program(program);
+        estimator.pop_frame();
+        estimator.results.sort_by_key(|(location, _)| location.first_offset());
+        for (i, (_, limits)) in estimator.results.iter_mut().enumerate() {
+            limits.method = i as u32;
+        }
+        estimator.results
+    }
+
+    fn push_frame(&mut self, name: String, location: Location, local_count: u32) {
+        self.frames.push(MethodFrame { name, location, local_count, scope_depth: 0, max_scope_depth: 0, max_stack: 0 });
+    }
+
+    fn pop_frame(&mut self) {
+        let frame = self.frames.pop().unwrap();
+        self.results.push((frame.location, AbcMethodLimits {
+            method: 0,
+            name: frame.name,
+            max_stack: frame.max_stack,
+            max_scope_depth: frame.max_scope_depth,
+            local_count: frame.local_count,
+        }));
+    }
+
+    fn frame(&mut self) -> &mut MethodFrame {
+        self.frames.last_mut().unwrap()
+    }
+
+    fn add_locals(&mut self, count: u32) {
+        // Variables of scripts and classes are slots rather than registers
+        if self.frames.len() > 1 {
+            self.frame().local_count += count;
+        }
+    }
+
+    fn visit_with_scope(&mut self, f: impl FnOnce(&mut Self)) {
+        let frame = self.frame();
+        frame.scope_depth += 1;
+        frame.max_scope_depth = frame.max_scope_depth.max(frame.scope_depth);
+        f(self);
+        self.frame().scope_depth -= 1;
+    }
+
+    /// Prefix of the names of the members of the innermost class, or of
+    /// the package-level definitions.
+    fn prefix(&self) -> String {
+        let qualified = |name: &str| if self.package.is_empty() { name.to_owned() } else { format!("{}::{name}", self.package) };
+        match self.classes.last() {
+            Some(class) => format!("{}/", qualified(class)),
+            None => qualified(""),
+        }
+    }
+
+    /// Operand stack slots used while evaluating an expression.
+    fn stack(exp: &Rc<Expression>) -> u32 {
+        let arguments = |offset: u32, arguments: &[Rc<Expression>]| arguments.iter().enumerate()
+            .map(|(i, arg)| offset + i as u32 + Self::stack(arg))
+            .max()
+            .unwrap_or(offset);
+        match exp.as_ref() {
+            Expression::Paren(e) => Self::stack(&e.expression),
+            Expression::Member(e) => Self::stack(&e.base),
+            Expression::ComputedMember(e) => Self::stack(&e.base).max(1 + Self::stack(&e.key)),
+            Expression::Descendants(e) => Self::stack(&e.base),
+            Expression::WithTypeArguments(e) => Self::stack(&e.base),
+            Expression::Filter(e) => Self::stack(&e.base).max(2 + Self::stack(&e.test)),
+            // The receiver and function precede the arguments
+            Expression::Call(e) => (1 + Self::stack(&e.base)).max(arguments(2, &e.arguments)),
+            Expression::New(e) => Self::stack(&e.base).max(arguments(1, e.arguments.as_deref().unwrap_or(&[]))),
+            Expression::ArrayLiteral(e) => Self::elements(0, &e.elements),
+            Expression::VectorLiteral(e) => Self::elements(2, &e.elements),
+            // Each field pushes its name and value
+            Expression::ObjectInitializer(e) => e.fields.iter().enumerate().map(|(i, field)| {
+                let value = match field.as_ref() {
+                    InitializerField::Field { value, .. } => value.as_ref().map(Self::stack).unwrap_or(1),
+                    InitializerField::Rest((exp, _)) => Self::stack(exp),
+                };
+                2 * i as u32 + 1 + value
+            }).max().unwrap_or(1),
+            Expression::Unary(e) => Self::stack(&e.expression),
+            Expression::Binary(e) => Self::stack(&e.left).max(1 + Self::stack(&e.right)),
+            Expression::OptionalChaining(e) => 1 + Self::stack(&e.base).max(Self::stack(&e.expression)),
+            Expression::Conditional(e) => Self::stack(&e.test).max(Self::stack(&e.consequent)).max(Self::stack(&e.alternative)),
+            Expression::Sequence(e) => Self::stack(&e.left).max(Self::stack(&e.right)),
+            // The value is duplicated as the result of the assignment
+            Expression::Assignment(e) => 1 + Self::stack(&e.left).max(1 + Self::stack(&e.right)),
+            _ => 1,
+        }
+    }
+
+    fn elements(offset: u32, elements: &[Element]) -> u32 {
+        elements.iter().enumerate().map(|(i, elem)| offset + i as u32 + match elem {
+            Element::Expression(exp) | Element::Rest((exp, _)) => Self::stack(exp),
+            Element::Elision => 1,
+        }).max().unwrap_or(offset.max(1))
+    }
+}
+
+impl Visitor for CodegenMethodLimits {
+    fn visit_program(&mut self, program: &Rc<Program>) {
+        for pckgdefn in program.packages.iter() {
+            self.package = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+            self.visit_block(&pckgdefn.block);
+        }
+        self.package = String::new();
+        for drtv in program.directives.iter() {
+            self.visit_directive(drtv);
+        }
+    }
+
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                self.classes.push(defn.name.0.clone());
+                walk_directive(self, drtv);
+                self.classes.pop();
+            },
+            Directive::FunctionDefinition(defn) => {
+                let prefix = self.prefix();
+                self.function_name = Some(match &defn.name {
+                    FunctionName::Identifier(name) => format!("{prefix}{}", name.0),
+                    FunctionName::Getter(name) => format!("{prefix}get {}", name.0),
+                    FunctionName::Setter(name) => format!("{prefix}set {}", name.0),
+                    FunctionName::Constructor(_) => format!("{prefix}constructor"),
+                });
+                walk_directive(self, drtv);
+            },
+            Directive::VariableDefinition(defn) => {
+                self.add_locals(defn.bindings.len() as u32);
+                walk_directive(self, drtv);
+            },
+            Directive::ForInStatement(_) => {
+                // The iterated object and the index
+                self.add_locals(2);
+                walk_directive(self, drtv);
+            },
+            Directive::WithStatement(wstmt) => {
+                self.add_locals(1);
+                self.visit_expression(&wstmt.object);
+                self.visit_with_scope(|this| this.visit_directive(&wstmt.body));
+            },
+            Directive::TryStatement(trystmt) => {
+                self.visit_block(&trystmt.block);
+                for catch_clause in trystmt.catch_clauses.iter() {
+                    self.add_locals(1);
+                    self.visit_with_scope(|this| this.visit_block(&catch_clause.block));
+                }
+                if let Some(finally_clause) = trystmt.finally_clause.as_ref() {
+                    self.visit_block(&finally_clause.block);
+                }
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if self.in_expression {
+            walk_expression(self, exp);
+            return;
+        }
+        let stack = Self::stack(exp);
+        let frame = self.frame();
+        frame.max_stack = frame.max_stack.max(stack);
+        self.in_expression = true;
+        walk_expression(self, exp);
+        self.in_expression = false;
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        let name = self.function_name.take().unwrap_or_else(|| format!("{}<anonymous>", self.prefix()));
+        let in_expression = std::mem::replace(&mut self.in_expression, false);
+        // `this` and the parameters
+        self.push_frame(name, common.location.clone(), 1 + common.signature.parameters.len() as u32);
+        walk_function_common(self, common);
+        self.pop_frame();
+        self.in_expression = in_expression;
+    }
+}
\ No newline at end of file
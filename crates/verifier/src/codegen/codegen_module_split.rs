@@ -0,0 +1,278 @@
+use crate::ns::*;
+
+/// A SWF built from part of the application classes.
+#[derive(Clone, Debug)]
+pub struct SwfModule {
+    pub name: String,
+    pub classes: Vec<Entity>,
+    /// Package-level functions and variables.
+    pub package_definitions: Vec<Entity>,
+    /// Classes and package-level definitions of other modules referenced
+    /// by this module, emitted as external definitions.
+    pub externals: Vec<Entity>,
+    /// Names of the modules defining the externals, which must be
+    /// loaded first.
+    pub dependencies: Vec<String>,
+}
+
+/// Partitions the application classes into SWF modules loaded at
+/// runtime, in the style of runtime shared libraries, when the
+/// `swf_modules` compiler option is given.
+///
+/// Each class or package-level definition goes to the first module listing
+/// its package or a parent package, and otherwise to the main module,
+/// [`CodegenModuleSplit::MAIN_MODULE`]. References across modules are found
+/// from the verified programs: base classes, implemented interfaces and any
+/// type, static member or package-level definition referred to by
+/// expressions in the definition. Definitions named by the constant
+/// strings given to `getDefinitionByName()` (see
+/// `Verifier::reflected_definitions()`) count as references, so that the
+/// modules defining them are loaded first.
+///
+/// ```ignore
+/// let split = CodegenModuleSplit::new(&host, &programs, verifier.reflected_definitions(), &compiler_options);
+/// for module in split.modules() {
+///     // Emit module.classes, treating module.externals as external
+/// }
+/// std::fs::write(CodegenModuleSplit::MANIFEST_FILE_NAME, split.manifest_json()?)?;
+/// ```
+pub struct CodegenModuleSplit {
+    modules: Vec<SwfModule>,
+}
+
+impl CodegenModuleSplit {
+    pub const MAIN_MODULE: &'static str = "main";
+    pub const MANIFEST_FILE_NAME: &'static str = "modules.json";
+
+    pub fn new(host: &Database, programs: &[Rc<Program>], reflected_definitions: &[ReflectedDefinition], compiler_options: &CompilerOptions) -> Self {
+        let mut modules = vec![SwfModule {
+            name: Self::MAIN_MODULE.into(),
+            classes: vec![],
+            package_definitions: vec![],
+            externals: vec![],
+            dependencies: vec![],
+        }];
+        for options in compiler_options.swf_modules.iter() {
+            modules.push(SwfModule {
+                name: options.name.clone(),
+                classes: vec![],
+                package_definitions: vec![],
+                externals: vec![],
+                dependencies: vec![],
+            });
+        }
+
+        let mut collector = ModuleReferenceCollector {
+            host,
+            definitions: vec![],
+            current: None,
+            reflected: reflected_definitions.iter().filter_map(|r| {
+                Some(((r.location.compilation_unit().file_path().unwrap_or_default(), r.location.first_offset()), r.definition.clone()?))
+            }).collect(),
+        };
+        for program in programs.iter() {
+            collector.visit_program(program);
+        }
+
+        let module_index = |definition: &Entity| -> usize {
+            let pckg_name = Self::package_name(definition);
+            compiler_options.swf_modules.iter().position(|m| {
+                m.packages.iter().any(|p| pckg_name == *p || pckg_name.starts_with(&format!("{p}.")))
+            }).map(|i| i + 1).unwrap_or(0)
+        };
+        let indices: HashMap<Entity, usize> = collector.definitions.iter().map(|(definition, _)| (definition.clone(), module_index(definition))).collect();
+        for (definition, references) in collector.definitions.iter() {
+            let index = indices[definition];
+            if definition.is::<Type>() {
+                modules[index].classes.push(definition.clone());
+            } else {
+                modules[index].package_definitions.push(definition.clone());
+            }
+            for reference in references.iter() {
+                let Some(&other) = indices.get(reference) else {
+                    continue;
+                };
+                if other == index {
+                    continue;
+                }
+                if !modules[index].externals.contains(reference) {
+                    modules[index].externals.push(reference.clone());
+                }
+                let dependency = modules[other].name.clone();
+                if !modules[index].dependencies.contains(&dependency) {
+                    modules[index].dependencies.push(dependency);
+                }
+            }
+        }
+        Self { modules }
+    }
+
+    pub fn modules(&self) -> &[SwfModule] {
+        &self.modules
+    }
+
+    /// Returns the module containing a class or package-level definition.
+    pub fn module_of(&self, definition: &Entity) -> Option<&SwfModule> {
+        self.modules.iter().find(|m| m.classes.contains(definition) || m.package_definitions.contains(definition))
+    }
+
+    /// Returns the module names in an order where each module follows its
+    /// dependencies, or `Err` with the modules of a dependency cycle.
+    pub fn load_order(&self) -> Result<Vec<String>, Vec<String>> {
+        let mut order: Vec<String> = vec![];
+        let mut visiting: Vec<String> = vec![];
+        for module in self.modules.iter() {
+            self.visit_load_order(module, &mut order, &mut visiting)?;
+        }
+        Ok(order)
+    }
+
+    fn visit_load_order(&self, module: &SwfModule, order: &mut Vec<String>, visiting: &mut Vec<String>) -> Result<(), Vec<String>> {
+        if order.contains(&module.name) {
+            return Ok(());
+        }
+        if let Some(i) = visiting.iter().position(|m| *m == module.name) {
+            return Err(visiting[i..].to_vec());
+        }
+        visiting.push(module.name.clone());
+        for dependency in module.dependencies.iter() {
+            if let Some(dependency) = self.modules.iter().find(|m| m.name == *dependency) {
+                self.visit_load_order(dependency, order, visiting)?;
+            }
+        }
+        visiting.pop();
+        order.push(module.name.clone());
+        Ok(())
+    }
+
+    /// Serializes the load manifest as JSON, listing the modules in load
+    /// order with their SWF file, classes and dependencies. Fails with
+    /// the modules of a dependency cycle.
+    pub fn manifest_json(&self) -> Result<String, Vec<String>> {
+        let order = self.load_order()?;
+        let modules = order.iter().map(|name| {
+            let module = self.modules.iter().find(|m| m.name == *name).unwrap();
+            let classes = module.classes.iter().map(|c| json_string(&c.to_string())).collect::<Vec<_>>().join(",");
+            let package_definitions = module.package_definitions.iter().map(|d| json_string(&d.to_string())).collect::<Vec<_>>().join(",");
+            let dependencies = module.dependencies.iter().map(|d| json_string(d)).collect::<Vec<_>>().join(",");
+            format!("{{\"name\":{},\"file\":{},\"classes\":[{}],\"packageDefinitions\":[{}],\"dependencies\":[{}]}}",
+                json_string(name), json_string(&format!("{name}.swf")), classes, package_definitions, dependencies)
+        }).collect::<Vec<_>>().join(",");
+        Ok(format!("{{\"version\":1,\"modules\":[{}]}}", modules))
+    }
+
+    fn package_name(definition: &Entity) -> String {
+        let mut pckg = definition.parent();
+        while let Some(p) = pckg.as_ref().filter(|p| !p.is::<Package>()) {
+            pckg = p.parent();
+        }
+        pckg.map(|p| p.fully_qualified_name_list().join(".")).unwrap_or_default()
+    }
+}
+
+/// Collects the classes and package-level definitions of the programs
+/// and the definitions each refers to.
+struct ModuleReferenceCollector<'a> {
+    host: &'a Database,
+    definitions: Vec<(Entity, Vec<Entity>)>,
+    /// Index of the definition being visited in `definitions`.
+    current: Option<usize>,
+    /// Reflected definitions by the file path and offset of the string
+    /// naming them.
+    reflected: HashMap<(String, usize), Entity>,
+}
+
+impl<'a> ModuleReferenceCollector<'a> {
+    fn add_reference(&mut self, t: Entity) {
+        let Some(i) = self.current else {
+            return;
+        };
+        let t = if t.is::<TypeAfterSubstitution>() { t.origin() } else { t };
+        if !(t.is::<ClassType>() || t.is::<InterfaceType>() || t.is::<EnumType>() || Self::is_package_definition(&t)) || t == self.definitions[i].0 {
+            return;
+        }
+        if !self.definitions[i].1.contains(&t) {
+            self.definitions[i].1.push(t);
+        }
+    }
+
+    fn is_package_definition(entity: &Entity) -> bool {
+        (entity.is::<MethodSlot>() || entity.is::<VariableSlot>()) && entity.parent().map(|p| p.is::<Package>()).unwrap_or(false)
+    }
+
+    /// Visits a definition with its references attributed to it.
+    fn enter(&mut self, definition: Entity, visit: impl FnOnce(&mut Self)) {
+        let previous = self.current;
+        self.definitions.push((definition, vec![]));
+        self.current = Some(self.definitions.len() - 1);
+        visit(self);
+        self.current = previous;
+    }
+}
+
+impl<'a> Visitor for ModuleReferenceCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(_) | Directive::InterfaceDefinition(_) | Directive::EnumDefinition(_) => {
+                let Some(class) = self.host.node_mapping().get(drtv) else {
+                    walk_directive(self, drtv);
+                    return;
+                };
+                self.enter(class.clone(), |collector| {
+                    if class.is::<ClassType>() {
+                        if let Some(base) = class.extends_class(collector.host) {
+                            collector.add_reference(base);
+                        }
+                        for itrfc in class.implements(collector.host).iter() {
+                            collector.add_reference(itrfc);
+                        }
+                    }
+                    walk_directive(collector, drtv);
+                });
+            },
+            Directive::FunctionDefinition(_) => {
+                match self.host.node_mapping().get(drtv).filter(Self::is_package_definition) {
+                    Some(slot) => self.enter(slot, |collector| walk_directive(collector, drtv)),
+                    None => walk_directive(self, drtv),
+                }
+            },
+            Directive::VariableDefinition(defn) => {
+                for binding in defn.bindings.iter() {
+                    let slot = self.host.node_mapping().get(&binding.destructuring.destructuring).filter(Self::is_package_definition);
+                    let visit = |collector: &mut Self| {
+                        if let Some(t) = binding.destructuring.type_annotation.as_ref() {
+                            collector.visit_expression(t);
+                        }
+                        if let Some(init) = binding.initializer.as_ref() {
+                            collector.visit_expression(init);
+                        }
+                    };
+                    match slot {
+                        Some(slot) => self.enter(slot, visit),
+                        None => visit(self),
+                    }
+                }
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        let location = exp.location();
+        if let Some(definition) = self.reflected.get(&(location.compilation_unit().file_path().unwrap_or_default(), location.first_offset())).cloned() {
+            self.add_reference(definition);
+        }
+        if let Some(val) = self.host.node_mapping().get(exp) {
+            if val.is::<Type>() {
+                self.add_reference(val);
+            } else if val.is::<StaticReferenceValue>() {
+                if let Some(parent) = val.property().parent() {
+                    self.add_reference(parent);
+                }
+            } else if val.is::<PackageReferenceValue>() || val.is::<ScopeReferenceValue>() {
+                self.add_reference(val.property());
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,148 @@
+use crate::ns::*;
+
+/// Renames `private` and `internal` classes and members to short names
+/// in release builds, when the `minify_identifiers` compiler option is set.
+///
+/// Names are preserved when they may be reached through reflection:
+/// names appearing as string literals in the programs, definitions with
+/// meta-data, and classes marked with `[RemoteClass]` together with their
+/// members. Members of the same original name are given the same short
+/// name, so that overrides remain consistent.
+///
+/// ```ignore
+/// let renaming = CodegenRenaming::new(&host, &programs, &compiler_options);
+/// let name = renaming.name_of(&member);
+/// std::fs::write("rename-map.txt", renaming.rename_map())?;
+/// ```
+pub struct CodegenRenaming {
+    renames: HashMap<Entity, String>,
+    /// Fully qualified original names and their short names, in
+    /// renaming order.
+    map: Vec<(String, String)>,
+}
+
+impl CodegenRenaming {
+    const RESERVED_WORDS: [&'static str; 8] = ["as", "do", "if", "in", "is", "for", "new", "try"];
+
+    pub fn new(host: &Database, programs: &[Rc<Program>], compiler_options: &CompilerOptions) -> Self {
+        let mut renaming = Self { renames: HashMap::new(), map: vec![] };
+        if !compiler_options.minify_identifiers {
+            return renaming;
+        }
+        let mut collector = RenamingCollector {
+            host,
+            string_literals: HashSet::new(),
+            classes: vec![],
+        };
+        for program in programs.iter() {
+            collector.visit_program(program);
+        }
+        let RenamingCollector { string_literals: mut preserved, classes, .. } = collector;
+
+        // Candidates first, so that no short name collides with a preserved name
+        let mut candidates: Vec<(Entity, String)> = vec![];
+        for (class, remote) in classes.iter() {
+            if *remote {
+                preserved.insert(class.name().local_name());
+                for member in Self::members(host, class) {
+                    preserved.insert(member.name().local_name());
+                }
+                continue;
+            }
+            if Self::is_renameable(class) {
+                candidates.push((class.clone(), class.to_string()));
+            }
+            for member in Self::members(host, class) {
+                if Self::is_renameable(&member) {
+                    candidates.push((member.clone(), format!("{class}.{}", member.name().local_name())));
+                } else {
+                    preserved.insert(member.name().local_name());
+                }
+            }
+        }
+        candidates.retain(|(entity, _)| !preserved.contains(&entity.name().local_name()));
+
+        let mut short_names: HashMap<String, String> = HashMap::new();
+        let mut next_index = 0usize;
+        for (entity, qualified_name) in candidates {
+            let local_name = entity.name().local_name();
+            let short_name = short_names.entry(local_name).or_insert_with(|| {
+                loop {
+                    let name = Self::short_name(next_index);
+                    next_index += 1;
+                    if !preserved.contains(&name) && !Self::RESERVED_WORDS.contains(&name.as_str()) {
+                        break name;
+                    }
+                }
+            }).clone();
+            renaming.map.push((qualified_name, short_name.clone()));
+            renaming.renames.insert(entity, short_name);
+        }
+        renaming
+    }
+
+    /// Returns the name to emit for a definition.
+    pub fn name_of(&self, entity: &Entity) -> String {
+        self.renames.get(entity).cloned().unwrap_or_else(|| entity.name().local_name())
+    }
+
+    pub fn is_renamed(&self, entity: &Entity) -> bool {
+        self.renames.contains_key(entity)
+    }
+
+    /// Returns a line per renamed definition, as `com.example.Foo.bar -> c`,
+    /// for de-obfuscating stack traces.
+    pub fn rename_map(&self) -> String {
+        self.map.iter().map(|(original, short_name)| format!("{original} -> {short_name}\n")).collect()
+    }
+
+    fn members(host: &Database, class: &Entity) -> Vec<Entity> {
+        let mut members: Vec<Entity> = class.properties(host).borrow().iter().map(|(_, m)| m.clone()).collect();
+        members.extend(class.prototype(host).borrow().iter().map(|(_, m)| m.clone()));
+        members
+    }
+
+    fn is_renameable(entity: &Entity) -> bool {
+        let private_or_internal = matches!(entity.name().namespace().system_ns_kind(), Some(SystemNamespaceKind::Private | SystemNamespaceKind::Internal));
+        private_or_internal && !entity.is_external() && entity.metadata().iter().next().is_none()
+    }
+
+    /// Returns `a` through `z`, then `aa`, `ab` and so on.
+    fn short_name(mut index: usize) -> String {
+        let mut name = String::new();
+        loop {
+            name.insert(0, (b'a' + (index % 26) as u8) as char);
+            if index < 26 {
+                break;
+            }
+            index = index / 26 - 1;
+        }
+        name
+    }
+}
+
+struct RenamingCollector<'a> {
+    host: &'a Database,
+    string_literals: HashSet<String>,
+    /// Classes and whether they are marked with `[RemoteClass]`.
+    classes: Vec<(Entity, bool)>,
+}
+
+impl<'a> Visitor for RenamingCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            if let Some(class) = self.host.node_mapping().get(drtv).filter(|c| c.is::<ClassType>()) {
+                let remote = Attribute::find_metadata(&defn.attributes).iter().any(|m| m.name.0 == "RemoteClass");
+                self.classes.push((class, remote));
+            }
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::StringLiteral(literal) = exp.as_ref() {
+            self.string_literals.insert(literal.value.clone());
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
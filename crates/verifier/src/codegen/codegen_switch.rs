@@ -0,0 +1,109 @@
+use crate::ns::*;
+
+/// How a `switch` statement is lowered.
+#[derive(Clone, PartialEq, Debug)]
+pub enum SwitchLowering {
+    /// A `lookupswitch` over the discriminant minus `min`. `table[i]` is the
+    /// index of the case taken for the value `min + i`, or `None` for the
+    /// default case.
+    LookupSwitch {
+        min: i32,
+        table: Vec<Option<usize>>,
+    },
+    /// A lookup of the discriminant in a static object mapping each string
+    /// to its case index, followed by a `lookupswitch` over the index.
+    /// `keys[i]` is a string and the index of the first case matching it.
+    StringTable {
+        keys: Vec<(String, usize)>,
+    },
+    /// Chained `ifstricteq` comparisons in case order.
+    Chained,
+}
+
+/// Chooses between `lookupswitch` and chained comparisons for a `switch`
+/// statement.
+///
+/// `lookupswitch` is used only when every case is an integer constant and
+/// the values are dense enough that the jump table stays small.
+pub struct CodegenSwitch;
+
+impl CodegenSwitch {
+    /// Minimum number of cases for which a jump table pays off.
+    pub const MIN_CASES: usize = 4;
+
+    /// Minimum ratio of cases to jump table entries.
+    pub const MIN_DENSITY: f64 = 0.5;
+
+    /// Maximum number of jump table entries.
+    pub const MAX_TABLE_LENGTH: usize = 1024;
+
+    /// Returns the integer constant of a case label, as resolved by the verifier.
+    pub fn case_value(host: &Database, exp: &Rc<Expression>) -> Option<i32> {
+        let val = host.node_mapping().get(exp)?;
+        if !val.is::<NumberConstant>() {
+            return None;
+        }
+        let v = val.number_value().force_double();
+        if v.fract() != 0.0 || v < i32::MIN as f64 || v > i32::MAX as f64 {
+            return None;
+        }
+        Some(v as i32)
+    }
+
+    /// Returns the string constant of a case label, as resolved by the verifier.
+    pub fn string_case_value(host: &Database, exp: &Rc<Expression>) -> Option<String> {
+        host.node_mapping().get(exp).filter(|v| v.is::<StringConstant>()).map(|v| v.string_value())
+    }
+
+    /// Plans the lowering of a `switch` over a `String` from the values of
+    /// its case labels in order, where `None` is a non-constant case.
+    pub fn plan_strings(case_values: &[Option<String>]) -> SwitchLowering {
+        if case_values.len() < Self::MIN_CASES || case_values.iter().any(|v| v.is_none()) {
+            return SwitchLowering::Chained;
+        }
+        let mut keys: Vec<(String, usize)> = vec![];
+        for (i, v) in case_values.iter().enumerate() {
+            let v = v.as_ref().unwrap();
+            // The first matching case wins.
+            if !keys.iter().any(|(k, _)| k == v) {
+                keys.push((v.clone(), i));
+            }
+        }
+        SwitchLowering::StringTable { keys }
+    }
+
+    /// Plans the lowering of a `switch` from the values of its case labels
+    /// in order, where `None` is a non-constant case.
+    pub fn plan(case_values: &[Option<i32>]) -> SwitchLowering {
+        if case_values.len() < Self::MIN_CASES || case_values.iter().any(|v| v.is_none()) {
+            return SwitchLowering::Chained;
+        }
+        let values: Vec<i32> = case_values.iter().map(|v| v.unwrap()).collect();
+        let min = *values.iter().min().unwrap();
+        let max = *values.iter().max().unwrap();
+        let length = (max as i64 - min as i64 + 1) as usize;
+        if length > Self::MAX_TABLE_LENGTH || (values.len() as f64) / (length as f64) < Self::MIN_DENSITY {
+            return SwitchLowering::Chained;
+        }
+        let mut table = vec![None; length];
+        for (i, v) in values.iter().enumerate() {
+            // The first matching case wins.
+            let entry = &mut table[(*v as i64 - min as i64) as usize];
+            if entry.is_none() {
+                *entry = Some(i);
+            }
+        }
+        SwitchLowering::LookupSwitch { min, table }
+    }
+
+    /// Writes a `lookupswitch` instruction. Offsets are relative to the
+    /// start of the instruction; `case_offsets` must not be empty.
+    pub fn write_lookupswitch(writer: &mut AbcWriter, default_offset: i32, case_offsets: &[i32]) {
+        writer.write_u8(0x1B);
+        writer.write_s24(default_offset);
+        writer.write_u30(case_offsets.len() as u32 - 1);
+        for offset in case_offsets {
+            writer.write_s24(*offset);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use crate::ns::*;
+
+/// A direct self-recursive call (`f(...)` or `this.f(...)`) within the
+/// body of the function `f`.
+#[derive(Clone, Debug)]
+pub struct SelfCall {
+    /// The call expression.
+    pub call: Rc<Expression>,
+    /// Whether the call is the returned value outside of any `try`
+    /// statement, in which case it may be converted to a jump to the
+    /// start of the function body.
+    pub is_tail: bool,
+}
+
+/// Detects self-recursive tail calls. AVM2 has no tail call instruction,
+/// so these are lowered into loops that rebind the parameters and jump
+/// back to the start of the body.
+pub struct CodegenTailCalls;
+
+impl CodegenTailCalls {
+    /// Collects the self-recursive calls of the function `name`. Nested
+    /// functions are not visited.
+    pub fn self_calls(name: &str, common: &Rc<FunctionCommon>) -> Vec<SelfCall> {
+        let mut collector = SelfCallCollector {
+            name: name.to_owned(),
+            tail_calls: vec![],
+            try_depth: 0,
+            calls: vec![],
+        };
+        match &common.body {
+            Some(FunctionBody::Block(block)) => {
+                collector.visit_block(block);
+            },
+            Some(FunctionBody::Expression(exp)) => {
+                collector.mark_tail(exp);
+                collector.visit_expression(exp);
+            },
+            None => {},
+        }
+        collector.calls
+    }
+
+    /// Whether the function may be lowered into a loop: it calls itself
+    /// only in tail position, and at least once.
+    pub fn can_convert_to_loop(calls: &[SelfCall]) -> bool {
+        !calls.is_empty() && calls.iter().all(|c| c.is_tail)
+    }
+}
+
+struct SelfCallCollector {
+    name: String,
+    tail_calls: Vec<Rc<Expression>>,
+    try_depth: usize,
+    calls: Vec<SelfCall>,
+}
+
+impl SelfCallCollector {
+    fn mark_tail(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Paren(e) => self.mark_tail(&e.expression),
+            Expression::Conditional(e) => {
+                self.mark_tail(&e.consequent);
+                self.mark_tail(&e.alternative);
+            },
+            Expression::Call(_) => self.tail_calls.push(exp.clone()),
+            _ => {},
+        }
+    }
+
+    fn is_self_call(&self, call: &CallExpression) -> bool {
+        match call.base.as_ref() {
+            Expression::QualifiedIdentifier(id) => {
+                !id.attribute && id.qualifier.is_none() && matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if *name == self.name)
+            },
+            Expression::Member(member) => {
+                matches!(member.base.as_ref(), Expression::ThisLiteral(_))
+                    && member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name == self.name).unwrap_or(false)
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Visitor for SelfCallCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(_) => {},
+            Directive::ReturnStatement(retstmt) => {
+                if let (Some(exp), 0) = (retstmt.expression.as_ref(), self.try_depth) {
+                    self.mark_tail(exp);
+                }
+                walk_directive(self, drtv);
+            },
+            Directive::TryStatement(_) => {
+                self.try_depth += 1;
+                walk_directive(self, drtv);
+                self.try_depth -= 1;
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::Call(call) => {
+                if self.is_self_call(call) {
+                    let is_tail = self.tail_calls.iter().any(|e| Rc::ptr_eq(e, exp));
+                    self.calls.push(SelfCall { call: exp.clone(), is_tail });
+                }
+                walk_expression(self, exp);
+            },
+            _ => {
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
\ No newline at end of file
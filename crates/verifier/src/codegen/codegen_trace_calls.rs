@@ -0,0 +1,104 @@
+use crate::ns::*;
+
+/// Instruments method bodies with tracing calls when the `trace_calls`
+/// compiler option is set.
+///
+/// The entry of a traced method calls `enter()` and each of its exits
+/// calls `exit()` on the callback class, a class with static methods
+/// receiving the traced method name, such as `com.example.Foo.bar`:
+///
+/// ```as3
+/// public class CallTracer {
+///     public static function enter(method: String): void {}
+///     public static function exit(method: String): void {}
+/// }
+/// ```
+pub struct CodegenTraceCalls {
+    options: Option<TraceCallsOptions>,
+}
+
+impl CodegenTraceCalls {
+    pub const GETLEX: u8 = 0x60;
+    pub const PUSHSTRING: u8 = 0x2C;
+    pub const CALLPROPVOID: u8 = 0x4F;
+
+    pub const ENTER_METHOD: &'static str = "enter";
+    pub const EXIT_METHOD: &'static str = "exit";
+
+    pub fn new(compiler_options: &CompilerOptions) -> Self {
+        Self {
+            options: compiler_options.trace_calls.clone(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.options.is_some()
+    }
+
+    /// Returns the package name and the local name of the callback class.
+    pub fn callback_class(&self) -> Option<(String, String)> {
+        let name = &self.options.as_ref()?.callback_class;
+        Some(match name.rsplit_once('.') {
+            Some((pckg, local_name)) => (pckg.to_owned(), local_name.to_owned()),
+            None => ("".to_owned(), name.clone()),
+        })
+    }
+
+    /// Whether a method is traced: it must belong to one of the selected
+    /// packages or their subpackages, and must not be the callback class
+    /// itself.
+    pub fn is_traced(&self, method: &Entity) -> bool {
+        let Some(options) = self.options.as_ref() else {
+            return false;
+        };
+        let Some(class_or_package) = method.parent() else {
+            return false;
+        };
+        if class_or_package.to_string() == options.callback_class {
+            return false;
+        }
+        let mut pckg = Some(class_or_package);
+        while let Some(p) = pckg.as_ref().filter(|p| !p.is::<Package>()) {
+            pckg = p.parent();
+        }
+        let Some(pckg) = pckg else {
+            return false;
+        };
+        let pckg_name = pckg.fully_qualified_name_list().join(".");
+        options.packages.is_empty() || options.packages.iter().any(|p| pckg_name == *p || pckg_name.starts_with(&format!("{p}.")))
+    }
+
+    /// Returns the name passed to the callback class for a method.
+    pub fn trace_name(method: &Entity) -> String {
+        let local_name = method.name().local_name();
+        match method.parent() {
+            Some(parent) if !parent.is::<Package>() => format!("{parent}.{local_name}"),
+            Some(parent) if !parent.fully_qualified_name_list().is_empty() => format!("{}.{local_name}", parent.fully_qualified_name_list().join(".")),
+            _ => local_name,
+        }
+    }
+
+    /// Emits the call at the entry of a method. `callback_class` is the
+    /// multiname index of the callback class, `enter` the multiname index
+    /// of [`CodegenTraceCalls::ENTER_METHOD`] and `trace_name` the string
+    /// constant index of the [`CodegenTraceCalls::trace_name`].
+    pub fn emit_enter(code: &mut AbcWriter, callback_class: u32, enter: u32, trace_name: u32) {
+        Self::emit_call(code, callback_class, enter, trace_name);
+    }
+
+    /// Emits the call preceding a `returnvoid` or `returnvalue`
+    /// instruction. The stack is left unchanged.
+    pub fn emit_exit(code: &mut AbcWriter, callback_class: u32, exit: u32, trace_name: u32) {
+        Self::emit_call(code, callback_class, exit, trace_name);
+    }
+
+    fn emit_call(code: &mut AbcWriter, callback_class: u32, method: u32, trace_name: u32) {
+        code.write_u8(Self::GETLEX);
+        code.write_u30(callback_class);
+        code.write_u8(Self::PUSHSTRING);
+        code.write_u30(trace_name);
+        code.write_u8(Self::CALLPROPVOID);
+        code.write_u30(method);
+        code.write_u30(1);
+    }
+}
\ No newline at end of file
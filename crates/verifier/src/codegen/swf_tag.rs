@@ -0,0 +1,18 @@
+/// Record headers of SWF tags.
+pub struct SwfTag;
+
+impl SwfTag {
+    /// Prefixes a tag body with its record header, in the long form if
+    /// the body has 63 bytes or more.
+    pub fn record(code: u16, body: &[u8]) -> Vec<u8> {
+        let mut r = vec![];
+        if body.len() < 0x3F {
+            r.extend_from_slice(&((code << 6) | body.len() as u16).to_le_bytes());
+        } else {
+            r.extend_from_slice(&((code << 6) | 0x3F).to_le_bytes());
+            r.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        }
+        r.extend_from_slice(body);
+        r
+    }
+}
\ No newline at end of file
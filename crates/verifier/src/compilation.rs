@@ -0,0 +1,53 @@
+mod source_provider;
+pub use source_provider::*;
+
+mod source_text;
+pub use source_text::*;
+
+mod definition_provider;
+pub use definition_provider::*;
+
+mod artifact;
+pub use artifact::*;
+
+mod compilation;
+pub use compilation::*;
+
+mod content_hash;
+pub use content_hash::*;
+
+mod build_manifest;
+pub use build_manifest::*;
+
+mod database_snapshot;
+pub use database_snapshot::*;
+
+mod speculative_verification;
+pub use speculative_verification::*;
+
+mod compile_server;
+pub use compile_server::*;
+
+mod crash_report;
+pub use crash_report::*;
+
+mod workspace_session;
+pub use workspace_session::*;
+
+mod snippet;
+pub use snippet::*;
+
+mod source_edit_reparse;
+pub use source_edit_reparse::*;
+
+mod legacy_syntax;
+pub use legacy_syntax::*;
+
+mod partial_input_recovery;
+pub use partial_input_recovery::*;
+
+mod parse_cache;
+pub use parse_cache::*;
+
+mod diagnostic_baseline;
+pub use diagnostic_baseline::*;
\ No newline at end of file
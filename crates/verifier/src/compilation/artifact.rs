@@ -0,0 +1,23 @@
+/// A compilation output held in memory.
+#[derive(Clone, Debug)]
+pub struct Artifact {
+    /// Output path relative to the output directory.
+    pub path: String,
+    /// Such as `application/json`.
+    pub media_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Artifact {
+    pub fn new(path: &str, media_type: &str, bytes: Vec<u8>) -> Self {
+        Self {
+            path: path.to_owned(),
+            media_type: media_type.to_owned(),
+            bytes,
+        }
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+}
\ No newline at end of file
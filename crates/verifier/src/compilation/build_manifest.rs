@@ -0,0 +1,69 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Content hashes of the inputs and outputs of a build, so that external
+/// build caches can decide whether to reuse outputs without running the
+/// compiler.
+///
+/// ```ignore
+/// let output = Compilation::run(&host, &compiler_options, &sources);
+/// let mut manifest = BuildManifest::new(&sources, &output.artifacts);
+/// manifest.add_input("whack.toml", &std::fs::read("whack.toml")?);
+/// std::fs::write(BuildManifest::FILE_NAME, manifest.format_json())?;
+/// ```
+///
+/// Inputs other than sources, such as SWCs and configuration files, are
+/// added by the caller. Hashes are SHA-256 digests (see `ContentHash`).
+#[derive(Clone, Default)]
+pub struct BuildManifest {
+    pub inputs: BTreeMap<String, String>,
+    pub outputs: BTreeMap<String, String>,
+}
+
+impl BuildManifest {
+    pub const FILE_NAME: &'static str = "whack-build-manifest.json";
+
+    pub fn new(sources: &dyn SourceProvider, artifacts: &[Artifact]) -> Self {
+        let mut manifest = Self::default();
+        for path in sources.source_files() {
+            if let Some(text) = sources.read_to_string(&path) {
+                manifest.add_input(&path, text.as_bytes());
+            }
+        }
+        for artifact in artifacts.iter() {
+            manifest.add_output(&artifact.path, &artifact.bytes);
+        }
+        manifest
+    }
+
+    pub fn add_input(&mut self, path: &str, bytes: &[u8]) {
+        self.inputs.insert(path.to_owned(), ContentHash::sha256(bytes));
+    }
+
+    pub fn add_output(&mut self, path: &str, bytes: &[u8]) {
+        self.outputs.insert(path.to_owned(), ContentHash::sha256(bytes));
+    }
+
+    /// A single hash over every input, changing whenever any input
+    /// is added, removed or modified.
+    pub fn input_key(&self) -> String {
+        let mut joined = String::new();
+        for (path, hash) in self.inputs.iter() {
+            joined.push_str(&format!("{path}\0{hash}\n"));
+        }
+        ContentHash::sha256(joined.as_bytes())
+    }
+
+    pub fn format_json(&self) -> String {
+        let entries = |map: &BTreeMap<String, String>| map.iter()
+            .map(|(path, hash)| format!("{}:{}", json_string(path), json_string(&format!("sha256:{hash}"))))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{{\"version\":1,\"inputKey\":{},\"inputs\":{{{}}},\"outputs\":{{{}}}}}",
+            json_string(&format!("sha256:{}", self.input_key())),
+            entries(&self.inputs),
+            entries(&self.outputs),
+        )
+    }
+}
\ No newline at end of file
@@ -0,0 +1,284 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Result of a `Compilation`.
+pub struct CompilationOutput {
+    pub compilation_units: Vec<Rc<CompilationUnit>>,
+    pub programs: Vec<Rc<Program>>,
+    /// Whether an error was found.
+    pub invalidated: bool,
+    pub artifacts: Vec<Artifact>,
+    /// Estimated limits of the methods of the source programs, as
+    /// computed by `CodegenMethodLimits`, keyed by file path.
+    pub method_limits: BTreeMap<String, Vec<AbcMethodLimits>>,
+    /// Time spent per phase and per file, parsing included.
+    pub timings: CompilerTimings,
+}
+
+impl CompilationOutput {
+    /// Diagnostics of every compilation unit formatted in English.
+    pub fn diagnostics(&self) -> Vec<String> {
+        let mut r = vec![];
+        for cu in self.compilation_units.iter() {
+            cu.sort_diagnostics();
+            for diagnostic in cu.nested_diagnostics().iter() {
+                r.push(WhackDiagnostic(diagnostic).format_english());
+            }
+        }
+        r
+    }
+}
+
+/// Runs the pipeline over sources from a `SourceProvider`, producing
+/// in-memory artifacts, so that playgrounds and test harnesses need no
+/// filesystem.
+///
+/// ```ignore
+/// let mut sources = MemorySourceProvider::new();
+/// sources.insert("Main.as", "trace(10);");
+/// let output = Compilation::run(&host, &compiler_options, &sources);
+/// for message in output.diagnostics() {
+///     println!("{message}");
+/// }
+/// ```
+///
+/// The core library selected by `compiler_options.core_library` is
+/// verified along with the sources, and resource bundles are read from
+/// the `SourceProvider` as well. The artifacts are the classes of
+/// referenced resource bundles, followed by the dynamic access audit,
+/// the load manifest of `CodegenModuleSplit`, the embedded bitmap report,
+/// the test manifest and runner of `TestDiscovery` and a `BuildManifest`,
+/// each if its compiler option is set; ABC artifacts will follow once
+/// codegen emits whole programs.
+pub struct Compilation;
+
+impl Compilation {
+    pub fn run(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider) -> CompilationOutput {
+        Self::run_with_providers(host, compiler_options, sources, &[])
+    }
+
+    /// Runs the pipeline, materializing the definitions of `providers`
+    /// that the sources refer to (see `DefinitionMaterializer`).
+    pub fn run_with_providers(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider, providers: &[Rc<dyn DefinitionProvider>]) -> CompilationOutput {
+        Self::run_with_events(host, compiler_options, sources, providers, None)
+    }
+
+    /// Runs the pipeline, emitting progress events to `subscriber`.
+    pub fn run_with_events(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider, providers: &[Rc<dyn DefinitionProvider>], subscriber: Option<Rc<dyn CompilerEventSubscriber>>) -> CompilationOutput {
+        Self::run_with_cache(host, compiler_options, sources, providers, subscriber, &ParseCache::new())
+    }
+
+    /// Runs the pipeline, reusing the programs of `cache` parsed by
+    /// previous compilations from unchanged files.
+    pub fn run_with_cache(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider, providers: &[Rc<dyn DefinitionProvider>], subscriber: Option<Rc<dyn CompilerEventSubscriber>>, cache: &ParseCache) -> CompilationOutput {
+        let (mut compilation_units, mut programs) = CoreLibrary::parse_with_cache(compiler_options, cache);
+        let mut mxml_list = vec![];
+        let mut invalidated = compilation_units.iter().any(|cu| cu.invalidated());
+
+        if let Some(subscriber) = subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::PhaseStart(CompilerPhase::Parsing));
+        }
+        let mut timings = CompilerTimings::new();
+        let parsing_start = Stopwatch::start();
+        let first_source_program = programs.len();
+        for path in sources.source_files() {
+            let file_start = Stopwatch::start();
+            let Some(text) = sources.read_to_string(&path) else {
+                continue;
+            };
+            if let Some(subscriber) = subscriber.as_ref() {
+                subscriber.on_event(&CompilerEvent::FileEnqueued(path.clone()));
+            }
+            let is_mxml = path.ends_with(".mxml");
+            let cu = if is_mxml {
+                let cu = CompilationUnit::new(Some(path.clone()), text);
+                cu.set_compiler_options(Some(compiler_options.clone()));
+                mxml_list.push(ParserFacade(&cu, ParserOptions::default()).parse_mxml());
+                cu
+            } else {
+                let (cu, program) = cache.parse(&path, &text, compiler_options, || {
+                    let (text, legacy_commas) = if compiler_options.legacy_syntax {
+                        LegacySyntax::rewrite(&text)
+                    } else {
+                        (text.clone(), vec![])
+                    };
+                    let (text, recoveries) = if compiler_options.verify_with_syntax_errors {
+                        PartialInputRecovery::recover(&text)
+                    } else {
+                        (text, vec![])
+                    };
+                    let cu = CompilationUnit::new(Some(path.clone()), text);
+                    cu.set_compiler_options(Some(compiler_options.clone()));
+                    let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+                    LegacySyntax::report(&cu, &legacy_commas);
+                    PartialInputRecovery::report(&cu, &recoveries);
+                    (cu, program)
+                });
+                programs.push(program);
+                cu
+            };
+            invalidated = invalidated || cu.invalidated();
+            compilation_units.push(cu);
+            timings.add_file(&path, file_start.elapsed());
+        }
+        timings.add_phase(CompilerPhase::Parsing, parsing_start.elapsed());
+        if let Some(subscriber) = subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::PhaseEnd(CompilerPhase::Parsing, parsing_start.elapsed()));
+        }
+
+        DefinitionMaterializer::report(&DefinitionMaterializer::source_conflicts(&programs[first_source_program..]));
+        if !providers.is_empty() {
+            let mut materializer = DefinitionMaterializer::new(providers);
+            let (provided_compilation_units, provided_programs) = materializer.materialize_referenced(compiler_options, &programs);
+            materializer.report_conflicts();
+            invalidated = invalidated || provided_compilation_units.iter().any(|cu| cu.invalidated());
+            compilation_units.extend(provided_compilation_units);
+            programs.extend(provided_programs);
+        }
+
+        let emit = &compiler_options.emit;
+        let emitted_programs: Vec<Rc<Program>> = programs[first_source_program..].iter()
+            .filter(|program| emit.selects(&program.location.compilation_unit().file_path().unwrap_or_default()))
+            .cloned()
+            .collect();
+        let mut artifacts = vec![];
+        if emit.includes(IntermediateForm::Ast) {
+            // Exported before verification, so no node is mapped
+            Self::emit_programs(host, compiler_options, &emitted_programs, IntermediateForm::Ast, &mut artifacts);
+        }
+        if emit.includes(IntermediateForm::Cfg) {
+            Self::emit_programs(host, compiler_options, &emitted_programs, IntermediateForm::Cfg, &mut artifacts);
+        }
+
+        let embedded_files = Rc::new(EmbeddedFiles::load_from(sources, &programs, &compiler_options.source_path));
+        if !invalidated || compiler_options.verify_with_syntax_errors {
+            let mut verifier = Verifier::new(host);
+            verifier.set_event_subscriber(subscriber.clone());
+            if !compiler_options.locales.is_empty() {
+                let mut registry = ResourceBundleRegistry::load_from(sources, &compiler_options.locales, &compiler_options.locale_source_path);
+                if let Some(pseudo_locale) = compiler_options.pseudo_locale.as_ref() {
+                    PseudoLocale::add_to(&mut registry, pseudo_locale, &compiler_options.locales[0]);
+                }
+                verifier.set_resource_bundles(Rc::new(registry));
+            }
+            verifier.set_embedded_files(embedded_files.clone());
+            if compiler_options.warnings.directory_config {
+                verifier.set_warning_config(Rc::new(WarningConfig::read_from(sources, &programs, &compiler_options.source_path)));
+            }
+            verifier.verify_programs(compiler_options, programs.clone(), mxml_list);
+            invalidated = invalidated || verifier.invalidated();
+            timings.extend(verifier.timings());
+            if let Some(report) = verifier.dynamic_access_audit_report() {
+                artifacts.push(Artifact::new(DynamicAccessAuditReport::FILE_NAME, "text/plain", report.format_human().into_bytes()));
+            }
+
+            if !compiler_options.swf_modules.is_empty() && !invalidated {
+                let split = CodegenModuleSplit::new(host, &programs[first_source_program..], verifier.reflected_definitions(), compiler_options);
+                match split.manifest_json() {
+                    Ok(manifest) => artifacts.push(Artifact::new(CodegenModuleSplit::MANIFEST_FILE_NAME, "application/json", manifest.into_bytes())),
+                    Err(cycle) => if let Some(program) = programs[first_source_program..].first() {
+                        let location = &program.location;
+                        location.compilation_unit().add_diagnostic(WhackDiagnostic::new_verify_error(location, WhackDiagnosticKind::SwfModuleDependencyCycle, diagarg![cycle.join(", ")]));
+                        invalidated = true;
+                    },
+                }
+            }
+
+            let registry = verifier.resource_bundles();
+            let mut bundle_names: Vec<&String> = verifier.referenced_resource_bundles().iter().collect();
+            bundle_names.sort();
+            for locale in registry.locales.iter() {
+                for name in bundle_names.iter() {
+                    if let Some(source) = registry.bundle_class_source(locale, name) {
+                        let path = format!("{}.as", ResourceBundleRegistry::bundle_class_name(locale, name));
+                        artifacts.push(Artifact::new(&path, "text/x-actionscript", source.into_bytes()));
+                    }
+                }
+            }
+
+            if emit.includes(IntermediateForm::TypedAst) {
+                Self::emit_programs(host, compiler_options, &emitted_programs, IntermediateForm::TypedAst, &mut artifacts);
+            }
+            if emit.includes(IntermediateForm::AbcAsm) {
+                // Inlined methods may be defined by any program
+                let inlining = CodegenInlining::collect(host, &programs);
+                Self::emit_abc_listings(host, compiler_options, &emitted_programs, &inlining, &mut artifacts);
+            }
+        }
+
+        if compiler_options.embedded_bitmap_report {
+            let assets = CodegenBitmapAssets::collect(&programs[first_source_program..], &embedded_files, &compiler_options.source_path, compiler_options.optimize_embedded_bitmaps);
+            artifacts.push(Artifact::new(CodegenBitmapAssets::REPORT_FILE_NAME, "text/plain", assets.format_report().into_bytes()));
+        }
+
+        if let Some(options) = compiler_options.test_discovery.as_ref() {
+            let manifest = TestDiscovery::discover(&programs[first_source_program..]);
+            invalidated = invalidated || programs[first_source_program..].iter().any(|program| program.location.compilation_unit().invalidated());
+            artifacts.push(Artifact::new(TestManifest::FILE_NAME, "application/json", manifest.to_json().into_bytes()));
+            if let Some(class_name) = options.runner_class.as_ref() {
+                artifacts.push(Artifact::new(&format!("{class_name}.as"), "text/x-actionscript", manifest.generate_runner(class_name).into_bytes()));
+            }
+        }
+
+        let mut method_limits = BTreeMap::new();
+        for program in programs[first_source_program..].iter() {
+            let file_path = program.location.compilation_unit().file_path().unwrap_or_default();
+            let estimates = CodegenMethodLimits::estimate(program);
+            if let Some(thresholds) = compiler_options.method_limit_thresholds.as_ref() {
+                for (location, limits) in estimates.iter() {
+                    for (limit, value, threshold) in thresholds.exceeded(limits) {
+                        let description = match limit {
+                            "max-stack" => "operand stack slots",
+                            "max-scope-depth" => "scope stack entries",
+                            _ => "registers",
+                        };
+                        location.compilation_unit().add_diagnostic(WhackDiagnostic::new_warning(location, WhackDiagnosticKind::MethodLimitExceeded, diagarg![limits.name.clone(), value.to_string(), description.to_owned(), threshold.to_string()]));
+                        if let Some(subscriber) = subscriber.as_ref() {
+                            subscriber.on_event(&CompilerEvent::MethodLimitExceeded {
+                                artifact: file_path.clone(),
+                                method: limits.name.clone(),
+                                limit,
+                                value,
+                                threshold,
+                            });
+                        }
+                    }
+                }
+            }
+            method_limits.insert(file_path, estimates.into_iter().map(|(_, limits)| limits).collect());
+        }
+
+        if compiler_options.build_manifest && !invalidated {
+            let manifest = BuildManifest::new(sources, &artifacts);
+            artifacts.push(Artifact::new(BuildManifest::FILE_NAME, "application/json", manifest.format_json().into_bytes()));
+        }
+
+        if let Some(subscriber) = subscriber.as_ref() {
+            emit_diagnostic_counts(subscriber.as_ref(), &compilation_units);
+        }
+
+        CompilationOutput { compilation_units, programs, invalidated, artifacts, method_limits, timings }
+    }
+
+    /// Exports each program as an artifact of the given intermediate form.
+    fn emit_programs(host: &Rc<Database>, compiler_options: &CompilerOptions, programs: &[Rc<Program>], form: IntermediateForm, artifacts: &mut Vec<Artifact>) {
+        for program in programs.iter() {
+            let file_path = program.location.compilation_unit().file_path().unwrap_or_default();
+            let path = compiler_options.emit.artifact_path(&compiler_options.source_path, &file_path, form);
+            let artifact = match form {
+                IntermediateForm::Cfg => Artifact::new(&path, "text/vnd.graphviz", FlowGraph::format_program_dot(program).into_bytes()),
+                _ => Artifact::new(&path, "application/json", TypedAstJsonExporter::export(host, std::slice::from_ref(program)).into_bytes()),
+            };
+            artifacts.push(artifact);
+        }
+    }
+
+    /// Exports the `CodegenListing` of each program as an artifact.
+    fn emit_abc_listings(host: &Rc<Database>, compiler_options: &CompilerOptions, programs: &[Rc<Program>], inlining: &CodegenInlining, artifacts: &mut Vec<Artifact>) {
+        for program in programs.iter() {
+            let file_path = program.location.compilation_unit().file_path().unwrap_or_default();
+            let path = compiler_options.emit.artifact_path(&compiler_options.source_path, &file_path, IntermediateForm::AbcAsm);
+            artifacts.push(Artifact::new(&path, "text/plain", CodegenListing::format(host, program, inlining).into_bytes()));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,212 @@
+use crate::ns::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Compiles sources on behalf of a long-running process, such as
+/// `whack serve`, so that build tools avoid restarting the compiler on
+/// every invocation.
+///
+/// Requests are handled one line at a time by `handle_request()`:
+///
+/// * `compile PATHS` compiles the sources under `PATHS`, a list
+///   separated as the `PATH` environment variable.
+/// * `query QUERY` answers a `SemanticQuery` over the last compilation
+///   with a JSON array of rows.
+/// * `invalidate` drops the retained result.
+/// * `shutdown` ends the session.
+///
+/// Each response is a single JSON line with the `invalidated`, `cached`
+/// and `diagnostics` fields, diagnostics being objects with the `file`,
+/// `severity`, `id`, `message`, `line` and `column` fields.
+///
+/// A panic during compilation answers with an `internalError` field and
+/// keeps a `CrashReport`, taken by `take_crash_report()`.
+///
+/// The result of the last compilation is retained and returned as is
+/// when the same sources are requested again unchanged. Otherwise the
+/// programs of the core library and of the unchanged files are reused
+/// through a `ParseCache`, and only the changed files are parsed again.
+/// The `Database` itself is created anew for every compilation, as it
+/// cannot retract definitions yet (see "Incremental verification" in
+/// `todo/verifier.md`).
+pub struct CompileServer {
+    compiler_options: Rc<CompilerOptions>,
+    parse_cache: ParseCache,
+    last: Option<(u64, CompileResponse)>,
+    event_subscriber: Option<Rc<dyn CompilerEventSubscriber>>,
+    crash_report: Option<CrashReport>,
+    /// The last compilation, indexed for `query` on the first request.
+    last_compilation: Option<(Rc<Database>, Rc<CompilationOutput>)>,
+    query_index: Option<Rc<SemanticQueryIndex>>,
+}
+
+#[derive(Clone)]
+pub struct CompileResponse {
+    pub invalidated: bool,
+    /// Whether the response was retained from a previous request.
+    pub cached: bool,
+    /// Diagnostics as JSON objects.
+    pub diagnostics: Vec<String>,
+    /// Message of the internal error the compilation ended with.
+    pub internal_error: Option<String>,
+}
+
+impl CompileResponse {
+    pub fn to_json(&self) -> String {
+        let internal_error = self.internal_error.as_ref().map(|m| format!(",\"internalError\":{}", json_string(m))).unwrap_or_default();
+        format!(
+            "{{\"invalidated\":{},\"cached\":{},\"diagnostics\":[{}]{internal_error}}}",
+            self.invalidated,
+            self.cached,
+            self.diagnostics.join(","),
+        )
+    }
+}
+
+impl CompileServer {
+    pub fn new(compiler_options: &Rc<CompilerOptions>) -> Self {
+        Self {
+            compiler_options: compiler_options.clone(),
+            parse_cache: ParseCache::new(),
+            last: None,
+            event_subscriber: None,
+            crash_report: None,
+            last_compilation: None,
+            query_index: None,
+        }
+    }
+
+    /// Sets the subscriber receiving the progress of compilations and
+    /// whether retained results are reused.
+    pub fn set_event_subscriber(&mut self, subscriber: Option<Rc<dyn CompilerEventSubscriber>>) {
+        self.event_subscriber = subscriber;
+    }
+
+    /// Takes the report of the last compilation that panicked.
+    pub fn take_crash_report(&mut self) -> Option<CrashReport> {
+        self.crash_report.take()
+    }
+
+    /// Drops the retained result and parsed files.
+    pub fn invalidate(&mut self) {
+        self.parse_cache.clear();
+        self.last = None;
+        self.last_compilation = None;
+        self.query_index = None;
+    }
+
+    /// Indexes the last compilation for `SemanticQuery`, or returns
+    /// `None` if nothing was compiled.
+    pub fn query_index(&mut self) -> Option<Rc<SemanticQueryIndex>> {
+        if self.query_index.is_none() {
+            let (host, output) = self.last_compilation.as_ref()?;
+            self.query_index = Some(Rc::new(SemanticQueryIndex::build(host, output)));
+        }
+        self.query_index.clone()
+    }
+
+    pub fn compile(&mut self, sources: &dyn SourceProvider) -> CompileResponse {
+        let fingerprint = Self::fingerprint(sources);
+        let key = format!("{fingerprint:016x}");
+        if let Some((_, response)) = self.last.as_ref().filter(|(f, _)| *f == fingerprint) {
+            if let Some(subscriber) = self.event_subscriber.as_ref() {
+                subscriber.on_event(&CompilerEvent::CacheHit(key));
+            }
+            return CompileResponse { cached: true, ..response.clone() };
+        }
+        if let Some(subscriber) = self.event_subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::CacheMiss(key));
+        }
+        let host = Rc::new(Database::new(Default::default()));
+        let tracker = Rc::new(CrashTracker::new(self.event_subscriber.clone()));
+        let subscriber: Rc<dyn CompilerEventSubscriber> = tracker.clone();
+        let output = match CrashReport::catch(&tracker, &self.compiler_options, || Compilation::run_with_cache(&host, &self.compiler_options, sources, &[], Some(subscriber), &self.parse_cache)) {
+            Ok(output) => output,
+            Err(mut report) => {
+                report.attach_source(sources);
+                let response = CompileResponse {
+                    invalidated: true,
+                    cached: false,
+                    diagnostics: vec![],
+                    internal_error: Some(report.message.clone()),
+                };
+                self.crash_report = Some(report);
+                self.invalidate();
+                return response;
+            },
+        };
+        let response = CompileResponse {
+            invalidated: output.invalidated,
+            cached: false,
+            diagnostics: Self::diagnostics_json(&output),
+            internal_error: None,
+        };
+        self.last = Some((fingerprint, response.clone()));
+        self.last_compilation = Some((host, Rc::new(output)));
+        self.query_index = None;
+        response
+    }
+
+    /// Handles a request line, returning the response line, or `None`
+    /// on `shutdown`.
+    #[cfg(feature = "fs")]
+    pub fn handle_request(&mut self, line: &str) -> Option<String> {
+        let line = line.trim();
+        let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "compile" => {
+                let source_path = std::env::split_paths(argument.trim())
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                let sources = FileSystemSourceProvider { source_path };
+                Some(self.compile(&sources).to_json())
+            },
+            "query" => {
+                let Some(index) = self.query_index() else {
+                    return Some(format!("{{\"error\":{}}}", json_string("Nothing was compiled yet")));
+                };
+                Some(match index.run_json(argument) {
+                    Ok(rows) => format!("{{\"rows\":{rows}}}"),
+                    Err(error) => format!("{{\"error\":{}}}", json_string(&error.message())),
+                })
+            },
+            "invalidate" => {
+                self.invalidate();
+                Some("{}".into())
+            },
+            "shutdown" => None,
+            _ => Some(format!("{{\"error\":{}}}", json_string(&format!("Unknown request '{command}'")))),
+        }
+    }
+
+    fn fingerprint(sources: &dyn SourceProvider) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for path in sources.source_files() {
+            path.hash(&mut hasher);
+            sources.read_to_string(&path).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn diagnostics_json(output: &CompilationOutput) -> Vec<String> {
+        let mut list = vec![];
+        for cu in output.compilation_units.iter() {
+            cu.sort_diagnostics();
+            for diagnostic in cu.nested_diagnostics().iter() {
+                let d = WhackDiagnostic(diagnostic);
+                let location = diagnostic.location();
+                list.push(format!(
+                    "{{\"file\":{},\"severity\":{},\"id\":{},\"message\":{},\"line\":{},\"column\":{}}}",
+                    json_string(&location.compilation_unit().file_path().unwrap_or_default()),
+                    json_string(if diagnostic.is_warning() { "warning" } else { "error" }),
+                    d.id(),
+                    json_string(&d.format_message_english()),
+                    location.first_line_number(),
+                    location.first_column() + 1,
+                ));
+            }
+        }
+        list
+    }
+}
\ No newline at end of file
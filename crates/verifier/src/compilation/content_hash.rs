@@ -0,0 +1,11 @@
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digests of compilation inputs and outputs.
+pub struct ContentHash;
+
+impl ContentHash {
+    /// Returns the SHA-256 digest of `bytes` as lowercase hexadecimal.
+    pub fn sha256(bytes: &[u8]) -> String {
+        Sha256::digest(bytes).iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
\ No newline at end of file
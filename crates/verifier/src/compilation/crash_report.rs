@@ -0,0 +1,197 @@
+use crate::ns::*;
+use std::cell::{Cell, RefCell};
+use std::panic::AssertUnwindSafe;
+
+/// Records the phase and file a compilation is at, for `CrashReport`,
+/// forwarding every event to another subscriber.
+pub struct CrashTracker {
+    inner: Option<Rc<dyn CompilerEventSubscriber>>,
+    phase: Cell<Option<CompilerPhase>>,
+    file_path: RefCell<Option<String>>,
+    files_enqueued: Cell<usize>,
+    units_verified: Cell<usize>,
+    definitions_verified: Cell<usize>,
+}
+
+impl CrashTracker {
+    pub fn new(inner: Option<Rc<dyn CompilerEventSubscriber>>) -> Self {
+        Self {
+            inner,
+            phase: Cell::new(None),
+            file_path: RefCell::new(None),
+            files_enqueued: Cell::new(0),
+            units_verified: Cell::new(0),
+            definitions_verified: Cell::new(0),
+        }
+    }
+}
+
+impl CompilerEventSubscriber for CrashTracker {
+    fn on_event(&self, event: &CompilerEvent) {
+        match event {
+            CompilerEvent::PhaseStart(phase) => {
+                self.phase.set(Some(*phase));
+            },
+            CompilerEvent::FileEnqueued(file_path) => {
+                self.file_path.replace(Some(file_path.clone()));
+                self.files_enqueued.set(self.files_enqueued.get() + 1);
+            },
+            CompilerEvent::UnitStarted(file_path) => {
+                self.file_path.replace(Some(file_path.clone()));
+            },
+            CompilerEvent::UnitVerified { .. } => {
+                self.units_verified.set(self.units_verified.get() + 1);
+            },
+            CompilerEvent::DefinitionVerified { .. } => {
+                self.definitions_verified.set(self.definitions_verified.get() + 1);
+            },
+            _ => {},
+        }
+        if let Some(inner) = self.inner.as_ref() {
+            inner.on_event(event);
+        }
+    }
+}
+
+/// Reproduction details of an internal error, written as a bundle that
+/// users attach to issues.
+///
+/// ```ignore
+/// let tracker = Rc::new(CrashTracker::new(None));
+/// match CrashReport::catch(&tracker, &compiler_options, || Compilation::run_with_events(&host, &compiler_options, &sources, &[], Some(tracker.clone()))) {
+///     Ok(output) => {},
+///     Err(mut report) => {
+///         report.attach_source(&sources);
+///         report.redact();
+///         let path = report.write_bundle(Path::new("."))?;
+///     },
+/// }
+/// ```
+///
+/// The file is the last one read or, during verification, the unit
+/// being verified.
+#[derive(Clone, Debug)]
+pub struct CrashReport {
+    pub message: String,
+    pub backtrace: String,
+    pub phase: Option<String>,
+    pub file_path: Option<String>,
+    /// Text of `file_path`, if attached.
+    pub source: Option<String>,
+    pub compiler_options: Vec<(String, String)>,
+    /// Counts of the files read, units verified and definitions verified
+    /// before the error.
+    pub stats: Vec<(String, usize)>,
+}
+
+thread_local! {
+    static PANIC: RefCell<Option<(String, String)>> = RefCell::new(None);
+}
+
+impl CrashReport {
+    /// Runs a compilation, turning a panic into a report. The panic hook
+    /// is replaced while it runs.
+    pub fn catch<T>(tracker: &CrashTracker, compiler_options: &CompilerOptions, f: impl FnOnce() -> T) -> Result<T, CrashReport> {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|info| {
+            let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+            PANIC.with(|p| p.replace(Some((info.to_string(), backtrace))));
+        }));
+        let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+        std::panic::set_hook(previous_hook);
+
+        result.map_err(|_| {
+            let (message, backtrace) = PANIC.with(|p| p.take()).unwrap_or_default();
+            CrashReport {
+                message,
+                backtrace,
+                phase: tracker.phase.get().map(|phase| phase.name().to_owned()),
+                file_path: tracker.file_path.borrow().clone(),
+                source: None,
+                compiler_options: Self::describe_options(compiler_options),
+                stats: vec![
+                    ("files-enqueued".into(), tracker.files_enqueued.get()),
+                    ("units-verified".into(), tracker.units_verified.get()),
+                    ("definitions-verified".into(), tracker.definitions_verified.get()),
+                ],
+            }
+        })
+    }
+
+    pub fn attach_source(&mut self, sources: &dyn SourceProvider) {
+        self.source = self.file_path.as_ref().and_then(|path| sources.read_to_string(path));
+    }
+
+    /// Removes the source text and reduces paths to their file names.
+    pub fn redact(&mut self) {
+        self.source = None;
+        self.file_path = self.file_path.as_ref().map(|path| Self::file_name(path).to_owned());
+        for (name, value) in self.compiler_options.iter_mut() {
+            if name == "source-path" {
+                *value = value.split(", ").map(Self::file_name).collect::<Vec<_>>().join(", ");
+            }
+        }
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = format!("Internal error: {}\n", self.message);
+        r.push_str(&format!("Phase: {}\n", self.phase.as_deref().unwrap_or("<unknown>")));
+        r.push_str(&format!("File: {}\n", self.file_path.as_deref().unwrap_or("<unknown>")));
+        r.push_str(&format!("Compiler version: {}\n", env!("CARGO_PKG_VERSION")));
+        r.push_str("\nCompiler options:\n");
+        for (name, value) in self.compiler_options.iter() {
+            r.push_str(&format!("  {name}: {value}\n"));
+        }
+        r.push_str("\nProgress:\n");
+        for (name, count) in self.stats.iter() {
+            r.push_str(&format!("  {name}: {count}\n"));
+        }
+        r.push_str(&format!("\nBacktrace:\n{}\n", self.backtrace));
+        r
+    }
+
+    /// Writes `report.txt` and, if attached, the source file into a new
+    /// `whack-crash-*` directory under `dir`, returning its path. The name
+    /// is suffixed with a counter if a bundle of the same second exists.
+    #[cfg(feature = "fs")]
+    pub fn write_bundle(&self, dir: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+        let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        std::fs::create_dir_all(dir)?;
+        let mut suffix = 0;
+        let bundle = loop {
+            let name = if suffix == 0 { format!("whack-crash-{timestamp}") } else { format!("whack-crash-{timestamp}-{suffix}") };
+            let bundle = dir.join(name);
+            match std::fs::create_dir(&bundle) {
+                Ok(()) => break bundle,
+                Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => suffix += 1,
+                Err(error) => return Err(error),
+            }
+        };
+        std::fs::write(bundle.join("report.txt"), self.format_human())?;
+        if let (Some(path), Some(source)) = (self.file_path.as_ref(), self.source.as_ref()) {
+            std::fs::write(bundle.join(Self::file_name(path)), source)?;
+        }
+        Ok(bundle)
+    }
+
+    fn describe_options(options: &CompilerOptions) -> Vec<(String, String)> {
+        vec![
+            ("source-path".into(), options.source_path.join(", ")),
+            ("syntax-only".into(), options.syntax_only.to_string()),
+            ("verify-with-syntax-errors".into(), options.verify_with_syntax_errors.to_string()),
+            ("debug".into(), options.debug.to_string()),
+            ("coverage".into(), options.coverage.to_string()),
+            ("minify-identifiers".into(), options.minify_identifiers.to_string()),
+            ("swf-modules".into(), options.swf_modules.iter().map(|m| m.name.clone()).collect::<Vec<_>>().join(", ")),
+            ("frame-script".into(), options.frame_script.is_some().to_string()),
+            ("operator-overloading".into(), options.operator_overloading.to_string()),
+            ("const-parameters".into(), options.const_parameters.to_string()),
+            ("locales".into(), options.locales.join(", ")),
+            ("target-runtime-version".into(), options.target_runtime_version.as_ref().map(|v| v.to_string()).unwrap_or_default()),
+        ]
+    }
+
+    fn file_name(path: &str) -> &str {
+        path.rsplit(['/', '\\']).next().unwrap_or(path)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// The `Database` and programs of a completed verification, only read
+/// once published.
+pub struct DatabaseSnapshot {
+    pub generation: u64,
+    pub host: Rc<Database>,
+    pub output: CompilationOutput,
+    /// Diagnostics of each compilation unit when the snapshot was
+    /// published, keyed by file path.
+    diagnostics: BTreeMap<String, Vec<Diagnostic>>,
+}
+
+impl DatabaseSnapshot {
+    /// Diagnostics of a file as of this snapshot. Compilation units shared
+    /// with later generations may have gained others since.
+    pub fn diagnostics(&self, file_path: &str) -> &[Diagnostic] {
+        self.diagnostics.get(file_path).map(|list| list.as_slice()).unwrap_or(&[])
+    }
+}
+
+/// Keeps the last verified `DatabaseSnapshot` available to queries, such
+/// as hovers in a language server, while a newer verification runs over
+/// fresh edits in its own `Database`.
+///
+/// ```ignore
+/// let (generation, host, token) = snapshots.begin();
+/// let output = Compilation::run_with_cache(&host, &compiler_options, &sources, &[], None, snapshots.parse_cache());
+/// snapshots.publish(generation, host, output);
+///
+/// // elsewhere, queries keep the snapshot they started with
+/// let snapshot = snapshots.current().unwrap();
+/// ```
+///
+/// Beginning a verification cancels the previous one, if still running,
+/// and only the last begun generation can be published.
+///
+/// Snapshots are not copy-on-write: each generation verifies every
+/// source into its own `Database`, which holds the semantic model of the
+/// generation. Only the programs of unchanged files are shared, through
+/// `parse_cache()`. As the compilation units of shared programs accumulate
+/// the diagnostics of later generations, a snapshot copies the
+/// diagnostics when published.
+#[derive(Default)]
+pub struct DatabaseSnapshots {
+    current: Option<Rc<DatabaseSnapshot>>,
+    parse_cache: ParseCache,
+    next_generation: u64,
+    in_flight: Option<CancellationToken>,
+}
+
+impl DatabaseSnapshots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parsed files shared by the verifications of every generation.
+    pub fn parse_cache(&self) -> &ParseCache {
+        &self.parse_cache
+    }
+
+    /// The last published snapshot.
+    pub fn current(&self) -> Option<Rc<DatabaseSnapshot>> {
+        self.current.clone()
+    }
+
+    /// Starts a verification, returning its generation, a new `Database`
+    /// and the token to give the `Verifier`.
+    pub fn begin(&mut self) -> (u64, Rc<Database>, CancellationToken) {
+        if let Some(token) = self.in_flight.take() {
+            token.cancel();
+        }
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let token = CancellationToken::new();
+        self.in_flight = Some(token.clone());
+        (generation, Rc::new(Database::new(Default::default())), token)
+    }
+
+    /// Publishes the result of a verification, returning whether it
+    /// became the current snapshot.
+    pub fn publish(&mut self, generation: u64, host: Rc<Database>, output: CompilationOutput) -> bool {
+        // Superseded verifications were cancelled and are incomplete
+        if generation + 1 != self.next_generation {
+            return false;
+        }
+        self.in_flight = None;
+        let diagnostics = output.compilation_units.iter().map(|cu| {
+            cu.sort_diagnostics();
+            (cu.file_path().unwrap_or_default(), cu.nested_diagnostics())
+        }).collect();
+        self.current = Some(Rc::new(DatabaseSnapshot { generation, host, output, diagnostics }));
+        true
+    }
+}
\ No newline at end of file
@@ -0,0 +1,239 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Name of a package-level definition supplied by a `DefinitionProvider`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct ProvidedDefinition {
+    /// Dot-delimited package name, empty for the top-level package.
+    pub package: String,
+    pub name: String,
+}
+
+impl ProvidedDefinition {
+    pub fn new(package: &str, name: &str) -> Self {
+        Self { package: package.to_owned(), name: name.to_owned() }
+    }
+
+    pub fn fully_qualified_name(&self) -> String {
+        if self.package.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.package, self.name)
+        }
+    }
+}
+
+/// Supplies definitions from sources other than ActionScript files,
+/// such as native extension (ANE) descriptors, JSON IDL or generated
+/// bindings.
+///
+/// Providers list their definitions up front, but only produce the
+/// source of those the compiled programs refer to, through
+/// `DefinitionMaterializer`.
+pub trait DefinitionProvider {
+    /// Used in the file paths of materialized sources, such as `ane`.
+    fn name(&self) -> String;
+
+    /// Definitions the provider can supply. This should be cheap.
+    fn definitions(&self) -> Vec<ProvidedDefinition>;
+
+    /// ActionScript source declaring a definition, usually as
+    /// `native` members.
+    fn materialize(&self, definition: &ProvidedDefinition) -> Option<String>;
+}
+
+/// Definitions held in memory as ActionScript sources.
+///
+/// ```ignore
+/// let mut provider = MemoryDefinitionProvider::new("ane");
+/// provider.insert(ProvidedDefinition::new("com.example", "Vibration"),
+///     "package com.example { public class Vibration { public static native function vibrate(ms: Number): void; } }");
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryDefinitionProvider {
+    pub name: String,
+    pub sources: BTreeMap<ProvidedDefinition, String>,
+}
+
+impl MemoryDefinitionProvider {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_owned(), sources: BTreeMap::new() }
+    }
+
+    pub fn insert(&mut self, definition: ProvidedDefinition, source: &str) {
+        self.sources.insert(definition, source.to_owned());
+    }
+}
+
+impl DefinitionProvider for MemoryDefinitionProvider {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn definitions(&self) -> Vec<ProvidedDefinition> {
+        self.sources.keys().cloned().collect()
+    }
+
+    fn materialize(&self, definition: &ProvidedDefinition) -> Option<String> {
+        self.sources.get(definition).cloned()
+    }
+}
+
+/// A definition offered by more than one origin, in resolution order.
+#[derive(Clone, Debug)]
+pub struct DefinitionConflict {
+    pub definition: ProvidedDefinition,
+    /// Source file paths and provider names; the first is used.
+    pub origins: Vec<String>,
+    /// Location of the definition that is used.
+    pub location: Location,
+}
+
+/// Parses the provided definitions referred to by programs, and those
+/// the materialized definitions refer to in turn.
+///
+/// A definition is considered referred to when an identifier or an
+/// import names it, so a definition may be materialized without being
+/// used; definitions never named are never materialized.
+///
+/// Definitions in the sources take precedence over provided ones, and
+/// earlier providers over later ones. Each definition shadowed this way
+/// is recorded as a `DefinitionConflict`.
+pub struct DefinitionMaterializer {
+    providers: Vec<Rc<dyn DefinitionProvider>>,
+    /// Provider index and definition per local name.
+    offered: HashMap<String, Vec<(usize, ProvidedDefinition)>>,
+    materialized: HashSet<ProvidedDefinition>,
+    conflicts: Vec<DefinitionConflict>,
+}
+
+impl DefinitionMaterializer {
+    pub fn new(providers: &[Rc<dyn DefinitionProvider>]) -> Self {
+        let mut offered: HashMap<String, Vec<(usize, ProvidedDefinition)>> = HashMap::new();
+        for (i, provider) in providers.iter().enumerate() {
+            for definition in provider.definitions() {
+                offered.entry(definition.name.clone()).or_default().push((i, definition));
+            }
+        }
+        Self { providers: providers.to_vec(), offered, materialized: HashSet::new(), conflicts: vec![] }
+    }
+
+    /// Definitions shadowed by the materialization so far.
+    pub fn conflicts(&self) -> &[DefinitionConflict] {
+        &self.conflicts
+    }
+
+    /// Adds a warning for every conflict to the compilation unit of the
+    /// definition that is used.
+    pub fn report_conflicts(&self) {
+        Self::report(&self.conflicts);
+    }
+
+    /// Definitions of the given source programs that more than one file
+    /// defines, as happens when source roots overlap. The file verified
+    /// first is used.
+    pub fn source_conflicts(programs: &[Rc<Program>]) -> Vec<DefinitionConflict> {
+        let mut origins = Vec::<(ProvidedDefinition, Vec<String>, Location)>::new();
+        for program in programs.iter() {
+            // Definitions private to a file outside a package do not clash
+            for symbol in SymbolCollector::collect(program).into_iter().filter(|symbol| symbol.is_public) {
+                let definition = ProvidedDefinition::new(&symbol.package, &symbol.name);
+                match origins.iter_mut().find(|(d, _, _)| *d == definition) {
+                    Some((_, files, _)) => {
+                        if !files.contains(&symbol.file_path) {
+                            files.push(symbol.file_path);
+                        }
+                    },
+                    None => origins.push((definition, vec![symbol.file_path], program.location.clone())),
+                }
+            }
+        }
+        origins.into_iter()
+            .filter(|(_, files, _)| files.len() > 1)
+            .map(|(definition, origins, location)| DefinitionConflict { definition, origins, location })
+            .collect()
+    }
+
+    pub fn report(conflicts: &[DefinitionConflict]) {
+        for conflict in conflicts.iter() {
+            let origins = conflict.origins.join(", ");
+            conflict.location.compilation_unit().add_diagnostic(WhackDiagnostic::new_warning(&conflict.location, WhackDiagnosticKind::DefinitionConflict, diagarg![conflict.definition.fully_qualified_name(), origins, conflict.origins[0].clone()]));
+        }
+    }
+
+    /// Materializes the definitions referred to by `programs`, returning
+    /// the compilation units and programs of the produced sources.
+    pub fn materialize_referenced(&mut self, compiler_options: &Rc<CompilerOptions>, programs: &[Rc<Program>]) -> (Vec<Rc<CompilationUnit>>, Vec<Rc<Program>>) {
+        let mut compilation_units = vec![];
+        let mut new_programs = vec![];
+        let mut pending = programs.to_vec();
+
+        // Definitions of the sources, with the location of their program
+        let mut source_definitions = HashMap::<ProvidedDefinition, (String, Location)>::new();
+        for program in programs.iter() {
+            for symbol in SymbolCollector::collect(program) {
+                source_definitions.entry(ProvidedDefinition::new(&symbol.package, &symbol.name)).or_insert((symbol.file_path, program.location.clone()));
+            }
+        }
+
+        while !pending.is_empty() {
+            let mut names = ReferencedNameCollector(HashSet::new());
+            for program in pending.iter() {
+                names.visit_program(program);
+            }
+            pending.clear();
+            for name in names.0 {
+                let Some(candidates) = self.offered.get(&name) else {
+                    continue;
+                };
+                for (_, definition) in candidates.clone() {
+                    if !self.materialized.insert(definition.clone()) {
+                        continue;
+                    }
+                    let offering: Vec<usize> = candidates.iter().filter(|(_, d)| *d == definition).map(|(i, _)| *i).collect();
+                    let mut origins: Vec<String> = offering.iter().map(|i| self.providers[*i].name()).collect();
+                    if let Some((file_path, location)) = source_definitions.get(&definition) {
+                        origins.insert(0, file_path.clone());
+                        self.conflicts.push(DefinitionConflict { definition, origins, location: location.clone() });
+                        continue;
+                    }
+                    let provider = &self.providers[offering[0]];
+                    let Some(text) = provider.materialize(&definition) else {
+                        continue;
+                    };
+                    let path = format!("{}:{}.as", provider.name(), definition.fully_qualified_name());
+                    let cu = CompilationUnit::new(Some(path), text);
+                    cu.set_compiler_options(Some(compiler_options.clone()));
+                    let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+                    if origins.len() > 1 {
+                        self.conflicts.push(DefinitionConflict { definition, origins, location: program.location.clone() });
+                    }
+                    compilation_units.push(cu);
+                    pending.push(program.clone());
+                    new_programs.push(program);
+                }
+            }
+        }
+        (compilation_units, new_programs)
+    }
+}
+
+struct ReferencedNameCollector(HashSet<String>);
+
+impl Visitor for ReferencedNameCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ImportDirective(impdrtv) = drtv.as_ref() {
+            if let ImportSpecifier::Identifier(name) = &impdrtv.import_specifier {
+                self.0.insert(name.0.clone());
+            }
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_qualified_identifier(&mut self, id: &QualifiedIdentifier) {
+        if let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id {
+            self.0.insert(name.clone());
+        }
+        walk_qualified_identifier(self, id);
+    }
+}
\ No newline at end of file
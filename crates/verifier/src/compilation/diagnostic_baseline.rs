@@ -0,0 +1,208 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// A diagnostic keyed by `DiagnosticBaseline`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BaselineDiagnostic {
+    /// `StableEntityId` of the innermost definition enclosing the
+    /// diagnostic, or `file:PATH` outside of definitions.
+    pub anchor: String,
+    pub id: i32,
+    pub file_path: String,
+    /// One-based line number.
+    pub line: usize,
+    pub message: String,
+    pub is_warning: bool,
+}
+
+/// Diagnostics of a run missing from a `DiagnosticBaseline`, and
+/// diagnostics of the baseline no longer reported.
+#[derive(Clone, Default, Debug)]
+pub struct DiagnosticBaselineDelta {
+    pub introduced: Vec<BaselineDiagnostic>,
+    /// Anchor, diagnostic ID and number of diagnostics fixed.
+    pub fixed: Vec<(String, i32, usize)>,
+}
+
+impl DiagnosticBaselineDelta {
+    pub fn introduces_errors(&self) -> bool {
+        self.introduced.iter().any(|d| !d.is_warning)
+    }
+
+    pub fn introduces_warnings(&self) -> bool {
+        self.introduced.iter().any(|d| d.is_warning)
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = String::new();
+        for d in self.introduced.iter() {
+            let severity = if d.is_warning { "warning" } else { "error" };
+            r.push_str(&format!("new {severity}: {}:{}: {} ({})\n", d.file_path, d.line, d.message, d.id));
+        }
+        for (anchor, id, count) in self.fixed.iter() {
+            r.push_str(&format!("fixed: {count} x {id} in {anchor}\n"));
+        }
+        r.push_str(&format!("{} new, {} fixed\n", self.introduced.len(), self.fixed.iter().map(|(_, _, count)| count).sum::<usize>()));
+        r
+    }
+}
+
+/// Diagnostics of a baseline run, counted by the definition they occur
+/// in and their ID rather than by line, so that later runs report only
+/// new and fixed diagnostics even as code moves around.
+///
+/// ```ignore
+/// // Once, on the main branch:
+/// DiagnosticBaseline::collect(&host, &output).save(&baseline_path)?;
+///
+/// // On later runs:
+/// let delta = DiagnosticBaseline::load(&baseline_path)?.compare(&host, &output);
+/// if delta.introduces_warnings() {
+///     // fail the "no new warnings" policy
+/// }
+/// ```
+///
+/// Definitions are identified by `StableEntityId`. Changing the
+/// signature of a definition thus reports its diagnostics as fixed and
+/// reported anew.
+#[derive(Clone, Default, Debug)]
+pub struct DiagnosticBaseline {
+    counts: BTreeMap<(String, i32), usize>,
+}
+
+impl DiagnosticBaseline {
+    pub fn collect(host: &Database, output: &CompilationOutput) -> Self {
+        let mut counts = BTreeMap::new();
+        for d in Self::diagnostics(host, output) {
+            *counts.entry((d.anchor, d.id)).or_insert(0) += 1;
+        }
+        Self { counts }
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn compare(&self, host: &Database, output: &CompilationOutput) -> DiagnosticBaselineDelta {
+        let mut groups = BTreeMap::<(String, i32), Vec<BaselineDiagnostic>>::new();
+        for d in Self::diagnostics(host, output) {
+            groups.entry((d.anchor.clone(), d.id)).or_default().push(d);
+        }
+        let mut delta = DiagnosticBaselineDelta::default();
+        for (key, list) in groups.iter() {
+            // Which of several diagnostics of a key is new is unknown;
+            // the last ones are reported.
+            let known = self.counts.get(key).copied().unwrap_or(0);
+            delta.introduced.extend(list.iter().skip(known).cloned());
+        }
+        for ((anchor, id), count) in self.counts.iter() {
+            let current = groups.get(&(anchor.clone(), *id)).map(|list| list.len()).unwrap_or(0);
+            if current < *count {
+                delta.fixed.push((anchor.clone(), *id, count - current));
+            }
+        }
+        delta.introduced.sort_by(|a, b| a.file_path.cmp(&b.file_path).then(a.line.cmp(&b.line)));
+        delta
+    }
+
+    /// Parses a baseline saved by `to_tsv()`.
+    pub fn from_tsv(text: &str) -> Self {
+        let mut counts = BTreeMap::new();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [anchor, id, count] = fields[..] else {
+                continue;
+            };
+            let (Ok(id), Ok(count)) = (id.parse(), count.parse()) else {
+                continue;
+            };
+            counts.insert((anchor.to_owned(), id), count);
+        }
+        Self { counts }
+    }
+
+    pub fn to_tsv(&self) -> String {
+        let mut text = String::new();
+        for ((anchor, id), count) in self.counts.iter() {
+            text.push_str(&format!("{anchor}\t{id}\t{count}\n"));
+        }
+        text
+    }
+
+    #[cfg(feature = "fs")]
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        Ok(Self::from_tsv(&std::fs::read_to_string(path)?))
+    }
+
+    #[cfg(feature = "fs")]
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_tsv())
+    }
+
+    fn diagnostics(host: &Database, output: &CompilationOutput) -> Vec<BaselineDiagnostic> {
+        let mut definitions = DefinitionSpanCollector { host, spans: vec![] };
+        for program in output.programs.iter() {
+            definitions.visit_program(program);
+        }
+        let mut r = vec![];
+        for cu in output.compilation_units.iter() {
+            cu.sort_diagnostics();
+            for diagnostic in cu.nested_diagnostics().iter() {
+                let d = WhackDiagnostic(diagnostic);
+                let location = diagnostic.location();
+                let file_path = location.compilation_unit().file_path().unwrap_or_default();
+                let anchor = definitions.spans.iter()
+                    .filter(|(span, _)| Rc::ptr_eq(&span.compilation_unit(), &location.compilation_unit())
+                        && span.first_offset() <= location.first_offset() && location.first_offset() < span.last_offset())
+                    .min_by_key(|(span, _)| span.last_offset() - span.first_offset())
+                    .map(|(_, id)| id.clone())
+                    .unwrap_or(format!("file:{file_path}"));
+                r.push(BaselineDiagnostic {
+                    anchor,
+                    id: d.id(),
+                    file_path,
+                    line: location.first_line_number(),
+                    message: d.format_message_english(),
+                    is_warning: diagnostic.is_warning(),
+                });
+            }
+        }
+        r
+    }
+}
+
+/// Collects the source ranges of definitions with their stable
+/// identifiers.
+struct DefinitionSpanCollector<'a> {
+    host: &'a Database,
+    spans: Vec<(Location, String)>,
+}
+
+impl<'a> DefinitionSpanCollector<'a> {
+    fn add(&mut self, location: Location, entity: Option<Entity>) {
+        if let Some(id) = entity.and_then(|entity| StableEntityId::of(self.host, &entity)) {
+            self.spans.push((location, id));
+        }
+    }
+}
+
+impl<'a> Visitor for DefinitionSpanCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(_) | Directive::InterfaceDefinition(_) | Directive::EnumDefinition(_) | Directive::FunctionDefinition(_) => {
+                self.add(drtv.location(), self.host.node_mapping().get(drtv));
+            },
+            Directive::VariableDefinition(defn) => {
+                for binding in defn.bindings.iter() {
+                    self.add(drtv.location(), self.host.node_mapping().get(&binding.destructuring.destructuring));
+                }
+            },
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,280 @@
+use crate::ns::*;
+
+/// Accepts historical syntax quirks of old ActionScript 3 codebases when
+/// `CompilerOptions::legacy_syntax` is set, warning on each of them.
+///
+/// The source text is rewritten before parsing without moving any
+/// character, so that locations still match the original file:
+///
+/// * A trailing comma closing an argument or parameter list, as in
+///   `f(a, b,)`, is replaced by a space.
+///
+/// Strings, comments, regular expression literals and XML literals are
+/// skipped. Trailing commas of object and array literals are valid
+/// syntax and are kept.
+pub struct LegacySyntax;
+
+/// Keywords after which `(` opens a parenthesized expression rather
+/// than an argument list.
+const PARENTHESIZING_KEYWORDS: [&str; 18] = [
+    "if", "while", "for", "each", "switch", "with", "catch", "return", "throw",
+    "typeof", "delete", "void", "in", "instanceof", "is", "as", "case", "new",
+];
+
+/// Keywords after which `/` and `<` begin a literal rather than an
+/// operator.
+const OPERAND_KEYWORDS: [&str; 14] = [
+    "return", "throw", "typeof", "delete", "void", "in", "instanceof", "is",
+    "as", "case", "new", "else", "do", "yield",
+];
+
+/// Previous significant token, used to tell literals from operators
+/// and argument lists from parenthesized expressions.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum PreviousToken<'a> {
+    None,
+    Word(&'a [u8]),
+    /// A string, number or literal.
+    Operand,
+    Punctuator(u8),
+}
+
+impl PreviousToken<'_> {
+    pub(crate) fn expects_operand(&self) -> bool {
+        match self {
+            Self::None => true,
+            Self::Word(word) => OPERAND_KEYWORDS.iter().any(|k| k.as_bytes() == *word),
+            Self::Operand => false,
+            // `.<` begins type arguments
+            Self::Punctuator(b) => !matches!(b, b')' | b']' | b'.'),
+        }
+    }
+
+    fn opens_argument_list(&self) -> bool {
+        match self {
+            Self::Word(word) => !PARENTHESIZING_KEYWORDS.iter().any(|k| k.as_bytes() == *word),
+            Self::Punctuator(b) => matches!(b, b')' | b']' | b'>'),
+            _ => false,
+        }
+    }
+}
+
+impl LegacySyntax {
+    /// Rewrites a source text, returning it with the offsets of the
+    /// removed trailing commas.
+    pub fn rewrite(text: &str) -> (String, Vec<usize>) {
+        let source = text.as_bytes();
+        let mut bytes = source.to_vec();
+        let mut commas = vec![];
+        // Whether each open parenthesis opens an argument list
+        let mut parens: Vec<bool> = vec![];
+        let mut previous = PreviousToken::None;
+        let mut i = 0;
+        while i < source.len() {
+            let b = source[i];
+            match b {
+                b'"' | b'\'' => {
+                    i = Self::skip_string(source, i);
+                    previous = PreviousToken::Operand;
+                },
+                b'/' if source.get(i + 1) == Some(&b'/') => i = Self::skip_line_comment(source, i),
+                b'/' if source.get(i + 1) == Some(&b'*') => i = Self::skip_block_comment(source, i),
+                b'/' if previous.expects_operand() => {
+                    i = Self::skip_regex(source, i);
+                    previous = PreviousToken::Operand;
+                },
+                b'<' if previous.expects_operand() && Self::starts_xml(source, i) => {
+                    i = Self::skip_xml(source, i);
+                    previous = PreviousToken::Operand;
+                },
+                b'(' => {
+                    parens.push(previous.opens_argument_list() || previous == PreviousToken::Word(b"function"));
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+                b')' => {
+                    parens.pop();
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+                b',' => {
+                    let next = Self::skip_trivia(source, i + 1);
+                    if source.get(next) == Some(&b')') && parens.last().copied().unwrap_or(false) {
+                        bytes[i] = b' ';
+                        commas.push(i);
+                    }
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+                _ if b.is_ascii_whitespace() => i += 1,
+                _ if Self::is_word_byte(b) => {
+                    let start = i;
+                    while i < source.len() && (Self::is_word_byte(source[i]) || (source[i] == b'.' && source[start].is_ascii_digit())) {
+                        i += 1;
+                    }
+                    previous = if source[start].is_ascii_digit() { PreviousToken::Operand } else { PreviousToken::Word(&source[start..i]) };
+                },
+                _ => {
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+            }
+        }
+        // Only an ASCII comma is ever replaced by an ASCII space
+        (String::from_utf8(bytes).unwrap(), commas)
+    }
+
+    /// Adds a warning per rewritten quirk to a compilation unit parsed
+    /// from the rewritten text.
+    pub fn report(cu: &Rc<CompilationUnit>, commas: &[usize]) {
+        for offset in commas.iter() {
+            let location = Location::with_offsets(cu, *offset, *offset + 1);
+            cu.add_diagnostic(WhackDiagnostic::new_warning(&location, WhackDiagnosticKind::LegacyTrailingComma, diagarg![]));
+        }
+    }
+
+    pub(crate) fn skip_string(bytes: &[u8], start: usize) -> usize {
+        let quote = bytes[start];
+        let mut i = start + 1;
+        while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+            i += if bytes[i] == b'\\' { 2 } else { 1 };
+        }
+        i + 1
+    }
+
+    pub(crate) fn skip_line_comment(bytes: &[u8], start: usize) -> usize {
+        bytes[start..].iter().position(|b| *b == b'\n').map(|n| start + n).unwrap_or(bytes.len())
+    }
+
+    pub(crate) fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+        bytes[start + 2..].windows(2).position(|w| w == b"*/").map(|n| start + n + 4).unwrap_or(bytes.len())
+    }
+
+    pub(crate) fn is_word_byte(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+    }
+
+    /// Skips a regular expression literal and its flags. A `/` without
+    /// a closing `/` on the same line is skipped alone.
+    pub(crate) fn skip_regex(bytes: &[u8], start: usize) -> usize {
+        let mut i = start + 1;
+        let mut in_class = false;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'\n' => return start + 1,
+                b'[' => {
+                    in_class = true;
+                    i += 1;
+                },
+                b']' => {
+                    in_class = false;
+                    i += 1;
+                },
+                b'/' if !in_class => {
+                    i += 1;
+                    while i < bytes.len() && Self::is_word_byte(bytes[i]) {
+                        i += 1;
+                    }
+                    return i;
+                },
+                _ => i += 1,
+            }
+        }
+        start + 1
+    }
+
+    /// Whether a `<` in operand position begins an XML or XMLList literal.
+    pub(crate) fn starts_xml(bytes: &[u8], start: usize) -> bool {
+        matches!(bytes.get(start + 1), Some(b) if b.is_ascii_alphabetic() || matches!(b, b'_' | b'!' | b'?' | b'>' | b'{'))
+    }
+
+    /// Skips an XML or XMLList literal up to the end of its root element.
+    pub(crate) fn skip_xml(bytes: &[u8], start: usize) -> usize {
+        let find = |from: usize, end: &[u8]| bytes[from..].windows(end.len()).position(|w| w == end).map(|n| from + n + end.len()).unwrap_or(bytes.len());
+        let mut depth = 0usize;
+        let mut i = start;
+        while i < bytes.len() {
+            let rest = &bytes[i..];
+            if rest.starts_with(b"<!--") {
+                i = find(i + 4, b"-->");
+            } else if rest.starts_with(b"<![CDATA[") {
+                i = find(i + 9, b"]]>");
+            } else if rest.starts_with(b"<?") {
+                i = find(i + 2, b"?>");
+            } else if rest.starts_with(b"</") {
+                i = find(i + 2, b">");
+                depth = depth.saturating_sub(1);
+            } else if rest[0] == b'<' {
+                let (end, self_closing) = Self::skip_xml_tag(bytes, i);
+                i = end;
+                if !self_closing {
+                    depth += 1;
+                }
+            } else if rest[0] == b'{' {
+                i = Self::skip_braces(bytes, i);
+                continue;
+            } else {
+                i += 1;
+                continue;
+            }
+            if depth == 0 {
+                return i;
+            }
+        }
+        bytes.len()
+    }
+
+    /// Skips a start tag, returning its end and whether it is self-closing.
+    fn skip_xml_tag(bytes: &[u8], start: usize) -> (usize, bool) {
+        let mut i = start + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'"' | b'\'' => {
+                    let quote = bytes[i];
+                    i = bytes[i + 1..].iter().position(|b| *b == quote).map(|n| i + n + 2).unwrap_or(bytes.len());
+                },
+                b'{' => i = Self::skip_braces(bytes, i),
+                b'>' => return (i + 1, bytes[i - 1] == b'/'),
+                _ => i += 1,
+            }
+        }
+        (bytes.len(), true)
+    }
+
+    /// Skips an expression between balanced braces embedded in XML.
+    fn skip_braces(bytes: &[u8], start: usize) -> usize {
+        let mut depth = 0usize;
+        let mut i = start;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'"' | b'\'' => {
+                    i = Self::skip_string(bytes, i);
+                    continue;
+                },
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                },
+                _ => {},
+            }
+            i += 1;
+        }
+        bytes.len()
+    }
+
+    /// Skips whitespace and comments.
+    pub(crate) fn skip_trivia(bytes: &[u8], mut i: usize) -> usize {
+        loop {
+            match bytes.get(i) {
+                Some(b) if b.is_ascii_whitespace() => i += 1,
+                Some(b'/') if bytes.get(i + 1) == Some(&b'/') => i = Self::skip_line_comment(bytes, i),
+                Some(b'/') if bytes.get(i + 1) == Some(&b'*') => i = Self::skip_block_comment(bytes, i),
+                _ => return i,
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use crate::ns::*;
+use std::cell::{Cell, RefCell};
+
+/// Memoizes the parsing of source files across compilations, keyed by
+/// file path, text and compiler options, so that a long-running host
+/// such as `CompileServer` parses again only the files whose text
+/// changed, the core library included. Only parsing is memoized; the
+/// verification of every program runs again in each new `Database`.
+///
+/// ```ignore
+/// let cache = ParseCache::new();
+/// let output = Compilation::run_with_cache(&host, &compiler_options, &sources, &[], None, &cache);
+/// // the unchanged files are not parsed again
+/// let host = Rc::new(Database::new(Default::default()));
+/// let output = Compilation::run_with_cache(&host, &compiler_options, &sources, &[], None, &cache);
+/// ```
+///
+/// A program is reused only while its compilation unit holds no
+/// diagnostics, as the diagnostics of a compilation unit accumulate
+/// across verifications; a file with syntax errors or diagnostics from
+/// a previous verification is parsed again. Programs can be verified
+/// again in a new `Database`, which keeps its semantic model in its own
+/// node mapping.
+#[derive(Default)]
+pub struct ParseCache {
+    entries: RefCell<HashMap<String, ParseCacheEntry>>,
+    hits: Cell<usize>,
+}
+
+struct ParseCacheEntry {
+    text: String,
+    compilation_unit: Rc<CompilationUnit>,
+    program: Rc<Program>,
+}
+
+impl ParseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the program previously parsed from the same text with the
+    /// same compiler options, or the result of `parse()`, retained for
+    /// the next compilations.
+    pub fn parse(&self, path: &str, text: &str, compiler_options: &Rc<CompilerOptions>, parse: impl FnOnce() -> (Rc<CompilationUnit>, Rc<Program>)) -> (Rc<CompilationUnit>, Rc<Program>) {
+        if let Some(entry) = self.entries.borrow().get(path) {
+            let same_options = entry.compilation_unit.compiler_options()
+                .and_then(|options| Rc::downcast::<CompilerOptions>(options).ok())
+                .map(|options| Rc::ptr_eq(&options, compiler_options))
+                .unwrap_or(false);
+            if entry.text == text && same_options && entry.compilation_unit.nested_diagnostics().is_empty() {
+                self.hits.set(self.hits.get() + 1);
+                return (entry.compilation_unit.clone(), entry.program.clone());
+            }
+        }
+        let (compilation_unit, program) = parse();
+        self.entries.borrow_mut().insert(path.to_owned(), ParseCacheEntry {
+            text: text.to_owned(),
+            compilation_unit: compilation_unit.clone(),
+            program: program.clone(),
+        });
+        (compilation_unit, program)
+    }
+
+    /// Number of programs reused since the cache was created.
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,164 @@
+use crate::ns::*;
+
+/// Recovers common states of a file being typed when
+/// `CompilerOptions::verify_with_syntax_errors` is set, so that the
+/// parser still produces the nodes around the insertion point:
+///
+/// * A dangling member access, as in `obj.` followed by a closing
+///   bracket, a `;`, a `,` or the end of the file, has its `.` (or `?.`)
+///   replaced by a space, leaving the base expression.
+/// * Brackets left open at the end of the file are closed there. An
+///   `if (`, `while (` or `with (` heading is completed with an empty
+///   statement, a `switch (` heading with an empty body, and an empty
+///   heading is given a `false` test.
+///
+/// Characters are either replaced by spaces or appended, so that
+/// locations still match the original file. Each recovery is reported
+/// as a syntax error. Strings, comments, regular expression literals and
+/// XML literals are skipped, as in `LegacySyntax`.
+pub struct PartialInputRecovery;
+
+/// A recovery made by `PartialInputRecovery::recover()`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InputRecovery {
+    /// A `.` or `?.` at the offset replaced by spaces.
+    DanglingMemberAccess { offset: usize, punctuator: &'static str },
+    /// A bracket at the offset closed at the end of the file.
+    UnclosedBracket { offset: usize, bracket: char },
+}
+
+/// Keywords whose parenthesized heading is followed by a statement.
+const STATEMENT_HEADINGS: [&str; 4] = ["if", "while", "with", "switch"];
+
+impl PartialInputRecovery {
+    /// Rewrites a source text, returning it with the recoveries made.
+    pub fn recover(text: &str) -> (String, Vec<InputRecovery>) {
+        let source = text.as_bytes();
+        let mut bytes = source.to_vec();
+        let mut recoveries = vec![];
+        // Open brackets with their offset and, for a parenthesis, the
+        // keyword of the heading it opens
+        let mut brackets: Vec<(usize, u8, Option<&'static str>)> = vec![];
+        let mut previous = PreviousToken::None;
+        let mut i = 0;
+        while i < source.len() {
+            let b = source[i];
+            match b {
+                b'"' | b'\'' => {
+                    i = LegacySyntax::skip_string(source, i);
+                    previous = PreviousToken::Operand;
+                },
+                b'/' if source.get(i + 1) == Some(&b'/') => i = LegacySyntax::skip_line_comment(source, i),
+                b'/' if source.get(i + 1) == Some(&b'*') => {
+                    // Appended characters would be commented out
+                    if !source[i + 2..].windows(2).any(|w| w == b"*/") {
+                        return (text.to_owned(), vec![]);
+                    }
+                    i = LegacySyntax::skip_block_comment(source, i);
+                },
+                b'/' if previous.expects_operand() => {
+                    i = LegacySyntax::skip_regex(source, i);
+                    previous = PreviousToken::Operand;
+                },
+                b'<' if previous.expects_operand() && LegacySyntax::starts_xml(source, i) => {
+                    i = LegacySyntax::skip_xml(source, i);
+                    previous = PreviousToken::Operand;
+                },
+                b'(' | b'[' | b'{' => {
+                    let heading = match previous {
+                        PreviousToken::Word(word) if b == b'(' => STATEMENT_HEADINGS.iter().find(|k| k.as_bytes() == word).copied(),
+                        _ => None,
+                    };
+                    brackets.push((i, b, heading));
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+                b')' | b']' | b'}' => {
+                    let open = match b { b')' => b'(', b']' => b'[', _ => b'{' };
+                    // Brackets left open inside the closed one cannot
+                    // be closed without moving characters
+                    if let Some(j) = brackets.iter().rposition(|(_, bracket, _)| *bracket == open) {
+                        brackets.truncate(j);
+                    }
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+                b'.' if source.get(i + 1) != Some(&b'.') && (i == 0 || source[i - 1] != b'.') => {
+                    let next = LegacySyntax::skip_trivia(source, i + 1);
+                    if matches!(source.get(next), None | Some(b')' | b']' | b'}' | b';' | b',')) {
+                        if i > 0 && source[i - 1] == b'?' {
+                            bytes[i - 1] = b' ';
+                            recoveries.push(InputRecovery::DanglingMemberAccess { offset: i - 1, punctuator: "?." });
+                        } else {
+                            recoveries.push(InputRecovery::DanglingMemberAccess { offset: i, punctuator: "." });
+                        }
+                        bytes[i] = b' ';
+                        // The base expression is left as an operand
+                        i += 1;
+                        continue;
+                    }
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+                _ if b.is_ascii_whitespace() => i += 1,
+                _ if LegacySyntax::is_word_byte(b) => {
+                    let start = i;
+                    while i < source.len() && (LegacySyntax::is_word_byte(source[i]) || (source[i] == b'.' && source[start].is_ascii_digit())) {
+                        i += 1;
+                    }
+                    previous = if source[start].is_ascii_digit() { PreviousToken::Operand } else { PreviousToken::Word(&source[start..i]) };
+                },
+                _ => {
+                    previous = PreviousToken::Punctuator(b);
+                    i += 1;
+                },
+            }
+        }
+
+        if !brackets.is_empty() {
+            // A line break ends a trailing line comment
+            bytes.push(b'\n');
+            for (offset, bracket, heading) in brackets.iter().rev() {
+                match bracket {
+                    b'(' => {
+                        if heading.is_some() && LegacySyntax::skip_trivia(source, offset + 1) == source.len() {
+                            bytes.extend(b"false");
+                        }
+                        bytes.push(b')');
+                        match heading {
+                            Some("switch") => bytes.extend(b"{}"),
+                            Some(_) => bytes.push(b';'),
+                            None => {},
+                        }
+                    },
+                    b'[' => bytes.push(b']'),
+                    _ => bytes.push(b'}'),
+                }
+                recoveries.push(InputRecovery::UnclosedBracket { offset: *offset, bracket: *bracket as char });
+            }
+        }
+        recoveries.sort_by_key(|recovery| match recovery {
+            InputRecovery::DanglingMemberAccess { offset, .. } | InputRecovery::UnclosedBracket { offset, .. } => *offset,
+        });
+        // Only ASCII characters are ever replaced or appended
+        (String::from_utf8(bytes).unwrap(), recoveries)
+    }
+
+    /// Adds a syntax error per recovery to a compilation unit parsed
+    /// from the rewritten text.
+    pub fn report(cu: &Rc<CompilationUnit>, recoveries: &[InputRecovery]) {
+        for recovery in recoveries.iter() {
+            let diagnostic = match recovery {
+                InputRecovery::DanglingMemberAccess { offset, punctuator } => {
+                    let location = Location::with_offsets(cu, *offset, *offset + punctuator.len());
+                    WhackDiagnostic::new_syntax_error(&location, WhackDiagnosticKind::DanglingMemberAccess, diagarg![punctuator.to_string()])
+                },
+                InputRecovery::UnclosedBracket { offset, bracket } => {
+                    let location = Location::with_offsets(cu, *offset, *offset + 1);
+                    WhackDiagnostic::new_syntax_error(&location, WhackDiagnosticKind::UnclosedBracket, diagarg![bracket.to_string()])
+                },
+            };
+            cu.add_diagnostic(diagnostic);
+        }
+    }
+}
\ No newline at end of file
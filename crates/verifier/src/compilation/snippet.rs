@@ -0,0 +1,58 @@
+use crate::ns::*;
+
+/// Runs verified snippets, such as an interpreter or an AVM2 hosted by a
+/// playground, until codegen produces ABC.
+pub trait SnippetRuntime {
+    /// Returns the output of the program, or an error message.
+    fn run(&self, host: &Rc<Database>, output: &CompilationOutput) -> Result<String, String>;
+}
+
+/// Result of `Snippet::eval()`.
+pub struct SnippetEvaluation {
+    pub output: CompilationOutput,
+    /// Result of the runtime, if one was given and verification succeeded.
+    pub result: Option<Result<String, String>>,
+}
+
+/// Evaluates a loose list of statements for REPL and playground
+/// scenarios, by wrapping it in a synthetic `main` function.
+///
+/// ```ignore
+/// let evaluation = Snippet::eval(&host, &compiler_options, "var x: Number = 10;\ntrace(x);", None);
+/// for message in evaluation.output.diagnostics() {
+///     println!("{message}");
+/// }
+/// ```
+pub struct Snippet;
+
+impl Snippet {
+    /// File path of the synthetic compilation unit.
+    pub const FILE_PATH: &'static str = "Snippet.as";
+
+    /// The wrapper prefix shares the first line of the snippet, so that
+    /// diagnostic line numbers match the snippet.
+    const PREFIX: &'static str = "package { public function main(): void { ";
+    const SUFFIX: &'static str = "\n} }";
+
+    pub fn wrap(source: &str) -> String {
+        format!("{}{source}{}", Self::PREFIX, Self::SUFFIX)
+    }
+
+    /// Column of the snippet's first line within the wrapped source.
+    pub fn first_line_column_offset() -> usize {
+        Self::PREFIX.len()
+    }
+
+    /// Verifies a snippet along with the core library selected by
+    /// `compiler_options.core_library`, then runs it if a runtime is given.
+    pub fn eval(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, source: &str, runtime: Option<&dyn SnippetRuntime>) -> SnippetEvaluation {
+        let mut sources = MemorySourceProvider::new();
+        sources.insert(Self::FILE_PATH, &Self::wrap(source));
+        let output = Compilation::run(host, compiler_options, &sources);
+        let result = match runtime {
+            Some(runtime) if !output.invalidated => Some(runtime.run(host, &output)),
+            _ => None,
+        };
+        SnippetEvaluation { output, result }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use crate::ns::*;
+
+/// Result of `SourceEditReparse::reparse()`.
+pub struct ReparsedProgram {
+    /// Compilation unit holding the edited text.
+    pub compilation_unit: Rc<CompilationUnit>,
+    /// Program parsed from the edited text; every node locates into
+    /// `compilation_unit`.
+    pub program: Rc<Program>,
+    /// Byte range, into the edited text, of the only directive changed
+    /// by the edit, or `None` if the edit was not confined to a single
+    /// directive.
+    pub changed_range: Option<(usize, usize)>,
+    /// Difference in length between the edited and previous texts.
+    pub delta: isize,
+    edit_end: usize,
+}
+
+impl ReparsedProgram {
+    /// Whether the edit was confined to a single directive, in which case
+    /// state computed for every other directive remains valid once its
+    /// offsets are mapped by `map_offset()`.
+    pub fn is_confined(&self) -> bool {
+        self.changed_range.is_some()
+    }
+
+    /// Maps an offset into the previous text, such as that of a cached
+    /// result, to the edited text.
+    pub fn map_offset(&self, offset: usize) -> usize {
+        if offset < self.edit_end {
+            offset
+        } else {
+            offset.saturating_add_signed(self.delta)
+        }
+    }
+}
+
+/// Applies an edit to a compilation unit, parses the edited text again
+/// and determines which top-level directive the edit touched, so that a
+/// language server can keep the state of the directives that did not
+/// change. This is not incremental parsing: the parsing cost is that of
+/// the whole file on every edit.
+///
+/// ```ignore
+/// let edit = SourceEdit::new(120, 120, "x");
+/// let reparsed = SourceEditReparse::reparse(&cu, &program, &edit)?;
+/// program = reparsed.program;
+/// cu = reparsed.compilation_unit;
+/// ```
+///
+/// The edited text is parsed into a new compilation unit, so that no
+/// node keeps a location into the previous one. The edit is
+/// confined if it falls strictly within a directive of the program or of
+/// one of its package blocks, and the edited text parses without errors
+/// into the same shape, with that directive spanning its previous range
+/// adjusted by the edit.
+pub struct SourceEditReparse;
+
+impl SourceEditReparse {
+    pub fn reparse(cu: &Rc<CompilationUnit>, program: &Rc<Program>, edit: &SourceEdit) -> Result<ReparsedProgram, SourceEditError> {
+        let mut batch = SourceEditBatch::new();
+        batch.push(edit.clone());
+        let text = batch.apply(&cu.text())?;
+        let delta = edit.replacement.len() as isize - (edit.end - edit.start) as isize;
+
+        let new_cu = CompilationUnit::new(cu.file_path(), text);
+        new_cu.set_compiler_options(cu.compiler_options());
+        let new_program = ParserFacade(&new_cu, ParserOptions::default()).parse_program();
+        let changed_range = if new_cu.invalidated() {
+            None
+        } else {
+            Self::changed_range(program, &new_program, edit, delta)
+        };
+        Ok(ReparsedProgram { compilation_unit: new_cu, program: new_program, changed_range, delta, edit_end: edit.end })
+    }
+
+    fn changed_range(old_program: &Rc<Program>, new_program: &Rc<Program>, edit: &SourceEdit, delta: isize) -> Option<(usize, usize)> {
+        let contains = |drtv: &Rc<Directive>| {
+            let location = drtv.location();
+            location.first_offset() < edit.start && edit.end < location.last_offset()
+        };
+
+        if old_program.packages.len() != new_program.packages.len() || old_program.directives.len() != new_program.directives.len() {
+            return None;
+        }
+
+        // Package index, or `None` for the top-level directives, and
+        // directive index.
+        let mut found: Option<(Option<usize>, usize)> = None;
+        for (i, pckgdefn) in old_program.packages.iter().enumerate() {
+            if let Some(j) = pckgdefn.block.directives.iter().position(contains) {
+                found = Some((Some(i), j));
+            }
+        }
+        if found.is_none() {
+            found = old_program.directives.iter().position(contains).map(|j| (None, j));
+        }
+        let (package_index, index) = found?;
+
+        let (old, new) = match package_index {
+            Some(i) => {
+                let (old_block, new_block) = (&old_program.packages[i].block, &new_program.packages[i].block);
+                if old_block.directives.len() != new_block.directives.len() {
+                    return None;
+                }
+                (old_block.directives[index].clone(), new_block.directives[index].clone())
+            },
+            None => (old_program.directives[index].clone(), new_program.directives[index].clone()),
+        };
+        if std::mem::discriminant(old.as_ref()) != std::mem::discriminant(new.as_ref()) {
+            return None;
+        }
+        let (old_location, new_location) = (old.location(), new.location());
+        let range = (new_location.first_offset(), new_location.last_offset());
+        if old_location.first_offset() != range.0 || old_location.last_offset().checked_add_signed(delta)? != range.1 {
+            return None;
+        }
+        Some(range)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Supplies source files to a `Compilation`, allowing the pipeline to run
+/// without touching the filesystem.
+pub trait SourceProvider {
+    /// Paths of the ActionScript and MXML sources to compile.
+    fn source_files(&self) -> Vec<String>;
+
+    fn read_to_string(&self, path: &str) -> Option<String>;
+
+    /// Reads a binary file, such as one embedded by an `[Embed]`
+    /// meta-data.
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
+
+    /// Paths of the files directly under a directory.
+    fn read_dir(&self, dir: &str) -> Vec<String>;
+}
+
+/// Sources held in memory, keyed by path.
+///
+/// ```ignore
+/// let mut sources = MemorySourceProvider::new();
+/// sources.insert("src/Main.as", "package { public class Main {} }");
+/// sources.insert_bytes("src/assets/Inter.ttf", &font_data);
+/// ```
+#[derive(Clone, Default)]
+pub struct MemorySourceProvider {
+    pub files: BTreeMap<String, String>,
+    /// Binary files keyed by path.
+    pub binary_files: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemorySourceProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, path: &str, text: &str) {
+        self.files.insert(path.to_owned(), text.to_owned());
+    }
+
+    pub fn insert_bytes(&mut self, path: &str, data: &[u8]) {
+        self.binary_files.insert(path.to_owned(), data.to_vec());
+    }
+}
+
+impl SourceProvider for MemorySourceProvider {
+    fn source_files(&self) -> Vec<String> {
+        self.files.keys().filter(|p| p.ends_with(".as") || p.ends_with(".mxml")).cloned().collect()
+    }
+
+    fn read_to_string(&self, path: &str) -> Option<String> {
+        self.files.get(path).cloned()
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.binary_files.get(path).cloned().or_else(|| self.read_to_string(path).map(String::into_bytes))
+    }
+
+    fn read_dir(&self, dir: &str) -> Vec<String> {
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        let mut r: Vec<String> = self.files.keys().chain(self.binary_files.keys())
+            .filter(|p| p.strip_prefix(&prefix).map(|rest| !rest.contains('/')).unwrap_or(false))
+            .cloned()
+            .collect();
+        r.sort();
+        r.dedup();
+        r
+    }
+}
+
+/// Sources read from the filesystem under the given source paths.
+#[cfg(feature = "fs")]
+#[derive(Clone, Default)]
+pub struct FileSystemSourceProvider {
+    pub source_path: Vec<String>,
+}
+
+#[cfg(feature = "fs")]
+impl FileSystemSourceProvider {
+    fn collect(dir: &std::path::Path, r: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                Self::collect(&path, r);
+            } else if path.extension().map(|e| e == "as" || e == "mxml").unwrap_or(false) {
+                r.push(path.to_string_lossy().into_owned());
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fs")]
+impl SourceProvider for FileSystemSourceProvider {
+    fn source_files(&self) -> Vec<String> {
+        let mut r = vec![];
+        for path in self.source_path.iter() {
+            Self::collect(std::path::Path::new(path), &mut r);
+        }
+        r.sort();
+        r
+    }
+
+    fn read_to_string(&self, path: &str) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        std::fs::read(path).ok()
+    }
+
+    fn read_dir(&self, dir: &str) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return vec![];
+        };
+        let mut r: Vec<String> = entries.flatten().map(|e| e.path()).filter(|p| p.is_file()).map(|p| p.to_string_lossy().into_owned()).collect();
+        r.sort();
+        r
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+use crate::ns::*;
+
+/// Unit in which `SourceText` counts columns.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColumnEncoding {
+    /// Bytes, as the offsets of `Location`.
+    Utf8,
+    /// UTF-16 code units, as required by the Language Server Protocol.
+    Utf16,
+}
+
+/// Zero-based line and column.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Text of a compilation unit with a precomputed line index, converting
+/// byte offsets to and from lines and columns in logarithmic time.
+///
+/// ```ignore
+/// let text = SourceText::new(&cu.text());
+/// let start = text.line_column(location.first_offset(), ColumnEncoding::Utf16);
+/// let offset = text.offset(LineColumn { line: 3, column: 10 }, ColumnEncoding::Utf16);
+/// ```
+///
+/// Lines are terminated as by the parser: by line feed, carriage
+/// return, both in sequence, or the line and paragraph separators.
+#[derive(Clone, Debug)]
+pub struct SourceText {
+    text: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceText {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut chars = text.char_indices().peekable();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '\r' => {
+                    if chars.next_if(|(_, ch)| *ch == '\n').is_some() {
+                        line_starts.push(i + 2);
+                    } else {
+                        line_starts.push(i + 1);
+                    }
+                },
+                '\n' | '\u{2028}' | '\u{2029}' => line_starts.push(i + ch.len_utf8()),
+                _ => {},
+            }
+        }
+        Self { text: text.to_owned(), line_starts }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Byte offset at which a zero-based line starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line).cloned()
+    }
+
+    /// Text of a zero-based line, excluding its terminator.
+    pub fn line_text(&self, line: usize) -> Option<&str> {
+        let start = self.line_start(line)?;
+        let end = self.line_start(line + 1).unwrap_or(self.text.len());
+        Some(self.text[start..end].trim_end_matches(['\n', '\r', '\u{2028}', '\u{2029}']))
+    }
+
+    /// Zero-based line containing a byte offset.
+    pub fn line_of_offset(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next) => next - 1,
+        }
+    }
+
+    /// Line and column of a byte offset. Offsets past the end of the
+    /// text or inside a character are clamped to the preceding
+    /// character boundary.
+    pub fn line_column(&self, offset: usize, encoding: ColumnEncoding) -> LineColumn {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_of_offset(offset);
+        let prefix = &self.text[self.line_starts[line]..offset];
+        let column = match encoding {
+            ColumnEncoding::Utf8 => prefix.len(),
+            ColumnEncoding::Utf16 => prefix.encode_utf16().count(),
+        };
+        LineColumn { line, column }
+    }
+
+    /// Byte offset of a line and column, or `None` if the line does not
+    /// exist. Columns past the end of the line are clamped to it.
+    pub fn offset(&self, position: LineColumn, encoding: ColumnEncoding) -> Option<usize> {
+        let start = self.line_start(position.line)?;
+        let line = self.line_text(position.line)?;
+        let mut units = 0;
+        for (i, ch) in line.char_indices() {
+            if units >= position.column {
+                return Some(start + i);
+            }
+            units += match encoding {
+                ColumnEncoding::Utf8 => ch.len_utf8(),
+                ColumnEncoding::Utf16 => ch.len_utf16(),
+            };
+        }
+        Some(start + line.len())
+    }
+
+    /// Start and end positions of a location within this text.
+    pub fn range(&self, location: &Location, encoding: ColumnEncoding) -> (LineColumn, LineColumn) {
+        (self.line_column(location.first_offset(), encoding), self.line_column(location.last_offset(), encoding))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Sources of a `SourceProvider` with some files replaced or added,
+/// leaving the underlying provider untouched.
+pub struct OverlaySourceProvider<'a> {
+    pub base: &'a dyn SourceProvider,
+    /// Replaced and added texts keyed by path.
+    pub files: BTreeMap<String, String>,
+}
+
+impl<'a> OverlaySourceProvider<'a> {
+    pub fn new(base: &'a dyn SourceProvider) -> Self {
+        Self { base, files: BTreeMap::new() }
+    }
+}
+
+impl<'a> SourceProvider for OverlaySourceProvider<'a> {
+    fn source_files(&self) -> Vec<String> {
+        let mut r = self.base.source_files();
+        for path in self.files.keys() {
+            if (path.ends_with(".as") || path.ends_with(".mxml")) && !r.contains(path) {
+                r.push(path.clone());
+            }
+        }
+        r.sort();
+        r
+    }
+
+    fn read_to_string(&self, path: &str) -> Option<String> {
+        self.files.get(path).cloned().or_else(|| self.base.read_to_string(path))
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.files.get(path).map(|text| text.clone().into_bytes()).or_else(|| self.base.read(path))
+    }
+
+    fn read_dir(&self, dir: &str) -> Vec<String> {
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        let mut r = self.base.read_dir(dir);
+        r.extend(self.files.keys().filter(|p| p.strip_prefix(&prefix).map(|rest| !rest.contains('/')).unwrap_or(false)).cloned());
+        r.sort();
+        r.dedup();
+        r
+    }
+}
+
+/// A diagnostic compared by `DiagnosticDelta`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct SpeculativeDiagnostic {
+    pub file_path: String,
+    pub id: i32,
+    pub message: String,
+    pub is_warning: bool,
+    /// One-based line number in the text the diagnostic was reported for.
+    pub line: usize,
+}
+
+/// Diagnostics introduced and resolved by a speculative edit.
+///
+/// Diagnostics are matched by file, ID and message, so that those only
+/// moved by the edit are in neither list.
+#[derive(Clone, Default, Debug)]
+pub struct DiagnosticDelta {
+    pub introduced: Vec<SpeculativeDiagnostic>,
+    pub resolved: Vec<SpeculativeDiagnostic>,
+}
+
+impl DiagnosticDelta {
+    /// Whether the edit introduces any error.
+    pub fn introduces_errors(&self) -> bool {
+        self.introduced.iter().any(|d| !d.is_warning)
+    }
+}
+
+/// Verifies proposed edits without applying them, answering questions
+/// such as "will this rename introduce errors" for language servers.
+///
+/// ```ignore
+/// let speculation = SpeculativeVerification::new();
+/// let mut edits = BTreeMap::new();
+/// edits.insert("src/Main.as".to_owned(), batch);
+/// let delta = speculation.verify(&compiler_options, &sources, &edits, None)?;
+/// if delta.introduces_errors() {
+///     // warn before applying the rename
+/// }
+/// ```
+///
+/// The edited sources are read through an `OverlaySourceProvider` and
+/// verified in a separate `Database`, leaving the caller's `Database`
+/// untouched. A `baseline` compilation of the unedited sources may be
+/// given to avoid compiling them again.
+///
+/// Only the parsed programs are shared: the programs of files left
+/// unchanged are reused across verifications through a `ParseCache`, and
+/// only the edited files and those holding diagnostics from a previous
+/// verification are parsed again. The semantic model is not shared, as
+/// the `Database` has no copy-on-write overlay; every speculative
+/// verification verifies all sources again in a new `Database`. The
+/// programs of a `baseline` are not shared, as the diagnostics of the
+/// speculative verification would accumulate in them.
+#[derive(Default)]
+pub struct SpeculativeVerification {
+    parse_cache: ParseCache,
+}
+
+impl SpeculativeVerification {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn verify(&self, compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider, edits: &BTreeMap<String, SourceEditBatch>, baseline: Option<&CompilationOutput>) -> Result<DiagnosticDelta, SourceEditError> {
+        let mut overlay = OverlaySourceProvider::new(sources);
+        for (path, batch) in edits.iter() {
+            let text = sources.read_to_string(path).unwrap_or_default();
+            overlay.files.insert(path.clone(), batch.apply(&text)?);
+        }
+
+        let before = match baseline {
+            Some(output) => Self::diagnostics(output),
+            None => {
+                let host = Rc::new(Database::new(Default::default()));
+                Self::diagnostics(&Compilation::run_with_cache(&host, compiler_options, sources, &[], None, &self.parse_cache))
+            },
+        };
+        let host = Rc::new(Database::new(Default::default()));
+        let after = Self::diagnostics(&Compilation::run_with_cache(&host, compiler_options, &overlay, &[], None, &self.parse_cache));
+
+        Ok(DiagnosticDelta {
+            introduced: Self::difference(&after, &before),
+            resolved: Self::difference(&before, &after),
+        })
+    }
+
+    fn diagnostics(output: &CompilationOutput) -> Vec<SpeculativeDiagnostic> {
+        let mut r = vec![];
+        for cu in output.compilation_units.iter() {
+            cu.sort_diagnostics();
+            for diagnostic in cu.nested_diagnostics().iter() {
+                let d = WhackDiagnostic(diagnostic);
+                r.push(SpeculativeDiagnostic {
+                    file_path: cu.file_path().unwrap_or_default(),
+                    id: d.id(),
+                    message: d.format_message_english(),
+                    is_warning: diagnostic.is_warning(),
+                    line: diagnostic.location().first_line_number(),
+                });
+            }
+        }
+        r
+    }
+
+    /// Diagnostics of `a` not matched by one of `b`, each diagnostic of
+    /// `b` matching at most once.
+    fn difference(a: &[SpeculativeDiagnostic], b: &[SpeculativeDiagnostic]) -> Vec<SpeculativeDiagnostic> {
+        let mut remaining = BTreeMap::<(&str, i32, &str), usize>::new();
+        for d in b.iter() {
+            *remaining.entry((d.file_path.as_str(), d.id, d.message.as_str())).or_insert(0) += 1;
+        }
+        let mut r = vec![];
+        for d in a.iter() {
+            match remaining.get_mut(&(d.file_path.as_str(), d.id, d.message.as_str())) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => r.push(d.clone()),
+            }
+        }
+        r
+    }
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// A project root of a `WorkspaceSession`, such as an application or
+/// one of its library projects.
+#[derive(Clone, Default)]
+pub struct WorkspaceRoot {
+    pub name: String,
+    pub source_path: Vec<String>,
+    /// ABC files and SWFs whose public definitions are resolved as
+    /// `native` definitions.
+    pub library_path: Vec<String>,
+    /// Configuration constants, as `CONFIG::debug` to `true`.
+    pub defines: BTreeMap<String, String>,
+    /// Names of the sibling roots whose sources this root refers to.
+    pub dependencies: Vec<String>,
+}
+
+/// Hosts several project roots in one session, each with its own source
+/// path, library path and configuration constants.
+///
+/// ```ignore
+/// let mut session = WorkspaceSession::new(&compiler_options);
+/// session.add_root(WorkspaceRoot { name: "app".into(), source_path: vec!["app/src".into()], dependencies: vec!["lib".into()], ..default() });
+/// session.add_root(WorkspaceRoot { name: "lib".into(), source_path: vec!["lib/src".into()], ..default() });
+/// let output = session.compile("app").unwrap();
+/// ```
+///
+/// A root is compiled along with the sources of the roots it depends on,
+/// so that definitions of sibling roots resolve to their sources and
+/// `root_of_file()` maps their locations back to the owning root.
+/// Libraries are loaded once and shared by every root listing them, and
+/// the parsed files of a root are kept in a `ParseCache`, so that
+/// compiling it again parses only the files that changed. A library that
+/// fails to load is skipped and listed by `library_errors()`.
+pub struct WorkspaceSession {
+    compiler_options: Rc<CompilerOptions>,
+    roots: Vec<WorkspaceRoot>,
+    libraries: HashMap<String, Rc<dyn DefinitionProvider>>,
+    /// Library path entries that failed to load in the last compilation
+    /// of each root, with the reason.
+    library_errors: HashMap<String, Vec<(String, String)>>,
+    outputs: HashMap<String, (Rc<Database>, Rc<CompilationOutput>)>,
+    /// Compiler options of the last compilation of each root, and the
+    /// files it parsed.
+    parse_caches: HashMap<String, (Rc<CompilerOptions>, ParseCache)>,
+}
+
+impl WorkspaceSession {
+    pub fn new(compiler_options: &Rc<CompilerOptions>) -> Self {
+        Self {
+            compiler_options: compiler_options.clone(),
+            roots: vec![],
+            libraries: HashMap::new(),
+            library_errors: HashMap::new(),
+            outputs: HashMap::new(),
+            parse_caches: HashMap::new(),
+        }
+    }
+
+    /// Adds a root, replacing one of the same name.
+    pub fn add_root(&mut self, root: WorkspaceRoot) {
+        self.outputs.remove(&root.name);
+        self.parse_caches.remove(&root.name);
+        self.library_errors.remove(&root.name);
+        self.roots.retain(|r| r.name != root.name);
+        self.roots.push(root);
+    }
+
+    pub fn remove_root(&mut self, name: &str) {
+        self.outputs.remove(name);
+        self.parse_caches.remove(name);
+        self.library_errors.remove(name);
+        self.roots.retain(|r| r.name != name);
+    }
+
+    pub fn roots(&self) -> &[WorkspaceRoot] {
+        &self.roots
+    }
+
+    pub fn root(&self, name: &str) -> Option<&WorkspaceRoot> {
+        self.roots.iter().find(|r| r.name == name)
+    }
+
+    /// Supplies the definitions of a library path entry, such as the
+    /// library of a SWC extracted by the caller, instead of loading it.
+    pub fn insert_library(&mut self, path: &str, provider: Rc<dyn DefinitionProvider>) {
+        self.libraries.insert(path.to_owned(), provider);
+    }
+
+    /// Returns the root whose source path contains a file, preferring
+    /// the most specific source path.
+    pub fn root_of_file(&self, file_path: &str) -> Option<&WorkspaceRoot> {
+        let file_path = file_path.replace('\\', "/");
+        self.roots.iter()
+            .filter_map(|root| {
+                let longest = root.source_path.iter()
+                    .map(|dir| format!("{}/", dir.replace('\\', "/").trim_end_matches('/')))
+                    .filter(|dir| file_path.starts_with(dir.as_str()))
+                    .map(|dir| dir.len())
+                    .max()?;
+                Some((longest, root))
+            })
+            .max_by_key(|(longest, _)| *longest)
+            .map(|(_, root)| root)
+    }
+
+    /// Library path entries of a root that failed to load in its last
+    /// compilation, with the reason.
+    pub fn library_errors(&self, name: &str) -> &[(String, String)] {
+        self.library_errors.get(name).map(|errors| errors.as_slice()).unwrap_or(&[])
+    }
+
+    /// The `Database` and output of the last compilation of a root.
+    pub fn output(&self, name: &str) -> Option<(Rc<Database>, Rc<CompilationOutput>)> {
+        self.outputs.get(name).cloned()
+    }
+
+    /// Compiles a root with the sources of its dependencies, returning
+    /// `None` if there is no root of that name.
+    #[cfg(feature = "fs")]
+    pub fn compile(&mut self, name: &str) -> Option<Rc<CompilationOutput>> {
+        let root = self.root(name)?.clone();
+        let mut source_path = vec![];
+        for dependency in self.dependency_roots(&root).iter() {
+            source_path.extend(dependency.source_path.iter().cloned());
+        }
+        source_path.extend(root.source_path.iter().cloned());
+
+        let mut providers = vec![];
+        let mut library_errors = vec![];
+        for path in root.library_path.iter() {
+            match self.library(path) {
+                Ok(provider) => providers.push(provider),
+                Err(error) => library_errors.push((path.clone(), error)),
+            }
+        }
+        self.library_errors.insert(root.name.clone(), library_errors);
+
+        // Parsed files are reused while the compiler options are the same
+        let (compiler_options, parse_cache) = match self.parse_caches.remove(&root.name) {
+            Some((compiler_options, parse_cache)) if compiler_options.source_path == source_path => (compiler_options, parse_cache),
+            _ => (Rc::new(CompilerOptions {
+                source_path: source_path.clone(),
+                ..(*self.compiler_options).clone()
+            }), ParseCache::new()),
+        };
+        let host = Rc::new(Database::new(Default::default()));
+        for (name, value) in root.defines.iter() {
+            host.config_constants().set(name.clone(), value.clone());
+        }
+        let sources = FileSystemSourceProvider { source_path };
+        let output = Rc::new(Compilation::run_with_cache(&host, &compiler_options, &sources, &providers, None, &parse_cache));
+        self.outputs.insert(root.name.clone(), (host, output.clone()));
+        self.parse_caches.insert(root.name.clone(), (compiler_options, parse_cache));
+        Some(output)
+    }
+
+    /// Roots a root depends on, transitively, excluding itself.
+    fn dependency_roots(&self, root: &WorkspaceRoot) -> Vec<WorkspaceRoot> {
+        let mut visited = vec![root.name.clone()];
+        let mut pending = root.dependencies.clone();
+        let mut r = vec![];
+        while let Some(name) = pending.pop() {
+            if visited.contains(&name) {
+                continue;
+            }
+            visited.push(name.clone());
+            if let Some(dependency) = self.root(&name) {
+                pending.extend(dependency.dependencies.iter().cloned());
+                r.push(dependency.clone());
+            }
+        }
+        r
+    }
+
+    /// Loads a library path entry, sharing it across roots. The provider
+    /// is named after the path, as are the origins of conflicting
+    /// definitions.
+    #[cfg(feature = "fs")]
+    fn library(&mut self, path: &str) -> Result<Rc<dyn DefinitionProvider>, String> {
+        if let Some(provider) = self.libraries.get(path) {
+            return Ok(provider.clone());
+        }
+        let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+        let abc_files = SwfAbcTag::read_library(path, bytes).map_err(|error| format!("{error:?}"))?;
+        let mut provider = MemoryDefinitionProvider::new(path);
+        for abc in abc_files.iter() {
+            for (definition, source) in AbcStubGenerator::generate(abc) {
+                provider.insert(definition, &source);
+            }
+        }
+        let provider: Rc<dyn DefinitionProvider> = Rc::new(provider);
+        self.libraries.insert(path.to_owned(), provider.clone());
+        Ok(provider)
+    }
+}
\ No newline at end of file
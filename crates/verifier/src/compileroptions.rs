@@ -1,2 +1,17 @@
 mod compiler_options;
-pub use compiler_options::*;
\ No newline at end of file
+pub use compiler_options::*;
+
+mod emit_options;
+pub use emit_options::*;
+
+mod language_features;
+pub use language_features::*;
+
+mod runtime_version;
+pub use runtime_version::*;
+
+mod target_profile;
+pub use target_profile::*;
+
+mod warning_config;
+pub use warning_config::*;
\ No newline at end of file
@@ -5,6 +5,101 @@ pub struct CompilerOptions {
     pub warnings: CompilerWarningOptions,
     /// Used for identifying the AS3 package in a MXML source tree.
     pub source_path: Vec<String>,
+    /// Skips semantic verification, performing only
+    /// declaration checks over the parsed programs.
+    pub syntax_only: bool,
+    /// Verifies programs containing syntax errors, skipping the
+    /// invalidated parts, so that the rest of a file still has
+    /// diagnostics and a semantic model. Partial input, such as a
+    /// dangling `obj.`, is recovered first (see `PartialInputRecovery`).
+    pub verify_with_syntax_errors: bool,
+    /// Accepts historical syntax quirks, such as trailing commas in
+    /// argument lists, with a warning each (see `LegacySyntax`).
+    pub legacy_syntax: bool,
+    /// Overrides the severity of lint rules by name.
+    pub lint_severities: HashMap<String, LintSeverity>,
+    pub api_stability: ApiStabilityOptions,
+    /// Emits debug instructions with file names, line numbers
+    /// and local register names in the generated ABC.
+    pub debug: bool,
+    /// Instruments statements and branches with coverage counters.
+    pub coverage: bool,
+    /// Instruments the entry and exits of the methods of selected packages
+    /// with calls to a tracer class.
+    pub trace_calls: Option<TraceCallsOptions>,
+    /// Renames `private` and `internal` definitions to short names in the
+    /// generated output and emits a rename map.
+    pub minify_identifiers: bool,
+    /// Meta-data tags kept in the generated output for runtime reflection,
+    /// besides those kept by `[KeepMetadata]`.
+    pub retained_metadata: Vec<String>,
+    /// SWF modules loaded at runtime, each built from the classes of its
+    /// packages. Other classes go to the main SWF.
+    pub swf_modules: Vec<SwfModuleOptions>,
+    /// Main file whose top-level statements form the frame script of a
+    /// timeline, in place of a document class.
+    pub frame_script: Option<FrameScriptOptions>,
+    /// Adds a `BuildManifest` of input and output content hashes to the
+    /// artifacts of a `Compilation`.
+    pub build_manifest: bool,
+    /// Intermediate forms added to the artifacts of a `Compilation`.
+    pub emit: EmitOptions,
+    /// Stores embedded PNG and GIF bitmaps as is rather than decoded.
+    pub optimize_embedded_bitmaps: bool,
+    /// Adds a size report of the embedded bitmaps to the artifacts of
+    /// a `Compilation`.
+    pub embedded_bitmap_report: bool,
+    /// Adds the manifest of the test methods found by `TestDiscovery` to
+    /// the artifacts of a `Compilation`, and optionally a runner class.
+    pub test_discovery: Option<TestDiscoveryOptions>,
+    /// Reports methods whose estimated limits exceed these, as warnings
+    /// and through `CompilerEvent::MethodLimitExceeded`.
+    pub method_limit_thresholds: Option<MethodLimitThresholds>,
+    /// Generates `fromJSON()` and `toJSON()` static methods for classes
+    /// marked with `[JsonType]` meta-data.
+    pub json_type_helpers: bool,
+    /// Resolves `+`, `-`, `*`, `==` and `!=` over class operands to
+    /// static operator methods, such as `op_add()`.
+    pub operator_overloading: bool,
+    /// Fully qualified name of a Promise-like class whose instances
+    /// `await` accepts besides `Promise`, as `com.example.Future`. The
+    /// result of awaiting a parameterized class is its first type
+    /// argument, and otherwise `*`.
+    pub promise_like_type: Option<String>,
+    /// Treats function parameters as constants, reporting their
+    /// reassignment.
+    pub const_parameters: bool,
+    /// Restricts language extensions, such as optional chaining, to the
+    /// packages and files opting into them. All are enabled if `None`.
+    pub language_features: Option<LanguageFeatureOptions>,
+    /// Locales to verify and embed resource bundles for, such as `en_US`.
+    pub locales: Vec<String>,
+    /// Directories containing `.properties` files, where `{locale}`
+    /// is replaced by each locale name.
+    pub locale_source_path: Vec<String>,
+    /// Generates a pseudo-locale of this name from the first locale.
+    pub pseudo_locale: Option<String>,
+    /// Records every implicit coercion from `*` or `Object` to a more
+    /// specific type into `Verifier::coercion_audit_report()`.
+    pub coercion_audit: bool,
+    /// Records every property access that could not be resolved
+    /// statically into `Verifier::dynamic_access_audit_report()`, which
+    /// `Compilation` writes to `DynamicAccessAuditReport::FILE_NAME`.
+    pub dynamic_access_audit: bool,
+    /// Records the regions eliminated by configuration constants into
+    /// `Verifier::config_elimination_report()`, checking the constants
+    /// referenced within them.
+    pub config_elimination_report: bool,
+    /// Core library loaded by `Compilation` before the sources.
+    pub core_library: CoreLibrarySource,
+    /// Runtime whose APIs the sources may reference.
+    pub target_profile: TargetProfile,
+    /// Runtime version the sources are compiled for. References to APIs
+    /// introduced by later versions are reported.
+    pub target_runtime_version: Option<RuntimeVersion>,
+    /// IDs of the loaded native extensions. When set, constant IDs given to
+    /// `ExtensionContext.createExtensionContext()` must be among them.
+    pub native_extension_ids: Option<Vec<String>>,
 }
 
 impl CompilerOptions {
@@ -18,6 +113,101 @@ impl CompilerOptions {
 #[non_exhaustive]
 pub struct CompilerWarningOptions {
     pub unused: bool,
+    /// Reads warning levels from `WarningConfig::FILE_NAME` files
+    /// under the source path.
+    pub directory_config: bool,
+    /// Warns on `throw` statements reachable from event handlers
+    /// without an enclosing `try` statement.
+    pub uncaught_handler_errors: bool,
+    /// Warns on methods with `[Throws]` meta-data throwing
+    /// types they do not declare.
+    pub undeclared_throws: bool,
+    /// Warns on functions created inside a loop capturing a
+    /// variable declared by the loop heading.
+    pub loop_variable_capture: bool,
+    /// Warns on variables of a loop body redeclaring a variable
+    /// of the loop heading.
+    pub loop_variable_redeclaration: bool,
+    /// Warns on string `switch` cases differing only in letter case.
+    pub switch_case_letter_case: bool,
+    /// Warns on `switch` cases falling through into the next one, unless
+    /// a comment mentioning fallthrough precedes the next case.
+    pub switch_fallthrough: bool,
+    /// Warns on expression statements discarding the result of a
+    /// `[Pure]` method or of a core method such as `String.replace()`.
+    pub discarded_results: bool,
+    /// Warns on lossy `int`, `uint` and `Number` arithmetic, such as
+    /// subtraction from a `uint` and division of integers.
+    pub numeric_promotion: bool,
+    /// Warns on `describeType()` calls over classes whose meta-data is
+    /// partly stripped from the generated output.
+    pub stripped_metadata: bool,
+    pub shadowing: ShadowingWarningOptions,
+}
+
+/// Kinds of definitions whose shadowing by a local declaration
+/// is reported, none by default.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct ShadowingWarningOptions {
+    pub parameters: bool,
+    /// Locals of enclosing blocks and functions.
+    pub outer_locals: bool,
+    /// Instance members of the enclosing class, accessible without `this`.
+    pub instance_members: bool,
+}
+
+/// Severities of references to `[Experimental]` and `[InternalAPI]`
+/// definitions from outside their owning package.
+#[derive(Clone)]
+pub struct ApiStabilityOptions {
+    pub experimental: LintSeverity,
+    pub internal_api: LintSeverity,
+}
+
+/// A SWF module partitioned by `CodegenModuleSplit`.
+#[derive(Clone)]
+pub struct SwfModuleOptions {
+    pub name: String,
+    /// Packages whose classes, including these of subpackages, are
+    /// built into the module.
+    pub packages: Vec<String>,
+}
+
+/// A frame script emitted by `CodegenFrameScript`.
+#[derive(Clone)]
+pub struct FrameScriptOptions {
+    /// Path of the main file, or a suffix of it, as `src/main.as`.
+    pub file_path: String,
+    /// Fully qualified name of the class `this` refers to in the script.
+    pub timeline_class: String,
+}
+
+impl FrameScriptOptions {
+    pub fn is_frame_script(&self, file_path: &str) -> bool {
+        let file_path = file_path.replace('\\', "/");
+        let main = self.file_path.replace('\\', "/");
+        file_path == main || file_path.ends_with(&format!("/{}", main.trim_start_matches("./")))
+    }
+}
+
+/// Methods instrumented by `CodegenTraceCalls`.
+#[derive(Clone)]
+pub struct TraceCallsOptions {
+    /// Traced packages, including their subpackages, such as
+    /// `com.example`. If empty, every package is traced.
+    pub packages: Vec<String>,
+    /// Fully qualified name of the class whose static `enter()` and
+    /// `exit()` methods receive the traced method name.
+    pub callback_class: String,
+}
+
+/// Test methods discovered by `TestDiscovery` in a `Compilation`.
+#[derive(Clone, Default)]
+pub struct TestDiscoveryOptions {
+    /// Name of the top-level runner class generated by
+    /// `TestManifest::generate_runner()`, if any.
+    pub runner_class: Option<String>,
 }
 
 impl Default for CompilerOptions {
@@ -25,6 +215,39 @@ impl Default for CompilerOptions {
         Self {
             warnings: Default::default(),
             source_path: vec![],
+            syntax_only: false,
+            verify_with_syntax_errors: false,
+            legacy_syntax: false,
+            lint_severities: HashMap::new(),
+            api_stability: Default::default(),
+            debug: false,
+            coverage: false,
+            trace_calls: None,
+            minify_identifiers: false,
+            retained_metadata: ["Bindable", "ChangeEvent", "Managed", "NonCommittingChangeEvent", "Transient"].iter().map(|s| s.to_string()).collect(),
+            swf_modules: vec![],
+            frame_script: None,
+            build_manifest: false,
+            emit: Default::default(),
+            optimize_embedded_bitmaps: false,
+            embedded_bitmap_report: false,
+            test_discovery: None,
+            method_limit_thresholds: None,
+            json_type_helpers: false,
+            operator_overloading: false,
+            promise_like_type: None,
+            const_parameters: false,
+            language_features: None,
+            locales: vec![],
+            locale_source_path: vec![],
+            pseudo_locale: None,
+            coercion_audit: false,
+            dynamic_access_audit: false,
+            config_elimination_report: false,
+            core_library: CoreLibrarySource::Embedded,
+            target_profile: TargetProfile::Avm2,
+            target_runtime_version: None,
+            native_extension_ids: None,
         }
     }
 }
@@ -33,6 +256,54 @@ impl Default for CompilerWarningOptions {
     fn default() -> Self {
         Self {
             unused: true,
+            directory_config: true,
+            uncaught_handler_errors: false,
+            undeclared_throws: false,
+            loop_variable_capture: true,
+            loop_variable_redeclaration: false,
+            switch_case_letter_case: false,
+            switch_fallthrough: false,
+            discarded_results: true,
+            numeric_promotion: false,
+            stripped_metadata: false,
+            shadowing: Default::default(),
+        }
+    }
+}
+
+impl Default for ShadowingWarningOptions {
+    fn default() -> Self {
+        Self {
+            parameters: false,
+            outer_locals: false,
+            instance_members: false,
+        }
+    }
+}
+
+impl Default for TraceCallsOptions {
+    fn default() -> Self {
+        Self {
+            packages: vec![],
+            callback_class: "whack.trace.CallTracer".into(),
+        }
+    }
+}
+
+impl Default for FrameScriptOptions {
+    fn default() -> Self {
+        Self {
+            file_path: String::new(),
+            timeline_class: "flash.display.MovieClip".into(),
+        }
+    }
+}
+
+impl Default for ApiStabilityOptions {
+    fn default() -> Self {
+        Self {
+            experimental: LintSeverity::Warning,
+            internal_api: LintSeverity::Error,
         }
     }
 }
\ No newline at end of file
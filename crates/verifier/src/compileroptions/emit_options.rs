@@ -0,0 +1,82 @@
+/// Intermediate form dumped by a `Compilation` as an artifact.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum IntermediateForm {
+    /// Parsed programs, as `<file>.ast.json`.
+    Ast,
+    /// Verified programs with their entities and static types, as
+    /// `<file>.typed-ast.json`.
+    TypedAst,
+    /// Control-flow graphs of the top-level statements and function
+    /// bodies, as `<file>.cfg.dot`.
+    Cfg,
+    /// Methods planned for the source files with their limits and the
+    /// lowering of their constructs, as `<file>.abc-asm.txt` (see
+    /// `CodegenListing`).
+    AbcAsm,
+}
+
+impl IntermediateForm {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ast => "ast",
+            Self::TypedAst => "typed-ast",
+            Self::Cfg => "cfg",
+            Self::AbcAsm => "abc-asm",
+        }
+    }
+
+    /// Extension of the artifacts dumping a source file.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Cfg => "dot",
+            Self::AbcAsm => "txt",
+            _ => "json",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ast" => Some(Self::Ast),
+            "typed-ast" => Some(Self::TypedAst),
+            "cfg" => Some(Self::Cfg),
+            "abc-asm" => Some(Self::AbcAsm),
+            _ => None,
+        }
+    }
+}
+
+/// Intermediate forms to dump, for debugging how sources compile.
+#[derive(Clone, Default)]
+pub struct EmitOptions {
+    pub forms: Vec<IntermediateForm>,
+    /// Paths of the source files to dump, or suffixes of them, as
+    /// `src/Main.as`. If empty, every source file is dumped.
+    pub files: Vec<String>,
+}
+
+impl EmitOptions {
+    pub fn includes(&self, form: IntermediateForm) -> bool {
+        self.forms.contains(&form)
+    }
+
+    pub fn selects(&self, file_path: &str) -> bool {
+        let file_path = file_path.replace('\\', "/");
+        self.files.is_empty() || self.files.iter().any(|f| {
+            let f = f.replace('\\', "/");
+            file_path == f || file_path.ends_with(&format!("/{}", f.trim_start_matches("./")))
+        })
+    }
+
+    /// Path of the artifact dumping a source file, relative to the
+    /// source path entry containing it.
+    pub fn artifact_path(&self, source_path: &[String], file_path: &str, form: IntermediateForm) -> String {
+        let file_path = file_path.replace('\\', "/");
+        let relative = source_path.iter()
+            .map(|dir| format!("{}/", dir.replace('\\', "/").trim_end_matches('/')))
+            .filter(|dir| file_path.starts_with(dir.as_str()))
+            .max_by_key(|dir| dir.len())
+            .map(|dir| file_path[dir.len()..].to_owned())
+            .unwrap_or_else(|| file_path.rsplit('/').next().unwrap_or_default().to_owned());
+        format!("{relative}.{}.{}", form.name(), form.extension())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Language extension beyond ActionScript 3.0 that files and packages
+/// opt into when `CompilerOptions::language_features` is set.
+///
+/// `let` bindings and arrow functions are not parsed yet and have no
+/// feature of their own.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LanguageFeature {
+    /// `o?.x`, `o?.[k]` and `f?.()`.
+    OptionalChaining,
+    /// `a ?? b` and `a ??= b`.
+    NullCoalescing,
+    /// `a ** b` and `a **= b`.
+    Exponentiation,
+}
+
+impl LanguageFeature {
+    pub const ALL: [LanguageFeature; 3] = [Self::OptionalChaining, Self::NullCoalescing, Self::Exponentiation];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OptionalChaining => "optional-chaining",
+            Self::NullCoalescing => "null-coalescing",
+            Self::Exponentiation => "exponentiation",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|feature| feature.name() == name)
+    }
+}
+
+/// Language features enabled per package and per file.
+///
+/// A file enables features for itself through a line comment pragma:
+///
+/// ```plain
+/// // @whack-features optional-chaining, null-coalescing
+/// ```
+#[derive(Clone, Default)]
+pub struct LanguageFeatureOptions {
+    /// Features enabled for every file.
+    pub enabled: Vec<LanguageFeature>,
+    /// Features enabled for packages by dot-separated name, including
+    /// their subpackages.
+    pub packages: BTreeMap<String, Vec<LanguageFeature>>,
+}
+
+impl LanguageFeatureOptions {
+    pub const PRAGMA: &'static str = "@whack-features";
+
+    /// Features enabled for the contents of a package within a file
+    /// of the given text.
+    pub fn enabled_features(&self, package: &str, text: &str) -> HashSet<LanguageFeature> {
+        let mut r = self.enabled.iter().copied().collect::<HashSet<_>>();
+        for (name, features) in self.packages.iter() {
+            if package == name || package.strip_prefix(name.as_str()).map(|rest| rest.starts_with('.')).unwrap_or(false) {
+                r.extend(features.iter().copied());
+            }
+        }
+        r.extend(Self::pragma_features(text));
+        r
+    }
+
+    /// Features named by the pragmas of a file. Unknown names are ignored.
+    pub fn pragma_features(text: &str) -> Vec<LanguageFeature> {
+        let mut r = vec![];
+        for line in text.lines() {
+            let Some(comment) = line.trim().strip_prefix("//") else {
+                continue;
+            };
+            let Some(names) = comment.trim().strip_prefix(Self::PRAGMA) else {
+                continue;
+            };
+            r.extend(names.split(|ch: char| ch == ',' || ch.is_whitespace()).filter_map(LanguageFeature::from_name));
+        }
+        r
+    }
+}
\ No newline at end of file
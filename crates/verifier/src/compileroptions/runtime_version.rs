@@ -0,0 +1,30 @@
+/// Runtime version given as `major.minor`, such as `11.4` for Flash
+/// Player 11.4 and AIR 3.4.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct RuntimeVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl RuntimeVersion {
+    pub fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Parses `major` or `major.minor`, ignoring further components.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut components = version.trim().split('.');
+        let major = components.next()?.parse().ok()?;
+        let minor = match components.next() {
+            Some(minor) => minor.parse().ok()?,
+            None => 0,
+        };
+        Some(Self { major, minor })
+    }
+}
+
+impl std::fmt::Display for RuntimeVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
\ No newline at end of file
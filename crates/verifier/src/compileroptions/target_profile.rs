@@ -0,0 +1,45 @@
+/// Runtime whose global definitions are available to a compilation.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum TargetProfile {
+    /// Flash Player and AIR.
+    #[default]
+    Avm2,
+    /// Browsers through the JavaScript backend, which lack the AIR APIs.
+    BrowserJs,
+    /// Command-line and server runtimes, which lack display, media and
+    /// user interface APIs.
+    Headless,
+}
+
+impl TargetProfile {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Avm2 => "avm2",
+            Self::BrowserJs => "browser-js",
+            Self::Headless => "headless",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "avm2" => Some(Self::Avm2),
+            "browser-js" => Some(Self::BrowserJs),
+            "headless" => Some(Self::Headless),
+            _ => None,
+        }
+    }
+
+    /// Packages absent from the profile, including their subpackages.
+    pub fn unavailable_packages(&self) -> &'static [&'static str] {
+        match self {
+            Self::Avm2 => &[],
+            Self::BrowserJs => &["air", "flash.data", "flash.desktop", "flash.filesystem", "flash.html", "flash.permissions"],
+            Self::Headless => &["flash.display", "flash.display3D", "flash.filters", "flash.media", "flash.text", "flash.ui", "mx", "spark"],
+        }
+    }
+
+    /// Whether a package given by its dot-separated name is available.
+    pub fn is_package_available(&self, package: &str) -> bool {
+        !self.unavailable_packages().iter().any(|p| package == *p || package.strip_prefix(*p).map(|rest| rest.starts_with('.')).unwrap_or(false))
+    }
+}
\ No newline at end of file
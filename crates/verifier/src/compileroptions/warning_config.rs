@@ -0,0 +1,130 @@
+use crate::ns::*;
+use std::cell::RefCell;
+use std::path::Path;
+
+/// Warning levels configured per directory of the source path through
+/// [`WarningConfig::FILE_NAME`] files. A file applies to the packages of
+/// its directory and is inherited by subdirectories, whose own files
+/// override it per warning:
+///
+/// ```properties
+/// # src/com/example/legacy/whack-warnings.properties
+/// SwitchCaseFallthrough = allow
+/// 2240 = error
+/// ```
+///
+/// Keys are warning kind names or IDs, and values are `allow`, `warning`
+/// or `error`. Files are read once per directory and the levels are
+/// resolved once per compilation unit.
+pub struct WarningConfig {
+    /// Provider of the files of directories not read in advance.
+    sources: Option<Rc<dyn SourceProvider>>,
+    roots: Vec<String>,
+    /// Levels of the file directly under each directory read so far.
+    directories: RefCell<HashMap<String, Option<Rc<HashMap<i32, LintSeverity>>>>>,
+    /// Resolved levels per file path.
+    resolved: RefCell<HashMap<String, Rc<HashMap<i32, LintSeverity>>>>,
+}
+
+impl WarningConfig {
+    pub const FILE_NAME: &'static str = "whack-warnings.properties";
+
+    /// Reads configuration files from the file system under the
+    /// given source path.
+    #[cfg(feature = "fs")]
+    pub fn load(source_path: &[String]) -> Self {
+        Self::load_from(Rc::new(FileSystemSourceProvider::default()), source_path)
+    }
+
+    pub fn load_from(sources: Rc<dyn SourceProvider>, source_path: &[String]) -> Self {
+        Self {
+            sources: Some(sources),
+            roots: source_path.to_vec(),
+            directories: RefCell::new(HashMap::new()),
+            resolved: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Reads the configuration files applying to the given programs,
+    /// as `Compilation` does from its sources.
+    pub fn read_from(sources: &dyn SourceProvider, programs: &[Rc<Program>], source_path: &[String]) -> Self {
+        let r = Self {
+            sources: None,
+            roots: source_path.to_vec(),
+            directories: RefCell::new(HashMap::new()),
+            resolved: RefCell::new(HashMap::new()),
+        };
+        for program in programs.iter() {
+            let Some(file_path) = program.location.compilation_unit().file_path() else {
+                continue;
+            };
+            for dir in r.directories_of(&file_path) {
+                if r.directories.borrow().contains_key(&dir) {
+                    continue;
+                }
+                let path = Path::new(&dir).join(Self::FILE_NAME);
+                let levels = sources.read_to_string(&path.to_string_lossy()).map(|text| Rc::new(Self::parse(&text)));
+                r.directories.borrow_mut().insert(dir, levels);
+            }
+        }
+        r
+    }
+
+    /// Returns the configured level of a warning at a location, or `None`
+    /// if the warning keeps its default level.
+    pub fn level(&self, location: &Location, kind: WhackDiagnosticKind) -> Option<LintSeverity> {
+        let file_path = location.compilation_unit().file_path()?;
+        self.resolve(&file_path).get(&kind.id()).copied()
+    }
+
+    fn resolve(&self, file_path: &str) -> Rc<HashMap<i32, LintSeverity>> {
+        if let Some(levels) = self.resolved.borrow().get(file_path) {
+            return levels.clone();
+        }
+        let mut levels = HashMap::new();
+        for dir in self.directories_of(file_path) {
+            if let Some(file_levels) = self.directory_levels(&dir) {
+                levels.extend(file_levels.iter().map(|(k, v)| (*k, *v)));
+            }
+        }
+        let levels = Rc::new(levels);
+        self.resolved.borrow_mut().insert(file_path.to_owned(), levels.clone());
+        levels
+    }
+
+    /// Directories from the innermost source root containing a file to
+    /// the directory of the file, outermost first, so that nested files
+    /// override.
+    fn directories_of(&self, file_path: &str) -> Vec<String> {
+        let path = Path::new(file_path);
+        let Some(root) = self.roots.iter().map(Path::new).filter(|root| path.starts_with(root)).max_by_key(|root| root.components().count()) else {
+            return vec![];
+        };
+        let mut directories = path.ancestors().skip(1).take_while(|dir| dir.starts_with(root)).map(|dir| dir.to_string_lossy().into_owned()).collect::<Vec<_>>();
+        directories.reverse();
+        directories
+    }
+
+    fn directory_levels(&self, dir: &str) -> Option<Rc<HashMap<i32, LintSeverity>>> {
+        if let Some(levels) = self.directories.borrow().get(dir) {
+            return levels.clone();
+        }
+        let path = Path::new(dir).join(Self::FILE_NAME);
+        let levels = self.sources.as_ref()?.read_to_string(&path.to_string_lossy()).map(|text| Rc::new(Self::parse(&text)));
+        self.directories.borrow_mut().insert(dir.to_owned(), levels.clone());
+        levels
+    }
+
+    fn parse(text: &str) -> HashMap<i32, LintSeverity> {
+        PropertiesFile::parse(text).entries.iter().filter_map(|(key, value)| {
+            let kind = key.parse::<i32>().ok().and_then(WhackDiagnosticKind::from_id).or_else(|| WhackDiagnosticKind::from_name(key))?;
+            let level = match value.trim().to_lowercase().as_str() {
+                "allow" => LintSeverity::Allow,
+                "warning" => LintSeverity::Warning,
+                "error" => LintSeverity::Error,
+                _ => return None,
+            };
+            Some((kind.id(), level))
+        }).collect()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+mod core_library;
+pub use core_library::*;
\ No newline at end of file
@@ -0,0 +1,85 @@
+use crate::ns::*;
+
+/// Core library verified into a `Database` before the first programs
+/// verified by a `Verifier`, unless they include it.
+#[derive(Clone, Default)]
+pub enum CoreLibrarySource {
+    /// The definitions embedded in this crate (see `CoreLibrary`).
+    #[default]
+    Embedded,
+    /// Substitutes the core with the given `(file path, text)` sources.
+    Custom(Vec<(String, String)>),
+    /// Loads no core library, such as when compiling the core itself.
+    Omitted,
+}
+
+thread_local! {
+    /// Core library programs shared by the verifiers of a thread.
+    static CORE_LIBRARY_CACHE: ParseCache = ParseCache::new();
+}
+
+/// The AS3 core classes (`Object`, `Array`, `Vector`, `String`, `Number`,
+/// `XML`, `Dictionary`, `Map`, `Set`, `ByteArray`, `Endian` and the `Error`
+/// hierarchy), declared as native definitions and embedded in the crate.
+pub struct CoreLibrary;
+
+impl CoreLibrary {
+    /// Version of the embedded definitions, bumped on every change to them.
+    pub const VERSION: &'static str = "1.2.0";
+
+    /// Prefix of the file paths of embedded sources.
+    pub const PATH_PREFIX: &'static str = "corelib:";
+
+    const EMBEDDED: [(&'static str, &'static str); 12] = [
+        ("Object.as", include_str!("../../corelib/Object.as")),
+        ("String.as", include_str!("../../corelib/String.as")),
+        ("Number.as", include_str!("../../corelib/Number.as")),
+        ("Array.as", include_str!("../../corelib/Array.as")),
+        ("Vector.as", include_str!("../../corelib/Vector.as")),
+        ("XML.as", include_str!("../../corelib/XML.as")),
+        ("Error.as", include_str!("../../corelib/Error.as")),
+        ("Dictionary.as", include_str!("../../corelib/Dictionary.as")),
+        ("Map.as", include_str!("../../corelib/Map.as")),
+        ("Set.as", include_str!("../../corelib/Set.as")),
+        ("ByteArray.as", include_str!("../../corelib/ByteArray.as")),
+        ("Endian.as", include_str!("../../corelib/Endian.as")),
+    ];
+
+    pub fn embedded_sources() -> Vec<(String, String)> {
+        Self::EMBEDDED.iter().map(|(name, text)| (format!("{}{name}", Self::PATH_PREFIX), (*text).to_owned())).collect()
+    }
+
+    pub fn sources(source: &CoreLibrarySource) -> Vec<(String, String)> {
+        match source {
+            CoreLibrarySource::Embedded => Self::embedded_sources(),
+            CoreLibrarySource::Custom(sources) => sources.clone(),
+            CoreLibrarySource::Omitted => vec![],
+        }
+    }
+
+    /// Parses the core library selected by `compiler_options.core_library`,
+    /// reusing the programs parsed earlier on the same thread with the
+    /// same compiler options, so that each `Verifier` does not parse the
+    /// core library again.
+    pub fn parse(compiler_options: &Rc<CompilerOptions>) -> (Vec<Rc<CompilationUnit>>, Vec<Rc<Program>>) {
+        CORE_LIBRARY_CACHE.with(|cache| Self::parse_with_cache(compiler_options, cache))
+    }
+
+    /// Parses the core library selected by `compiler_options.core_library`,
+    /// reusing the programs of `cache`.
+    pub fn parse_with_cache(compiler_options: &Rc<CompilerOptions>, cache: &ParseCache) -> (Vec<Rc<CompilationUnit>>, Vec<Rc<Program>>) {
+        let mut compilation_units = vec![];
+        let mut programs = vec![];
+        for (path, text) in Self::sources(&compiler_options.core_library) {
+            let (cu, program) = cache.parse(&path, &text, compiler_options, || {
+                let cu = CompilationUnit::new(Some(path.clone()), text.clone());
+                cu.set_compiler_options(Some(compiler_options.clone()));
+                let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+                (cu, program)
+            });
+            programs.push(program);
+            compilation_units.push(cu);
+        }
+        (compilation_units, programs)
+    }
+}
\ No newline at end of file
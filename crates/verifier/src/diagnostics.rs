@@ -2,4 +2,10 @@ mod whack_diagnostic;
 pub use whack_diagnostic::*;
 
 mod whack_diagnostic_kind;
-pub use whack_diagnostic_kind::*;
\ No newline at end of file
+pub use whack_diagnostic_kind::*;
+
+mod whack_diagnostic_explanation;
+pub use whack_diagnostic_explanation::*;
+
+mod related_information;
+pub use related_information::*;
\ No newline at end of file
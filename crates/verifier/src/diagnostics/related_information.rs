@@ -0,0 +1,11 @@
+use crate::ns::*;
+
+/// Secondary location attached to a diagnostic, such as the
+/// definition a declaration conflicts with.
+#[derive(Clone, Debug)]
+pub struct RelatedInformation {
+    /// Location of the diagnostic the information applies to.
+    pub location: Location,
+    pub related_location: Location,
+    pub message: String,
+}
\ No newline at end of file
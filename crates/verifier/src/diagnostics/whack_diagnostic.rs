@@ -59,6 +59,12 @@ impl<'a> WhackDiagnostic<'a> {
         self.format_message(&data::DATA)
     }
     
+    /// Unformatted English message of a diagnostic kind, with `{N}`
+    /// placeholders for arguments.
+    pub fn message_template(kind: WhackDiagnosticKind) -> String {
+        data::DATA.get(&kind.id()).cloned().unwrap_or_default()
+    }
+
     pub fn format_message(&self, messages: &HashMap<i32, String>) -> String {
         let mut string_arguments: HashMap<String, String> = hashmap!{};
         let mut i = 1;
@@ -0,0 +1,62 @@
+use crate::ns::*;
+
+#[path = "whack_diagnostics_explanations.rs"]
+mod explanations;
+
+/// Extended description of a diagnostic kind, as shown by `whack explain`.
+///
+/// Kinds without a registered explanation are described by their message.
+#[derive(Clone, Debug)]
+pub struct WhackDiagnosticExplanation {
+    pub kind: WhackDiagnosticKind,
+    pub message: String,
+    pub description: Option<String>,
+    pub example: Option<String>,
+    pub fixes: Vec<String>,
+}
+
+impl WhackDiagnosticExplanation {
+    pub fn explain(kind: WhackDiagnosticKind) -> Self {
+        let message = WhackDiagnostic::message_template(kind);
+        let Some(data) = explanations::EXPLANATIONS.get(&kind.id()) else {
+            return Self { kind, message, description: None, example: None, fixes: vec![] };
+        };
+        Self {
+            kind,
+            message,
+            description: Some(data.description.to_owned()),
+            example: data.example.map(|e| e.to_owned()),
+            fixes: data.fixes.iter().map(|f| (*f).to_owned()).collect(),
+        }
+    }
+
+    /// Finds the explanation of a diagnostic given its ID or kind name,
+    /// such as `2049` or `ImplicitCoercionToUnrelatedType`.
+    pub fn find(query: &str) -> Option<Self> {
+        let kind = match query.parse::<i32>() {
+            Ok(id) => WhackDiagnosticKind::from_id(id),
+            Err(_) => WhackDiagnosticKind::from_name(query),
+        };
+        kind.map(Self::explain)
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = format!("{:?} ({})\n\n{}\n", self.kind, self.kind.id(), self.message);
+        if let Some(description) = self.description.as_ref() {
+            r.push_str(&format!("\n{description}\n"));
+        }
+        if let Some(example) = self.example.as_ref() {
+            r.push_str("\nExample:\n\n");
+            for line in example.lines() {
+                r.push_str(&format!("    {line}\n"));
+            }
+        }
+        if !self.fixes.is_empty() {
+            r.push_str("\nCommon fixes:\n\n");
+            for fix in self.fixes.iter() {
+                r.push_str(&format!("* {fix}\n"));
+            }
+        }
+        r
+    }
+}
\ No newline at end of file
@@ -1,6 +1,29 @@
-#[repr(i32)]
-#[derive(Eq, PartialEq, Clone, Copy)]
-pub enum WhackDiagnosticKind {
+/// Declares `WhackDiagnosticKind` along with a table of its variants,
+/// so that IDs resolve to kinds without relying on their discriminants
+/// being contiguous.
+macro_rules! whack_diagnostic_kinds {
+    ($($name:ident = $id:literal,)*) => {
+        #[repr(i32)]
+        #[derive(Eq, PartialEq, Clone, Copy, Debug)]
+        pub enum WhackDiagnosticKind {
+            $($name = $id,)*
+        }
+
+        impl WhackDiagnosticKind {
+            /// Every kind, in ID order.
+            pub const ALL: &'static [WhackDiagnosticKind] = &[$(WhackDiagnosticKind::$name,)*];
+
+            pub fn from_id(id: i32) -> Option<Self> {
+                match id {
+                    $($id => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+whack_diagnostic_kinds! {
     EntityIsNotAType = 2048,
     ImplicitCoercionToUnrelatedType = 2049,
     EntityIsReadOnly = 2050,
@@ -112,10 +135,135 @@ pub enum WhackDiagnosticKind {
     ReturnNotAllowedInGlobalInit = 2156,
     NamespaceConflictsWithConfigurationNs = 2157,
     Unused = 2158,
+    LintRuleViolation = 2159,
+    ResourceBundleNotFound = 2160,
+    ResourceKeyNotFound = 2161,
+    UndefinedState = 2162,
+    IncludeInConflictsWithExcludeFrom = 2163,
+    MissingRequiredSkinPart = 2164,
+    MissingSkinState = 2165,
+    NonShareableWorkerValue = 2166,
+    WorkerClosureReferencesDisplayObject = 2167,
+    ImplicitVectorCoercion = 2168,
+    VectorConversionRequiresArrayOrVector = 2169,
+    UnknownPropertyForClass = 2170,
+    TupleIndexOutOfRange = 2171,
+    NonExhaustiveEnumSwitch = 2172,
+    ImplicitCoercionOfRawValueToEnum = 2173,
+    UnreachableSwitchTypeCase = 2174,
+    NonExhaustiveSwitchType = 2175,
+    MalformedStaticAssert = 2176,
+    StaticAssertRequiresConstant = 2177,
+    StaticAssertFailed = 2178,
+    InlineMethodMustBeFinalOrStatic = 2179,
+    InlineMethodMustHaveBody = 2180,
+    InlineMethodContainsClosure = 2181,
+    InlineMethodContainsTry = 2182,
+    TailCallNotConverted = 2183,
+    InternalCompilerError = 2184,
+    UnusedImport = 2185,
+    AmbiguousWildcardImportReference = 2186,
+    ComparisonWithUndefined = 2187,
+    VoidResultUsedAsValue = 2188,
+    ComparisonBetweenTypesWithoutCommonSubtype = 2189,
+    ImplicitNumberStringComparison = 2190,
+    DuplicateDiagnosticsSuppressed = 2191,
+    UnavailableInTargetProfile = 2192,
+    UncaughtErrorInEventHandler = 2193,
+    ThrowsMetadataTypeNotFound = 2194,
+    ThrowsMetadataTypeMustBeError = 2195,
+    UndeclaredThrownType = 2196,
+    SwitchCaseFallthrough = 2197,
+    InfiniteLoop = 2198,
+    LoopBodyNeverRuns = 2199,
+    ShadowedDeclaration = 2200,
+    ThisUsedInStaticContext = 2201,
+    SuperStatementRequiresBaseClass = 2202,
+    SuperStatementOutsideConstructor = 2203,
+    SuperStatementMustBeCalledOnce = 2204,
+    ThisUsedBeforeSuperStatement = 2205,
+    FieldNotInitializedInConstructor = 2206,
+    FieldReadBeforeInitialization = 2207,
+    StaticInitializationOrderDependency = 2208,
+    RecursiveAccessorReference = 2209,
+    InvalidMxmlId = 2210,
+    DuplicateMxmlId = 2211,
+    MxmlIdConflictsWithInheritedMember = 2212,
+    DuplicateXmlAttribute = 2213,
+    XmlEndTagMismatch = 2214,
+    UndeclaredXmlNamespacePrefix = 2215,
+    TestMethodMustHaveNoParameters = 2216,
+    TestMethodMustBePublic = 2217,
+    TestMethodMustNotBeStatic = 2218,
+    TestMethodMustBeStatic = 2219,
+    UnknownNativeExtensionId = 2220,
+    DuplicateRemoteClassAlias = 2221,
+    RemoteClassRequiresNoArgumentConstructor = 2222,
+    RemoteClassPropertyNotReadWrite = 2223,
+    NonSerializableRemoteClassProperty = 2224,
+    UnsupportedJsonTypeProperty = 2225,
+    JsonTypeRequiresNoArgumentConstructor = 2226,
+    JsonTypeHelperConflict = 2227,
+    MemberGeneratorError = 2228,
+    GeneratedMemberConflict = 2229,
+    BindableEventNotDeclared = 2231,
+    BindableGetterRequiresEvent = 2232,
+    MixinTypeNotFound = 2233,
+    MixinMemberConflict = 2234,
+    MixinMemberConflictsWithInherited = 2235,
+    AmbiguousOperatorOverload = 2236,
+    InvalidOperatorOverload = 2237,
+    AmbiguousExtensionMethod = 2238,
+    ConstParameterReassignment = 2239,
+    LoopVariableCapturedByClosure = 2240,
+    UseBeforeDeclaration = 2241,
+    DuplicateSwitchCase = 2242,
+    SwitchCaseDiffersOnlyInLetterCase = 2243,
+    NonExportedDefinitionReference = 2244,
+    ModuleExportNotFound = 2245,
+    ExperimentalApiReference = 2246,
+    InternalApiReference = 2247,
+    DefinitionConflict = 2248,
+    ApiRequiresRuntimeVersion = 2249,
+    DiscardedPureResult = 2250,
+    UintSubtractionMayWrap = 2251,
+    IntegerDivisionTruncated = 2252,
+    FractionalBitwiseOperand = 2253,
+    UnknownReflectedDefinition = 2254,
+    DescribedMetadataStripped = 2255,
+    PackageDefinitionNamedAsType = 2256,
+    FrameScriptTimelineClassNotFound = 2257,
+    LoopVariableRedeclared = 2258,
+    TestIsAlwaysTruthy = 2259,
+    MethodReferenceUsedAsTest = 2260,
+    ConditionalFunctionDeclaration = 2261,
+    LanguageFeatureNotEnabled = 2262,
+    EmbeddedFontNameMissing = 2263,
+    EmbedOptionMustBeBoolean = 2264,
+    InvalidUnicodeRange = 2265,
+    EmbeddedFileNotFound = 2266,
+    InvalidEmbeddedFont = 2267,
+    EmbeddedFontStyleMismatch = 2268,
+    EmbeddedFontMissingGlyphs = 2269,
+    LegacyTrailingComma = 2270,
+    UndefinedConfigConstantInEliminatedCode = 2271,
+    EmbeddedFontUsesTrueTypeOutlines = 2272,
+    EmbeddedFontUsesCffOutlines = 2273,
+    MethodLimitExceeded = 2274,
+    UnclosedBracket = 2275,
+    DanglingMemberAccess = 2276,
+    MxmlChildWithoutDefaultProperty = 2277,
+    IncompatibleMxmlChild = 2278,
+    SwfModuleDependencyCycle = 2279,
 }
 
 impl WhackDiagnosticKind {
     pub fn id(&self) -> i32 {
         *self as i32
     }
+
+    /// Resolves a kind from its variant name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| format!("{kind:?}") == name)
+    }
 }
\ No newline at end of file
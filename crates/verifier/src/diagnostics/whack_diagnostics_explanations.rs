@@ -0,0 +1,75 @@
+use lazy_static::lazy_static;
+use maplit::hashmap;
+use crate::ns::*;
+
+/// Extended description, example and fixes of a diagnostic kind.
+pub struct ExplanationData {
+    pub description: &'static str,
+    pub example: Option<&'static str>,
+    pub fixes: &'static [&'static str],
+}
+
+lazy_static! {
+    pub static ref EXPLANATIONS: HashMap<i32, ExplanationData> = hashmap! {
+        WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id() => ExplanationData {
+            description: "A value is used where a type it cannot implicitly convert to is expected, such as assigning a String to a variable of type Sprite.",
+            example: Some("var s: Sprite = \"text\";"),
+            fixes: &["Convert the value explicitly, as in T(value) or value as T.", "Change the declared type of the target."],
+        },
+        WhackDiagnosticKind::UndefinedProperty.id() => ExplanationData {
+            description: "A name could not be resolved in the scope chain or as a property of the base object.",
+            example: Some("trace(undeclaredName);"),
+            fixes: &["Check the spelling of the name.", "Import the package that defines the name.", "Open the namespace the property is defined in."],
+        },
+        WhackDiagnosticKind::UndefinedPropertyWithStaticType.id() => ExplanationData {
+            description: "A property is accessed on a value whose static type does not declare it, and the type is not dynamic.",
+            example: Some("var p: Point = new Point();\np.z = 0;"),
+            fixes: &["Check the spelling of the property.", "Cast the base to a type declaring the property.", "Type the base as * to access it dynamically."],
+        },
+        WhackDiagnosticKind::AmbiguousReference.id() => ExplanationData {
+            description: "A name matches several definitions, usually through wildcard imports of packages declaring the same name.",
+            example: Some("import flash.display.*;\nimport mx.core.*;\nvar s: Sprite;"),
+            fixes: &["Qualify the name with its package.", "Replace a wildcard import by an explicit import of the intended definition."],
+        },
+        WhackDiagnosticKind::AccessOfNullable.id() => ExplanationData {
+            description: "A property is accessed on a value whose type allows null.",
+            example: Some("var o: ?Object = null;\no.toString();"),
+            fixes: &["Check the value against null first.", "Assert non-nullability with the ! operator when the value cannot be null."],
+        },
+        WhackDiagnosticKind::IncorrectNumArguments.id() => ExplanationData {
+            description: "A function is called with a number of arguments that does not match its required parameters.",
+            example: Some("function f(a: Number): void {}\nf();"),
+            fixes: &["Pass every required argument.", "Give the parameter a default value."],
+        },
+        WhackDiagnosticKind::ComparisonBetweenUnrelatedTypes.id() => ExplanationData {
+            description: "Values of two unrelated types are compared, so the comparison is likely always false.",
+            example: Some("var n: Number = 0;\nif (n == new Sprite()) {}"),
+            fixes: &["Compare values of related types.", "Convert one of the operands explicitly."],
+        },
+        WhackDiagnosticKind::NanComparison.id() => ExplanationData {
+            description: "NaN is not equal to any value, including itself, so comparing against NaN is always false.",
+            example: Some("if (x == NaN) {}"),
+            fixes: &["Use isNaN(x) instead."],
+        },
+        WhackDiagnosticKind::ImplicitVectorCoercion.id() => ExplanationData {
+            description: "Vector types are invariant: Vector.<Sprite> is not a Vector.<DisplayObject>.",
+            example: Some("var v: Vector.<DisplayObject> = new Vector.<Sprite>();"),
+            fixes: &["Convert explicitly, as in Vector.<DisplayObject>(v).", "Declare the vector with the expected element type."],
+        },
+        WhackDiagnosticKind::NonExhaustiveEnumSwitch.id() => ExplanationData {
+            description: "A switch over an enum value lacks a default case and does not handle some of the enum members.",
+            example: Some("switch (direction) {\n    case Direction.LEFT: break;\n}"),
+            fixes: &["Add cases for the missing members.", "Add a default case."],
+        },
+        WhackDiagnosticKind::UnusedImport.id() => ExplanationData {
+            description: "An import directive is never used by the package block it appears in.",
+            example: Some("import flash.utils.ByteArray;"),
+            fixes: &["Remove the import.", "Disable unused warnings through CompilerOptions::warnings."],
+        },
+        WhackDiagnosticKind::InternalCompilerError.id() => ExplanationData {
+            description: "The verifier reached a state it does not expect. This is a bug in the compiler rather than in the sources.",
+            example: None,
+            fixes: &["Report the issue along with a minimal source reproducing it."],
+        },
+    };
+}
\ No newline at end of file
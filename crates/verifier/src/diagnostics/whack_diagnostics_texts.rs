@@ -4,7 +4,6 @@ use crate::ns::*;
 
 lazy_static! {
     pub static ref DATA: HashMap<i32, String> = hashmap! {
-        // WhackDiagnosticKind::K.id() => ".".into(),
         WhackDiagnosticKind::EntityIsNotAType.id() => "Entity is not a type.".into(),
         WhackDiagnosticKind::ImplicitCoercionToUnrelatedType.id() => "Implicit coercion of a value of type {1} to an unrelated type {2}.".into(),
         WhackDiagnosticKind::EntityIsReadOnly.id() => "Entity is read-only.".into(),
@@ -116,6 +115,126 @@ lazy_static! {
         WhackDiagnosticKind::ReturnNotAllowedInGlobalInit.id() => "The return statement cannot be used in global initialization code.".into(),
         WhackDiagnosticKind::NamespaceConflictsWithConfigurationNs.id() => "Namespace conflicts with a configuration namespace.".into(),
         WhackDiagnosticKind::Unused.id() => "$1 is unused.".into(),
+        WhackDiagnosticKind::LintRuleViolation.id() => "{1} ({2}).".into(),
+        WhackDiagnosticKind::ResourceBundleNotFound.id() => "Resource bundle {1} was not found for locale {2}.".into(),
+        WhackDiagnosticKind::ResourceKeyNotFound.id() => "Resource {1} was not found in bundle {2} for locale {3}.".into(),
+        WhackDiagnosticKind::UndefinedState.id() => "Undefined state {1}.".into(),
+        WhackDiagnosticKind::IncludeInConflictsWithExcludeFrom.id() => "The includeIn and excludeFrom attributes cannot be used together.".into(),
+        WhackDiagnosticKind::MissingRequiredSkinPart.id() => "Skin {1} is missing the required skin part {2} of {3}.".into(),
+        WhackDiagnosticKind::MissingSkinState.id() => "Skin {1} does not declare the skin state {2} of {3}.".into(),
+        WhackDiagnosticKind::NonShareableWorkerValue.id() => "A value of type {1} cannot be shared with a worker.".into(),
+        WhackDiagnosticKind::WorkerClosureReferencesDisplayObject.id() => "Closure passed to a worker references the display object {1}.".into(),
+        WhackDiagnosticKind::ImplicitVectorCoercion.id() => "Implicit coercion of a value of type {1} to {2}; Vector types are invariant, use {2}(value) to convert.".into(),
+        WhackDiagnosticKind::VectorConversionRequiresArrayOrVector.id() => "Conversion to {1} requires an Array or Vector, but got {2}.".into(),
+        WhackDiagnosticKind::UnknownPropertyForClass.id() => "Unknown property {1} for {2}.".into(),
+        WhackDiagnosticKind::TupleIndexOutOfRange.id() => "Index out of range for tuple type {1}.".into(),
+        WhackDiagnosticKind::NonExhaustiveEnumSwitch.id() => "Switch over {1} does not handle {2}.".into(),
+        WhackDiagnosticKind::ImplicitCoercionOfRawValueToEnum.id() => "Implicit coercion of a value of type {1} to the enum {2}; use a member of {2}.".into(),
+        WhackDiagnosticKind::UnreachableSwitchTypeCase.id() => "Unreachable case {1}; values are already matched by {2}.".into(),
+        WhackDiagnosticKind::NonExhaustiveSwitchType.id() => "Switch type over {1} has no default case and does not match every value.".into(),
+        WhackDiagnosticKind::MalformedStaticAssert.id() => "static_assert() takes a constant test and an optional string literal message.".into(),
+        WhackDiagnosticKind::StaticAssertRequiresConstant.id() => "static_assert() test must be a constant Boolean expression.".into(),
+        WhackDiagnosticKind::StaticAssertFailed.id() => "{1}".into(),
+        WhackDiagnosticKind::InlineMethodMustBeFinalOrStatic.id() => "[Inline] methods must be static or final.".into(),
+        WhackDiagnosticKind::InlineMethodMustHaveBody.id() => "[Inline] methods must have a body.".into(),
+        WhackDiagnosticKind::InlineMethodContainsClosure.id() => "[Inline] methods must not contain closures.".into(),
+        WhackDiagnosticKind::InlineMethodContainsTry.id() => "[Inline] methods must not contain try statements.".into(),
+        WhackDiagnosticKind::TailCallNotConverted.id() => "Recursive call to {1} is not in tail position and cannot be converted into a loop.".into(),
+        WhackDiagnosticKind::InternalCompilerError.id() => "Internal compiler error: {1}".into(),
+        WhackDiagnosticKind::UnusedImport.id() => "Unused import {1}.".into(),
+        WhackDiagnosticKind::AmbiguousWildcardImportReference.id() => "Ambiguous reference to {1}, imported by several wildcard imports: {2}.".into(),
+        WhackDiagnosticKind::ComparisonWithUndefined.id() => "Comparison of a value of type {1} with undefined is always {2}, as the type holds no value equal to undefined.".into(),
+        WhackDiagnosticKind::VoidResultUsedAsValue.id() => "The result of a void function is used as a value.".into(),
+        WhackDiagnosticKind::ComparisonBetweenTypesWithoutCommonSubtype.id() => "Values of types {1} and {2} are equal only when both are null, since the types have no common subtype.".into(),
+        WhackDiagnosticKind::ImplicitNumberStringComparison.id() => "Comparison between {1} and {2} implicitly converts the string to a number.".into(),
+        WhackDiagnosticKind::DuplicateDiagnosticsSuppressed.id() => "Further occurrences of this diagnostic in this file are suppressed.".into(),
+        WhackDiagnosticKind::UnavailableInTargetProfile.id() => "{1} is not available in the {2} target profile.".into(),
+        WhackDiagnosticKind::UncaughtErrorInEventHandler.id() => "Error thrown here is not caught within the event handler {1}.".into(),
+        WhackDiagnosticKind::ThrowsMetadataTypeNotFound.id() => "Type '{1}' given in [Throws] meta-data is not defined.".into(),
+        WhackDiagnosticKind::ThrowsMetadataTypeMustBeError.id() => "Type '{1}' given in [Throws] meta-data is not a subtype of Error.".into(),
+        WhackDiagnosticKind::UndeclaredThrownType.id() => "Method '{1}' throws '{2}', which is not declared in its [Throws] meta-data.".into(),
+        WhackDiagnosticKind::SwitchCaseFallthrough.id() => "Previous case falls through into this case; end it with 'break' or add a fallthrough comment.".into(),
+        WhackDiagnosticKind::InfiniteLoop.id() => "Loop condition is always true and the loop body never exits.".into(),
+        WhackDiagnosticKind::LoopBodyNeverRuns.id() => "Loop condition is always false; the loop body never runs.".into(),
+        WhackDiagnosticKind::ShadowedDeclaration.id() => "Declaration of '{1}' shadows {2}.".into(),
+        WhackDiagnosticKind::ThisUsedInStaticContext.id() => "'this' cannot be used in static methods or package-level functions.".into(),
+        WhackDiagnosticKind::SuperStatementRequiresBaseClass.id() => "A super statement can be used only in subclasses of Object.".into(),
+        WhackDiagnosticKind::SuperStatementOutsideConstructor.id() => "A super statement can be used only in class constructors.".into(),
+        WhackDiagnosticKind::SuperStatementMustBeCalledOnce.id() => "The base constructor must be called only once.".into(),
+        WhackDiagnosticKind::ThisUsedBeforeSuperStatement.id() => "'this' cannot be used before the super statement.".into(),
+        WhackDiagnosticKind::FieldNotInitializedInConstructor.id() => "Field '{1}' is not assigned on every path through the constructor.".into(),
+        WhackDiagnosticKind::FieldReadBeforeInitialization.id() => "Method '{1}' reads field '{2}' before the constructor assigns it.".into(),
+        WhackDiagnosticKind::StaticInitializationOrderDependency.id() => "Static initializer of {1} reads {2}.{3}, whose initialization may not precede it.".into(),
+        WhackDiagnosticKind::RecursiveAccessorReference.id() => "Accessor '{1}' refers to itself here, which recurses infinitely; use a backing field.".into(),
+        WhackDiagnosticKind::InvalidMxmlId.id() => "'{1}' is not a valid identifier for an id.".into(),
+        WhackDiagnosticKind::DuplicateMxmlId.id() => "Duplicate id '{1}' in the document.".into(),
+        WhackDiagnosticKind::MxmlIdConflictsWithInheritedMember.id() => "Id '{1}' conflicts with a member inherited from {2}.".into(),
+        WhackDiagnosticKind::DuplicateXmlAttribute.id() => "Duplicate XML attribute '{1}'.".into(),
+        WhackDiagnosticKind::XmlEndTagMismatch.id() => "End tag '{1}' does not match the start tag '{2}'.".into(),
+        WhackDiagnosticKind::UndeclaredXmlNamespacePrefix.id() => "XML namespace prefix '{1}' is not declared.".into(),
+        WhackDiagnosticKind::TestMethodMustHaveNoParameters.id() => "Test method '{1}' must have no parameters.".into(),
+        WhackDiagnosticKind::TestMethodMustBePublic.id() => "Test method '{1}' must be public.".into(),
+        WhackDiagnosticKind::TestMethodMustNotBeStatic.id() => "Test method '{1}' must not be static.".into(),
+        WhackDiagnosticKind::TestMethodMustBeStatic.id() => "[{2}] method '{1}' must be static.".into(),
+        WhackDiagnosticKind::UnknownNativeExtensionId.id() => "No loaded native extension has the ID '{1}'.".into(),
+        WhackDiagnosticKind::DuplicateRemoteClassAlias.id() => "Alias '{1}' is already registered by '{2}'.".into(),
+        WhackDiagnosticKind::RemoteClassRequiresNoArgumentConstructor.id() => "Remote class '{1}' must have a constructor without required parameters.".into(),
+        WhackDiagnosticKind::RemoteClassPropertyNotReadWrite.id() => "Property '{1}' is not both readable and writable; it is lost in AMF serialization.".into(),
+        WhackDiagnosticKind::NonSerializableRemoteClassProperty.id() => "Property '{1}' of type '{2}' cannot be serialized to AMF.".into(),
+        WhackDiagnosticKind::UnsupportedJsonTypeProperty.id() => "Property '{1}' of type '{2}' has no JSON representation.".into(),
+        WhackDiagnosticKind::JsonTypeRequiresNoArgumentConstructor.id() => "JSON type '{1}' must have a constructor without required parameters.".into(),
+        WhackDiagnosticKind::JsonTypeHelperConflict.id() => "'{1}' conflicts with the generated JSON helper.".into(),
+        WhackDiagnosticKind::MemberGeneratorError.id() => "{1} ({2}).".into(),
+        WhackDiagnosticKind::GeneratedMemberConflict.id() => "Member '{1}' generated for [{2}] conflicts with an existing definition.".into(),
+        WhackDiagnosticKind::BindableEventNotDeclared.id() => "Event '{1}' of [Bindable] is not declared by [Event] meta-data of '{2}'.".into(),
+        WhackDiagnosticKind::BindableGetterRequiresEvent.id() => "Read-only [Bindable] property '{1}' must specify the event signaling its change.".into(),
+        WhackDiagnosticKind::MixinTypeNotFound.id() => "Mixin '{1}' is not a class.".into(),
+        WhackDiagnosticKind::MixinMemberConflict.id() => "'{1}' is included from both '{2}' and '{3}'; define it in the class to resolve the conflict.".into(),
+        WhackDiagnosticKind::MixinMemberConflictsWithInherited.id() => "'{1}' included from '{2}' conflicts with the member inherited from '{3}'.".into(),
+        WhackDiagnosticKind::AmbiguousOperatorOverload.id() => "Operator method '{1}' is defined by both '{2}' and '{3}'.".into(),
+        WhackDiagnosticKind::InvalidOperatorOverload.id() => "Operator method '{1}' must be static and take two parameters.".into(),
+        WhackDiagnosticKind::AmbiguousExtensionMethod.id() => "Extension method '{1}' is defined by both '{2}' and '{3}'.".into(),
+        WhackDiagnosticKind::ConstParameterReassignment.id() => "Parameter '{1}' is constant and must not be reassigned.".into(),
+        WhackDiagnosticKind::LoopVariableCapturedByClosure.id() => "Function captures the loop variable '{1}', whose last value is shared by every function created by the loop. Pass '{1}' to an immediately invoked function creating this function instead.".into(),
+        WhackDiagnosticKind::UseBeforeDeclaration.id() => "'{1}' is used before its declaration.".into(),
+        WhackDiagnosticKind::DuplicateSwitchCase.id() => "Duplicate case '{1}'.".into(),
+        WhackDiagnosticKind::SwitchCaseDiffersOnlyInLetterCase.id() => "Case '{1}' differs from case '{2}' only in letter case.".into(),
+        WhackDiagnosticKind::NonExportedDefinitionReference.id() => "'{1}' is not exported by the module '{2}'.".into(),
+        WhackDiagnosticKind::ModuleExportNotFound.id() => "The module '{1}' exports '{2}', which it does not define.".into(),
+        WhackDiagnosticKind::ExperimentalApiReference.id() => "'{1}' is experimental and may change or be removed.".into(),
+        WhackDiagnosticKind::InternalApiReference.id() => "'{1}' is internal to '{2}' and must not be used outside it.".into(),
+        WhackDiagnosticKind::DefinitionConflict.id() => "{1} is defined by more than one origin ({2}); the definition from {3} is used.".into(),
+        WhackDiagnosticKind::ApiRequiresRuntimeVersion.id() => "'{1}' requires runtime version {2}, but the target version is {3}.".into(),
+        WhackDiagnosticKind::DiscardedPureResult.id() => "The result of '{1}' is discarded; it returns a new value rather than modifying its operands.".into(),
+        WhackDiagnosticKind::UintSubtractionMayWrap.id() => "Subtracting from a uint wraps around to a large positive value instead of going below zero.".into(),
+        WhackDiagnosticKind::IntegerDivisionTruncated.id() => "Dividing {1} operands produces a fractional Number, which is truncated if stored as an integer.".into(),
+        WhackDiagnosticKind::FractionalBitwiseOperand.id() => "The fractional part of {1} is discarded by the bitwise operator.".into(),
+        WhackDiagnosticKind::UnknownReflectedDefinition.id() => "'{1}' passed to getDefinitionByName() does not name a known definition.".into(),
+        WhackDiagnosticKind::DescribedMetadataStripped.id() => "describeType() does not see the {2} meta-data of {1}, which is stripped from compiled output.".into(),
+        WhackDiagnosticKind::PackageDefinitionNamedAsType.id() => "The package-level {1} '{2}' has the same name as {3}.".into(),
+        WhackDiagnosticKind::FrameScriptTimelineClassNotFound.id() => "The timeline class {1} of the frame script was not found.".into(),
+        WhackDiagnosticKind::LoopVariableRedeclared.id() => "'{1}' is already declared by the heading of the enclosing loop.".into(),
+        WhackDiagnosticKind::TestIsAlwaysTruthy.id() => "The test is always true; an object is always truthy.".into(),
+        WhackDiagnosticKind::MethodReferenceUsedAsTest.id() => "Method '{1}' is used as a test without being called; is a call missing?".into(),
+        WhackDiagnosticKind::ConditionalFunctionDeclaration.id() => "Function '{1}' is declared inside a conditional statement; it is hoisted to the enclosing function, which runtimes do not handle alike.".into(),
+        WhackDiagnosticKind::LanguageFeatureNotEnabled.id() => "The '{1}' language feature is not enabled for this file. Enable it with a '// @whack-features {1}' comment or for the package in the compiler options.".into(),
+        WhackDiagnosticKind::EmbeddedFontNameMissing.id() => "A font embed must give the name it is registered under through 'fontName'.".into(),
+        WhackDiagnosticKind::EmbedOptionMustBeBoolean.id() => "The '{1}' option must be 'true' or 'false'.".into(),
+        WhackDiagnosticKind::InvalidUnicodeRange.id() => "Invalid unicode range '{1}'.".into(),
+        WhackDiagnosticKind::EmbeddedFileNotFound.id() => "Embedded file '{1}' not found.".into(),
+        WhackDiagnosticKind::InvalidEmbeddedFont.id() => "'{1}' is not a TrueType or OpenType font.".into(),
+        WhackDiagnosticKind::EmbeddedFontStyleMismatch.id() => "The '{2}' option does not match the style of the font '{1}'.".into(),
+        WhackDiagnosticKind::EmbeddedFontMissingGlyphs.id() => "The font '{1}' has no glyphs for {2}.".into(),
+        WhackDiagnosticKind::LegacyTrailingComma.id() => "Trailing comma accepted for compatibility with legacy syntax.".into(),
+        WhackDiagnosticKind::UndefinedConfigConstantInEliminatedCode.id() => "Configuration constant '{1}' is not defined; the code is eliminated in this configuration but would not compile in others.".into(),
+        WhackDiagnosticKind::EmbeddedFontUsesTrueTypeOutlines.id() => "The font '{1}' has TrueType outlines, so it is embedded for classic text fields rather than the Flash Text Engine; set 'embedAsCFF' to false.".into(),
+        WhackDiagnosticKind::EmbeddedFontUsesCffOutlines.id() => "The font '{1}' has CFF outlines, which cannot be embedded with 'embedAsCFF' set to false.".into(),
+        WhackDiagnosticKind::MethodLimitExceeded.id() => "The method '{1}' is estimated to use {2} {3}, past the threshold of {4}.".into(),
+        WhackDiagnosticKind::UnclosedBracket.id() => "'{1}' is not closed before the end of the file.".into(),
+        WhackDiagnosticKind::DanglingMemberAccess.id() => "Expecting an identifier after '{1}'.".into(),
+        WhackDiagnosticKind::MxmlChildWithoutDefaultProperty.id() => "{1} declares no default property, so its child {2} must be wrapped in a property tag.".into(),
+        WhackDiagnosticKind::IncompatibleMxmlChild.id() => "Child {1} is not a {3}, the element type of the default property '{2}'.".into(),
+        WhackDiagnosticKind::SwfModuleDependencyCycle.id() => "The SWF modules {1} depend on each other, so no load order exists.".into(),
         // WhackDiagnosticKind::K.id() => ".".into(),
     };
 }
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod entity_display;
+pub use entity_display::*;
+
+mod stable_entity_id;
+pub use stable_entity_id::*;
\ No newline at end of file
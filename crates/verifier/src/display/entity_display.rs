@@ -0,0 +1,116 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum DisplayVerbosity {
+    /// Local names, such as `Vector.<Sprite>`.
+    #[default]
+    Short,
+    /// Fully qualified names, such as `Vector.<flash.display.Sprite>`.
+    FullyQualified,
+}
+
+/// Renders types, signatures and properties for diagnostics, hovers
+/// and generated documentation.
+///
+/// ```ignore
+/// let display = EntityDisplay::new(&host, DisplayVerbosity::Short);
+/// display.type_name(&t); // "Vector.<Sprite>"
+/// display.signature("addChild", &slot.signature(&host), &["child".into()]); // "function addChild(child: DisplayObject): DisplayObject"
+/// ```
+pub struct EntityDisplay<'a> {
+    host: &'a Database,
+    verbosity: DisplayVerbosity,
+}
+
+impl<'a> EntityDisplay<'a> {
+    pub fn new(host: &'a Database, verbosity: DisplayVerbosity) -> Self {
+        Self { host, verbosity }
+    }
+
+    pub fn type_name(&self, t: &Entity) -> String {
+        if *t == self.host.any_type() {
+            return "*".into();
+        }
+        if *t == self.host.void_type() {
+            return "void".into();
+        }
+        if t.is::<NullableType>() {
+            return format!("?{}", self.type_name(&t.escape_of_nullable_or_non_nullable()));
+        }
+        if t.is::<NonNullableType>() {
+            return format!("{}!", self.type_name(&t.escape_of_nullable_or_non_nullable()));
+        }
+        if t.is::<TupleType>() {
+            let elements = t.element_types().iter().map(|t| self.type_name(&t)).collect::<Vec<_>>();
+            return format!("[{}]", elements.join(", "));
+        }
+        if t.is::<FunctionType>() {
+            return format!("function({}): {}", self.parameters(t, &[]), self.type_name(&t.result_type()));
+        }
+        if t.is::<TypeAfterSubstitution>() {
+            let arguments = t.substitute_types().iter().map(|t| self.type_name(&t)).collect::<Vec<_>>();
+            return format!("{}.<{}>", self.type_name(&t.origin()), arguments.join(", "));
+        }
+        self.name(t)
+    }
+
+    /// Renders a method signature, naming parameters after
+    /// `parameter_names` where given.
+    pub fn signature(&self, name: &str, signature: &Entity, parameter_names: &[String]) -> String {
+        format!("function {name}({}): {}", self.parameters(signature, parameter_names), self.type_name(&signature.result_type()))
+    }
+
+    /// Renders a method signature with the parameter names of its definition.
+    pub fn function_definition(&self, defn: &FunctionDefinition, signature: &Entity) -> String {
+        let name = match &defn.name {
+            FunctionName::Identifier(name) => name.0.clone(),
+            FunctionName::Getter(name) => format!("get {}", name.0),
+            FunctionName::Setter(name) => format!("set {}", name.0),
+            FunctionName::Constructor(name) => name.0.clone(),
+        };
+        let parameter_names = defn.common.signature.parameters.iter()
+            .map(|p| p.destructuring.destructuring.to_identifier_name().map(|(name, _)| name).unwrap_or_default())
+            .collect::<Vec<_>>();
+        self.signature(&name, signature, &parameter_names)
+    }
+
+    /// Renders a property as its name followed by its type or signature,
+    /// or any other entity through its name.
+    pub fn property(&self, property: &Entity) -> String {
+        if property.is::<MethodSlot>() {
+            self.signature(&self.name(property), &property.signature(self.host), &[])
+        } else if property.is::<VariableSlot>() || property.is::<VirtualSlot>() {
+            format!("{}: {}", self.name(property), self.type_name(&property.static_type(self.host)))
+        } else if property.is::<ClassType>() || property.is::<InterfaceType>() || property.is::<EnumType>() {
+            self.type_name(property)
+        } else {
+            self.name(property)
+        }
+    }
+
+    pub fn namespace(&self, ns: &Entity) -> String {
+        ns.to_string()
+    }
+
+    fn name(&self, entity: &Entity) -> String {
+        match self.verbosity {
+            DisplayVerbosity::Short => entity.name().local_name(),
+            DisplayVerbosity::FullyQualified => entity.to_string(),
+        }
+    }
+
+    fn parameters(&self, signature: &Entity, parameter_names: &[String]) -> String {
+        signature.params().iter().enumerate().map(|(i, param)| {
+            let t = self.type_name(&param.static_type);
+            let name = parameter_names.get(i).filter(|name| !name.is_empty());
+            match (param.kind, name) {
+                (ParameterKind::Rest, Some(name)) => format!("...{name}: {t}"),
+                (ParameterKind::Rest, None) => format!("...{t}"),
+                (ParameterKind::Optional, Some(name)) => format!("{name}: {t} = ..."),
+                (ParameterKind::Optional, None) => format!("{t}="),
+                (_, Some(name)) => format!("{name}: {t}"),
+                (_, None) => t,
+            }
+        }).collect::<Vec<_>>().join(", ")
+    }
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use crate::ns::*;
+
+/// Derives identifiers for definitions from their fully qualified name
+/// and signature rather than from their position in the `Database`, so
+/// that external indexes, API baselines and coverage maps remain valid
+/// across compiler runs and source reorderings.
+///
+/// An identifier has the form `KIND:NAME#HASH`, where `HASH` is the
+/// first 8 hexadecimal digits of the SHA-256 digest of the fully
+/// qualified signature or type, as in
+/// `method:com.example.Player.jump#5e0f13a2`. Entities other than
+/// definitions have no identifier.
+///
+/// ```ignore
+/// let id = StableEntityId::of(&host, &slot).unwrap();
+/// ```
+pub struct StableEntityId;
+
+impl StableEntityId {
+    pub fn of(host: &Database, entity: &Entity) -> Option<String> {
+        let kind = Self::kind(entity)?;
+        let signature = EntityDisplay::new(host, DisplayVerbosity::FullyQualified).property(entity);
+        let hash = ContentHash::sha256(format!("{kind}\0{signature}").as_bytes());
+        Some(format!("{kind}:{}#{}", entity.to_string(), &hash[..8]))
+    }
+
+    fn kind(entity: &Entity) -> Option<&'static str> {
+        Some(if entity.is::<ClassType>() {
+            "class"
+        } else if entity.is::<InterfaceType>() {
+            "interface"
+        } else if entity.is::<EnumType>() {
+            "enum"
+        } else if entity.is::<MethodSlot>() {
+            "method"
+        } else if entity.is::<VirtualSlot>() {
+            "accessor"
+        } else if entity.is::<VariableSlot>() {
+            "variable"
+        } else {
+            return None;
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+mod inlay_hints;
+pub use inlay_hints::*;
+
+mod hover;
+pub use hover::*;
+
+mod selection_ranges;
+pub use selection_ranges::*;
+
+mod code_regions;
+pub use code_regions::*;
+
+mod document_outline;
+pub use document_outline::*;
+
+mod folding_ranges;
+pub use folding_ranges::*;
+
+mod on_type_formatting;
+pub use on_type_formatting::*;
\ No newline at end of file
@@ -0,0 +1,42 @@
+use crate::ns::*;
+
+/// A region delimited by `// #region NAME` and `// #endregion`
+/// comments.
+#[derive(Clone, Debug)]
+pub struct CodeRegion {
+    /// Name following `#region`, which may be empty.
+    pub name: String,
+    /// From the start of the opening comment to the end of the closing
+    /// comment.
+    pub location: Location,
+}
+
+impl CodeRegion {
+    /// Regions of a compilation unit, ordered by start. Unbalanced
+    /// `#region` and `#endregion` comments are ignored.
+    pub fn collect(cu: &Rc<CompilationUnit>) -> Vec<CodeRegion> {
+        let mut open: Vec<(String, Location)> = vec![];
+        let mut r = vec![];
+        for comment in cu.comments().iter() {
+            if comment.multiline() {
+                continue;
+            }
+            let content = comment.content();
+            let content = content.trim();
+            if let Some(name) = content.strip_prefix("#region") {
+                open.push((name.trim().to_owned(), comment.location()));
+            } else if content.starts_with("#endregion") {
+                if let Some((name, start)) = open.pop() {
+                    let location = Location::with_offsets(cu, start.first_offset(), comment.location().last_offset());
+                    r.push(CodeRegion { name, location });
+                }
+            }
+        }
+        r.sort_by_key(|region| region.location.first_offset());
+        r
+    }
+
+    pub(crate) fn contains(&self, location: &Location) -> bool {
+        self.location.first_offset() <= location.first_offset() && location.last_offset() <= self.location.last_offset()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OutlineItemKind {
+    Package,
+    Class,
+    Interface,
+    Enum,
+    Function,
+    Getter,
+    Setter,
+    Constructor,
+    Variable,
+    Constant,
+    Namespace,
+    TypeAlias,
+    Region,
+}
+
+/// An entry of a `DocumentOutline`.
+#[derive(Clone, Debug)]
+pub struct OutlineItem {
+    pub name: String,
+    pub kind: OutlineItemKind,
+    /// Location of the whole definition.
+    pub location: Location,
+    /// Location of the name, selected when the item is chosen.
+    pub name_location: Location,
+    /// Members of a type, definitions of a package or items of a
+    /// region.
+    pub children: Vec<OutlineItem>,
+}
+
+/// Outline of the definitions of a program, for document symbol
+/// requests and breadcrumbs.
+///
+/// ```ignore
+/// for item in DocumentOutline::of(&program).items.iter() {
+///     // item.children are the definitions of a package or members of a type
+/// }
+/// ```
+///
+/// Definitions inside `CONFIG::x { }` blocks are listed as if defined
+/// directly, while those of included files are left to the outlines of
+/// those files, as they locate into them. `CodeRegion`s group the items
+/// they contain, where they do not cut across a definition.
+#[derive(Clone, Default, Debug)]
+pub struct DocumentOutline {
+    pub items: Vec<OutlineItem>,
+}
+
+impl DocumentOutline {
+    pub fn of(program: &Rc<Program>) -> Self {
+        let regions = CodeRegion::collect(&program.location.compilation_unit());
+        let mut items = vec![];
+        for pckgdefn in program.packages.iter() {
+            let name = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+            let name_location = pckgdefn.name.first().map(|name| name.1.clone()).unwrap_or(pckgdefn.location.clone());
+            items.push(OutlineItem {
+                name,
+                kind: OutlineItemKind::Package,
+                location: pckgdefn.location.clone(),
+                name_location,
+                children: Self::directives(&pckgdefn.block.directives),
+            });
+        }
+        items.extend(Self::directives(&program.directives));
+        items.sort_by_key(|item| item.location.first_offset());
+        Self { items: Self::group_regions(items, &regions) }
+    }
+
+    fn directives(list: &[Rc<Directive>]) -> Vec<OutlineItem> {
+        let mut r = vec![];
+        for drtv in list.iter() {
+            let item = |name: &str, kind: OutlineItemKind, name_location: &Location, children: Vec<OutlineItem>| OutlineItem {
+                name: name.to_owned(),
+                kind,
+                location: drtv.location(),
+                name_location: name_location.clone(),
+                children,
+            };
+            match drtv.as_ref() {
+                Directive::ClassDefinition(defn) => r.push(item(&defn.name.0, OutlineItemKind::Class, &defn.name.1, Self::directives(&defn.block.directives))),
+                Directive::InterfaceDefinition(defn) => r.push(item(&defn.name.0, OutlineItemKind::Interface, &defn.name.1, Self::directives(&defn.block.directives))),
+                Directive::EnumDefinition(defn) => r.push(item(&defn.name.0, OutlineItemKind::Enum, &defn.name.1, Self::directives(&defn.block.directives))),
+                Directive::NamespaceDefinition(defn) => r.push(item(&defn.left.0, OutlineItemKind::Namespace, &defn.left.1, vec![])),
+                Directive::TypeDefinition(defn) => r.push(item(&defn.left.0, OutlineItemKind::TypeAlias, &defn.left.1, vec![])),
+                Directive::FunctionDefinition(defn) => {
+                    let (name, kind) = match &defn.name {
+                        FunctionName::Identifier(name) => (name, OutlineItemKind::Function),
+                        FunctionName::Getter(name) => (name, OutlineItemKind::Getter),
+                        FunctionName::Setter(name) => (name, OutlineItemKind::Setter),
+                        FunctionName::Constructor(name) => (name, OutlineItemKind::Constructor),
+                    };
+                    r.push(item(&name.0, kind, &name.1, vec![]));
+                },
+                Directive::VariableDefinition(defn) => {
+                    let kind = if defn.kind.0 == VariableDefinitionKind::Const { OutlineItemKind::Constant } else { OutlineItemKind::Variable };
+                    for binding in defn.bindings.iter() {
+                        if let Expression::QualifiedIdentifier(id) = binding.destructuring.destructuring.as_ref() {
+                            if let Some((name, location)) = id.to_identifier_name_or_asterisk() {
+                                r.push(item(&name, kind, &location, vec![]));
+                            }
+                        }
+                    }
+                },
+                Directive::ConfigurationDirective(cfgdrtv) => {
+                    if let Directive::Block(block) = cfgdrtv.directive.as_ref() {
+                        r.extend(Self::directives(&block.directives));
+                    }
+                },
+                _ => {},
+            }
+        }
+        r
+    }
+
+    /// Moves the items inside each region into an item for the region,
+    /// and does the same within packages and types.
+    fn group_regions(items: Vec<OutlineItem>, regions: &[CodeRegion]) -> Vec<OutlineItem> {
+        let items = items.into_iter().map(|mut item| {
+            if matches!(item.kind, OutlineItemKind::Package | OutlineItemKind::Class | OutlineItemKind::Interface | OutlineItemKind::Enum) {
+                let inner: Vec<CodeRegion> = regions.iter().filter(|region| Self::is_within(&region.location, &item.location)).cloned().collect();
+                item.children = Self::group_regions(std::mem::take(&mut item.children), &inner);
+            }
+            item
+        }).collect();
+        Self::group_level(items, regions)
+    }
+
+    fn group_level(items: Vec<OutlineItem>, regions: &[CodeRegion]) -> Vec<OutlineItem> {
+        // Regions within an item or cutting across one are not at this
+        // level.
+        let level: Vec<&CodeRegion> = regions.iter().filter(|region| {
+            items.iter().all(|item| !Self::overlaps(&region.location, &item.location) || region.contains(&item.location))
+        }).collect();
+        // Of regions spanning the same text, the first one encloses the
+        // others.
+        let encloses = |i: usize, j: usize| i != j && level[i].contains(&level[j].location) && (!level[j].contains(&level[i].location) || i < j);
+        let mut remaining = items;
+        let mut r = vec![];
+        for i in (0..level.len()).filter(|&i| !(0..level.len()).any(|j| encloses(j, i))) {
+            let region = level[i];
+            let (inside, outside): (Vec<OutlineItem>, Vec<OutlineItem>) = remaining.into_iter().partition(|item| region.contains(&item.location));
+            remaining = outside;
+            let nested: Vec<CodeRegion> = (0..level.len()).filter(|&j| encloses(i, j)).map(|j| level[j].clone()).collect();
+            let name_location = Location::with_offsets(&region.location.compilation_unit(), region.location.first_offset(), region.location.first_offset());
+            r.push(OutlineItem {
+                name: region.name.clone(),
+                kind: OutlineItemKind::Region,
+                location: region.location.clone(),
+                name_location,
+                children: Self::group_level(inside, &nested),
+            });
+        }
+        r.extend(remaining);
+        r.sort_by_key(|item| item.location.first_offset());
+        r
+    }
+
+    fn is_within(inner: &Location, outer: &Location) -> bool {
+        outer.first_offset() <= inner.first_offset() && inner.last_offset() <= outer.last_offset()
+    }
+
+    fn overlaps(a: &Location, b: &Location) -> bool {
+        a.first_offset() < b.last_offset() && b.first_offset() < a.last_offset()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FoldingRangeKind {
+    Block,
+    Comment,
+    Imports,
+    Metadata,
+    Region,
+    /// MXML element.
+    Tag,
+}
+
+/// A foldable range of lines.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FoldingRange {
+    /// One-based number of the first line.
+    pub first_line: usize,
+    /// One-based number of the last line.
+    pub last_line: usize,
+    pub kind: FoldingRangeKind,
+}
+
+/// Folding ranges of a program or MXML document.
+///
+/// ```ignore
+/// let ranges = FoldingRanges::of_program(&program);
+/// ```
+///
+/// Blocks, switch statements, multi-line comments, runs of consecutive
+/// imports, groups of meta-data and `CodeRegion`s are foldable in
+/// ActionScript; elements and comments spanning several lines are
+/// foldable in MXML. Ranges covering a single line are omitted.
+pub struct FoldingRanges;
+
+impl FoldingRanges {
+    pub fn of_program(program: &Rc<Program>) -> Vec<FoldingRange> {
+        let cu = program.location.compilation_unit();
+        let mut collector = FoldingRangeCollector { compilation_unit: cu.clone(), ranges: vec![] };
+        collector.visit_program(program);
+        collector.imports(&program.directives);
+        for comment in cu.comments().iter() {
+            if comment.multiline() {
+                collector.push(&comment.location(), FoldingRangeKind::Comment);
+            }
+        }
+        for region in CodeRegion::collect(&cu).iter() {
+            collector.push(&region.location, FoldingRangeKind::Region);
+        }
+        Self::finish(collector.ranges)
+    }
+
+    pub fn of_mxml(mxml: &Rc<Mxml>) -> Vec<FoldingRange> {
+        let mut r = vec![];
+        for content in mxml.content.iter() {
+            Self::mxml_content(content, &mut r);
+        }
+        Self::finish(r)
+    }
+
+    fn mxml_content(content: &Rc<MxmlContent>, output: &mut Vec<FoldingRange>) {
+        match content.as_ref() {
+            MxmlContent::Element(element) => {
+                Self::push(output, &element.location, FoldingRangeKind::Tag);
+                for content in element.content.iter().flatten() {
+                    Self::mxml_content(content, output);
+                }
+            },
+            MxmlContent::Comment((_, location)) => Self::push(output, location, FoldingRangeKind::Comment),
+            _ => {},
+        }
+    }
+
+    fn push(output: &mut Vec<FoldingRange>, location: &Location, kind: FoldingRangeKind) {
+        let (first_line, last_line) = (location.first_line_number(), location.last_line_number());
+        if last_line > first_line {
+            output.push(FoldingRange { first_line, last_line, kind });
+        }
+    }
+
+    /// Orders ranges by line, dropping those of the same lines as a
+    /// previous range.
+    fn finish(mut ranges: Vec<FoldingRange>) -> Vec<FoldingRange> {
+        ranges.sort_by_key(|r| (r.first_line, std::cmp::Reverse(r.last_line)));
+        ranges.dedup_by(|b, a| a.first_line == b.first_line && a.last_line == b.last_line);
+        ranges
+    }
+}
+
+struct FoldingRangeCollector {
+    compilation_unit: Rc<CompilationUnit>,
+    ranges: Vec<FoldingRange>,
+}
+
+impl FoldingRangeCollector {
+    fn push(&mut self, location: &Location, kind: FoldingRangeKind) {
+        // Included files have ranges of their own.
+        if Rc::ptr_eq(&location.compilation_unit(), &self.compilation_unit) {
+            FoldingRanges::push(&mut self.ranges, location, kind);
+        }
+    }
+
+    /// Folds runs of consecutive import directives.
+    fn imports(&mut self, list: &[Rc<Directive>]) {
+        let mut run: Option<(Location, Location)> = None;
+        for drtv in list.iter() {
+            if matches!(drtv.as_ref(), Directive::ImportDirective(_)) {
+                run = Some(match run {
+                    Some((first, _)) => (first, drtv.location()),
+                    None => (drtv.location(), drtv.location()),
+                });
+                continue;
+            }
+            if let Some((first, last)) = run.take() {
+                self.push(&first.combine_with(last), FoldingRangeKind::Imports);
+            }
+        }
+        if let Some((first, last)) = run {
+            self.push(&first.combine_with(last), FoldingRangeKind::Imports);
+        }
+    }
+
+    fn metadata(&mut self, attributes: &[Attribute]) {
+        let metadata = Attribute::find_metadata(attributes);
+        if let (Some(first), Some(last)) = (metadata.first(), metadata.last()) {
+            self.push(&first.location.combine_with(last.location.clone()), FoldingRangeKind::Metadata);
+        }
+    }
+}
+
+impl Visitor for FoldingRangeCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => self.metadata(&defn.attributes),
+            Directive::InterfaceDefinition(defn) => self.metadata(&defn.attributes),
+            Directive::EnumDefinition(defn) => self.metadata(&defn.attributes),
+            Directive::FunctionDefinition(defn) => self.metadata(&defn.attributes),
+            Directive::VariableDefinition(defn) => self.metadata(&defn.attributes),
+            Directive::SwitchStatement(_) | Directive::SwitchTypeStatement(_) => self.push(&drtv.location(), FoldingRangeKind::Block),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.push(&block.location, FoldingRangeKind::Block);
+        self.imports(&block.directives);
+        walk_block(self, block);
+    }
+}
\ No newline at end of file
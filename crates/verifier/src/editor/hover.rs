@@ -0,0 +1,52 @@
+use crate::ns::*;
+
+/// Hover text of the reference at an offset of a verified program.
+///
+/// ```ignore
+/// if let Some(text) = Hover::at(&host, verifier.extension_method_calls(), &program, offset) {
+///     // "(extension) StringUtils.function trimmed(s: String): String"
+/// }
+/// ```
+///
+/// Calls of extension methods name the static method they resolve to,
+/// as given by `ExtensionMethodCall::hover_text()`. Other references are
+/// rendered by `EntityDisplay::property()`, the innermost one first.
+pub struct Hover;
+
+impl Hover {
+    pub fn at(host: &Database, extension_method_calls: &[ExtensionMethodCall], program: &Rc<Program>, offset: usize) -> Option<String> {
+        let compilation_unit = program.location.compilation_unit();
+        let call = extension_method_calls.iter().find(|call| {
+            Rc::ptr_eq(&call.location.compilation_unit(), &compilation_unit)
+                && call.location.first_offset() <= offset && offset <= call.location.last_offset()
+        });
+        if let Some(call) = call {
+            return Some(call.hover_text(host));
+        }
+        let mut finder = ReferenceFinder { host, compilation_unit, offset, found: None };
+        finder.visit_program(program);
+        finder.found.map(|entity| EntityDisplay::new(host, DisplayVerbosity::Short).property(&entity))
+    }
+}
+
+/// Finds the innermost expression containing an offset that refers to
+/// a definition.
+struct ReferenceFinder<'a> {
+    host: &'a Database,
+    compilation_unit: Rc<CompilationUnit>,
+    offset: usize,
+    found: Option<Entity>,
+}
+
+impl<'a> Visitor for ReferenceFinder<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        let location = exp.location();
+        if !(Rc::ptr_eq(&location.compilation_unit(), &self.compilation_unit) && location.first_offset() <= self.offset && self.offset <= location.last_offset()) {
+            return;
+        }
+        if let Some(entity) = referenced_entity(self.host, exp) {
+            self.found = Some(entity);
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
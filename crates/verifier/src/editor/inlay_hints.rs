@@ -0,0 +1,191 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InlayHintKind {
+    /// Name of the parameter a literal argument is passed to.
+    ParameterName,
+    /// Inferred type of a variable without a type annotation.
+    Type,
+    /// Base class or interface member a method overrides or implements.
+    Override,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InlayHint {
+    /// Offset in the text of the compilation unit the hint is shown at.
+    pub offset: usize,
+    pub label: String,
+    pub kind: InlayHintKind,
+}
+
+/// Inlay hints of a verified program: parameter names before literal
+/// arguments, inferred types after untyped local variables, and the
+/// origin of methods that override or implement another.
+///
+/// ```ignore
+/// let hints = InlayHints::collect(&host, &programs, &program);
+/// for hint in hints.iter() {
+///     let position = text.line_column(hint.offset, ColumnEncoding::Utf16);
+/// }
+/// ```
+///
+/// Parameter names are taken from the function definitions of
+/// `programs`, so calls to library definitions compiled from ABC get no
+/// parameter name hints.
+pub struct InlayHints;
+
+impl InlayHints {
+    pub fn collect(host: &Database, programs: &[Rc<Program>], program: &Rc<Program>) -> Vec<InlayHint> {
+        let mut parameters = ParameterNameCollector { host, names: HashMap::new() };
+        for program in programs.iter() {
+            parameters.visit_program(program);
+        }
+        let mut collector = InlayHintCollector {
+            host,
+            parameter_names: parameters.names,
+            function_depth: 0,
+            hints: vec![],
+        };
+        collector.visit_program(program);
+        collector.hints.sort_by_key(|hint| hint.offset);
+        collector.hints
+    }
+
+    /// Member that a method overrides, or else the interface member it
+    /// implements, labelled after its declaring type.
+    fn origin(host: &Database, method: &Entity) -> Option<String> {
+        if let Some(overridden) = TypeHierarchy(host).overridden(method) {
+            let parent = overridden.parent()?;
+            return Some(format!("overrides {}.{}", parent.name().local_name(), method.name().local_name()));
+        }
+        let class = method.parent().filter(|p| p.is::<ClassType>())?;
+        if !method.name().namespace().is_public_ns() {
+            return None;
+        }
+        TypeHierarchy(host).supertypes(&class).iter()
+            .filter(|item| item.entity.is::<InterfaceType>())
+            .find(|item| item.entity.prototype(host).borrow().iter().any(|(name, _)| name.local_name() == method.name().local_name()))
+            .map(|item| format!("implements {}.{}", item.entity.name().local_name(), method.name().local_name()))
+    }
+
+    fn is_literal(exp: &Rc<Expression>) -> bool {
+        matches!(exp.as_ref(), Expression::NumericLiteral(_) | Expression::StringLiteral(_) | Expression::BooleanLiteral(_) | Expression::NullLiteral(_))
+    }
+}
+
+/// Maps the methods defined in source to their parameter names.
+struct ParameterNameCollector<'a> {
+    host: &'a Database,
+    names: HashMap<Entity, Vec<String>>,
+}
+
+impl<'a> Visitor for ParameterNameCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::FunctionDefinition(defn) = drtv.as_ref() {
+            if let Some(slot) = self.host.node_mapping().get(drtv) {
+                let names = defn.common.signature.parameters.iter()
+                    .map(|p| p.destructuring.destructuring.to_identifier_name().map(|(name, _)| name).unwrap_or_default())
+                    .collect();
+                self.names.insert(slot, names);
+            }
+        }
+        walk_directive(self, drtv);
+    }
+}
+
+struct InlayHintCollector<'a> {
+    host: &'a Database,
+    parameter_names: HashMap<Entity, Vec<String>>,
+    function_depth: usize,
+    hints: Vec<InlayHint>,
+}
+
+impl<'a> InlayHintCollector<'a> {
+    fn call_hints(&mut self, base: &Rc<Expression>, arguments: &[Rc<Expression>]) {
+        let Some(method) = referenced_entity(self.host, base) else {
+            return;
+        };
+        let Some(names) = self.parameter_names.get(&method) else {
+            return;
+        };
+        let params = method.signature(self.host).params();
+        for (i, argument) in arguments.iter().enumerate() {
+            // Arguments passed to a rest parameter are not named.
+            if params.iter().nth(i).map(|p| p.kind == ParameterKind::Rest).unwrap_or(true) {
+                break;
+            }
+            let Some(name) = names.get(i).filter(|name| !name.is_empty()) else {
+                continue;
+            };
+            if InlayHints::is_literal(argument) {
+                self.hints.push(InlayHint {
+                    offset: argument.location().first_offset(),
+                    label: format!("{name}:"),
+                    kind: InlayHintKind::ParameterName,
+                });
+            }
+        }
+    }
+
+    fn variable_hints(&mut self, defn: &VariableDefinition) {
+        for binding in defn.bindings.iter() {
+            if binding.destructuring.type_annotation.is_some() {
+                continue;
+            }
+            let Some((_, location)) = binding.destructuring.destructuring.to_identifier_name() else {
+                continue;
+            };
+            let Some(slot) = self.host.node_mapping().get(&binding.destructuring.destructuring) else {
+                continue;
+            };
+            let t = slot.static_type(self.host);
+            if t == self.host.any_type() || t.is::<InvalidationEntity>() {
+                continue;
+            }
+            self.hints.push(InlayHint {
+                offset: location.last_offset(),
+                label: format!(": {}", EntityDisplay::new(self.host, DisplayVerbosity::Short).type_name(&t)),
+                kind: InlayHintKind::Type,
+            });
+        }
+    }
+
+    fn method_hint(&mut self, drtv: &Rc<Directive>, defn: &FunctionDefinition) {
+        let location = match &defn.name {
+            FunctionName::Identifier((_, location)) |
+            FunctionName::Getter((_, location)) |
+            FunctionName::Setter((_, location)) => location.clone(),
+            FunctionName::Constructor(_) => return,
+        };
+        let Some(method) = self.host.node_mapping().get(drtv) else {
+            return;
+        };
+        if let Some(label) = InlayHints::origin(self.host, &method) {
+            self.hints.push(InlayHint { offset: location.last_offset(), label, kind: InlayHintKind::Override });
+        }
+    }
+}
+
+impl<'a> Visitor for InlayHintCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::VariableDefinition(defn) if self.function_depth > 0 => self.variable_hints(defn),
+            Directive::FunctionDefinition(defn) if self.function_depth == 0 => self.method_hint(drtv, defn),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            self.call_hints(&call.base, &call.arguments);
+        }
+        walk_expression(self, exp);
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        self.function_depth += 1;
+        walk_function_common(self, common);
+        self.function_depth -= 1;
+    }
+}
\ No newline at end of file
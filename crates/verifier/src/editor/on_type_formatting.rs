@@ -0,0 +1,188 @@
+use crate::ns::*;
+
+/// Indentation produced by `OnTypeFormatting`.
+#[derive(Clone, Debug)]
+pub struct IndentStyle {
+    /// Text of one indentation level, as four spaces or a tab.
+    pub unit: String,
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self {
+        Self { unit: "    ".into() }
+    }
+}
+
+/// Edits applied as a character is typed: the line of a typed `}` or
+/// `;` is re-indented, and a typed newline indents the new line and
+/// closes a block left open by it.
+///
+/// ```ignore
+/// // `offset` is just after the typed character in the reparsed text.
+/// let edits = OnTypeFormatting::edits(&program, offset, '\n', &IndentStyle::default());
+/// ```
+///
+/// A line is indented one level deeper than the line where its
+/// innermost enclosing block, `switch` body, `case`, multi-line
+/// literal or E4X element starts, or as deep as that line if it starts
+/// with the closing `}`, `]` or `</`. Indentation before the enclosing
+/// construct is kept as is, so E4X and `fx:Script` contents are
+/// indented relative to their tag.
+pub struct OnTypeFormatting;
+
+impl OnTypeFormatting {
+    pub fn edits(program: &Rc<Program>, offset: usize, typed: char, style: &IndentStyle) -> Vec<SourceEdit> {
+        let mut collector = IndentScopeCollector { text: program.location.compilation_unit().text(), scopes: vec![] };
+        collector.visit_program(program);
+        Self::edits_with_scopes(&collector.text, &collector.scopes, offset, typed, style)
+    }
+
+    /// Edits for a character typed in an MXML document, whose `fx:Script`
+    /// blocks are parsed as `scripts` with locations in the document.
+    pub fn edits_in_mxml(mxml: &Rc<Mxml>, scripts: &[Rc<Program>], offset: usize, typed: char, style: &IndentStyle) -> Vec<SourceEdit> {
+        let mut collector = IndentScopeCollector { text: mxml.location.compilation_unit().text(), scopes: vec![] };
+        for content in mxml.content.iter() {
+            collector.mxml_content(content);
+        }
+        for program in scripts.iter() {
+            collector.visit_program(program);
+        }
+        Self::edits_with_scopes(&collector.text, &collector.scopes, offset, typed, style)
+    }
+
+    fn edits_with_scopes(text: &str, scopes: &[IndentScope], offset: usize, typed: char, style: &IndentStyle) -> Vec<SourceEdit> {
+        if !matches!(typed, '}' | ';' | '\n') {
+            return vec![];
+        }
+        let text = SourceText::new(text);
+        let line = text.line_of_offset(offset);
+        let (Some(line_start), Some(line_text)) = (text.line_start(line), text.line_text(line)) else {
+            return vec![];
+        };
+        let leading = line_text.len() - line_text.trim_start().len();
+        let first = line_start + leading;
+        let rest = &line_text[leading..];
+
+        let mut edits = vec![];
+        let scope = scopes.iter()
+            .filter(|scope| text.line_of_offset(scope.start) < line && first <= scope.end)
+            .max_by_key(|scope| scope.start);
+        let indentation = match scope {
+            Some(scope) => {
+                let base = Self::indentation_of(&text, scope.start);
+                let closes = scope.close.map(|close| rest.starts_with(close)).unwrap_or(false);
+                if closes { base } else { format!("{base}{}", style.unit) }
+            },
+            None => String::new(),
+        };
+        if line_text[..leading] != indentation {
+            edits.push(SourceEdit::new(line_start, first, &indentation));
+        }
+
+        // A newline typed after the `{` of a block that the parser
+        // closed at the end of the text.
+        if typed == '\n' {
+            if let Some(scope) = scope.filter(|scope| scope.close == Some("}") && scope.unclosed && text.line_of_offset(scope.start) + 1 == line) {
+                let base = Self::indentation_of(&text, scope.start);
+                let end = line_start + line_text.len();
+                edits.push(SourceEdit::new(end, end, &format!("\n{base}}}")));
+            }
+        }
+        edits
+    }
+
+    fn indentation_of(text: &SourceText, offset: usize) -> String {
+        let line_text = text.line_text(text.line_of_offset(offset)).unwrap_or("");
+        line_text[..line_text.len() - line_text.trim_start().len()].to_owned()
+    }
+}
+
+/// A construct whose lines are indented one level deeper.
+struct IndentScope {
+    start: usize,
+    end: usize,
+    /// Token that closes the construct at the start of a line.
+    close: Option<&'static str>,
+    /// Whether a `}` is missing at the end of the construct.
+    unclosed: bool,
+}
+
+struct IndentScopeCollector {
+    text: String,
+    scopes: Vec<IndentScope>,
+}
+
+impl IndentScopeCollector {
+    fn push(&mut self, location: &Location, close: Option<&'static str>) {
+        let unclosed = close == Some("}") && !self.text.get(..location.last_offset()).map(|t| t.ends_with('}')).unwrap_or(true);
+        self.scopes.push(IndentScope { start: location.first_offset(), end: location.last_offset(), close, unclosed });
+    }
+
+    fn xml_element(&mut self, elem: &XmlElement) {
+        self.push(&elem.location, Some("</"));
+        for content in elem.content.iter().flatten() {
+            match content.as_ref() {
+                XmlContent::Element(elem) => self.xml_element(elem),
+                XmlContent::Expression(exp) => self.visit_expression(exp),
+                _ => {},
+            }
+        }
+    }
+
+    fn mxml_content(&mut self, content: &Rc<MxmlContent>) {
+        if let MxmlContent::Element(element) = content.as_ref() {
+            self.push(&element.location, Some("</"));
+            for content in element.content.iter().flatten() {
+                self.mxml_content(content);
+            }
+        }
+    }
+
+    /// Each case spans from its first label to the next case or the
+    /// closing brace of the `switch`.
+    fn cases(&mut self, swstmt: &SwitchStatement, location: &Location) {
+        let starts: Vec<usize> = swstmt.cases.iter()
+            .filter_map(|case| case.labels.first())
+            .map(|label| match label {
+                CaseLabel::Case((_, location)) => location.first_offset(),
+                CaseLabel::Default(location) => location.first_offset(),
+            })
+            .collect();
+        for (i, start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).cloned().unwrap_or(location.last_offset().saturating_sub(1)).saturating_sub(1);
+            self.scopes.push(IndentScope { start: *start, end, close: None, unclosed: false });
+        }
+    }
+}
+
+impl Visitor for IndentScopeCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::SwitchStatement(swstmt) => {
+                self.push(&drtv.location(), Some("}"));
+                self.cases(swstmt, &drtv.location());
+            },
+            Directive::SwitchTypeStatement(_) => self.push(&drtv.location(), Some("}")),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.push(&block.location, Some("}"));
+        walk_block(self, block);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::ObjectInitializer(_) => self.push(&exp.location(), Some("}")),
+            Expression::ArrayLiteral(_) | Expression::VectorLiteral(_) => self.push(&exp.location(), Some("]")),
+            Expression::Xml(e) => {
+                self.xml_element(&e.element);
+                return;
+            },
+            _ => {},
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SelectionRangeKind {
+    Expression,
+    Statement,
+    Block,
+    Function,
+    /// Definition directly inside a class, interface or enum.
+    Member,
+    /// Class, interface or enum definition.
+    Type,
+    Package,
+    Program,
+}
+
+#[derive(Clone, Debug)]
+pub struct SelectionRange {
+    pub location: Location,
+    pub kind: SelectionRangeKind,
+}
+
+/// Enclosing syntactic constructs of a position, for expand-selection
+/// in editors.
+///
+/// ```ignore
+/// let ranges = SelectionRanges::at(&program, offset);
+/// // ranges[0] is the innermost expression, the last range the program
+/// ```
+///
+/// Ranges are listed innermost first and each strictly contains the
+/// previous one; of constructs spanning the same text, the outermost is
+/// kept. Constructs of included files are not listed.
+pub struct SelectionRanges;
+
+impl SelectionRanges {
+    pub fn at(program: &Rc<Program>, offset: usize) -> Vec<SelectionRange> {
+        let mut collector = SelectionRangeCollector {
+            compilation_unit: program.location.compilation_unit(),
+            offset,
+            ranges: vec![],
+            level_start: 0,
+        };
+        collector.visit_program(program);
+        collector.ranges.reverse();
+        collector.ranges
+    }
+}
+
+struct SelectionRangeCollector {
+    compilation_unit: Rc<CompilationUnit>,
+    offset: usize,
+    /// Ranges outermost first.
+    ranges: Vec<SelectionRange>,
+    /// Number of ranges enclosing the constructs being visited, past
+    /// which a sibling construct has been entered.
+    level_start: usize,
+}
+
+impl SelectionRangeCollector {
+    fn contains(&self, location: &Location) -> bool {
+        Rc::ptr_eq(&location.compilation_unit(), &self.compilation_unit)
+            && location.first_offset() <= self.offset && self.offset <= location.last_offset()
+    }
+
+    /// Pushes a range and visits the children of its construct if it
+    /// contains the position.
+    ///
+    /// Only one construct of a level is entered: at the boundary between
+    /// two siblings, as in `f();|g();`, the one starting at the position
+    /// replaces the one ending there.
+    fn enter(&mut self, location: &Location, kind: SelectionRangeKind, visit_children: impl FnOnce(&mut Self)) {
+        if !self.contains(location) {
+            return;
+        }
+        if self.ranges.len() > self.level_start {
+            let previous = &self.ranges[self.level_start];
+            if previous.location.last_offset() != self.offset || location.first_offset() != self.offset {
+                return;
+            }
+            self.ranges.truncate(self.level_start);
+        }
+        let same_span = self.ranges.last().map(|r| r.location.first_offset() == location.first_offset() && r.location.last_offset() == location.last_offset()).unwrap_or(false);
+        if !same_span {
+            self.ranges.push(SelectionRange { location: location.clone(), kind });
+        }
+        let level_start = std::mem::replace(&mut self.level_start, self.ranges.len());
+        visit_children(self);
+        self.level_start = level_start;
+    }
+
+    /// Whether the innermost range is the block of a type definition.
+    fn in_type_block(&self) -> bool {
+        let n = self.level_start;
+        n >= 2 && self.ranges[n - 1].kind == SelectionRangeKind::Block && self.ranges[n - 2].kind == SelectionRangeKind::Type
+    }
+}
+
+impl Visitor for SelectionRangeCollector {
+    fn visit_program(&mut self, program: &Rc<Program>) {
+        self.enter(&program.location, SelectionRangeKind::Program, |this| {
+            for pckgdef in program.packages.iter() {
+                this.enter(&pckgdef.location, SelectionRangeKind::Package, |this| this.visit_block(&pckgdef.block));
+            }
+            for drtv in program.directives.iter() {
+                this.visit_directive(drtv);
+            }
+        });
+    }
+
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        let kind = match drtv.as_ref() {
+            Directive::ClassDefinition(_) | Directive::InterfaceDefinition(_) | Directive::EnumDefinition(_) => SelectionRangeKind::Type,
+            Directive::Block(_) => SelectionRangeKind::Block,
+            _ if self.in_type_block() => SelectionRangeKind::Member,
+            _ => SelectionRangeKind::Statement,
+        };
+        self.enter(&drtv.location(), kind, |this| walk_directive(this, drtv));
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.enter(&block.location, SelectionRangeKind::Block, |this| walk_block(this, block));
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        self.enter(&exp.location(), SelectionRangeKind::Expression, |this| walk_expression(this, exp));
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        self.enter(&common.location, SelectionRangeKind::Function, |this| walk_function_common(this, common));
+    }
+}
\ No newline at end of file
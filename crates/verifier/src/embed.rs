@@ -0,0 +1,20 @@
+mod bitmap_embed;
+pub use bitmap_embed::*;
+
+mod embed_source;
+pub use embed_source::*;
+
+mod font_file;
+pub use font_file::*;
+
+mod unicode_range;
+pub use unicode_range::*;
+
+mod font_embed;
+pub use font_embed::*;
+
+mod embedded_files;
+pub use embedded_files::*;
+
+mod true_type_outlines;
+pub use true_type_outlines::*;
\ No newline at end of file
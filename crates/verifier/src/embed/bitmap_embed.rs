@@ -0,0 +1,117 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BitmapFormat {
+    Png,
+    Jpeg,
+    Gif,
+}
+
+impl BitmapFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Gif => "GIF",
+        }
+    }
+}
+
+/// Format and dimensions of a bitmap file, read from its header.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BitmapInfo {
+    pub format: BitmapFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Whether the bitmap has an alpha channel or transparent color.
+    pub has_alpha: bool,
+}
+
+impl BitmapInfo {
+    /// Reads the header of a PNG, JPEG or GIF file, returning `None`
+    /// for other or truncated files.
+    pub fn read(data: &[u8]) -> Option<Self> {
+        let u16_be = |i: usize| data.get(i..i + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+        let u32_be = |i: usize| data.get(i..i + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            let color_type = *data.get(25)?;
+            let mut has_alpha = color_type == 4 || color_type == 6;
+            // A tRNS chunk gives a transparent color or palette alpha
+            let mut offset = 8;
+            while let (Some(length), Some(kind)) = (u32_be(offset), data.get(offset + 4..offset + 8)) {
+                match kind {
+                    b"tRNS" => has_alpha = true,
+                    b"IDAT" | b"IEND" => break,
+                    _ => {},
+                }
+                offset += 12 + length as usize;
+            }
+            return Some(Self { format: BitmapFormat::Png, width: u32_be(16)?, height: u32_be(20)?, has_alpha });
+        }
+        if data.starts_with(&[0xFF, 0xD8]) {
+            let mut offset = 2;
+            while *data.get(offset)? == 0xFF {
+                let marker = *data.get(offset + 1)?;
+                // Start of frame markers, excluding DHT, JPG and DAC
+                if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+                    return Some(Self { format: BitmapFormat::Jpeg, width: u16_be(offset + 7)? as u32, height: u16_be(offset + 5)? as u32, has_alpha: false });
+                }
+                offset += 2 + u16_be(offset + 2)? as usize;
+            }
+            return None;
+        }
+        if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+            let u16_le = |i: usize| data.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+            // Transparency is given by graphic control extensions
+            let has_alpha = data.windows(3).any(|w| w[0] == 0x21 && w[1] == 0xF9 && w[2] == 4);
+            return Some(Self { format: BitmapFormat::Gif, width: u16_le(6)? as u32, height: u16_le(8)? as u32, has_alpha });
+        }
+        None
+    }
+}
+
+/// Options of an `[Embed]` meta-data embedding a bitmap:
+///
+/// ```actionscript
+/// [Embed(source="assets/logo.png", compression="true", quality="80")]
+/// public static const Logo:Class;
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct BitmapEmbedOptions {
+    pub source: String,
+    /// Whether a lossless bitmap may be stored with lossy compression.
+    pub compression: bool,
+    /// JPEG quality from 0 to 100.
+    pub quality: Option<u8>,
+}
+
+impl BitmapEmbedOptions {
+    pub const MIME_TYPES: [&'static str; 4] = ["image/png", "image/jpeg", "image/jpg", "image/gif"];
+
+    /// Reads the options of an `[Embed]` meta-data, returning `None` if
+    /// it does not embed a bitmap.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        if metadata.name.0 != "Embed" {
+            return None;
+        }
+        let value = |key: &str| metadata.entries.iter().flatten()
+            .find(|entry| entry.key.as_ref().map(|(k, _)| k == key).unwrap_or(false))
+            .map(|entry| match entry.value.as_ref() {
+                MetadataValue::String(val) => val.0.clone(),
+                MetadataValue::IdentifierString(val) => val.0.clone(),
+            });
+        let source = value("source")?;
+        let is_bitmap = match value("mimeType") {
+            Some(mime_type) => Self::MIME_TYPES.contains(&mime_type.as_str()),
+            None => [".png", ".jpg", ".jpeg", ".gif"].iter().any(|ext| source.to_lowercase().ends_with(ext)),
+        };
+        if !is_bitmap {
+            return None;
+        }
+        Some(Self {
+            source,
+            compression: value("compression").map(|v| v == "true").unwrap_or(false),
+            quality: value("quality").and_then(|v| v.parse::<u8>().ok()).filter(|q| *q <= 100),
+        })
+    }
+}
\ No newline at end of file
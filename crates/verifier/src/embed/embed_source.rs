@@ -0,0 +1,31 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Resolution of the `source` of an `[Embed]` meta-data.
+pub struct EmbedSource;
+
+impl EmbedSource {
+    /// Paths a `source` may refer to, in order of preference, with `.`
+    /// and `..` components resolved: relative to the embedding file, or
+    /// to a source path directory if it starts with a slash.
+    pub fn candidates(source: &str, file_path: &str, source_path: &[String]) -> Vec<String> {
+        let candidates: Vec<PathBuf> = match source.strip_prefix('/') {
+            Some(relative) => source_path.iter().map(|root| Path::new(root).join(relative)).collect(),
+            None => vec![Path::new(file_path).parent().unwrap_or(Path::new("")).join(source)],
+        };
+        candidates.iter().map(|p| Self::normalize(p)).collect()
+    }
+
+    fn normalize(path: &Path) -> String {
+        let mut r = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {},
+                Component::ParentDir if matches!(r.components().next_back(), Some(Component::Normal(_))) => {
+                    r.pop();
+                },
+                component => r.push(component),
+            }
+        }
+        r.to_string_lossy().into_owned()
+    }
+}
\ No newline at end of file
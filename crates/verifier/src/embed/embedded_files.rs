@@ -0,0 +1,101 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Contents of the files embedded by the `[Embed]` meta-data of a set
+/// of programs, read from a `SourceProvider` before verification.
+///
+/// ```ignore
+/// let files = EmbeddedFiles::load_from(&sources, &programs, &compiler_options.source_path);
+/// verifier.set_embedded_files(Rc::new(files));
+/// ```
+#[derive(Clone, Default)]
+pub struct EmbeddedFiles {
+    /// Contents keyed by path.
+    files: BTreeMap<String, Rc<Vec<u8>>>,
+}
+
+impl EmbeddedFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the embedded files from the file system.
+    #[cfg(feature = "fs")]
+    pub fn load(programs: &[Rc<Program>], source_path: &[String]) -> Self {
+        Self::load_from(&FileSystemSourceProvider::default(), programs, source_path)
+    }
+
+    /// Reads the files the `[Embed]` meta-data of the given programs
+    /// refer to. Files that cannot be read are left out.
+    pub fn load_from(sources: &dyn SourceProvider, programs: &[Rc<Program>], source_path: &[String]) -> Self {
+        let mut collector = EmbedSourceCollector { sources: vec![] };
+        for program in programs.iter() {
+            collector.visit_program(program);
+        }
+        let mut r = Self::new();
+        for (source, file_path) in collector.sources {
+            for path in EmbedSource::candidates(&source, &file_path, source_path) {
+                if r.files.contains_key(&path) {
+                    break;
+                }
+                if let Some(data) = sources.read(&path) {
+                    r.files.insert(path, Rc::new(data));
+                    break;
+                }
+            }
+        }
+        r
+    }
+
+    pub fn insert(&mut self, path: &str, data: Vec<u8>) {
+        self.files.insert(path.to_owned(), Rc::new(data));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns the path and contents of the file a `source` refers to,
+    /// as `EmbedSource::candidates()` resolves it.
+    pub fn resolve(&self, source: &str, file_path: &str, source_path: &[String]) -> Option<(String, Rc<Vec<u8>>)> {
+        EmbedSource::candidates(source, file_path, source_path).into_iter()
+            .find_map(|path| self.files.get(&path).cloned().map(|data| (path, data)))
+    }
+}
+
+/// Collects the `source` of the `[Embed]` meta-data of classes and
+/// variables with the path of the embedding file.
+struct EmbedSourceCollector {
+    sources: Vec<(String, String)>,
+}
+
+impl EmbedSourceCollector {
+    fn collect(&mut self, attributes: &[Attribute]) {
+        for metadata in Attribute::find_metadata(attributes).iter() {
+            if metadata.name.0 != "Embed" {
+                continue;
+            }
+            let source = metadata.entries.iter().flatten()
+                .find(|entry| entry.key.as_ref().map(|(k, _)| k == "source").unwrap_or(false))
+                .map(|entry| match entry.value.as_ref() {
+                    MetadataValue::String(val) => val.0.clone(),
+                    MetadataValue::IdentifierString(val) => val.0.clone(),
+                });
+            if let Some(source) = source {
+                let file_path = metadata.location.compilation_unit().file_path().unwrap_or_default();
+                self.sources.push((source, file_path));
+            }
+        }
+    }
+}
+
+impl Visitor for EmbedSourceCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => self.collect(&defn.attributes),
+            Directive::VariableDefinition(defn) => self.collect(&defn.attributes),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use crate::ns::*;
+
+/// Options of an `[Embed]` meta-data embedding a font file:
+///
+/// ```actionscript
+/// [Embed(source="assets/Inter-Bold.otf", fontName="Inter", fontWeight="bold",
+///     unicodeRange="U+0020-U+007E", embedAsCFF="true", mimeType="application/x-font")]
+/// private static const InterBold:Class;
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct FontEmbedOptions {
+    pub source: String,
+    /// Name the font is registered under, used by text fields.
+    pub font_name: Option<String>,
+    pub font_weight: Option<String>,
+    pub font_style: Option<String>,
+    pub unicode_range: Option<String>,
+    pub embed_as_cff: Option<String>,
+}
+
+impl FontEmbedOptions {
+    /// MIME types of font embeds, besides those inferred from the file
+    /// extension.
+    pub const MIME_TYPES: [&'static str; 3] = ["application/x-font", "application/x-font-truetype", "application/x-font-opentype"];
+
+    /// Reads the options of an `[Embed]` meta-data, returning `None` if
+    /// it does not embed a font file.
+    pub fn from_metadata(metadata: &Metadata) -> Option<Self> {
+        if metadata.name.0 != "Embed" {
+            return None;
+        }
+        let value = |key: &str| metadata.entries.iter().flatten()
+            .find(|entry| entry.key.as_ref().map(|(k, _)| k == key).unwrap_or(false))
+            .map(|entry| match entry.value.as_ref() {
+                MetadataValue::String(val) => val.0.clone(),
+                MetadataValue::IdentifierString(val) => val.0.clone(),
+            });
+        let source = value("source")?;
+        let is_font = match value("mimeType") {
+            Some(mime_type) => Self::MIME_TYPES.contains(&mime_type.as_str()),
+            None => [".ttf", ".otf", ".ttc"].iter().any(|ext| source.to_lowercase().ends_with(ext)),
+        };
+        if !is_font {
+            return None;
+        }
+        Some(Self {
+            source,
+            font_name: value("fontName"),
+            font_weight: value("fontWeight"),
+            font_style: value("fontStyle"),
+            unicode_range: value("unicodeRange"),
+            embed_as_cff: value("embedAsCFF"),
+        })
+    }
+
+    pub fn is_bold(&self) -> bool {
+        self.font_weight.as_ref().map(|w| w.eq_ignore_ascii_case("bold")).unwrap_or(false)
+    }
+
+    pub fn is_italic(&self) -> bool {
+        self.font_style.as_ref().map(|s| s.eq_ignore_ascii_case("italic") || s.eq_ignore_ascii_case("oblique")).unwrap_or(false)
+    }
+
+    /// Whether to embed the font for the Flash Text Engine, the default,
+    /// or `None` if `embedAsCFF` is neither `true` nor `false`.
+    pub fn embed_as_cff(&self) -> Option<bool> {
+        match self.embed_as_cff.as_deref() {
+            None | Some("true") => Some(true),
+            Some("false") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Ranges to embed, or the whole Unicode range if unspecified.
+    pub fn ranges(&self) -> Option<Vec<(u32, u32)>> {
+        match self.unicode_range.as_ref() {
+            Some(range) => UnicodeRange::parse(range).map(|r| r.ranges),
+            None => Some(vec![(0, 0x10FFFF)]),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,324 @@
+#[derive(Clone, PartialEq, Debug)]
+pub enum FontFileError {
+    UnexpectedEof,
+    /// Neither a TrueType, OpenType nor TrueType collection file.
+    UnsupportedFormat,
+    /// A required table is missing, such as `cmap`.
+    MissingTable(&'static str),
+}
+
+/// Names, style and character coverage of a TrueType or OpenType font,
+/// read for validating `[Embed]` font options.
+///
+/// ```ignore
+/// let font = FontFile::parse(&std::fs::read("assets/Inter.otf")?)?;
+/// assert!(font.covers('A' as u32));
+/// ```
+///
+/// Of a TrueType collection, only the first font is read.
+#[derive(Clone, Default, Debug)]
+pub struct FontFile {
+    /// Typographic family name, as `Inter`.
+    pub family_name: Option<String>,
+    /// Typographic subfamily name, as `Bold Italic`.
+    pub subfamily_name: Option<String>,
+    pub full_name: Option<String>,
+    pub postscript_name: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    /// Whether the outlines are CFF (an `OTTO` OpenType font) rather
+    /// than TrueType.
+    pub cff: bool,
+    pub glyph_count: u16,
+    /// Sorted, disjoint ranges of the code points mapped to a glyph.
+    pub coverage: Vec<(u32, u32)>,
+}
+
+impl FontFile {
+    pub fn parse(data: &[u8]) -> Result<Self, FontFileError> {
+        let tables = FontTables::parse(data)?;
+        let table = |name: &[u8]| tables.get(name);
+
+        let mut r = Self { cff: tables.cff, ..Self::default() };
+        if let Some(maxp) = table(b"maxp") {
+            r.glyph_count = read_u16(maxp, 4)?;
+        }
+        if let Some(os2) = table(b"OS/2") {
+            let selection = read_u16(os2, 62)?;
+            r.italic = selection & 1 != 0;
+            r.bold = selection & 0x20 != 0;
+        } else if let Some(head) = table(b"head") {
+            let mac_style = read_u16(head, 44)?;
+            r.bold = mac_style & 1 != 0;
+            r.italic = mac_style & 2 != 0;
+        }
+        if let Some(name) = table(b"name") {
+            r.read_names(name)?;
+        }
+        r.coverage = Self::read_cmap(table(b"cmap").ok_or(FontFileError::MissingTable("cmap"))?)?;
+        Ok(r)
+    }
+
+    /// Whether a code point is mapped to a glyph.
+    pub fn covers(&self, code_point: u32) -> bool {
+        let i = self.coverage.partition_point(|(_, last)| *last < code_point);
+        self.coverage.get(i).map(|(first, _)| *first <= code_point).unwrap_or(false)
+    }
+
+    /// Parts of the given ranges not mapped to a glyph.
+    pub fn missing_ranges(&self, ranges: &[(u32, u32)]) -> Vec<(u32, u32)> {
+        let mut r = vec![];
+        for &(first, last) in ranges.iter() {
+            let mut start: Option<u32> = None;
+            for code_point in first..=last {
+                match (self.covers(code_point), start) {
+                    (false, None) => start = Some(code_point),
+                    (true, Some(s)) => {
+                        r.push((s, code_point - 1));
+                        start = None;
+                    },
+                    _ => {},
+                }
+            }
+            if let Some(s) = start {
+                r.push((s, last));
+            }
+        }
+        r
+    }
+
+    fn read_names(&mut self, table: &[u8]) -> Result<(), FontFileError> {
+        let count = read_u16(table, 2)? as usize;
+        let storage = read_u16(table, 4)? as usize;
+        // Names per ID, preferring Windows names to Macintosh ones
+        let mut names = std::collections::BTreeMap::<u16, (bool, String)>::new();
+        for i in 0..count {
+            let record = 6 + i * 12;
+            let platform = read_u16(table, record)?;
+            let encoding = read_u16(table, record + 2)?;
+            let name_id = read_u16(table, record + 6)?;
+            let length = read_u16(table, record + 8)? as usize;
+            let start = storage + read_u16(table, record + 10)? as usize;
+            let bytes = read_bytes(table, start, length)?;
+            let (windows, value) = match (platform, encoding) {
+                (0, _) | (3, 0) | (3, 1) | (3, 10) => {
+                    let units = bytes.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect::<Vec<_>>();
+                    (true, String::from_utf16_lossy(&units))
+                },
+                (1, 0) => (false, bytes.iter().map(|b| *b as char).collect()),
+                _ => continue,
+            };
+            if names.get(&name_id).map(|(w, _)| !*w && windows).unwrap_or(true) {
+                names.insert(name_id, (windows, value));
+            }
+        }
+        let name = |id: u16| names.get(&id).map(|(_, value)| value.clone());
+        self.family_name = name(16).or_else(|| name(1));
+        self.subfamily_name = name(17).or_else(|| name(2));
+        self.full_name = name(4);
+        self.postscript_name = name(6);
+        Ok(())
+    }
+
+    /// Reads the coverage of the Unicode subtable, preferring a full
+    /// repertoire (format 12) subtable to a BMP (format 4) one.
+    fn read_cmap(table: &[u8]) -> Result<Vec<(u32, u32)>, FontFileError> {
+        let mut ranges = vec![];
+        match CmapSubtable::find(table)? {
+            Some(CmapSubtable::Full(offset)) => {
+                let groups = read_u32(table, checked_offset(offset, 12)?)? as usize;
+                for i in 0..groups {
+                    let group = checked_offset(offset, 16 + i * 12)?;
+                    let mut first = read_u32(table, group)?;
+                    let last = read_u32(table, group + 4)?;
+                    if read_u32(table, group + 8)? == 0 {
+                        first = first.saturating_add(1);
+                    }
+                    if first <= last {
+                        ranges.push((first, last));
+                    }
+                }
+            },
+            Some(subtable @ CmapSubtable::Bmp(_)) => {
+                for i in 0..subtable.segment_count(table)? {
+                    let (first, last) = subtable.segment_range(table, i)?;
+                    for code_point in first..=last.min(0xFFFE) {
+                        if subtable.segment_glyph(table, i, code_point)? != 0 {
+                            ranges.push((code_point, code_point));
+                        }
+                    }
+                }
+            },
+            None => {},
+        }
+        ranges.sort();
+        let mut r: Vec<(u32, u32)> = vec![];
+        for (first, last) in ranges {
+            match r.last_mut() {
+                Some(prev) if first <= prev.1 + 1 => prev.1 = prev.1.max(last),
+                _ => r.push((first, last)),
+            }
+        }
+        Ok(r)
+    }
+}
+
+/// Table directory of a TrueType or OpenType font.
+pub(crate) struct FontTables<'a> {
+    /// Whether the outlines are CFF rather than TrueType.
+    pub cff: bool,
+    tables: Vec<(&'a [u8], &'a [u8])>,
+}
+
+impl<'a> FontTables<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, FontFileError> {
+        let mut offset = 0;
+        if read_u32(data, 0)? == 0x74746366 {
+            // `ttcf`: the offset of the first font follows the header
+            offset = read_u32(data, 12)? as usize;
+        }
+        let cff = match read_u32(data, offset)? {
+            0x00010000 | 0x74727565 => false,
+            0x4F54544F => true,
+            _ => return Err(FontFileError::UnsupportedFormat),
+        };
+        let num_tables = read_u16(data, checked_offset(offset, 4)?)? as usize;
+        let mut tables = Vec::with_capacity(num_tables);
+        for i in 0..num_tables {
+            let record = checked_offset(offset, 12 + i * 16)?;
+            let tag = read_bytes(data, record, 4)?;
+            let table_offset = read_u32(data, record + 8)? as usize;
+            let length = read_u32(data, record + 12)? as usize;
+            tables.push((tag, read_bytes(data, table_offset, length)?));
+        }
+        Ok(Self { cff, tables })
+    }
+
+    pub fn get(&self, name: &[u8]) -> Option<&'a [u8]> {
+        self.tables.iter().find(|(tag, _)| *tag == name).map(|(_, table)| *table)
+    }
+
+    pub fn require(&self, name: &'static str) -> Result<&'a [u8], FontFileError> {
+        self.get(name.as_bytes()).ok_or(FontFileError::MissingTable(name))
+    }
+}
+
+/// Unicode subtable of a `cmap` table, by offset into the table.
+#[derive(Copy, Clone)]
+pub(crate) enum CmapSubtable {
+    /// Format 12, covering the full repertoire.
+    Full(usize),
+    /// Format 4, covering the BMP.
+    Bmp(usize),
+}
+
+impl CmapSubtable {
+    /// Finds the Unicode subtable, preferring a full repertoire subtable
+    /// to a BMP one.
+    pub fn find(table: &[u8]) -> Result<Option<Self>, FontFileError> {
+        let count = read_u16(table, 2)? as usize;
+        let mut bmp: Option<usize> = None;
+        let mut full: Option<usize> = None;
+        for i in 0..count {
+            let record = 4 + i * 8;
+            let platform = read_u16(table, record)?;
+            let encoding = read_u16(table, record + 2)?;
+            let offset = read_u32(table, record + 4)? as usize;
+            if platform != 0 && platform != 3 {
+                continue;
+            }
+            match read_u16(table, offset)? {
+                12 if platform == 0 || encoding == 10 => full = full.or(Some(offset)),
+                4 if platform == 0 || encoding == 1 => bmp = bmp.or(Some(offset)),
+                _ => {},
+            }
+        }
+        Ok(full.map(Self::Full).or(bmp.map(Self::Bmp)))
+    }
+
+    /// Returns the glyph a code point is mapped to, or 0 if it is
+    /// not mapped.
+    pub fn glyph(&self, table: &[u8], code_point: u32) -> Result<u16, FontFileError> {
+        match *self {
+            Self::Full(offset) => {
+                let groups = read_u32(table, checked_offset(offset, 12)?)? as usize;
+                for i in 0..groups {
+                    let group = checked_offset(offset, 16 + i * 12)?;
+                    let first = read_u32(table, group)?;
+                    if (first..=read_u32(table, group + 4)?).contains(&code_point) {
+                        return Ok(read_u32(table, group + 8)?.wrapping_add(code_point - first) as u16);
+                    }
+                }
+                Ok(0)
+            },
+            Self::Bmp(_) => {
+                for i in 0..self.segment_count(table)? {
+                    let (first, last) = self.segment_range(table, i)?;
+                    if (first..=last).contains(&code_point) {
+                        return self.segment_glyph(table, i, code_point);
+                    }
+                }
+                Ok(0)
+            },
+        }
+    }
+
+    fn segment_count(&self, table: &[u8]) -> Result<usize, FontFileError> {
+        let Self::Bmp(offset) = *self else {
+            return Ok(0);
+        };
+        Ok(read_u16(table, checked_offset(offset, 6)?)? as usize / 2)
+    }
+
+    /// Offsets of the end code, start code, delta and range offset
+    /// arrays of a format 4 subtable.
+    fn segment_arrays(&self, table: &[u8]) -> Result<[usize; 4], FontFileError> {
+        let Self::Bmp(offset) = *self else {
+            return Ok([0; 4]);
+        };
+        let seg_count = self.segment_count(table)?;
+        let end_codes = checked_offset(offset, 14)?;
+        let start_codes = end_codes + seg_count * 2 + 2;
+        let deltas = start_codes + seg_count * 2;
+        Ok([end_codes, start_codes, deltas, deltas + seg_count * 2])
+    }
+
+    fn segment_range(&self, table: &[u8], i: usize) -> Result<(u32, u32), FontFileError> {
+        let [end_codes, start_codes, ..] = self.segment_arrays(table)?;
+        Ok((read_u16(table, start_codes + i * 2)? as u32, read_u16(table, end_codes + i * 2)? as u32))
+    }
+
+    fn segment_glyph(&self, table: &[u8], i: usize, code_point: u32) -> Result<u16, FontFileError> {
+        let [_, start_codes, deltas, range_offsets] = self.segment_arrays(table)?;
+        let first = read_u16(table, start_codes + i * 2)? as u32;
+        let delta = read_u16(table, deltas + i * 2)? as u32;
+        let range_offset = read_u16(table, range_offsets + i * 2)? as usize;
+        let glyph = if range_offset == 0 {
+            (code_point + delta) & 0xFFFF
+        } else {
+            let address = range_offsets + i * 2 + range_offset + (code_point - first) as usize * 2;
+            match read_u16(table, address)? as u32 {
+                0 => 0,
+                glyph => (glyph + delta) & 0xFFFF,
+            }
+        };
+        Ok(glyph as u16)
+    }
+}
+
+/// Adds a length to an offset read from a font, failing on overflow.
+pub(crate) fn checked_offset(offset: usize, length: usize) -> Result<usize, FontFileError> {
+    offset.checked_add(length).ok_or(FontFileError::UnexpectedEof)
+}
+
+pub(crate) fn read_bytes(data: &[u8], offset: usize, length: usize) -> Result<&[u8], FontFileError> {
+    data.get(offset..checked_offset(offset, length)?).ok_or(FontFileError::UnexpectedEof)
+}
+
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Result<u16, FontFileError> {
+    read_bytes(data, offset, 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+pub(crate) fn read_u32(data: &[u8], offset: usize) -> Result<u32, FontFileError> {
+    read_bytes(data, offset, 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
\ No newline at end of file
@@ -0,0 +1,227 @@
+use crate::ns::*;
+
+/// A point of a glyph contour, in font units with the y axis
+/// pointing up.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GlyphPoint {
+    pub x: f64,
+    pub y: f64,
+    /// Whether the point is on the contour rather than the control
+    /// point of a quadratic curve.
+    pub on_curve: bool,
+}
+
+/// Glyph outlines and metrics of a TrueType font, read for transcoding
+/// an `[Embed]` font into a `DefineFont3` tag.
+///
+/// ```ignore
+/// let outlines = TrueTypeOutlines::parse(&data)?;
+/// let glyph = outlines.glyph_index('A' as u32)?;
+/// let contours = outlines.contours(glyph)?;
+/// ```
+pub struct TrueTypeOutlines<'a> {
+    pub units_per_em: u16,
+    pub ascender: i16,
+    pub descender: i16,
+    pub line_gap: i16,
+    pub glyph_count: u16,
+    cmap: &'a [u8],
+    cmap_subtable: Option<CmapSubtable>,
+    loca: &'a [u8],
+    glyf: &'a [u8],
+    hmtx: &'a [u8],
+    long_loca: bool,
+    metric_count: u16,
+}
+
+impl<'a> TrueTypeOutlines<'a> {
+    /// Nesting limit of composite glyphs.
+    const MAX_COMPONENT_DEPTH: usize = 8;
+
+    pub fn parse(data: &'a [u8]) -> Result<Self, FontFileError> {
+        let tables = FontTables::parse(data)?;
+        if tables.cff {
+            return Err(FontFileError::MissingTable("glyf"));
+        }
+        let head = tables.require("head")?;
+        let hhea = tables.require("hhea")?;
+        let cmap = tables.require("cmap")?;
+        Ok(Self {
+            units_per_em: read_u16(head, 18)?.max(1),
+            ascender: read_u16(hhea, 4)? as i16,
+            descender: read_u16(hhea, 6)? as i16,
+            line_gap: read_u16(hhea, 8)? as i16,
+            glyph_count: read_u16(tables.require("maxp")?, 4)?,
+            cmap,
+            cmap_subtable: CmapSubtable::find(cmap)?,
+            loca: tables.require("loca")?,
+            glyf: tables.require("glyf")?,
+            hmtx: tables.require("hmtx")?,
+            long_loca: read_u16(head, 50)? != 0,
+            metric_count: read_u16(hhea, 34)?,
+        })
+    }
+
+    /// Returns the glyph a code point is mapped to, or 0, the missing
+    /// glyph, if it is not mapped.
+    pub fn glyph_index(&self, code_point: u32) -> Result<u16, FontFileError> {
+        match self.cmap_subtable {
+            Some(subtable) => subtable.glyph(self.cmap, code_point),
+            None => Ok(0),
+        }
+    }
+
+    /// Advance width of a glyph, in font units.
+    pub fn advance(&self, glyph: u16) -> Result<u16, FontFileError> {
+        // Glyphs past the last metric share its advance
+        let metric = glyph.min(self.metric_count.saturating_sub(1)) as usize;
+        read_u16(self.hmtx, metric * 4)
+    }
+
+    /// Contours of a glyph, resolving the components of
+    /// composite glyphs.
+    pub fn contours(&self, glyph: u16) -> Result<Vec<Vec<GlyphPoint>>, FontFileError> {
+        let mut r = vec![];
+        self.read_glyph(glyph, &[1.0, 0.0, 0.0, 1.0, 0.0, 0.0], 0, &mut r)?;
+        Ok(r)
+    }
+
+    fn glyph_data(&self, glyph: u16) -> Result<&'a [u8], FontFileError> {
+        let i = glyph as usize;
+        let (start, end) = if self.long_loca {
+            (read_u32(self.loca, i * 4)? as usize, read_u32(self.loca, i * 4 + 4)? as usize)
+        } else {
+            (read_u16(self.loca, i * 2)? as usize * 2, read_u16(self.loca, i * 2 + 2)? as usize * 2)
+        };
+        if end <= start {
+            return Ok(&[]);
+        }
+        read_bytes(self.glyf, start, end - start)
+    }
+
+    /// Reads the contours of a glyph transformed by the matrix
+    /// `[a, b, c, d, dx, dy]`.
+    fn read_glyph(&self, glyph: u16, transform: &[f64; 6], depth: usize, r: &mut Vec<Vec<GlyphPoint>>) -> Result<(), FontFileError> {
+        if glyph >= self.glyph_count || depth > Self::MAX_COMPONENT_DEPTH {
+            return Ok(());
+        }
+        let data = self.glyph_data(glyph)?;
+        if data.is_empty() {
+            return Ok(());
+        }
+        let contour_count = read_u16(data, 0)? as i16;
+        if contour_count < 0 {
+            return self.read_composite(data, transform, depth, r);
+        }
+        let contour_count = contour_count as usize;
+        let mut end_points = Vec::with_capacity(contour_count);
+        for i in 0..contour_count {
+            end_points.push(read_u16(data, 10 + i * 2)? as usize);
+        }
+        let point_count = end_points.last().map(|p| p + 1).unwrap_or(0);
+        let instructions_length = read_u16(data, 10 + contour_count * 2)? as usize;
+        let mut offset = checked_offset(12 + contour_count * 2, instructions_length)?;
+
+        let mut flags = Vec::with_capacity(point_count);
+        while flags.len() < point_count {
+            let flag = *data.get(offset).ok_or(FontFileError::UnexpectedEof)?;
+            offset += 1;
+            let mut repeat = 1;
+            if flag & 8 != 0 {
+                repeat += *data.get(offset).ok_or(FontFileError::UnexpectedEof)? as usize;
+                offset += 1;
+            }
+            flags.extend(std::iter::repeat_n(flag, repeat));
+        }
+        flags.truncate(point_count);
+
+        // Coordinates are deltas, either bytes with a separate sign or
+        // words, the "same" flag meaning a byte's sign or a zero delta
+        let mut read_coordinates = |short: u8, same: u8| -> Result<Vec<i32>, FontFileError> {
+            let mut value = 0;
+            let mut r = Vec::with_capacity(point_count);
+            for flag in flags.iter() {
+                if flag & short != 0 {
+                    let delta = *data.get(offset).ok_or(FontFileError::UnexpectedEof)? as i32;
+                    offset += 1;
+                    value += if flag & same != 0 { delta } else { -delta };
+                } else if flag & same == 0 {
+                    value += read_u16(data, offset)? as i16 as i32;
+                    offset += 2;
+                }
+                r.push(value);
+            }
+            Ok(r)
+        };
+        let xs = read_coordinates(2, 0x10)?;
+        let ys = read_coordinates(4, 0x20)?;
+
+        let [a, b, c, d, dx, dy] = *transform;
+        let mut start = 0;
+        for end in end_points {
+            if end < start || end >= point_count {
+                return Err(FontFileError::UnexpectedEof);
+            }
+            r.push((start..=end).map(|i| {
+                let (x, y) = (xs[i] as f64, ys[i] as f64);
+                GlyphPoint { x: a * x + c * y + dx, y: b * x + d * y + dy, on_curve: flags[i] & 1 != 0 }
+            }).collect());
+            start = end + 1;
+        }
+        Ok(())
+    }
+
+    fn read_composite(&self, data: &[u8], transform: &[f64; 6], depth: usize, r: &mut Vec<Vec<GlyphPoint>>) -> Result<(), FontFileError> {
+        const ARGS_ARE_WORDS: u16 = 1;
+        const ARGS_ARE_XY_VALUES: u16 = 2;
+        const HAS_SCALE: u16 = 8;
+        const MORE_COMPONENTS: u16 = 0x20;
+        const HAS_XY_SCALE: u16 = 0x40;
+        const HAS_2X2: u16 = 0x80;
+
+        let f2dot14 = |offset: usize| read_u16(data, offset).map(|v| v as i16 as f64 / 16384.0);
+        let mut offset = 10;
+        loop {
+            let flags = read_u16(data, offset)?;
+            let glyph = read_u16(data, offset + 2)?;
+            offset += 4;
+            let (arg1, arg2) = if flags & ARGS_ARE_WORDS != 0 {
+                offset += 4;
+                (read_u16(data, offset - 4)? as i16 as f64, read_u16(data, offset - 2)? as i16 as f64)
+            } else {
+                offset += 2;
+                (read_bytes(data, offset - 2, 1)?[0] as i8 as f64, read_bytes(data, offset - 1, 1)?[0] as i8 as f64)
+            };
+            // Components positioned by matching points are placed
+            // at the origin
+            let (dx, dy) = if flags & ARGS_ARE_XY_VALUES != 0 { (arg1, arg2) } else { (0.0, 0.0) };
+            let (a, b, c, d) = if flags & HAS_SCALE != 0 {
+                offset += 2;
+                let scale = f2dot14(offset - 2)?;
+                (scale, 0.0, 0.0, scale)
+            } else if flags & HAS_XY_SCALE != 0 {
+                offset += 4;
+                (f2dot14(offset - 4)?, 0.0, 0.0, f2dot14(offset - 2)?)
+            } else if flags & HAS_2X2 != 0 {
+                offset += 8;
+                (f2dot14(offset - 8)?, f2dot14(offset - 6)?, f2dot14(offset - 4)?, f2dot14(offset - 2)?)
+            } else {
+                (1.0, 0.0, 0.0, 1.0)
+            };
+            // Apply the component's transform, then the glyph's
+            let [ta, tb, tc, td, tdx, tdy] = *transform;
+            let combined = [
+                ta * a + tc * b,
+                tb * a + td * b,
+                ta * c + tc * d,
+                tb * c + td * d,
+                ta * dx + tc * dy + tdx,
+                tb * dx + td * dy + tdy,
+            ];
+            self.read_glyph(glyph, &combined, depth + 1, r)?;
+            if flags & MORE_COMPONENTS == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+/// Code point ranges of an `[Embed]` `unicodeRange` option, as
+/// `U+0020-U+007E,U+00A0`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UnicodeRange {
+    /// Inclusive ranges, in the order given.
+    pub ranges: Vec<(u32, u32)>,
+}
+
+impl UnicodeRange {
+    /// Parses comma-separated `U+XXXX` code points and `U+XXXX-U+YYYY`
+    /// ranges, returning `None` if malformed.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut ranges = vec![];
+        for item in text.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (first, last) = match item.split_once('-') {
+                Some((first, last)) => (Self::code_point(first)?, Self::code_point(last)?),
+                None => {
+                    let code_point = Self::code_point(item)?;
+                    (code_point, code_point)
+                },
+            };
+            if first > last {
+                return None;
+            }
+            ranges.push((first, last));
+        }
+        (!ranges.is_empty()).then_some(Self { ranges })
+    }
+
+    pub fn contains(&self, code_point: u32) -> bool {
+        self.ranges.iter().any(|(first, last)| (*first..=*last).contains(&code_point))
+    }
+
+    /// Formats ranges back into the `unicodeRange` syntax.
+    pub fn format(ranges: &[(u32, u32)]) -> String {
+        ranges.iter().map(|(first, last)| if first == last {
+            format!("U+{first:04X}")
+        } else {
+            format!("U+{first:04X}-U+{last:04X}")
+        }).collect::<Vec<_>>().join(",")
+    }
+
+    fn code_point(text: &str) -> Option<u32> {
+        let text = text.trim();
+        let digits = text.strip_prefix("U+").or_else(|| text.strip_prefix("u+"))?;
+        u32::from_str_radix(digits, 16).ok().filter(|code_point| *code_point <= 0x10FFFF)
+    }
+}
\ No newline at end of file
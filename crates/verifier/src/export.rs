@@ -0,0 +1,8 @@
+mod json;
+pub(crate) use json::*;
+
+mod typed_ast_json;
+pub use typed_ast_json::*;
+
+mod semantic_query;
+pub use semantic_query::*;
\ No newline at end of file
@@ -0,0 +1,17 @@
+/// Quotes and escapes a string as a JSON string literal.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut r = String::from("\"");
+    for ch in s.chars() {
+        match ch {
+            '"' => r.push_str("\\\""),
+            '\\' => r.push_str("\\\\"),
+            '\n' => r.push_str("\\n"),
+            '\r' => r.push_str("\\r"),
+            '\t' => r.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => r.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => r.push(ch),
+        }
+    }
+    r.push('"');
+    r
+}
\ No newline at end of file
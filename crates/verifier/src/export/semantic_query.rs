@@ -0,0 +1,438 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Field value of a `SemanticQueryIndex` row.
+#[derive(Clone, PartialEq, Debug)]
+pub enum QueryValue {
+    Text(String),
+    Number(usize),
+}
+
+impl QueryValue {
+    pub fn to_json(&self) -> String {
+        match self {
+            Self::Text(s) => json_string(s),
+            Self::Number(n) => n.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for QueryValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text(s) => write!(f, "{s}"),
+            Self::Number(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+pub type QueryRow = BTreeMap<&'static str, QueryValue>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum QueryCollection {
+    /// Classes, interfaces, enums and package-level functions and
+    /// variables, with the `id`, `kind`, `name`, `qualifiedName`,
+    /// `package`, `file` and `line` fields.
+    Definitions,
+    /// Members of classes, interfaces and enums, with the `owner`,
+    /// `name`, `kind`, `static`, `file` and `line` fields.
+    Members,
+    /// References from a package-level definition to another, with the
+    /// `from`, `from.package`, `to`, `to.package`, `file` and `line`
+    /// fields.
+    References,
+    /// Diagnostics, with the `file`, `line`, `column`, `severity`, `id`
+    /// and `message` fields.
+    Diagnostics,
+}
+
+impl QueryCollection {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "definitions" => Some(Self::Definitions),
+            "members" => Some(Self::Members),
+            "references" => Some(Self::References),
+            "diagnostics" => Some(Self::Diagnostics),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum QueryOperator {
+    /// `=`
+    Equals,
+    /// `!=`
+    NotEquals,
+    /// `^=`
+    StartsWith,
+    /// `$=`
+    EndsWith,
+    /// `*=`
+    Contains,
+}
+
+impl QueryOperator {
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "=" => Some(Self::Equals),
+            "!=" => Some(Self::NotEquals),
+            "^=" => Some(Self::StartsWith),
+            "$=" => Some(Self::EndsWith),
+            "*=" => Some(Self::Contains),
+            _ => None,
+        }
+    }
+
+    fn test(&self, field: &str, value: &str) -> bool {
+        match self {
+            Self::Equals => field == value,
+            Self::NotEquals => field != value,
+            Self::StartsWith => field.starts_with(value),
+            Self::EndsWith => field.ends_with(value),
+            Self::Contains => field.contains(value),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct QueryCondition {
+    pub field: String,
+    pub operator: QueryOperator,
+    pub value: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum SemanticQueryError {
+    UnknownCollection(String),
+    UnknownOperator(String),
+    /// A token other than the expected one, or `None` at the end of
+    /// the query.
+    Unexpected(Option<String>),
+    UnterminatedString,
+}
+
+impl SemanticQueryError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::UnknownCollection(name) => format!("Unknown collection '{name}'; expected definitions, members, references or diagnostics"),
+            Self::UnknownOperator(op) => format!("Unknown operator '{op}'; expected =, !=, ^=, $= or *="),
+            Self::Unexpected(Some(token)) => format!("Unexpected '{token}'"),
+            Self::Unexpected(None) => "Unexpected end of query".into(),
+            Self::UnterminatedString => "Unterminated string".into(),
+        }
+    }
+}
+
+/// A query over a `SemanticQueryIndex`, as
+/// `references where from.package ^= "app.ui" and to.package ^= "app.network"`.
+///
+/// The syntax is `COLLECTION [where CONDITION (and CONDITION)*] [limit N]`,
+/// where each condition compares a field with a value, quoted or not, by
+/// one of `=`, `!=`, `^=` (starts with), `$=` (ends with) and `*=`
+/// (contains). Unquoted values extend to the next whitespace, so they may
+/// contain operator characters, as in `name $= $internal`. Fields compare
+/// as text, and rows lacking a field never match a condition on it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SemanticQuery {
+    pub collection: QueryCollection,
+    pub conditions: Vec<QueryCondition>,
+    pub limit: Option<usize>,
+}
+
+impl SemanticQuery {
+    pub fn parse(text: &str) -> Result<Self, SemanticQueryError> {
+        let mut tokens = Self::tokenize(text)?.into_iter().peekable();
+        let collection = tokens.next().ok_or(SemanticQueryError::Unexpected(None))?;
+        let collection = QueryCollection::from_name(&collection).ok_or(SemanticQueryError::UnknownCollection(collection))?;
+        let mut query = Self { collection, conditions: vec![], limit: None };
+        if tokens.peek().map(|t| t == "where").unwrap_or(false) {
+            tokens.next();
+            loop {
+                let field = tokens.next().ok_or(SemanticQueryError::Unexpected(None))?;
+                let operator = tokens.next().ok_or(SemanticQueryError::Unexpected(None))?;
+                let operator = QueryOperator::from_token(&operator).ok_or(SemanticQueryError::UnknownOperator(operator))?;
+                let value = tokens.next().ok_or(SemanticQueryError::Unexpected(None))?;
+                query.conditions.push(QueryCondition { field, operator, value });
+                if tokens.peek().map(|t| t == "and").unwrap_or(false) {
+                    tokens.next();
+                    continue;
+                }
+                break;
+            }
+        }
+        if tokens.peek().map(|t| t == "limit").unwrap_or(false) {
+            tokens.next();
+            let n = tokens.next().ok_or(SemanticQueryError::Unexpected(None))?;
+            query.limit = Some(n.parse().map_err(|_| SemanticQueryError::Unexpected(Some(n)))?);
+        }
+        if let Some(token) = tokens.next() {
+            return Err(SemanticQueryError::Unexpected(Some(token)));
+        }
+        Ok(query)
+    }
+
+    /// Splits a query into words, operators and quoted strings, the
+    /// latter unquoted. The word following an operator ends only at
+    /// whitespace.
+    fn tokenize(text: &str) -> Result<Vec<String>, SemanticQueryError> {
+        let mut r = vec![];
+        let mut chars = text.chars().peekable();
+        let mut after_operator = false;
+        while let Some(&ch) = chars.peek() {
+            if ch.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            let is_operator = !after_operator && "=!^$*".contains(ch);
+            if ch == '"' {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => s.extend(chars.next()),
+                        Some(ch) => s.push(ch),
+                        None => return Err(SemanticQueryError::UnterminatedString),
+                    }
+                }
+                r.push(s);
+            } else if is_operator {
+                let mut s = String::new();
+                while let Some(&ch) = chars.peek().filter(|ch| "=!^$*".contains(**ch)) {
+                    s.push(ch);
+                    chars.next();
+                }
+                r.push(s);
+            } else {
+                let delimiters = if after_operator { "" } else { "=!^$*\"" };
+                let mut s = String::new();
+                while let Some(&ch) = chars.peek().filter(|ch| !ch.is_whitespace() && !delimiters.contains(**ch)) {
+                    s.push(ch);
+                    chars.next();
+                }
+                r.push(s);
+            }
+            after_operator = is_operator;
+        }
+        Ok(r)
+    }
+}
+
+/// Read-only tables of the definitions, members, references and
+/// diagnostics of a completed compilation, queried through
+/// `SemanticQuery`, so that architecture checks can be scripted without
+/// writing Rust.
+///
+/// ```ignore
+/// let index = SemanticQueryIndex::build(&host, &output);
+/// let json = index.run_json("references where from.package ^= \"app.ui\" and to.package ^= \"app.network\"")?;
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SemanticQueryIndex {
+    pub definitions: Vec<QueryRow>,
+    pub members: Vec<QueryRow>,
+    pub references: Vec<QueryRow>,
+    pub diagnostics: Vec<QueryRow>,
+}
+
+impl SemanticQueryIndex {
+    pub fn build(host: &Database, output: &CompilationOutput) -> Self {
+        let mut collector = QueryIndexCollector { host, index: Self::default(), current: None };
+        for program in output.programs.iter() {
+            collector.visit_program(program);
+        }
+        let mut index = collector.index;
+        for cu in output.compilation_units.iter() {
+            cu.sort_diagnostics();
+            for diagnostic in cu.nested_diagnostics().iter() {
+                let d = WhackDiagnostic(diagnostic);
+                let location = diagnostic.location();
+                let mut row = QueryRow::new();
+                row.insert("file", QueryValue::Text(location.compilation_unit().file_path().unwrap_or_default()));
+                row.insert("line", QueryValue::Number(location.first_line_number()));
+                row.insert("column", QueryValue::Number(location.first_column() + 1));
+                row.insert("severity", QueryValue::Text(if diagnostic.is_warning() { "warning" } else { "error" }.into()));
+                row.insert("id", QueryValue::Number(d.id() as usize));
+                row.insert("message", QueryValue::Text(d.format_message_english()));
+                index.diagnostics.push(row);
+            }
+        }
+        index
+    }
+
+    pub fn rows(&self, collection: QueryCollection) -> &[QueryRow] {
+        match collection {
+            QueryCollection::Definitions => &self.definitions,
+            QueryCollection::Members => &self.members,
+            QueryCollection::References => &self.references,
+            QueryCollection::Diagnostics => &self.diagnostics,
+        }
+    }
+
+    pub fn run(&self, query: &SemanticQuery) -> Vec<&QueryRow> {
+        self.rows(query.collection).iter()
+            .filter(|row| query.conditions.iter().all(|c| {
+                row.get(c.field.as_str()).map(|v| c.operator.test(&v.to_string(), &c.value)).unwrap_or(false)
+            }))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    /// Runs a query given as text, returning the matching rows as a JSON
+    /// array of objects.
+    pub fn run_json(&self, query: &str) -> Result<String, SemanticQueryError> {
+        let query = SemanticQuery::parse(query)?;
+        let rows = self.run(&query).iter().map(|row| {
+            let fields = row.iter().map(|(k, v)| format!("{}:{}", json_string(k), v.to_json())).collect::<Vec<_>>();
+            format!("{{{}}}", fields.join(","))
+        }).collect::<Vec<_>>();
+        Ok(format!("[{}]", rows.join(",")))
+    }
+
+    /// Kind of a definition or member, as in `StableEntityId`.
+    fn kind(entity: &Entity) -> &'static str {
+        if entity.is::<ClassType>() {
+            "class"
+        } else if entity.is::<InterfaceType>() {
+            "interface"
+        } else if entity.is::<EnumType>() {
+            "enum"
+        } else if entity.is::<MethodSlot>() {
+            "method"
+        } else if entity.is::<VirtualSlot>() {
+            "accessor"
+        } else if entity.is::<VariableSlot>() {
+            "variable"
+        } else {
+            "other"
+        }
+    }
+
+    fn package_name(entity: &Entity) -> String {
+        let mut pckg = entity.parent();
+        while let Some(p) = pckg.as_ref().filter(|p| !p.is::<Package>()) {
+            pckg = p.parent();
+        }
+        pckg.map(|p| p.fully_qualified_name_list().join(".")).unwrap_or_default()
+    }
+
+    fn location_fields(row: &mut QueryRow, location: Option<Location>) {
+        if let Some(location) = location {
+            row.insert("file", QueryValue::Text(location.compilation_unit().file_path().unwrap_or_default()));
+            row.insert("line", QueryValue::Number(location.first_line_number()));
+        }
+    }
+}
+
+struct QueryIndexCollector<'a> {
+    host: &'a Database,
+    index: SemanticQueryIndex,
+    /// Package-level definition being visited.
+    current: Option<Entity>,
+}
+
+impl<'a> QueryIndexCollector<'a> {
+    fn is_package_definition(entity: &Entity) -> bool {
+        entity.parent().map(|p| p.is::<Package>()).unwrap_or(false)
+    }
+
+    fn add_definition(&mut self, definition: &Entity) {
+        let host = self.host;
+        let mut row = QueryRow::new();
+        if let Some(id) = StableEntityId::of(host, definition) {
+            row.insert("id", QueryValue::Text(id));
+        }
+        row.insert("kind", QueryValue::Text(SemanticQueryIndex::kind(definition).into()));
+        row.insert("name", QueryValue::Text(definition.name().local_name()));
+        row.insert("qualifiedName", QueryValue::Text(definition.to_string()));
+        row.insert("package", QueryValue::Text(SemanticQueryIndex::package_name(definition)));
+        SemanticQueryIndex::location_fields(&mut row, definition.location());
+        self.index.definitions.push(row);
+
+        if !(definition.is::<ClassType>() || definition.is::<InterfaceType>() || definition.is::<EnumType>()) {
+            return;
+        }
+        let mut members: Vec<(Entity, bool)> = definition.properties(host).borrow().iter().map(|(_, m)| (m.clone(), true)).collect();
+        members.extend(definition.prototype(host).borrow().iter().map(|(_, m)| (m.clone(), false)));
+        for (member, is_static) in members {
+            let mut row = QueryRow::new();
+            row.insert("owner", QueryValue::Text(definition.to_string()));
+            row.insert("name", QueryValue::Text(member.name().local_name()));
+            row.insert("kind", QueryValue::Text(SemanticQueryIndex::kind(&member).into()));
+            row.insert("static", QueryValue::Text(is_static.to_string()));
+            SemanticQueryIndex::location_fields(&mut row, member.location());
+            self.index.members.push(row);
+        }
+    }
+
+    fn add_reference(&mut self, target: Entity, location: &Location) {
+        let Some(from) = self.current.clone() else {
+            return;
+        };
+        let mut target = if target.is::<TypeAfterSubstitution>() { target.origin() } else { target };
+        // Members refer to their package-level definition.
+        while !Self::is_package_definition(&target) || target.is::<Package>() {
+            match target.parent() {
+                Some(parent) => target = parent,
+                None => return,
+            }
+        }
+        if target == from {
+            return;
+        }
+        let mut row = QueryRow::new();
+        row.insert("from", QueryValue::Text(from.to_string()));
+        row.insert("from.package", QueryValue::Text(SemanticQueryIndex::package_name(&from)));
+        row.insert("to", QueryValue::Text(target.to_string()));
+        row.insert("to.package", QueryValue::Text(SemanticQueryIndex::package_name(&target)));
+        SemanticQueryIndex::location_fields(&mut row, Some(location.clone()));
+        self.index.references.push(row);
+    }
+
+    /// Visits a package-level definition with its references attributed
+    /// to it.
+    fn enter(&mut self, definition: Entity, visit: impl FnOnce(&mut Self)) {
+        self.add_definition(&definition);
+        let previous = self.current.replace(definition);
+        visit(self);
+        self.current = previous;
+    }
+}
+
+impl<'a> Visitor for QueryIndexCollector<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        let definition = match drtv.as_ref() {
+            Directive::ClassDefinition(_) | Directive::InterfaceDefinition(_) | Directive::EnumDefinition(_) | Directive::FunctionDefinition(_) => {
+                self.host.node_mapping().get(drtv).filter(Self::is_package_definition)
+            },
+            Directive::VariableDefinition(defn) => {
+                defn.bindings.iter().find_map(|binding| self.host.node_mapping().get(&binding.destructuring.destructuring).filter(Self::is_package_definition))
+            },
+            _ => None,
+        };
+        match definition {
+            Some(definition) if self.current.is_none() => {
+                self.enter(definition.clone(), |collector| {
+                    if definition.is::<ClassType>() {
+                        if let Some(base) = definition.extends_class(collector.host) {
+                            collector.add_reference(base, &drtv.location());
+                        }
+                        for itrfc in definition.implements(collector.host).iter() {
+                            collector.add_reference(itrfc, &drtv.location());
+                        }
+                    }
+                    walk_directive(collector, drtv);
+                });
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Some(definition) = referenced_entity(self.host, exp) {
+            self.add_reference(definition, &exp.location());
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,170 @@
+use crate::ns::*;
+
+/// Exports verified programs as JSON, annotating nodes with
+/// the entities and static types from the node mapping.
+///
+/// Each program is exported as an object containing the file path
+/// and a list of nodes. Each node contains its kind, location
+/// (line and byte offsets) and its children. Nodes mapped to
+/// values and variables include their static type in the `type`
+/// field. Nodes mapped to definitions, or to references to them,
+/// include the definition as rendered by `EntityDisplay` with fully
+/// qualified names in the `entity` field, and its `StableEntityId`
+/// in the `id` field.
+///
+/// ```json
+/// [{"file":"Main.as","nodes":[{"kind":"ExpressionStatement","line":1,"start":0,"end":9,"children":[
+///     {"kind":"Call","line":1,"start":0,"end":8,"type":"void","children":[
+///         {"kind":"QualifiedIdentifier","line":1,"start":0,"end":6,"entity":"function f(): void","type":"function(): void","id":"method:f#5e0c1a2b","children":[]}
+///     ]}
+/// ]}]}]
+/// ```
+pub struct TypedAstJsonExporter {
+    host: Rc<Database>,
+    stack: Vec<Vec<String>>,
+}
+
+impl TypedAstJsonExporter {
+    pub fn export(host: &Rc<Database>, programs: &[Rc<Program>]) -> String {
+        let mut exporter = Self { host: host.clone(), stack: vec![] };
+        let mut list = Vec::<String>::new();
+        for program in programs.iter() {
+            exporter.stack.push(vec![]);
+            walk_program(&mut exporter, program);
+            let nodes = exporter.stack.pop().unwrap().join(",");
+            let file_path = program.location.compilation_unit().file_path().unwrap_or("".into());
+            list.push(format!("{{\"file\":{},\"nodes\":[{nodes}]}}", json_string(&file_path)));
+        }
+        format!("[{}]", list.join(","))
+    }
+
+    fn enter(&mut self) {
+        self.stack.push(vec![]);
+    }
+
+    fn exit(&mut self, kind: &str, location: &Location, entity: Option<Entity>) {
+        let children = self.stack.pop().unwrap().join(",");
+        let mut r = format!("{{\"kind\":{},\"line\":{},\"start\":{},\"end\":{}",
+            json_string(kind),
+            location.first_line_number(),
+            location.first_offset(),
+            location.last_offset());
+        if let Some(entity) = entity {
+            let definition = referenced_entity_of_value(&entity).or_else(|| Self::is_definition(&entity).then(|| entity.clone()));
+            if let Some(definition) = definition.as_ref() {
+                let display = EntityDisplay::new(&self.host, DisplayVerbosity::FullyQualified);
+                r.push_str(&format!(",\"entity\":{}", json_string(&display.property(definition))));
+            }
+            if entity.is::<Value>() || entity.is::<VariableSlot>() {
+                r.push_str(&format!(",\"type\":{}", json_string(&entity.static_type(&self.host).to_string())));
+            }
+            if let Some(id) = definition.and_then(|definition| StableEntityId::of(&self.host, &definition)) {
+                r.push_str(&format!(",\"id\":{}", json_string(&id)));
+            }
+        }
+        r.push_str(&format!(",\"children\":[{children}]}}"));
+        if let Some(parent) = self.stack.last_mut() {
+            parent.push(r);
+        }
+    }
+
+    fn is_definition(entity: &Entity) -> bool {
+        entity.is::<VariableSlot>() || entity.is::<MethodSlot>() || entity.is::<VirtualSlot>() || entity.is::<Type>() || entity.is::<Namespace>()
+    }
+
+    fn directive_kind(drtv: &Directive) -> &'static str {
+        match drtv {
+            Directive::Block(_) => "Block",
+            Directive::ClassDefinition(_) => "ClassDefinition",
+            Directive::ConfigurationDirective(_) => "ConfigurationDirective",
+            Directive::DefaultXmlNamespaceStatement(_) => "DefaultXmlNamespaceStatement",
+            Directive::DirectiveInjection(_) => "DirectiveInjection",
+            Directive::DoStatement(_) => "DoStatement",
+            Directive::EnumDefinition(_) => "EnumDefinition",
+            Directive::ExpressionStatement(_) => "ExpressionStatement",
+            Directive::ForInStatement(_) => "ForInStatement",
+            Directive::ForStatement(_) => "ForStatement",
+            Directive::FunctionDefinition(_) => "FunctionDefinition",
+            Directive::IfStatement(_) => "IfStatement",
+            Directive::ImportDirective(_) => "ImportDirective",
+            Directive::IncludeDirective(_) => "IncludeDirective",
+            Directive::InterfaceDefinition(_) => "InterfaceDefinition",
+            Directive::LabeledStatement(_) => "LabeledStatement",
+            Directive::NamespaceDefinition(_) => "NamespaceDefinition",
+            Directive::PackageConcatDirective(_) => "PackageConcatDirective",
+            Directive::ReturnStatement(_) => "ReturnStatement",
+            Directive::SuperStatement(_) => "SuperStatement",
+            Directive::SwitchStatement(_) => "SwitchStatement",
+            Directive::SwitchTypeStatement(_) => "SwitchTypeStatement",
+            Directive::ThrowStatement(_) => "ThrowStatement",
+            Directive::TryStatement(_) => "TryStatement",
+            Directive::TypeDefinition(_) => "TypeDefinition",
+            Directive::UseNamespaceDirective(_) => "UseNamespaceDirective",
+            Directive::VariableDefinition(_) => "VariableDefinition",
+            Directive::WhileStatement(_) => "WhileStatement",
+            Directive::WithStatement(_) => "WithStatement",
+            _ => "Directive",
+        }
+    }
+
+    fn expression_kind(exp: &Expression) -> &'static str {
+        match exp {
+            Expression::QualifiedIdentifier(_) => "QualifiedIdentifier",
+            Expression::Member(_) => "Member",
+            Expression::ComputedMember(_) => "ComputedMember",
+            Expression::NumericLiteral(_) => "NumericLiteral",
+            Expression::StringLiteral(_) => "StringLiteral",
+            Expression::Paren(_) => "Paren",
+            Expression::NullLiteral(_) => "NullLiteral",
+            Expression::BooleanLiteral(_) => "BooleanLiteral",
+            Expression::ThisLiteral(_) => "ThisLiteral",
+            Expression::RegExpLiteral(_) => "RegExpLiteral",
+            Expression::Xml(_) => "Xml",
+            Expression::XmlList(_) => "XmlList",
+            Expression::XmlMarkup(_) => "XmlMarkup",
+            Expression::ArrayLiteral(_) => "ArrayLiteral",
+            Expression::VectorLiteral(_) => "VectorLiteral",
+            Expression::ObjectInitializer(_) => "ObjectInitializer",
+            Expression::Invalidated(_) => "Invalidated",
+            Expression::ImportMeta(_) => "ImportMeta",
+            Expression::New(_) => "New",
+            Expression::Descendants(_) => "Descendants",
+            Expression::Filter(_) => "Filter",
+            Expression::Super(_) => "Super",
+            Expression::Call(_) => "Call",
+            Expression::WithTypeArguments(_) => "WithTypeArguments",
+            Expression::Unary(_) => "Unary",
+            Expression::OptionalChaining(_) => "OptionalChaining",
+            Expression::OptionalChainingPlaceholder(_) => "OptionalChainingPlaceholder",
+            Expression::Binary(_) => "Binary",
+            Expression::Conditional(_) => "Conditional",
+            Expression::Sequence(_) => "Sequence",
+            Expression::ReservedNamespace(_) => "ReservedNamespace",
+            Expression::NullableType(_) => "NullableType",
+            Expression::NonNullableType(_) => "NonNullableType",
+            Expression::AnyType(_) => "AnyType",
+            Expression::VoidType(_) => "VoidType",
+            Expression::ArrayType(_) => "ArrayType",
+            Expression::TupleType(_) => "TupleType",
+            Expression::FunctionType(_) => "FunctionType",
+            Expression::Assignment(_) => "Assignment",
+            Expression::Function(_) => "Function",
+        }
+    }
+}
+
+impl Visitor for TypedAstJsonExporter {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        self.enter();
+        walk_directive(self, drtv);
+        let entity = self.host.node_mapping().get(drtv);
+        self.exit(Self::directive_kind(drtv), &drtv.location(), entity);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        self.enter();
+        walk_expression(self, exp);
+        let entity = self.host.node_mapping().get(exp);
+        self.exit(Self::expression_kind(exp), &exp.location(), entity);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod flow_graph;
+pub use flow_graph::*;
+
+mod flow_graph_builder;
+pub(crate) use flow_graph_builder::*;
\ No newline at end of file
@@ -0,0 +1,179 @@
+use crate::ns::*;
+use std::collections::HashSet;
+
+pub type FlowBlockId = usize;
+
+/// Kind of transfer along a `FlowEdge`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FlowEdgeKind {
+    Normal,
+    /// Taken when the test ending the source block holds.
+    True,
+    /// Taken when the test ending the source block fails.
+    False,
+    /// From a `switch` discriminant to one of its cases.
+    Case,
+    /// Back to the test of a loop.
+    Loop,
+    /// From a `try` block to one of its `catch` clauses.
+    Exception,
+    /// By `break`, `continue`, `return` or `throw`.
+    Jump,
+}
+
+impl FlowEdgeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::True => "true",
+            Self::False => "false",
+            Self::Case => "case",
+            Self::Loop => "loop",
+            Self::Exception => "exception",
+            Self::Jump => "jump",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FlowEdge {
+    pub from: FlowBlockId,
+    pub to: FlowBlockId,
+    pub kind: FlowEdgeKind,
+}
+
+/// Statements executed in sequence. A compound statement ending a
+/// block, such as an `if` statement, is listed as its branch.
+#[derive(Clone, Default)]
+pub struct FlowBlock {
+    pub id: FlowBlockId,
+    pub statements: Vec<Rc<Directive>>,
+    /// Statement whose test or discriminant selects the outgoing edge.
+    pub branch: Option<Rc<Directive>>,
+}
+
+/// Control-flow graph of a statement list, such as a function body,
+/// for flow-sensitive checks and instrumentation.
+///
+/// ```ignore
+/// let graph = FlowGraph::build(&block.directives);
+/// if graph.completes_normally() {
+///     // the end of the body is reachable
+/// }
+/// for stmt in graph.unreachable_statements() {
+///     // ...
+/// }
+/// ```
+///
+/// Falling off the end of the list reaches the `end` block, which leads
+/// to `exit`; `return`, `throw`, and `break` or `continue` statements
+/// targeting statements outside the list lead to `exit` directly.
+/// Jumps leaving a `try` statement pass through its `finally` block
+/// first, which is built a second time for them so that it resumes the
+/// jump instead of completing the statement normally.
+/// Nested function bodies are not part of the graph.
+#[derive(Clone, Default)]
+pub struct FlowGraph {
+    pub blocks: Vec<FlowBlock>,
+    pub edges: Vec<FlowEdge>,
+    pub entry: FlowBlockId,
+    pub end: FlowBlockId,
+    pub exit: FlowBlockId,
+}
+
+impl FlowGraph {
+    pub fn build(list: &[Rc<Directive>]) -> Self {
+        FlowGraphBuilder::build(list)
+    }
+
+    pub fn block(&self, id: FlowBlockId) -> &FlowBlock {
+        &self.blocks[id]
+    }
+
+    pub fn successors(&self, id: FlowBlockId) -> impl Iterator<Item = &FlowEdge> {
+        self.edges.iter().filter(move |e| e.from == id)
+    }
+
+    pub fn predecessors(&self, id: FlowBlockId) -> impl Iterator<Item = &FlowEdge> {
+        self.edges.iter().filter(move |e| e.to == id)
+    }
+
+    /// Blocks reachable from `entry`.
+    pub fn reachable(&self) -> HashSet<FlowBlockId> {
+        let mut r = HashSet::new();
+        let mut pending = vec![self.entry];
+        while let Some(id) = pending.pop() {
+            if r.insert(id) {
+                pending.extend(self.successors(id).map(|e| e.to));
+            }
+        }
+        r
+    }
+
+    /// Whether the end of the list is reachable.
+    pub fn completes_normally(&self) -> bool {
+        self.reachable().contains(&self.end)
+    }
+
+    /// Statements of blocks unreachable from `entry`.
+    pub fn unreachable_statements(&self) -> Vec<Rc<Directive>> {
+        let reachable = self.reachable();
+        self.blocks.iter()
+            .filter(|b| !reachable.contains(&b.id))
+            .flat_map(|b| b.statements.iter().chain(b.branch.iter()).cloned())
+            // `finally` blocks appear twice; one copy may be reached.
+            .filter(|s| !self.blocks.iter().filter(|b| reachable.contains(&b.id)).any(|b| b.statements.iter().chain(b.branch.iter()).any(|r| Rc::ptr_eq(r, s))))
+            .collect()
+    }
+
+    /// Formats the graph in the Graphviz DOT language, labeling blocks
+    /// with the line numbers of their statements.
+    pub fn format_dot(&self, name: &str) -> String {
+        let mut r = format!("digraph {} {{\n", json_string(name));
+        for block in self.blocks.iter() {
+            let label = if block.id == self.entry {
+                "entry".to_owned()
+            } else if block.id == self.exit {
+                "exit".to_owned()
+            } else {
+                let lines: Vec<String> = block.statements.iter().chain(block.branch.iter())
+                    .map(|s| s.location().first_line_number().to_string())
+                    .collect();
+                format!("B{} [{}]", block.id, lines.join(", "))
+            };
+            r.push_str(&format!("  b{} [label={}];\n", block.id, json_string(&label)));
+        }
+        for edge in self.edges.iter() {
+            r.push_str(&format!("  b{} -> b{} [label={}];\n", edge.from, edge.to, json_string(edge.kind.name())));
+        }
+        r.push_str("}\n");
+        r
+    }
+
+    /// Formats the graphs of the top-level statements of a program, of
+    /// its package blocks and of every function body in DOT.
+    pub fn format_program_dot(program: &Rc<Program>) -> String {
+        let mut dumper = FlowGraphDumper { graphs: vec![] };
+        dumper.graphs.push(Self::build(&program.directives).format_dot("program"));
+        for pckgdefn in program.packages.iter() {
+            let name = format!("package@{}", pckgdefn.location.first_line_number());
+            dumper.graphs.push(Self::build(&pckgdefn.block.directives).format_dot(&name));
+        }
+        walk_program(&mut dumper, program);
+        dumper.graphs.concat()
+    }
+}
+
+struct FlowGraphDumper {
+    graphs: Vec<String>,
+}
+
+impl Visitor for FlowGraphDumper {
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        if let Some(FunctionBody::Block(block)) = common.body.as_ref() {
+            let name = format!("function@{}", common.location.first_line_number());
+            self.graphs.push(FlowGraph::build(&block.directives).format_dot(&name));
+        }
+        walk_function_common(self, common);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,379 @@
+use crate::ns::*;
+
+/// Statement targeted by `break` and, for loops, `continue`.
+struct JumpTarget {
+    label: Option<String>,
+    break_to: FlowBlockId,
+    continue_to: Option<FlowBlockId>,
+    /// Labeled statements other than loops and `switch` are only
+    /// targeted by a labeled `break`.
+    labeled_only: bool,
+    /// Number of enclosing `try` handlers, whose `finally` blocks a jump
+    /// to this statement does not leave.
+    handler_depth: usize,
+}
+
+/// Where a jump that entered a `finally` block continues after it.
+#[derive(Copy, Clone, PartialEq)]
+enum PendingJump {
+    /// `break`, `continue` or `return` to a block, with the handler depth
+    /// of its target.
+    To(FlowBlockId, usize),
+    /// An exception propagating to the enclosing handlers.
+    Throw,
+}
+
+/// Handlers of an enclosing `try` statement.
+struct TryHandlers {
+    catches: Vec<FlowBlockId>,
+    /// `finally` block entered by jumps and exceptions; normal completion
+    /// enters a copy of it that continues after the `try` statement.
+    finally: Option<FlowBlockId>,
+    pending: Vec<PendingJump>,
+}
+
+pub(crate) struct FlowGraphBuilder {
+    graph: FlowGraph,
+    current: FlowBlockId,
+    targets: Vec<JumpTarget>,
+    handlers: Vec<TryHandlers>,
+    /// Label of the labeled statement whose substatement is built next.
+    pending_label: Option<String>,
+}
+
+impl FlowGraphBuilder {
+    pub fn build(list: &[Rc<Directive>]) -> FlowGraph {
+        let mut builder = Self {
+            graph: FlowGraph::default(),
+            current: 0,
+            targets: vec![],
+            handlers: vec![],
+            pending_label: None,
+        };
+        let entry = builder.new_block();
+        let exit = builder.new_block();
+        builder.graph.entry = entry;
+        builder.graph.exit = exit;
+        let first = builder.new_block();
+        builder.edge(entry, first, FlowEdgeKind::Normal);
+        builder.current = first;
+        builder.statements(list);
+        let end = builder.new_block();
+        builder.edge(builder.current, end, FlowEdgeKind::Normal);
+        builder.edge(end, exit, FlowEdgeKind::Normal);
+        builder.graph.end = end;
+        builder.graph
+    }
+
+    fn new_block(&mut self) -> FlowBlockId {
+        let id = self.graph.blocks.len();
+        self.graph.blocks.push(FlowBlock { id, ..Default::default() });
+        id
+    }
+
+    fn edge(&mut self, from: FlowBlockId, to: FlowBlockId, kind: FlowEdgeKind) {
+        self.graph.edges.push(FlowEdge { from, to, kind });
+    }
+
+    fn push(&mut self, stmt: &Rc<Directive>) {
+        self.graph.blocks[self.current].statements.push(stmt.clone());
+    }
+
+    /// Ends the current block with a branching statement.
+    fn branch(&mut self, stmt: &Rc<Directive>) -> FlowBlockId {
+        self.graph.blocks[self.current].branch = Some(stmt.clone());
+        self.current
+    }
+
+    /// Jumps from the current block, continuing in an unreachable one.
+    fn jump(&mut self, to: FlowBlockId, kind: FlowEdgeKind) {
+        self.edge(self.current, to, kind);
+        self.current = self.new_block();
+    }
+
+    fn statements(&mut self, list: &[Rc<Directive>]) {
+        for stmt in list.iter() {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &Rc<Directive>) {
+        let label = self.pending_label.take();
+        match stmt.as_ref() {
+            Directive::Block(block) => self.statements(&block.directives),
+            Directive::ConfigurationDirective(cfgdrtv) => self.statement(&cfgdrtv.directive),
+            Directive::WithStatement(wstmt) => {
+                self.push(stmt);
+                self.statement(&wstmt.body);
+            },
+            Directive::LabeledStatement(labstmt) => {
+                let name = labstmt.label.0.clone();
+                match labstmt.substatement.as_ref() {
+                    Directive::WhileStatement(_) |
+                    Directive::DoStatement(_) |
+                    Directive::ForStatement(_) |
+                    Directive::ForInStatement(_) |
+                    Directive::SwitchStatement(_) |
+                    Directive::SwitchTypeStatement(_) => {
+                        self.pending_label = Some(name);
+                        self.statement(&labstmt.substatement);
+                    },
+                    _ => {
+                        let after = self.new_block();
+                        self.targets.push(JumpTarget { label: Some(name), break_to: after, continue_to: None, labeled_only: true, handler_depth: self.handlers.len() });
+                        self.statement(&labstmt.substatement);
+                        self.targets.pop();
+                        self.edge(self.current, after, FlowEdgeKind::Normal);
+                        self.current = after;
+                    },
+                }
+            },
+            Directive::IfStatement(ifstmt) => {
+                let test = self.branch(stmt);
+                let join = self.new_block();
+                let consequent = self.new_block();
+                self.edge(test, consequent, FlowEdgeKind::True);
+                self.current = consequent;
+                self.statement(&ifstmt.consequent);
+                self.edge(self.current, join, FlowEdgeKind::Normal);
+                if let Some(alt) = ifstmt.alternative.as_ref() {
+                    let alternative = self.new_block();
+                    self.edge(test, alternative, FlowEdgeKind::False);
+                    self.current = alternative;
+                    self.statement(alt);
+                    self.edge(self.current, join, FlowEdgeKind::Normal);
+                } else {
+                    self.edge(test, join, FlowEdgeKind::False);
+                }
+                self.current = join;
+            },
+            Directive::WhileStatement(wstmt) => {
+                let test = self.new_block();
+                self.edge(self.current, test, FlowEdgeKind::Normal);
+                self.current = test;
+                self.branch(stmt);
+                let after = self.new_block();
+                self.edge(test, after, FlowEdgeKind::False);
+                self.loop_body(&wstmt.body, label, test, test, after);
+            },
+            Directive::DoStatement(dostmt) => {
+                let body = self.new_block();
+                let test = self.new_block();
+                let after = self.new_block();
+                self.graph.blocks[test].branch = Some(stmt.clone());
+                self.edge(self.current, body, FlowEdgeKind::Normal);
+                self.edge(test, body, FlowEdgeKind::Loop);
+                self.edge(test, after, FlowEdgeKind::False);
+                self.targets.push(JumpTarget { label, break_to: after, continue_to: Some(test), labeled_only: false, handler_depth: self.handlers.len() });
+                self.current = body;
+                self.statement(&dostmt.body);
+                self.targets.pop();
+                self.edge(self.current, test, FlowEdgeKind::Normal);
+                self.current = after;
+            },
+            Directive::ForStatement(forstmt) => {
+                let test = self.new_block();
+                self.edge(self.current, test, FlowEdgeKind::Normal);
+                self.current = test;
+                self.branch(stmt);
+                let update = self.new_block();
+                let after = self.new_block();
+                self.edge(update, test, FlowEdgeKind::Loop);
+                // Without a test, the loop is only left by jumps
+                if forstmt.test.is_some() {
+                    self.edge(test, after, FlowEdgeKind::False);
+                }
+                self.loop_body(&forstmt.body, label, test, update, after);
+            },
+            Directive::ForInStatement(forstmt) => {
+                let test = self.new_block();
+                self.edge(self.current, test, FlowEdgeKind::Normal);
+                self.current = test;
+                self.branch(stmt);
+                let after = self.new_block();
+                self.edge(test, after, FlowEdgeKind::False);
+                self.loop_body(&forstmt.body, label, test, test, after);
+            },
+            Directive::SwitchStatement(swstmt) => {
+                let discriminant = self.branch(stmt);
+                let after = self.new_block();
+                self.targets.push(JumpTarget { label, break_to: after, continue_to: None, labeled_only: false, handler_depth: self.handlers.len() });
+                let mut previous_end: Option<FlowBlockId> = None;
+                let mut has_default = false;
+                for case in swstmt.cases.iter() {
+                    has_default = has_default || case.labels.iter().any(|l| matches!(l, CaseLabel::Default(_)));
+                    let block = self.new_block();
+                    self.edge(discriminant, block, FlowEdgeKind::Case);
+                    // Fallthrough from the previous case
+                    if let Some(previous_end) = previous_end {
+                        self.edge(previous_end, block, FlowEdgeKind::Normal);
+                    }
+                    self.current = block;
+                    self.statements(&case.directives);
+                    previous_end = Some(self.current);
+                }
+                self.targets.pop();
+                if let Some(previous_end) = previous_end {
+                    self.edge(previous_end, after, FlowEdgeKind::Normal);
+                }
+                if !has_default {
+                    self.edge(discriminant, after, FlowEdgeKind::False);
+                }
+                self.current = after;
+            },
+            Directive::SwitchTypeStatement(swstmt) => {
+                let discriminant = self.branch(stmt);
+                let after = self.new_block();
+                self.targets.push(JumpTarget { label, break_to: after, continue_to: None, labeled_only: false, handler_depth: self.handlers.len() });
+                for case in swstmt.cases.iter() {
+                    let block = self.new_block();
+                    self.edge(discriminant, block, FlowEdgeKind::Case);
+                    self.current = block;
+                    self.statements(&case.block.directives);
+                    self.edge(self.current, after, FlowEdgeKind::Normal);
+                }
+                self.targets.pop();
+                if !swstmt.cases.iter().any(|case| case.parameter.is_none()) {
+                    self.edge(discriminant, after, FlowEdgeKind::False);
+                }
+                self.current = after;
+            },
+            Directive::TryStatement(trystmt) => {
+                let catches: Vec<FlowBlockId> = trystmt.catch_clauses.iter().map(|_| self.new_block()).collect();
+                // The `finally` block is built twice: once continuing after
+                // the statement, and once continuing wherever the jumps
+                // and exceptions entering it were going.
+                let finally = trystmt.finally_clause.as_ref().map(|_| self.new_block());
+                let abrupt_finally = trystmt.finally_clause.as_ref().map(|_| self.new_block());
+                let after = self.new_block();
+                let continuation = finally.unwrap_or(after);
+                let mut pending = vec![];
+
+                let block = self.new_block();
+                self.edge(self.current, block, FlowEdgeKind::Normal);
+                self.handlers.push(TryHandlers { catches: catches.clone(), finally: abrupt_finally, pending: vec![] });
+                self.current = block;
+                self.statements(&trystmt.block.directives);
+                pending.extend(self.handlers.pop().unwrap().pending);
+                // Any statement of the block may throw
+                for catch in catches.iter() {
+                    self.edge(block, *catch, FlowEdgeKind::Exception);
+                }
+                if let (true, Some(abrupt_finally)) = (catches.is_empty(), abrupt_finally) {
+                    self.edge(block, abrupt_finally, FlowEdgeKind::Exception);
+                    pending.push(PendingJump::Throw);
+                }
+                self.edge(self.current, continuation, FlowEdgeKind::Normal);
+
+                self.handlers.push(TryHandlers { catches: vec![], finally: abrupt_finally, pending: vec![] });
+                for (clause, catch) in trystmt.catch_clauses.iter().zip(catches.iter()) {
+                    self.current = *catch;
+                    self.statements(&clause.block.directives);
+                    self.edge(self.current, continuation, FlowEdgeKind::Normal);
+                    if let Some(abrupt_finally) = abrupt_finally {
+                        self.edge(*catch, abrupt_finally, FlowEdgeKind::Exception);
+                        pending.push(PendingJump::Throw);
+                    }
+                }
+                pending.extend(self.handlers.pop().unwrap().pending);
+
+                if let (Some(finally), Some(abrupt_finally), Some(finally_clause)) = (finally, abrupt_finally, trystmt.finally_clause.as_ref()) {
+                    self.current = finally;
+                    self.statements(&finally_clause.block.directives);
+                    self.edge(self.current, after, FlowEdgeKind::Normal);
+
+                    self.current = abrupt_finally;
+                    self.statements(&finally_clause.block.directives);
+                    let end = self.current;
+                    let mut resumed = vec![];
+                    for jump in pending {
+                        if resumed.contains(&jump) {
+                            continue;
+                        }
+                        resumed.push(jump);
+                        self.current = end;
+                        match jump {
+                            PendingJump::To(to, handler_depth) => self.jump_across_handlers(to, handler_depth),
+                            PendingJump::Throw => self.throw(),
+                        }
+                    }
+                }
+                self.current = after;
+            },
+            Directive::BreakStatement(brkstmt) => {
+                self.push(stmt);
+                let label = brkstmt.label.as_ref().map(|l| l.0.clone());
+                let (to, handler_depth) = self.targets.iter().rev()
+                    .find(|t| match label.as_ref() {
+                        Some(label) => t.label.as_ref() == Some(label),
+                        None => !t.labeled_only,
+                    })
+                    .map(|t| (t.break_to, t.handler_depth))
+                    .unwrap_or((self.graph.exit, 0));
+                self.jump_across_handlers(to, handler_depth);
+            },
+            Directive::ContinueStatement(contstmt) => {
+                self.push(stmt);
+                let label = contstmt.label.as_ref().map(|l| l.0.clone());
+                let (to, handler_depth) = self.targets.iter().rev()
+                    .filter(|t| t.continue_to.is_some())
+                    .find(|t| label.is_none() || t.label == label)
+                    .map(|t| (t.continue_to.unwrap(), t.handler_depth))
+                    .unwrap_or((self.graph.exit, 0));
+                self.jump_across_handlers(to, handler_depth);
+            },
+            Directive::ReturnStatement(_) => {
+                self.push(stmt);
+                self.jump_across_handlers(self.graph.exit, 0);
+            },
+            Directive::ThrowStatement(_) => {
+                self.push(stmt);
+                self.throw();
+            },
+            _ => self.push(stmt),
+        }
+    }
+
+    /// Jumps to a block, first through the `finally` block of every
+    /// handler deeper than `handler_depth`.
+    fn jump_across_handlers(&mut self, to: FlowBlockId, handler_depth: usize) {
+        let crossed = (handler_depth..self.handlers.len()).rev().find(|&i| self.handlers[i].finally.is_some());
+        match crossed {
+            Some(i) => {
+                self.handlers[i].pending.push(PendingJump::To(to, handler_depth));
+                let finally = self.handlers[i].finally.unwrap();
+                self.jump(finally, FlowEdgeKind::Jump);
+            },
+            None => self.jump(to, FlowEdgeKind::Jump),
+        }
+    }
+
+    /// Throws to the innermost `catch` clauses or `finally` block.
+    fn throw(&mut self) {
+        let handler = self.handlers.iter().rposition(|h| !h.catches.is_empty() || h.finally.is_some());
+        let targets = match handler {
+            Some(i) if !self.handlers[i].catches.is_empty() => self.handlers[i].catches.clone(),
+            Some(i) => {
+                self.handlers[i].pending.push(PendingJump::Throw);
+                self.handlers[i].finally.into_iter().collect()
+            },
+            None => vec![self.graph.exit],
+        };
+        for to in targets {
+            self.edge(self.current, to, FlowEdgeKind::Jump);
+        }
+        self.current = self.new_block();
+    }
+
+    fn loop_body(&mut self, body: &Rc<Directive>, label: Option<String>, test: FlowBlockId, continue_to: FlowBlockId, after: FlowBlockId) {
+        let block = self.new_block();
+        self.edge(test, block, FlowEdgeKind::True);
+        self.targets.push(JumpTarget { label, break_to: after, continue_to: Some(continue_to), labeled_only: false, handler_depth: self.handlers.len() });
+        self.current = block;
+        self.statement(body);
+        self.targets.pop();
+        let kind = if continue_to == test { FlowEdgeKind::Loop } else { FlowEdgeKind::Normal };
+        self.edge(self.current, continue_to, kind);
+        self.current = after;
+    }
+}
\ No newline at end of file
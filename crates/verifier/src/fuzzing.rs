@@ -0,0 +1,26 @@
+//! No-panic entry points for cargo-fuzz targets.
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     whackengine_verifier::fuzzing::fuzz_verify(data);
+//! });
+//! ```
+
+use crate::ns::*;
+
+/// Parses arbitrary bytes as an ActionScript program.
+pub fn fuzz_parse(bytes: &[u8]) {
+    let cu = CompilationUnit::new(None, String::from_utf8_lossy(bytes).into_owned());
+    let _ = ParserFacade(&cu, ParserOptions::default()).parse_program();
+}
+
+/// Parses and verifies arbitrary bytes as an ActionScript program,
+/// without the core library. Programs with syntax errors are not
+/// verified.
+pub fn fuzz_verify(bytes: &[u8]) {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Fuzz.as", &String::from_utf8_lossy(bytes));
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { core_library: CoreLibrarySource::Omitted, ..default() });
+    let _ = Compilation::run(&host, &compiler_options, &sources);
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+mod fxg_document;
+pub use fxg_document::*;
+
+mod fxg_path_data;
+pub use fxg_path_data::*;
+
+mod fxg_lowering;
+pub use fxg_lowering::*;
\ No newline at end of file
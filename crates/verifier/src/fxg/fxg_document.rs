@@ -0,0 +1,197 @@
+use crate::ns::*;
+
+/// An FXG graphic document.
+#[derive(Clone, Debug)]
+pub struct FxgDocument {
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub children: Vec<FxgNode>,
+}
+
+#[derive(Clone, Debug)]
+pub enum FxgNode {
+    Group {
+        transform: FxgTransform,
+        children: Vec<FxgNode>,
+    },
+    Rect {
+        transform: FxgTransform,
+        width: f64,
+        height: f64,
+        radius_x: f64,
+        radius_y: f64,
+        paint: FxgPaint,
+    },
+    Ellipse {
+        transform: FxgTransform,
+        width: f64,
+        height: f64,
+        paint: FxgPaint,
+    },
+    Line {
+        transform: FxgTransform,
+        x_from: f64,
+        y_from: f64,
+        x_to: f64,
+        y_to: f64,
+        paint: FxgPaint,
+    },
+    Path {
+        transform: FxgTransform,
+        data: Vec<FxgPathCommand>,
+        paint: FxgPaint,
+    },
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct FxgTransform {
+    pub x: f64,
+    pub y: f64,
+    pub alpha: Option<f64>,
+}
+
+/// Solid fill and stroke of a shape.
+#[derive(Clone, Default, Debug)]
+pub struct FxgPaint {
+    /// Fill color and alpha.
+    pub fill: Option<(u32, f64)>,
+    /// Stroke color, alpha and weight.
+    pub stroke: Option<(u32, f64, f64)>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum FxgError {
+    Malformed,
+    /// The root element is not `Graphic`.
+    InvalidRoot,
+    InvalidPathData(String),
+    /// An element that cannot be lowered, such as a gradient or filter.
+    UnsupportedElement(String),
+}
+
+impl FxgDocument {
+    pub fn parse(file_path: &str, text: &str) -> Result<FxgDocument, FxgError> {
+        let cu = CompilationUnit::new(Some(file_path.to_owned()), text.to_owned());
+        let mxml = ParserFacade(&cu, ParserOptions::default()).parse_mxml();
+        if cu.invalidated() {
+            return Err(FxgError::Malformed);
+        }
+        let Some(root) = mxml.content.iter().find_map(|c| if let MxmlContent::Element(e) = c.as_ref() { Some(e.clone()) } else { None }) else {
+            return Err(FxgError::InvalidRoot);
+        };
+        if root.name.name != "Graphic" {
+            return Err(FxgError::InvalidRoot);
+        }
+        let mut children = vec![];
+        for child in Self::children(&root).iter() {
+            // Library and private data are not part of the display list.
+            if ["Library", "Private", "mask"].contains(&child.name.name.as_str()) {
+                continue;
+            }
+            children.push(Self::parse_node(child)?);
+        }
+        Ok(FxgDocument {
+            width: Self::number(&root, "viewWidth"),
+            height: Self::number(&root, "viewHeight"),
+            children,
+        })
+    }
+
+    fn parse_node(element: &Rc<MxmlElement>) -> Result<FxgNode, FxgError> {
+        let transform = FxgTransform {
+            x: Self::number(element, "x").unwrap_or(0.0),
+            y: Self::number(element, "y").unwrap_or(0.0),
+            alpha: Self::number(element, "alpha"),
+        };
+        match element.name.name.as_str() {
+            "Group" => {
+                let mut children = vec![];
+                for child in Self::children(element).iter() {
+                    children.push(Self::parse_node(child)?);
+                }
+                Ok(FxgNode::Group { transform, children })
+            },
+            "Rect" => {
+                let radius_x = Self::number(element, "radiusX").unwrap_or(0.0);
+                Ok(FxgNode::Rect {
+                    transform,
+                    width: Self::number(element, "width").unwrap_or(0.0),
+                    height: Self::number(element, "height").unwrap_or(0.0),
+                    radius_x,
+                    radius_y: Self::number(element, "radiusY").unwrap_or(radius_x),
+                    paint: Self::parse_paint(element)?,
+                })
+            },
+            "Ellipse" => Ok(FxgNode::Ellipse {
+                transform,
+                width: Self::number(element, "width").unwrap_or(0.0),
+                height: Self::number(element, "height").unwrap_or(0.0),
+                paint: Self::parse_paint(element)?,
+            }),
+            "Line" => Ok(FxgNode::Line {
+                transform,
+                x_from: Self::number(element, "xFrom").unwrap_or(0.0),
+                y_from: Self::number(element, "yFrom").unwrap_or(0.0),
+                x_to: Self::number(element, "xTo").unwrap_or(0.0),
+                y_to: Self::number(element, "yTo").unwrap_or(0.0),
+                paint: Self::parse_paint(element)?,
+            }),
+            "Path" => {
+                let data = Self::attribute(element, "data").unwrap_or_default();
+                Ok(FxgNode::Path {
+                    transform,
+                    data: FxgPathCommand::parse(&data)?,
+                    paint: Self::parse_paint(element)?,
+                })
+            },
+            name => Err(FxgError::UnsupportedElement(name.to_owned())),
+        }
+    }
+
+    fn parse_paint(element: &Rc<MxmlElement>) -> Result<FxgPaint, FxgError> {
+        let mut paint = FxgPaint::default();
+        for child in Self::children(element).iter() {
+            let Some(solid) = Self::children(child).into_iter().next() else {
+                continue;
+            };
+            let color = Self::attribute(&solid, "color").and_then(|c| Self::parse_color(&c)).unwrap_or(0);
+            let alpha = Self::number(&solid, "alpha").unwrap_or(1.0);
+            match (child.name.name.as_str(), solid.name.name.as_str()) {
+                ("fill", "SolidColor") => {
+                    paint.fill = Some((color, alpha));
+                },
+                ("stroke", "SolidColorStroke") => {
+                    let weight = Self::number(&solid, "weight").unwrap_or(1.0);
+                    paint.stroke = Some((color, alpha, weight));
+                },
+                ("fill", name) | ("stroke", name) => {
+                    return Err(FxgError::UnsupportedElement(name.to_owned()));
+                },
+                _ => {},
+            }
+        }
+        Ok(paint)
+    }
+
+    fn parse_color(color: &str) -> Option<u32> {
+        u32::from_str_radix(color.trim().trim_start_matches('#').trim_start_matches("0x"), 16).ok()
+    }
+
+    fn attribute(element: &Rc<MxmlElement>, name: &str) -> Option<String> {
+        element.attributes.iter().find(|a| !a.xmlns && a.name.name == name).map(|a| a.value.0.clone())
+    }
+
+    fn number(element: &Rc<MxmlElement>, name: &str) -> Option<f64> {
+        Self::attribute(element, name).and_then(|v| v.trim().parse::<f64>().ok())
+    }
+
+    fn children(element: &Rc<MxmlElement>) -> Vec<Rc<MxmlElement>> {
+        let Some(content) = element.content.as_ref() else {
+            return vec![];
+        };
+        content.iter().filter_map(|c| match c.as_ref() {
+            MxmlContent::Element(e) => Some(e.clone()),
+            _ => None,
+        }).collect()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use crate::ns::*;
+
+/// Lowers an FXG document into an ActionScript class that draws it
+/// through the display list, extending `flash.display.Sprite`.
+pub struct FxgLowering;
+
+impl FxgLowering {
+    pub fn lower(document: &FxgDocument, package_name: &str, class_name: &str) -> String {
+        let mut body = String::new();
+        let mut counter = 0usize;
+        for node in document.children.iter() {
+            Self::lower_node(&mut body, node, "this", &mut counter, 2);
+        }
+        let mut r = String::new();
+        r.push_str(&format!("package {package_name} {{\n"));
+        r.push_str("    import flash.display.*;\n\n");
+        r.push_str(&format!("    public class {class_name} extends Sprite {{\n"));
+        r.push_str(&format!("        public function {class_name}() {{\n"));
+        r.push_str(&body);
+        r.push_str("        }\n");
+        r.push_str("    }\n");
+        r.push_str("}\n");
+        r
+    }
+
+    fn lower_node(r: &mut String, node: &FxgNode, parent: &str, counter: &mut usize, indent: usize) {
+        let pad = "    ".repeat(indent + 1);
+        let name = format!("s{counter}");
+        *counter += 1;
+        let (transform, is_group) = match node {
+            FxgNode::Group { transform, .. } => (transform, true),
+            FxgNode::Rect { transform, .. } |
+            FxgNode::Ellipse { transform, .. } |
+            FxgNode::Line { transform, .. } |
+            FxgNode::Path { transform, .. } => (transform, false),
+        };
+        r.push_str(&format!("{pad}var {name}:{} = new {}();\n", if is_group { "Sprite" } else { "Shape" }, if is_group { "Sprite" } else { "Shape" }));
+        if transform.x != 0.0 {
+            r.push_str(&format!("{pad}{name}.x = {};\n", transform.x));
+        }
+        if transform.y != 0.0 {
+            r.push_str(&format!("{pad}{name}.y = {};\n", transform.y));
+        }
+        if let Some(alpha) = transform.alpha {
+            r.push_str(&format!("{pad}{name}.alpha = {alpha};\n"));
+        }
+        let g = format!("{name}.graphics");
+        match node {
+            FxgNode::Group { children, .. } => {
+                for child in children.iter() {
+                    Self::lower_node(r, child, &name, counter, indent);
+                }
+            },
+            FxgNode::Rect { width, height, radius_x, radius_y, paint, .. } => {
+                Self::begin_paint(r, &pad, &g, paint);
+                if *radius_x == 0.0 && *radius_y == 0.0 {
+                    r.push_str(&format!("{pad}{g}.drawRect(0, 0, {width}, {height});\n"));
+                } else {
+                    r.push_str(&format!("{pad}{g}.drawRoundRect(0, 0, {width}, {height}, {}, {});\n", radius_x * 2.0, radius_y * 2.0));
+                }
+                Self::end_paint(r, &pad, &g, paint);
+            },
+            FxgNode::Ellipse { width, height, paint, .. } => {
+                Self::begin_paint(r, &pad, &g, paint);
+                r.push_str(&format!("{pad}{g}.drawEllipse(0, 0, {width}, {height});\n"));
+                Self::end_paint(r, &pad, &g, paint);
+            },
+            FxgNode::Line { x_from, y_from, x_to, y_to, paint, .. } => {
+                Self::begin_paint(r, &pad, &g, paint);
+                r.push_str(&format!("{pad}{g}.moveTo({x_from}, {y_from});\n"));
+                r.push_str(&format!("{pad}{g}.lineTo({x_to}, {y_to});\n"));
+            },
+            FxgNode::Path { data, paint, .. } => {
+                Self::begin_paint(r, &pad, &g, paint);
+                let mut start = (0.0, 0.0);
+                for command in data.iter() {
+                    match command {
+                        FxgPathCommand::MoveTo(x, y) => {
+                            start = (*x, *y);
+                            r.push_str(&format!("{pad}{g}.moveTo({x}, {y});\n"));
+                        },
+                        FxgPathCommand::LineTo(x, y) => r.push_str(&format!("{pad}{g}.lineTo({x}, {y});\n")),
+                        FxgPathCommand::CurveTo(cx, cy, x, y) => r.push_str(&format!("{pad}{g}.curveTo({cx}, {cy}, {x}, {y});\n")),
+                        FxgPathCommand::CubicCurveTo(c1x, c1y, c2x, c2y, x, y) => r.push_str(&format!("{pad}{g}.cubicCurveTo({c1x}, {c1y}, {c2x}, {c2y}, {x}, {y});\n")),
+                        FxgPathCommand::Close => r.push_str(&format!("{pad}{g}.lineTo({}, {});\n", start.0, start.1)),
+                    }
+                }
+                Self::end_paint(r, &pad, &g, paint);
+            },
+        }
+        r.push_str(&format!("{pad}{parent}.addChild({name});\n"));
+    }
+
+    fn begin_paint(r: &mut String, pad: &str, g: &str, paint: &FxgPaint) {
+        if let Some((color, alpha, weight)) = paint.stroke {
+            r.push_str(&format!("{pad}{g}.lineStyle({weight}, 0x{color:06X}, {alpha});\n"));
+        }
+        if let Some((color, alpha)) = paint.fill {
+            r.push_str(&format!("{pad}{g}.beginFill(0x{color:06X}, {alpha});\n"));
+        }
+    }
+
+    fn end_paint(r: &mut String, pad: &str, g: &str, paint: &FxgPaint) {
+        if paint.fill.is_some() {
+            r.push_str(&format!("{pad}{g}.endFill();\n"));
+        }
+    }
+}
\ No newline at end of file
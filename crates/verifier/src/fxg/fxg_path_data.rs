@@ -0,0 +1,114 @@
+use crate::ns::*;
+
+/// A command of the FXG `Path.data` attribute, in absolute coordinates.
+#[derive(Clone, PartialEq, Debug)]
+pub enum FxgPathCommand {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    /// Quadratic Bézier curve: control point, then anchor point.
+    CurveTo(f64, f64, f64, f64),
+    /// Cubic Bézier curve: two control points, then anchor point.
+    CubicCurveTo(f64, f64, f64, f64, f64, f64),
+    Close,
+}
+
+impl FxgPathCommand {
+    /// Parses path data, converting relative commands
+    /// (lowercase letters) and `H`/`V` into absolute ones.
+    pub fn parse(data: &str) -> Result<Vec<FxgPathCommand>, FxgError> {
+        let tokens = Self::tokenize(data);
+        let mut r = vec![];
+        let mut i = 0;
+        let (mut x, mut y) = (0.0, 0.0);
+        let (mut start_x, mut start_y) = (0.0, 0.0);
+        let mut command = 'M';
+        let number = |i: &mut usize| -> Result<f64, FxgError> {
+            let v = tokens.get(*i).and_then(|t| t.parse::<f64>().ok()).ok_or_else(|| FxgError::InvalidPathData(data.to_owned()))?;
+            *i += 1;
+            Ok(v)
+        };
+        while i < tokens.len() {
+            if let Some(ch) = tokens[i].chars().next().filter(|ch| ch.is_ascii_alphabetic()) {
+                command = ch;
+                i += 1;
+            }
+            let relative = command.is_ascii_lowercase();
+            let (ox, oy) = if relative { (x, y) } else { (0.0, 0.0) };
+            match command.to_ascii_uppercase() {
+                'M' => {
+                    x = ox + number(&mut i)?;
+                    y = oy + number(&mut i)?;
+                    start_x = x;
+                    start_y = y;
+                    r.push(FxgPathCommand::MoveTo(x, y));
+                    // Subsequent pairs are implicit line-to commands.
+                    command = if relative { 'l' } else { 'L' };
+                },
+                'L' => {
+                    x = ox + number(&mut i)?;
+                    y = oy + number(&mut i)?;
+                    r.push(FxgPathCommand::LineTo(x, y));
+                },
+                'H' => {
+                    x = ox + number(&mut i)?;
+                    r.push(FxgPathCommand::LineTo(x, y));
+                },
+                'V' => {
+                    y = oy + number(&mut i)?;
+                    r.push(FxgPathCommand::LineTo(x, y));
+                },
+                'Q' => {
+                    let cx = ox + number(&mut i)?;
+                    let cy = oy + number(&mut i)?;
+                    x = ox + number(&mut i)?;
+                    y = oy + number(&mut i)?;
+                    r.push(FxgPathCommand::CurveTo(cx, cy, x, y));
+                },
+                'C' => {
+                    let c1x = ox + number(&mut i)?;
+                    let c1y = oy + number(&mut i)?;
+                    let c2x = ox + number(&mut i)?;
+                    let c2y = oy + number(&mut i)?;
+                    x = ox + number(&mut i)?;
+                    y = oy + number(&mut i)?;
+                    r.push(FxgPathCommand::CubicCurveTo(c1x, c1y, c2x, c2y, x, y));
+                },
+                'Z' => {
+                    x = start_x;
+                    y = start_y;
+                    r.push(FxgPathCommand::Close);
+                    // Takes no coordinates, so a command must follow.
+                    command = '\0';
+                },
+                _ => return Err(FxgError::InvalidPathData(data.to_owned())),
+            }
+        }
+        Ok(r)
+    }
+
+    fn tokenize(data: &str) -> Vec<String> {
+        let mut r = vec![];
+        let mut current = String::new();
+        for ch in data.chars() {
+            if ch.is_ascii_alphabetic() && ch != 'e' && ch != 'E' {
+                if !current.is_empty() {
+                    r.push(std::mem::take(&mut current));
+                }
+                r.push(ch.to_string());
+            } else if ch.is_whitespace() || ch == ',' {
+                if !current.is_empty() {
+                    r.push(std::mem::take(&mut current));
+                }
+            } else if ch == '-' && !current.is_empty() && !current.ends_with(['e', 'E']) {
+                r.push(std::mem::take(&mut current));
+                current.push(ch);
+            } else {
+                current.push(ch);
+            }
+        }
+        if !current.is_empty() {
+            r.push(current);
+        }
+        r
+    }
+}
\ No newline at end of file
@@ -1,15 +1,64 @@
 #![feature(decl_macro)]
 
+pub mod abc;
+pub mod astbuilder;
+pub mod bindings;
 pub mod codegen;
+pub mod compilation;
 pub mod compileroptions;
+pub mod corelib;
 pub mod diagnostics;
+pub mod display;
+pub mod editor;
+pub mod embed;
+pub mod export;
+pub mod flowgraph;
+pub mod fxg;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod lint;
+pub mod membergeneration;
+pub mod migration;
+pub mod mutation;
+pub mod mxml;
+pub mod resources;
+pub mod rewrite;
+pub mod symbolindex;
+pub mod testdiscovery;
+pub mod testing;
+pub mod timings;
 pub mod verifier;
+pub mod visit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Unified compiler module.
 pub mod ns {
     pub use mxmlextrema_mxmlcaot::ns::*;
+    pub use super::abc::*;
+    pub use super::astbuilder::*;
+    pub use super::bindings::*;
     pub use super::codegen::*;
+    pub use super::compilation::*;
     pub use super::compileroptions::*;
+    pub use super::corelib::*;
     pub use super::diagnostics::*;
+    pub use super::display::*;
+    pub use super::editor::*;
+    pub use super::embed::*;
+    pub use super::export::*;
+    pub use super::flowgraph::*;
+    pub use super::fxg::*;
+    pub use super::lint::*;
+    pub use super::membergeneration::*;
+    pub use super::migration::*;
+    pub use super::mutation::*;
+    pub use super::mxml::*;
+    pub use super::resources::*;
+    pub use super::rewrite::*;
+    pub use super::symbolindex::*;
+    pub use super::testdiscovery::*;
+    pub use super::timings::*;
     pub use super::verifier::*;
+    pub use super::visit::*;
 }
\ No newline at end of file
@@ -0,0 +1,23 @@
+mod lint_rule;
+pub use lint_rule::*;
+
+mod dynamic_receiver_rule;
+pub use dynamic_receiver_rule::*;
+
+mod assignment_in_condition_rule;
+pub use assignment_in_condition_rule::*;
+
+mod byte_array_rule;
+pub use byte_array_rule::*;
+
+mod missing_implements_rule;
+pub use missing_implements_rule::*;
+
+mod security_rules;
+pub use security_rules::*;
+
+mod performance_rules;
+pub use performance_rules::*;
+
+mod lint_runner;
+pub(crate) use lint_runner::*;
\ No newline at end of file
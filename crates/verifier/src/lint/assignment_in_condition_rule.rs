@@ -0,0 +1,61 @@
+use crate::ns::*;
+
+/// Flags `if`, `while`, `do` and `for` tests whose top-level expression
+/// is a plain assignment, suggesting `==` in place of `=`.
+///
+/// Wrapping the assignment in an extra pair of parentheses, as in
+/// `while ((line = reader.readLine()))`, marks it as intended.
+///
+/// ```ignore
+/// verifier.register_lint_rule(Rc::new(AssignmentInConditionRule));
+/// ```
+pub struct AssignmentInConditionRule;
+
+impl AssignmentInConditionRule {
+    fn check_test(context: &mut LintContext, test: &Rc<Expression>) {
+        let Expression::Assignment(assign) = test.as_ref() else {
+            return;
+        };
+        if assign.compound.is_some() {
+            return;
+        }
+        // The assigned value must coerce to Boolean
+        let host = context.host();
+        let Some(t) = host.node_mapping().get(test).map(|v| v.static_type(&host)) else {
+            return;
+        };
+        if t == host.void_type() || t.is::<InvalidationEntity>() {
+            return;
+        }
+        context.report(&assign.location, "Assignment used as a condition; did you mean ==?");
+
+        // Locate the = operator between both operands
+        let text = assign.location.compilation_unit().text();
+        let start = assign.left.location().last_offset();
+        let end = assign.right.location().first_offset();
+        let Some(i) = text.get(start..end).and_then(|between| between.find('=')) else {
+            return;
+        };
+        context.suggest_fix(&assign.location, "Replace = with ==", vec![SourceEdit::new(start + i, start + i + 1, "==")]);
+    }
+}
+
+impl LintRule for AssignmentInConditionRule {
+    fn name(&self) -> String {
+        "assignment-in-condition".into()
+    }
+
+    fn check_statement(&self, context: &mut LintContext, stmt: &Rc<Directive>) {
+        match stmt.as_ref() {
+            Directive::IfStatement(ifstmt) => Self::check_test(context, &ifstmt.test),
+            Directive::WhileStatement(wstmt) => Self::check_test(context, &wstmt.test),
+            Directive::DoStatement(dostmt) => Self::check_test(context, &dostmt.test),
+            Directive::ForStatement(forstmt) => {
+                if let Some(test) = forstmt.test.as_ref() {
+                    Self::check_test(context, test);
+                }
+            },
+            _ => {},
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use crate::ns::*;
+
+/// Flags misuse of `flash.utils.ByteArray` locals within a function:
+/// reads at constant positions past a constant `length`, reads under
+/// an `endian` differing from the one the data was written with, and
+/// `readObject()` calls, which deserialize arbitrary AMF data.
+///
+/// Only unqualified receivers whose static type is `ByteArray` are
+/// tracked, in source order; branches and loops are not considered.
+///
+/// ```ignore
+/// verifier.register_lint_rule(Rc::new(ByteArrayRule::default()));
+/// ```
+pub struct ByteArrayRule {
+    /// Reports `readObject()` calls.
+    pub report_read_object: bool,
+}
+
+impl Default for ByteArrayRule {
+    fn default() -> Self {
+        Self {
+            report_read_object: true,
+        }
+    }
+}
+
+impl LintRule for ByteArrayRule {
+    fn name(&self) -> String {
+        "byte-array".into()
+    }
+
+    fn check_definition(&self, context: &mut LintContext, defn: &Rc<Directive>) {
+        let Directive::FunctionDefinition(defn) = defn.as_ref() else {
+            return;
+        };
+        let host = context.host();
+        let pckg = host.factory().create_package(vec!["flash", "utils"]);
+        let Some(byte_array_type) = pckg.public_ns().and_then(|ns| pckg.properties(&host).get(&host.factory().create_qname(&ns, "ByteArray".to_owned()))) else {
+            return;
+        };
+        let Some(FunctionBody::Block(block)) = defn.common.body.as_ref() else {
+            return;
+        };
+        let mut tracker = ByteArrayTracker {
+            host,
+            byte_array_type,
+            report_read_object: self.report_read_object,
+            locals: HashMap::new(),
+            found: vec![],
+        };
+        tracker.visit_block(block);
+        for (location, message) in tracker.found {
+            context.report(&location, &message);
+        }
+    }
+}
+
+#[derive(Default)]
+struct ByteArrayState {
+    length: Option<f64>,
+    position: Option<f64>,
+    /// Current `endian`, defaulting to big endian.
+    endian: Option<String>,
+    /// `endian` in effect at the last write.
+    written_endian: Option<String>,
+}
+
+struct ByteArrayTracker {
+    host: Rc<Database>,
+    byte_array_type: Entity,
+    report_read_object: bool,
+    locals: HashMap<String, ByteArrayState>,
+    found: Vec<(Location, String)>,
+}
+
+impl ByteArrayTracker {
+    /// Returns the name of an unqualified `ByteArray` local.
+    fn local_name(&self, exp: &Rc<Expression>) -> Option<String> {
+        let Expression::QualifiedIdentifier(id) = exp.as_ref() else {
+            return None;
+        };
+        let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id else {
+            return None;
+        };
+        if id.qualifier.is_some() {
+            return None;
+        }
+        let t = self.host.node_mapping().get(exp)?.static_type(&self.host).escape_of_nullable_or_non_nullable();
+        (t == self.byte_array_type).then(|| name.clone())
+    }
+
+    /// Returns the base local and property name of `ba.name`.
+    fn local_member(&self, exp: &Rc<Expression>) -> Option<(String, String)> {
+        let Expression::Member(member) = exp.as_ref() else {
+            return None;
+        };
+        let local = self.local_name(&member.base)?;
+        let (name, _) = member.identifier.to_identifier_name_or_asterisk()?;
+        Some((local, name))
+    }
+
+    fn number_constant(&self, exp: &Rc<Expression>) -> Option<f64> {
+        self.host.node_mapping().get(exp).filter(|v| v.is::<NumberConstant>()).map(|v| v.number_value().force_double())
+    }
+
+    fn endian_constant(&self, exp: &Rc<Expression>) -> Option<String> {
+        if let Some(v) = self.host.node_mapping().get(exp).filter(|v| v.is::<StringConstant>()) {
+            return Some(v.string_value());
+        }
+        let Expression::Member(member) = exp.as_ref() else {
+            return None;
+        };
+        match member.identifier.to_identifier_name_or_asterisk()?.0.as_str() {
+            "BIG_ENDIAN" => Some("bigEndian".into()),
+            "LITTLE_ENDIAN" => Some("littleEndian".into()),
+            _ => None,
+        }
+    }
+
+    fn assign(&mut self, left: &Rc<Expression>, right: &Rc<Expression>) {
+        if let Some(local) = self.local_name(left) {
+            self.locals.remove(&local);
+            return;
+        }
+        let Some((local, name)) = self.local_member(left) else {
+            return;
+        };
+        let value = self.number_constant(right);
+        let endian = self.endian_constant(right);
+        let state = self.locals.entry(local).or_default();
+        match name.as_str() {
+            "length" => state.length = value,
+            "position" => state.position = value,
+            "endian" => state.endian = endian,
+            _ => {},
+        }
+    }
+
+    fn call(&mut self, base: &Rc<Expression>, location: &Location) {
+        let Some((local, name)) = self.local_member(base) else {
+            return;
+        };
+        let state = self.locals.entry(local.clone()).or_default();
+        let endian = state.endian.clone().unwrap_or("bigEndian".into());
+        if name.starts_with("write") {
+            state.written_endian = Some(endian);
+            state.position = None;
+            state.length = None;
+            return;
+        }
+        if !name.starts_with("read") {
+            return;
+        }
+        if let (Some(position), Some(length)) = (state.position, state.length) {
+            if position >= length {
+                self.found.push((location.clone(), format!("{local}.{name}() reads at position {position}, past the length {length}")));
+            }
+        }
+        if let Some(written) = state.written_endian.as_ref().filter(|w| **w != endian) {
+            self.found.push((location.clone(), format!("{local}.{name}() reads as {endian}, but {local} was written as {written}")));
+        }
+        state.position = None;
+        if name == "readObject" && self.report_read_object {
+            self.found.push((location.clone(), format!("{local}.readObject() deserializes arbitrary objects; do not call it on untrusted data")));
+        }
+    }
+
+    fn index(&mut self, base: &Rc<Expression>, key: &Rc<Expression>, location: &Location) {
+        let Some(local) = self.local_name(base) else {
+            return;
+        };
+        let index = self.number_constant(key);
+        let Some(state) = self.locals.get(&local) else {
+            return;
+        };
+        if let (Some(index), Some(length)) = (index, state.length) {
+            if index >= length {
+                self.found.push((location.clone(), format!("{local}[{index}] is past the length {length}")));
+            }
+        }
+    }
+}
+
+impl Visitor for ByteArrayTracker {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if !matches!(drtv.as_ref(), Directive::FunctionDefinition(_)) {
+            walk_directive(self, drtv);
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::Assignment(assign) => {
+                self.visit_expression(&assign.right);
+                if assign.compound.is_none() {
+                    self.assign(&assign.left, &assign.right);
+                }
+            },
+            Expression::Call(call) => {
+                walk_expression(self, exp);
+                self.call(&call.base, &call.location);
+            },
+            Expression::ComputedMember(member) => {
+                walk_expression(self, exp);
+                self.index(&member.base, &member.key, &member.location);
+            },
+            _ => {
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use crate::ns::*;
+
+/// Flags accesses that the verifier cannot check because their receiver is
+/// dynamic: member chains over `*` values deeper than `max_depth`, and
+/// undeclared properties of dynamic classes.
+///
+/// ```ignore
+/// verifier.register_lint_rule(Rc::new(DynamicReceiverRule::default()));
+/// ```
+pub struct DynamicReceiverRule {
+    /// Number of consecutive member accesses on `*` values allowed
+    /// before a chain is reported.
+    pub max_depth: usize,
+    /// Suggests declaring the accessed properties in an interface.
+    pub suggest_interface: bool,
+}
+
+impl Default for DynamicReceiverRule {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+            suggest_interface: true,
+        }
+    }
+}
+
+impl DynamicReceiverRule {
+    /// Number of consecutive member accesses on `*` values ending at `exp`.
+    fn any_chain_depth(host: &Database, exp: &Rc<Expression>) -> usize {
+        let base = match exp.as_ref() {
+            Expression::Member(e) => &e.base,
+            Expression::ComputedMember(e) => &e.base,
+            Expression::Call(e) => return Self::any_chain_depth(host, &e.base),
+            Expression::Paren(e) => return Self::any_chain_depth(host, &e.expression),
+            _ => return 0,
+        };
+        if Self::static_type(host, base).map(|t| t == host.any_type()).unwrap_or(false) {
+            1 + Self::any_chain_depth(host, base)
+        } else {
+            0
+        }
+    }
+
+    fn static_type(host: &Database, exp: &Rc<Expression>) -> Option<Entity> {
+        host.node_mapping().get(exp).map(|v| v.static_type(host))
+    }
+
+    fn with_suggestion(&self, message: String, receiver: &str) -> String {
+        if self.suggest_interface {
+            format!("{message}; consider declaring the accessed properties in an interface and typing {receiver} with it")
+        } else {
+            message
+        }
+    }
+}
+
+impl LintRule for DynamicReceiverRule {
+    fn name(&self) -> String {
+        "dynamic-receiver".into()
+    }
+
+    fn check_expression(&self, context: &mut LintContext, exp: &Rc<Expression>) {
+        let Expression::Member(member_exp) = exp.as_ref() else {
+            return;
+        };
+        let host = context.host();
+
+        // Report a chain once, at the access exceeding the depth.
+        if Self::any_chain_depth(&host, exp) == self.max_depth + 1 {
+            let message = format!("Member access chain over values of type * is deeper than {}", self.max_depth);
+            context.report(&exp.location(), &self.with_suggestion(message, "the receiver"));
+            return;
+        }
+
+        // Undeclared property of a dynamic class
+        let Some(r) = host.node_mapping().get(exp) else {
+            return;
+        };
+        if !r.is::<DynamicReferenceValue>() {
+            return;
+        }
+        let Some(base_type) = Self::static_type(&host, &member_exp.base) else {
+            return;
+        };
+        let base_type_esc = base_type.escape_of_nullable_or_non_nullable();
+        if base_type_esc.is::<ClassType>() && base_type_esc.is_dynamic() {
+            let name = member_exp.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name).unwrap_or("*".into());
+            let message = format!("{name} is not declared by the dynamic class {base_type_esc}");
+            context.report(&member_exp.identifier.location, &self.with_suggestion(message, "its values"));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum LintSeverity {
+    Allow,
+    Warning,
+    Error,
+}
+
+/// A lint rule compiled against the verifier.
+///
+/// Rules are registered through `Verifier::register_lint_rule()` and run
+/// once the programs are verified, thus the node mapping
+/// of the `Database` is available to every hook.
+///
+/// ```ignore
+/// struct NoWith;
+///
+/// impl LintRule for NoWith {
+///     fn name(&self) -> String {
+///         "no-with".into()
+///     }
+///
+///     fn check_statement(&self, context: &mut LintContext, stmt: &Rc<Directive>) {
+///         if let Directive::WithStatement(wstmt) = stmt.as_ref() {
+///             context.report(&wstmt.location, "The with statement is not allowed");
+///         }
+///     }
+/// }
+///
+/// verifier.register_lint_rule(Rc::new(NoWith));
+/// ```
+pub trait LintRule {
+    /// Name used in diagnostics and as key of `CompilerOptions::lint_severities`.
+    fn name(&self) -> String;
+
+    fn default_severity(&self) -> LintSeverity {
+        LintSeverity::Warning
+    }
+
+    /// Invoked for every directive that is not a definition.
+    fn check_statement(&self, _context: &mut LintContext, _stmt: &Rc<Directive>) {}
+
+    fn check_expression(&self, _context: &mut LintContext, _exp: &Rc<Expression>) {}
+
+    /// Invoked for variable, function, class, enum, interface,
+    /// type and namespace definitions.
+    fn check_definition(&self, _context: &mut LintContext, _defn: &Rc<Directive>) {}
+}
+
+/// Context given to the hooks of a `LintRule`.
+pub struct LintContext<'a> {
+    pub(crate) verifier: &'a mut Subverifier,
+    pub(crate) rule_name: String,
+    pub(crate) severity: LintSeverity,
+}
+
+impl<'a> LintContext<'a> {
+    pub fn host(&self) -> Rc<Database> {
+        self.verifier.host.clone()
+    }
+
+    /// The severity the rule was configured with.
+    pub fn severity(&self) -> LintSeverity {
+        self.severity
+    }
+
+    /// Reports a diagnostic over a location at the rule's severity.
+    pub fn report(&mut self, location: &Location, message: &str) {
+        let args = diagarg![message.to_owned(), self.rule_name.clone()];
+        match self.severity {
+            LintSeverity::Allow => {},
+            LintSeverity::Warning => {
+                self.verifier.add_warning(location, WhackDiagnosticKind::LintRuleViolation, args);
+            },
+            LintSeverity::Error => {
+                self.verifier.add_verify_error(location, WhackDiagnosticKind::LintRuleViolation, args);
+            },
+        }
+    }
+
+    /// Attaches a suggested fix to a diagnostic previously reported
+    /// over the same location.
+    pub fn suggest_fix(&mut self, location: &Location, title: &str, edits: Vec<SourceEdit>) {
+        if self.severity == LintSeverity::Allow {
+            return;
+        }
+        self.verifier.suggested_fixes.push(SuggestedFix {
+            location: location.clone(),
+            title: title.to_owned(),
+            edits,
+        });
+    }
+}
\ No newline at end of file
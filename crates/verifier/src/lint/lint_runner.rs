@@ -0,0 +1,62 @@
+use crate::ns::*;
+
+/// Runs registered lint rules over verified programs.
+pub(crate) struct LintRunner<'a> {
+    pub verifier: &'a mut Subverifier,
+    pub rules: Vec<(Rc<dyn LintRule>, LintSeverity)>,
+}
+
+impl<'a> LintRunner<'a> {
+    pub fn new(verifier: &'a mut Subverifier, compiler_options: &CompilerOptions, rules: &[Rc<dyn LintRule>]) -> Self {
+        let rules = rules.iter()
+            .map(|rule| {
+                let severity = compiler_options.lint_severities.get(&rule.name()).cloned().unwrap_or(rule.default_severity());
+                (rule.clone(), severity)
+            })
+            .filter(|(_, severity)| *severity != LintSeverity::Allow)
+            .collect();
+        Self { verifier, rules }
+    }
+
+    fn is_definition(drtv: &Directive) -> bool {
+        matches!(drtv,
+            Directive::VariableDefinition(_) |
+            Directive::FunctionDefinition(_) |
+            Directive::ClassDefinition(_) |
+            Directive::EnumDefinition(_) |
+            Directive::InterfaceDefinition(_) |
+            Directive::TypeDefinition(_) |
+            Directive::NamespaceDefinition(_))
+    }
+}
+
+impl<'a> Visitor for LintRunner<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        let is_definition = Self::is_definition(drtv);
+        for (rule, severity) in self.rules.clone().iter() {
+            let mut context = LintContext {
+                verifier: &mut *self.verifier,
+                rule_name: rule.name(),
+                severity: *severity,
+            };
+            if is_definition {
+                rule.check_definition(&mut context, drtv);
+            } else {
+                rule.check_statement(&mut context, drtv);
+            }
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        for (rule, severity) in self.rules.clone().iter() {
+            let mut context = LintContext {
+                verifier: &mut *self.verifier,
+                rule_name: rule.name(),
+                severity: *severity,
+            };
+            rule.check_expression(&mut context, exp);
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
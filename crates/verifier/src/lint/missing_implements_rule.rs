@@ -0,0 +1,46 @@
+use crate::ns::*;
+
+/// Suggests declaring `implements` for interfaces a class already
+/// satisfies member by member, given by fully qualified name.
+///
+/// ```ignore
+/// verifier.register_lint_rule(Rc::new(MissingImplementsRule {
+///     interfaces: vec!["flash.events.IEventDispatcher".into()],
+/// }));
+/// ```
+pub struct MissingImplementsRule {
+    pub interfaces: Vec<String>,
+}
+
+impl LintRule for MissingImplementsRule {
+    fn name(&self) -> String {
+        "missing-implements".into()
+    }
+
+    fn check_definition(&self, context: &mut LintContext, defn: &Rc<Directive>) {
+        let Directive::ClassDefinition(class_defn) = defn.as_ref() else {
+            return;
+        };
+        let host = context.host();
+        let Some(class) = host.node_mapping().get(defn).filter(|c| c.is::<ClassType>()) else {
+            return;
+        };
+        let conformance = StructuralConformance(&host);
+        let interfaces = self.interfaces.iter().filter_map(|name| conformance.resolve_interface(name)).collect::<Vec<_>>();
+        let Ok(missing) = conformance.missing_implements(&class, &interfaces) else {
+            return;
+        };
+        for interface in missing {
+            let name = interface.name().local_name();
+            context.report(&class_defn.name.1, &format!("{} has every member of {name}, but does not implement it", class_defn.name.0));
+            let edit = match class_defn.implements_clause.as_ref().and_then(|list| list.last()) {
+                Some(last) => SourceEdit::insert_after(&last.location(), &format!(", {name}")),
+                None => {
+                    let after = class_defn.extends_clause.as_ref().map(|exp| exp.location()).unwrap_or(class_defn.name.1.clone());
+                    SourceEdit::insert_after(&after, &format!(" implements {name}"))
+                },
+            };
+            context.suggest_fix(&class_defn.name.1, &format!("Implement {name}"), vec![edit]);
+        }
+    }
+}
\ No newline at end of file
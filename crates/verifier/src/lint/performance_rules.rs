@@ -0,0 +1,347 @@
+use crate::ns::*;
+
+/// Opt-in performance lint rules for hot display-list code, registered
+/// together through `PerformanceLintGroup::rules()`.
+///
+/// ```ignore
+/// for rule in PerformanceLintGroup::rules() {
+///     verifier.register_lint_rule(rule);
+/// }
+/// ```
+pub struct PerformanceLintGroup;
+
+impl PerformanceLintGroup {
+    pub fn rules() -> Vec<Rc<dyn LintRule>> {
+        vec![
+            Rc::new(EnterFrameAllocationRule),
+            Rc::new(StringConcatenationInLoopRule),
+            Rc::new(RepeatedDefinitionLookupRule),
+            Rc::new(LongChainInLoopRule::default()),
+        ]
+    }
+
+    fn loop_body(stmt: &Rc<Directive>) -> Option<&Rc<Directive>> {
+        match stmt.as_ref() {
+            Directive::WhileStatement(wstmt) => Some(&wstmt.body),
+            Directive::DoStatement(dostmt) => Some(&dostmt.body),
+            Directive::ForStatement(forstmt) => Some(&forstmt.body),
+            Directive::ForInStatement(forstmt) => Some(&forstmt.body),
+            _ => None,
+        }
+    }
+
+    /// Expressions evaluated by a loop body, outside nested loops,
+    /// which are checked on their own, and nested functions.
+    fn loop_body_expressions(body: &Rc<Directive>) -> Vec<Rc<Expression>> {
+        let mut collector = ExpressionCollector { exps: vec![], skip_loops: true };
+        collector.visit_directive(body);
+        collector.exps
+    }
+
+    fn function_name(call: &CallExpression) -> Option<String> {
+        match call.base.as_ref() {
+            Expression::Member(member) => member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name),
+            Expression::QualifiedIdentifier(_) => call.base.to_identifier_name().map(|(name, _)| name),
+            _ => None,
+        }
+    }
+}
+
+/// Collects expressions in evaluation order, outside nested functions.
+struct ExpressionCollector {
+    exps: Vec<Rc<Expression>>,
+    skip_loops: bool,
+}
+
+impl Visitor for ExpressionCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        let is_loop = PerformanceLintGroup::loop_body(drtv).is_some();
+        if matches!(drtv.as_ref(), Directive::FunctionDefinition(_)) || (is_loop && self.skip_loops) {
+            return;
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        self.exps.push(exp.clone());
+        if !matches!(exp.as_ref(), Expression::Function(_)) {
+            walk_expression(self, exp);
+        }
+    }
+}
+
+/// Flags allocations in `enterFrame` handlers, which run on every frame.
+///
+/// Handlers are methods of the class registering them through
+/// `addEventListener(Event.ENTER_FRAME, ...)`, together with the
+/// methods of the same class they call, and function expressions.
+pub struct EnterFrameAllocationRule;
+
+impl EnterFrameAllocationRule {
+    fn is_enter_frame(host: &Database, exp: &Rc<Expression>) -> bool {
+        if let Some(v) = host.node_mapping().get(exp).filter(|v| v.is::<StringConstant>()) {
+            return v.string_value() == "enterFrame";
+        }
+        match exp.as_ref() {
+            Expression::StringLiteral(literal) => literal.value == "enterFrame",
+            Expression::Member(member) => member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name == "ENTER_FRAME").unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// Returns the method name of `f` or `this.f`.
+    fn method_name(exp: &Rc<Expression>) -> Option<String> {
+        match exp.as_ref() {
+            Expression::Member(member) if matches!(member.base.as_ref(), Expression::ThisLiteral(_)) => {
+                member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name)
+            },
+            Expression::QualifiedIdentifier(_) => exp.to_identifier_name().map(|(name, _)| name),
+            _ => None,
+        }
+    }
+
+    fn report_allocations(context: &mut LintContext, exps: &[Rc<Expression>]) {
+        for exp in exps.iter() {
+            let kind = match exp.as_ref() {
+                Expression::New(_) => "Object construction",
+                Expression::ArrayLiteral(_) | Expression::VectorLiteral(_) => "Array allocation",
+                Expression::ObjectInitializer(_) => "Object allocation",
+                Expression::Function(_) => "Closure allocation",
+                _ => continue,
+            };
+            context.report(&exp.location(), &format!("{kind} in an enterFrame handler runs on every frame"));
+        }
+    }
+}
+
+impl LintRule for EnterFrameAllocationRule {
+    fn name(&self) -> String {
+        "perf-enter-frame-allocation".into()
+    }
+
+    fn check_definition(&self, context: &mut LintContext, defn: &Rc<Directive>) {
+        let Directive::ClassDefinition(defn) = defn.as_ref() else {
+            return;
+        };
+        let host = context.host();
+        let mut methods = HashMap::<String, Rc<FunctionCommon>>::new();
+        let mut collector = ExpressionCollector { exps: vec![], skip_loops: false };
+        for drtv in defn.block.directives.iter() {
+            if let Directive::FunctionDefinition(fndefn) = drtv.as_ref() {
+                if let FunctionName::Identifier(name) = &fndefn.name {
+                    methods.insert(name.0.clone(), fndefn.common.clone());
+                }
+                walk_function_common(&mut collector, &fndefn.common);
+            }
+        }
+
+        // Find the handlers registered by the class
+        let mut pending = vec![];
+        for exp in collector.exps.iter() {
+            let Expression::Call(call) = exp.as_ref() else {
+                continue;
+            };
+            if PerformanceLintGroup::function_name(call).as_deref() != Some("addEventListener") || call.arguments.len() < 2 {
+                continue;
+            }
+            if !Self::is_enter_frame(&host, &call.arguments[0]) {
+                continue;
+            }
+            match call.arguments[1].as_ref() {
+                Expression::Function(function) => {
+                    let mut body = ExpressionCollector { exps: vec![], skip_loops: false };
+                    walk_function_common(&mut body, &function.common);
+                    Self::report_allocations(context, &body.exps);
+                },
+                _ => {
+                    if let Some(name) = Self::method_name(&call.arguments[1]) {
+                        pending.push(name);
+                    }
+                },
+            }
+        }
+
+        // Follow calls to methods of the class
+        let mut visited = HashSet::<String>::new();
+        while let Some(name) = pending.pop() {
+            if !visited.insert(name.clone()) {
+                continue;
+            }
+            let Some(common) = methods.get(&name) else {
+                continue;
+            };
+            let mut body = ExpressionCollector { exps: vec![], skip_loops: false };
+            walk_function_common(&mut body, common);
+            Self::report_allocations(context, &body.exps);
+            for exp in body.exps.iter() {
+                if let Expression::Call(call) = exp.as_ref() {
+                    if let Some(callee) = Self::method_name(&call.base) {
+                        pending.push(callee);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Flags `s += x` and `s = s + x` on `String` values inside loops,
+/// which allocate a new string per iteration.
+pub struct StringConcatenationInLoopRule;
+
+impl LintRule for StringConcatenationInLoopRule {
+    fn name(&self) -> String {
+        "perf-string-concatenation-in-loop".into()
+    }
+
+    fn check_statement(&self, context: &mut LintContext, stmt: &Rc<Directive>) {
+        let Some(body) = PerformanceLintGroup::loop_body(stmt) else {
+            return;
+        };
+        let host = context.host();
+        for exp in PerformanceLintGroup::loop_body_expressions(body) {
+            let Expression::Assignment(assign) = exp.as_ref() else {
+                continue;
+            };
+            let is_concatenation = match assign.compound {
+                Some(Operator::Add) => true,
+                Some(_) => false,
+                None => matches!(assign.right.as_ref(), Expression::Binary(binary)
+                    if matches!(binary.operator, Operator::Add)
+                    && binary.left.to_identifier_name().map(|(name, _)| name) == assign.left.to_identifier_name().map(|(name, _)| name)
+                    && assign.left.to_identifier_name().is_some()),
+            };
+            if !is_concatenation {
+                continue;
+            }
+            let is_string = host.node_mapping().get(&assign.left).map(|v| v.static_type(&host) == host.string_type()).unwrap_or(false);
+            if is_string {
+                context.report(&assign.location, "String concatenation inside a loop; consider collecting the parts in an Array and calling join()");
+            }
+        }
+    }
+}
+
+/// Flags `getDefinitionByName()` calls with a constant name that repeat
+/// within a function or run inside a loop; the result can be cached.
+pub struct RepeatedDefinitionLookupRule;
+
+impl LintRule for RepeatedDefinitionLookupRule {
+    fn name(&self) -> String {
+        "perf-repeated-get-definition-by-name".into()
+    }
+
+    fn check_definition(&self, context: &mut LintContext, defn: &Rc<Directive>) {
+        let Directive::FunctionDefinition(defn) = defn.as_ref() else {
+            return;
+        };
+        let host = context.host();
+        let mut collector = ExpressionCollector { exps: vec![], skip_loops: false };
+        walk_function_common(&mut collector, &defn.common);
+        let mut in_loops = HashSet::<NodeAsKey<Rc<Expression>>>::new();
+        let mut loops = LoopCallCollector { calls: vec![], depth: 0 };
+        walk_function_common(&mut loops, &defn.common);
+        in_loops.extend(loops.calls.into_iter().map(NodeAsKey));
+
+        let mut seen = HashSet::<String>::new();
+        for exp in collector.exps.iter() {
+            let Expression::Call(call) = exp.as_ref() else {
+                continue;
+            };
+            if PerformanceLintGroup::function_name(call).as_deref() != Some("getDefinitionByName") {
+                continue;
+            }
+            let Some(name) = call.arguments.first().and_then(|arg| host.node_mapping().get(arg)).filter(|v| v.is::<StringConstant>()).map(|v| v.string_value()) else {
+                continue;
+            };
+            if !seen.insert(name.clone()) {
+                context.report(&call.location, &format!("getDefinitionByName(\"{name}\") is repeated; cache its result"));
+            } else if in_loops.contains(&NodeAsKey(exp.clone())) {
+                context.report(&call.location, &format!("getDefinitionByName(\"{name}\") runs on every iteration; cache its result"));
+            }
+        }
+    }
+}
+
+/// Collects the calls inside loops, outside nested functions.
+struct LoopCallCollector {
+    calls: Vec<Rc<Expression>>,
+    depth: usize,
+}
+
+impl Visitor for LoopCallCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if matches!(drtv.as_ref(), Directive::FunctionDefinition(_)) {
+            return;
+        }
+        let is_loop = PerformanceLintGroup::loop_body(drtv).is_some();
+        if is_loop {
+            self.depth += 1;
+        }
+        walk_directive(self, drtv);
+        if is_loop {
+            self.depth -= 1;
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::Call(_) if self.depth != 0 => {
+                self.calls.push(exp.clone());
+                walk_expression(self, exp);
+            },
+            _ => {
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
+
+/// Flags member chains longer than `max_depth` inside loops, such as
+/// `a.b.c.d`, whose lookups repeat on every iteration.
+pub struct LongChainInLoopRule {
+    pub max_depth: usize,
+}
+
+impl Default for LongChainInLoopRule {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+        }
+    }
+}
+
+impl LongChainInLoopRule {
+    fn chain_depth(exp: &Rc<Expression>) -> usize {
+        match exp.as_ref() {
+            Expression::Member(member) => 1 + Self::chain_depth(&member.base),
+            _ => 0,
+        }
+    }
+}
+
+impl LintRule for LongChainInLoopRule {
+    fn name(&self) -> String {
+        "perf-long-chain-in-loop".into()
+    }
+
+    fn check_statement(&self, context: &mut LintContext, stmt: &Rc<Directive>) {
+        let Some(body) = PerformanceLintGroup::loop_body(stmt) else {
+            return;
+        };
+        let exps = PerformanceLintGroup::loop_body_expressions(body);
+        // Report the outermost access of each chain
+        let inner = exps.iter().filter_map(|exp| match exp.as_ref() {
+            Expression::Member(member) => Some(NodeAsKey(member.base.clone())),
+            _ => None,
+        }).collect::<HashSet<_>>();
+        for exp in exps.iter() {
+            if !matches!(exp.as_ref(), Expression::Member(_)) || inner.contains(&NodeAsKey(exp.clone())) {
+                continue;
+            }
+            if Self::chain_depth(exp) > self.max_depth {
+                context.report(&exp.location(), &format!("Member chain deeper than {} inside a loop; consider caching its base in a local", self.max_depth));
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+use crate::ns::*;
+
+/// Security lint rules for auditing legacy Flash content, registered
+/// together through `SecurityLintGroup::rules()`.
+///
+/// ```ignore
+/// for rule in SecurityLintGroup::rules() {
+///     verifier.register_lint_rule(rule);
+/// }
+/// ```
+pub struct SecurityLintGroup;
+
+impl SecurityLintGroup {
+    pub fn rules() -> Vec<Rc<dyn LintRule>> {
+        vec![
+            Rc::new(ExternalInterfaceCallRule),
+            Rc::new(LoaderWithoutContextRule),
+            Rc::new(NavigateToUrlRule),
+            Rc::new(AllowDomainWildcardRule),
+        ]
+    }
+
+    /// Returns the receiver name, if unqualified, and the method name of a call.
+    fn call_target(call: &CallExpression) -> Option<(Option<String>, String)> {
+        match call.base.as_ref() {
+            Expression::Member(member) => {
+                let (name, _) = member.identifier.to_identifier_name_or_asterisk()?;
+                let receiver = member.base.to_identifier_name().map(|(name, _)| name);
+                Some((receiver, name))
+            },
+            Expression::QualifiedIdentifier(_) => Some((None, call.base.to_identifier_name()?.0)),
+            _ => None,
+        }
+    }
+
+    fn is_string_constant(host: &Database, exp: &Rc<Expression>) -> bool {
+        matches!(exp.as_ref(), Expression::StringLiteral(_))
+            || host.node_mapping().get(exp).map(|v| v.is::<StringConstant>()).unwrap_or(false)
+    }
+
+    fn string_constant(host: &Database, exp: &Rc<Expression>) -> Option<String> {
+        if let Expression::StringLiteral(literal) = exp.as_ref() {
+            return Some(literal.value.clone());
+        }
+        host.node_mapping().get(exp).filter(|v| v.is::<StringConstant>()).map(|v| v.string_value())
+    }
+}
+
+/// Flags `ExternalInterface.call()` with a non-constant function name,
+/// which lets data pick the JavaScript function to run.
+pub struct ExternalInterfaceCallRule;
+
+impl LintRule for ExternalInterfaceCallRule {
+    fn name(&self) -> String {
+        "security-external-interface-call".into()
+    }
+
+    fn check_expression(&self, context: &mut LintContext, exp: &Rc<Expression>) {
+        let Expression::Call(call) = exp.as_ref() else {
+            return;
+        };
+        let Some((Some(receiver), name)) = SecurityLintGroup::call_target(call) else {
+            return;
+        };
+        if receiver != "ExternalInterface" || name != "call" {
+            return;
+        }
+        if let Some(function_name) = call.arguments.first() {
+            if !SecurityLintGroup::is_string_constant(&context.host(), function_name) {
+                context.report(&function_name.location(), "ExternalInterface.call() is given a non-constant function name");
+            }
+        }
+    }
+}
+
+/// Flags `Loader.load()` and `Loader.loadBytes()` without a
+/// `LoaderContext`, which run loaded content under default permissions.
+pub struct LoaderWithoutContextRule;
+
+impl LintRule for LoaderWithoutContextRule {
+    fn name(&self) -> String {
+        "security-loader-without-context".into()
+    }
+
+    fn check_expression(&self, context: &mut LintContext, exp: &Rc<Expression>) {
+        let Expression::Call(call) = exp.as_ref() else {
+            return;
+        };
+        let Expression::Member(member) = call.base.as_ref() else {
+            return;
+        };
+        let Some((name, _)) = member.identifier.to_identifier_name_or_asterisk() else {
+            return;
+        };
+        if !(name == "load" || name == "loadBytes") || call.arguments.len() >= 2 {
+            return;
+        }
+        let host = context.host();
+        let Some(t) = host.node_mapping().get(&member.base).map(|v| v.static_type(&host).escape_of_nullable_or_non_nullable()) else {
+            return;
+        };
+        if t.is::<ClassType>() && t.name().local_name() == "Loader" {
+            context.report(&call.location, &format!("Loader.{name}() is called without a LoaderContext"));
+        }
+    }
+}
+
+/// Flags `navigateToURL()` with a request whose URL is not a constant.
+pub struct NavigateToUrlRule;
+
+impl LintRule for NavigateToUrlRule {
+    fn name(&self) -> String {
+        "security-navigate-to-url".into()
+    }
+
+    fn check_expression(&self, context: &mut LintContext, exp: &Rc<Expression>) {
+        let Expression::Call(call) = exp.as_ref() else {
+            return;
+        };
+        let Some((None, name)) = SecurityLintGroup::call_target(call) else {
+            return;
+        };
+        if name != "navigateToURL" {
+            return;
+        }
+        let Some(request) = call.arguments.first() else {
+            return;
+        };
+        // Accept only new URLRequest("constant")
+        if let Expression::New(new_exp) = request.as_ref() {
+            let url = new_exp.arguments.as_ref().and_then(|arguments| arguments.first());
+            if url.map(|url| SecurityLintGroup::is_string_constant(&context.host(), url)).unwrap_or(false) {
+                return;
+            }
+        }
+        context.report(&request.location(), "navigateToURL() is given a URL that is not a constant");
+    }
+}
+
+/// Flags `Security.allowDomain("*")` and `Security.allowInsecureDomain("*")`.
+pub struct AllowDomainWildcardRule;
+
+impl LintRule for AllowDomainWildcardRule {
+    fn name(&self) -> String {
+        "security-allow-domain-wildcard".into()
+    }
+
+    fn check_expression(&self, context: &mut LintContext, exp: &Rc<Expression>) {
+        let Expression::Call(call) = exp.as_ref() else {
+            return;
+        };
+        let Some((Some(receiver), name)) = SecurityLintGroup::call_target(call) else {
+            return;
+        };
+        if receiver != "Security" || !(name == "allowDomain" || name == "allowInsecureDomain") {
+            return;
+        }
+        let host = context.host();
+        for arg in call.arguments.iter() {
+            if SecurityLintGroup::string_constant(&host, arg).as_deref() == Some("*") {
+                context.report(&arg.location(), &format!("Security.{name}(\"*\") allows content from any domain"));
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+mod member_generator;
+pub use member_generator::*;
+
+mod bindable_generator;
+pub use bindable_generator::*;
+
+mod mixin_generator;
+pub use mixin_generator::*;
\ No newline at end of file
@@ -0,0 +1,221 @@
+use crate::ns::*;
+use std::cell::RefCell;
+
+/// How a `[Bindable]` property is declared.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BindablePropertyKind {
+    /// A variable, lowered into an accessor pair over backing storage.
+    Variable,
+    /// A setter, wrapped to dispatch the change event.
+    Setter,
+    /// A getter without a setter, whose change event is dispatched by user code.
+    Getter,
+}
+
+/// A `[Bindable]` property and its lowering.
+#[derive(Clone, Debug)]
+pub struct BindableProperty {
+    pub class: Entity,
+    pub name: String,
+    pub kind: BindablePropertyKind,
+    /// Event signaling a change, `propertyChange` by default.
+    pub event: String,
+    /// Private variable holding the value of a `Variable` property.
+    pub backing_field: Option<Entity>,
+}
+
+impl BindableProperty {
+    pub const PROPERTY_CHANGE: &'static str = "propertyChange";
+
+    /// Whether codegen dispatches a `PropertyChangeEvent` from the setter
+    /// when the value changes; custom events are dispatched by user code.
+    pub fn dispatches_property_change(&self) -> bool {
+        self.event == Self::PROPERTY_CHANGE && self.kind != BindablePropertyKind::Getter
+    }
+}
+
+/// An `IEventDispatcher` implementation injected into a `[Bindable]`
+/// class that does not implement the interface, as Flex does.
+#[derive(Clone, Debug)]
+pub struct EventDispatcherInjection {
+    pub class: Entity,
+    /// Private `EventDispatcher` variable to which codegen delegates
+    /// the included interface methods, constructed with the instance
+    /// as its target.
+    pub field: Entity,
+    /// Interface methods included into the class.
+    pub methods: Vec<Entity>,
+}
+
+/// Implements `[Bindable]` meta-data at class, variable and accessor
+/// definitions. Registered on every `Verifier`; its properties are
+/// listed by `Verifier::bindable_properties()`.
+///
+/// Variables get backing storage named `_bindable_{name}`, and together
+/// with setters are lowered to dispatch `PropertyChangeEvent.createUpdateEvent()`
+/// when `event` is omitted. A custom `event` must be declared by `[Event]`
+/// meta-data of the class or a base class. A class that does not
+/// implement `flash.events.IEventDispatcher` is made to implement it
+/// through an `EventDispatcherInjection`.
+#[derive(Default)]
+pub struct BindableGenerator {
+    properties: RefCell<Vec<BindableProperty>>,
+    /// Classes checked for implementing `IEventDispatcher`.
+    checked_classes: RefCell<HashSet<Entity>>,
+    injections: RefCell<Vec<EventDispatcherInjection>>,
+}
+
+impl BindableGenerator {
+    /// Name of the private variable of an `EventDispatcherInjection`.
+    pub const DISPATCHER_FIELD: &'static str = "_bindingEventDispatcher";
+
+    pub fn properties(&self) -> Vec<BindableProperty> {
+        self.properties.borrow().clone()
+    }
+
+    pub fn event_dispatcher_injections(&self) -> Vec<EventDispatcherInjection> {
+        self.injections.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.properties.borrow_mut().clear();
+        self.checked_classes.borrow_mut().clear();
+        self.injections.borrow_mut().clear();
+    }
+
+    /// Value of a meta-data entry, or of a sole unkeyed entry for
+    /// `event`, as in `[Bindable("change")]`.
+    fn metadata_value(metadata: &Metadata, key: &str) -> Option<String> {
+        let entries = metadata.entries.as_ref()?;
+        let entry = entries.iter().find(|e| e.key.as_ref().map(|(k, _)| k == key).unwrap_or(false))
+            .or_else(|| entries.iter().find(|e| e.key.is_none()).filter(|_| key == "event" && entries.len() == 1))?;
+        Some(match entry.value.as_ref() {
+            MetadataValue::String(val) => val.0.clone(),
+            MetadataValue::IdentifierString(val) => val.0.clone(),
+        })
+    }
+
+    fn inject_event_dispatcher(&self, context: &mut MemberGeneratorContext) {
+        let class = context.class();
+        if !self.checked_classes.borrow_mut().insert(class.clone()) {
+            return;
+        }
+        let host = context.host();
+        let pckg = host.factory().create_package(vec!["flash", "events"]);
+        let Some(ns) = pckg.public_ns() else {
+            return;
+        };
+        let find = |name: &str| pckg.properties(&host).get(&host.factory().create_qname(&ns, name.to_owned()));
+        let (Some(interface), Some(dispatcher)) = (find("IEventDispatcher"), find("EventDispatcher")) else {
+            return;
+        };
+        if class.is_subtype_of(&interface, &host).unwrap_or(true) {
+            return;
+        }
+        let Some(field) = context.define_private_variable(Self::DISPATCHER_FIELD, &dispatcher) else {
+            return;
+        };
+        let interface_methods: Vec<Entity> = interface.prototype(&host).borrow().iter()
+            .map(|(_, m)| m.clone())
+            .filter(|m| m.is::<MethodSlot>())
+            .collect();
+        let mut methods = vec![];
+        for method in interface_methods.iter() {
+            // A method declared by the class is kept as is
+            if context.member(&method.name().local_name(), false).is_some() {
+                continue;
+            }
+            methods.extend(context.include_member(method));
+        }
+        class.implements(&host).push(interface);
+        self.injections.borrow_mut().push(EventDispatcherInjection { class, field, methods });
+    }
+
+    fn declares_event(host: &Database, class: &Entity, event: &str) -> bool {
+        let mut class = Some(class.clone());
+        while let Some(c) = class {
+            let declared = c.metadata().iter().any(|m| m.name.0 == "Event" && Self::metadata_value(&m, "name").as_deref() == Some(event));
+            if declared {
+                return true;
+            }
+            class = c.extends_class(host);
+        }
+        false
+    }
+
+    fn add_property(&self, context: &mut MemberGeneratorContext, metadata: &Rc<Metadata>, name: &(String, Location), kind: BindablePropertyKind, static_type: Option<Entity>) {
+        self.inject_event_dispatcher(context);
+        let event = Self::metadata_value(metadata, "event").unwrap_or(BindableProperty::PROPERTY_CHANGE.into());
+        let class = context.class();
+        if event != BindableProperty::PROPERTY_CHANGE && !Self::declares_event(&context.host(), &class, &event) {
+            context.add_warning(&metadata.location, WhackDiagnosticKind::BindableEventNotDeclared, diagarg![event.clone(), class.clone()]);
+        }
+        if kind == BindablePropertyKind::Getter && event == BindableProperty::PROPERTY_CHANGE {
+            context.add_warning(&name.1, WhackDiagnosticKind::BindableGetterRequiresEvent, diagarg![name.0.clone()]);
+        }
+        let backing_field = static_type.and_then(|t| context.define_private_variable(&format!("_bindable_{}", name.0), &t));
+        self.properties.borrow_mut().push(BindableProperty { class, name: name.0.clone(), kind, event, backing_field });
+    }
+
+    fn add_member(&self, context: &mut MemberGeneratorContext, member: &Rc<Directive>, metadata: &Rc<Metadata>, setters: &HashSet<String>) {
+        let host = context.host();
+        match member.as_ref() {
+            Directive::VariableDefinition(defn) => {
+                if defn.kind.0 == VariableDefinitionKind::Const || Attribute::find_static(&defn.attributes).is_some() {
+                    return;
+                }
+                for binding in defn.bindings.iter() {
+                    let Some(name) = binding.destructuring.destructuring.to_identifier_name() else {
+                        continue;
+                    };
+                    let static_type = context.member(&name.0, false).map(|slot| slot.static_type(&host)).unwrap_or(host.any_type());
+                    self.add_property(context, metadata, &name, BindablePropertyKind::Variable, Some(static_type));
+                }
+            },
+            Directive::FunctionDefinition(defn) => match &defn.name {
+                FunctionName::Setter(name) => self.add_property(context, metadata, name, BindablePropertyKind::Setter, None),
+                FunctionName::Getter(name) if !setters.contains(&name.0) => self.add_property(context, metadata, name, BindablePropertyKind::Getter, None),
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+
+    fn setters(definition: &Rc<Directive>) -> (Vec<Rc<Directive>>, HashSet<String>) {
+        let Directive::ClassDefinition(defn) = definition.as_ref() else {
+            return (vec![], HashSet::new());
+        };
+        let setters = defn.block.directives.iter().filter_map(|d| match d.as_ref() {
+            Directive::FunctionDefinition(f) => if let FunctionName::Setter(name) = &f.name { Some(name.0.clone()) } else { None },
+            _ => None,
+        }).collect();
+        (defn.block.directives.clone(), setters)
+    }
+}
+
+impl MemberGenerator for BindableGenerator {
+    fn metadata_name(&self) -> String {
+        "Bindable".into()
+    }
+
+    fn generate(&self, context: &mut MemberGeneratorContext, metadata: &Rc<Metadata>) {
+        let (members, setters) = Self::setters(context.definition());
+        for member in members.iter() {
+            let attributes = match member.as_ref() {
+                Directive::VariableDefinition(defn) => &defn.attributes,
+                Directive::FunctionDefinition(defn) => &defn.attributes,
+                _ => continue,
+            };
+            // Public members without their own [Bindable] meta-data
+            let is_public = attributes.iter().any(|a| matches!(a, Attribute::Public(_)));
+            if is_public && !Attribute::find_metadata(attributes).iter().any(|m| m.name.0 == "Bindable") {
+                self.add_member(context, member, metadata, &setters);
+            }
+        }
+    }
+
+    fn generate_member(&self, context: &mut MemberGeneratorContext, member: &Rc<Directive>, metadata: &Rc<Metadata>) {
+        let (_, setters) = Self::setters(context.definition());
+        self.add_member(context, member, metadata, &setters);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,339 @@
+use crate::ns::*;
+
+/// A compile-time generator contributing members to classes marked with
+/// its meta-data, such as `[Bindable]` setters, builders or equality
+/// methods.
+///
+/// Generators are registered through `Verifier::register_member_generator()`
+/// and run once signatures are resolved, before method bodies are
+/// verified, so that bodies may refer to the generated members.
+///
+/// ```ignore
+/// struct Equatable;
+///
+/// impl MemberGenerator for Equatable {
+///     fn metadata_name(&self) -> String {
+///         "Equatable".into()
+///     }
+///
+///     fn generate(&self, context: &mut MemberGeneratorContext, _metadata: &Rc<Metadata>) {
+///         let host = context.host();
+///         let other = context.class();
+///         context.define_method_with_body("equals", vec![("other", other)], &host.boolean_type(), false, "return this.x == other.x;");
+///     }
+/// }
+///
+/// verifier.register_member_generator(Rc::new(Equatable));
+/// ```
+pub trait MemberGenerator {
+    /// Name of the class meta-data the generator applies to.
+    fn metadata_name(&self) -> String;
+
+    /// Invoked once per occurrence of the meta-data on a class.
+    fn generate(&self, _context: &mut MemberGeneratorContext, _metadata: &Rc<Metadata>) {}
+
+    /// Invoked once per occurrence of the meta-data on a member
+    /// definition of a class, such as a variable or setter.
+    fn generate_member(&self, _context: &mut MemberGeneratorContext, _member: &Rc<Directive>, _metadata: &Rc<Metadata>) {}
+}
+
+/// Context given to `MemberGenerator::generate()`.
+pub struct MemberGeneratorContext<'a> {
+    pub(crate) verifier: &'a mut Subverifier,
+    pub(crate) generator_name: String,
+    pub(crate) class: Entity,
+    pub(crate) definition: Rc<Directive>,
+}
+
+impl<'a> MemberGeneratorContext<'a> {
+    pub fn host(&self) -> Rc<Database> {
+        self.verifier.host.clone()
+    }
+
+    /// The class the meta-data is attached to.
+    pub fn class(&self) -> Entity {
+        self.class.clone()
+    }
+
+    /// The class definition, with its declarations verified.
+    pub fn definition(&self) -> &Rc<Directive> {
+        &self.definition
+    }
+
+    /// Finds a public member of the class, either static or of its prototype.
+    pub fn member(&self, name: &str, is_static: bool) -> Option<Entity> {
+        let host = self.host();
+        let names = if is_static { self.class.properties(&host) } else { self.class.prototype(&host) };
+        names.get_in_any_public_ns(name).ok().flatten()
+    }
+
+    /// Defines a public variable, returning `None` if a member of the
+    /// same name already exists.
+    pub fn define_variable(&mut self, name: &str, static_type: &Entity, read_only: bool, is_static: bool) -> Option<Entity> {
+        let host = self.host();
+        let qname = self.qname(self.class.parent()?.public_ns()?, name, is_static)?;
+        let slot = host.factory().create_variable_slot(&qname, read_only, static_type);
+        slot.set_is_static(is_static);
+        self.define(qname, slot, is_static)
+    }
+
+    /// Defines a private instance variable, such as backing storage,
+    /// returning `None` if a member of the same name already exists.
+    pub fn define_private_variable(&mut self, name: &str, static_type: &Entity) -> Option<Entity> {
+        let host = self.host();
+        let qname = self.qname(self.class.private_ns()?, name, false)?;
+        let slot = host.factory().create_variable_slot(&qname, false, static_type);
+        self.define(qname, slot, false)
+    }
+
+    /// Defines a public method, returning `None` if a member of the same
+    /// name already exists. Its body is supplied by codegen.
+    pub fn define_method(&mut self, name: &str, params: Vec<(ParameterKind, Entity)>, result_type: &Entity, is_static: bool) -> Option<Entity> {
+        let host = self.host();
+        let qname = self.qname(self.class.parent()?.public_ns()?, name, is_static)?;
+        let params = params.into_iter().map(|(kind, static_type)| Rc::new(SemanticFunctionTypeParameter { kind, static_type })).collect();
+        let signature = host.factory().create_function_type(params, result_type.clone());
+        let slot = host.factory().create_method_slot(&qname, &signature);
+        slot.set_is_static(is_static);
+        slot.set_is_constructor(false);
+        let activation = host.factory().create_activation(&slot);
+        slot.set_activation(Some(activation));
+        self.define(qname, slot, is_static)
+    }
+
+    /// Defines a public method whose body is given as ActionScript
+    /// statements, such as `return this.x == other.x;`, with a required
+    /// parameter per name and type of `params`. The body is verified
+    /// within the class once the other bodies are verified, built by an
+    /// `AstBuilder` nested in the compilation unit of the class, so that
+    /// its diagnostics are listed with those of the class. Returns `None`
+    /// if a member of the same name already exists, a name is not a valid
+    /// identifier or the body has syntax errors.
+    pub fn define_method_with_body(&mut self, name: &str, params: Vec<(&str, Entity)>, result_type: &Entity, is_static: bool, body: &str) -> Option<Entity> {
+        let location = self.class.location().unwrap_or(self.definition.location());
+        let builder = AstBuilder::nested_in(&location.compilation_unit(), &format!("{}.{name}", self.generator_name));
+        let names = std::iter::once(name).chain(params.iter().map(|(name, _)| *name)).map(AstBuilder::identifier).collect::<Result<Vec<_>, _>>();
+        let directives = names.and_then(|_| builder.directives(&format!("function {name}() {{\n{body}\n}}")));
+        let common = match directives.as_ref().map(|directives| directives.first().map(|drtv| drtv.as_ref())) {
+            Ok(Some(Directive::FunctionDefinition(defn))) => defn.common.clone(),
+            Ok(_) => return None,
+            Err(error) => {
+                self.report_error(&location, &format!("The body of '{name}' is invalid: {error}"));
+                return None;
+            },
+        };
+        let parameter_names = params.iter().map(|(name, _)| (*name).to_owned()).collect();
+        let method = self.define_method(name, params.into_iter().map(|(_, t)| (ParameterKind::Required, t)).collect(), result_type, is_static)?;
+        self.verifier.generated_method_bodies.push(GeneratedMethodBody {
+            generator_name: self.generator_name.clone(),
+            class: self.class.clone(),
+            definition: self.definition.clone(),
+            method: method.clone(),
+            parameter_names,
+            common,
+        });
+        Some(method)
+    }
+
+    /// Defines a copy of a public instance member of another class,
+    /// such as a mixin, returning `None` if a member of the same name
+    /// already exists.
+    pub fn include_member(&mut self, member: &Entity) -> Option<Entity> {
+        let host = self.host();
+        let name = member.name().local_name();
+        if member.is::<VariableSlot>() {
+            self.define_variable(&name, &member.static_type(&host), member.read_only(&host), false)
+        } else if member.is::<MethodSlot>() {
+            let signature = member.signature(&host);
+            let params = signature.params().iter().map(|p| (p.kind, p.static_type.clone())).collect();
+            self.define_method(&name, params, &signature.result_type(), false)
+        } else if member.is::<VirtualSlot>() {
+            let qname = self.qname(self.class.parent()?.public_ns()?, &name, false)?;
+            let slot = host.factory().create_virtual_slot(&qname);
+            for (accessor, is_getter) in [(member.getter(&host), true), (member.setter(&host), false)] {
+                let Some(accessor) = accessor else {
+                    continue;
+                };
+                let method = host.factory().create_method_slot(&qname, &accessor.signature(&host));
+                method.set_is_constructor(false);
+                method.set_parent(Some(self.class.clone()));
+                method.set_of_virtual_slot(Some(slot.clone()));
+                method.set_activation(Some(host.factory().create_activation(&method)));
+                if is_getter {
+                    slot.set_getter(Some(method));
+                } else {
+                    slot.set_setter(Some(method));
+                }
+            }
+            self.define(qname, slot, false)
+        } else {
+            None
+        }
+    }
+
+    /// Reports an error at a location, attributed to the generator.
+    pub fn report_error(&mut self, location: &Location, message: &str) {
+        let args = diagarg![message.to_owned(), self.generator_name.clone()];
+        self.verifier.add_verify_error(location, WhackDiagnosticKind::MemberGeneratorError, args);
+    }
+
+    pub fn add_verify_error(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        self.verifier.add_verify_error(location, kind, arguments);
+    }
+
+    pub fn add_warning(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        self.verifier.add_warning(location, kind, arguments);
+    }
+
+    fn qname(&mut self, ns: Entity, name: &str, is_static: bool) -> Option<Entity> {
+        let host = self.host();
+        let exists = if is_static { self.class.properties(&host) } else { self.class.prototype(&host) }
+            .get(&host.factory().create_qname(&ns, name.to_owned())).is_some();
+        if exists || self.member(name, is_static).is_some() {
+            let location = self.class.location().unwrap_or(self.definition.location());
+            self.verifier.add_verify_error(&location, WhackDiagnosticKind::GeneratedMemberConflict, diagarg![name.to_owned(), self.generator_name.clone()]);
+            return None;
+        }
+        Some(host.factory().create_qname(&ns, name.to_owned()))
+    }
+
+    fn define(&mut self, qname: Entity, slot: Entity, is_static: bool) -> Option<Entity> {
+        let host = self.host();
+        slot.set_parent(Some(self.class.clone()));
+        slot.set_location(self.class.location());
+        if is_static {
+            self.class.properties(&host).set(qname, slot.clone());
+        } else {
+            self.class.prototype(&host).set(qname, slot.clone());
+        }
+        self.verifier.generated_members.insert(slot.clone(), self.generator_name.clone());
+        Some(slot)
+    }
+}
+
+/// A method body contributed by `MemberGeneratorContext::define_method_with_body()`.
+pub(crate) struct GeneratedMethodBody {
+    generator_name: String,
+    class: Entity,
+    definition: Rc<Directive>,
+    method: Entity,
+    parameter_names: Vec<String>,
+    common: Rc<FunctionCommon>,
+}
+
+impl GeneratedMethodBody {
+    /// Verifies the generated method bodies within their classes.
+    pub fn verify_all(verifier: &mut Subverifier) {
+        for body in std::mem::take(&mut verifier.generated_method_bodies) {
+            body.verify(verifier);
+        }
+    }
+
+    fn verify(&self, verifier: &mut Subverifier) {
+        let host = verifier.host.clone();
+        let Directive::ClassDefinition(defn) = self.definition.as_ref() else {
+            return;
+        };
+        let (Some(class_scope), Some(activation)) = (host.node_mapping().get(&defn.block), self.method.activation()) else {
+            return;
+        };
+        let Some(internal_ns) = class_scope.search_system_ns_in_scope_chain(SystemNamespaceKind::Internal) else {
+            return;
+        };
+        if !self.method.is_static() {
+            activation.set_this(Some(host.factory().create_this_object(&self.class)));
+        }
+
+        // Parameters are local variables of the activation
+        let signature = self.method.signature(&host);
+        let params: Vec<Rc<SemanticFunctionTypeParameter>> = signature.params().iter().collect();
+        for (name, param) in self.parameter_names.iter().zip(params.iter()) {
+            let qname = host.factory().create_qname(&internal_ns, name.clone());
+            let slot = host.factory().create_variable_slot(&qname, false, &param.static_type);
+            activation.properties(&host).set(qname, slot);
+        }
+
+        let location = self.class.location().unwrap_or(self.definition.location());
+        let partials = VerifierFunctionPartials::new(&activation, &location);
+        partials.set_params(Some(params));
+        partials.set_result_type(Some(signature.result_type()));
+        partials.set_signature(Some(signature));
+
+        let kscope = verifier.scope();
+        verifier.set_scope(&class_scope);
+        verifier.inherit_and_enter_scope(&activation);
+        let mut resolved = false;
+        for _ in 0..Verifier::MAX_CYCLES {
+            if FunctionCommonSubverifier::verify_function_definition_common(verifier, &self.common, &partials).is_ok() {
+                resolved = true;
+                break;
+            }
+        }
+        verifier.set_scope(&kscope);
+
+        if !resolved {
+            let message = format!("The body of '{}' could not be resolved", self.method.name().local_name());
+            verifier.add_verify_error(&location, WhackDiagnosticKind::MemberGeneratorError, diagarg![message, self.generator_name.clone()]);
+        }
+    }
+}
+
+/// Runs the registered member generators over the class definitions
+/// of the programs.
+pub(crate) struct MemberGeneratorRunner<'a> {
+    verifier: &'a mut Subverifier,
+    generators: &'a [Rc<dyn MemberGenerator>],
+}
+
+impl<'a> MemberGeneratorRunner<'a> {
+    pub fn run(verifier: &'a mut Subverifier, programs: &[Rc<Program>], generators: &'a [Rc<dyn MemberGenerator>]) {
+        let mut runner = Self { verifier, generators };
+        for program in programs.iter() {
+            runner.visit_program(program);
+        }
+    }
+
+    fn generate(&mut self, drtv: &Rc<Directive>, defn: &ClassDefinition) {
+        let Some(class) = self.verifier.host.node_mapping().get(drtv).filter(|c| c.is::<ClassType>()) else {
+            return;
+        };
+        for metadata in Attribute::find_metadata(&defn.attributes).iter() {
+            for generator in self.generators.iter().filter(|g| g.metadata_name() == metadata.name.0) {
+                let mut context = MemberGeneratorContext {
+                    verifier: &mut *self.verifier,
+                    generator_name: metadata.name.0.clone(),
+                    class: class.clone(),
+                    definition: drtv.clone(),
+                };
+                generator.generate(&mut context, metadata);
+            }
+        }
+        for member in defn.block.directives.iter() {
+            let attributes = match member.as_ref() {
+                Directive::VariableDefinition(defn) => &defn.attributes,
+                Directive::FunctionDefinition(defn) => &defn.attributes,
+                _ => continue,
+            };
+            for metadata in Attribute::find_metadata(attributes).iter() {
+                for generator in self.generators.iter().filter(|g| g.metadata_name() == metadata.name.0) {
+                    let mut context = MemberGeneratorContext {
+                        verifier: &mut *self.verifier,
+                        generator_name: metadata.name.0.clone(),
+                        class: class.clone(),
+                        definition: drtv.clone(),
+                    };
+                    generator.generate_member(&mut context, member, metadata);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visitor for MemberGeneratorRunner<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            self.generate(drtv, defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use crate::ns::*;
+use std::cell::RefCell;
+
+/// A member included into a class from a mixin.
+#[derive(Clone, Debug)]
+pub struct MixinInclusion {
+    pub class: Entity,
+    pub mixin: Entity,
+    /// The member of the mixin.
+    pub source: Entity,
+    /// The member defined in the class.
+    pub member: Entity,
+}
+
+/// Implements `[Mixin(type="T")]` meta-data at class definitions, which
+/// includes the public instance members of the class `T`. Registered on
+/// every `Verifier`; inclusions are listed by `Verifier::mixin_inclusions()`.
+///
+/// Members are resolved as follows: members defined by the class itself
+/// take precedence over included ones; a member included from several
+/// mixins, or shadowing an inherited member, is an error.
+///
+/// ```ignore
+/// [Mixin(type="com.example.Observable")]
+/// [Mixin(type="com.example.Disposable")]
+/// public class Model {}
+/// ```
+#[derive(Default)]
+pub struct MixinGenerator {
+    inclusions: RefCell<Vec<MixinInclusion>>,
+}
+
+impl MixinGenerator {
+    pub fn inclusions(&self) -> Vec<MixinInclusion> {
+        self.inclusions.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.inclusions.borrow_mut().clear();
+    }
+
+    fn type_name(metadata: &Metadata) -> Option<(String, Location)> {
+        let entries = metadata.entries.as_ref()?;
+        let entry = entries.iter().find(|e| e.key.as_ref().map(|(k, _)| k == "type").unwrap_or(false))
+            .or_else(|| entries.iter().find(|e| e.key.is_none()).filter(|_| entries.len() == 1))?;
+        Some(match entry.value.as_ref() {
+            MetadataValue::String(val) => val.clone(),
+            MetadataValue::IdentifierString(val) => val.clone(),
+        })
+    }
+
+    fn resolve(context: &MemberGeneratorContext, name: &str) -> Option<Entity> {
+        let host = context.host();
+        if let Some((pckg_name, local_name)) = name.rsplit_once('.') {
+            let pckg = host.factory().create_package(pckg_name.split('.').collect::<Vec<_>>());
+            let qname = host.factory().create_qname(&pckg.public_ns()?, local_name.to_owned());
+            return pckg.properties(&host).get(&qname);
+        }
+        let Directive::ClassDefinition(defn) = context.definition().as_ref() else {
+            return None;
+        };
+        let scope = host.node_mapping().get(&defn.block)?;
+        scope.lookup_in_scope_chain(&host, None, &PropertyLookupKey::LocalName(name.to_owned())).ok()??.expect_type().ok()
+    }
+
+    fn public_members(host: &Database, mixin: &Entity) -> Vec<Entity> {
+        mixin.prototype(host).borrow().iter()
+            .filter(|(name, _)| name.namespace().is_public_ns())
+            .map(|(_, member)| member.clone())
+            .collect()
+    }
+}
+
+impl MemberGenerator for MixinGenerator {
+    fn metadata_name(&self) -> String {
+        "Mixin".into()
+    }
+
+    fn generate(&self, context: &mut MemberGeneratorContext, metadata: &Rc<Metadata>) {
+        let Some((type_name, location)) = Self::type_name(metadata) else {
+            context.report_error(&metadata.location, "[Mixin] must specify a type");
+            return;
+        };
+        let host = context.host();
+        let Some(mixin) = Self::resolve(context, &type_name).filter(|t| t.is::<ClassType>()) else {
+            context.add_verify_error(&location, WhackDiagnosticKind::MixinTypeNotFound, diagarg![type_name]);
+            return;
+        };
+        let class = context.class();
+        for source in Self::public_members(&host, &mixin) {
+            let name = source.name().local_name();
+            let previous = self.inclusions.borrow().iter()
+                .find(|i| i.class == class && i.member.name().local_name() == name)
+                .map(|i| i.mixin.clone());
+            if let Some(previous) = previous {
+                context.add_verify_error(&location, WhackDiagnosticKind::MixinMemberConflict, diagarg![name, previous, mixin.clone()]);
+                continue;
+            }
+            // Members of the class itself take precedence
+            if context.member(&name, false).is_some() {
+                continue;
+            }
+            if let Some(base) = class.extends_class(&host) {
+                if let Ok(Some(inherited)) = base.prototype(&host).get_in_any_public_ns(&name) {
+                    context.add_verify_error(&location, WhackDiagnosticKind::MixinMemberConflictsWithInherited, diagarg![name, mixin.clone(), inherited.parent().unwrap_or(base.clone())]);
+                    continue;
+                }
+            }
+            if let Some(member) = context.include_member(&source) {
+                self.inclusions.borrow_mut().push(MixinInclusion { class: class.clone(), mixin: mixin.clone(), source, member });
+            }
+        }
+    }
+}
\ No newline at end of file
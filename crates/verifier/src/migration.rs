@@ -0,0 +1,8 @@
+mod migration_analyzer;
+pub use migration_analyzer::*;
+
+mod codemods;
+pub use codemods::*;
+
+mod codemod_runner;
+pub use codemod_runner::*;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Result of `CodemodRunner::run()`.
+#[derive(Clone, Default, Debug)]
+pub struct CodemodReport {
+    /// Rewritten texts of the files kept, keyed by path.
+    pub files: BTreeMap<String, String>,
+    /// Number of edits per file and codemod name.
+    pub edit_counts: BTreeMap<String, BTreeMap<String, usize>>,
+    /// Files left unchanged because their edits overlapped or
+    /// introduced errors, with the errors introduced.
+    pub rejected: BTreeMap<String, Vec<SpeculativeDiagnostic>>,
+}
+
+impl CodemodReport {
+    pub fn format_human(&self) -> String {
+        let mut r = String::new();
+        for (file_path, counts) in self.edit_counts.iter() {
+            if !self.files.contains_key(file_path) {
+                continue;
+            }
+            let counts = counts.iter().map(|(name, count)| format!("{name}: {count}")).collect::<Vec<_>>();
+            r.push_str(&format!("{file_path}: {}\n", counts.join(", ")));
+        }
+        for (file_path, diagnostics) in self.rejected.iter() {
+            r.push_str(&format!("{file_path}: rejected\n"));
+            for d in diagnostics.iter() {
+                r.push_str(&format!("  {}: {}\n", d.line, d.message));
+            }
+        }
+        if r.is_empty() {
+            r.push_str("No changes.\n");
+        }
+        r
+    }
+}
+
+/// Applies codemods over the sources of a project, verifying the result
+/// before keeping it.
+///
+/// ```ignore
+/// let codemods = vec![builtin_codemod("int-division").unwrap()];
+/// let report = CodemodRunner::run(&compiler_options, &sources, &codemods);
+/// for (path, text) in report.files.iter() {
+///     std::fs::write(path, text)?;
+/// }
+/// ```
+///
+/// The edits of each file are verified together through
+/// `SpeculativeVerification`. A file whose edits introduce errors is
+/// left unchanged, and the remaining files are verified again.
+pub struct CodemodRunner;
+
+impl CodemodRunner {
+    pub fn run(compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider, codemods: &[Rc<dyn Codemod>]) -> CodemodReport {
+        let host = Rc::new(Database::new(Default::default()));
+        let baseline = Compilation::run(&host, compiler_options, sources);
+        let mut report = CodemodReport::default();
+        let mut edits = BTreeMap::<String, SourceEditBatch>::new();
+        for program in baseline.programs.iter() {
+            let file_path = program.location.compilation_unit().file_path().unwrap_or_default();
+            let Some(text) = sources.read_to_string(&file_path) else {
+                continue;
+            };
+            let mut batch = SourceEditBatch::new();
+            for codemod in codemods.iter() {
+                let list = codemod.edits(&host, program);
+                if !list.is_empty() {
+                    report.edit_counts.entry(file_path.clone()).or_default().insert(codemod.name(), list.len());
+                }
+                batch.extend(list);
+            }
+            if batch.is_empty() {
+                continue;
+            }
+            if batch.apply(&text).is_err() {
+                report.rejected.insert(file_path, vec![]);
+                continue;
+            }
+            edits.insert(file_path, batch);
+        }
+
+        let speculation = SpeculativeVerification::new();
+        while !edits.is_empty() {
+            let Ok(delta) = speculation.verify(compiler_options, sources, &edits, Some(&baseline)) else {
+                break;
+            };
+            let introduced: Vec<SpeculativeDiagnostic> = delta.introduced.into_iter().filter(|d| !d.is_warning).collect();
+            if introduced.is_empty() {
+                break;
+            }
+            // Errors only outside the edited files are blamed on every
+            // edited file.
+            let blames_edited_files = introduced.iter().any(|d| edits.contains_key(&d.file_path));
+            let blamed: Vec<String> = edits.keys()
+                .filter(|file_path| !blames_edited_files || introduced.iter().any(|d| d.file_path == **file_path))
+                .cloned()
+                .collect();
+            for file_path in blamed {
+                let diagnostics = introduced.iter().filter(|d| !blames_edited_files || d.file_path == file_path).cloned().collect();
+                edits.remove(&file_path);
+                report.rejected.insert(file_path, diagnostics);
+            }
+        }
+
+        for (file_path, batch) in edits.iter() {
+            let text = sources.read_to_string(file_path).unwrap_or_default();
+            if let Ok(text) = batch.apply(&text) {
+                report.files.insert(file_path.clone(), text);
+            }
+        }
+        report
+    }
+}
\ No newline at end of file
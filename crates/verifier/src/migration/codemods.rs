@@ -0,0 +1,382 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// A transform of verified programs into source edits, run by
+/// `CodemodRunner`.
+pub trait Codemod {
+    /// Name of the codemod, as given to `whack codemod --transform`.
+    fn name(&self) -> String;
+
+    fn edits(&self, host: &Database, program: &Rc<Program>) -> Vec<SourceEdit>;
+}
+
+/// Codemods shipped with the compiler, by name.
+pub fn builtin_codemod(name: &str) -> Option<Rc<dyn Codemod>> {
+    match name {
+        "array-to-vector" => Some(Rc::new(ArrayToVectorCodemod)),
+        "int-division" => Some(Rc::new(IntDivisionCodemod)),
+        _ => None,
+    }
+}
+
+/// Converts variables typed `Array` into `Vector.<T>` where the element
+/// type is given by a comment after the type annotation, as
+/// `var names:Array /* of String */ = [];`, or by an
+/// `[ArrayElementType("T")]` meta-data.
+///
+/// A variable is converted only if its initializer is absent, `new Array()`
+/// or an array literal whose elements are all of the element type.
+pub struct ArrayToVectorCodemod;
+
+impl ArrayToVectorCodemod {
+    /// Element type given by the comment following a type annotation,
+    /// with the offset after the comment.
+    fn comment_element_type(text: &str, annotation: &Location) -> Option<(String, usize)> {
+        let after = text.get(annotation.last_offset()..)?;
+        let trimmed = after.trim_start();
+        let comment = trimmed.strip_prefix("/*")?;
+        let end = comment.find("*/")?;
+        let content = comment[..end].trim();
+        let name = content.strip_prefix("of ").unwrap_or(content).trim();
+        let is_name = !name.is_empty() && name.split('.').all(|part| {
+            part.chars().next().map(|ch| ch.is_alphabetic() || ch == '_' || ch == '$').unwrap_or(false)
+                && part.chars().all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+        });
+        is_name.then(|| (name.to_owned(), annotation.last_offset() + (after.len() - trimmed.len()) + 2 + end + 2))
+    }
+
+    fn metadata_element_type(attributes: &[Attribute]) -> Option<String> {
+        let m = Attribute::find_metadata(attributes).into_iter().find(|m| m.name.0 == "ArrayElementType")?;
+        let entry = m.entries.as_ref()?.first()?.clone();
+        Some(match entry.value.as_ref() {
+            MetadataValue::String(val) => val.0.clone(),
+            MetadataValue::IdentifierString(val) => val.0.clone(),
+        })
+    }
+
+    /// Resolves the element type as written, either fully qualified or
+    /// from the scope of the variable.
+    fn resolve_type(host: &Database, scope: Option<&Entity>, name: &str) -> Option<Entity> {
+        if let Some((pckg_name, local_name)) = name.rsplit_once('.') {
+            let pckg = host.factory().create_package(pckg_name.split('.').collect::<Vec<_>>());
+            let qname = host.factory().create_qname(&pckg.public_ns()?, local_name.to_owned());
+            return pckg.properties(host).get(&qname).filter(|t| t.is_class_type_possibly_after_sub());
+        }
+        scope?.lookup_in_scope_chain(host, None, &PropertyLookupKey::LocalName(name.to_owned())).ok()??.expect_type().ok()
+    }
+
+    /// Whether an element is provably of the element type. Numeric
+    /// literals are only of `int` and `uint` if they are integers in
+    /// their range.
+    fn is_of_type(host: &Database, exp: &Rc<Expression>, element_type: &Entity) -> bool {
+        if let Expression::NumericLiteral(literal) = exp.as_ref() {
+            let Ok(value) = literal.parse_double(false) else {
+                return false;
+            };
+            return if element_type == &host.int_type() {
+                value.fract() == 0.0 && value >= i32::MIN as f64 && value <= i32::MAX as f64
+            } else if element_type == &host.uint_type() {
+                value.fract() == 0.0 && value >= 0.0 && value <= u32::MAX as f64
+            } else {
+                element_type == &host.number_type() || element_type == &host.float_type()
+            };
+        }
+        let Some(val) = host.node_mapping().get(exp) else {
+            return false;
+        };
+        &val.static_type(host).escape_of_nullable_or_non_nullable() == element_type
+    }
+
+    fn is_array_annotation(exp: &Rc<Expression>) -> bool {
+        matches!(exp.as_ref(), Expression::QualifiedIdentifier(id) if id.qualifier.is_none() && matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "Array"))
+    }
+}
+
+impl Codemod for ArrayToVectorCodemod {
+    fn name(&self) -> String {
+        "array-to-vector".into()
+    }
+
+    fn edits(&self, host: &Database, program: &Rc<Program>) -> Vec<SourceEdit> {
+        let text = program.location.compilation_unit().text();
+        let mut collector = VariableDefinitionCollector { host, scopes: vec![], definitions: vec![] };
+        collector.visit_program(program);
+        let mut edits = vec![];
+        for (defn, scope) in collector.definitions.iter() {
+            let Directive::VariableDefinition(defn) = defn.as_ref() else {
+                continue;
+            };
+            let metadata_type = Self::metadata_element_type(&defn.attributes);
+            for binding in defn.bindings.iter() {
+                let Some(annotation) = binding.destructuring.type_annotation.as_ref().filter(|t| Self::is_array_annotation(t)) else {
+                    continue;
+                };
+                let (element_type, annotation_end) = match Self::comment_element_type(&text, &annotation.location()) {
+                    Some(r) => r,
+                    None => match metadata_type.clone() {
+                        Some(t) => (t, annotation.location().last_offset()),
+                        None => continue,
+                    },
+                };
+                let initializer_edit = match binding.initializer.as_ref() {
+                    None => None,
+                    Some(init) => match init.as_ref() {
+                        Expression::ArrayLiteral(lit) => {
+                            let Some(resolved_type) = Self::resolve_type(host, scope.as_ref(), &element_type) else {
+                                continue;
+                            };
+                            let provable = lit.elements.iter().all(|elem| match elem {
+                                Element::Expression(exp) => Self::is_of_type(host, exp, &resolved_type),
+                                _ => false,
+                            });
+                            if !provable {
+                                continue;
+                            }
+                            Some(SourceEdit::insert_before(&init.location(), &format!("new <{element_type}>")))
+                        },
+                        Expression::New(e) if Self::is_array_annotation(&e.base) && e.arguments.as_ref().map(|a| a.is_empty()).unwrap_or(true) => {
+                            Some(SourceEdit::replace(&init.location(), &format!("new <{element_type}>[]")))
+                        },
+                        _ => continue,
+                    },
+                };
+                edits.push(SourceEdit::new(annotation.location().first_offset(), annotation_end, &format!("Vector.<{element_type}>")));
+                edits.extend(initializer_edit);
+            }
+        }
+        edits
+    }
+}
+
+/// Collects variable definitions with the innermost scope of the
+/// program, package or class enclosing them.
+struct VariableDefinitionCollector<'a> {
+    host: &'a Database,
+    scopes: Vec<Option<Entity>>,
+    definitions: Vec<(Rc<Directive>, Option<Entity>)>,
+}
+
+impl<'a> Visitor for VariableDefinitionCollector<'a> {
+    fn visit_program(&mut self, program: &Rc<Program>) {
+        self.scopes.push(self.host.node_mapping().get(program));
+        for pckgdefn in program.packages.iter() {
+            self.scopes.push(self.host.node_mapping().get(&pckgdefn.block));
+            self.visit_block(&pckgdefn.block);
+            self.scopes.pop();
+        }
+        for drtv in program.directives.iter() {
+            self.visit_directive(drtv);
+        }
+        self.scopes.pop();
+    }
+
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::VariableDefinition(_) => {
+                self.definitions.push((drtv.clone(), self.scopes.iter().rev().flatten().next().cloned()));
+                walk_directive(self, drtv);
+            },
+            Directive::ClassDefinition(defn) => {
+                self.scopes.push(self.host.node_mapping().get(&defn.block));
+                walk_directive(self, drtv);
+                self.scopes.pop();
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+}
+
+/// Replaces the `int(x / y)` idiom by `(x / y | 0)`, which truncates
+/// the same way without calling the `int` class.
+pub struct IntDivisionCodemod;
+
+impl Codemod for IntDivisionCodemod {
+    fn name(&self) -> String {
+        "int-division".into()
+    }
+
+    fn edits(&self, host: &Database, program: &Rc<Program>) -> Vec<SourceEdit> {
+        let text = program.location.compilation_unit().text();
+        let mut collector = IntDivisionCollector { host, text: &text, edits: vec![] };
+        collector.visit_program(program);
+        collector.edits
+    }
+}
+
+struct IntDivisionCollector<'a> {
+    host: &'a Database,
+    text: &'a str,
+    edits: Vec<SourceEdit>,
+}
+
+impl<'a> IntDivisionCollector<'a> {
+    fn division(exp: &Rc<Expression>) -> Option<&BinaryExpression> {
+        match exp.as_ref() {
+            Expression::Paren(e) => Self::division(&e.expression),
+            Expression::Binary(e) if e.operator == Operator::Divide => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Whether the callee is the `int` class rather than a local of
+    /// the same name.
+    fn is_int_class(&self, base: &Rc<Expression>) -> bool {
+        let Expression::QualifiedIdentifier(id) = base.as_ref() else {
+            return false;
+        };
+        if id.qualifier.is_some() || !matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "int") {
+            return false;
+        }
+        let Some(val) = self.host.node_mapping().get(base) else {
+            return false;
+        };
+        val == self.host.int_type() || val.property() == self.host.int_type()
+    }
+}
+
+impl<'a> Visitor for IntDivisionCollector<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            if call.arguments.len() == 1 && Self::division(&call.arguments[0]).is_some() && self.is_int_class(&call.base) {
+                let argument = call.arguments[0].location();
+                if let Some(argument) = self.text.get(argument.first_offset()..argument.last_offset()) {
+                    self.edits.push(SourceEdit::replace(&exp.location(), &format!("({argument} | 0)")));
+                    // Nested occurrences would overlap this edit.
+                    return;
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
+
+/// Rewrites references to deprecated definitions and static members
+/// following a mapping file, one `old = replacement` pair per line, as
+/// `flash.system.System.setClipboard = flash.desktop.Clipboard.generalClipboard.setData`.
+///
+/// The left side is the fully qualified name of the definition or
+/// member; references to it are replaced by the right side as is, so it
+/// is normally fully qualified too, and the package-level definition it
+/// starts with is imported unless already imported. Lines starting with
+/// `#` are comments.
+pub struct DeprecatedApiCodemod {
+    pub mapping: BTreeMap<String, String>,
+}
+
+impl DeprecatedApiCodemod {
+    /// Parses a mapping file, failing with the one-based number of the
+    /// first malformed line.
+    pub fn parse(text: &str) -> Result<Self, usize> {
+        let mut mapping = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((old, new)) = line.split_once('=') else {
+                return Err(i + 1);
+            };
+            let (old, new) = (old.trim(), new.trim());
+            if old.is_empty() || new.is_empty() {
+                return Err(i + 1);
+            }
+            mapping.insert(old.to_owned(), new.to_owned());
+        }
+        Ok(Self { mapping })
+    }
+
+    /// Fully qualified name of a definition or of a member, as
+    /// `flash.system.System.setClipboard`.
+    fn qualified_name(entity: &Entity) -> String {
+        match entity.parent() {
+            Some(parent) if parent.is::<ClassType>() || parent.is::<InterfaceType>() || parent.is::<EnumType>() => {
+                format!("{}.{}", parent.to_string(), entity.name().local_name())
+            },
+            _ => entity.to_string(),
+        }
+    }
+
+    /// Fully qualified name of the package-level definition a
+    /// replacement starts with, if it is not in the top-level package,
+    /// as `flash.desktop.Clipboard` for
+    /// `flash.desktop.Clipboard.generalClipboard.setData`.
+    fn imported_definition(host: &Database, replacement: &str) -> Option<String> {
+        let parts: Vec<&str> = replacement.split('.').collect();
+        (1..parts.len()).rev().find_map(|i| {
+            let pckg = host.factory().create_package(parts[..i].to_vec());
+            let qname = host.factory().create_qname(&pckg.public_ns()?, parts[i].to_owned());
+            pckg.properties(host).get(&qname).map(|_| parts[..=i].join("."))
+        })
+    }
+
+    /// Inserts an import before the first directive of a directive list
+    /// unless the definition or its package is already imported.
+    fn import_edit(text: &str, directives: &[Rc<Directive>], name: &str) -> Option<SourceEdit> {
+        let (pckg_name, _) = name.rsplit_once('.')?;
+        let wildcard = format!("{pckg_name}.*");
+        let imported = directives.iter().any(|drtv| match drtv.as_ref() {
+            Directive::ImportDirective(impdrtv) if impdrtv.alias.is_none() => {
+                let import_name = ImportOrganizer::import_name(impdrtv);
+                import_name == name || import_name == wildcard
+            },
+            _ => false,
+        });
+        if imported {
+            return None;
+        }
+        let offset = directives.first()?.location().first_offset();
+        let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let indent: String = text[line_start..offset].chars().take_while(|ch| ch.is_whitespace()).collect();
+        Some(SourceEdit::new(line_start, line_start, &format!("{indent}import {name};\n")))
+    }
+}
+
+impl Codemod for DeprecatedApiCodemod {
+    fn name(&self) -> String {
+        "deprecated-api".into()
+    }
+
+    fn edits(&self, host: &Database, program: &Rc<Program>) -> Vec<SourceEdit> {
+        let text = program.location.compilation_unit().text();
+        let mut edits = vec![];
+        // Package blocks, then the top-level directives
+        let lists = program.packages.iter().map(|pckgdefn| &pckgdefn.block.directives).chain([&program.directives]);
+        for directives in lists {
+            let mut collector = DeprecatedReferenceCollector { host, codemod: self, edits: vec![], imports: vec![] };
+            for drtv in directives.iter() {
+                collector.visit_directive(drtv);
+            }
+            edits.extend(collector.imports.iter().filter_map(|name| Self::import_edit(&text, directives, name)));
+            edits.extend(collector.edits);
+        }
+        edits
+    }
+}
+
+struct DeprecatedReferenceCollector<'a> {
+    host: &'a Database,
+    codemod: &'a DeprecatedApiCodemod,
+    edits: Vec<SourceEdit>,
+    /// Definitions the replacements refer to.
+    imports: Vec<String>,
+}
+
+impl<'a> Visitor for DeprecatedReferenceCollector<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if matches!(exp.as_ref(), Expression::QualifiedIdentifier(_) | Expression::Member(_)) {
+            if let Some(definition) = referenced_entity(self.host, exp) {
+                let name = DeprecatedApiCodemod::qualified_name(&definition);
+                if let Some(replacement) = self.codemod.mapping.get(&name) {
+                    if let Some(import) = DeprecatedApiCodemod::imported_definition(self.host, replacement) {
+                        if !self.imports.contains(&import) {
+                            self.imports.push(import);
+                        }
+                    }
+                    self.edits.push(SourceEdit::replace(&exp.location(), replacement));
+                    return;
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
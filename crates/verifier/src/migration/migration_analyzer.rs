@@ -0,0 +1,203 @@
+use crate::ns::*;
+
+/// Meta-data understood by the Whack compiler besides that consumed by
+/// the verifier and stripped by codegen, which is listed by
+/// `CodegenMetadataRetention::COMPILER_METADATA`.
+const FRAMEWORK_METADATA: [&'static str; 14] = [
+    "After", "ArrayElementType", "Before", "DefaultProperty", "HostComponent", "KeepMetadata", "Mixin",
+    "RemoteClass", "SkinPart", "SkinState", "States", "Test", "Transient", "Whack::External",
+];
+
+/// Whether meta-data is understood by the Whack compiler.
+pub fn is_supported_metadata(name: &str) -> bool {
+    CodegenMetadataRetention::COMPILER_METADATA.contains(&name) || FRAMEWORK_METADATA.contains(&name)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MigrationIssueKind {
+    WithStatement,
+    DefaultXmlNamespaceStatement,
+    UnsupportedMetadata(String),
+    ProxySubclassWithoutOverrides,
+    /// A class satisfies an interface without implementing it.
+    MissingImplements(String),
+}
+
+impl MigrationIssueKind {
+    pub fn description(&self) -> String {
+        match self {
+            Self::WithStatement => "The with statement is not supported".into(),
+            Self::DefaultXmlNamespaceStatement => "The default XML namespace statement is not supported".into(),
+            Self::UnsupportedMetadata(name) => format!("The [{name}] meta-data is not supported"),
+            Self::ProxySubclassWithoutOverrides => "Proxy subclass does not override any flash_proxy method".into(),
+            Self::MissingImplements(name) => format!("The class is used as {name} without implementing it"),
+        }
+    }
+
+    pub fn suggestion(&self) -> String {
+        match self {
+            Self::WithStatement => "Qualify each member access with the object explicitly.".into(),
+            Self::DefaultXmlNamespaceStatement => "Qualify XML names with the namespace explicitly.".into(),
+            Self::UnsupportedMetadata(_) => "Remove the meta-data or replace it by a supported equivalent.".into(),
+            Self::ProxySubclassWithoutOverrides => "Override the flash_proxy methods the class relies on, such as getProperty() and callProperty().".into(),
+            Self::MissingImplements(name) => format!("Add {name} to the implements clause of the class."),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MigrationIssue {
+    pub location: Location,
+    pub kind: MigrationIssueKind,
+}
+
+/// Result of `MigrationAnalyzer::analyze()`.
+#[derive(Clone, Default)]
+pub struct MigrationReport {
+    pub issues: Vec<MigrationIssue>,
+}
+
+impl MigrationReport {
+    /// Number of issues per file, sorted by descending count.
+    pub fn per_file_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for issue in self.issues.iter() {
+            *counts.entry(Self::file_path(&issue.location)).or_insert(0) += 1;
+        }
+        let mut r: Vec<(String, usize)> = counts.into_iter().collect();
+        r.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        r
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = String::new();
+        for issue in self.issues.iter() {
+            r.push_str(&format!("{}:{}: {}. {}\n",
+                Self::file_path(&issue.location),
+                issue.location.first_line_number(),
+                issue.kind.description(),
+                issue.kind.suggestion()));
+        }
+        let counts = self.per_file_counts();
+        if !counts.is_empty() {
+            r.push_str("Issues per file:\n");
+            for (path, count) in counts.iter() {
+                r.push_str(&format!("  {:>6}  {}\n", count, path));
+            }
+        }
+        r
+    }
+
+    fn file_path(location: &Location) -> String {
+        location.compilation_unit().file_path().unwrap_or("<unknown>".into())
+    }
+}
+
+/// Scans legacy ActionScript 3 programs for constructs that are unsupported
+/// or behave differently under Whack.
+///
+/// The analysis is syntactic and does not require verifying the programs.
+pub struct MigrationAnalyzer {
+    issues: Vec<MigrationIssue>,
+}
+
+impl MigrationAnalyzer {
+    pub fn analyze(programs: &[Rc<Program>]) -> MigrationReport {
+        let mut analyzer = Self { issues: vec![] };
+        for program in programs.iter() {
+            analyzer.visit_program(program);
+        }
+        MigrationReport { issues: analyzer.issues }
+    }
+
+    /// Reports classes of verified programs that satisfy an interface,
+    /// given by fully qualified name, without implementing it, as code
+    /// relying on duck typing does.
+    pub fn missing_implements(host: &Database, programs: &[Rc<Program>], interface_names: &[String]) -> MigrationReport {
+        let conformance = StructuralConformance(host);
+        let interfaces = interface_names.iter().filter_map(|name| conformance.resolve_interface(name)).collect::<Vec<_>>();
+        let mut collector = ClassCollector(vec![]);
+        for program in programs.iter() {
+            collector.visit_program(program);
+        }
+        let mut issues = vec![];
+        for (location, drtv) in collector.0 {
+            let Some(class) = host.node_mapping().get(&drtv).filter(|c| c.is::<ClassType>()) else {
+                continue;
+            };
+            for interface in conformance.missing_implements(&class, &interfaces).unwrap_or_default() {
+                issues.push(MigrationIssue { location: location.clone(), kind: MigrationIssueKind::MissingImplements(interface.to_string()) });
+            }
+        }
+        MigrationReport { issues }
+    }
+
+    fn check_metadata(&mut self, attributes: &Vec<Attribute>) {
+        for m in Attribute::find_metadata(attributes).iter() {
+            if !is_supported_metadata(&m.name.0) {
+                self.issues.push(MigrationIssue {
+                    location: m.name.1.clone(),
+                    kind: MigrationIssueKind::UnsupportedMetadata(m.name.0.clone()),
+                });
+            }
+        }
+    }
+
+    fn extends_proxy(defn: &ClassDefinition) -> bool {
+        match defn.extends_clause.as_ref().map(|exp| exp.as_ref()) {
+            Some(Expression::QualifiedIdentifier(id)) => {
+                matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "Proxy")
+            },
+            Some(Expression::Member(e)) => {
+                matches!(&e.identifier.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "Proxy")
+            },
+            _ => false,
+        }
+    }
+
+    fn has_override(block: &Block) -> bool {
+        block.directives.iter().any(|drtv| {
+            if let Directive::FunctionDefinition(defn) = drtv.as_ref() {
+                Attribute::find_override(&defn.attributes).is_some()
+            } else {
+                false
+            }
+        })
+    }
+}
+
+impl Visitor for MigrationAnalyzer {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::WithStatement(wstmt) => {
+                self.issues.push(MigrationIssue { location: wstmt.location.clone(), kind: MigrationIssueKind::WithStatement });
+            },
+            Directive::DefaultXmlNamespaceStatement(dxns) => {
+                self.issues.push(MigrationIssue { location: dxns.location.clone(), kind: MigrationIssueKind::DefaultXmlNamespaceStatement });
+            },
+            Directive::ClassDefinition(defn) => {
+                self.check_metadata(&defn.attributes);
+                if Self::extends_proxy(defn) && !Self::has_override(&defn.block) {
+                    self.issues.push(MigrationIssue { location: defn.name.1.clone(), kind: MigrationIssueKind::ProxySubclassWithoutOverrides });
+                }
+            },
+            Directive::VariableDefinition(defn) => self.check_metadata(&defn.attributes),
+            Directive::FunctionDefinition(defn) => self.check_metadata(&defn.attributes),
+            Directive::EnumDefinition(defn) => self.check_metadata(&defn.attributes),
+            Directive::InterfaceDefinition(defn) => self.check_metadata(&defn.attributes),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+}
+
+struct ClassCollector(Vec<(Location, Rc<Directive>)>);
+
+impl Visitor for ClassCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            self.0.push((defn.name.1.clone(), drtv.clone()));
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
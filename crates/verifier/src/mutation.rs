@@ -0,0 +1,5 @@
+mod mutation_point;
+pub use mutation_point::*;
+
+mod mutation_collector;
+pub use mutation_collector::*;
\ No newline at end of file
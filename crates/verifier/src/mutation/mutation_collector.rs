@@ -0,0 +1,128 @@
+use crate::ns::*;
+
+/// Enumerates mutation points of verified programs, for mutation testing.
+///
+/// Static types resolved by the verifier are used to skip mutations that
+/// would not compile, such as swapping `+` over strings for `-`.
+///
+/// ```ignore
+/// let output = Compilation::run(&host, &compiler_options, &sources);
+/// for program in output.programs.iter() {
+///     for point in MutationCollector::collect(&host, program) {
+///         let mutant = point.compile(&compiler_options, &sources)?;
+///         if !mutant.invalidated {
+///             run_tests(&mutant);
+///         }
+///     }
+/// }
+/// ```
+pub struct MutationCollector {
+    host: Rc<Database>,
+    text: String,
+    file_path: String,
+    points: Vec<MutationPoint>,
+}
+
+impl MutationCollector {
+    pub fn collect(host: &Rc<Database>, program: &Rc<Program>) -> Vec<MutationPoint> {
+        let cu = program.location.compilation_unit();
+        let mut collector = Self {
+            host: host.clone(),
+            text: cu.text().to_owned(),
+            file_path: cu.file_path().unwrap_or_default(),
+            points: vec![],
+        };
+        collector.visit_program(program);
+        collector.points
+    }
+
+    fn operator_mutations(operator: Operator) -> &'static [(MutationKind, &'static str, &'static str)] {
+        use MutationKind::*;
+        match operator {
+            Operator::Add => &[(OperatorSwap, "+", "-")],
+            Operator::Subtract => &[(OperatorSwap, "-", "+")],
+            Operator::Multiply => &[(OperatorSwap, "*", "/")],
+            Operator::Divide => &[(OperatorSwap, "/", "*")],
+            Operator::Remainder => &[(OperatorSwap, "%", "*")],
+            Operator::LogicalAnd => &[(OperatorSwap, "&&", "||")],
+            Operator::LogicalOr => &[(OperatorSwap, "||", "&&")],
+            Operator::Equals => &[(OperatorSwap, "==", "!=")],
+            Operator::NotEquals => &[(OperatorSwap, "!=", "==")],
+            Operator::StrictEquals => &[(OperatorSwap, "===", "!==")],
+            Operator::StrictNotEquals => &[(OperatorSwap, "!==", "===")],
+            Operator::Lt => &[(BoundaryChange, "<", "<="), (OperatorSwap, "<", ">=")],
+            Operator::Le => &[(BoundaryChange, "<=", "<"), (OperatorSwap, "<=", ">")],
+            Operator::Gt => &[(BoundaryChange, ">", ">="), (OperatorSwap, ">", "<=")],
+            Operator::Ge => &[(BoundaryChange, ">=", ">"), (OperatorSwap, ">=", "<")],
+            _ => &[],
+        }
+    }
+
+    fn is_numeric(&self, exp: &Rc<Expression>) -> bool {
+        let Some(v) = self.host.node_mapping().get(exp) else {
+            return false;
+        };
+        let t = v.static_type(&self.host).escape_of_nullable_or_non_nullable();
+        self.host.numeric_types().map(|types| types.contains(&t)).unwrap_or(false)
+    }
+
+    /// Finds the byte offset of an operator between two operands,
+    /// skipping parentheses and whitespace around them.
+    fn operator_offset(&self, left: &Rc<Expression>, right: &Rc<Expression>, operator: &str) -> Option<usize> {
+        let start = left.location().last_offset();
+        let end = right.location().first_offset();
+        let between = self.text.get(start..end)?;
+        let trimmed = between.trim_start_matches(|ch: char| ch == ')' || ch.is_whitespace());
+        trimmed.starts_with(operator).then(|| start + between.len() - trimmed.len())
+    }
+
+    fn collect_binary(&mut self, exp: &BinaryExpression) {
+        // `+` over strings concatenates; other swaps would not compile.
+        if matches!(exp.operator, Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide | Operator::Remainder)
+            && !(self.is_numeric(&exp.left) && self.is_numeric(&exp.right)) {
+            return;
+        }
+        for (kind, operator, replacement) in Self::operator_mutations(exp.operator).iter() {
+            let Some(offset) = self.operator_offset(&exp.left, &exp.right, operator) else {
+                continue;
+            };
+            self.points.push(MutationPoint {
+                kind: *kind,
+                file_path: self.file_path.clone(),
+                line: exp.location.first_line_number(),
+                edit: SourceEdit::new(offset, offset + operator.len(), replacement),
+                description: format!("{operator} to {replacement}"),
+            });
+        }
+    }
+}
+
+impl Visitor for MutationCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ExpressionStatement(estmt) = drtv.as_ref() {
+            // Deleting a lone literal or identifier changes nothing observable
+            let has_effect = match estmt.expression.as_ref() {
+                Expression::Call(_) | Expression::Assignment(_) => true,
+                Expression::Unary(e) => matches!(e.operator, Operator::PreIncrement | Operator::PreDecrement | Operator::PostIncrement | Operator::PostDecrement | Operator::Delete),
+                _ => false,
+            };
+            if has_effect {
+                self.points.push(MutationPoint {
+                    kind: MutationKind::StatementDeletion,
+                    file_path: self.file_path.clone(),
+                    line: estmt.location.first_line_number(),
+                    edit: SourceEdit::replace(&estmt.expression.location(), "{}"),
+                    description: "delete statement".into(),
+                });
+            }
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Binary(binary) = exp.as_ref() {
+            self.collect_binary(binary);
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
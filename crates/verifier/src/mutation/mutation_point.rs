@@ -0,0 +1,57 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MutationKind {
+    /// Replaces an operator by a related one, such as `+` by `-`.
+    OperatorSwap,
+    /// Replaces a relational operator by its inclusive or exclusive
+    /// counterpart, such as `<` by `<=`.
+    BoundaryChange,
+    /// Removes an expression statement.
+    StatementDeletion,
+}
+
+impl MutationKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::OperatorSwap => "operator-swap",
+            Self::BoundaryChange => "boundary-change",
+            Self::StatementDeletion => "statement-deletion",
+        }
+    }
+}
+
+/// A single mutation of a source file.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MutationPoint {
+    pub kind: MutationKind,
+    pub file_path: String,
+    /// One-based line number.
+    pub line: usize,
+    pub edit: SourceEdit,
+    /// Such as `"< to <="`.
+    pub description: String,
+}
+
+impl MutationPoint {
+    /// Returns the sources with this mutation applied to its file.
+    pub fn apply(&self, sources: &MemorySourceProvider) -> Result<MemorySourceProvider, SourceEditError> {
+        let mut mutated = sources.clone();
+        if let Some(text) = sources.files.get(&self.file_path) {
+            let mut batch = SourceEditBatch::new();
+            batch.push(self.edit.clone());
+            mutated.insert(&self.file_path, &batch.apply(text)?);
+        }
+        Ok(mutated)
+    }
+
+    /// Compiles the sources with this mutation applied, in a fresh database.
+    ///
+    /// An invalidated output means the mutant does not compile and
+    /// should not be counted as surviving.
+    pub fn compile(&self, compiler_options: &Rc<CompilerOptions>, sources: &MemorySourceProvider) -> Result<CompilationOutput, SourceEditError> {
+        let mutated = self.apply(sources)?;
+        let host = Rc::new(Database::new(Default::default()));
+        Ok(Compilation::run(&host, compiler_options, &mutated))
+    }
+}
\ No newline at end of file
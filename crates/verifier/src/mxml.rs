@@ -0,0 +1,5 @@
+mod mxml_default_property;
+pub use mxml_default_property::*;
+
+mod mxml_states;
+pub use mxml_states::*;
\ No newline at end of file
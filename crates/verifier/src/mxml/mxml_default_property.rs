@@ -0,0 +1,52 @@
+use crate::ns::*;
+
+/// Resolves the `[DefaultProperty("name")]` meta-data of component classes,
+/// which names the property MXML child elements are assigned to when they
+/// are not wrapped in a property tag.
+pub struct MxmlDefaultProperty;
+
+impl MxmlDefaultProperty {
+    /// Returns the default property of a class, searching base classes
+    /// when the class itself does not declare one.
+    pub fn of(host: &Database, class: &Entity) -> Option<String> {
+        let mut class = Some(class.clone());
+        while let Some(c) = class {
+            if let Some(name) = Self::declared(&c) {
+                return Some(name);
+            }
+            class = c.extends_class(host);
+        }
+        None
+    }
+
+    /// Returns the default property declared directly by a class.
+    pub fn declared(class: &Entity) -> Option<String> {
+        for m in class.metadata().iter() {
+            if m.name.0 != "DefaultProperty" {
+                continue;
+            }
+            let entry = m.entries.as_ref()?.first()?.clone();
+            return Some(match entry.value.as_ref() {
+                MetadataValue::String(val) => val.0.clone(),
+                MetadataValue::IdentifierString(val) => val.0.clone(),
+            });
+        }
+        None
+    }
+
+    /// Returns the `[ArrayElementType("T")]` type name of an `Array`
+    /// default property, against which each MXML child is checked.
+    /// `Vector` properties use their `vector_element_type()` instead.
+    pub fn array_element_type_name(property: &Entity) -> Option<String> {
+        for m in property.metadata().iter() {
+            if m.name.0 == "ArrayElementType" {
+                let entry = m.entries.as_ref()?.first()?.clone();
+                return Some(match entry.value.as_ref() {
+                    MetadataValue::String(val) => val.0.clone(),
+                    MetadataValue::IdentifierString(val) => val.0.clone(),
+                });
+            }
+        }
+        None
+    }
+}
\ No newline at end of file
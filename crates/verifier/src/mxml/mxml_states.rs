@@ -0,0 +1,158 @@
+use crate::ns::*;
+
+/// View states declared by the `states` property of an MXML component.
+#[derive(Clone, Default, Debug)]
+pub struct MxmlStates {
+    pub names: Vec<String>,
+    /// State groups from the `stateGroups` attribute, mapped to their states.
+    pub groups: HashMap<String, Vec<String>>,
+}
+
+/// An override applied by a component when it enters a view state.
+#[derive(Clone)]
+pub enum MxmlStateOverride {
+    /// Adds an element that is only present in some states to its parent,
+    /// at its position among the child elements of the parent.
+    AddItems {
+        state: String,
+        element: Rc<MxmlElement>,
+        parent: Rc<MxmlElement>,
+        position: usize,
+    },
+    /// Sets a property to its state-specific value, as in `label.hover="..."`.
+    SetProperty {
+        state: String,
+        target: Rc<MxmlElement>,
+        name: String,
+        value: String,
+    },
+}
+
+impl MxmlStateOverride {
+    pub fn state(&self) -> &str {
+        match self {
+            Self::AddItems { state, .. } | Self::SetProperty { state, .. } => state,
+        }
+    }
+}
+
+impl MxmlStates {
+    /// Collects the `<states>` declarations of a component root element.
+    pub fn collect(root: &Rc<MxmlElement>) -> Self {
+        let mut r = Self::default();
+        let Some(states) = Self::children(root).into_iter().find(|e| e.name.name == "states") else {
+            return r;
+        };
+        for state in Self::children(&states).iter() {
+            let Some(name) = Self::attribute(state, "name") else {
+                continue;
+            };
+            if let Some(groups) = Self::attribute(state, "stateGroups") {
+                for group in Self::split_list(&groups.0) {
+                    r.groups.entry(group).or_default().push(name.0.clone());
+                }
+            }
+            r.names.push(name.0);
+        }
+        r
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Whether a name refers to a state or a state group.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name) || self.groups.contains_key(name)
+    }
+
+    /// Expands state groups in an `includeIn` or `excludeFrom` list.
+    pub fn resolve(&self, list: &str) -> Vec<String> {
+        let mut r = vec![];
+        for name in Self::split_list(list) {
+            if let Some(states) = self.groups.get(&name) {
+                r.extend(states.iter().cloned());
+            } else {
+                r.push(name);
+            }
+        }
+        r.dedup();
+        r
+    }
+
+    /// States an element is present in, given its `includeIn`
+    /// or `excludeFrom` attributes.
+    pub fn element_states(&self, element: &Rc<MxmlElement>) -> Vec<String> {
+        if let Some(include_in) = Self::attribute(element, "includeIn") {
+            return self.resolve(&include_in.0);
+        }
+        if let Some(exclude_from) = Self::attribute(element, "excludeFrom") {
+            let excluded = self.resolve(&exclude_from.0);
+            return self.names.iter().filter(|n| !excluded.contains(n)).cloned().collect();
+        }
+        self.names.clone()
+    }
+
+    /// Lowers the state-specific parts of a component into the overrides
+    /// the generated component class applies when entering each state:
+    /// an `AddItems` per state an element with `includeIn` or
+    /// `excludeFrom` is present in, and a `SetProperty` per state of a
+    /// state-specific attribute, in document order.
+    pub fn overrides(&self, root: &Rc<MxmlElement>) -> Vec<MxmlStateOverride> {
+        let mut r = vec![];
+        self.collect_overrides(root, &mut r);
+        r
+    }
+
+    fn collect_overrides(&self, element: &Rc<MxmlElement>, overrides: &mut Vec<MxmlStateOverride>) {
+        for attr in element.attributes.iter() {
+            if attr.xmlns {
+                continue;
+            }
+            if let Some((name, state)) = attr.name.name.split_once('.') {
+                for state in self.resolve(state) {
+                    overrides.push(MxmlStateOverride::SetProperty {
+                        state,
+                        target: element.clone(),
+                        name: name.to_owned(),
+                        value: attr.value.0.clone(),
+                    });
+                }
+            }
+        }
+        for (position, child) in Self::children(element).iter().enumerate() {
+            if child.name.name == "states" {
+                continue;
+            }
+            if Self::attribute(child, "includeIn").is_some() || Self::attribute(child, "excludeFrom").is_some() {
+                for state in self.element_states(child) {
+                    overrides.push(MxmlStateOverride::AddItems {
+                        state,
+                        element: child.clone(),
+                        parent: element.clone(),
+                        position,
+                    });
+                }
+            }
+            self.collect_overrides(child, overrides);
+        }
+    }
+
+    pub fn split_list(list: &str) -> Vec<String> {
+        list.split(',').map(|s| s.trim().to_owned()).filter(|s| !s.is_empty()).collect()
+    }
+
+    pub(crate) fn attribute(element: &Rc<MxmlElement>, name: &str) -> Option<(String, Location)> {
+        element.attributes.iter().find(|a| !a.xmlns && a.name.prefix.is_none() && a.name.name == name).map(|a| a.value.clone())
+    }
+
+    pub(crate) fn children(element: &Rc<MxmlElement>) -> Vec<Rc<MxmlElement>> {
+        let Some(content) = element.content.as_ref() else {
+            return vec![];
+        };
+        content.iter().filter_map(|c| match c.as_ref() {
+            MxmlContent::Element(e) => Some(e.clone()),
+            _ => None,
+        }).collect()
+    }
+}
\ No newline at end of file
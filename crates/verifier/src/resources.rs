@@ -0,0 +1,11 @@
+mod properties_file;
+pub use properties_file::*;
+
+mod resource_bundle_registry;
+pub use resource_bundle_registry::*;
+
+mod missing_resource_report;
+pub use missing_resource_report::*;
+
+mod pseudo_locale;
+pub use pseudo_locale::*;
\ No newline at end of file
@@ -0,0 +1,70 @@
+use crate::ns::*;
+use std::collections::BTreeSet;
+
+/// A resource key or bundle present in some locale but absent in another.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MissingResource {
+    pub locale: String,
+    pub bundle: String,
+    /// The missing key, or `None` if the whole bundle is missing.
+    pub key: Option<String>,
+}
+
+/// Report of localization gaps across the configured locales.
+#[derive(Clone, Default, Debug)]
+pub struct MissingResourceReport {
+    pub missing: Vec<MissingResource>,
+}
+
+impl MissingResourceReport {
+    /// Compares every locale against the union of bundles
+    /// and keys across all locales.
+    pub fn new(registry: &ResourceBundleRegistry) -> Self {
+        let mut bundle_names = BTreeSet::<String>::new();
+        for bundles in registry.bundles.values() {
+            bundle_names.extend(bundles.keys().cloned());
+        }
+        let mut missing = vec![];
+        for bundle in bundle_names.iter() {
+            let mut keys = BTreeSet::<String>::new();
+            for locale in registry.locales.iter() {
+                if let Some(file) = registry.bundle(locale, bundle) {
+                    keys.extend(file.entries.keys().cloned());
+                }
+            }
+            for locale in registry.locales.iter() {
+                let Some(file) = registry.bundle(locale, bundle) else {
+                    missing.push(MissingResource { locale: locale.clone(), bundle: bundle.clone(), key: None });
+                    continue;
+                };
+                for key in keys.iter().filter(|k| !file.contains_key(k)) {
+                    missing.push(MissingResource { locale: locale.clone(), bundle: bundle.clone(), key: Some(key.clone()) });
+                }
+            }
+        }
+        Self { missing }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    pub fn format_human(&self) -> String {
+        if self.missing.is_empty() {
+            return "No missing resources.\n".into();
+        }
+        let mut r = String::new();
+        let mut last_locale: Option<&str> = None;
+        for m in self.missing.iter() {
+            if last_locale != Some(m.locale.as_str()) {
+                r.push_str(&format!("{}:\n", m.locale));
+                last_locale = Some(m.locale.as_str());
+            }
+            match m.key.as_ref() {
+                Some(key) => r.push_str(&format!("  {}: missing key {}\n", m.bundle, key)),
+                None => r.push_str(&format!("  {}: missing bundle\n", m.bundle)),
+            }
+        }
+        r
+    }
+}
\ No newline at end of file
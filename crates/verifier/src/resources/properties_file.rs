@@ -0,0 +1,94 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// A parsed `.properties` file.
+///
+/// Supports `#` and `!` comments, the `=`, `:` and whitespace
+/// separators, line continuations and the `\n`, `\t`, `\r`
+/// and `\uXXXX` escapes.
+#[derive(Clone, Default, Debug)]
+pub struct PropertiesFile {
+    pub entries: BTreeMap<String, String>,
+}
+
+impl PropertiesFile {
+    pub fn parse(text: &str) -> Self {
+        let mut entries = BTreeMap::new();
+        let mut lines = text.lines();
+        while let Some(line) = lines.next() {
+            let mut line = line.trim_start().to_owned();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+            while Self::ends_with_continuation(&line) {
+                line.pop();
+                match lines.next() {
+                    Some(next) => line.push_str(next.trim_start()),
+                    None => break,
+                }
+            }
+            let (key, value) = Self::split_entry(&line);
+            entries.insert(Self::unescape(key), Self::unescape(value));
+        }
+        Self { entries }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.entries.get(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn ends_with_continuation(line: &str) -> bool {
+        line.chars().rev().take_while(|ch| *ch == '\\').count() % 2 == 1
+    }
+
+    fn split_entry(line: &str) -> (&str, &str) {
+        let mut escaped = false;
+        for (i, ch) in line.char_indices() {
+            if escaped {
+                escaped = false;
+                continue;
+            }
+            match ch {
+                '\\' => escaped = true,
+                '=' | ':' => return (&line[..i], line[i + 1..].trim_start()),
+                ' ' | '\t' | '\x0C' => {
+                    let rest = line[i..].trim_start();
+                    let rest = rest.strip_prefix(['=', ':']).unwrap_or(rest);
+                    return (&line[..i], rest.trim_start());
+                },
+                _ => {},
+            }
+        }
+        (line, "")
+    }
+
+    fn unescape(s: &str) -> String {
+        let mut r = String::new();
+        let mut chars = s.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                r.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => r.push('\n'),
+                Some('t') => r.push('\t'),
+                Some('r') => r.push('\r'),
+                Some('f') => r.push('\x0C'),
+                Some('u') => {
+                    let hex = chars.by_ref().take(4).collect::<String>();
+                    if let Some(ch) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        r.push(ch);
+                    }
+                },
+                Some(ch) => r.push(ch),
+                None => {},
+            }
+        }
+        r
+    }
+}
\ No newline at end of file
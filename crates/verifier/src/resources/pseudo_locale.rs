@@ -0,0 +1,68 @@
+use crate::ns::*;
+
+/// Generates pseudo-localized text, replacing ASCII letters with accented
+/// look-alikes and padding the result, so that untranslated strings and
+/// truncated layouts stand out while testing.
+///
+/// Placeholders such as `{0}` are preserved.
+pub struct PseudoLocale;
+
+impl PseudoLocale {
+    /// Default name of the generated locale.
+    pub const DEFAULT_NAME: &'static str = "en_XA";
+
+    pub fn localize(text: &str) -> String {
+        let mut r = String::from("[");
+        let mut in_placeholder = false;
+        for ch in text.chars() {
+            match ch {
+                '{' => in_placeholder = true,
+                '}' => in_placeholder = false,
+                _ => {},
+            }
+            r.push(if in_placeholder { ch } else { Self::accent(ch) });
+        }
+        // Roughly 30% expansion, as translated text is often longer.
+        let padding = (text.chars().count() * 3 + 9) / 10;
+        if padding > 0 {
+            r.push(' ');
+            r.push_str(&"~".repeat(padding));
+        }
+        r.push(']');
+        r
+    }
+
+    /// Adds a pseudo-locale to a registry, derived from the bundles of
+    /// an existing locale.
+    pub fn add_to(registry: &mut ResourceBundleRegistry, name: &str, from_locale: &str) {
+        let Some(bundles) = registry.bundles.get(from_locale).cloned() else {
+            return;
+        };
+        let bundles = bundles.into_iter().map(|(bundle, file)| {
+            let entries = file.entries.into_iter().map(|(k, v)| (k, Self::localize(&v))).collect();
+            (bundle, PropertiesFile { entries })
+        }).collect();
+        registry.bundles.insert(name.to_owned(), bundles);
+        if !registry.locales.iter().any(|l| l == name) {
+            registry.locales.push(name.to_owned());
+        }
+    }
+
+    fn accent(ch: char) -> char {
+        match ch {
+            'a' => 'à', 'b' => 'ƀ', 'c' => 'ç', 'd' => 'ð', 'e' => 'é',
+            'f' => 'ƒ', 'g' => 'ĝ', 'h' => 'ĥ', 'i' => 'î', 'j' => 'ĵ',
+            'k' => 'ķ', 'l' => 'ļ', 'm' => 'ɱ', 'n' => 'ñ', 'o' => 'ö',
+            'p' => 'þ', 'q' => 'ǫ', 'r' => 'ŕ', 's' => 'š', 't' => 'ţ',
+            'u' => 'û', 'v' => 'ṽ', 'w' => 'ŵ', 'x' => 'ẋ', 'y' => 'ý',
+            'z' => 'ž',
+            'A' => 'Å', 'B' => 'Ɓ', 'C' => 'Ç', 'D' => 'Ð', 'E' => 'É',
+            'F' => 'Ƒ', 'G' => 'Ĝ', 'H' => 'Ĥ', 'I' => 'Î', 'J' => 'Ĵ',
+            'K' => 'Ķ', 'L' => 'Ļ', 'M' => 'Ṁ', 'N' => 'Ñ', 'O' => 'Ö',
+            'P' => 'Þ', 'Q' => 'Ǫ', 'R' => 'Ŕ', 'S' => 'Š', 'T' => 'Ţ',
+            'U' => 'Û', 'V' => 'Ṽ', 'W' => 'Ŵ', 'X' => 'Ẋ', 'Y' => 'Ý',
+            'Z' => 'Ž',
+            _ => ch,
+        }
+    }
+}
\ No newline at end of file
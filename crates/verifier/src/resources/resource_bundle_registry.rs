@@ -0,0 +1,93 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// Resource bundles gathered from the locale source paths,
+/// indexed by locale and then by bundle name.
+#[derive(Clone, Default)]
+pub struct ResourceBundleRegistry {
+    pub locales: Vec<String>,
+    pub bundles: HashMap<String, BTreeMap<String, PropertiesFile>>,
+}
+
+impl ResourceBundleRegistry {
+    /// Token replaced by the locale name in locale source paths,
+    /// such as `locale/{locale}`.
+    pub const LOCALE_TOKEN: &'static str = "{locale}";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every `.properties` file found directly under each locale
+    /// source path, for each locale. Unreadable directories are skipped.
+    #[cfg(feature = "fs")]
+    pub fn load(locales: &[String], locale_source_path: &[String]) -> Self {
+        Self::load_from(&FileSystemSourceProvider::default(), locales, locale_source_path)
+    }
+
+    /// Loads resource bundles as `load()` does, reading files
+    /// from a `SourceProvider`.
+    pub fn load_from(sources: &dyn SourceProvider, locales: &[String], locale_source_path: &[String]) -> Self {
+        let mut r = Self::new();
+        for locale in locales.iter() {
+            r.locales.push(locale.clone());
+            let bundles = r.bundles.entry(locale.clone()).or_default();
+            for path in locale_source_path.iter() {
+                let dir = path.replace(Self::LOCALE_TOKEN, locale);
+                for path in sources.read_dir(&dir) {
+                    let path = std::path::Path::new(&path);
+                    if path.extension().map(|e| e == "properties").unwrap_or(false) {
+                        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+                            continue;
+                        };
+                        if let Some(text) = sources.read_to_string(&path.to_string_lossy()) {
+                            bundles.insert(name, PropertiesFile::parse(&text));
+                        }
+                    }
+                }
+            }
+        }
+        r
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locales.is_empty()
+    }
+
+    pub fn bundle(&self, locale: &str, name: &str) -> Option<&PropertiesFile> {
+        self.bundles.get(locale)?.get(name)
+    }
+
+    /// Generates the ActionScript source of the class embedding a bundle
+    /// for a locale, following the `locale$bundle_properties` naming
+    /// used by the Flex resource manager.
+    pub fn bundle_class_source(&self, locale: &str, name: &str) -> Option<String> {
+        let bundle = self.bundle(locale, name)?;
+        let class_name = Self::bundle_class_name(locale, name);
+        let mut r = String::new();
+        r.push_str("package {\n");
+        r.push_str("    import mx.resources.ResourceBundle;\n\n");
+        r.push_str(&format!("    public class {class_name} extends ResourceBundle {{\n"));
+        r.push_str(&format!("        public function {class_name}() {{\n"));
+        r.push_str(&format!("            super({}, {});\n", json_string(locale), json_string(name)));
+        r.push_str("        }\n\n");
+        r.push_str("        override protected function getContent():Object {\n");
+        r.push_str("            return {\n");
+        let entries = bundle.entries.iter()
+            .map(|(k, v)| format!("                {}: {}", json_string(k), json_string(v)))
+            .collect::<Vec<_>>();
+        r.push_str(&entries.join(",\n"));
+        if !entries.is_empty() {
+            r.push('\n');
+        }
+        r.push_str("            };\n");
+        r.push_str("        }\n");
+        r.push_str("    }\n");
+        r.push_str("}\n");
+        Some(r)
+    }
+
+    pub fn bundle_class_name(locale: &str, name: &str) -> String {
+        format!("{locale}${name}_properties")
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+mod source_edit;
+pub use source_edit::*;
+
+mod import_organizer;
+pub use import_organizer::*;
+
+mod import_alias_rename;
+pub use import_alias_rename::*;
+
+mod suggested_fix;
+pub use suggested_fix::*;
\ No newline at end of file
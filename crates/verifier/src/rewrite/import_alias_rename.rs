@@ -0,0 +1,70 @@
+use crate::ns::*;
+
+/// Produces edits renaming the alias of an aliased import, as `Btn` in
+/// `import Btn = spark.components.Button;`, along with the unqualified
+/// references to it within the same program.
+///
+/// ```ignore
+/// let edits = ImportAliasRename::rename(&host, &program, &import_drtv, "SparkButton");
+/// ```
+///
+/// References are matched by name and by the definition they resolve
+/// to, so that a local of the same name shadowing the alias is left
+/// unchanged. The program must have been verified.
+pub struct ImportAliasRename;
+
+impl ImportAliasRename {
+    pub fn rename(host: &Database, program: &Rc<Program>, import_drtv: &Rc<Directive>, new_name: &str) -> Vec<SourceEdit> {
+        let Directive::ImportDirective(impdrtv) = import_drtv.as_ref() else {
+            return vec![];
+        };
+        let Some((name, location)) = impdrtv.alias.clone() else {
+            return vec![];
+        };
+        let Some(alias) = host.node_mapping().get(import_drtv).filter(|a| a.is::<Alias>()) else {
+            return vec![];
+        };
+        let mut collector = AliasReferenceCollector {
+            host,
+            name,
+            alias: alias.clone(),
+            target: alias.alias_of(),
+            locations: vec![location],
+        };
+        collector.visit_program(program);
+        collector.locations.iter().map(|location| SourceEdit::replace(location, new_name)).collect()
+    }
+}
+
+struct AliasReferenceCollector<'a> {
+    host: &'a Database,
+    name: String,
+    alias: Entity,
+    target: Entity,
+    locations: Vec<Location>,
+}
+
+impl<'a> AliasReferenceCollector<'a> {
+    fn refers_to_alias(&self, exp: &Rc<Expression>) -> bool {
+        let Some(val) = self.host.node_mapping().get(exp) else {
+            return false;
+        };
+        if val == self.alias || val == self.target {
+            return true;
+        }
+        referenced_entity_of_value(&val).map(|definition| definition == self.target).unwrap_or(false)
+    }
+}
+
+impl<'a> Visitor for AliasReferenceCollector<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::QualifiedIdentifier(id) = exp.as_ref() {
+            if let (None, QualifiedIdentifierIdentifier::Id((name, location))) = (&id.qualifier, &id.id) {
+                if *name == self.name && !id.attribute && self.refers_to_alias(exp) {
+                    self.locations.push(location.clone());
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
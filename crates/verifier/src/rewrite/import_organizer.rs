@@ -0,0 +1,170 @@
+use crate::ns::*;
+
+/// Ordering of organized imports.
+#[derive(Clone, Debug)]
+pub struct ImportOrganizerStyle {
+    /// Top-level package names whose imports come first, in order,
+    /// such as `["flash", "mx"]`. Remaining imports follow alphabetically.
+    pub leading_packages: Vec<String>,
+    /// Separates groups of imports with distinct top-level packages
+    /// by a blank line.
+    pub blank_line_between_groups: bool,
+}
+
+impl Default for ImportOrganizerStyle {
+    fn default() -> Self {
+        Self {
+            leading_packages: vec!["flash".into()],
+            blank_line_between_groups: false,
+        }
+    }
+}
+
+/// Produces edits that remove unused imports, add imports for
+/// unresolved names with a single candidate in a `SymbolIndex`, and sort
+/// the imports of each package block.
+///
+/// Aliased imports, as `import Btn = spark.components.Button;`, sort by
+/// the name they import and are kept apart from a plain import of the
+/// same name.
+///
+/// ```ignore
+/// let edits = ImportOrganizer::organize(&host, &program, &index, &unresolved_names, &Default::default());
+/// let mut batch = SourceEditBatch::new();
+/// batch.extend(edits);
+/// let text = batch.apply(&cu.text())?;
+/// ```
+pub struct ImportOrganizer;
+
+impl ImportOrganizer {
+    pub fn organize(host: &Database, program: &Rc<Program>, index: &SymbolIndex, unresolved_names: &[String], style: &ImportOrganizerStyle) -> Vec<SourceEdit> {
+        let text = program.location.compilation_unit().text();
+        let mut r = vec![];
+        for pckgdefn in program.packages.iter() {
+            let package = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+            r.extend(Self::organize_block(host, &text, &pckgdefn.block, &package, index, unresolved_names, style));
+        }
+        r
+    }
+
+    /// Lists the import directives of a directive list that were not used
+    /// during verification, including aliased imports whose alias is
+    /// not referenced.
+    pub fn unused_imports(host: &Database, list: &[Rc<Directive>]) -> Vec<Rc<Directive>> {
+        list.iter().filter(|drtv| {
+            if !matches!(drtv.as_ref(), Directive::ImportDirective(_)) {
+                return false;
+            }
+            host.node_mapping().get(drtv).map(|imp| Unused(host).is_unused(&imp)).unwrap_or(false)
+        }).cloned().collect()
+    }
+
+    /// Name of an import as written, such as `flash.display.*`.
+    pub fn import_name(impdrtv: &ImportDirective) -> String {
+        let mut name = impdrtv.package_name.iter().map(|name| name.0.clone()).collect::<Vec<_>>();
+        name.push(match &impdrtv.import_specifier {
+            ImportSpecifier::Identifier(name) => name.0.clone(),
+            ImportSpecifier::Wildcard(_) => "*".into(),
+            ImportSpecifier::Recursive(_) => "**".into(),
+        });
+        name.join(".")
+    }
+
+    fn organize_block(host: &Database, text: &str, block: &Rc<Block>, package: &str, index: &SymbolIndex, unresolved_names: &[String], style: &ImportOrganizerStyle) -> Vec<SourceEdit> {
+        let imports: Vec<&Rc<Directive>> = block.directives.iter().filter(|drtv| matches!(drtv.as_ref(), Directive::ImportDirective(_))).collect();
+        let unused = Self::unused_imports(host, &block.directives);
+
+        // Imports to keep, as (sort key, alias, source text)
+        let mut kept: Vec<(String, Option<String>, String)> = vec![];
+        for drtv in imports.iter() {
+            if unused.iter().any(|u| Rc::ptr_eq(u, drtv)) {
+                continue;
+            }
+            let Directive::ImportDirective(impdrtv) = drtv.as_ref() else {
+                continue;
+            };
+            let location = drtv.location();
+            let source = text[location.first_offset()..location.last_offset()].to_owned();
+            kept.push((Self::import_name(impdrtv), impdrtv.alias.as_ref().map(|alias| alias.0.clone()), source));
+        }
+
+        // Imports for unresolved names with a single candidate
+        for name in unresolved_names.iter() {
+            let candidates: Vec<&IndexedSymbol> = index.find_by_name(name).into_iter().filter(|s| !s.package.is_empty() && s.package != package).collect();
+            if candidates.len() != 1 {
+                continue;
+            }
+            let fqn = candidates[0].fully_qualified_name();
+            if !kept.iter().any(|(k, alias, _)| *k == fqn && alias.is_none()) {
+                kept.push((fqn.clone(), None, format!("import {fqn};")));
+            }
+        }
+
+        if imports.is_empty() && kept.is_empty() {
+            return vec![];
+        }
+
+        kept.sort_by(|a, b| Self::sort_key(&a.0, style).cmp(&Self::sort_key(&b.0, style)).then(a.1.cmp(&b.1)));
+        kept.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+        // Replace the imports by the organized list at the first import,
+        // or at the start of the package block.
+        let (insert_at, indent) = match imports.first() {
+            Some(first) => Self::line_start_and_indent(text, first.location().first_offset()),
+            None => {
+                let Some(first) = block.directives.first() else {
+                    return vec![];
+                };
+                Self::line_start_and_indent(text, first.location().first_offset())
+            },
+        };
+        let mut organized = String::new();
+        let mut last_group: Option<String> = None;
+        for (name, _, source) in kept.iter() {
+            let group = name.split('.').next().unwrap_or("").to_owned();
+            if style.blank_line_between_groups && last_group.as_ref().map(|g| *g != group).unwrap_or(false) {
+                organized.push('\n');
+            }
+            organized.push_str(&format!("{indent}{source}\n"));
+            last_group = Some(group);
+        }
+        if imports.is_empty() {
+            organized.push('\n');
+        }
+
+        let mut r = vec![SourceEdit::new(insert_at, insert_at, &organized)];
+        for drtv in imports.iter() {
+            let (start, end) = Self::line_range(text, &drtv.location());
+            r.push(SourceEdit::new(start, end, ""));
+        }
+        r
+    }
+
+    fn sort_key(name: &str, style: &ImportOrganizerStyle) -> (usize, String) {
+        let group = name.split('.').next().unwrap_or("");
+        let rank = style.leading_packages.iter().position(|p| p == group).unwrap_or(style.leading_packages.len());
+        (rank, name.to_owned())
+    }
+
+    /// Returns the start of the line containing `offset` and its indentation.
+    fn line_start_and_indent(text: &str, offset: usize) -> (usize, String) {
+        let start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let indent = text[start..offset].chars().take_while(|c| c.is_whitespace()).collect();
+        (start, indent)
+    }
+
+    /// Extends the range of a directive to its whole line if the line
+    /// contains nothing else.
+    fn line_range(text: &str, location: &Location) -> (usize, usize) {
+        let (mut start, mut end) = (location.first_offset(), location.last_offset());
+        let (line_start, _) = Self::line_start_and_indent(text, start);
+        let before_is_blank = text[line_start..start].trim().is_empty();
+        let line_end = text[end..].find('\n').map(|i| end + i + 1).unwrap_or(text.len());
+        let after_is_blank = text[end..line_end].trim().is_empty();
+        if before_is_blank && after_is_blank {
+            start = line_start;
+            end = line_end;
+        }
+        (start, end)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::ns::*;
+
+/// Replacement of a source range, given in byte offsets.
+///
+/// An empty range denotes an insertion.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SourceEdit {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl SourceEdit {
+    pub fn new(start: usize, end: usize, replacement: &str) -> Self {
+        Self { start, end, replacement: replacement.to_owned() }
+    }
+
+    /// Replaces the source range of a node.
+    pub fn replace(location: &Location, replacement: &str) -> Self {
+        Self::new(location.first_offset(), location.last_offset(), replacement)
+    }
+
+    pub fn insert_before(location: &Location, text: &str) -> Self {
+        Self::new(location.first_offset(), location.first_offset(), text)
+    }
+
+    pub fn insert_after(location: &Location, text: &str) -> Self {
+        Self::new(location.last_offset(), location.last_offset(), text)
+    }
+
+    pub fn delete(location: &Location) -> Self {
+        Self::replace(location, "")
+    }
+
+    fn overlaps(&self, other: &SourceEdit) -> bool {
+        // Insertions only conflict when strictly inside a replaced range.
+        if self.start == self.end {
+            return other.start < self.start && self.start < other.end;
+        }
+        if other.start == other.end {
+            return self.start < other.start && other.start < self.end;
+        }
+        self.start < other.end && other.start < self.end
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SourceEditError {
+    /// Two edits modify overlapping ranges.
+    Overlap(SourceEdit, SourceEdit),
+    /// An edit is out of bounds or does not fall at a character boundary.
+    InvalidRange(SourceEdit),
+}
+
+/// Batch of edits over the text of a single compilation unit.
+///
+/// Edits are applied against the original text, so their offsets
+/// never need adjusting for previous edits.
+///
+/// ```ignore
+/// let mut batch = SourceEditBatch::new();
+/// batch.push(SourceEdit::replace(&id.location, "newName"));
+/// let text = batch.apply(&cu.text())?;
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SourceEditBatch {
+    edits: Vec<SourceEdit>,
+}
+
+impl SourceEditBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, edit: SourceEdit) {
+        self.edits.push(edit);
+    }
+
+    pub fn extend(&mut self, edits: impl IntoIterator<Item = SourceEdit>) {
+        self.edits.extend(edits);
+    }
+
+    pub fn edits(&self) -> &[SourceEdit] {
+        &self.edits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Returns the edits sorted by position, failing on the first
+    /// pair of overlapping edits.
+    ///
+    /// Insertions at the same offset keep the order they were pushed in.
+    pub fn sorted(&self) -> Result<Vec<SourceEdit>, SourceEditError> {
+        let mut edits = self.edits.clone();
+        edits.sort_by_key(|edit| (edit.start, edit.end));
+        for i in 0..edits.len() {
+            for j in (i + 1)..edits.len() {
+                if edits[j].start > edits[i].end {
+                    break;
+                }
+                if edits[i].overlaps(&edits[j]) {
+                    return Err(SourceEditError::Overlap(edits[i].clone(), edits[j].clone()));
+                }
+            }
+        }
+        Ok(edits)
+    }
+
+    /// Applies the edits to a text.
+    pub fn apply(&self, text: &str) -> Result<String, SourceEditError> {
+        let edits = self.sorted()?;
+        let mut r = String::with_capacity(text.len());
+        let mut offset = 0;
+        for edit in edits.iter() {
+            if edit.start > edit.end || edit.end > text.len() || !text.is_char_boundary(edit.start) || !text.is_char_boundary(edit.end) {
+                return Err(SourceEditError::InvalidRange(edit.clone()));
+            }
+            r.push_str(&text[offset..edit.start]);
+            r.push_str(&edit.replacement);
+            offset = edit.end;
+        }
+        r.push_str(&text[offset..]);
+        Ok(r)
+    }
+}
\ No newline at end of file
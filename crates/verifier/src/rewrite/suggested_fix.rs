@@ -0,0 +1,11 @@
+use crate::ns::*;
+
+/// Edits that resolve a diagnostic, offered by the verifier to hosts
+/// such as a language server as quick fixes.
+#[derive(Clone, Debug)]
+pub struct SuggestedFix {
+    /// Location of the diagnostic the fix applies to.
+    pub location: Location,
+    pub title: String,
+    pub edits: Vec<SourceEdit>,
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+mod symbol_index;
+pub use symbol_index::*;
+
+mod symbol_collector;
+pub use symbol_collector::*;
+
+mod fuzzy_symbol_search;
+pub use fuzzy_symbol_search::*;
\ No newline at end of file
@@ -0,0 +1,152 @@
+use crate::ns::*;
+
+/// How a query matched a symbol name, best first.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum SymbolMatchKind {
+    /// The name equals the query, ignoring case.
+    Exact,
+    /// The name starts with the query, ignoring case.
+    Prefix,
+    /// The query is made of prefixes of the words of the name, in
+    /// order, as `EvDi` or `ed` for `EventDispatcher`.
+    CamelHump,
+    /// The name contains the query, ignoring case.
+    Substring,
+}
+
+#[derive(Clone, Debug)]
+pub struct SymbolSearchMatch<'a> {
+    pub symbol: &'a IndexedSymbol,
+    pub kind: SymbolMatchKind,
+}
+
+/// Fuzzy search over a `SymbolIndex`, for workspace symbol requests and
+/// quick-open.
+///
+/// ```ignore
+/// let matches = FuzzySymbolSearch(&index).search("EvDi", Some(&file_path), 50);
+/// ```
+///
+/// A query containing a dot, as `events.ED`, matches the name against
+/// the part after the last dot and requires the package to contain the
+/// part before it. Matches are ranked by how the name matched, then by
+/// whether the definition is visible from the requesting file, then by
+/// proximity to it: the same file, the same package, and then the
+/// distance between their directories.
+pub struct FuzzySymbolSearch<'a>(pub &'a SymbolIndex);
+
+impl<'a> FuzzySymbolSearch<'a> {
+    pub fn search(&self, query: &str, requesting_file: Option<&str>, limit: usize) -> Vec<SymbolSearchMatch<'a>> {
+        let index = self.0;
+        let (package_query, name_query) = match query.rsplit_once('.') {
+            Some((package, name)) => (Some(package.to_lowercase()), name),
+            None => (None, query),
+        };
+        if name_query.is_empty() && package_query.is_none() {
+            return vec![];
+        }
+        let requesting_package = requesting_file
+            .and_then(|f| index.file_symbols(f).first())
+            .map(|s| s.package.clone());
+
+        let mut r: Vec<(SymbolSearchMatch<'a>, (bool, bool, bool, usize))> = index.symbols()
+            .filter(|s| package_query.as_ref().map(|p| s.package.to_lowercase().contains(p.as_str())).unwrap_or(true))
+            .filter_map(|symbol| {
+                let kind = Self::match_kind(&symbol.name, name_query)?;
+                let same_file = requesting_file.map(|f| f == symbol.file_path).unwrap_or(false);
+                let same_package = requesting_package.as_ref().map(|p| *p == symbol.package).unwrap_or(false);
+                let is_visible = same_file || symbol.is_public || same_package;
+                let distance = requesting_file.map(|f| Self::directory_distance(f, &symbol.file_path)).unwrap_or(0);
+                Some((SymbolSearchMatch { symbol, kind }, (!is_visible, !same_file, !same_package, distance)))
+            })
+            .collect();
+        r.sort_by(|(a, a_rank), (b, b_rank)| {
+            a.kind.cmp(&b.kind)
+                .then_with(|| a_rank.cmp(b_rank))
+                .then_with(|| a.symbol.name.len().cmp(&b.symbol.name.len()))
+                .then_with(|| a.symbol.fully_qualified_name().cmp(&b.symbol.fully_qualified_name()))
+        });
+        r.truncate(limit);
+        r.into_iter().map(|(m, _)| m).collect()
+    }
+
+    /// An empty query matches every name as a prefix.
+    pub fn match_kind(name: &str, query: &str) -> Option<SymbolMatchKind> {
+        let lower_name = name.to_lowercase();
+        let lower_query = query.to_lowercase();
+        if lower_name == lower_query {
+            Some(SymbolMatchKind::Exact)
+        } else if lower_name.starts_with(&lower_query) {
+            Some(SymbolMatchKind::Prefix)
+        } else if Self::matches_humps(&Self::humps(name), &query.chars().collect::<Vec<_>>()) {
+            Some(SymbolMatchKind::CamelHump)
+        } else if lower_name.contains(&lower_query) {
+            Some(SymbolMatchKind::Substring)
+        } else {
+            None
+        }
+    }
+
+    /// Words of a name, split before an uppercase letter following a
+    /// lowercase letter or digit, before the last uppercase letter of a
+    /// run followed by a lowercase letter, and at underscores and
+    /// dollar signs, as `URL`, `Loader` for `URLLoader`.
+    fn humps(name: &str) -> Vec<Vec<char>> {
+        let chars: Vec<char> = name.chars().collect();
+        let mut r: Vec<Vec<char>> = vec![];
+        for (i, ch) in chars.iter().enumerate() {
+            if *ch == '_' || *ch == '$' {
+                r.push(vec![]);
+                continue;
+            }
+            let previous = i.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(i + 1);
+            let starts_word = match previous {
+                None => true,
+                Some(p) => ch.is_uppercase() && (p.is_lowercase() || p.is_ascii_digit() || (p.is_uppercase() && next.map(|n| n.is_lowercase()).unwrap_or(false))),
+            };
+            if starts_word || r.is_empty() {
+                r.push(vec![]);
+            }
+            r.last_mut().unwrap().push(ch.to_lowercase().next().unwrap_or(*ch));
+        }
+        r.retain(|hump| !hump.is_empty());
+        r
+    }
+
+    /// Whether the query is a sequence of non-empty prefixes of
+    /// successive words starting at the first word, words being
+    /// skippable after the first.
+    fn matches_humps(humps: &[Vec<char>], query: &[char]) -> bool {
+        let query: Vec<char> = query.iter().map(|ch| ch.to_lowercase().next().unwrap_or(*ch)).collect();
+        !query.is_empty() && Self::match_humps_from(humps, 0, &query, true)
+    }
+
+    fn match_humps_from(humps: &[Vec<char>], hump: usize, query: &[char], first: bool) -> bool {
+        if query.is_empty() {
+            return true;
+        }
+        for (i, word) in humps.iter().enumerate().skip(hump) {
+            let common = word.iter().zip(query.iter()).take_while(|(a, b)| a == b).count();
+            // Longer prefixes first, so that `URLLo` takes `URL` whole.
+            for taken in (1..=common).rev() {
+                if Self::match_humps_from(humps, i + 1, &query[taken..], false) {
+                    return true;
+                }
+            }
+            if first {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Number of directories between the directories of two files.
+    fn directory_distance(a: &str, b: &str) -> usize {
+        let a: Vec<&str> = a.split(['/', '\\']).collect();
+        let b: Vec<&str> = b.split(['/', '\\']).collect();
+        let (a, b) = (&a[..a.len().saturating_sub(1)], &b[..b.len().saturating_sub(1)]);
+        let common = a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count();
+        (a.len() - common) + (b.len() - common)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use crate::ns::*;
+
+/// Collects the package-level definitions of a program, or the public
+/// definitions of a library, for `SymbolIndex`.
+pub struct SymbolCollector;
+
+impl SymbolCollector {
+    pub fn collect(program: &Rc<Program>) -> Vec<IndexedSymbol> {
+        let file_path = program.location.compilation_unit().file_path().unwrap_or_default();
+        let mut r = vec![];
+        for pckgdefn in program.packages.iter() {
+            let package = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+            Self::collect_directives(&pckgdefn.block.directives, &package, &file_path, &mut r);
+        }
+        Self::collect_directives(&program.directives, "", &file_path, &mut r);
+        r
+    }
+
+    /// Collects the public definitions of an ABC file, such as one read
+    /// from the `library.swf` of a SWC by `SwfAbcTag::read_library()`.
+    /// They are given the library path and no line.
+    pub fn collect_abc(file_path: &str, abc: &AbcFile) -> Vec<IndexedSymbol> {
+        let mut r: Vec<IndexedSymbol> = vec![];
+        let mut push = |kind: IndexedSymbolKind, (package, name): (String, String)| {
+            // A getter and its setter are one definition
+            if !r.iter().any(|s| s.name == name && s.package == package) {
+                r.push(IndexedSymbol { kind, name, package, file_path: file_path.to_owned(), line: 0, is_public: true });
+            }
+        };
+        for instance in abc.instances.iter() {
+            if let Some(qname) = AbcStubGenerator::public_qname(abc, instance.name) {
+                let is_interface = instance.flags & AbcInstanceInfo::INTERFACE != 0;
+                push(if is_interface { IndexedSymbolKind::Interface } else { IndexedSymbolKind::Class }, qname);
+            }
+        }
+        for script in abc.scripts.iter() {
+            for t in script.traits.iter() {
+                let kind = match t.data {
+                    AbcTraitData::Class { .. } => continue,
+                    AbcTraitData::Method { .. } | AbcTraitData::Function { .. } => IndexedSymbolKind::Function,
+                    AbcTraitData::Getter { .. } | AbcTraitData::Setter { .. } | AbcTraitData::Slot { .. } | AbcTraitData::Const { .. } => IndexedSymbolKind::Variable,
+                };
+                if let Some(qname) = AbcStubGenerator::public_qname(abc, t.name) {
+                    push(kind, qname);
+                }
+            }
+        }
+        r
+    }
+
+    fn collect_directives(list: &[Rc<Directive>], package: &str, file_path: &str, output: &mut Vec<IndexedSymbol>) {
+        for drtv in list.iter() {
+            let is_public = Self::is_public(drtv);
+            let mut push = |kind: IndexedSymbolKind, name: &str, location: &Location| {
+                output.push(IndexedSymbol {
+                    kind,
+                    name: name.to_owned(),
+                    package: package.to_owned(),
+                    file_path: file_path.to_owned(),
+                    line: location.first_line_number(),
+                    is_public,
+                });
+            };
+            match drtv.as_ref() {
+                Directive::ClassDefinition(defn) => push(IndexedSymbolKind::Class, &defn.name.0, &defn.name.1),
+                Directive::InterfaceDefinition(defn) => push(IndexedSymbolKind::Interface, &defn.name.0, &defn.name.1),
+                Directive::EnumDefinition(defn) => push(IndexedSymbolKind::Enum, &defn.name.0, &defn.name.1),
+                Directive::NamespaceDefinition(defn) => push(IndexedSymbolKind::Namespace, &defn.left.0, &defn.left.1),
+                Directive::TypeDefinition(defn) => push(IndexedSymbolKind::TypeAlias, &defn.left.0, &defn.left.1),
+                Directive::FunctionDefinition(defn) => {
+                    if let FunctionName::Identifier(name) = &defn.name {
+                        push(IndexedSymbolKind::Function, &name.0, &name.1);
+                    }
+                },
+                Directive::VariableDefinition(defn) => {
+                    for binding in defn.bindings.iter() {
+                        if let Expression::QualifiedIdentifier(id) = binding.destructuring.destructuring.as_ref() {
+                            if let Some((name, location)) = id.to_identifier_name_or_asterisk() {
+                                push(IndexedSymbolKind::Variable, &name, &location);
+                            }
+                        }
+                    }
+                },
+                Directive::ConfigurationDirective(cfgdrtv) => {
+                    if let Directive::Block(block) = cfgdrtv.directive.as_ref() {
+                        Self::collect_directives(&block.directives, package, file_path, output);
+                    }
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    Self::collect_directives(&incdrtv.nested_directives, package, file_path, output);
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn is_public(drtv: &Rc<Directive>) -> bool {
+        let attributes = match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => &defn.attributes,
+            Directive::InterfaceDefinition(defn) => &defn.attributes,
+            Directive::EnumDefinition(defn) => &defn.attributes,
+            Directive::NamespaceDefinition(defn) => &defn.attributes,
+            Directive::TypeDefinition(defn) => &defn.attributes,
+            Directive::FunctionDefinition(defn) => &defn.attributes,
+            Directive::VariableDefinition(defn) => &defn.attributes,
+            _ => return false,
+        };
+        attributes.iter().any(|a| matches!(a, Attribute::Public(_)))
+    }
+}
\ No newline at end of file
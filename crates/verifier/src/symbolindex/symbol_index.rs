@@ -0,0 +1,212 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum IndexedSymbolKind {
+    Class,
+    Interface,
+    Enum,
+    Function,
+    Variable,
+    Namespace,
+    TypeAlias,
+}
+
+impl IndexedSymbolKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Class => "class",
+            Self::Interface => "interface",
+            Self::Enum => "enum",
+            Self::Function => "function",
+            Self::Variable => "variable",
+            Self::Namespace => "namespace",
+            Self::TypeAlias => "type",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "class" => Self::Class,
+            "interface" => Self::Interface,
+            "enum" => Self::Enum,
+            "function" => Self::Function,
+            "variable" => Self::Variable,
+            "namespace" => Self::Namespace,
+            "type" => Self::TypeAlias,
+            _ => return None,
+        })
+    }
+}
+
+/// A package-level definition.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IndexedSymbol {
+    pub kind: IndexedSymbolKind,
+    pub name: String,
+    /// Dot-delimited package name, empty for the top-level package.
+    pub package: String,
+    /// Source file or library (SWC) path the definition comes from.
+    pub file_path: String,
+    /// One-based line number, or zero for library definitions.
+    pub line: usize,
+    /// Whether the definition is `public`, rather than `internal` to its
+    /// package or private to its file outside a package.
+    pub is_public: bool,
+}
+
+impl IndexedSymbol {
+    pub fn fully_qualified_name(&self) -> String {
+        if self.package.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.package, self.name)
+        }
+    }
+}
+
+/// Index of package-level definitions across a workspace, searchable by
+/// name through trigrams, for "open type by name" and import suggestions.
+///
+/// Entries are grouped by file so that a file can be re-indexed alone after
+/// it changes. The index persists as a tab-separated text file.
+///
+/// ```ignore
+/// let mut index = SymbolIndex::load(&index_path).unwrap_or_default();
+/// index.update_file(&file_path, SymbolCollector::collect(&program));
+/// // a SWC is indexed under its path from its `library.swf`
+/// let abc_files = SwfAbcTag::read_library("library.swf", library_swf)?;
+/// index.update_file(&swc_path, abc_files.iter().flat_map(|abc| SymbolCollector::collect_abc(&swc_path, abc)).collect());
+/// let candidates = index.find_by_name("EventDispatcher");
+/// index.save(&index_path)?;
+/// ```
+#[derive(Clone, Default)]
+pub struct SymbolIndex {
+    files: BTreeMap<String, Vec<IndexedSymbol>>,
+    /// Lowercase trigram to (file path, entry index).
+    trigrams: HashMap<String, HashSet<(String, usize)>>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the entries of a file.
+    pub fn update_file(&mut self, file_path: &str, symbols: Vec<IndexedSymbol>) {
+        self.remove_file(file_path);
+        for (i, symbol) in symbols.iter().enumerate() {
+            for trigram in Self::trigrams_of(&symbol.name) {
+                self.trigrams.entry(trigram).or_default().insert((file_path.to_owned(), i));
+            }
+        }
+        self.files.insert(file_path.to_owned(), symbols);
+    }
+
+    pub fn remove_file(&mut self, file_path: &str) {
+        if self.files.remove(file_path).is_none() {
+            return;
+        }
+        self.trigrams.retain(|_, entries| {
+            entries.retain(|(f, _)| f != file_path);
+            !entries.is_empty()
+        });
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &IndexedSymbol> {
+        self.files.values().flatten()
+    }
+
+    pub fn file_symbols(&self, file_path: &str) -> &[IndexedSymbol] {
+        self.files.get(file_path).map(|list| list.as_slice()).unwrap_or(&[])
+    }
+
+    /// Definitions named exactly `name`, such as candidates for an import.
+    pub fn find_by_name(&self, name: &str) -> Vec<&IndexedSymbol> {
+        self.symbols().filter(|s| s.name == name).collect()
+    }
+
+    /// Definitions whose name contains `query`, case-insensitively.
+    pub fn search(&self, query: &str) -> Vec<&IndexedSymbol> {
+        let query = query.to_lowercase();
+        let trigrams = Self::trigrams_of(&query);
+        let mut r: Vec<&IndexedSymbol> = if trigrams.is_empty() {
+            self.symbols().filter(|s| s.name.to_lowercase().contains(&query)).collect()
+        } else {
+            let mut candidates: Option<HashSet<(String, usize)>> = None;
+            for trigram in trigrams.iter() {
+                let entries = self.trigrams.get(trigram).cloned().unwrap_or_default();
+                candidates = Some(match candidates {
+                    Some(c) => c.intersection(&entries).cloned().collect(),
+                    None => entries,
+                });
+            }
+            candidates.unwrap_or_default().iter()
+                .filter_map(|(f, i)| self.files.get(f).and_then(|list| list.get(*i)))
+                .filter(|s| s.name.to_lowercase().contains(&query))
+                .collect()
+        };
+        r.sort_by(|a, b| a.name.len().cmp(&b.name.len()).then_with(|| a.fully_qualified_name().cmp(&b.fully_qualified_name())));
+        r
+    }
+
+    fn trigrams_of(name: &str) -> Vec<String> {
+        let chars: Vec<char> = name.to_lowercase().chars().collect();
+        if chars.len() < 3 {
+            return vec![];
+        }
+        let mut r: Vec<String> = chars.windows(3).map(|w| w.iter().collect()).collect();
+        r.sort();
+        r.dedup();
+        r
+    }
+
+    /// Parses an index saved by `to_tsv()`. Entries saved without a
+    /// visibility field are taken as public.
+    pub fn from_tsv(text: &str) -> Self {
+        let mut files: BTreeMap<String, Vec<IndexedSymbol>> = BTreeMap::new();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let (kind, package, name, file_path, line_number, is_public) = match fields[..] {
+                [kind, package, name, file_path, line_number] => (kind, package, name, file_path, line_number, true),
+                [kind, package, name, file_path, line_number, visibility] => (kind, package, name, file_path, line_number, visibility == "public"),
+                _ => continue,
+            };
+            let (Some(kind), Ok(line_number)) = (IndexedSymbolKind::from_name(kind), line_number.parse()) else {
+                continue;
+            };
+            files.entry(file_path.to_owned()).or_default().push(IndexedSymbol {
+                kind,
+                name: name.to_owned(),
+                package: package.to_owned(),
+                file_path: file_path.to_owned(),
+                line: line_number,
+                is_public,
+            });
+        }
+        let mut index = Self::new();
+        for (file_path, symbols) in files {
+            index.update_file(&file_path, symbols);
+        }
+        index
+    }
+
+    pub fn to_tsv(&self) -> String {
+        let mut text = String::new();
+        for s in self.symbols() {
+            let visibility = if s.is_public { "public" } else { "internal" };
+            text.push_str(&format!("{}\t{}\t{}\t{}\t{}\t{visibility}\n", s.kind.name(), s.package, s.name, s.file_path, s.line));
+        }
+        text
+    }
+
+    #[cfg(feature = "fs")]
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        Ok(Self::from_tsv(&std::fs::read_to_string(path)?))
+    }
+
+    #[cfg(feature = "fs")]
+    pub fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_tsv())
+    }
+}
\ No newline at end of file
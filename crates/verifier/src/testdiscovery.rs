@@ -0,0 +1,5 @@
+mod test_manifest;
+pub use test_manifest::*;
+
+mod test_discovery;
+pub use test_discovery::*;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use crate::ns::*;
+
+/// Discovers FlexUnit-style test methods, annotated with `[Test]`,
+/// `[Before]`, `[After]`, `[BeforeClass]` or `[AfterClass]`, in
+/// package-level classes.
+///
+/// Test methods must be public and take no parameters. `[BeforeClass]`
+/// and `[AfterClass]` methods must be static and the others must not;
+/// methods violating these are reported and left out of the manifest.
+///
+/// ```ignore
+/// let manifest = TestDiscovery::discover(&programs);
+/// std::fs::write("tests.json", manifest.to_json())?;
+/// std::fs::write("src/TestRunner.as", manifest.generate_runner("TestRunner"))?;
+/// ```
+pub struct TestDiscovery;
+
+impl TestDiscovery {
+    pub fn discover(programs: &[Rc<Program>]) -> TestManifest {
+        let mut manifest = TestManifest::default();
+        for program in programs.iter() {
+            let file_path = program.location.compilation_unit().file_path().unwrap_or_default();
+            for pckgdefn in program.packages.iter() {
+                let package = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+                Self::discover_directives(&pckgdefn.block.directives, &package, &file_path, &mut manifest);
+            }
+            Self::discover_directives(&program.directives, "", &file_path, &mut manifest);
+        }
+        manifest
+    }
+
+    fn discover_directives(list: &[Rc<Directive>], package: &str, file_path: &str, manifest: &mut TestManifest) {
+        for drtv in list.iter() {
+            match drtv.as_ref() {
+                Directive::ClassDefinition(defn) => {
+                    let methods = Self::discover_methods(&defn.block.directives);
+                    if !methods.is_empty() {
+                        manifest.classes.push(DiscoveredTestClass {
+                            package: package.to_owned(),
+                            name: defn.name.0.clone(),
+                            file_path: file_path.to_owned(),
+                            methods,
+                        });
+                    }
+                },
+                Directive::ConfigurationDirective(cfgdrtv) => {
+                    if let Directive::Block(block) = cfgdrtv.directive.as_ref() {
+                        Self::discover_directives(&block.directives, package, file_path, manifest);
+                    }
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    Self::discover_directives(&incdrtv.nested_directives, package, file_path, manifest);
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn discover_methods(list: &[Rc<Directive>]) -> Vec<DiscoveredTestMethod> {
+        let mut methods = vec![];
+        for drtv in list.iter() {
+            let Directive::FunctionDefinition(defn) = drtv.as_ref() else {
+                continue;
+            };
+            let FunctionName::Identifier(name) = &defn.name else {
+                continue;
+            };
+            let Some(kind) = Attribute::find_metadata(&defn.attributes).iter().find_map(|m| TestMethodKind::from_name(&m.name.0)) else {
+                continue;
+            };
+            let mut valid = true;
+            let mut report = |diagnostic: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>| {
+                name.1.compilation_unit().add_diagnostic(WhackDiagnostic::new_verify_error(&name.1, diagnostic, arguments));
+                valid = false;
+            };
+            if !defn.common.signature.parameters.is_empty() {
+                report(WhackDiagnosticKind::TestMethodMustHaveNoParameters, diagarg![name.0.clone()]);
+            }
+            if !defn.attributes.iter().any(|a| matches!(a, Attribute::Public(_))) {
+                report(WhackDiagnosticKind::TestMethodMustBePublic, diagarg![name.0.clone()]);
+            }
+            let is_static = Attribute::find_static(&defn.attributes).is_some();
+            if kind.is_class_level() && !is_static {
+                report(WhackDiagnosticKind::TestMethodMustBeStatic, diagarg![name.0.clone(), kind.name().to_owned()]);
+            } else if !kind.is_class_level() && is_static {
+                report(WhackDiagnosticKind::TestMethodMustNotBeStatic, diagarg![name.0.clone()]);
+            }
+            if valid {
+                methods.push(DiscoveredTestMethod {
+                    kind,
+                    name: name.0.clone(),
+                    line: name.1.first_line_number(),
+                });
+            }
+        }
+        methods
+    }
+}
\ No newline at end of file
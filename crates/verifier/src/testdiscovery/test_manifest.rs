@@ -0,0 +1,154 @@
+use crate::ns::*;
+
+/// Meta-data annotating a test method, as in FlexUnit 4.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TestMethodKind {
+    Test,
+    Before,
+    After,
+    BeforeClass,
+    AfterClass,
+}
+
+impl TestMethodKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Test => "Test",
+            Self::Before => "Before",
+            Self::After => "After",
+            Self::BeforeClass => "BeforeClass",
+            Self::AfterClass => "AfterClass",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "Test" => Self::Test,
+            "Before" => Self::Before,
+            "After" => Self::After,
+            "BeforeClass" => Self::BeforeClass,
+            "AfterClass" => Self::AfterClass,
+            _ => return None,
+        })
+    }
+
+    /// Whether methods of this kind run once per class and are static.
+    pub fn is_class_level(&self) -> bool {
+        matches!(self, Self::BeforeClass | Self::AfterClass)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DiscoveredTestMethod {
+    pub kind: TestMethodKind,
+    pub name: String,
+    /// One-based line number.
+    pub line: usize,
+}
+
+/// A class declaring at least one test method.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DiscoveredTestClass {
+    /// Dot-delimited package name, empty for the top-level package.
+    pub package: String,
+    pub name: String,
+    pub file_path: String,
+    pub methods: Vec<DiscoveredTestMethod>,
+}
+
+impl DiscoveredTestClass {
+    pub fn fully_qualified_name(&self) -> String {
+        if self.package.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}.{}", self.package, self.name)
+        }
+    }
+
+    pub fn methods_of_kind(&self, kind: TestMethodKind) -> impl Iterator<Item = &DiscoveredTestMethod> {
+        self.methods.iter().filter(move |m| m.kind == kind)
+    }
+}
+
+/// Test classes found by `TestDiscovery`.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct TestManifest {
+    pub classes: Vec<DiscoveredTestClass>,
+}
+
+impl TestManifest {
+    /// Name of the manifest artifact of a `Compilation`.
+    pub const FILE_NAME: &'static str = "test-manifest.json";
+
+    pub fn test_count(&self) -> usize {
+        self.classes.iter().map(|c| c.methods_of_kind(TestMethodKind::Test).count()).sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        let classes = self.classes.iter().map(|c| {
+            let methods = c.methods.iter().map(|m| {
+                format!("{{\"kind\":{},\"name\":{},\"line\":{}}}", json_string(m.kind.name()), json_string(&m.name), m.line)
+            }).collect::<Vec<_>>().join(",");
+            format!("{{\"class\":{},\"file\":{},\"methods\":[{}]}}", json_string(&c.fully_qualified_name()), json_string(&c.file_path), methods)
+        }).collect::<Vec<_>>().join(",");
+        format!("{{\"classes\":[{}]}}", classes)
+    }
+
+    /// Generates the source of a top-level ActionScript class whose static
+    /// `run()` method runs every test and returns the failures as an array
+    /// of `"Class.method: message"` strings.
+    ///
+    /// `[Before]` and `[After]` methods run around each test, and
+    /// `[BeforeClass]` and `[AfterClass]` methods around each class.
+    pub fn generate_runner(&self, class_name: &str) -> String {
+        let mut r = String::new();
+        r.push_str("package {\n");
+        for c in self.classes.iter().filter(|c| !c.package.is_empty()) {
+            r.push_str(&format!("    import {};\n", c.fully_qualified_name()));
+        }
+        r.push_str(&format!("\n    public class {} {{\n", class_name));
+        r.push_str("        public static function run(): Array {\n");
+        r.push_str("            var failures: Array = [];\n");
+        for c in self.classes.iter() {
+            let class_name = c.fully_qualified_name();
+            let tests = c.methods_of_kind(TestMethodKind::Test).collect::<Vec<_>>();
+            if tests.is_empty() {
+                continue;
+            }
+            for m in c.methods_of_kind(TestMethodKind::BeforeClass) {
+                r.push_str(&format!("            {}.{}();\n", class_name, m.name));
+            }
+            for test in tests {
+                r.push_str("            try {\n");
+                r.push_str(&format!("                var {}: {} = new {}();\n", Self::instance_name(c, &test.name), class_name, class_name));
+                let instance = Self::instance_name(c, &test.name);
+                for m in c.methods_of_kind(TestMethodKind::Before) {
+                    r.push_str(&format!("                {}.{}();\n", instance, m.name));
+                }
+                r.push_str("                try {\n");
+                r.push_str(&format!("                    {}.{}();\n", instance, test.name));
+                r.push_str("                } finally {\n");
+                for m in c.methods_of_kind(TestMethodKind::After) {
+                    r.push_str(&format!("                    {}.{}();\n", instance, m.name));
+                }
+                r.push_str("                }\n");
+                r.push_str("            } catch (e: Error) {\n");
+                r.push_str(&format!("                failures.push(\"{}.{}: \" + e.message);\n", class_name, test.name));
+                r.push_str("            }\n");
+            }
+            for m in c.methods_of_kind(TestMethodKind::AfterClass) {
+                r.push_str(&format!("            {}.{}();\n", class_name, m.name));
+            }
+        }
+        r.push_str("            return failures;\n");
+        r.push_str("        }\n");
+        r.push_str("    }\n");
+        r.push_str("}\n");
+        r
+    }
+
+    /// Variables are function-scoped, so each test gets its own instance name.
+    fn instance_name(class: &DiscoveredTestClass, method: &str) -> String {
+        format!("{}_{}_{}", class.package.replace('.', "_"), class.name, method)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+mod diagnostic_expectation;
+pub use diagnostic_expectation::*;
+
+mod verifier_fixture;
+pub use verifier_fixture::*;
+
+mod codegen_snapshot;
+pub use codegen_snapshot::*;
\ No newline at end of file
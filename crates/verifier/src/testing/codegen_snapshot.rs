@@ -0,0 +1,113 @@
+use crate::ns::*;
+
+/// Compiles a fixture program and compares its `--emit=abc-asm` output
+/// against a checked-in golden listing: the methods planned for the
+/// fixture with the lowering of their constructs (see `CodegenListing`).
+///
+/// ```ignore
+/// let snapshot = CodegenSnapshot::compile(&host, &compiler_options, "fixtures/switch.as", &text);
+/// snapshot.assert_matches_golden_file("fixtures/switch.abc.txt");
+/// ```
+///
+/// Setting the `WHACK_UPDATE_GOLDEN` environment variable rewrites
+/// golden files with the actual listing instead of comparing.
+pub struct CodegenSnapshot {
+    pub file_path: String,
+    /// Every `abc-asm` artifact, each preceded by a `; <artifact path>`
+    /// line, after the diagnostics if the fixture failed to compile.
+    pub listing: String,
+}
+
+impl CodegenSnapshot {
+    pub const UPDATE_VARIABLE: &'static str = "WHACK_UPDATE_GOLDEN";
+
+    /// Lines of unchanged context shown around each difference.
+    const CONTEXT_LINES: usize = 3;
+
+    /// Compiles a fixture in a `Compilation` over `host`, emitting the
+    /// `abc-asm` form of the fixture alone.
+    pub fn compile(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, file_path: &str, text: &str) -> Self {
+        let mut sources = MemorySourceProvider::new();
+        sources.insert(file_path, text);
+        let compiler_options = Rc::new(CompilerOptions {
+            emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![file_path.to_owned()] },
+            ..compiler_options.as_ref().clone()
+        });
+        let output = Compilation::run(host, &compiler_options, &sources);
+        let mut listing = String::new();
+        if output.invalidated {
+            for message in output.diagnostics() {
+                listing.push_str(&format!("; {message}\n"));
+            }
+        }
+        for artifact in output.artifacts.iter().filter(|a| a.path.ends_with(".abc-asm.txt")) {
+            listing.push_str(&format!("; {}\n", artifact.path));
+            listing.push_str(&String::from_utf8_lossy(&artifact.bytes));
+        }
+        Self { file_path: file_path.to_owned(), listing }
+    }
+
+    /// Panics with a line diff if the listing differs from `expected`.
+    /// Line endings are normalized before comparing.
+    pub fn assert_matches(&self, expected: &str) {
+        let expected = expected.replace("\r\n", "\n");
+        if expected.trim_end() == self.listing.trim_end() {
+            return;
+        }
+        panic!("{}: codegen output differs from the golden listing (- expected, + actual)\n{}", self.file_path, Self::diff(&expected, &self.listing));
+    }
+
+    #[cfg(feature = "fs")]
+    pub fn assert_matches_golden_file(&self, golden_path: &str) {
+        if std::env::var_os(Self::UPDATE_VARIABLE).is_some() {
+            std::fs::write(golden_path, &self.listing).unwrap_or_else(|error| panic!("{golden_path}: {error}"));
+            return;
+        }
+        let expected = std::fs::read_to_string(golden_path)
+            .unwrap_or_else(|error| panic!("{golden_path}: {error}; set {} to create it", Self::UPDATE_VARIABLE));
+        self.assert_matches(&expected);
+    }
+
+    /// Formats a line diff based on the longest common subsequence,
+    /// showing changed lines with their surrounding context.
+    pub fn diff(expected: &str, actual: &str) -> String {
+        let a: Vec<&str> = expected.lines().collect();
+        let b: Vec<&str> = actual.lines().collect();
+        let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+            }
+        }
+        let mut lines: Vec<(char, &str)> = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                lines.push((' ', a[i]));
+                i += 1;
+                j += 1;
+            } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+                lines.push(('+', b[j]));
+                j += 1;
+            } else {
+                lines.push(('-', a[i]));
+                i += 1;
+            }
+        }
+        let changed: Vec<usize> = lines.iter().enumerate().filter(|(_, (tag, _))| *tag != ' ').map(|(k, _)| k).collect();
+        let mut r = String::new();
+        let mut last_shown: Option<usize> = None;
+        for (k, (tag, line)) in lines.iter().enumerate() {
+            let near = changed.iter().any(|c| c.abs_diff(k) <= Self::CONTEXT_LINES);
+            if !near {
+                continue;
+            }
+            if last_shown.map(|l| l + 1 != k).unwrap_or(k != 0) {
+                r.push_str("  ...\n");
+            }
+            r.push_str(&format!("{tag} {line}\n"));
+            last_shown = Some(k);
+        }
+        r
+    }
+}
\ No newline at end of file
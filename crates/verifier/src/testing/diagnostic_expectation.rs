@@ -0,0 +1,73 @@
+/// Whether an expected diagnostic is an error or a warning.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExpectedDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A diagnostic expected by a fixture, written as a comment:
+///
+/// ```text
+/// // expect-error: ImplicitCoercionToUnrelatedType @ 3:13
+/// // expect-warning: VariableHasNoTypeAnnotation @ 5:5
+/// ```
+///
+/// The kind is the name of a `WhackDiagnosticKind` variant (or of a
+/// parser `DiagnosticKind` variant); line and column are one-based.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DiagnosticExpectation {
+    pub severity: ExpectedDiagnosticSeverity,
+    pub kind: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl DiagnosticExpectation {
+    /// Collects the expectation comments of a fixture. Malformed
+    /// expectations are returned as errors with their one-based line.
+    pub fn parse_all(text: &str) -> Result<Vec<DiagnosticExpectation>, (usize, String)> {
+        let mut r = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let Some(comment) = line.find("//").map(|j| line[j + 2..].trim()) else {
+                continue;
+            };
+            let (severity, rest) = if let Some(rest) = comment.strip_prefix("expect-error:") {
+                (ExpectedDiagnosticSeverity::Error, rest)
+            } else if let Some(rest) = comment.strip_prefix("expect-warning:") {
+                (ExpectedDiagnosticSeverity::Warning, rest)
+            } else {
+                continue;
+            };
+            let Some(expectation) = Self::parse_rest(severity, rest) else {
+                return Err((i + 1, line.trim().to_owned()));
+            };
+            r.push(expectation);
+        }
+        Ok(r)
+    }
+
+    fn parse_rest(severity: ExpectedDiagnosticSeverity, rest: &str) -> Option<DiagnosticExpectation> {
+        let (kind, position) = rest.split_once('@')?;
+        let (line, column) = position.trim().split_once(':')?;
+        let kind = kind.trim();
+        if kind.is_empty() {
+            return None;
+        }
+        Some(DiagnosticExpectation {
+            severity,
+            kind: kind.to_owned(),
+            line: line.trim().parse().ok()?,
+            column: column.trim().parse().ok()?,
+        })
+    }
+}
+
+impl std::fmt::Display for DiagnosticExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            ExpectedDiagnosticSeverity::Error => "error",
+            ExpectedDiagnosticSeverity::Warning => "warning",
+        };
+        write!(f, "{severity}: {} @ {}:{}", self.kind, self.line, self.column)
+    }
+}
\ No newline at end of file
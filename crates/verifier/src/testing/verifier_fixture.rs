@@ -0,0 +1,77 @@
+use crate::ns::*;
+
+/// Verifies an ActionScript fixture and compares the produced
+/// diagnostics against its `// expect-error:` and `// expect-warning:`
+/// comments (see `DiagnosticExpectation`).
+///
+/// ```ignore
+/// let outcome = VerifierFixture::verify(&host, &compiler_options, "fixtures/coercion.as", &text);
+/// outcome.assert_matches();
+/// ```
+pub struct VerifierFixture {
+    pub file_path: String,
+    pub expected: Vec<DiagnosticExpectation>,
+    pub actual: Vec<DiagnosticExpectation>,
+}
+
+impl VerifierFixture {
+    /// Compiles a fixture along with the core library in a `Compilation`
+    /// over `host`, keeping only the diagnostics of the fixture itself.
+    pub fn verify(host: &Rc<Database>, compiler_options: &Rc<CompilerOptions>, file_path: &str, text: &str) -> Self {
+        let expected = match DiagnosticExpectation::parse_all(text) {
+            Ok(list) => list,
+            Err((line, comment)) => panic!("{file_path}:{line}: malformed expectation: {comment}"),
+        };
+        let mut sources = MemorySourceProvider::new();
+        sources.insert(file_path, text);
+        let output = Compilation::run(host, compiler_options, &sources);
+        let mut actual = vec![];
+        for cu in output.compilation_units.iter().filter(|cu| cu.file_path().as_deref() == Some(file_path)) {
+            cu.sort_diagnostics();
+            actual.extend(cu.nested_diagnostics().iter().map(Self::expectation_of));
+        }
+        Self { file_path: file_path.to_owned(), expected, actual }
+    }
+
+    fn expectation_of(diagnostic: &Diagnostic) -> DiagnosticExpectation {
+        let kind = match WhackDiagnostic(diagnostic).fx_kind() {
+            Some(kind) => format!("{kind:?}"),
+            None => format!("{:?}", diagnostic.kind()),
+        };
+        let location = diagnostic.location();
+        DiagnosticExpectation {
+            severity: if diagnostic.is_warning() { ExpectedDiagnosticSeverity::Warning } else { ExpectedDiagnosticSeverity::Error },
+            kind,
+            line: location.first_line_number(),
+            column: location.first_column() + 1,
+        }
+    }
+
+    /// Expected diagnostics that were not produced.
+    pub fn missing(&self) -> Vec<&DiagnosticExpectation> {
+        self.expected.iter().filter(|e| !self.actual.contains(e)).collect()
+    }
+
+    /// Produced diagnostics that were not expected.
+    pub fn unexpected(&self) -> Vec<&DiagnosticExpectation> {
+        self.actual.iter().filter(|e| !self.expected.contains(e)).collect()
+    }
+
+    /// Panics with a report if the produced diagnostics differ from the
+    /// expected ones.
+    pub fn assert_matches(&self) {
+        let missing = self.missing();
+        let unexpected = self.unexpected();
+        if missing.is_empty() && unexpected.is_empty() {
+            return;
+        }
+        let mut report = format!("{}: diagnostics differ from expectations\n", self.file_path);
+        for e in missing {
+            report.push_str(&format!("  missing    {e}\n"));
+        }
+        for e in unexpected {
+            report.push_str(&format!("  unexpected {e}\n"));
+        }
+        panic!("{report}");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+mod compiler_timings;
+pub use compiler_timings::*;
+
+mod compiler_events;
+pub use compiler_events::*;
+
+mod stopwatch;
+pub use stopwatch::*;
+
+mod corpus_benchmark;
+pub use corpus_benchmark::*;
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::ns::*;
+use std::time::Duration;
+
+/// Progress event emitted to a `CompilerEventSubscriber`.
+#[derive(Clone, Debug)]
+pub enum CompilerEvent {
+    PhaseStart(CompilerPhase),
+    PhaseEnd(CompilerPhase, Duration),
+    /// A source file was read for compilation.
+    FileEnqueued(String),
+    /// A package or program starts body verification.
+    UnitStarted(String),
+    /// A package or program finished body verification, as the
+    /// `completed`-th of `total`.
+    UnitVerified {
+        file_path: String,
+        completed: usize,
+        total: usize,
+    },
+    /// The body of a package-level definition was verified.
+    DefinitionVerified {
+        name: String,
+        file_path: String,
+        duration: Duration,
+    },
+    /// Diagnostics of a compilation unit, emitted once compilation ends.
+    Diagnostics {
+        file_path: String,
+        errors: usize,
+        warnings: usize,
+    },
+    /// A retained result was reused, as by `CompileServer`.
+    CacheHit(String),
+    CacheMiss(String),
+    /// The estimated limits of a method exceed one of
+    /// `CompilerOptions::method_limit_thresholds`.
+    MethodLimitExceeded {
+        /// Path of the source file defining the method.
+        artifact: String,
+        method: String,
+        limit: &'static str,
+        value: u32,
+        threshold: u32,
+    },
+}
+
+impl CompilerEvent {
+    /// Formats the event as a single-line JSON object with a `kind` field.
+    pub fn format_json(&self) -> String {
+        match self {
+            Self::PhaseStart(phase) => format!("{{\"kind\":\"phase-start\",\"phase\":{}}}", json_string(phase.name())),
+            Self::PhaseEnd(phase, duration) => format!("{{\"kind\":\"phase-end\",\"phase\":{},\"ms\":{:.3}}}", json_string(phase.name()), duration.as_secs_f64() * 1000.0),
+            Self::FileEnqueued(file_path) => format!("{{\"kind\":\"file-enqueued\",\"file\":{}}}", json_string(file_path)),
+            Self::UnitStarted(file_path) => format!("{{\"kind\":\"unit-started\",\"file\":{}}}", json_string(file_path)),
+            Self::UnitVerified { file_path, completed, total } => format!("{{\"kind\":\"unit-verified\",\"file\":{},\"completed\":{completed},\"total\":{total}}}", json_string(file_path)),
+            Self::DefinitionVerified { name, file_path, duration } => format!("{{\"kind\":\"definition-verified\",\"name\":{},\"file\":{},\"ms\":{:.3}}}", json_string(name), json_string(file_path), duration.as_secs_f64() * 1000.0),
+            Self::Diagnostics { file_path, errors, warnings } => format!("{{\"kind\":\"diagnostics\",\"file\":{},\"errors\":{errors},\"warnings\":{warnings}}}", json_string(file_path)),
+            Self::CacheHit(key) => format!("{{\"kind\":\"cache-hit\",\"key\":{}}}", json_string(key)),
+            Self::CacheMiss(key) => format!("{{\"kind\":\"cache-miss\",\"key\":{}}}", json_string(key)),
+            Self::MethodLimitExceeded { artifact, method, limit, value, threshold } => format!("{{\"kind\":\"method-limit-exceeded\",\"artifact\":{},\"method\":{},\"limit\":{},\"value\":{value},\"threshold\":{threshold}}}", json_string(artifact), json_string(method), json_string(limit)),
+        }
+    }
+}
+
+/// Receives progress events of a compilation, such as to show real-time
+/// progress in a build dashboard.
+///
+/// ```ignore
+/// struct Printer;
+///
+/// impl CompilerEventSubscriber for Printer {
+///     fn on_event(&self, event: &CompilerEvent) {
+///         eprintln!("{}", event.format_json());
+///     }
+/// }
+///
+/// verifier.set_event_subscriber(Some(Rc::new(Printer)));
+/// ```
+pub trait CompilerEventSubscriber {
+    fn on_event(&self, event: &CompilerEvent);
+}
+
+/// Emits the `Diagnostics` events of the given compilation units.
+pub(crate) fn emit_diagnostic_counts(subscriber: &dyn CompilerEventSubscriber, compilation_units: &[Rc<CompilationUnit>]) {
+    for cu in compilation_units.iter() {
+        let diagnostics = cu.nested_diagnostics();
+        let warnings = diagnostics.iter().filter(|d| d.is_warning()).count();
+        subscriber.on_event(&CompilerEvent::Diagnostics {
+            file_path: cu.file_path().unwrap_or_default(),
+            errors: diagnostics.len() - warnings,
+            warnings,
+        });
+    }
+}
\ No newline at end of file
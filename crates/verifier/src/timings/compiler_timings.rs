@@ -0,0 +1,148 @@
+use crate::ns::*;
+use std::time::Duration;
+
+/// A compilation phase measured by `CompilerTimings`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum CompilerPhase {
+    Lexing,
+    Parsing,
+    DeclarationCollection,
+    Verification,
+    Codegen,
+    Emit,
+}
+
+impl CompilerPhase {
+    pub const LIST: [CompilerPhase; 6] = [
+        Self::Lexing,
+        Self::Parsing,
+        Self::DeclarationCollection,
+        Self::Verification,
+        Self::Codegen,
+        Self::Emit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lexing => "lexing",
+            Self::Parsing => "parsing",
+            Self::DeclarationCollection => "declaration-collection",
+            Self::Verification => "verification",
+            Self::Codegen => "codegen",
+            Self::Emit => "emit",
+        }
+    }
+}
+
+/// Per-phase and per-file timing information.
+///
+/// Durations are accumulated; adding the same phase or file
+/// twice sums both durations. Phases never added, such as lexing, which
+/// the parser interleaves with parsing, and codegen and emit, which no
+/// `Compilation` runs yet, are reported as not measured.
+///
+/// ```ignore
+/// let start = Stopwatch::start();
+/// // parse...
+/// timings.add_phase(CompilerPhase::Parsing, start.elapsed());
+/// timings.add_file("src/Main.as", start.elapsed());
+///
+/// println!("{}", timings.format_human());
+/// ```
+#[derive(Clone, Default)]
+pub struct CompilerTimings {
+    phases: HashMap<CompilerPhase, Duration>,
+    files: HashMap<String, Duration>,
+}
+
+impl CompilerTimings {
+    /// Number of files listed as hot spots in the human-readable output.
+    pub const HOT_SPOT_COUNT: usize = 10;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_phase(&mut self, phase: CompilerPhase, duration: Duration) {
+        *self.phases.entry(phase).or_insert(Duration::ZERO) += duration;
+    }
+
+    pub fn add_file(&mut self, file_path: &str, duration: Duration) {
+        *self.files.entry(file_path.to_owned()).or_insert(Duration::ZERO) += duration;
+    }
+
+    /// Adds the durations of `other`.
+    pub fn extend(&mut self, other: &CompilerTimings) {
+        for (phase, duration) in other.phases.iter() {
+            self.add_phase(*phase, *duration);
+        }
+        for (file_path, duration) in other.files.iter() {
+            self.add_file(file_path, *duration);
+        }
+    }
+
+    pub fn phase(&self, phase: CompilerPhase) -> Duration {
+        self.phases.get(&phase).cloned().unwrap_or(Duration::ZERO)
+    }
+
+    /// Whether a duration was added for `phase`.
+    pub fn measured(&self, phase: CompilerPhase) -> bool {
+        self.phases.contains_key(&phase)
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.values().sum()
+    }
+
+    /// Returns files sorted by descending duration.
+    pub fn hot_spots(&self) -> Vec<(String, Duration)> {
+        let mut r: Vec<(String, Duration)> = self.files.iter().map(|(path, d)| (path.clone(), *d)).collect();
+        r.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        r
+    }
+
+    pub fn clear(&mut self) {
+        self.phases.clear();
+        self.files.clear();
+    }
+
+    /// Formats the timings as a human-readable report.
+    pub fn format_human(&self) -> String {
+        let mut r = String::new();
+        r.push_str("Phase timings:\n");
+        for phase in CompilerPhase::LIST {
+            let d = if self.measured(phase) { Self::format_duration(self.phase(phase)) } else { "n/a".to_owned() };
+            r.push_str(&format!("  {:<24}{:>12}\n", phase.name(), d));
+        }
+        r.push_str(&format!("  {:<24}{:>12}\n", "total", Self::format_duration(self.total())));
+
+        let hot_spots = self.hot_spots();
+        if !hot_spots.is_empty() {
+            r.push_str("Hot spots:\n");
+            for (path, d) in hot_spots.iter().take(Self::HOT_SPOT_COUNT) {
+                r.push_str(&format!("  {:>12}  {}\n", Self::format_duration(*d), path));
+            }
+        }
+        r
+    }
+
+    /// Formats the timings as JSON. Durations are given in milliseconds,
+    /// and are `null` for phases not measured.
+    pub fn format_json(&self) -> String {
+        let phases = CompilerPhase::LIST.iter()
+            .map(|phase| format!("\"{}\":{}", phase.name(), if self.measured(*phase) { Self::millis(self.phase(*phase)) } else { "null".to_owned() }))
+            .collect::<Vec<_>>().join(",");
+        let files = self.hot_spots().iter()
+            .map(|(path, d)| format!("{{\"path\":{},\"ms\":{}}}", json_string(path), Self::millis(*d)))
+            .collect::<Vec<_>>().join(",");
+        format!("{{\"phases\":{{{phases}}},\"total\":{},\"files\":[{files}]}}", Self::millis(self.total()))
+    }
+
+    fn millis(d: Duration) -> String {
+        format!("{:.3}", d.as_secs_f64() * 1000.0)
+    }
+
+    fn format_duration(d: Duration) -> String {
+        format!("{}ms", Self::millis(d))
+    }
+}
\ No newline at end of file
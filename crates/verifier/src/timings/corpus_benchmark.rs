@@ -0,0 +1,80 @@
+use crate::ns::*;
+use std::time::Duration;
+
+/// Throughput of the verifier over a corpus, measured by `bench_corpus()`.
+#[derive(Clone, Debug)]
+pub struct CorpusBenchmark {
+    pub files: usize,
+    /// Directives in the corpus, including nested statements and
+    /// definitions.
+    pub statements: usize,
+    pub parsing: Duration,
+    pub verification: Duration,
+    /// Whether the corpus produced errors, in which case verification
+    /// may have stopped early and the figures are not comparable.
+    pub invalidated: bool,
+}
+
+impl CorpusBenchmark {
+    pub fn files_per_second(&self) -> f64 {
+        self.files as f64 / self.verification.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn statements_per_second(&self) -> f64 {
+        self.statements as f64 / self.verification.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn format_human(&self) -> String {
+        format!("{} files, {} statements\nparsing: {:.2?}\nverification: {:.2?} ({:.1} files/s, {:.1} statements/s)\n",
+            self.files, self.statements, self.parsing, self.verification, self.files_per_second(), self.statements_per_second())
+    }
+}
+
+/// Parses and verifies the ActionScript sources under a directory
+/// along with the embedded core library, measuring verifier throughput.
+///
+/// ```ignore
+/// let benchmark = bench_corpus("tests/corpus");
+/// print!("{}", benchmark.format_human());
+/// ```
+#[cfg(feature = "fs")]
+pub fn bench_corpus(path: &str) -> CorpusBenchmark {
+    let sources = FileSystemSourceProvider { source_path: vec![path.to_owned()] };
+    bench_sources(&Rc::new(CompilerOptions::default()), &sources)
+}
+
+/// Measures verifier throughput over the ActionScript sources of a
+/// `SourceProvider`, compiled through `Compilation::run()`. The core
+/// library selected by `compiler_options.core_library` and MXML sources
+/// are compiled but not counted in the figures.
+pub fn bench_sources(compiler_options: &Rc<CompilerOptions>, sources: &dyn SourceProvider) -> CorpusBenchmark {
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, compiler_options, sources);
+
+    let source_files: HashSet<String> = sources.source_files().into_iter().filter(|p| p.ends_with(".as")).collect();
+    let mut files = 0;
+    let mut counter = StatementCounter(0);
+    for program in output.programs.iter() {
+        if source_files.contains(&program.location.compilation_unit().file_path().unwrap_or_default()) {
+            counter.visit_program(program);
+            files += 1;
+        }
+    }
+
+    CorpusBenchmark {
+        files,
+        statements: counter.0,
+        parsing: output.timings.phase(CompilerPhase::Parsing),
+        verification: output.timings.phase(CompilerPhase::DeclarationCollection) + output.timings.phase(CompilerPhase::Verification),
+        invalidated: output.invalidated,
+    }
+}
+
+struct StatementCounter(usize);
+
+impl Visitor for StatementCounter {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        self.0 += 1;
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+/// Measures elapsed time for `CompilerTimings`.
+///
+/// `wasm32-unknown-unknown` has no clock, in which case durations are zero.
+#[derive(Copy, Clone, Debug)]
+pub struct Stopwatch {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Self {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    pub fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
\ No newline at end of file
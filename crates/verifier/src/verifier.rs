@@ -1,6 +1,33 @@
 mod verifier;
 pub use verifier::*;
 
+mod cancellation_token;
+pub use cancellation_token::*;
+
+mod coercion_audit;
+pub use coercion_audit::*;
+
+mod dynamic_access_audit;
+pub use dynamic_access_audit::*;
+
+mod config_elimination;
+pub use config_elimination::*;
+
+mod scope_chain;
+pub use scope_chain::*;
+
+mod scope_snapshot;
+pub use scope_snapshot::*;
+
+mod type_relation_cache;
+pub use type_relation_cache::*;
+
+mod pass_manager;
+pub use pass_manager::*;
+
+mod referenced_entity;
+pub use referenced_entity::*;
+
 mod exp;
 pub(crate) use exp::*;
 
@@ -28,5 +55,134 @@ pub(crate) use directive::*;
 mod statement;
 pub(crate) use statement::*;
 
+mod collection_types;
+pub(crate) use collection_types::*;
+
+mod constructor_initialization;
+pub(crate) use constructor_initialization::*;
+
 mod control_flow;
-pub(crate) use control_flow::*;
\ No newline at end of file
+pub(crate) use control_flow::*;
+
+mod declaration_check;
+pub(crate) use declaration_check::*;
+
+mod resource_bundle;
+pub(crate) use resource_bundle::*;
+
+mod mxml_states;
+pub(crate) use mxml_states::*;
+
+mod mxml_ids;
+pub(crate) use mxml_ids::*;
+
+mod mxml_default_property;
+pub(crate) use mxml_default_property::*;
+
+mod skin_contract;
+pub(crate) use skin_contract::*;
+
+mod worker_sharing;
+pub(crate) use worker_sharing::*;
+
+mod inline_metadata;
+pub(crate) use inline_metadata::*;
+
+mod tail_call;
+pub(crate) use tail_call::*;
+
+mod unused_imports;
+pub(crate) use unused_imports::*;
+
+mod recursive_accessor;
+pub(crate) use recursive_accessor::*;
+
+mod font_embeds;
+pub(crate) use font_embeds::*;
+
+mod language_feature_gates;
+pub(crate) use language_feature_gates::*;
+
+mod conditional_functions;
+pub(crate) use conditional_functions::*;
+
+mod boolean_tests;
+pub(crate) use boolean_tests::*;
+
+mod loop_redeclarations;
+pub(crate) use loop_redeclarations::*;
+
+mod deferred_statements;
+pub(crate) use deferred_statements::*;
+
+mod package_definitions;
+pub(crate) use package_definitions::*;
+
+mod metadata_retention;
+pub(crate) use metadata_retention::*;
+
+mod reflected_definitions;
+pub use reflected_definitions::*;
+
+mod numeric_promotion;
+pub(crate) use numeric_promotion::*;
+
+mod discarded_results;
+pub(crate) use discarded_results::*;
+
+mod api_version;
+pub(crate) use api_version::*;
+
+mod api_stability;
+pub(crate) use api_stability::*;
+
+mod target_profile_references;
+pub(crate) use target_profile_references::*;
+
+mod module_exports;
+pub(crate) use module_exports::*;
+
+mod temporal_dead_zone;
+pub(crate) use temporal_dead_zone::*;
+
+mod loop_capture;
+pub(crate) use loop_capture::*;
+
+mod const_parameters;
+pub(crate) use const_parameters::*;
+
+mod extension_methods;
+pub use extension_methods::*;
+
+mod operator_overloading;
+pub use operator_overloading::*;
+
+mod json_type;
+pub(crate) use json_type::*;
+
+mod remote_class;
+pub(crate) use remote_class::*;
+
+mod native_extension_ids;
+pub(crate) use native_extension_ids::*;
+
+mod shadowing;
+pub(crate) use shadowing::*;
+
+mod structural_conformance;
+pub use structural_conformance::*;
+
+mod type_hierarchy;
+pub use type_hierarchy::*;
+
+mod static_initialization;
+pub(crate) use static_initialization::*;
+
+mod super_statement;
+pub(crate) use super_statement::*;
+
+mod throws_metadata;
+pub use throws_metadata::*;
+
+mod uncaught_handler_errors;
+pub(crate) use uncaught_handler_errors::*;
\ No newline at end of file
@@ -0,0 +1,103 @@
+use crate::ns::*;
+
+/// Reports references to definitions marked `[Experimental]` or
+/// `[InternalAPI]` from outside their owning package, at the severities
+/// of `CompilerOptions::api_stability`. Markers on a class apply to
+/// its members.
+///
+/// The owning package defaults to the package of the definition and may
+/// be widened through the `package` entry, as in
+/// `[InternalAPI(package="mx")]`; its subpackages are included.
+pub(crate) struct ApiStabilitySubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    options: ApiStabilityOptions,
+    current_package: Option<Entity>,
+}
+
+impl<'a> ApiStabilitySubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], options: &ApiStabilityOptions) {
+        if options.experimental == LintSeverity::Allow && options.internal_api == LintSeverity::Allow {
+            return;
+        }
+        let host = verifier.host.clone();
+        let mut subverifier = Self { verifier, options: options.clone(), current_package: None };
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                subverifier.current_package = Some(host.factory().create_package(pckgdef.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>()));
+                subverifier.visit_block(&pckgdef.block);
+            }
+            subverifier.current_package = Some(host.top_level_package());
+            for drtv in program.directives.iter() {
+                subverifier.visit_directive(drtv);
+            }
+        }
+    }
+
+    fn package_of(definition: &Entity) -> Option<Entity> {
+        let mut pckg = definition.parent();
+        while let Some(p) = pckg.as_ref().filter(|p| !p.is::<Package>()) {
+            pckg = p.parent();
+        }
+        pckg
+    }
+
+    /// Returns the marker meta-data of a definition or of its class.
+    fn marker(definition: &Entity, name: &str) -> Option<Rc<Metadata>> {
+        let find = |entity: &Entity| entity.metadata().iter().find(|m| m.name.0 == name);
+        find(definition).or_else(|| definition.parent().filter(|p| p.is::<ClassType>() || p.is::<InterfaceType>()).and_then(|p| find(&p)))
+    }
+
+    /// Returns the owning package name of a marked definition.
+    fn owner(definition: &Entity, metadata: &Metadata) -> Vec<String> {
+        let entry = metadata.entries.iter().flatten()
+            .find(|entry| entry.key.as_ref().map(|(k, _)| k == "package").unwrap_or(false))
+            .map(|entry| match entry.value.as_ref() {
+                MetadataValue::String(val) => val.0.clone(),
+                MetadataValue::IdentifierString(val) => val.0.clone(),
+            });
+        match entry {
+            Some(name) => name.split('.').filter(|s| !s.is_empty()).map(|s| s.to_owned()).collect(),
+            None => Self::package_of(definition).map(|p| p.fully_qualified_name_list()).unwrap_or_default(),
+        }
+    }
+
+    fn is_within_owner(&self, owner: &[String]) -> bool {
+        let Some(name) = self.current_package.as_ref().map(|p| p.fully_qualified_name_list()) else {
+            return false;
+        };
+        name.len() >= owner.len() && name[..owner.len()] == owner[..]
+    }
+
+    fn report(&mut self, severity: LintSeverity, location: &Location, kind: WhackDiagnosticKind, args: Vec<Rc<dyn DiagnosticArgument>>) {
+        match severity {
+            LintSeverity::Allow => {},
+            LintSeverity::Warning => self.verifier.add_warning(location, kind, args),
+            LintSeverity::Error => self.verifier.add_verify_error(location, kind, args),
+        }
+    }
+
+    fn verify_reference(&mut self, definition: &Entity, location: &Location) {
+        let name = definition.name().local_name();
+        if let Some(metadata) = Self::marker(definition, "InternalAPI") {
+            let owner = Self::owner(definition, &metadata);
+            if !self.is_within_owner(&owner) {
+                self.report(self.options.internal_api, location, WhackDiagnosticKind::InternalApiReference, diagarg![name.clone(), owner.join(".")]);
+            }
+        }
+        if let Some(metadata) = Self::marker(definition, "Experimental") {
+            let owner = Self::owner(definition, &metadata);
+            if !self.is_within_owner(&owner) {
+                self.report(self.options.experimental, location, WhackDiagnosticKind::ExperimentalApiReference, diagarg![name]);
+            }
+        }
+    }
+}
+
+impl<'a> Visitor for ApiStabilitySubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Some(definition) = referenced_entity(&self.verifier.host, exp) {
+            self.verify_reference(&definition, &exp.location());
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use crate::ns::*;
+
+/// Reports references to definitions introduced by a runtime version
+/// later than `CompilerOptions::target_runtime_version`.
+///
+/// The version of a definition is given by `[API("11.4")]` meta-data on
+/// it or on its class, as in library stubs, and otherwise by a bundled
+/// table of core APIs, where an entry may name a whole package. Numeric
+/// API codes of SDK stubs, such as `[API("667")]`, identify a runtime
+/// and not a version, and are ignored.
+pub(crate) struct ApiVersionSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    target: RuntimeVersion,
+}
+
+impl<'a> ApiVersionSubverifier<'a> {
+    /// Versions introducing core APIs, by fully qualified name.
+    const CORE_API_VERSIONS: &'static [(&'static str, u32, u32)] = &[
+        ("flash.text.engine", 10, 0),
+        ("flash.globalization", 10, 1),
+        ("flash.events.GestureEvent", 10, 1),
+        ("flash.events.TouchEvent", 10, 1),
+        ("flash.events.UncaughtErrorEvent", 10, 1),
+        ("flash.events.UncaughtErrorEvents", 10, 1),
+        ("flash.net.NetGroup", 10, 1),
+        ("flash.ui.Multitouch", 10, 1),
+        ("flash.media.StageVideo", 10, 2),
+        ("flash.display3D", 11, 0),
+        ("flash.display.Stage3D", 11, 0),
+        ("flash.system.Worker", 11, 4),
+        ("flash.system.WorkerDomain", 11, 4),
+        ("flash.system.MessageChannel", 11, 4),
+        ("flash.concurrent.Mutex", 11, 4),
+        ("flash.concurrent.Condition", 11, 5),
+        ("flash.ui.GameInput", 11, 8),
+    ];
+
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], target: RuntimeVersion) {
+        let mut subverifier = Self { verifier, target };
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                subverifier.visit_block(&pckgdef.block);
+            }
+            for drtv in program.directives.iter() {
+                subverifier.visit_directive(drtv);
+            }
+        }
+    }
+
+    /// Returns the version introducing a definition, if known.
+    fn required_version(definition: &Entity) -> Option<RuntimeVersion> {
+        let class = definition.parent().filter(|p| p.is::<ClassType>() || p.is::<InterfaceType>());
+        if let Some(version) = Self::metadata_version(definition).or_else(|| class.as_ref().and_then(Self::metadata_version)) {
+            return Some(version);
+        }
+        let name = definition.to_string();
+        Self::CORE_API_VERSIONS.iter()
+            .filter(|(api, _, _)| name == *api || name.strip_prefix(*api).map(|rest| rest.starts_with('.')).unwrap_or(false))
+            .map(|(_, major, minor)| RuntimeVersion::new(*major, *minor))
+            .max()
+    }
+
+    fn metadata_version(entity: &Entity) -> Option<RuntimeVersion> {
+        let metadata = entity.metadata().iter().find(|m| m.name.0 == "API")?.clone();
+        let entry = metadata.entries.iter().flatten()
+            .find(|entry| entry.key.as_ref().map(|(k, _)| k == "version").unwrap_or(true))?;
+        let version = match entry.value.as_ref() {
+            MetadataValue::String(val) => &val.0,
+            MetadataValue::IdentifierString(val) => &val.0,
+        };
+        if !version.contains('.') {
+            return None;
+        }
+        RuntimeVersion::parse(version)
+    }
+
+    fn verify_reference(&mut self, definition: &Entity, location: &Location) {
+        let Some(version) = Self::required_version(definition) else {
+            return;
+        };
+        if version > self.target {
+            let name = definition.name().local_name();
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::ApiRequiresRuntimeVersion, diagarg![name, version.to_string(), self.target.to_string()]);
+        }
+    }
+}
+
+impl<'a> Visitor for ApiVersionSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Some(definition) = referenced_entity(&self.verifier.host, exp) {
+            self.verify_reference(&definition, &exp.location());
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -53,6 +53,35 @@ impl ArraySubverifier {
             if elision_found {
                 verifier.add_verify_error(&literal.location, WhackDiagnosticKind::UnexpectedElision, diagarg![]);
             }
+        } else if let Some(element_type) = context_type_esc.vector_element_type(&verifier.host)? {
+            // Array literal in a Vector context, coercing each element
+            // to the Vector element type.
+            let mut elision_found = false;
+            for elem in &literal.elements {
+                match elem {
+                    Element::Elision => {
+                        elision_found = true;
+                    },
+                    Element::Rest((exp, _)) => {
+                        verifier.imp_coerce_exp(exp, &context_type_esc)?;
+                    },
+                    Element::Expression(exp) => {
+                        verifier.imp_coerce_exp(exp, &element_type)?;
+                    },
+                }
+            }
+            if elision_found {
+                verifier.add_verify_error(&literal.location, WhackDiagnosticKind::UnexpectedElision, diagarg![]);
+            }
+        } else if let Some(element_type) = CollectionTypes::set_element_type(&verifier.host, &context_type_esc) {
+            // Array literal in a Set context, coercing each element
+            // to the Set element type.
+            Self::verify_collection_elements(verifier, literal, &context_type_esc, &element_type)?;
+        } else if let Some((key_type, value_type)) = CollectionTypes::map_key_value_types(&verifier.host, &context_type_esc) {
+            // Array literal in a Map context, coercing each element
+            // to a [K, V] tuple, as in `[["a", 1], ["b", 2]]`.
+            let entry_type = verifier.host.factory().create_tuple_type(vec![key_type, value_type]);
+            Self::verify_collection_elements(verifier, literal, &context_type_esc, &entry_type)?;
         } else {
             let element_type = context_type_esc.array_element_type(&verifier.host)?;
             if let Some(element_type) = element_type {
@@ -92,6 +121,27 @@ impl ArraySubverifier {
         Ok(Some(verifier.host.factory().create_value(&context_type)))
     }
 
+    fn verify_collection_elements(verifier: &mut Subverifier, literal: &ArrayLiteral, collection_type: &Entity, element_type: &Entity) -> Result<(), DeferError> {
+        let mut elision_found = false;
+        for elem in &literal.elements {
+            match elem {
+                Element::Elision => {
+                    elision_found = true;
+                },
+                Element::Rest((exp, _)) => {
+                    verifier.imp_coerce_exp(exp, collection_type)?;
+                },
+                Element::Expression(exp) => {
+                    verifier.imp_coerce_exp(exp, element_type)?;
+                },
+            }
+        }
+        if elision_found {
+            verifier.add_verify_error(&literal.location, WhackDiagnosticKind::UnexpectedElision, diagarg![]);
+        }
+        Ok(())
+    }
+
     pub fn verify_vector_literal(verifier: &mut Subverifier, literal: &VectorLiteral, _context: &VerifierExpressionContext) -> Result<Option<Entity>, DeferError> {
         let element_type = verifier.verify_type_expression(&literal.element_type)?;
         let vector_type = if element_type.is_none() {
@@ -100,12 +150,13 @@ impl ArraySubverifier {
             verifier.host.vector_type().defer()?.apply_type(&verifier.host, &verifier.host.vector_type().defer()?.type_params().unwrap(), &shared_array![element_type.clone().unwrap()])
         };
 
+        let mut elision_found = false;
         if !vector_type.is::<InvalidationEntity>() {
             let element_type = element_type.unwrap();
             for elem in &literal.elements {
                 match elem {
                     Element::Elision => {
-                        panic!();
+                        elision_found = true;
                     },
                     Element::Rest((exp, _)) => {
                         verifier.imp_coerce_exp(exp, &vector_type)?;
@@ -119,7 +170,7 @@ impl ArraySubverifier {
             for elem in &literal.elements {
                 match elem {
                     Element::Elision => {
-                        panic!();
+                        elision_found = true;
                     },
                     Element::Rest((exp, _)) => {
                         verifier.verify_expression(exp, &default())?;
@@ -131,6 +182,10 @@ impl ArraySubverifier {
             }
         }
 
+        if elision_found {
+            verifier.add_verify_error(&literal.location, WhackDiagnosticKind::UnexpectedElision, diagarg![]);
+        }
+
         if vector_type.is::<InvalidationEntity>() {
             return Ok(Some(verifier.host.invalidation_entity()));
         }
@@ -79,10 +79,13 @@ impl AssignmentDestructuringSubverifier {
             verifier.host.node_mapping().set(pattern, None);
             return Ok(());
         };
+        if val.read_only(&verifier.host) {
+            verifier.add_verify_error(&id.location, WhackDiagnosticKind::EntityIsReadOnly, diagarg![]);
+        }
 
         // Implicit coercion
         let Some(val) = ConversionMethods(&verifier.host).implicit(&val, &init_st, false)? else {
-            verifier.add_verify_error(&id.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![val.static_type(&verifier.host), init_st]);
+            verifier.add_verify_error(&id.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![verifier.display_type(&val.static_type(&verifier.host)), verifier.display_type(&init_st)]);
             verifier.host.node_mapping().set(pattern, None);
             return Ok(());
         };
@@ -208,9 +211,9 @@ impl AssignmentDestructuringSubverifier {
                     }
                 },
                 Element::Rest((restpat, _)) => {
-                    let array_type_of_any = verifier.host.array_type_of_any()?;
+                    let rest_type = DestructuringDeclarationSubverifier::tuple_rest_type(verifier, tuple_type, i)?;
                     rest_found = true;
-                    Self::verify_pattern(verifier, restpat, &verifier.host.factory().create_value(&array_type_of_any))?;
+                    Self::verify_pattern(verifier, restpat, &verifier.host.factory().create_value(&rest_type))?;
                 },
                 Element::Elision => {},
             }
@@ -380,7 +383,7 @@ impl AssignmentDestructuringSubverifier {
 
                                 // Implicit coercion
                                 let Some(_) = ConversionMethods(&verifier.host).implicit(&postval, &target.static_type(&verifier.host), false)? else {
-                                    verifier.add_verify_error(&name_loc, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![postval.static_type(&verifier.host), target.static_type(&verifier.host)]);
+                                    verifier.add_verify_error(&name_loc, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![verifier.display_type(&postval.static_type(&verifier.host)), verifier.display_type(&target.static_type(&verifier.host))]);
                                     verifier.host.node_mapping().set(pattern, None);
                                     continue;
                                 };
@@ -455,6 +458,10 @@ impl AssignmentDestructuringSubverifier {
         verifier.detect_local_capture(&r);
 
         // Post-processing
-        verifier.reference_post_processing(r, &default())
+        let val = verifier.reference_post_processing(r, &default())?;
+        if val.as_ref().map(|v| v.read_only(&verifier.host)).unwrap_or(false) {
+            verifier.add_verify_error(&shorthand.1, WhackDiagnosticKind::EntityIsReadOnly, diagarg![]);
+        }
+        Ok(val)
     }
 }
\ No newline at end of file
@@ -0,0 +1,33 @@
+use crate::ns::*;
+
+/// Warns on tests of `if`, `while`, `do`, `for` and conditional
+/// expressions that are always truthy, such as a `new` expression, or
+/// that refer to a method without calling it.
+pub(crate) struct BooleanTest;
+
+impl BooleanTest {
+    pub fn check(verifier: &mut Subverifier, test: &Rc<Expression>, value: Option<&Entity>) {
+        let mut exp = test;
+        while let Expression::Paren(paren) = exp.as_ref() {
+            exp = &paren.expression;
+        }
+        match exp.as_ref() {
+            Expression::New(_) |
+            Expression::ArrayLiteral(_) |
+            Expression::ObjectInitializer(_) |
+            Expression::VectorLiteral(_) |
+            Expression::Function(_) => {
+                verifier.add_warning(&test.location(), WhackDiagnosticKind::TestIsAlwaysTruthy, diagarg![]);
+                return;
+            },
+            _ => {},
+        }
+
+        let Some(property) = value.and_then(referenced_entity_of_value) else {
+            return;
+        };
+        if property.is::<MethodSlot>() {
+            verifier.add_warning(&test.location(), WhackDiagnosticKind::MethodReferenceUsedAsTest, diagarg![property.name().local_name()]);
+        }
+    }
+}
\ No newline at end of file
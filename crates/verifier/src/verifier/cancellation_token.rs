@@ -0,0 +1,32 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Cancels an in-flight verification from another thread, such as
+/// when a language server receives a newer version of a document.
+///
+/// The verifier checks the token between directives and statements;
+/// a cancelled verification leaves incomplete results that should be
+/// discarded along with the `Database`.
+///
+/// ```ignore
+/// let token = CancellationToken::new();
+/// verifier.set_cancellation_token(Some(token.clone()));
+/// // elsewhere
+/// token.cancel();
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// An implicit coercion from `*` or `Object` to a more specific type.
+#[derive(Clone, Debug)]
+pub struct AuditedCoercion {
+    pub location: Location,
+    pub from_type: String,
+    pub to_type: String,
+}
+
+/// Report of implicit coercions from `*` and `Object` performed during
+/// verification, grouped by file path. Recorded only when
+/// `CompilerOptions::coercion_audit` is set.
+#[derive(Clone, Default, Debug)]
+pub struct CoercionAuditReport {
+    pub files: BTreeMap<String, Vec<AuditedCoercion>>,
+}
+
+impl CoercionAuditReport {
+    pub fn add(&mut self, location: &Location, from_type: &Entity, to_type: &Entity) {
+        let file_path = location.compilation_unit().file_path().unwrap_or("<unknown>".into());
+        self.files.entry(file_path).or_default().push(AuditedCoercion {
+            location: location.clone(),
+            from_type: from_type.to_string(),
+            to_type: to_type.to_string(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Number of coercions across all files.
+    pub fn len(&self) -> usize {
+        self.files.values().map(|c| c.len()).sum()
+    }
+
+    pub fn format_human(&self) -> String {
+        if self.files.is_empty() {
+            return "No implicit coercions from * or Object.\n".into();
+        }
+        let mut r = String::new();
+        for (file_path, coercions) in self.files.iter() {
+            r.push_str(&format!("{} ({} coercions):\n", file_path, coercions.len()));
+            for c in coercions.iter() {
+                r.push_str(&format!("  {}:{}: {} to {}\n", c.location.first_line_number(), c.location.first_column() + 1, c.from_type, c.to_type));
+            }
+        }
+        r
+    }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use crate::ns::*;
+
+/// Typing of the top-level `Map.<K, V>` and `Set.<T>` collection classes.
+///
+/// Unlike `Dictionary`, whose keys and values are both `*`, these classes
+/// carry their key and value types as type arguments.
+pub(crate) struct CollectionTypes;
+
+impl CollectionTypes {
+    /// Returns `(K, V)` if the given type is a `Map.<K, V>` substitution.
+    pub fn map_key_value_types(host: &Database, t: &Entity) -> Option<(Entity, Entity)> {
+        let map_t = Self::top_level_class(host, "Map")?;
+        if !t.type_after_sub_has_origin(&map_t) {
+            return None;
+        }
+        let args = t.substitute_types();
+        if args.length() != 2 {
+            return None;
+        }
+        Some((args.get(0)?, args.get(1)?))
+    }
+
+    /// Returns `T` if the given type is a `Set.<T>` substitution.
+    pub fn set_element_type(host: &Database, t: &Entity) -> Option<Entity> {
+        let set_t = Self::top_level_class(host, "Set")?;
+        if !t.type_after_sub_has_origin(&set_t) {
+            return None;
+        }
+        t.substitute_types().get(0)
+    }
+
+    fn top_level_class(host: &Database, name: &str) -> Option<Entity> {
+        let pckg = host.top_level_package();
+        let qname = host.factory().create_qname(&pckg.public_ns()?, name.to_owned());
+        pckg.properties(host).get(&qname).filter(|t| t.is::<ClassType>())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use crate::ns::*;
+
+/// Warns on function declarations nested in conditional statements,
+/// such as `if` and loops. These are hoisted to the enclosing function
+/// regardless of the condition, which runtimes do not agree on.
+pub(crate) struct ConditionalFunctionSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Number of enclosing conditional statements within the current
+    /// function.
+    conditional_depth: usize,
+}
+
+impl<'a> ConditionalFunctionSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, conditional_depth: 0 };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+}
+
+impl<'a> Visitor for ConditionalFunctionSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(defn) => {
+                if self.conditional_depth != 0 {
+                    if let FunctionName::Identifier((name, location)) = &defn.name {
+                        self.verifier.add_warning(location, WhackDiagnosticKind::ConditionalFunctionDeclaration, diagarg![name.clone()]);
+                    }
+                }
+                walk_directive(self, drtv);
+            },
+            Directive::IfStatement(_) |
+            Directive::SwitchStatement(_) |
+            Directive::SwitchTypeStatement(_) |
+            Directive::DoStatement(_) |
+            Directive::WhileStatement(_) |
+            Directive::ForStatement(_) |
+            Directive::ForInStatement(_) |
+            Directive::WithStatement(_) |
+            Directive::TryStatement(_) => {
+                self.conditional_depth += 1;
+                walk_directive(self, drtv);
+                self.conditional_depth -= 1;
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        let conditional_depth = std::mem::replace(&mut self.conditional_depth, 0);
+        walk_function_common(self, common);
+        self.conditional_depth = conditional_depth;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,135 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+/// A region of code excluded by a false configuration constant, as
+/// `CONFIG::debug { ... }` while `CONFIG::debug` is `false`.
+#[derive(Clone, Debug)]
+pub struct EliminatedRegion {
+    pub location: Location,
+    /// Name of the constant, as `CONFIG::debug`.
+    pub condition: String,
+}
+
+impl EliminatedRegion {
+    pub fn line_count(&self) -> usize {
+        self.location.last_line_number() + 1 - self.location.first_line_number()
+    }
+}
+
+/// Report of the regions eliminated by configuration constants, grouped
+/// by file path, along with the constants they were eliminated under.
+/// Recorded only when `CompilerOptions::config_elimination_report` is set.
+#[derive(Clone, Default, Debug)]
+pub struct ConfigEliminationReport {
+    /// Configuration constants and their values.
+    pub constants: BTreeMap<String, String>,
+    pub files: BTreeMap<String, Vec<EliminatedRegion>>,
+}
+
+impl ConfigEliminationReport {
+    pub fn add(&mut self, region: EliminatedRegion) {
+        let file_path = region.location.compilation_unit().file_path().unwrap_or("<unknown>".into());
+        self.files.entry(file_path).or_default().push(region);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Number of regions across all files.
+    pub fn len(&self) -> usize {
+        self.files.values().map(|r| r.len()).sum()
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = String::new();
+        let constants = self.constants.iter().map(|(name, value)| format!("{name}={value}")).collect::<Vec<_>>();
+        r.push_str(&format!("Configuration: {}\n", if constants.is_empty() { "none".to_owned() } else { constants.join(", ") }));
+        if self.files.is_empty() {
+            r.push_str("No eliminated regions.\n");
+            return r;
+        }
+        for (file_path, regions) in self.files.iter() {
+            let lines: usize = regions.iter().map(|region| region.line_count()).sum();
+            r.push_str(&format!("{} ({} regions, {} lines):\n", file_path, regions.len(), lines));
+            for region in regions.iter() {
+                r.push_str(&format!("  {}-{}: {}\n", region.location.first_line_number(), region.location.last_line_number(), region.condition));
+            }
+        }
+        r
+    }
+}
+
+/// Records the regions eliminated by false configuration constants and
+/// checks the configuration constants referenced within them, which
+/// verification skips.
+pub(crate) struct ConfigEliminationSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Number of enclosing eliminated regions.
+    eliminated_depth: usize,
+}
+
+impl<'a> ConfigEliminationSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let constants = verifier.host.config_constants().borrow().iter().map(|(name, value)| (name.clone(), value.clone())).collect();
+        verifier.config_elimination.as_mut().unwrap().constants = constants;
+        let mut subverifier = Self { verifier, eliminated_depth: 0 };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn is_defined(&self, name: &str) -> bool {
+        self.verifier.host.config_constants().get(name).is_some()
+    }
+
+    /// Whether a namespace prefix, as `CONFIG`, is that of a
+    /// configuration constant.
+    fn is_config_namespace(&self, namespace: &str) -> bool {
+        let prefix = format!("{namespace}::");
+        self.verifier.host.config_constants().borrow().iter().any(|(name, _)| name.starts_with(&prefix))
+    }
+}
+
+impl<'a> Visitor for ConfigEliminationSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        let Directive::ConfigurationDirective(cfgdrtv) = drtv.as_ref() else {
+            walk_directive(self, drtv);
+            return;
+        };
+        let name = format!("{}::{}", cfgdrtv.namespace.0, cfgdrtv.constant_name.0);
+        let location = cfgdrtv.namespace.1.combine_with(cfgdrtv.constant_name.1.clone());
+        if self.eliminated_depth != 0 {
+            if !self.is_defined(&name) {
+                self.verifier.add_warning(&location, WhackDiagnosticKind::UndefinedConfigConstantInEliminatedCode, diagarg![name]);
+            }
+            walk_directive(self, drtv);
+            return;
+        }
+        let host = self.verifier.host.clone();
+        let eliminated = host.node_mapping().get(drtv).map(|cval| cval.is::<BooleanConstant>() && !cval.boolean_value()).unwrap_or(false);
+        if !eliminated {
+            walk_directive(self, drtv);
+            return;
+        }
+        self.verifier.config_elimination.as_mut().unwrap().add(EliminatedRegion { location: drtv.location(), condition: name });
+        self.eliminated_depth += 1;
+        walk_directive(self, drtv);
+        self.eliminated_depth -= 1;
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if self.eliminated_depth != 0 {
+            if let Expression::QualifiedIdentifier(id) = exp.as_ref() {
+                let namespace = id.qualifier.as_ref().and_then(|q| q.to_identifier_name()).map(|name| name.0);
+                if let (Some(namespace), QualifiedIdentifierIdentifier::Id((name, location))) = (namespace, &id.id) {
+                    let name = format!("{namespace}::{name}");
+                    if self.is_config_namespace(&namespace) && !self.is_defined(&name) {
+                        self.verifier.add_warning(location, WhackDiagnosticKind::UndefinedConfigConstantInEliminatedCode, diagarg![name]);
+                    }
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use crate::ns::*;
+
+/// Reports reassignments of function parameters when
+/// `CompilerOptions::const_parameters` is set, including those within
+/// nested functions capturing the parameter.
+///
+/// Write sites are assignments, including destructuring assignments,
+/// increments, decrements and `for..in` destinations. Writes to `const`
+/// locals are reported as read-only by the expression verifier.
+pub(crate) struct ConstParameterSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Parameter slots and their names.
+    params: HashMap<Entity, String>,
+}
+
+impl<'a> ConstParameterSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, params: HashMap::new() };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_write(&mut self, target: &Rc<Expression>, location: &Location) {
+        match target.as_ref() {
+            Expression::QualifiedIdentifier(id) if id.qualifier.is_none() && !id.attribute => {
+                let Some(r) = self.verifier.host.node_mapping().get(target) else {
+                    return;
+                };
+                if !(r.is::<FixtureReferenceValue>() || r.is::<ScopeReferenceValue>()) {
+                    return;
+                }
+                if let Some(name) = self.params.get(&r.property()).cloned() {
+                    self.verifier.add_verify_error(location, WhackDiagnosticKind::ConstParameterReassignment, diagarg![name]);
+                }
+            },
+            Expression::Paren(paren) => self.verify_write(&paren.expression, location),
+            Expression::Unary(e) if e.operator == Operator::NonNull => self.verify_write(&e.expression, location),
+            Expression::ArrayLiteral(literal) => {
+                for elem in literal.elements.iter() {
+                    match elem {
+                        Element::Expression(exp) => self.verify_write(exp, &exp.location()),
+                        Element::Rest((exp, _)) => self.verify_write(exp, &exp.location()),
+                        Element::Elision => {},
+                    }
+                }
+            },
+            Expression::ObjectInitializer(literal) => {
+                for field in literal.fields.iter() {
+                    if let InitializerField::Field { value: Some(value), .. } = field.as_ref() {
+                        self.verify_write(value, &value.location());
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+impl<'a> Visitor for ConstParameterSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ForInStatement(forstmt) = drtv.as_ref() {
+            if let ForInBinding::Expression(exp) = &forstmt.left {
+                self.verify_write(exp, &exp.location());
+            }
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Assignment(assign) => {
+                self.verify_write(&assign.left, &assign.location);
+            },
+            Expression::Unary(e) if [Operator::PreIncrement, Operator::PreDecrement, Operator::PostIncrement, Operator::PostDecrement].contains(&e.operator) => {
+                self.verify_write(&e.expression, &e.location);
+            },
+            _ => {},
+        }
+        walk_expression(self, exp);
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        let host = self.verifier.host.clone();
+        for param in common.signature.parameters.iter() {
+            let pattern = &param.destructuring.destructuring;
+            if let Some((name, _)) = pattern.to_identifier_name() {
+                if let Some(slot) = host.node_mapping().get(pattern).filter(|s| s.is::<VariableSlot>()) {
+                    self.params.insert(slot, name);
+                }
+            }
+        }
+        walk_function_common(self, common);
+    }
+}
\ No newline at end of file
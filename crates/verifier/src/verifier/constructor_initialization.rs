@@ -0,0 +1,330 @@
+use crate::ns::*;
+
+/// Fields definitely assigned at a point of a constructor, or `None`
+/// past a statement that does not complete normally.
+type AssignedFields = Option<HashSet<String>>;
+
+/// Verifies that instance fields which are `const` or of a non-nullable
+/// type and lack an initializer are assigned on every path through the
+/// constructor, and that instance methods reading them are not called
+/// before.
+///
+/// The analysis is syntactic: fields are assigned as `this.f = v` or
+/// `f = v`. Assignments made within loops and `switch` cases are not
+/// taken as definite.
+pub(crate) struct ConstructorInitializationSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Fields to initialize, with their locations.
+    fields: Vec<(String, Location)>,
+    /// Fields to initialize read by each instance method.
+    method_reads: HashMap<String, HashSet<String>>,
+    reported: HashSet<String>,
+    /// Labeled non-loop statements being flowed through, with the
+    /// fields assigned at each `break` targeting them.
+    labeled_breaks: Vec<(String, Vec<HashSet<String>>)>,
+}
+
+impl<'a> ConstructorInitializationSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, fields: vec![], method_reads: HashMap::new(), reported: HashSet::new(), labeled_breaks: vec![] };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_class(&mut self, defn: &ClassDefinition) {
+        self.fields.clear();
+        self.method_reads.clear();
+        self.reported.clear();
+        let mut constructor = None;
+        for drtv in defn.block.directives.iter() {
+            match drtv.as_ref() {
+                Directive::VariableDefinition(vardefn) => {
+                    if Attribute::find_static(&vardefn.attributes).is_some() {
+                        continue;
+                    }
+                    let is_const = vardefn.kind.0 == VariableDefinitionKind::Const;
+                    for binding in vardefn.bindings.iter() {
+                        let is_non_nullable = matches!(binding.destructuring.type_annotation.as_ref().map(|t| t.as_ref()), Some(Expression::NonNullableType(_)));
+                        if binding.initializer.is_some() || !(is_const || is_non_nullable) {
+                            continue;
+                        }
+                        if let Some(name) = binding.destructuring.destructuring.to_identifier_name() {
+                            self.fields.push(name);
+                        }
+                    }
+                },
+                Directive::FunctionDefinition(fndefn) => {
+                    match &fndefn.name {
+                        FunctionName::Constructor(_) => {
+                            constructor = Some(fndefn);
+                        },
+                        FunctionName::Identifier(name) if Attribute::find_static(&fndefn.attributes).is_none() => {
+                            let mut reads = FieldReadCollector { names: HashSet::new() };
+                            reads.visit_function_common(&fndefn.common);
+                            self.method_reads.insert(name.0.clone(), reads.names);
+                        },
+                        _ => {},
+                    }
+                },
+                _ => {},
+            }
+        }
+        if self.fields.is_empty() {
+            return;
+        }
+        let field_names = self.fields.iter().map(|(name, _)| name.clone()).collect::<HashSet<_>>();
+        for reads in self.method_reads.values_mut() {
+            reads.retain(|name| field_names.contains(name));
+        }
+
+        let Some(constructor) = constructor else {
+            for (name, location) in self.fields.clone() {
+                self.verifier.add_warning(&location, WhackDiagnosticKind::FieldNotInitializedInConstructor, diagarg![name]);
+            }
+            return;
+        };
+        let Some(FunctionBody::Block(block)) = constructor.common.body.as_ref() else {
+            return;
+        };
+        let end = self.flow_list(&block.directives, Some(HashSet::new()));
+        if let Some(assigned) = end {
+            let FunctionName::Constructor(name) = &constructor.name else {
+                return;
+            };
+            self.check_assigned(&assigned, &name.1);
+        }
+    }
+
+    fn check_assigned(&mut self, assigned: &HashSet<String>, location: &Location) {
+        for (name, _) in self.fields.clone() {
+            if !assigned.contains(&name) && self.reported.insert(name.clone()) {
+                self.verifier.add_warning(location, WhackDiagnosticKind::FieldNotInitializedInConstructor, diagarg![name]);
+            }
+        }
+    }
+
+    fn flow_list(&mut self, list: &[Rc<Directive>], mut assigned: AssignedFields) -> AssignedFields {
+        for stmt in list.iter() {
+            assigned = self.flow(stmt, assigned);
+        }
+        assigned
+    }
+
+    fn flow(&mut self, stmt: &Rc<Directive>, assigned: AssignedFields) -> AssignedFields {
+        let assigned = assigned?;
+        match stmt.as_ref() {
+            Directive::ReturnStatement(retstmt) => {
+                if let Some(exp) = retstmt.expression.as_ref() {
+                    self.flow_exp(exp, assigned.clone());
+                }
+                self.check_assigned(&assigned, &retstmt.location);
+                None
+            },
+            Directive::BreakStatement(brkstmt) => {
+                let label = brkstmt.label.as_ref().map(|label| label.0.clone());
+                if let Some(target) = self.labeled_breaks.iter_mut().rev().find(|(name, _)| Some(name) == label.as_ref()) {
+                    target.1.push(assigned);
+                }
+                None
+            },
+            Directive::ThrowStatement(_) |
+            Directive::ContinueStatement(_) => None,
+            // A labeled non-loop statement completes at its end and at
+            // every `break` targeting its label.
+            Directive::LabeledStatement(labstmt) if !matches!(labstmt.substatement.as_ref(),
+                Directive::WhileStatement(_) | Directive::DoStatement(_) |
+                Directive::ForStatement(_) | Directive::ForInStatement(_)) => {
+                self.labeled_breaks.push((labstmt.label.0.clone(), vec![]));
+                let mut result = self.flow(&labstmt.substatement, Some(assigned));
+                let (_, breaks) = self.labeled_breaks.pop().unwrap();
+                for at_break in breaks {
+                    result = Some(match result {
+                        Some(a) => a.intersection(&at_break).cloned().collect(),
+                        None => at_break,
+                    });
+                }
+                result
+            },
+            Directive::FunctionDefinition(_) => Some(assigned),
+            Directive::Block(block) => self.flow_list(&block.directives, Some(assigned)),
+            Directive::IfStatement(ifstmt) => {
+                let assigned = self.flow_exp(&ifstmt.test, assigned);
+                let consequent = self.flow(&ifstmt.consequent, Some(assigned.clone()));
+                let alternative = match ifstmt.alternative.as_ref() {
+                    Some(alt) => self.flow(alt, Some(assigned)),
+                    None => Some(assigned),
+                };
+                match (consequent, alternative) {
+                    (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+                    (a, b) => a.or(b),
+                }
+            },
+            Directive::TryStatement(trystmt) => {
+                let mut result = self.flow_list(&trystmt.block.directives, Some(assigned.clone()));
+                for catch_clause in trystmt.catch_clauses.iter() {
+                    let caught = self.flow_list(&catch_clause.block.directives, Some(assigned.clone()));
+                    result = match (result, caught) {
+                        (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+                        (a, b) => a.or(b),
+                    };
+                }
+                match trystmt.finally_clause.as_ref() {
+                    Some(finally_clause) => {
+                        let completes = result.is_some();
+                        let after = self.flow_list(&finally_clause.block.directives, Some(result.unwrap_or(assigned)));
+                        after.filter(|_| completes)
+                    },
+                    None => result,
+                }
+            },
+            Directive::ExpressionStatement(estmt) => Some(self.flow_exp(&estmt.expression, assigned)),
+            Directive::VariableDefinition(defn) => {
+                let mut assigned = assigned;
+                for binding in defn.bindings.iter() {
+                    if let Some(init) = binding.initializer.as_ref() {
+                        assigned = self.flow_exp(init, assigned);
+                    }
+                }
+                Some(assigned)
+            },
+            Directive::SuperStatement(supstmt) => {
+                let mut assigned = assigned;
+                for exp in supstmt.arguments.iter() {
+                    assigned = self.flow_exp(exp, assigned);
+                }
+                Some(assigned)
+            },
+            Directive::SwitchStatement(swstmt) => {
+                let assigned = self.flow_exp(&swstmt.discriminant, assigned);
+                for case in swstmt.cases.iter() {
+                    for label in case.labels.iter() {
+                        if let CaseLabel::Case((exp, _)) = label {
+                            self.flow_exp(exp, assigned.clone());
+                        }
+                    }
+                    self.flow_list(&case.directives, Some(assigned.clone()));
+                }
+                Some(assigned)
+            },
+            // Visit the remaining statements for returns and calls,
+            // keeping none of their assignments.
+            _ => {
+                let mut nested = NestedStatementFlow { subverifier: self, assigned: assigned.clone() };
+                walk_directive(&mut nested, stmt);
+                Some(assigned)
+            },
+        }
+    }
+
+    /// Checks calls to instance methods within an expression and
+    /// returns the fields assigned after it.
+    fn flow_exp(&mut self, exp: &Rc<Expression>, assigned: HashSet<String>) -> HashSet<String> {
+        let mut collector = ConstructorExpressionCollector { calls: vec![], assignments: vec![] };
+        collector.visit_expression(exp);
+        for (method, location) in collector.calls {
+            let Some(reads) = self.method_reads.get(&method).cloned() else {
+                continue;
+            };
+            for field in reads {
+                if !assigned.contains(&field) {
+                    self.verifier.add_warning(&location, WhackDiagnosticKind::FieldReadBeforeInitialization, diagarg![method.clone(), field]);
+                }
+            }
+        }
+        let mut assigned = assigned;
+        assigned.extend(collector.assignments);
+        assigned
+    }
+
+    /// Returns `f` for `this.f` and for an unqualified `f` identifier.
+    fn field_name(exp: &Rc<Expression>) -> Option<(String, Location)> {
+        match exp.as_ref() {
+            Expression::Member(member) if matches!(member.base.as_ref(), Expression::ThisLiteral(_)) => {
+                member.identifier.to_identifier_name_or_asterisk()
+            },
+            Expression::QualifiedIdentifier(id) if id.qualifier.is_none() && !id.attribute => {
+                match &id.id {
+                    QualifiedIdentifierIdentifier::Id(name) => Some(name.clone()),
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Visitor for ConstructorInitializationSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            self.verify_class(defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
+
+/// Flows through the statements nested in a loop, `switch` or other
+/// compound statement, each from the same assigned fields.
+struct NestedStatementFlow<'a, 'b> {
+    subverifier: &'b mut ConstructorInitializationSubverifier<'a>,
+    assigned: HashSet<String>,
+}
+
+impl<'a, 'b> Visitor for NestedStatementFlow<'a, 'b> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        self.subverifier.flow(drtv, Some(self.assigned.clone()));
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        self.subverifier.flow_exp(exp, self.assigned.clone());
+    }
+}
+
+/// Collects the calls to unqualified or `this` methods and the
+/// field assignments of an expression, outside nested functions.
+struct ConstructorExpressionCollector {
+    calls: Vec<(String, Location)>,
+    assignments: Vec<String>,
+}
+
+impl Visitor for ConstructorExpressionCollector {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::Call(call) => {
+                if let Some((name, _)) = ConstructorInitializationSubverifier::field_name(&call.base) {
+                    self.calls.push((name, call.location.clone()));
+                }
+                walk_expression(self, exp);
+            },
+            Expression::Assignment(assign) => {
+                // The right-hand side is evaluated first
+                self.visit_expression(&assign.right);
+                if let Some((name, _)) = ConstructorInitializationSubverifier::field_name(&assign.left) {
+                    if assign.compound.is_none() {
+                        self.assignments.push(name);
+                    }
+                } else {
+                    self.visit_expression(&assign.left);
+                }
+            },
+            _ => {
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
+
+/// Collects the names read as `this.f` or `f` in a method.
+struct FieldReadCollector {
+    names: HashSet<String>,
+}
+
+impl Visitor for FieldReadCollector {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Some((name, _)) = ConstructorInitializationSubverifier::field_name(exp) {
+            self.names.insert(name);
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
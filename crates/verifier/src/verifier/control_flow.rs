@@ -33,4 +33,68 @@ impl ControlFlowAnalyser {
     ) {
         ControlFlowAnalysisIsUnimplemented::unimplemented();
     }
+}
+
+/// Reachability of the end of statements.
+///
+/// The end of a list is reachable if its `FlowGraph` reaches the end,
+/// not following the values of tests, so that a loop whose test is a
+/// constant `true` is still assumed to complete normally.
+pub(crate) struct Reachability;
+
+impl Reachability {
+    pub fn list_completes_normally(list: &[Rc<Directive>]) -> bool {
+        FlowGraph::build(list).completes_normally()
+    }
+
+    /// Returns whether a loop body contains a `break` targeting the
+    /// loop, a `break` or `continue` targeting a label outside of the
+    /// loop, or a `return` or `throw` statement.
+    pub fn may_exit_loop(body: &Rc<Directive>) -> bool {
+        Self::may_exit(body, false, &mut vec![])
+    }
+
+    /// `labels` are the labels declared within the loop body around
+    /// `stmt`, whose labeled jumps stay within the loop.
+    fn may_exit(stmt: &Rc<Directive>, nested: bool, labels: &mut Vec<String>) -> bool {
+        match stmt.as_ref() {
+            Directive::BreakStatement(brkstmt) => match brkstmt.label.as_ref() {
+                Some(label) => !labels.contains(&label.0),
+                None => !nested,
+            },
+            Directive::ContinueStatement(contstmt) => contstmt.label.as_ref().map(|label| !labels.contains(&label.0)).unwrap_or(false),
+            Directive::ReturnStatement(_) |
+            Directive::ThrowStatement(_) => true,
+            Directive::Block(block) => block.directives.iter().any(|d| Self::may_exit(d, nested, labels)),
+            Directive::LabeledStatement(labstmt) => {
+                labels.push(labstmt.label.0.clone());
+                let r = Self::may_exit(&labstmt.substatement, nested, labels);
+                labels.pop();
+                r
+            },
+            Directive::IfStatement(ifstmt) => {
+                Self::may_exit(&ifstmt.consequent, nested, labels)
+                    || ifstmt.alternative.as_ref().map(|alt| Self::may_exit(alt, nested, labels)).unwrap_or(false)
+            },
+            Directive::TryStatement(trystmt) => {
+                trystmt.block.directives.iter().any(|d| Self::may_exit(d, nested, labels))
+                    || trystmt.catch_clauses.iter().any(|c| c.block.directives.iter().any(|d| Self::may_exit(d, nested, labels)))
+                    || trystmt.finally_clause.as_ref().map(|f| f.block.directives.iter().any(|d| Self::may_exit(d, nested, labels))).unwrap_or(false)
+            },
+            Directive::WhileStatement(wstmt) => Self::may_exit(&wstmt.body, true, labels),
+            Directive::DoStatement(dostmt) => Self::may_exit(&dostmt.body, true, labels),
+            Directive::ForStatement(forstmt) => Self::may_exit(&forstmt.body, true, labels),
+            Directive::ForInStatement(forstmt) => Self::may_exit(&forstmt.body, true, labels),
+            Directive::WithStatement(wstmt) => Self::may_exit(&wstmt.body, nested, labels),
+            Directive::SwitchStatement(swstmt) => {
+                swstmt.cases.iter().any(|case| case.directives.iter().any(|d| Self::may_exit(d, true, labels)))
+            },
+            // Assume the remaining compound statements may exit
+            Directive::SwitchTypeStatement(_) |
+            Directive::ConfigurationDirective(_) |
+            Directive::IncludeDirective(_) |
+            Directive::DirectiveInjection(_) => true,
+            _ => false,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum CheckedDefinitionKind {
+    Class,
+    Interface,
+    Function,
+}
+
+/// Declaration checks performed without semantic verification.
+///
+/// Definitions are compared by their local names within the same
+/// package or program; no entities are created in the database.
+pub(crate) struct DeclarationCheckSubverifier;
+
+impl DeclarationCheckSubverifier {
+    pub fn check_program(verifier: &mut Subverifier, program: &Rc<Program>) {
+        for pckgdef in program.packages.iter() {
+            Self::check_directives(verifier, &pckgdef.block.directives, &mut HashMap::new());
+        }
+        Self::check_directives(verifier, &program.directives, &mut HashMap::new());
+    }
+
+    fn check_directives(verifier: &mut Subverifier, list: &[Rc<Directive>], names: &mut HashMap<String, CheckedDefinitionKind>) {
+        for drtv in list {
+            match drtv.as_ref() {
+                Directive::ClassDefinition(defn) => {
+                    Self::check_name(verifier, &defn.name, CheckedDefinitionKind::Class, names);
+                },
+                Directive::EnumDefinition(defn) => {
+                    Self::check_name(verifier, &defn.name, CheckedDefinitionKind::Class, names);
+                },
+                Directive::InterfaceDefinition(defn) => {
+                    Self::check_name(verifier, &defn.name, CheckedDefinitionKind::Interface, names);
+                },
+                Directive::FunctionDefinition(defn) => {
+                    if let FunctionName::Identifier(name) = &defn.name {
+                        Self::check_name(verifier, name, CheckedDefinitionKind::Function, names);
+                    }
+                },
+                Directive::Block(block) => {
+                    Self::check_directives(verifier, &block.directives, names);
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    for pckgdef in incdrtv.nested_packages.iter() {
+                        Self::check_directives(verifier, &pckgdef.block.directives, &mut HashMap::new());
+                    }
+                    Self::check_directives(verifier, &incdrtv.nested_directives, names);
+                },
+                Directive::DirectiveInjection(inj) => {
+                    Self::check_directives(verifier, inj.directives.borrow().as_ref(), names);
+                },
+                // Configuration directives are conditionally compiled, so
+                // their definitions may legitimately repeat.
+                _ => {},
+            }
+        }
+    }
+
+    fn check_name(verifier: &mut Subverifier, name: &(String, Location), kind: CheckedDefinitionKind, names: &mut HashMap<String, CheckedDefinitionKind>) {
+        if !names.contains_key(&name.0) {
+            names.insert(name.0.clone(), kind);
+            return;
+        }
+        let diag_kind = match kind {
+            CheckedDefinitionKind::Class => WhackDiagnosticKind::DuplicateClassDefinition,
+            CheckedDefinitionKind::Interface => WhackDiagnosticKind::DuplicateInterfaceDefinition,
+            CheckedDefinitionKind::Function => WhackDiagnosticKind::DuplicateFunctionDefinition,
+        };
+        verifier.add_verify_error(&name.1, diag_kind, diagarg![name.0.clone()]);
+    }
+}
\ No newline at end of file
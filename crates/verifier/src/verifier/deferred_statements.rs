@@ -0,0 +1,80 @@
+use crate::ns::*;
+
+/// A statement whose verification deferred, along with the scope it was
+/// verified in.
+pub(crate) struct DeferredStatement {
+    pub stmt: Rc<Directive>,
+    pub scope: Option<Entity>,
+    /// Entity whose resolution the statement waits for, if known.
+    pub blocker: Option<Entity>,
+}
+
+/// Retries the statements queued by `StatementSubverifier` once the
+/// statements of every program have been verified, rather than
+/// reporting them on their first deferral.
+///
+/// A statement is queued whole when one of its own expressions defers,
+/// before its substatements are verified, so that a retry runs every
+/// check of the statement (such as `with` scopes, `for..in` destination
+/// types and `switch` case coercions) exactly once.
+///
+/// Each round retries only the statements whose blocker resolved, or
+/// whose blocker is unknown. The queue is drained once a round resolves
+/// nothing or after `Verifier::MAX_CYCLES` rounds, retrying the remaining
+/// statements a last time with deferring expressions reported in place.
+pub(crate) struct DeferredStatementQueue;
+
+impl DeferredStatementQueue {
+    pub fn drain(verifier: &mut Subverifier) {
+        for _ in 0..Verifier::MAX_CYCLES {
+            if verifier.deferred_statements.is_empty() || verifier.cancelled() {
+                break;
+            }
+            verifier.type_relations.invalidate();
+            let pending = std::mem::take(&mut verifier.deferred_statements);
+            let mut remaining = vec![];
+            let mut progressed = false;
+            for entry in pending {
+                if entry.blocker.as_ref().map(|b| !Self::is_resolved(&verifier.host, b)).unwrap_or(false) {
+                    remaining.push(entry);
+                    continue;
+                }
+                Self::retry(verifier, &entry);
+                let requeued = verifier.deferred_statements.last().map(|e| Rc::ptr_eq(&e.stmt, &entry.stmt)).unwrap_or(false);
+                progressed = progressed || !requeued;
+            }
+            // Statements queued while retrying are kept for the next round
+            verifier.deferred_statements.extend(remaining);
+            if !progressed {
+                break;
+            }
+        }
+        // The last retry reports deferring expressions in place.
+        verifier.statement_deferral_enabled = false;
+        for entry in std::mem::take(&mut verifier.deferred_statements) {
+            if verifier.cancelled() {
+                break;
+            }
+            Self::retry(verifier, &entry);
+        }
+        verifier.statement_deferral_enabled = true;
+    }
+
+    fn retry(verifier: &mut Subverifier, entry: &DeferredStatement) {
+        let previous_scope = std::mem::replace(&mut verifier.scope, entry.scope.clone());
+        StatementSubverifier::verify_statement(verifier, &entry.stmt);
+        verifier.scope = previous_scope;
+    }
+
+    fn is_resolved(host: &Database, blocker: &Entity) -> bool {
+        if blocker.is::<UnresolvedEntity>() {
+            false
+        } else if blocker.is::<MethodSlot>() {
+            !blocker.signature(host).is::<UnresolvedEntity>()
+        } else if blocker.is::<VariableSlot>() || blocker.is::<VirtualSlot>() || blocker.is::<Value>() {
+            !blocker.static_type(host).is::<UnresolvedEntity>()
+        } else {
+            true
+        }
+    }
+}
\ No newline at end of file
@@ -109,7 +109,11 @@ impl DestructuringDeclarationSubverifier {
 
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_internal_error(&pattern.location(), "unexpected verifier phase");
+                verifier.phase_of_entity.remove(&slot);
+                Ok(())
+            },
         }
     }
 
@@ -208,7 +212,11 @@ impl DestructuringDeclarationSubverifier {
                     Self::verify_invalidation_array_pattern_omega(verifier, literal, &slot, read_only, output, ns, parent, is_external)
                 }
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_internal_error(&pattern.location(), "unexpected verifier phase");
+                verifier.phase_of_entity.remove(&slot);
+                Ok(())
+            },
         }
     }
 
@@ -264,9 +272,9 @@ impl DestructuringDeclarationSubverifier {
                     }
                 },
                 Element::Rest((restpat, _)) => {
-                    let array_type_of_any = verifier.host.array_type_of_any()?;
+                    let rest_type = Self::tuple_rest_type(verifier, tuple_type, i)?;
                     rest_found = true;
-                    Self::verify_pattern(verifier, restpat, &verifier.host.factory().create_value(&array_type_of_any), read_only, output, ns, parent, is_external)?;
+                    Self::verify_pattern(verifier, restpat, &verifier.host.factory().create_value(&rest_type), read_only, output, ns, parent, is_external)?;
                 },
                 Element::Elision => {},
             }
@@ -282,6 +290,16 @@ impl DestructuringDeclarationSubverifier {
         Ok(())
     }
 
+    /// Type of a rest pattern following the first `i` elements of a tuple:
+    /// a tuple of the remaining element types, or `Array` if none remain.
+    pub fn tuple_rest_type(verifier: &Subverifier, tuple_type: &Entity, i: usize) -> Result<Entity, DeferError> {
+        let rest_types = tuple_type.element_types().iter().skip(i).collect::<Vec<_>>();
+        if rest_types.is_empty() {
+            return verifier.host.array_type_of_any();
+        }
+        Ok(verifier.host.factory().create_tuple_type(rest_types))
+    }
+
     fn verify_untyped_array_pattern_omega(verifier: &mut Subverifier, literal: &ArrayLiteral, patslot: &Entity, read_only: bool, output: &mut Names, ns: &Entity, parent: &Entity, is_external: bool) -> Result<(), DeferError> {
         for elem in &literal.elements {
             match elem {
@@ -400,7 +418,11 @@ impl DestructuringDeclarationSubverifier {
 
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_internal_error(&pattern.location(), "unexpected verifier phase");
+                verifier.phase_of_entity.remove(&slot);
+                Ok(())
+            },
         }
     }
 
@@ -447,7 +469,11 @@ impl DestructuringDeclarationSubverifier {
             VerifierPhase::Omega => {
                 Self::verify_object_pattern_omega(verifier, literal, &slot, init, read_only, output, ns, parent, is_external)
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_internal_error(&pattern.location(), "unexpected verifier phase");
+                verifier.phase_of_entity.remove(&slot);
+                Ok(())
+            },
         }
     }
 
@@ -526,7 +552,9 @@ impl DestructuringDeclarationSubverifier {
                 InitializerField::Field { name, non_null, value: subpat } => {
                     // Fields have an already attached DeclarativeFieldDestructuringResolution.
                     // A field is considered already verified if `field_reference()` is `Some`.
-                    let resolution = verifier.host.node_mapping().get(field).unwrap();
+                    let Some(resolution) = verifier.host.node_mapping().get(field) else {
+                        continue;
+                    };
                     if resolution.field_reference().is_some() {
                         continue;
                     }
@@ -6,6 +6,9 @@ impl DirectiveSubverifier {
     pub fn verify_directives(verifier: &mut Subverifier, list: &[Rc<Directive>]) -> Result<(), DeferError> {
         let mut any_defer = false;
         for drtv in list {
+            if verifier.cancelled() {
+                return Ok(());
+            }
             let r = Self::verify_directive(verifier, drtv).is_err();
             any_defer = any_defer || r;
         }
@@ -113,7 +116,10 @@ impl DirectiveSubverifier {
                         verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                         Ok(())
                     },
-                    _ => panic!(),
+                    _ => {
+                        verifier.add_unexpected_phase_error(drtv);
+                        Ok(())
+                    },
                 }
             },
             Directive::IncludeDirective(incdrtv) => {
@@ -252,6 +258,9 @@ impl DirectiveSubverifier {
                         let _ = DestructuringDeclarationSubverifier::verify_pattern(verifier, &binding.destructuring.destructuring, &verifier.host.unresolved_entity(), defn.kind.0 == VariableDefinitionKind::Const, &mut scope.properties(&host), &internal_ns, &scope, false);
                     }
 
+                    // Report redeclarations of the heading's variables in the body
+                    LoopRedeclarations::check(verifier, defn, &forstmt.body);
+
                     // Next phase
                     verifier.set_drtv_phase(drtv, VerifierPhase::Beta);
                     return Err(DeferError(None));
@@ -375,7 +384,7 @@ impl DirectiveSubverifier {
                     verifier.set_drtv_phase(drtv, VerifierPhase::Omega);
                 },
                 VerifierPhase::Omega => {},
-                _ => panic!(),
+                _ => verifier.add_internal_error(&drtv.location(), "unexpected verifier phase"),
             }
         }
         verifier.inherit_and_enter_scope(&scope);
@@ -400,6 +409,9 @@ impl DirectiveSubverifier {
                     // Verify pattern (alpha)
                     let _ = DestructuringDeclarationSubverifier::verify_pattern(verifier, &binding.destructuring.destructuring, &verifier.host.unresolved_entity(), defn.kind.0 == VariableDefinitionKind::Const, &mut scope.properties(&host), &internal_ns, &scope, false);
 
+                    // Report redeclarations of the heading's variable in the body
+                    LoopRedeclarations::check(verifier, defn, &forstmt.body);
+
                     // Next phase
                     verifier.set_drtv_phase(drtv, VerifierPhase::Beta);
                     return Err(DeferError(None));
@@ -470,7 +482,7 @@ impl DirectiveSubverifier {
                         Err(DeferError(Some(VerifierPhase::Delta))) |
                         Err(DeferError(Some(VerifierPhase::Epsilon))) |
                         Err(DeferError(Some(VerifierPhase::Omega))) => {},
-                        Err(DeferError(Some(_))) => panic!(),
+                        Err(DeferError(Some(_))) => verifier.add_internal_error(&binding.destructuring.location, "unexpected deferral of a destructuring pattern"),
                     }
 
                     if illegal_obj {
@@ -487,7 +499,7 @@ impl DirectiveSubverifier {
                     return Err(DeferError(None));
                 },
                 VerifierPhase::Omega => {},
-                _ => panic!(),
+                _ => verifier.add_internal_error(&drtv.location(), "unexpected verifier phase"),
             }
         }
     verifier.inherit_and_enter_scope( &scope);
@@ -560,7 +572,11 @@ impl DirectiveSubverifier {
 
                         // Mark as external
                         class_entity.set_is_external(true);
+                    // [ResourceBundle] meta-data
+                    } else if m.name.0 == "ResourceBundle" {
+                        ResourceBundleSubverifier::verify_metadata(verifier, m);
                     }
+                    ResourceBundleSubverifier::verify_metadata_resources(verifier, m);
                 }
 
                 let event_metadata_list = metadata.iter().filter(|m| {
@@ -676,7 +692,10 @@ impl DirectiveSubverifier {
                 let host = verifier.host.clone();
 
                 // Class entity
-                let class_entity = host.node_mapping().get(drtv).unwrap();
+                let Some(class_entity) = host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Resolve the class inheritance (which class it extends)
                 // (CONDITION: in case it is "unresolved" yet).
@@ -898,7 +917,10 @@ impl DirectiveSubverifier {
                     }
                 }
 
-                let block_scope = verifier.host.node_mapping().get(&defn.block).unwrap();
+                let Some(block_scope) = verifier.host.node_mapping().get(&defn.block) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Contribute protected namespaces to open namespace set
                 let mut c = Some(class_entity);
@@ -932,12 +954,18 @@ impl DirectiveSubverifier {
                 let host = verifier.host.clone();
 
                 // Class entity
-                let class_entity = host.node_mapping().get(drtv).unwrap();
+                let Some(class_entity) = host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 let mut about_to_defer: bool;
 
                 // Class block scope
-                let block_scope = verifier.host.node_mapping().get(&defn.block).unwrap();
+                let Some(block_scope) = verifier.host.node_mapping().get(&defn.block) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Enter class block scope, then visit class block
                 // but DEFER ONLY AT THE FINAL STEP if necessary; then exit scope.
@@ -1057,7 +1085,10 @@ impl DirectiveSubverifier {
                     Ok(())
                 }
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -1267,6 +1298,12 @@ impl DirectiveSubverifier {
                 return Err(DeferError(None));
             },
             VerifierPhase::Beta => {
+                let Some(enum_entity) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
+                Self::define_implicit_enum_methods(verifier, &enum_entity)?;
+
                 verifier.set_drtv_phase(drtv, VerifierPhase::Omega);
                 Err(DeferError(None))
             },
@@ -1274,7 +1311,10 @@ impl DirectiveSubverifier {
                 let about_to_defer: bool;
 
                 // Enum block scope
-                let block_scope = verifier.host.node_mapping().get(&defn.block).unwrap();
+                let Some(block_scope) = verifier.host.node_mapping().get(&defn.block) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Enter enum block scope, then visit enum block
                 // but DEFER ONLY AT THE FINAL STEP if necessary; then exit scope.
@@ -1289,8 +1329,41 @@ impl DirectiveSubverifier {
                     Ok(())
                 }
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
+        }
+    }
+
+    /// Defines `valueOf(): Number` and `toString(): String` in an enum,
+    /// returning the value and the string of the constant, unless the
+    /// enum block defines them.
+    fn define_implicit_enum_methods(verifier: &mut Subverifier, enum_entity: &Entity) -> Result<(), DeferError> {
+        let host = verifier.host.clone();
+        let number_type = host.number_type().defer()?;
+        let string_type = host.string_type().defer()?;
+        let Some(public_ns) = enum_entity.parent().and_then(|parent| parent.public_ns()) else {
+            return Ok(());
+        };
+        for (name, result_type) in [("valueOf", number_type), ("toString", string_type)] {
+            let qname = host.factory().create_qname(&public_ns, name.to_owned());
+            if enum_entity.prototype(&host).has(&qname) {
+                continue;
+            }
+            let signature = host.factory().create_function_type(vec![], result_type);
+            let slot = host.factory().create_method_slot(&qname, &signature);
+            slot.set_is_constructor(false);
+            slot.set_is_final(true);
+            slot.set_is_native(true);
+            slot.set_is_overriding(true);
+            slot.set_parent(Some(enum_entity.clone()));
+            slot.set_location(enum_entity.location());
+            let activation = host.factory().create_activation(&slot);
+            slot.set_activation(Some(activation));
+            enum_entity.prototype(&host).set(qname, slot);
         }
+        Ok(())
     }
 
     fn verify_interface_defn(verifier: &mut Subverifier, drtv: &Rc<Directive>, defn: &InterfaceDefinition) -> Result<(), DeferError> {
@@ -1383,7 +1456,10 @@ impl DirectiveSubverifier {
                 let host = verifier.host.clone();
 
                 // Class entity
-                let itrfc_entity = host.node_mapping().get(drtv).unwrap();
+                let Some(itrfc_entity) = host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 let guard = verifier.itrfc_defn_guard(drtv);
 
@@ -1571,7 +1647,10 @@ impl DirectiveSubverifier {
                 let mut about_to_defer: bool = false;
 
                 // Class block scope
-                let block_scope = verifier.host.node_mapping().get(&defn.block).unwrap();
+                let Some(block_scope) = verifier.host.node_mapping().get(&defn.block) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Enter interface block scope, then visit interface block
                 // but DEFER ONLY AT THE FINAL STEP if necessary; then exit scope.
@@ -1590,7 +1669,10 @@ impl DirectiveSubverifier {
                     Ok(())
                 }
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -1642,7 +1724,10 @@ impl DirectiveSubverifier {
                 let host = verifier.host.clone();
 
                 // Type alias
-                let t_alias = host.node_mapping().get(drtv).unwrap();
+                let Some(t_alias) = host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 if t_alias.alias_of().is::<UnresolvedEntity>() {
                     let t = verifier.verify_type_expression(&defn.right)?.unwrap_or(verifier.host.any_type());
@@ -1652,7 +1737,10 @@ impl DirectiveSubverifier {
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -1720,7 +1808,10 @@ impl DirectiveSubverifier {
                 let host = verifier.host.clone();
 
                 // Type alias
-                let ns_alias = host.node_mapping().get(drtv).unwrap();
+                let Some(ns_alias) = host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 if ns_alias.alias_of().is::<UnresolvedEntity>() {
                     if let Some(r) = defn.right.as_ref() {
@@ -1753,7 +1844,10 @@ impl DirectiveSubverifier {
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -1799,12 +1893,12 @@ impl DirectiveSubverifier {
                     let _ = DestructuringDeclarationSubverifier::verify_pattern(verifier, &binding.destructuring.destructuring, &verifier.host.unresolved_entity(), defn.kind.0 == VariableDefinitionKind::Const, &mut var_out, &ns, &var_parent, is_external);
                 }
 
-                // Set ASDoc and meta-data
-                let slot1 = verifier.host.node_mapping().get(&defn.bindings[0].destructuring.destructuring);
-                if slot1.as_ref().and_then(|e| if e.is::<VariableSlot>() { Some(e) } else { None }).is_some() {
-                    let slot1 = slot1.unwrap();
-                    slot1.set_asdoc(defn.asdoc.clone());
-                    slot1.metadata().extend(Attribute::find_metadata(&defn.attributes));
+                // Set ASDoc and meta-data of every binding, as in `var x, y;`
+                for binding in defn.bindings.iter() {
+                    if let Some(slot) = verifier.host.node_mapping().get(&binding.destructuring.destructuring).filter(|e| e.is::<VariableSlot>()) {
+                        slot.set_asdoc(defn.asdoc.clone());
+                        slot.metadata().extend(Attribute::find_metadata(&defn.attributes));
+                    }
                 }
 
                 // Next phase
@@ -1948,7 +2042,10 @@ impl DirectiveSubverifier {
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -2090,6 +2187,17 @@ impl DirectiveSubverifier {
         }
     }
 
+    /// Activation of the function whose statement blocks enclose the
+    /// current scope, if any.
+    fn block_function_hoist_scope(verifier: &Subverifier) -> Option<Entity> {
+        let scope = verifier.scope();
+        if scope.is::<Activation>() {
+            return None;
+        }
+        let hoist_scope = scope.search_hoist_scope();
+        hoist_scope.is::<Activation>().then_some(hoist_scope)
+    }
+
     fn verify_normal_fn_defn(verifier: &mut Subverifier, drtv: &Rc<Directive>, defn: &FunctionDefinition, name: &(String, Location)) -> Result<(), DeferError> {
         let phase = verifier.lazy_init_drtv_phase(drtv, VerifierPhase::Alpha);
         if phase == VerifierPhase::Finished {
@@ -2104,7 +2212,14 @@ impl DirectiveSubverifier {
                     verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                     return Ok(());
                 }
-                let (_, fn_parent, mut fn_out, ns) = defn_local.unwrap();
+                let (_, mut fn_parent, mut fn_out, ns) = defn_local.unwrap();
+
+                // Hoist functions declared in statement blocks to the
+                // enclosing activation, so that duplicates within it conflict.
+                if let Some(activation) = Self::block_function_hoist_scope(verifier) {
+                    fn_out = activation.properties(&verifier.host);
+                    fn_parent = activation;
+                }
 
                 // Determine whether the definition is external or not
                 let is_external = if fn_parent.is::<Type>() && fn_parent.is_external() {
@@ -2168,7 +2283,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Beta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -2402,7 +2520,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Delta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Database
                 let host = verifier.host.clone();
@@ -2435,7 +2556,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Omega => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -2452,16 +2576,20 @@ impl DirectiveSubverifier {
                 // Enter scope
                 verifier.inherit_and_enter_scope(&activation);
 
-                FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials)?;
+                let result = FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials);
 
-                // Restore scope
+                // Restore scope, even if the body is deferred
                 verifier.set_scope(&kscope);
+                result?;
 
                 // Finish
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -2525,7 +2653,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Beta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -2768,7 +2899,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Omega => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -2785,16 +2919,20 @@ impl DirectiveSubverifier {
                 // Enter scope
                 verifier.inherit_and_enter_scope(&activation);
 
-                FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials)?;
+                let result = FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials);
 
-                // Restore scope
+                // Restore scope, even if the body is deferred
                 verifier.set_scope(&kscope);
+                result?;
 
                 // Finish
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -2890,7 +3028,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Beta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -3119,7 +3260,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Delta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Database
                 let host = verifier.host.clone();
@@ -3160,7 +3304,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Omega => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -3177,16 +3324,20 @@ impl DirectiveSubverifier {
                 // Enter scope
                 verifier.inherit_and_enter_scope(&activation);
 
-                FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials)?;
+                let result = FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials);
 
-                // Restore scope
+                // Restore scope, even if the body is deferred
                 verifier.set_scope(&kscope);
+                result?;
 
                 // Finish
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -3282,7 +3433,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Beta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -3518,7 +3672,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Delta => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Database
                 let host = verifier.host.clone();
@@ -3559,7 +3716,10 @@ impl DirectiveSubverifier {
             },
             VerifierPhase::Omega => {
                 // Retrieve method slot
-                let slot = verifier.host.node_mapping().get(drtv).unwrap();
+                let Some(slot) = verifier.host.node_mapping().get(drtv) else {
+                    verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
+                    return Ok(());
+                };
 
                 // Retrieve activation
                 let activation = slot.activation().unwrap();
@@ -3576,16 +3736,20 @@ impl DirectiveSubverifier {
                 // Enter scope
                 verifier.inherit_and_enter_scope(&activation);
 
-                FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials)?;
+                let result = FunctionCommonSubverifier::verify_function_definition_common(verifier, &common, &partials);
 
-                // Restore scope
+                // Restore scope, even if the body is deferred
                 verifier.set_scope(&kscope);
+                result?;
 
                 // Finish
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -3724,7 +3888,10 @@ impl DirectiveSubverifier {
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -3788,6 +3955,13 @@ impl DirectiveSubverifier {
             return Ok(());
         }
 
+        // Packages absent from the target profile
+        let package = impdrtv.package_name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+        if !verifier.target_profile.is_package_available(&package) {
+            let name = ImportOrganizer::import_name(impdrtv);
+            verifier.add_verify_error(&drtv.location(), WhackDiagnosticKind::UnavailableInTargetProfile, diagarg![name, verifier.target_profile.name().to_owned()]);
+        }
+
         // Import alias
         if impdrtv.alias.is_some() {
             return Self::verify_import_alias_directive(verifier, drtv, impdrtv);
@@ -3870,7 +4044,10 @@ impl DirectiveSubverifier {
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
@@ -3979,7 +4156,10 @@ impl DirectiveSubverifier {
                 verifier.set_drtv_phase(drtv, VerifierPhase::Finished);
                 Ok(())
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_unexpected_phase_error(drtv);
+                Ok(())
+            },
         }
     }
 
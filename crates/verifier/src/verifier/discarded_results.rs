@@ -0,0 +1,85 @@
+use crate::ns::*;
+
+/// Warns on expression statements calling a method marked `[Pure]`, or
+/// one of the core methods returning a new value rather than modifying
+/// their receiver, such as `String.replace()` and `Array.concat()`.
+pub(crate) struct DiscardedResultSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> DiscardedResultSubverifier<'a> {
+    /// Core methods whose result is their only effect.
+    const CORE_PURE_METHODS: &'static [&'static str] = &[
+        "String.charAt",
+        "String.charCodeAt",
+        "String.concat",
+        "String.indexOf",
+        "String.lastIndexOf",
+        "String.replace",
+        "String.slice",
+        "String.split",
+        "String.substr",
+        "String.substring",
+        "String.toLowerCase",
+        "String.toUpperCase",
+        "Array.concat",
+        "Array.filter",
+        "Array.indexOf",
+        "Array.join",
+        "Array.lastIndexOf",
+        "Array.map",
+        "Array.slice",
+        "Vector.concat",
+        "Vector.filter",
+        "Vector.join",
+        "Vector.map",
+        "Vector.slice",
+        "Math.abs",
+        "Math.ceil",
+        "Math.floor",
+        "Math.max",
+        "Math.min",
+        "Math.round",
+    ];
+
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn is_pure(method: &Entity) -> bool {
+        if method.metadata().iter().any(|m| m.name.0 == "Pure") {
+            return true;
+        }
+        let name = method.to_string();
+        Self::CORE_PURE_METHODS.contains(&name.as_str())
+    }
+
+    fn verify_statement(&mut self, exp: &Rc<Expression>) {
+        let mut exp = exp;
+        while let Expression::Paren(e) = exp.as_ref() {
+            exp = &e.expression;
+        }
+        let Expression::Call(call) = exp.as_ref() else {
+            return;
+        };
+        let Some(method) = referenced_entity(&self.verifier.host, &call.base) else {
+            return;
+        };
+        let method = if method.is::<MethodSlotAfterSubstitution>() { method.origin() } else { method };
+        if method.is::<MethodSlot>() && Self::is_pure(&method) {
+            self.verifier.add_warning(&exp.location(), WhackDiagnosticKind::DiscardedPureResult, diagarg![method.name().local_name()]);
+        }
+    }
+}
+
+impl<'a> Visitor for DiscardedResultSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ExpressionStatement(estmt) = drtv.as_ref() {
+            self.verify_statement(&estmt.expression);
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::ns::*;
+use std::collections::BTreeMap;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DynamicAccessKind {
+    /// `o.x` or `o.@x`.
+    Member,
+    /// `o[k]`.
+    Bracket,
+}
+
+/// A property access resolved only at runtime.
+#[derive(Clone, Debug)]
+pub struct AuditedDynamicAccess {
+    pub location: Location,
+    pub kind: DynamicAccessKind,
+    /// Accessed name, or `None` for a computed key.
+    pub name: Option<String>,
+}
+
+/// Report of the property accesses that could not be resolved statically,
+/// grouped by the static type of their receiver. Recorded only when
+/// `CompilerOptions::dynamic_access_audit` is set.
+#[derive(Clone, Default, Debug)]
+pub struct DynamicAccessAuditReport {
+    pub receivers: BTreeMap<String, Vec<AuditedDynamicAccess>>,
+}
+
+impl DynamicAccessAuditReport {
+    /// Name of the artifact holding the report.
+    pub const FILE_NAME: &'static str = "dynamic-access-audit.txt";
+
+    pub fn add(&mut self, receiver_type: &Entity, access: AuditedDynamicAccess) {
+        self.receivers.entry(receiver_type.to_string()).or_default().push(access);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.receivers.is_empty()
+    }
+
+    /// Number of accesses across all receiver types.
+    pub fn len(&self) -> usize {
+        self.receivers.values().map(|a| a.len()).sum()
+    }
+
+    /// Receiver types with their accesses, the most accessed first.
+    pub fn by_priority(&self) -> Vec<(&String, &Vec<AuditedDynamicAccess>)> {
+        let mut r = self.receivers.iter().collect::<Vec<_>>();
+        r.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+        r
+    }
+
+    pub fn format_human(&self) -> String {
+        if self.receivers.is_empty() {
+            return "No dynamic property accesses.\n".into();
+        }
+        let mut r = String::new();
+        for (receiver_type, accesses) in self.by_priority() {
+            r.push_str(&format!("{} ({} accesses):\n", receiver_type, accesses.len()));
+            for a in accesses.iter() {
+                let file_path = a.location.compilation_unit().file_path().unwrap_or("<unknown>".into());
+                let access = match (a.kind, a.name.as_ref()) {
+                    (DynamicAccessKind::Member, Some(name)) => format!(".{name}"),
+                    (DynamicAccessKind::Bracket, Some(name)) => format!("[\"{name}\"]"),
+                    _ => "[...]".into(),
+                };
+                r.push_str(&format!("  {}:{}:{}: {access}\n", file_path, a.location.first_line_number(), a.location.first_column() + 1));
+            }
+        }
+        r
+    }
+}
+
+/// Records dynamic property accesses into the `DynamicAccessAuditReport`
+/// of the verifier after verification.
+pub(crate) struct DynamicAccessAuditSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> DynamicAccessAuditSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn record(&mut self, exp: &Rc<Expression>, base: &Rc<Expression>, kind: DynamicAccessKind, name: Option<String>) {
+        let host = self.verifier.host.clone();
+        if !host.node_mapping().get(exp).map(|r| r.is::<DynamicReferenceValue>()).unwrap_or(false) {
+            return;
+        }
+        let Some(receiver_type) = host.node_mapping().get(base).map(|v| v.static_type(&host)) else {
+            return;
+        };
+        let access = AuditedDynamicAccess { location: exp.location(), kind, name };
+        self.verifier.dynamic_access_audit.as_mut().unwrap().add(&receiver_type, access);
+    }
+}
+
+impl<'a> Visitor for DynamicAccessAuditSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Member(e) => {
+                let name = e.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name);
+                self.record(exp, &e.base, DynamicAccessKind::Member, name);
+            },
+            Expression::ComputedMember(e) => {
+                let name = match e.key.as_ref() {
+                    Expression::StringLiteral(literal) => Some(literal.value.clone()),
+                    _ => None,
+                };
+                self.record(exp, &e.base, DynamicAccessKind::Bracket, name);
+            },
+            _ => {},
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -64,10 +64,13 @@ impl ExpSubverifier {
         }
 
         let r = verifier.scope().lookup_in_scope_chain(&verifier.host, qual, &key);
+        if verifier.scope_probe.is_some() {
+            verifier.capture_scope_snapshot(id, r.as_ref().ok().and_then(|r| r.as_ref()));
+        }
         if r.is_err() {
             match r.unwrap_err() {
                 PropertyLookupError::AmbiguousReference(name) => {
-                    verifier.add_verify_error(&id.location, WhackDiagnosticKind::AmbiguousReference, diagarg![name.clone()]);
+                    Self::report_ambiguous_reference(verifier, id, &name);
                     return Ok(None);
                 },
                 PropertyLookupError::Defer => {
@@ -97,6 +100,59 @@ impl ExpSubverifier {
         verifier.reference_post_processing(r, context)
     }
 
+    /// Reports an ambiguous unqualified name. If it comes from several
+    /// wildcard imports, the candidates are listed and fixes that qualify
+    /// the reference or import one of them explicitly are suggested.
+    fn report_ambiguous_reference(verifier: &mut Subverifier, id: &QualifiedIdentifier, name: &str) {
+        let host = verifier.host.clone();
+        let open_ns_set = verifier.scope().concat_open_ns_set_of_scope_chain();
+        let mut candidates: Vec<(String, Option<Location>)> = vec![];
+        for import in verifier.scope().imports() {
+            if !import.is::<PackageWildcardImport>() {
+                continue;
+            }
+            let pckg = import.package();
+            if let Ok(Some(_)) = pckg.properties(&host).get_in_ns_set_or_any_public_ns(&open_ns_set, name) {
+                let mut fqn = pckg.fully_qualified_name_list();
+                fqn.push(name.to_owned());
+                let fqn = fqn.join(".");
+                if !candidates.iter().any(|(c, _)| *c == fqn) {
+                    candidates.push((fqn, import.location()));
+                }
+            }
+        }
+
+        if candidates.len() < 2 {
+            verifier.add_verify_error(&id.location, WhackDiagnosticKind::AmbiguousReference, diagarg![name.to_owned()]);
+            return;
+        }
+        let list = candidates.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>().join(", ");
+        verifier.add_verify_error(&id.location, WhackDiagnosticKind::AmbiguousWildcardImportReference, diagarg![name.to_owned(), list]);
+
+        let text = id.location.compilation_unit().text();
+        for (fqn, import_location) in candidates {
+            if id.qualifier.is_none() {
+                verifier.suggested_fixes.push(SuggestedFix {
+                    location: id.location.clone(),
+                    title: format!("Qualify as {fqn}"),
+                    edits: vec![SourceEdit::replace(&id.location, &fqn)],
+                });
+            }
+            if let Some(import_location) = import_location {
+                // Insert before the wildcard import, on its own line.
+                let offset = import_location.first_offset();
+                let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+                let indent = &text[line_start..offset];
+                let indent = if indent.trim().is_empty() { indent } else { "" };
+                verifier.suggested_fixes.push(SuggestedFix {
+                    location: id.location.clone(),
+                    title: format!("Import {fqn}"),
+                    edits: vec![SourceEdit::insert_before(&import_location, &format!("import {fqn};\n{indent}"))],
+                });
+            }
+        }
+    }
+
     fn filter_inline_constant(verifier: &mut Subverifier, id: &QualifiedIdentifier) -> Option<(String, String)> {
         let QualifiedIdentifier { qualifier, id, .. } = id;
 
@@ -271,6 +327,9 @@ impl ExpSubverifier {
         let activation = verifier.scope().search_activation();
         if activation.is_some() && activation.as_ref().unwrap().this().is_some() {
             Ok(activation.clone().unwrap().this())
+        } else if activation.as_ref().map(|a| !a.is_package_initialization()).unwrap_or(false) {
+            verifier.add_verify_error(&literal.location, WhackDiagnosticKind::ThisUsedInStaticContext, diagarg![]);
+            Ok(None)
         } else {
             verifier.add_verify_error(&literal.location, WhackDiagnosticKind::UnexpectedThis, diagarg![]);
             Ok(None)
@@ -289,6 +348,7 @@ impl ExpSubverifier {
 
     pub fn verify_xml_exp(verifier: &mut Subverifier, exp: &XmlExpression, context: &VerifierExpressionContext) -> Result<Option<Entity>, DeferError> {
         Self::verify_xml_elem(verifier, &exp.element)?;
+        Self::verify_xml_names(verifier, &exp.element, &mut vec![]);
         if let Some(t) = context.context_type.as_ref() {
             let t_esc = t.escape_of_nullable_or_non_nullable();
             if [verifier.host.any_type(), verifier.host.object_type().defer()?, verifier.host.xml_type().defer()?].contains(&t_esc) {
@@ -302,6 +362,11 @@ impl ExpSubverifier {
         for content in exp.content.iter() {
             Self::verify_xml_content(verifier, content)?;
         }
+        for content in exp.content.iter() {
+            if let XmlContent::Element(elem) = content.as_ref() {
+                Self::verify_xml_names(verifier, elem, &mut vec![]);
+            }
+        }
         if let Some(t) = context.context_type.as_ref() {
             let t_esc = t.escape_of_nullable_or_non_nullable();
             if [verifier.host.any_type(), verifier.host.object_type().defer()?, verifier.host.xml_list_type().defer()?].contains(&t_esc) {
@@ -348,6 +413,56 @@ impl ExpSubverifier {
         }
     }
 
+    /// Checks the end tag, attribute names and namespace prefixes of an
+    /// XML literal element and its descendants. `prefixes` holds the
+    /// prefixes declared by the enclosing elements.
+    pub fn verify_xml_names(verifier: &mut Subverifier, elem: &XmlElement, prefixes: &mut Vec<String>) {
+        let enclosing_prefixes = prefixes.len();
+        for attr in &elem.attributes {
+            if let Some(prefix) = attr.name.0.strip_prefix("xmlns:") {
+                prefixes.push(prefix.to_owned());
+            }
+        }
+        let mut attribute_names = HashSet::<String>::new();
+        for attr in &elem.attributes {
+            let (name, location) = &attr.name;
+            if name == "xmlns" || name.starts_with("xmlns:") {
+                continue;
+            }
+            if !attribute_names.insert(name.clone()) {
+                verifier.add_verify_error(location, WhackDiagnosticKind::DuplicateXmlAttribute, diagarg![name.clone()]);
+            }
+            Self::verify_xml_prefix(verifier, name, location, prefixes);
+        }
+        if let XmlTagName::Name((name, location)) = &elem.name {
+            Self::verify_xml_prefix(verifier, name, location, prefixes);
+            if let Some(XmlTagName::Name((closing_name, closing_location))) = &elem.closing_name {
+                if closing_name != name {
+                    verifier.add_verify_error(closing_location, WhackDiagnosticKind::XmlEndTagMismatch, diagarg![closing_name.clone(), name.clone()]);
+                }
+            }
+        }
+        if let Some(content_list) = &elem.content {
+            for content in content_list {
+                if let XmlContent::Element(child) = content.as_ref() {
+                    Self::verify_xml_names(verifier, child, prefixes);
+                }
+            }
+        }
+        prefixes.truncate(enclosing_prefixes);
+    }
+
+    fn verify_xml_prefix(verifier: &mut Subverifier, name: &str, location: &Location, prefixes: &[String]) {
+        let Some((prefix, _)) = name.split_once(':') else {
+            return;
+        };
+        if prefix == "xml" || prefixes.iter().any(|p| p == prefix) {
+            return;
+        }
+        let prefix_location = Location::with_offsets(&location.compilation_unit(), location.first_offset(), location.first_offset() + prefix.len());
+        verifier.add_verify_error(&prefix_location, WhackDiagnosticKind::UndeclaredXmlNamespacePrefix, diagarg![prefix.to_owned()]);
+    }
+
     pub fn verify_new_exp(verifier: &mut Subverifier, exp: &NewExpression) -> Result<Option<Entity>, DeferError> {
         let Some(base) = verifier.verify_expression(&exp.base, &default())? else {
             if let Some(arguments) = &exp.arguments {
@@ -375,7 +490,7 @@ impl ExpSubverifier {
             let ctor = t.constructor_method(&verifier.host);
 
             if let Some(ctor) = ctor {
-                let sig = ctor.signature(&verifier.host).defer()?;
+                let sig = verifier.defer_on(&ctor, ctor.signature(&verifier.host))?;
                 match ArgumentsSubverifier::verify(verifier, exp.arguments.as_ref().unwrap_or(&vec![]), &sig) {
                     Ok(_) => {},
                     Err(VerifierArgumentsError::Defer) => {
@@ -431,6 +546,12 @@ impl ExpSubverifier {
             return Ok(None);
         };
 
+        // Absorb lookups on an invalidated base
+        if base.is::<InvalidationEntity>() || base.static_type(&verifier.host).is::<InvalidationEntity>() {
+            Self::verify_qualified_identifier(verifier, id)?;
+            return Ok(None);
+        }
+
         let qn = Self::verify_qualified_identifier(verifier, id)?;
         if qn.is_none() {
             return Ok(None);
@@ -464,6 +585,11 @@ impl ExpSubverifier {
             }
         }
         let r = r.unwrap();
+        if r.is_none() && context.followed_by_call && qual.is_none() {
+            if let Some(extension) = ExtensionMethodSubverifier::resolve(verifier, &base, &key.local_name().unwrap(), &id.location)? {
+                return Ok(Some(extension));
+            }
+        }
         if r.is_none() {
             verifier.add_verify_error(&id.location, WhackDiagnosticKind::UndefinedPropertyWithStaticType, diagarg![key.local_name().unwrap(), base.static_type(&verifier.host)]);
             return Ok(None);
@@ -505,7 +631,8 @@ impl ExpSubverifier {
                             return Err(DeferError(None));
                         },
                         Err(_) => {
-                            panic!();
+                            verifier.add_internal_error(&member_exp.identifier.location, "unexpected property lookup error");
+                            return Ok(None);
                         },
                     }
                 }
@@ -548,7 +675,8 @@ impl ExpSubverifier {
                     return Err(DeferError(None));
                 },
                 Err(_) => {
-                    panic!();
+                    verifier.add_internal_error(location, "unexpected property lookup error");
+                    return Ok(None);
                 },
             }
         } else if import.is::<PackageRecursiveImport>() {
@@ -571,7 +699,8 @@ impl ExpSubverifier {
                     return Err(DeferError(None));
                 },
                 Err(_) => {
-                    panic!();
+                    verifier.add_internal_error(location, "unexpected property lookup error");
+                    return Ok(None);
                 },
             }
         } else {
@@ -611,16 +740,52 @@ impl ExpSubverifier {
             return Ok(None);
         };
 
-        let Some(key) = verifier.verify_expression(&member_exp.key, &default())? else {
+        // Absorb lookups on an invalidated base
+        if base.is::<InvalidationEntity>() || base.static_type(&verifier.host).is::<InvalidationEntity>() {
+            verifier.verify_expression(&member_exp.key, &default())?;
             return Ok(None);
+        }
+
+        let base_st = base.static_type(&verifier.host).escape_of_non_nullable();
+
+        // Map.<K, V> indexer
+        let map_kv = CollectionTypes::map_key_value_types(&verifier.host, &base_st);
+
+        let key = if let Some((key_t, _)) = map_kv.as_ref() {
+            verifier.imp_coerce_exp(&member_exp.key, key_t)?
+        } else {
+            verifier.verify_expression(&member_exp.key, &default())?
         };
+        let Some(key) = key else {
+            return Ok(None);
+        };
+
+        if let Some((_, value_t)) = map_kv {
+            if context.mode == VerifyMode::Read && !context.followed_by_call {
+                return Ok(Some(verifier.host.factory().create_value(&value_t)));
+            }
+        }
+
+        // Tuple indexed by constant
+        if base_st.is::<TupleType>() && key.is::<NumberConstant>() {
+            let elem_types = base_st.element_types();
+            let index = key.number_value().force_double();
+            if index.fract() != 0.0 || index < 0.0 || index >= elem_types.length() as f64 {
+                verifier.add_verify_error(&member_exp.key.location(), WhackDiagnosticKind::TupleIndexOutOfRange, diagarg![base_st.clone()]);
+                return Ok(None);
+            }
+            if context.mode == VerifyMode::Read && !context.followed_by_call {
+                return Ok(Some(verifier.host.factory().create_value(&elem_types.get(index as usize).unwrap())));
+            }
+        }
 
         let open_ns_set = verifier.scope().concat_open_ns_set_of_scope_chain();
         let r = PropertyLookup(&verifier.host).lookup_in_object(&base, &open_ns_set, None, &PropertyLookupKey::Computed(key.clone()), context.followed_by_call);
         if r.is_err() {
             match r.unwrap_err() {
-                PropertyLookupError::AmbiguousReference(_) => {
-                    panic!();
+                PropertyLookupError::AmbiguousReference(name) => {
+                    verifier.add_verify_error(&member_exp.key.location(), WhackDiagnosticKind::AmbiguousReference, diagarg![name.clone()]);
+                    return Ok(None);
                 },
                 PropertyLookupError::Defer => {
                     return Err(DeferError(None));
@@ -635,11 +800,10 @@ impl ExpSubverifier {
                 },
             }
         }
-        let r = r.unwrap();
-        if r.is_none() {
-            panic!();
-        }
-        let r = r.unwrap();
+        let Some(r) = r.unwrap() else {
+            verifier.add_internal_error(&member_exp.key.location(), "computed property lookup yielded no reference");
+            return Ok(None);
+        };
 
         // No need to mark local capture for the property operator.
         // verifier.detect_local_capture(&r);
@@ -781,6 +945,7 @@ impl ExpSubverifier {
         if let Some(base_type) = base.as_type() {
             let array_type = verifier.host.array_type().defer()?;
             let date_type = verifier.host.date_type().defer()?;
+            let vector_type = verifier.host.vector_type().defer()?;
             // new Array
             if base_type == array_type || base_type.type_after_sub_has_origin(&array_type) {
                 for arg in &exp.arguments {
@@ -796,6 +961,32 @@ impl ExpSubverifier {
                 }
                 verifier.add_warning(&exp.base.location(), WhackDiagnosticKind::CallOnDateType, diagarg![]);
                 return Ok(Some(verifier.host.factory().create_value(&string_type)));
+            // Vector.<T>(x)
+            } else if base_type.type_after_sub_has_origin(&vector_type) {
+                let mut first = true;
+                for arg in &exp.arguments {
+                    let Some(val) = verifier.verify_expression(arg, &default())? else {
+                        first = false;
+                        continue;
+                    };
+                    if first {
+                        let st = val.static_type(&verifier.host).escape_of_non_nullable();
+                        let convertible = st == verifier.host.any_type()
+                            || st == verifier.host.object_type().defer()?
+                            || st == array_type || st.type_after_sub_has_origin(&array_type)
+                            || st.type_after_sub_has_origin(&vector_type);
+                        if !convertible {
+                            verifier.add_verify_error(&arg.location(), WhackDiagnosticKind::VectorConversionRequiresArrayOrVector, diagarg![base_type.clone(), st]);
+                        }
+                    }
+                    first = false;
+                }
+                if exp.arguments.is_empty() {
+                    verifier.add_verify_error(&exp.base.location(), WhackDiagnosticKind::IncorrectNumArguments, diagarg!["1".to_string()]);
+                } else if exp.arguments.len() > 1 {
+                    verifier.add_verify_error(&exp.base.location(), WhackDiagnosticKind::IncorrectNumArgumentsNoMoreThan, diagarg!["1".to_string()]);
+                }
+                return Ok(Some(verifier.host.factory().create_value(&base_type)));
             // Type cast
             } else {
                 let mut first = true;
@@ -810,7 +1001,7 @@ impl ExpSubverifier {
                     }
                     first = false;
                 }
-                if exp.arguments.len() < 1 {
+                if exp.arguments.is_empty() {
                     verifier.add_verify_error(&exp.base.location(), WhackDiagnosticKind::IncorrectNumArguments, diagarg!["1".to_string()]);
                 } else if exp.arguments.len() > 1 {
                     verifier.add_verify_error(&exp.base.location(), WhackDiagnosticKind::IncorrectNumArgumentsNoMoreThan, diagarg!["1".to_string()]);
@@ -820,7 +1011,8 @@ impl ExpSubverifier {
         }
 
         if base.is::<FixtureReferenceValue>() && base.property().is::<MethodSlot>() {
-            let sig = base.property().signature(&verifier.host).defer()?;
+            let method = base.property();
+            let sig = verifier.defer_on(&method, method.signature(&verifier.host))?;
             match ArgumentsSubverifier::verify(verifier, &exp.arguments, &sig) {
                 Ok(_) => {},
                 Err(VerifierArgumentsError::Defer) => {
@@ -833,7 +1025,17 @@ impl ExpSubverifier {
                     verifier.add_verify_error(&exp.base.location(), WhackDiagnosticKind::IncorrectNumArgumentsNoMoreThan, diagarg![n.to_string()]);
                 },
             }
-            return Ok(Some(verifier.host.factory().create_value(&sig.result_type())));
+            let mut result_type = sig.result_type();
+
+            // Vector methods returning a Vector of the same element type
+            if result_type == verifier.host.any_type() && ["concat", "filter", "map", "reverse", "slice", "sort", "splice"].contains(&base.property().name().local_name().as_str()) {
+                let object_type = base.base().static_type(&verifier.host).escape_of_non_nullable();
+                if object_type.vector_element_type(&verifier.host)?.is_some() {
+                    result_type = object_type;
+                }
+            }
+
+            return Ok(Some(verifier.host.factory().create_value(&result_type)));
         }
 
         let base_st = base.static_type(&verifier.host);
@@ -935,14 +1137,39 @@ impl ExpSubverifier {
         Ok(Some(verifier.host.factory().create_type_after_substitution(&base, &resolvee_args).wrap_property_reference(&verifier.host)?))
     }
 
+    /// Result type of awaiting an instance of `CompilerOptions::promise_like_type`.
+    fn promise_like_result_type(verifier: &mut Subverifier, t: &Entity) -> Result<Option<Entity>, DeferError> {
+        let host = verifier.host.clone();
+        let Some(class) = verifier.promise_like_type.as_ref().and_then(|name| verifier.resolve_qualified_name(name)).filter(|c| c.is::<ClassType>()) else {
+            return Ok(None);
+        };
+        if t.is::<TypeAfterSubstitution>() && t.origin() == class {
+            return Ok(t.substitute_types().get(0).or(Some(host.any_type())));
+        }
+        if *t == class || verifier.type_relations.is_subtype_of(&host, t, &class)? {
+            return Ok(Some(host.any_type()));
+        }
+        Ok(None)
+    }
+
     pub fn verify_unary_exp(verifier: &mut Subverifier, exp: &UnaryExpression) -> Result<Option<Entity>, DeferError> {
         if exp.operator == Operator::Await {
             let Some(val) = verifier.verify_expression(&exp.expression, &default())? else {
                 return Ok(None);
             };
 
-            let val_st = val.static_type(&verifier.host);
-            let Some(result_type) = val_st.escape_of_non_nullable().promise_result_type(&verifier.host)? else {
+            let val_st = val.static_type(&verifier.host).escape_of_non_nullable();
+
+            // Untyped operands may be thenables at runtime.
+            if val_st == verifier.host.any_type() || val_st == verifier.host.object_type().defer()? {
+                return Ok(Some(verifier.host.factory().create_value(&verifier.host.any_type())));
+            }
+
+            let result_type = match val_st.promise_result_type(&verifier.host)? {
+                Some(result_type) => Some(result_type),
+                None => Self::promise_like_result_type(verifier, &val_st)?,
+            };
+            let Some(result_type) = result_type else {
                 verifier.add_verify_error(&exp.location, WhackDiagnosticKind::AwaitOperandMustBeAPromise, diagarg![]);
                 return Ok(None);
             };
@@ -1057,7 +1284,8 @@ impl ExpSubverifier {
                 Ok(Some(verifier.host.factory().create_value(&verifier.host.boolean_type().defer()?)))
             },
             _ => {
-                panic!();
+                verifier.add_internal_error(&exp.location, "unexpected unary operator");
+                Ok(None)
             },
         }
     }
@@ -1105,6 +1333,70 @@ impl ExpSubverifier {
         Ok(Some(verifier.host.factory().create_value(&nullable_result_type)))
     }
 
+    fn classes_without_common_subtype(verifier: &Subverifier, left_st: &Entity, right_st: &Entity) -> Result<bool, DeferError> {
+        let left_esc = left_st.escape_of_nullable_or_non_nullable();
+        let right_esc = right_st.escape_of_nullable_or_non_nullable();
+        if !(left_esc.is::<ClassType>() && right_esc.is::<ClassType>()) || left_esc == right_esc {
+            return Ok(false);
+        }
+        let host = &verifier.host;
+        Ok(!verifier.type_relations.is_subtype_of(host, &left_esc, &right_esc)? && !verifier.type_relations.is_subtype_of(host, &right_esc, &left_esc)?)
+    }
+
+    /// Generates warnings for suspicious equality comparisons.
+    fn verify_equality_operands(verifier: &mut Subverifier, exp: &BinaryExpression, left: &Entity, right: &Entity) -> Result<(), DeferError> {
+        let left_st = left.static_type(&verifier.host);
+        let right_st = right.static_type(&verifier.host);
+
+        // Generate warning for unrelated types
+        if left.is_comparison_between_unrelated_types(right, &verifier.host)? {
+            verifier.add_warning(&exp.location, WhackDiagnosticKind::ComparisonBetweenUnrelatedTypes, diagarg![left_st.clone(), right_st.clone()]);
+        } else if Self::classes_without_common_subtype(verifier, &left_st, &right_st)? {
+            // Two classes neither of which extends the other have no common
+            // instance, so their values compare equal only when both are null.
+            verifier.add_warning(&exp.location, WhackDiagnosticKind::ComparisonBetweenTypesWithoutCommonSubtype, diagarg![left_st.clone(), right_st.clone()]);
+        }
+
+        // Generate warning for implicit Number-to-String comparison
+        if matches!(exp.operator, Operator::Equals | Operator::NotEquals) {
+            let numeric_types = verifier.host.numeric_types()?;
+            let string_type = verifier.host.string_type().defer()?;
+            let left_esc = left_st.escape_of_non_nullable();
+            let right_esc = right_st.escape_of_non_nullable();
+            if (numeric_types.contains(&left_esc) && right_esc == string_type) || (left_esc == string_type && numeric_types.contains(&right_esc)) {
+                verifier.add_warning(&exp.location, WhackDiagnosticKind::ImplicitNumberStringComparison, diagarg![left_st.clone(), right_st.clone()]);
+            }
+        }
+
+        // Generate warning for NaN comparison
+        if left.is::<NumberConstant>() && left.number_value().is_nan() {
+            verifier.add_warning(&exp.location, WhackDiagnosticKind::NanComparison, diagarg![]);
+        } else if right.is::<NumberConstant>() && right.number_value().is_nan() {
+            verifier.add_warning(&exp.location, WhackDiagnosticKind::NanComparison, diagarg![]);
+        }
+
+        // Generate warning for comparing with undefined a value of a type
+        // that does not hold undefined, nor null for a loose comparison,
+        // as null == undefined.
+        let other_st = if left.is::<UndefinedConstant>() {
+            Some(right_st)
+        } else if right.is::<UndefinedConstant>() {
+            Some(left_st)
+        } else {
+            None
+        };
+        if let Some(other_st) = other_st {
+            let strict = matches!(exp.operator, Operator::StrictEquals | Operator::StrictNotEquals);
+            let equal_to_undefined = other_st.includes_undefined(&verifier.host)? || (!strict && other_st.includes_null(&verifier.host)?);
+            if !equal_to_undefined && !other_st.is::<InvalidationEntity>() {
+                let result = matches!(exp.operator, Operator::NotEquals | Operator::StrictNotEquals);
+                verifier.add_warning(&exp.location, WhackDiagnosticKind::ComparisonWithUndefined, diagarg![verifier.display_type(&other_st), result.to_string()]);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn verify_binary_exp(verifier: &mut Subverifier, exp: &BinaryExpression) -> Result<Option<Entity>, DeferError> {
         let Some(left) = verifier.verify_expression(&exp.left, &default())? else {
             verifier.verify_expression(&exp.right, &default())?;
@@ -1114,6 +1406,10 @@ impl ExpSubverifier {
         let left_st = left.static_type(&verifier.host);
         let left_st_esc = left_st.escape_of_non_nullable();
 
+        if let Some(result) = OperatorOverloadingSubverifier::verify(verifier, exp, &left_st_esc)? {
+            return Ok(result);
+        }
+
         match exp.operator {
             Operator::Add => {
                 let Some(right) = verifier.verify_expression(&exp.right, &VerifierExpressionContext {
@@ -1303,20 +1599,8 @@ impl ExpSubverifier {
                 let Some(right) = verifier.verify_expression(&exp.right, &default())? else {
                     return Ok(None);
                 };
-                let right_st = right.static_type(&verifier.host);
                 let boolean_type = verifier.host.boolean_type().defer()?;
-
-                // Generate warning for unrelated types
-                if left.is_comparison_between_unrelated_types(&right, &verifier.host)? {
-                    verifier.add_warning(&exp.location, WhackDiagnosticKind::ComparisonBetweenUnrelatedTypes, diagarg![left_st.clone(), right_st.clone()]);
-                }
-
-                // Generate warning for NaN comparison
-                if left.is::<NumberConstant>() && left.number_value().is_nan() {
-                    verifier.add_warning(&exp.location, WhackDiagnosticKind::NanComparison, diagarg![]);
-                } else if right.is::<NumberConstant>() && right.number_value().is_nan() {
-                    verifier.add_warning(&exp.location, WhackDiagnosticKind::NanComparison, diagarg![]);
-                }
+                Self::verify_equality_operands(verifier, exp, &left, &right)?;
 
                 if left.is::<NumberConstant>() && right.is::<NumberConstant>() {
                     return Ok(Some(verifier.host.factory().create_boolean_constant(left.number_value() == right.number_value(), &boolean_type)));
@@ -1334,20 +1618,8 @@ impl ExpSubverifier {
                 let Some(right) = verifier.verify_expression(&exp.right, &default())? else {
                     return Ok(None);
                 };
-                let right_st = right.static_type(&verifier.host);
                 let boolean_type = verifier.host.boolean_type().defer()?;
-
-                // Generate warning for unrelated types
-                if left.is_comparison_between_unrelated_types(&right, &verifier.host)? {
-                    verifier.add_warning(&exp.location, WhackDiagnosticKind::ComparisonBetweenUnrelatedTypes, diagarg![left_st.clone(), right_st.clone()]);
-                }
-
-                // Generate warning for NaN comparison
-                if left.is::<NumberConstant>() && left.number_value().is_nan() {
-                    verifier.add_warning(&exp.location, WhackDiagnosticKind::NanComparison, diagarg![]);
-                } else if right.is::<NumberConstant>() && right.number_value().is_nan() {
-                    verifier.add_warning(&exp.location, WhackDiagnosticKind::NanComparison, diagarg![]);
-                }
+                Self::verify_equality_operands(verifier, exp, &left, &right)?;
 
                 if left.is::<NumberConstant>() && right.is::<NumberConstant>() {
                     return Ok(Some(verifier.host.factory().create_boolean_constant(left.number_value() != right.number_value(), &boolean_type)));
@@ -1531,12 +1803,16 @@ impl ExpSubverifier {
 
                 Ok(Some(verifier.host.factory().create_value(&left_st)))
             },
-            _ => panic!(),
+            _ => {
+                verifier.add_internal_error(&exp.location, "unexpected binary operator");
+                Ok(None)
+            },
         }
     }
 
     pub fn verify_conditional_exp(verifier: &mut Subverifier, exp: &ConditionalExpression, context: &VerifierExpressionContext) -> Result<Option<Entity>, DeferError> {
-        verifier.verify_expression(&exp.test, &default())?;
+        let test = verifier.verify_expression(&exp.test, &default())?;
+        BooleanTest::check(verifier, &exp.test, test.as_ref());
         let ctx1 = VerifierExpressionContext {
             context_type: context.context_type.clone(),
             ..default()
@@ -1733,7 +2009,7 @@ impl ExpSubverifier {
                         }
                     },
 
-                    _ => panic!(),
+                    _ => verifier.add_internal_error(&exp.location, "unexpected compound assignment operator"),
                 }
             }
 
@@ -1753,10 +2029,23 @@ impl ExpSubverifier {
         }
     }
 
-    pub fn verify_function_exp(verifier: &mut Subverifier, exp: &FunctionExpression) -> Result<Option<Entity>, DeferError> {
+    fn inferred_param_type(host: &Database, context_sig: Option<&Entity>, index: usize) -> Entity {
+        context_sig.and_then(|sig| sig.params().get(index))
+            .filter(|param| param.kind != ParameterKind::Rest)
+            .map(|param| param.static_type.clone())
+            .unwrap_or(host.any_type())
+    }
+
+    pub fn verify_function_exp(verifier: &mut Subverifier, exp: &FunctionExpression, context: &VerifierExpressionContext) -> Result<Option<Entity>, DeferError> {
         let host = verifier.host.clone();
         let kscope = verifier.scope();
 
+        // Expected function type, from which the types of
+        // unannotated parameters are inferred.
+        let context_sig = context.context_type.as_ref()
+            .map(|t| t.escape_of_nullable_or_non_nullable())
+            .filter(|t| t.is::<FunctionType>());
+
         let common = exp.common.clone();
         
         let internal_ns = kscope.search_system_ns_in_scope_chain(SystemNamespaceKind::Internal).unwrap();
@@ -1816,7 +2105,7 @@ impl ExpSubverifier {
                         if let Some(type_annot) = param_node.destructuring.type_annotation.as_ref() {
                             param_type = verifier.verify_type_expression(type_annot)?.unwrap_or(host.invalidation_entity());
                         } else {
-                            param_type = host.any_type();
+                            param_type = Self::inferred_param_type(&host, context_sig.as_ref(), params.len());
                         }
 
                         let pattern = &param_node.destructuring.destructuring;
@@ -1851,7 +2140,7 @@ impl ExpSubverifier {
                         if let Some(type_annot) = param_node.destructuring.type_annotation.as_ref() {
                             param_type = verifier.verify_type_expression(type_annot)?.unwrap_or(host.invalidation_entity());
                         } else {
-                            param_type = host.any_type();
+                            param_type = Self::inferred_param_type(&host, context_sig.as_ref(), params.len());
                         }
 
                         let pattern = &param_node.destructuring.destructuring;
@@ -0,0 +1,82 @@
+use crate::ns::*;
+
+/// A call of an extension method through its receiver, as `s.trimmed()`
+/// calling `[Extension] static function trimmed(s: String): String`.
+#[derive(Clone, Debug)]
+pub struct ExtensionMethodCall {
+    /// Location of the member name.
+    pub location: Location,
+    pub method: Entity,
+    pub receiver_type: Entity,
+}
+
+impl ExtensionMethodCall {
+    /// Hover text naming the real target, such as
+    /// `StringUtils.function trimmed(s: String): String`.
+    pub fn hover_text(&self, host: &Database) -> String {
+        let display = EntityDisplay::new(host, DisplayVerbosity::Short);
+        let class_name = self.method.parent().map(|c| display.type_name(&c)).unwrap_or_default();
+        format!("(extension) {class_name}.{}", display.signature(&self.method.name().local_name(), &self.method.signature(host), &[]))
+    }
+}
+
+/// Resolves calls of undefined instance methods to static methods marked
+/// with `[Extension]` meta-data, whose first parameter accepts the
+/// receiver. Only classes imported by name are searched.
+pub(crate) struct ExtensionMethodSubverifier;
+
+impl ExtensionMethodSubverifier {
+    fn accepts_receiver(host: &Database, relations: &TypeRelationCache, method: &Entity, receiver_type: &Entity) -> bool {
+        if !(method.is::<MethodSlot>() && method.is_static() && method.metadata().iter().any(|m| m.name.0 == "Extension")) {
+            return false;
+        }
+        let signature = method.signature(host);
+        let Some(first) = signature.params().first().map(|p| p.static_type.clone()) else {
+            return false;
+        };
+        first == *receiver_type || relations.is_subtype_of(host, receiver_type, &first).unwrap_or(false)
+    }
+
+    /// Returns a value of the method type without the receiver parameter,
+    /// or `None` if no extension method applies.
+    pub fn resolve(verifier: &mut Subverifier, base: &Entity, name: &str, location: &Location) -> Result<Option<Entity>, DeferError> {
+        let host = verifier.host.clone();
+        let receiver_type = base.static_type(&host).escape_of_nullable_or_non_nullable();
+        let mut found: Option<(Entity, Entity)> = None;
+        for import in verifier.scope().imports() {
+            if !import.is::<PackagePropertyImport>() {
+                continue;
+            }
+            let class = import.property();
+            class.defer()?;
+            if !class.is::<ClassType>() {
+                continue;
+            }
+            let Some(method) = class.properties(&host).get_in_any_public_ns(name).ok().flatten() else {
+                continue;
+            };
+            if !Self::accepts_receiver(&host, &verifier.type_relations, &method, &receiver_type) {
+                continue;
+            }
+            if let Some((previous, _)) = found.as_ref().filter(|(m, _)| *m != method) {
+                verifier.add_verify_error(location, WhackDiagnosticKind::AmbiguousExtensionMethod, diagarg![name.to_owned(), previous.parent().unwrap(), class]);
+                return Ok(Some(host.invalidation_entity()));
+            }
+            found = Some((method, import));
+        }
+        let Some((method, import)) = found else {
+            return Ok(None);
+        };
+        Unused(&host).mark_used(&import);
+
+        let signature = method.signature(&host);
+        let params = signature.params().iter().skip(1).cloned().collect::<Vec<_>>();
+        let bound_type = host.factory().create_function_type(params, signature.result_type());
+        verifier.extension_method_calls.push(ExtensionMethodCall {
+            location: location.clone(),
+            method,
+            receiver_type,
+        });
+        Ok(Some(host.factory().create_value(&bound_type)))
+    }
+}
\ No newline at end of file
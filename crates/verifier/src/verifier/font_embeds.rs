@@ -0,0 +1,117 @@
+use crate::ns::*;
+
+/// Verifies `[Embed]` meta-data embedding font files against the fonts
+/// themselves: the file must be a TrueType or OpenType font, the style
+/// options should match it and the glyphs of `unicodeRange` should
+/// be present. Verified fonts are recorded with their tags in
+/// `Subverifier::embedded_fonts`.
+pub(crate) struct FontEmbedSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    source_path: &'a [String],
+    /// Fonts read so far by path.
+    fonts: HashMap<String, Option<Rc<FontFile>>>,
+}
+
+impl<'a> FontEmbedSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], source_path: &'a [String]) {
+        let mut subverifier = Self { verifier, source_path, fonts: HashMap::new() };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_attributes(&mut self, attributes: &[Attribute]) {
+        for metadata in Attribute::find_metadata(attributes).iter() {
+            if let Some(options) = FontEmbedOptions::from_metadata(metadata) {
+                self.verify_font(&options, &metadata.location);
+            }
+        }
+    }
+
+    fn verify_font(&mut self, options: &FontEmbedOptions, location: &Location) {
+        if options.font_name.is_none() {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::EmbeddedFontNameMissing, diagarg![]);
+        }
+        if options.embed_as_cff().is_none() {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::EmbedOptionMustBeBoolean, diagarg!["embedAsCFF".to_owned()]);
+        }
+        let Some(ranges) = options.ranges() else {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::InvalidUnicodeRange, diagarg![options.unicode_range.clone().unwrap_or_default()]);
+            return;
+        };
+        let Some((path, font, data)) = self.read_font(&options.source, location) else {
+            return;
+        };
+        let font_name = font.full_name.clone().or(font.family_name.clone()).unwrap_or(options.source.clone());
+        if options.is_bold() != font.bold {
+            self.verifier.add_warning(location, WhackDiagnosticKind::EmbeddedFontStyleMismatch, diagarg![font_name.clone(), "fontWeight".to_owned()]);
+        }
+        if options.is_italic() != font.italic {
+            self.verifier.add_warning(location, WhackDiagnosticKind::EmbeddedFontStyleMismatch, diagarg![font_name.clone(), "fontStyle".to_owned()]);
+        }
+        // Without a unicodeRange, the whole font is embedded as is
+        if options.unicode_range.is_some() {
+            let missing = font.missing_ranges(&ranges);
+            if !missing.is_empty() {
+                self.verifier.add_warning(location, WhackDiagnosticKind::EmbeddedFontMissingGlyphs, diagarg![font_name.clone(), UnicodeRange::format(&missing)]);
+            }
+        }
+
+        let character_id = self.verifier.embedded_fonts.len() as u16 + 1;
+        let embed_as_cff = options.embed_as_cff().unwrap_or(true);
+        let tag = if font.cff {
+            if !embed_as_cff {
+                self.verifier.add_verify_error(location, WhackDiagnosticKind::EmbeddedFontUsesCffOutlines, diagarg![font_name]);
+                return;
+            }
+            CodegenFontEmbed::define_font4(character_id, options, &font, &data)
+        } else {
+            if embed_as_cff {
+                self.verifier.add_warning(location, WhackDiagnosticKind::EmbeddedFontUsesTrueTypeOutlines, diagarg![font_name]);
+            }
+            CodegenFontEmbed::define_font3(character_id, options, &font, &data, &ranges).ok()
+        };
+        let Some(tag) = tag else {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::InvalidEmbeddedFont, diagarg![options.source.clone()]);
+            return;
+        };
+        self.verifier.embedded_fonts.push(EmbeddedFont {
+            location: location.clone(),
+            font_name: options.font_name.clone().or(font.family_name.clone()).unwrap_or_default(),
+            file_path: path,
+            character_id,
+            tag,
+        });
+    }
+
+    fn read_font(&mut self, source: &str, location: &Location) -> Option<(String, Rc<FontFile>, Rc<Vec<u8>>)> {
+        let file_path = location.compilation_unit().file_path().unwrap_or_default();
+        let Some((path, data)) = self.verifier.embedded_files.resolve(source, &file_path, self.source_path) else {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::EmbeddedFileNotFound, diagarg![source.to_owned()]);
+            return None;
+        };
+        let font = match self.fonts.get(&path) {
+            Some(font) => font.clone(),
+            None => {
+                let font = FontFile::parse(&data).ok().map(Rc::new);
+                self.fonts.insert(path.clone(), font.clone());
+                font
+            },
+        };
+        if font.is_none() {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::InvalidEmbeddedFont, diagarg![source.to_owned()]);
+        }
+        Some((path, font?, data))
+    }
+}
+
+impl<'a> Visitor for FontEmbedSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => self.verify_attributes(&defn.attributes),
+            Directive::VariableDefinition(defn) => self.verify_attributes(&defn.attributes),
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
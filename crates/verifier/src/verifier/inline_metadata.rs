@@ -0,0 +1,100 @@
+use crate::ns::*;
+
+/// Verifies methods marked with `[Inline]` meta-data.
+///
+/// Inlinable methods must be static, final, members of a final class or
+/// package-level, and their body must be free of closures and `try`
+/// statements, so that codegen may copy it into call sites.
+pub(crate) struct InlineMetadataSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Finality of each enclosing class.
+    class_stack: Vec<bool>,
+}
+
+impl<'a> InlineMetadataSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, class_stack: vec![] };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_function_defn(&mut self, defn: &FunctionDefinition) {
+        let Some(metadata) = Attribute::find_metadata(&defn.attributes).into_iter().find(|m| m.name.0 == "Inline") else {
+            return;
+        };
+        let is_static = Attribute::find_static(&defn.attributes).is_some();
+        let is_final = Attribute::find_final(&defn.attributes).is_some();
+        let in_final_class = self.class_stack.last().copied();
+        if in_final_class == Some(false) && !(is_static || is_final) {
+            self.verifier.add_verify_error(&metadata.location, WhackDiagnosticKind::InlineMethodMustBeFinalOrStatic, diagarg![]);
+            return;
+        }
+        if defn.common.body.is_none() {
+            self.verifier.add_verify_error(&metadata.location, WhackDiagnosticKind::InlineMethodMustHaveBody, diagarg![]);
+            return;
+        }
+        for (kind, location) in Self::restrictions(&defn.common) {
+            self.verifier.add_verify_error(&location, kind, diagarg![]);
+        }
+    }
+
+    /// Constructs not allowed in the body of an inlinable method.
+    pub fn restrictions(common: &Rc<FunctionCommon>) -> Vec<(WhackDiagnosticKind, Location)> {
+        let mut restrictions = InlineRestrictions { found: vec![] };
+        restrictions.visit_function_common(common);
+        restrictions.found
+    }
+}
+
+impl<'a> Visitor for InlineMetadataSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(defn) => {
+                self.verify_function_defn(defn);
+                walk_directive(self, drtv);
+            },
+            Directive::ClassDefinition(defn) => {
+                self.class_stack.push(Attribute::find_final(&defn.attributes).is_some());
+                walk_directive(self, drtv);
+                self.class_stack.pop();
+            },
+            Directive::EnumDefinition(_) => {
+                self.class_stack.push(true);
+                walk_directive(self, drtv);
+                self.class_stack.pop();
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+}
+
+/// Collects constructs not allowed in the body of an inlinable method.
+struct InlineRestrictions {
+    found: Vec<(WhackDiagnosticKind, Location)>,
+}
+
+impl Visitor for InlineRestrictions {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(defn) => {
+                self.found.push((WhackDiagnosticKind::InlineMethodContainsClosure, defn.location.clone()));
+            },
+            Directive::TryStatement(trystmt) => {
+                self.found.push((WhackDiagnosticKind::InlineMethodContainsTry, trystmt.location.clone()));
+            },
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Function(_) = exp.as_ref() {
+            self.found.push((WhackDiagnosticKind::InlineMethodContainsClosure, exp.location()));
+            return;
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
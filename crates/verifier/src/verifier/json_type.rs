@@ -0,0 +1,54 @@
+use crate::ns::*;
+
+/// Verifies classes marked with `[JsonType]` meta-data against the
+/// helpers `CodegenJsonType` generates for them: every field must have
+/// a JSON representation, the constructor must be callable without
+/// arguments, and the class must not declare the helpers itself.
+pub(crate) struct JsonTypeSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> JsonTypeSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_class(&mut self, drtv: &Rc<Directive>, defn: &ClassDefinition) {
+        let Some(json_type) = CodegenJsonType::new(&self.verifier.host, drtv) else {
+            return;
+        };
+        for field in json_type.fields.iter().filter(|f| f.field_type.is_none()) {
+            self.verifier.add_verify_error(&field.location, WhackDiagnosticKind::UnsupportedJsonTypeProperty, diagarg![field.name.clone(), field.static_type.clone()]);
+        }
+        for drtv in defn.block.directives.iter() {
+            let Directive::FunctionDefinition(fndefn) = drtv.as_ref() else {
+                continue;
+            };
+            match &fndefn.name {
+                FunctionName::Constructor(_) => {
+                    if fndefn.common.signature.parameters.iter().any(|p| p.kind == ParameterKind::Required) {
+                        self.verifier.add_verify_error(&fndefn.location, WhackDiagnosticKind::JsonTypeRequiresNoArgumentConstructor, diagarg![json_type.class.clone()]);
+                    }
+                },
+                FunctionName::Identifier(name) if Attribute::find_static(&fndefn.attributes).is_some() => {
+                    if name.0 == CodegenJsonType::FROM_JSON || name.0 == CodegenJsonType::TO_JSON {
+                        self.verifier.add_verify_error(&name.1, WhackDiagnosticKind::JsonTypeHelperConflict, diagarg![name.0.clone()]);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+impl<'a> Visitor for JsonTypeSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            self.verify_class(drtv, defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
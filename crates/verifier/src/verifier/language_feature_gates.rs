@@ -0,0 +1,61 @@
+use crate::ns::*;
+
+/// Reports uses of language features not enabled for their package
+/// or file by `LanguageFeatureOptions`.
+pub(crate) struct LanguageFeatureSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    options: &'a LanguageFeatureOptions,
+    enabled: HashSet<LanguageFeature>,
+}
+
+impl<'a> LanguageFeatureSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], options: &'a LanguageFeatureOptions) {
+        let mut subverifier = Self { verifier, options, enabled: HashSet::new() };
+        for program in programs.iter() {
+            let text = program.location.compilation_unit().text();
+            for pckgdefn in program.packages.iter() {
+                let package = pckgdefn.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+                subverifier.enabled = subverifier.options.enabled_features(&package, &text);
+                subverifier.visit_block(&pckgdefn.block);
+            }
+            subverifier.enabled = subverifier.options.enabled_features("", &text);
+            for drtv in program.directives.iter() {
+                subverifier.visit_directive(drtv);
+            }
+        }
+    }
+
+    fn require(&mut self, feature: LanguageFeature, location: &Location) {
+        if !self.enabled.contains(&feature) {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::LanguageFeatureNotEnabled, diagarg![feature.name().to_owned()]);
+        }
+    }
+
+    fn operator_feature(operator: Operator) -> Option<LanguageFeature> {
+        match operator {
+            Operator::NullCoalescing => Some(LanguageFeature::NullCoalescing),
+            Operator::Power => Some(LanguageFeature::Exponentiation),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Visitor for LanguageFeatureSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::OptionalChaining(e) => self.require(LanguageFeature::OptionalChaining, &e.location),
+            Expression::Binary(e) => {
+                if let Some(feature) = Self::operator_feature(e.operator) {
+                    self.require(feature, &e.location);
+                }
+            },
+            Expression::Assignment(e) => {
+                if let Some(feature) = e.compound.and_then(Self::operator_feature) {
+                    self.require(feature, &e.location);
+                }
+            },
+            _ => {},
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use crate::ns::*;
+
+/// Warns on functions created inside a loop that refer to a variable
+/// declared by the `for`, `for..in` or `for each` heading. As `var` is
+/// scoped to the enclosing function, every such function observes the
+/// last value of the variable.
+///
+/// Each loop variable is reported at its first captured reference.
+pub(crate) struct LoopCaptureSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Variables of the enclosing loops, with their names and the
+    /// function depth of their loops.
+    loop_vars: HashMap<Entity, (String, usize)>,
+    function_depth: usize,
+    reported: HashSet<Entity>,
+}
+
+impl<'a> LoopCaptureSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self {
+            verifier,
+            loop_vars: HashMap::new(),
+            function_depth: 0,
+            reported: HashSet::new(),
+        };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn loop_variables(&self, defn: &VariableDefinition) -> Vec<(Entity, String)> {
+        let host = &self.verifier.host;
+        defn.bindings.iter().filter_map(|binding| {
+            let pattern = &binding.destructuring.destructuring;
+            let (name, _) = pattern.to_identifier_name()?;
+            let slot = host.node_mapping().get(pattern).filter(|s| s.is::<VariableSlot>())?;
+            Some((slot, name))
+        }).collect()
+    }
+
+    fn visit_loop(&mut self, vars: Vec<(Entity, String)>, drtv: &Rc<Directive>) {
+        let vars = vars.into_iter().filter(|(slot, _)| !self.loop_vars.contains_key(slot)).collect::<Vec<_>>();
+        for (slot, name) in vars.iter() {
+            self.loop_vars.insert(slot.clone(), (name.clone(), self.function_depth));
+        }
+        walk_directive(self, drtv);
+        for (slot, _) in vars.iter() {
+            self.loop_vars.remove(slot);
+        }
+    }
+}
+
+impl<'a> Visitor for LoopCaptureSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ForStatement(forstmt) => {
+                let vars = match forstmt.init.as_ref() {
+                    Some(ForInitializer::VariableDefinition(defn)) => self.loop_variables(defn),
+                    _ => vec![],
+                };
+                self.visit_loop(vars, drtv);
+            },
+            Directive::ForInStatement(forstmt) => {
+                let vars = match &forstmt.left {
+                    ForInBinding::VariableDefinition(defn) => self.loop_variables(defn),
+                    _ => vec![],
+                };
+                self.visit_loop(vars, drtv);
+            },
+            _ => walk_directive(self, drtv),
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::QualifiedIdentifier(id) = exp.as_ref() {
+            if id.qualifier.is_none() && !self.loop_vars.is_empty() {
+                let r = self.verifier.host.node_mapping().get(exp)
+                    .filter(|r| r.is::<FixtureReferenceValue>() || r.is::<ScopeReferenceValue>());
+                if let Some(slot) = r.map(|r| r.property()) {
+                    if let Some((name, depth)) = self.loop_vars.get(&slot).cloned() {
+                        if self.function_depth > depth && self.reported.insert(slot) {
+                            self.verifier.add_warning(&id.location, WhackDiagnosticKind::LoopVariableCapturedByClosure, diagarg![name]);
+                        }
+                    }
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        self.function_depth += 1;
+        walk_function_common(self, common);
+        self.function_depth -= 1;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use crate::ns::*;
+
+/// Warns on variables of a loop body redeclaring a variable of the
+/// `for` or `for..in` heading, which the body's block scope would
+/// otherwise silently shadow. Enabled by
+/// `CompilerWarningOptions::loop_variable_redeclaration`.
+///
+/// Declarations of nested functions are not considered.
+pub(crate) struct LoopRedeclarations {
+    names: Vec<String>,
+    found: Vec<(Location, String)>,
+}
+
+impl LoopRedeclarations {
+    pub fn check(verifier: &mut Subverifier, heading: &VariableDefinition, body: &Rc<Directive>) {
+        let names: Vec<String> = heading.bindings.iter()
+            .filter_map(|binding| binding.destructuring.destructuring.to_identifier_name())
+            .map(|(name, _)| name)
+            .collect();
+        if names.is_empty() || !verifier.loop_variable_redeclaration_warnings {
+            return;
+        }
+        let mut collector = Self { names, found: vec![] };
+        collector.visit_directive(body);
+        for (location, name) in collector.found {
+            verifier.add_warning(&location, WhackDiagnosticKind::LoopVariableRedeclared, diagarg![name]);
+        }
+    }
+
+    fn check_definition(&mut self, defn: &VariableDefinition) {
+        for binding in defn.bindings.iter() {
+            if let Some((name, location)) = binding.destructuring.destructuring.to_identifier_name() {
+                if self.names.contains(&name) {
+                    self.found.push((location, name));
+                }
+            }
+        }
+    }
+}
+
+impl Visitor for LoopRedeclarations {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::VariableDefinition(defn) => self.check_definition(defn),
+            Directive::ForStatement(forstmt) => {
+                if let Some(ForInitializer::VariableDefinition(defn)) = forstmt.init.as_ref() {
+                    self.check_definition(defn);
+                }
+            },
+            Directive::ForInStatement(forstmt) => {
+                if let ForInBinding::VariableDefinition(defn) = &forstmt.left {
+                    self.check_definition(defn);
+                }
+            },
+            _ => {},
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_function_common(&mut self, _common: &Rc<FunctionCommon>) {}
+}
\ No newline at end of file
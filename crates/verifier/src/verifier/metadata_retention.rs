@@ -0,0 +1,83 @@
+use crate::ns::*;
+
+/// Warns on `describeType()` calls over classes, or instances of
+/// classes, whose meta-data is partly stripped from compiled output
+/// according to `CodegenMetadataRetention`, naming the stripped tags.
+/// Meta-data the compiler consumes is not reported.
+pub(crate) struct MetadataRetentionSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    retention: CodegenMetadataRetention,
+}
+
+impl<'a> MetadataRetentionSubverifier<'a> {
+    /// Functions whose result depends on the meta-data of their argument.
+    const REFLECTION_FUNCTIONS: [&'static str; 3] = [
+        "flash.utils.describeType",
+        "mx.utils.DescribeTypeCache.describeType",
+        "mx.utils.ObjectUtil.getClassInfo",
+    ];
+
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], compiler_options: &CompilerOptions) {
+        let retention = CodegenMetadataRetention::new(compiler_options);
+        let mut subverifier = Self { verifier, retention };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn is_reflection_function(&self, base: &Rc<Expression>) -> bool {
+        let Some(function) = referenced_entity(&self.verifier.host, base) else {
+            return false;
+        };
+        Self::REFLECTION_FUNCTIONS.contains(&function.to_string().as_str())
+    }
+
+    /// Returns the class described by an argument, either a class
+    /// reference or a value.
+    fn described_class(&self, exp: &Rc<Expression>) -> Option<Entity> {
+        let host = self.verifier.host.clone();
+        let entity = host.node_mapping().get(exp)?;
+        let t = if entity.is::<Type>() {
+            entity
+        } else if entity.is::<Value>() {
+            let r = referenced_entity_of_value(&entity).unwrap_or_else(|| entity.clone());
+            if r.is::<ClassType>() { r } else { entity.static_type(&host).escape_of_nullable_or_non_nullable() }
+        } else {
+            return None;
+        };
+        t.is::<ClassType>().then_some(t)
+    }
+
+    fn stripped_tags(&self, class: &Entity) -> Vec<String> {
+        let host = self.verifier.host.clone();
+        let mut definitions = vec![class.clone()];
+        definitions.extend(class.properties(&host).borrow().iter().map(|(_, m)| m.clone()));
+        definitions.extend(class.prototype(&host).borrow().iter().map(|(_, m)| m.clone()));
+        let mut tags: Vec<String> = vec![];
+        for definition in definitions.iter() {
+            for metadata in self.retention.stripped_metadata(definition) {
+                let consumed = CodegenMetadataRetention::COMPILER_METADATA.contains(&metadata.name.0.as_str());
+                if !consumed && !tags.contains(&metadata.name.0) {
+                    tags.push(metadata.name.0.clone());
+                }
+            }
+        }
+        tags
+    }
+}
+
+impl<'a> Visitor for MetadataRetentionSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            let arg = call.arguments.first().filter(|_| self.is_reflection_function(&call.base));
+            if let Some((arg, class)) = arg.and_then(|arg| Some((arg, self.described_class(arg)?))) {
+                let tags = self.stripped_tags(&class);
+                if !tags.is_empty() {
+                    let tags = tags.iter().map(|t| format!("[{t}]")).collect::<Vec<_>>().join(", ");
+                    self.verifier.add_warning(&arg.location(), WhackDiagnosticKind::DescribedMetadataStripped, diagarg![class, tags]);
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
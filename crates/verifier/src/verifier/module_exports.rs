@@ -0,0 +1,106 @@
+use crate::ns::*;
+
+/// Enforces the export lists of modules. A package is a module when a
+/// file defining it declares the exported definitions through a line
+/// comment pragma, as in:
+///
+/// ```as3
+/// // @whack-module export Parser, Token
+/// package com.example.parser {
+///     public class Parser {}
+/// }
+/// ```
+///
+/// The export lists of the files of a package add up.
+///
+/// Public definitions of a module that are not exported must not be
+/// referenced outside the package and its subpackages.
+pub(crate) struct ModuleExportSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Exported names per module package.
+    modules: HashMap<Entity, HashSet<String>>,
+    current_package: Option<Entity>,
+}
+
+impl<'a> ModuleExportSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let host = verifier.host.clone();
+        let mut subverifier = Self { verifier, modules: HashMap::new(), current_package: None };
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                subverifier.collect_module(&host, pckgdef);
+            }
+        }
+        if subverifier.modules.is_empty() {
+            return;
+        }
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                subverifier.current_package = Some(Self::package(&host, pckgdef));
+                subverifier.visit_block(&pckgdef.block);
+            }
+            subverifier.current_package = Some(host.top_level_package());
+            for drtv in program.directives.iter() {
+                subverifier.visit_directive(drtv);
+            }
+        }
+    }
+
+    pub const PRAGMA: &'static str = "@whack-module export";
+
+    fn package(host: &Database, pckgdef: &PackageDefinition) -> Entity {
+        host.factory().create_package(pckgdef.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>())
+    }
+
+    fn collect_module(&mut self, host: &Database, pckgdef: &PackageDefinition) {
+        let pckg = Self::package(host, pckgdef);
+        let cu = pckgdef.location.compilation_unit();
+        for comment in cu.comments().iter() {
+            if comment.multiline() {
+                continue;
+            }
+            let content = comment.content();
+            let Some(names) = content.trim().strip_prefix(Self::PRAGMA) else {
+                continue;
+            };
+            let exports = self.modules.entry(pckg.clone()).or_default();
+            for name in names.split(',').map(|n| n.trim()).filter(|n| !n.is_empty()) {
+                if !matches!(pckg.properties(host).get_in_any_public_ns(name), Ok(Some(_))) {
+                    self.verifier.add_verify_error(&comment.location(), WhackDiagnosticKind::ModuleExportNotFound, diagarg![pckg.clone(), name.to_owned()]);
+                }
+                exports.insert(name.to_owned());
+            }
+        }
+    }
+
+    fn is_within(pckg: &Entity, module: &Entity) -> bool {
+        let name = pckg.fully_qualified_name_list();
+        let module_name = module.fully_qualified_name_list();
+        name.len() >= module_name.len() && name[..module_name.len()] == module_name[..]
+    }
+
+    fn verify_reference(&mut self, definition: &Entity, location: &Location) {
+        let Some(module) = definition.parent().filter(|p| self.modules.contains_key(p)) else {
+            return;
+        };
+        if !definition.name().namespace().is_public_ns() {
+            return;
+        }
+        if self.current_package.as_ref().map(|p| Self::is_within(p, &module)).unwrap_or(false) {
+            return;
+        }
+        let name = definition.name().local_name();
+        if !self.modules[&module].contains(&name) {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::NonExportedDefinitionReference, diagarg![name, module]);
+        }
+    }
+}
+
+impl<'a> Visitor for ModuleExportSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Some(definition) = referenced_entity(&self.verifier.host, exp) {
+            self.verify_reference(&definition, &exp.location());
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
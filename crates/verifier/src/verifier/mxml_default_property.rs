@@ -0,0 +1,82 @@
+use crate::ns::*;
+
+/// Verifies the child elements of MXML components that are not property
+/// tags, which are assigned to the `[DefaultProperty]` of the component
+/// class, against the element type of that property: the element type of
+/// a `Vector`, the `[ArrayElementType]` of an `Array`, or the type of the
+/// property itself.
+///
+/// Only elements given in package namespaces (`*` or `com.example.*`)
+/// are resolved to classes; other elements are skipped.
+pub(crate) struct MxmlDefaultPropertySubverifier;
+
+impl MxmlDefaultPropertySubverifier {
+    pub fn verify_mxml(verifier: &mut Subverifier, mxml: &Rc<Mxml>) {
+        for content in mxml.content.iter() {
+            if let MxmlContent::Element(root) = content.as_ref() {
+                Self::verify_element(verifier, root);
+            }
+        }
+    }
+
+    fn verify_element(verifier: &mut Subverifier, element: &Rc<MxmlElement>) {
+        let host = verifier.host.clone();
+        let class = MxmlIdSubverifier::resolve_element_class(&host, element);
+        let uri = element.name.resolve_name(&element.namespace).ok().map(|(uri, _)| uri);
+        for child in MxmlStates::children(element).iter() {
+            let Some((child_uri, child_name)) = child.name.resolve_name(&child.namespace).ok() else {
+                continue;
+            };
+            // Property tags hold the values of a property
+            if let Some(class) = class.as_ref().filter(|_| uri.as_ref() == Some(&child_uri)) {
+                if MxmlIdSubverifier::inherited_member(&host, class, &child_name).is_some() {
+                    for value in MxmlStates::children(child).iter() {
+                        Self::verify_element(verifier, value);
+                    }
+                    continue;
+                }
+            }
+            let Some(child_class) = MxmlIdSubverifier::resolve_element_class(&host, child) else {
+                continue;
+            };
+            if let Some(class) = class.as_ref() {
+                Self::verify_default_property_child(verifier, class, child, &child_class);
+            }
+            Self::verify_element(verifier, child);
+        }
+    }
+
+    fn verify_default_property_child(verifier: &mut Subverifier, class: &Entity, child: &Rc<MxmlElement>, child_class: &Entity) {
+        let host = verifier.host.clone();
+        let Some(name) = MxmlDefaultProperty::of(&host, class) else {
+            verifier.add_verify_error(&child.location, WhackDiagnosticKind::MxmlChildWithoutDefaultProperty, diagarg![class.clone(), child_class.clone()]);
+            return;
+        };
+        let Some(property) = MxmlIdSubverifier::inherited_member(&host, class, &name) else {
+            return;
+        };
+        let Some(element_type) = Self::element_type(verifier, &property) else {
+            return;
+        };
+        let compatible = *child_class == element_type || verifier.type_relations.is_subtype_of(&host, child_class, &element_type).unwrap_or(true);
+        if !compatible {
+            verifier.add_verify_error(&child.location, WhackDiagnosticKind::IncompatibleMxmlChild, diagarg![verifier.display_type(child_class), name, verifier.display_type(&element_type)]);
+        }
+    }
+
+    /// Type each child assigned to a default property must be of, or
+    /// `None` if it takes any object.
+    fn element_type(verifier: &mut Subverifier, property: &Entity) -> Option<Entity> {
+        let host = verifier.host.clone();
+        let t = property.static_type(&host).escape_of_nullable_or_non_nullable();
+        let t = if let Some(element_type) = t.vector_element_type(&host).ok()? {
+            element_type
+        } else if t == host.array_type() {
+            let name = MxmlDefaultProperty::array_element_type_name(property)?;
+            verifier.resolve_qualified_name(&name).filter(|t| t.is::<Type>())?
+        } else {
+            t
+        };
+        (t != host.any_type() && t != host.object_type() && !t.is::<InvalidationEntity>()).then_some(t)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use crate::ns::*;
+
+/// Verifies the `id` attributes of an MXML document: each must be a
+/// valid identifier, unique within the document and not collide with
+/// a public member inherited from the base component.
+///
+/// The base component is only resolved for package namespaces, such as
+/// `xmlns:local="com.example.*"`; manifest namespaces are not resolved.
+pub(crate) struct MxmlIdSubverifier;
+
+impl MxmlIdSubverifier {
+    pub fn verify_mxml(verifier: &mut Subverifier, mxml: &Rc<Mxml>) {
+        for content in mxml.content.iter() {
+            if let MxmlContent::Element(root) = content.as_ref() {
+                let base_class = Self::resolve_element_class(&verifier.host, root);
+                let mut ids = HashMap::<String, Location>::new();
+                Self::verify_element(verifier, root, base_class.as_ref(), &mut ids);
+            }
+        }
+    }
+
+    fn verify_element(verifier: &mut Subverifier, element: &Rc<MxmlElement>, base_class: Option<&Entity>, ids: &mut HashMap<String, Location>) {
+        if let Some((id, location)) = MxmlStates::attribute(element, "id") {
+            if !Self::is_identifier(&id) {
+                verifier.add_verify_error(&location, WhackDiagnosticKind::InvalidMxmlId, diagarg![id.clone()]);
+            } else if let Some(prev) = ids.get(&id).cloned() {
+                verifier.add_verify_error(&location, WhackDiagnosticKind::DuplicateMxmlId, diagarg![id.clone()]);
+                verifier.related_information.push(RelatedInformation {
+                    location: location.clone(),
+                    related_location: prev,
+                    message: format!("'{id}' is first used here"),
+                });
+            } else {
+                if let Some(member) = base_class.and_then(|c| Self::inherited_member(&verifier.host, c, &id)) {
+                    verifier.add_verify_error(&location, WhackDiagnosticKind::MxmlIdConflictsWithInheritedMember, diagarg![id.clone(), member.parent().unwrap_or(member.clone())]);
+                    if let Some(member_location) = member.location() {
+                        verifier.related_information.push(RelatedInformation {
+                            location: location.clone(),
+                            related_location: member_location,
+                            message: format!("'{id}' is inherited from here"),
+                        });
+                    }
+                }
+                ids.insert(id, location);
+            }
+        }
+        for child in MxmlStates::children(element).iter() {
+            Self::verify_element(verifier, child, base_class, ids);
+        }
+    }
+
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        let Some(first) = chars.next() else {
+            return false;
+        };
+        (first.is_alphabetic() || first == '_' || first == '$')
+            && chars.all(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+    }
+
+    /// Resolves the class of an element given in a package namespace,
+    /// such as a component root element.
+    pub(crate) fn resolve_element_class(host: &Database, element: &Rc<MxmlElement>) -> Option<Entity> {
+        let (uri, local_name) = element.name.resolve_name(&element.namespace).ok()?;
+        let pckg = if uri == "*" {
+            host.top_level_package()
+        } else {
+            let pckg_name = uri.strip_suffix(".*")?;
+            host.factory().create_package(pckg_name.split('.').collect::<Vec<_>>())
+        };
+        let qname = host.factory().create_qname(&pckg.public_ns()?, local_name);
+        pckg.properties(host).get(&qname).filter(|c| c.is::<ClassType>())
+    }
+
+    pub(crate) fn inherited_member(host: &Database, class: &Entity, name: &str) -> Option<Entity> {
+        let mut class = Some(class.clone());
+        while let Some(c) = class {
+            if let Ok(Some(member)) = c.prototype(host).get_in_any_public_ns(name) {
+                return Some(member);
+            }
+            class = c.extends_class(host);
+        }
+        None
+    }
+}
\ No newline at end of file
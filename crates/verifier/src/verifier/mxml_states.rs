@@ -0,0 +1,48 @@
+use crate::ns::*;
+
+/// Verifies `includeIn` and `excludeFrom` attributes and state-specific
+/// attribute values (`label.hover="..."`) against the declared view states,
+/// and collects the state overrides of each component into
+/// `Subverifier::mxml_state_overrides`.
+pub(crate) struct MxmlStatesSubverifier;
+
+impl MxmlStatesSubverifier {
+    pub fn verify_mxml(verifier: &mut Subverifier, mxml: &Rc<Mxml>) {
+        for content in mxml.content.iter() {
+            if let MxmlContent::Element(root) = content.as_ref() {
+                let states = MxmlStates::collect(root);
+                Self::verify_element(verifier, root, &states);
+                let path = root.location.compilation_unit().file_path().unwrap_or_default();
+                verifier.mxml_state_overrides.insert(path, states.overrides(root));
+            }
+        }
+    }
+
+    fn verify_element(verifier: &mut Subverifier, element: &Rc<MxmlElement>, states: &MxmlStates) {
+        let include_in = MxmlStates::attribute(element, "includeIn");
+        let exclude_from = MxmlStates::attribute(element, "excludeFrom");
+        if let (Some(_), Some(exclude_from)) = (include_in.as_ref(), exclude_from.as_ref()) {
+            verifier.add_verify_error(&exclude_from.1, WhackDiagnosticKind::IncludeInConflictsWithExcludeFrom, diagarg![]);
+        }
+        for list in [include_in, exclude_from].into_iter().flatten() {
+            for name in MxmlStates::split_list(&list.0) {
+                if !states.contains(&name) {
+                    verifier.add_verify_error(&list.1, WhackDiagnosticKind::UndefinedState, diagarg![name]);
+                }
+            }
+        }
+        for attr in element.attributes.iter() {
+            if attr.xmlns {
+                continue;
+            }
+            if let Some((_, state)) = attr.name.name.split_once('.') {
+                if !states.contains(state) {
+                    verifier.add_verify_error(&attr.location, WhackDiagnosticKind::UndefinedState, diagarg![state.to_owned()]);
+                }
+            }
+        }
+        for child in MxmlStates::children(element).iter() {
+            Self::verify_element(verifier, child, states);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use crate::ns::*;
+
+/// Verifies that constant extension IDs given to
+/// `ExtensionContext.createExtensionContext()` belong to a loaded
+/// native extension.
+pub(crate) struct NativeExtensionIdSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    ids: &'a [String],
+}
+
+impl<'a> NativeExtensionIdSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], ids: &'a [String]) {
+        let mut subverifier = Self { verifier, ids };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn extension_id(&self, exp: &Rc<Expression>) -> Option<String> {
+        if let Expression::StringLiteral(literal) = exp.as_ref() {
+            return Some(literal.value.clone());
+        }
+        self.verifier.host.node_mapping().get(exp).filter(|v| v.is::<StringConstant>()).map(|v| v.string_value())
+    }
+}
+
+impl<'a> Visitor for NativeExtensionIdSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            if let Expression::Member(member) = call.base.as_ref() {
+                let is_create = member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name == "createExtensionContext").unwrap_or(false)
+                    && member.base.to_identifier_name().map(|(name, _)| name == "ExtensionContext").unwrap_or(false);
+                let arg = call.arguments.first().filter(|_| is_create);
+                if let Some((arg, id)) = arg.and_then(|arg| Some((arg, self.extension_id(arg)?))) {
+                    if !self.ids.contains(&id) {
+                        self.verifier.add_verify_error(&arg.location(), WhackDiagnosticKind::UnknownNativeExtensionId, diagarg![id]);
+                    }
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
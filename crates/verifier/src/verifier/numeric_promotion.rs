@@ -0,0 +1,117 @@
+use crate::ns::*;
+
+/// Audits arithmetic over `int`, `uint` and `Number` operands for lossy
+/// patterns, when `CompilerWarningOptions::numeric_promotion` is set:
+///
+/// * Subtraction from a `uint`, which wraps around rather than going
+///   below zero.
+/// * Division of integer operands, whose fractional quotient is
+///   truncated once stored as an integer.
+/// * Bitwise operators over constants with a fractional part, which
+///   they discard.
+///
+/// Compound assignments are audited as their binary operation.
+pub(crate) struct NumericPromotionSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    int_type: Entity,
+    uint_type: Entity,
+}
+
+impl<'a> NumericPromotionSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let host = verifier.host.clone();
+        // Without the core library there is nothing to check against
+        let (Ok(int_type), Ok(uint_type)) = (host.int_type().defer(), host.uint_type().defer()) else {
+            return;
+        };
+        let mut subverifier = Self { verifier, int_type, uint_type };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_operation(&mut self, operator: Operator, left: &Rc<Expression>, right: &Rc<Expression>, location: &Location) {
+        match operator {
+            Operator::Subtract => {
+                if self.static_type(left).as_ref() == Some(&self.uint_type) && !self.is_constant(left) && Self::literal_value(right) != Some(0.0) {
+                    self.verifier.add_warning(location, WhackDiagnosticKind::UintSubtractionMayWrap, diagarg![]);
+                }
+            },
+            Operator::Divide => {
+                let integer_types = [self.int_type.clone(), self.uint_type.clone()];
+                let (Some(left_type), Some(right_type)) = (self.static_type(left), self.static_type(right)) else {
+                    return;
+                };
+                if !integer_types.contains(&left_type) || !integer_types.contains(&right_type) {
+                    return;
+                }
+                // Constant quotients without a fractional part lose nothing
+                if let (Some(a), Some(b)) = (self.constant_value(left), self.constant_value(right)) {
+                    if b != 0.0 && a % b == 0.0 {
+                        return;
+                    }
+                }
+                self.verifier.add_warning(location, WhackDiagnosticKind::IntegerDivisionTruncated, diagarg![left_type]);
+            },
+            Operator::BitwiseAnd |
+            Operator::BitwiseOr |
+            Operator::BitwiseXor |
+            Operator::ShiftLeft |
+            Operator::ShiftRight |
+            Operator::ShiftRightUnsigned => {
+                for operand in [left, right] {
+                    if let Some(value) = Self::literal_value(operand).filter(|v| v.fract() != 0.0) {
+                        self.verifier.add_warning(&operand.location(), WhackDiagnosticKind::FractionalBitwiseOperand, diagarg![value.to_string()]);
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn static_type(&self, exp: &Rc<Expression>) -> Option<Entity> {
+        let entity = self.verifier.host.node_mapping().get(exp)?;
+        if entity.is::<Value>() {
+            Some(entity.static_type(&self.verifier.host).escape_of_non_nullable())
+        } else {
+            None
+        }
+    }
+
+    fn is_constant(&self, exp: &Rc<Expression>) -> bool {
+        self.constant_value(exp).is_some()
+    }
+
+    fn constant_value(&self, exp: &Rc<Expression>) -> Option<f64> {
+        let entity = self.verifier.host.node_mapping().get(exp)?;
+        entity.is::<NumberConstant>().then(|| entity.number_value().force_double())
+    }
+
+    /// Value of a numeric literal, optionally negated or parenthesized.
+    fn literal_value(exp: &Rc<Expression>) -> Option<f64> {
+        match exp.as_ref() {
+            Expression::NumericLiteral(literal) => literal.parse_double(false).ok(),
+            Expression::Unary(e) if e.operator == Operator::Negative => match e.expression.as_ref() {
+                Expression::NumericLiteral(literal) => literal.parse_double(true).ok(),
+                _ => None,
+            },
+            Expression::Paren(e) => Self::literal_value(&e.expression),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Visitor for NumericPromotionSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Binary(e) => self.verify_operation(e.operator, &e.left, &e.right, &e.location),
+            Expression::Assignment(e) => {
+                if let Some(compound) = e.compound {
+                    self.verify_operation(compound, &e.left, &e.right, &e.location);
+                }
+            },
+            _ => {},
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
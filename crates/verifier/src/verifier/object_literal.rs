@@ -19,6 +19,8 @@ impl ObjectLiteralSubverifier {
             Self::verify_object_initializer_for_ecma_object(verifier, initializer)?;
         } else if context_type_esc.is_options_class() {
             Self::verify_object_initializer_for_options_class(verifier, initializer, &context_type_esc)?;
+        } else if context_type_esc.is::<ClassType>() && !context_type_esc.is_dynamic() {
+            Self::verify_object_initializer_for_sealed_class(verifier, initializer, &context_type_esc)?;
         } else {
             if !context_type_esc.is::<InvalidationEntity>() {
                 verifier.add_verify_error(&initializer.location, WhackDiagnosticKind::UnexpectedObject, diagarg![]);
@@ -84,7 +86,7 @@ impl ObjectLiteralSubverifier {
                                 variable_data_type.defer()?;
                                 let coercion = ConversionMethods(&verifier.host).implicit(short_ref_1, &variable_data_type, false)?;
                                 let Some(coercion) = coercion else {
-                                    verifier.add_verify_error(&name.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![short_ref_1.static_type(&verifier.host), variable_data_type]);
+                                    verifier.add_verify_error(&name.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![verifier.display_type(&short_ref_1.static_type(&verifier.host)), verifier.display_type(&variable_data_type)]);
                                     #[allow(unused_assignments)] {
                                         short_ref = None;
                                     }
@@ -110,9 +112,70 @@ impl ObjectLiteralSubverifier {
         Ok(())
     }
 
+    /// Verifies an object initializer against the writable properties of a
+    /// non-dynamic class. Unlike options classes, no property is required.
+    fn verify_object_initializer_for_sealed_class(verifier: &mut Subverifier, initializer: &ObjectInitializer, class: &Entity) -> Result<(), DeferError> {
+        for field in &initializer.fields {
+            match field.as_ref() {
+                InitializerField::Rest((exp, _)) => {
+                    verifier.imp_coerce_exp(exp, class)?;
+                },
+                InitializerField::Field { name, value, .. } => {
+                    if let Some(name) = field.shorthand() {
+                        let property = Self::resolve_writable_property(verifier, class, name)?;
+                        let short_ref = Self::verify_initializer_shorthand(verifier, name)?;
+                        let mut resolution = short_ref.clone();
+                        if let (Some(short_ref), Some(property)) = (short_ref.as_ref(), property.as_ref()) {
+                            let data_type = property.static_type(&verifier.host).defer()?;
+                            resolution = ConversionMethods(&verifier.host).implicit(short_ref, &data_type, false)?;
+                            if resolution.is_none() {
+                                verifier.add_verify_error(&name.location, WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![verifier.display_type(&short_ref.static_type(&verifier.host)), verifier.display_type(&data_type)]);
+                            }
+                        }
+                        let fr = verifier.host.lazy_node_mapping(field, || verifier.host.factory().create_field_resolution());
+                        fr.set_field_slot(property);
+                        fr.set_shorthand_resolution(resolution);
+                        continue;
+                    }
+                    let value_exp = value.as_ref().unwrap();
+                    let id = match &name.0 {
+                        FieldName::Identifier(id) => id.clone(),
+                        FieldName::StringLiteral(sl) => {
+                            let name_1 = verifier.verify_expression(sl, &default())?.unwrap().string_value();
+                            QualifiedIdentifier {
+                                location: sl.location(),
+                                attribute: false,
+                                qualifier: None,
+                                id: QualifiedIdentifierIdentifier::Id((name_1, sl.location())),
+                            }
+                        },
+                        FieldName::Brackets(exp) => {
+                            verifier.imp_coerce_exp(exp, &verifier.host.string_type())?;
+                            verifier.verify_expression(value_exp, &default())?;
+                            continue;
+                        },
+                        FieldName::NumericLiteral(_) => {
+                            verifier.verify_expression(value_exp, &default())?;
+                            verifier.add_verify_error(&name.1, WhackDiagnosticKind::UnexpectedFieldName, diagarg![]);
+                            continue;
+                        },
+                    };
+                    if let Some(property) = Self::resolve_writable_property(verifier, class, &id)? {
+                        let data_type = property.static_type(&verifier.host).defer()?;
+                        verifier.imp_coerce_exp(value_exp, &data_type)?;
+                    } else {
+                        verifier.verify_expression(value_exp, &default())?;
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
     fn verify_non_shorthand_notation_for_options_class(field: &Rc<InitializerField>, verifier: &mut Subverifier, options_class: &Entity, missing: &mut HashSet<Entity>) -> Result<(), DeferError> {
         let InitializerField::Field { name, value, .. } = field.as_ref() else {
-            panic!();
+            verifier.add_internal_error(&field.location(), "expected a non-shorthand field");
+            return Ok(());
         };
         let value_exp = value.as_ref().unwrap();
         match &name.0 {
@@ -204,6 +267,44 @@ impl ObjectLiteralSubverifier {
         verifier.reference_post_processing(r, &default())
     }
 
+    /// Resolves a writable variable or virtual property of a class.
+    fn resolve_writable_property(verifier: &mut Subverifier, class: &Entity, id: &QualifiedIdentifier) -> Result<Option<Entity>, DeferError> {
+        let qn = ExpSubverifier::verify_qualified_identifier(verifier, id)?;
+        let Some((qual, key)) = qn else {
+            return Ok(None);
+        };
+        let Some(local_name) = key.local_name() else {
+            verifier.add_verify_error(&id.location, WhackDiagnosticKind::UnexpectedFieldName, diagarg![]);
+            return Ok(None);
+        };
+
+        let open_ns_set = verifier.scope().concat_open_ns_set_of_scope_chain();
+        let lookup = match PropertyLookup(&verifier.host).get_qname_in_ns_set_or_any_public_ns(&class.prototype(&verifier.host), &open_ns_set, qual, &local_name) {
+            Ok(lookup) => lookup,
+            Err(PropertyLookupError::AmbiguousReference(name)) => {
+                verifier.add_verify_error(&id.location, WhackDiagnosticKind::AmbiguousReference, diagarg![name.clone()]);
+                return Ok(None);
+            },
+            Err(PropertyLookupError::Defer) => {
+                return Err(DeferError(None));
+            },
+            Err(_) => {
+                verifier.add_internal_error(&id.location, "unexpected property lookup error");
+                return Ok(None);
+            },
+        };
+
+        let Some(property) = lookup.filter(|p| p.is::<VariableSlot>() || p.is::<VirtualSlot>()) else {
+            verifier.add_verify_error(&id.location, WhackDiagnosticKind::UnknownPropertyForClass, diagarg![local_name, class.clone()]);
+            return Ok(None);
+        };
+        if property.read_only(&verifier.host) {
+            verifier.add_verify_error(&id.location, WhackDiagnosticKind::EntityIsReadOnly, diagarg![]);
+            return Ok(None);
+        }
+        Ok(Some(property))
+    }
+
     fn resolve_instance_variable(verifier: &mut Subverifier, class: &Entity, id: &QualifiedIdentifier) -> Result<Option<Entity>, DeferError> {
         let qn = ExpSubverifier::verify_qualified_identifier(verifier, id)?;
         if qn.is_none() {
@@ -233,7 +334,8 @@ impl ObjectLiteralSubverifier {
                     return Err(DeferError(None));
                 },
                 _ => {
-                    panic!();
+                    verifier.add_internal_error(&id.location, "unexpected property lookup error");
+                    return Ok(None);
                 },
             }
         }
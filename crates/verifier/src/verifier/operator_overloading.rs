@@ -0,0 +1,101 @@
+use crate::ns::*;
+
+/// A binary expression resolved to a static operator method.
+#[derive(Clone, Debug)]
+pub struct OperatorOverload {
+    pub location: Location,
+    pub operator: Operator,
+    pub method: Entity,
+    /// Whether the method result is negated, as for `!=` through `op_equals()`.
+    pub negated: bool,
+}
+
+/// Resolves `+`, `-`, `*`, `==` and `!=` over class operands to static
+/// methods of the convention `static function op_add(a: T, b: T): R`,
+/// when `CompilerOptions::operator_overloading` is set.
+///
+/// The method is looked up in the class of the left operand, and, if the
+/// right operand is a class as well, in its class; finding distinct
+/// methods in both is ambiguous. Classes of any package, the top-level
+/// one included, may overload operators; core classes declare no such
+/// methods.
+///
+/// The right operand is verified with the left operand type as context,
+/// as when the expression is not overloaded, since its result is kept
+/// either way.
+pub(crate) struct OperatorOverloadingSubverifier;
+
+impl OperatorOverloadingSubverifier {
+    pub fn method_name(operator: Operator) -> Option<&'static str> {
+        match operator {
+            Operator::Add => Some("op_add"),
+            Operator::Subtract => Some("op_subtract"),
+            Operator::Multiply => Some("op_multiply"),
+            Operator::Equals | Operator::NotEquals => Some("op_equals"),
+            _ => None,
+        }
+    }
+
+    fn is_overloadable(t: &Entity) -> bool {
+        t.is::<ClassType>()
+    }
+
+    fn find_method(verifier: &mut Subverifier, class: &Entity, name: &str, location: &Location) -> Option<Entity> {
+        let host = verifier.host.clone();
+        let method = class.properties(&host).get_in_any_public_ns(name).ok().flatten()?;
+        let valid = method.is::<MethodSlot>() && method.is_static() && method.signature(&host).params().len() == 2;
+        if !valid {
+            verifier.add_verify_error(location, WhackDiagnosticKind::InvalidOperatorOverload, diagarg![name.to_owned()]);
+            return None;
+        }
+        Some(method)
+    }
+
+    /// Returns `None` if the expression is not overloaded, in which case
+    /// the right operand may have been verified as well.
+    pub fn verify(verifier: &mut Subverifier, exp: &BinaryExpression, left_st: &Entity) -> Result<Option<Option<Entity>>, DeferError> {
+        let host = verifier.host.clone();
+        let Some(name) = Self::method_name(exp.operator) else {
+            return Ok(None);
+        };
+        if !verifier.operator_overloading || !Self::is_overloadable(left_st) {
+            return Ok(None);
+        }
+        let Some(right) = verifier.verify_expression(&exp.right, &VerifierExpressionContext {
+            context_type: Some(left_st.clone()),
+            ..default()
+        })? else {
+            return Ok(None);
+        };
+        let right_st = right.static_type(&host).escape_of_non_nullable();
+        let left_method = Self::find_method(verifier, left_st, name, &exp.location);
+        let right_method = if right_st != *left_st && Self::is_overloadable(&right_st) {
+            Self::find_method(verifier, &right_st, name, &exp.location)
+        } else {
+            None
+        };
+        let method = match (left_method, right_method) {
+            (Some(_), Some(_)) => {
+                verifier.add_verify_error(&exp.location, WhackDiagnosticKind::AmbiguousOperatorOverload, diagarg![name.to_owned(), left_st.clone(), right_st]);
+                return Ok(Some(None));
+            },
+            (Some(m), None) | (None, Some(m)) => m,
+            (None, None) => return Ok(None),
+        };
+
+        let signature = method.signature(&host);
+        let params = signature.params();
+        verifier.imp_coerce_exp(&exp.left, &params[0].static_type)?;
+        verifier.imp_coerce_exp(&exp.right, &params[1].static_type)?;
+
+        let negated = exp.operator == Operator::NotEquals;
+        verifier.operator_overloads.push(OperatorOverload {
+            location: exp.location.clone(),
+            operator: exp.operator,
+            method,
+            negated,
+        });
+        let result_type = if negated { host.boolean_type().defer()? } else { signature.result_type() };
+        Ok(Some(Some(host.factory().create_value(&result_type))))
+    }
+}
\ No newline at end of file
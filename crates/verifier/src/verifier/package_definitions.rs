@@ -0,0 +1,74 @@
+use crate::ns::*;
+
+/// Warns on package-level functions and variables named as a class,
+/// interface or enum of the same package or of the top-level package,
+/// such as `internal function Point()` beside `public class Point` or
+/// `public var Array` hiding the top-level `Array`. Such a name refers
+/// to either definition depending on the open namespaces.
+pub(crate) struct PackageDefinitionSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> PackageDefinitionSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let host = verifier.host.clone();
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                let pckg = host.factory().create_package(pckgdef.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>());
+                for drtv in pckgdef.block.directives.iter() {
+                    subverifier.verify_directive(&pckg, drtv);
+                }
+            }
+        }
+    }
+
+    fn verify_directive(&mut self, pckg: &Entity, drtv: &Rc<Directive>) {
+        let host = self.verifier.host.clone();
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(defn) => {
+                let FunctionName::Identifier((_, location)) = &defn.name else {
+                    return;
+                };
+                if let Some(slot) = host.node_mapping().get(drtv) {
+                    self.verify_definition(pckg, &slot, "function", location);
+                }
+            },
+            Directive::VariableDefinition(defn) => {
+                for binding in defn.bindings.iter() {
+                    if let Some(slot) = host.node_mapping().get(&binding.destructuring.destructuring).filter(|s| s.is::<VariableSlot>()) {
+                        self.verify_definition(pckg, &slot, "variable", &binding.destructuring.location);
+                    }
+                }
+            },
+            Directive::ConfigurationDirective(cfgdrtv) => {
+                self.verify_directive(pckg, &cfgdrtv.directive);
+            },
+            Directive::Block(block) => {
+                for drtv in block.directives.iter() {
+                    self.verify_directive(pckg, drtv);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn verify_definition(&mut self, pckg: &Entity, definition: &Entity, kind: &str, location: &Location) {
+        let name = definition.name().local_name();
+        let top_level = self.verifier.host.top_level_package();
+        for p in [pckg.clone(), top_level] {
+            if let Some(t) = Self::type_named(&self.verifier.host, &p, &name) {
+                self.verifier.add_warning(location, WhackDiagnosticKind::PackageDefinitionNamedAsType, diagarg![kind.to_owned(), name, t]);
+                return;
+            }
+        }
+    }
+
+    /// Returns a class, interface or enum of a package named `name` in
+    /// any namespace.
+    fn type_named(host: &Database, pckg: &Entity, name: &str) -> Option<Entity> {
+        pckg.properties(host).borrow().iter()
+            .map(|(_, p)| p.clone())
+            .find(|p| (p.is::<ClassType>() || p.is::<InterfaceType>() || p.is::<EnumType>()) && p.name().local_name() == name)
+    }
+}
\ No newline at end of file
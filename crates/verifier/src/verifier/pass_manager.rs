@@ -0,0 +1,95 @@
+use crate::ns::*;
+
+/// A pass of `Verifier::verify_programs()`, in running order.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum VerifierPass {
+    /// Creates packages and their scopes.
+    DeclarationCollection,
+    /// Resolves definitions and their signatures across packages and
+    /// top-level programs.
+    SignatureResolution,
+    /// Verifies statements and function bodies, including lambdas.
+    BodyVerification,
+    /// Runs checks over verified programs, such as unused imports,
+    /// `[Inline]` and `[TailCall]` meta-data and lint rules.
+    FlowAnalysis,
+}
+
+impl VerifierPass {
+    pub const LIST: [VerifierPass; 4] = [
+        Self::DeclarationCollection,
+        Self::SignatureResolution,
+        Self::BodyVerification,
+        Self::FlowAnalysis,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DeclarationCollection => "declaration-collection",
+            Self::SignatureResolution => "signature-resolution",
+            Self::BodyVerification => "body-verification",
+            Self::FlowAnalysis => "flow-analysis",
+        }
+    }
+}
+
+/// Observes the end of each verifier pass.
+pub trait VerifierPassObserver {
+    fn pass_finished(&self, pass: VerifierPass, host: &Rc<Database>);
+}
+
+/// Selects the passes run by `Verifier::verify_programs()` and registers
+/// observers invoked between them.
+///
+/// ```ignore
+/// // Resolve signatures only, as for a symbol indexer
+/// verifier.set_pass_manager(PassManager::new().up_to(VerifierPass::SignatureResolution));
+/// verifier.verify_programs(&compiler_options, programs, vec![]);
+/// ```
+#[derive(Clone)]
+pub struct PassManager {
+    last_pass: VerifierPass,
+    observers: Vec<Rc<dyn VerifierPassObserver>>,
+}
+
+impl PassManager {
+    /// Runs every pass.
+    pub fn new() -> Self {
+        Self {
+            last_pass: VerifierPass::FlowAnalysis,
+            observers: vec![],
+        }
+    }
+
+    /// Stops after the given pass. Later passes depend on earlier ones,
+    /// thus only a prefix of the passes may run.
+    pub fn up_to(mut self, pass: VerifierPass) -> Self {
+        self.last_pass = pass;
+        self
+    }
+
+    pub fn add_observer(mut self, observer: Rc<dyn VerifierPassObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    pub fn last_pass(&self) -> VerifierPass {
+        self.last_pass
+    }
+
+    pub fn runs(&self, pass: VerifierPass) -> bool {
+        pass <= self.last_pass
+    }
+
+    pub(crate) fn finish(&self, pass: VerifierPass, host: &Rc<Database>) {
+        for observer in self.observers.iter() {
+            observer.pass_finished(pass, host);
+        }
+    }
+}
+
+impl Default for PassManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use crate::ns::*;
+
+/// Warns on accessors referring to themselves, as in
+/// `function set x(v) { this.x = v }`, which recurses infinitely.
+///
+/// Getters are checked for reads of their own name and setters for
+/// assignments to it, either as `this.x` or as an unqualified `x`
+/// resolving to the accessor pair. Nested functions are not entered.
+pub(crate) struct RecursiveAccessorSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> RecursiveAccessorSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_accessor(&mut self, defn: &FunctionDefinition) {
+        let (name, is_setter) = match &defn.name {
+            FunctionName::Getter(name) => (name.0.clone(), false),
+            FunctionName::Setter(name) => (name.0.clone(), true),
+            _ => return,
+        };
+        let Some(FunctionBody::Block(block)) = defn.common.body.as_ref() else {
+            return;
+        };
+        let mut collector = SelfReferenceCollector {
+            host: self.verifier.host.clone(),
+            name: name.clone(),
+            is_setter,
+            found: vec![],
+        };
+        collector.visit_block(block);
+        for location in collector.found {
+            self.verifier.add_warning(&location, WhackDiagnosticKind::RecursiveAccessorReference, diagarg![name.clone()]);
+        }
+    }
+}
+
+impl<'a> Visitor for RecursiveAccessorSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            for drtv in defn.block.directives.iter() {
+                if let Directive::FunctionDefinition(fndefn) = drtv.as_ref() {
+                    self.verify_accessor(fndefn);
+                }
+            }
+        }
+        walk_directive(self, drtv);
+    }
+}
+
+/// Collects references of an accessor body to the accessor itself.
+struct SelfReferenceCollector {
+    host: Rc<Database>,
+    name: String,
+    is_setter: bool,
+    found: Vec<Location>,
+}
+
+impl SelfReferenceCollector {
+    fn refers_to_accessor(&self, exp: &Rc<Expression>) -> bool {
+        match exp.as_ref() {
+            Expression::Member(member) if matches!(member.base.as_ref(), Expression::ThisLiteral(_)) => {
+                member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name == self.name).unwrap_or(false)
+            },
+            Expression::QualifiedIdentifier(id) if id.qualifier.is_none() && !id.attribute => {
+                let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id else {
+                    return false;
+                };
+                // Parameters and locals of the same name shadow the accessor
+                *name == self.name && self.host.node_mapping().get(exp)
+                    .filter(|r| r.is::<FixtureReferenceValue>() || r.is::<ScopeReferenceValue>())
+                    .map(|r| r.property().is::<VirtualSlot>())
+                    .unwrap_or(false)
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Visitor for SelfReferenceCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if !matches!(drtv.as_ref(), Directive::FunctionDefinition(_)) {
+            walk_directive(self, drtv);
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::Assignment(assign) => {
+                if self.is_setter && self.refers_to_accessor(&assign.left) {
+                    self.found.push(assign.location.clone());
+                } else if !self.refers_to_accessor(&assign.left) {
+                    self.visit_expression(&assign.left);
+                }
+                self.visit_expression(&assign.right);
+            },
+            _ => {
+                if !self.is_setter && self.refers_to_accessor(exp) {
+                    self.found.push(exp.location());
+                    return;
+                }
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use crate::ns::*;
+
+/// Returns the definition an expression refers to after verification:
+/// a type, or the property a reference value refers to.
+///
+/// ```ignore
+/// if let Some(definition) = referenced_entity(&host, &exp) {
+///     println!("{} refers to {definition}", exp.location().line_number());
+/// }
+/// ```
+pub fn referenced_entity(host: &Database, exp: &Rc<Expression>) -> Option<Entity> {
+    referenced_entity_of_value(&host.node_mapping().get(exp)?)
+}
+
+/// Returns the definition the value of an expression refers to, as
+/// `referenced_entity()` does. Types after substitution resolve to
+/// their origin, as `Vector` for `Vector.<int>`.
+pub fn referenced_entity_of_value(val: &Entity) -> Option<Entity> {
+    if val.is::<Type>() {
+        Some(if val.is::<TypeAfterSubstitution>() { val.origin() } else { val.clone() })
+    } else if val.is::<FixtureReferenceValue>() || val.is::<ScopeReferenceValue>() || val.is::<StaticReferenceValue>() || val.is::<PackageReferenceValue>() {
+        Some(val.property())
+    } else {
+        None
+    }
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use crate::ns::*;
+
+/// A definition named by a constant string given to
+/// `flash.utils.getDefinitionByName()`.
+#[derive(Clone, Debug)]
+pub struct ReflectedDefinition {
+    /// Location of the string.
+    pub location: Location,
+    pub name: String,
+    /// The resolved definition, or `None` if unknown.
+    pub definition: Option<Entity>,
+}
+
+/// Resolves constant names given to `flash.utils.getDefinitionByName()`
+/// against the `Database`, warning on unknown names and recording the
+/// definitions into `Verifier::reflected_definitions()`, so that codegen
+/// keeps them although no other code refers to them.
+///
+/// Names are fully qualified, with the local name separated by either
+/// `.` or `::`, as in `com.example.Main` and `com.example::Main`.
+pub(crate) struct ReflectedDefinitionSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> ReflectedDefinitionSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn is_get_definition_by_name(&self, base: &Rc<Expression>) -> bool {
+        referenced_entity(&self.verifier.host, base)
+            .map(|function| function.to_string() == "flash.utils.getDefinitionByName")
+            .unwrap_or(false)
+    }
+
+    fn constant_string(&self, exp: &Rc<Expression>) -> Option<String> {
+        if let Expression::StringLiteral(literal) = exp.as_ref() {
+            return Some(literal.value.clone());
+        }
+        self.verifier.host.node_mapping().get(exp).filter(|v| v.is::<StringConstant>()).map(|v| v.string_value())
+    }
+}
+
+impl<'a> Visitor for ReflectedDefinitionSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            let arg = call.arguments.first().filter(|_| self.is_get_definition_by_name(&call.base));
+            if let Some((arg, name)) = arg.and_then(|arg| Some((arg, self.constant_string(arg)?))) {
+                let definition = self.verifier.resolve_qualified_name(&name);
+                if definition.is_none() {
+                    self.verifier.add_warning(&arg.location(), WhackDiagnosticKind::UnknownReflectedDefinition, diagarg![name.clone()]);
+                }
+                self.verifier.reflected_definitions.push(ReflectedDefinition {
+                    location: arg.location(),
+                    name,
+                    definition,
+                });
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
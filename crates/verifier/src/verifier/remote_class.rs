@@ -0,0 +1,147 @@
+use crate::ns::*;
+
+/// Verifies classes marked with `[RemoteClass]` meta-data for lossless
+/// AMF serialization.
+///
+/// Aliases, defaulting to the fully qualified class name, must be unique
+/// across the programs and the constructor must be callable without
+/// arguments. Public instance properties that are not both readable and
+/// writable, or whose type cannot be serialized, are warned about unless
+/// marked `[Transient]`.
+pub(crate) struct RemoteClassSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Classes per registered alias.
+    aliases: HashMap<String, (Entity, Location)>,
+}
+
+impl<'a> RemoteClassSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, aliases: HashMap::new() };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn is_public(attributes: &[Attribute]) -> bool {
+        attributes.iter().any(|a| matches!(a, Attribute::Public(_)))
+    }
+
+    fn is_transient(attributes: &[Attribute]) -> bool {
+        Attribute::find_metadata(attributes).iter().any(|m| m.name.0 == "Transient")
+    }
+
+    fn alias(metadata: &Metadata) -> Option<String> {
+        metadata.entries.iter().flatten()
+            .find(|entry| entry.key.as_ref().map(|(k, _)| k == "alias").unwrap_or(false))
+            .map(|entry| match entry.value.as_ref() {
+                MetadataValue::String(val) => val.0.clone(),
+                MetadataValue::IdentifierString(val) => val.0.clone(),
+            })
+    }
+
+    fn verify_class(&mut self, drtv: &Rc<Directive>, defn: &ClassDefinition) {
+        let Some(metadata) = Attribute::find_metadata(&defn.attributes).into_iter().find(|m| m.name.0 == "RemoteClass") else {
+            return;
+        };
+        let host = self.verifier.host.clone();
+        let Some(class) = host.node_mapping().get(drtv).filter(|c| c.is::<ClassType>()) else {
+            return;
+        };
+
+        let alias = Self::alias(&metadata).unwrap_or_else(|| class.to_string());
+        if let Some((other, other_location)) = self.aliases.get(&alias).cloned() {
+            self.verifier.add_verify_error(&metadata.location, WhackDiagnosticKind::DuplicateRemoteClassAlias, diagarg![alias.clone(), other]);
+            self.verifier.related_information.push(RelatedInformation {
+                location: metadata.location.clone(),
+                related_location: other_location,
+                message: format!("'{alias}' is first registered here"),
+            });
+        } else {
+            self.aliases.insert(alias, (class.clone(), metadata.location.clone()));
+        }
+
+        let mut getters = HashMap::<String, Location>::new();
+        let mut setters = HashMap::<String, Location>::new();
+        for drtv in defn.block.directives.iter() {
+            match drtv.as_ref() {
+                Directive::FunctionDefinition(fndefn) => {
+                    if let FunctionName::Constructor(_) = &fndefn.name {
+                        if fndefn.common.signature.parameters.iter().any(|p| p.kind == ParameterKind::Required) {
+                            self.verifier.add_verify_error(&fndefn.location, WhackDiagnosticKind::RemoteClassRequiresNoArgumentConstructor, diagarg![class.clone()]);
+                        }
+                        continue;
+                    }
+                    if !Self::is_public(&fndefn.attributes) || Attribute::find_static(&fndefn.attributes).is_some() || Self::is_transient(&fndefn.attributes) {
+                        continue;
+                    }
+                    match &fndefn.name {
+                        FunctionName::Getter(name) => { getters.insert(name.0.clone(), name.1.clone()); },
+                        FunctionName::Setter(name) => { setters.insert(name.0.clone(), name.1.clone()); },
+                        _ => {},
+                    }
+                },
+                Directive::VariableDefinition(vardefn) => {
+                    if !Self::is_public(&vardefn.attributes) || Attribute::find_static(&vardefn.attributes).is_some() || Self::is_transient(&vardefn.attributes) {
+                        continue;
+                    }
+                    let is_const = vardefn.kind.0 == VariableDefinitionKind::Const;
+                    for binding in vardefn.bindings.iter() {
+                        let Some((name, location)) = binding.destructuring.destructuring.to_identifier_name() else {
+                            continue;
+                        };
+                        if is_const {
+                            self.verifier.add_warning(&location, WhackDiagnosticKind::RemoteClassPropertyNotReadWrite, diagarg![name]);
+                        } else {
+                            self.verify_property_type(&class, &name, &location);
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+        // Report in source order
+        let mut accessors: Vec<(&String, &Location)> = getters.iter().chain(setters.iter()).collect();
+        accessors.sort_by_key(|(_, location)| location.first_offset());
+        for (name, location) in accessors.iter() {
+            if !(getters.contains_key(*name) && setters.contains_key(*name)) {
+                self.verifier.add_warning(location, WhackDiagnosticKind::RemoteClassPropertyNotReadWrite, diagarg![(*name).clone()]);
+            }
+        }
+        let mut read_write: Vec<(&String, &Location)> = getters.iter().filter(|(name, _)| setters.contains_key(*name)).collect();
+        read_write.sort_by_key(|(_, location)| location.first_offset());
+        for (name, location) in read_write {
+            self.verify_property_type(&class, name, location);
+        }
+    }
+
+    fn verify_property_type(&mut self, class: &Entity, name: &str, location: &Location) {
+        let host = self.verifier.host.clone();
+        let Ok(Some(property)) = class.prototype(&host).get_in_any_public_ns(name) else {
+            return;
+        };
+        let t = property.static_type(&host).escape_of_nullable_or_non_nullable();
+        if t == host.function_type() || t.is::<FunctionType>() || Self::is_display_object(&host, &t) {
+            self.verifier.add_warning(location, WhackDiagnosticKind::NonSerializableRemoteClassProperty, diagarg![name.to_owned(), t]);
+        }
+    }
+
+    fn is_display_object(host: &Database, t: &Entity) -> bool {
+        let mut t = Some(t.clone());
+        while let Some(t1) = t {
+            if t1.is::<ClassType>() && t1.to_string() == "flash.display.DisplayObject" {
+                return true;
+            }
+            t = t1.extends_class(host);
+        }
+        false
+    }
+}
+
+impl<'a> Visitor for RemoteClassSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            self.verify_class(drtv, defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
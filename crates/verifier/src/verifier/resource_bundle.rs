@@ -0,0 +1,100 @@
+use crate::ns::*;
+
+/// Verifies `[ResourceBundle("name")]` meta-data and `@Resource(...)`
+/// directives against the bundles loaded for each configured locale.
+pub(crate) struct ResourceBundleSubverifier;
+
+impl ResourceBundleSubverifier {
+    /// Verifies a `[ResourceBundle]` meta-data and records the bundle
+    /// as referenced so that it is embedded into the output.
+    pub fn verify_metadata(verifier: &mut Subverifier, metadata: &Rc<Metadata>) {
+        let Some(entry) = metadata.entries.as_ref().and_then(|entries| entries.first().cloned()) else {
+            return;
+        };
+        let (name, location) = match entry.value.as_ref() {
+            MetadataValue::String(val) => val.clone(),
+            MetadataValue::IdentifierString(val) => val.clone(),
+        };
+        Self::verify_bundle(verifier, &location, &name);
+    }
+
+    /// Verifies the `@Resource(...)` directives given as meta-data
+    /// values, such as `[Inspectable(defaultValue="@Resource(...)")]`.
+    pub fn verify_metadata_resources(verifier: &mut Subverifier, metadata: &Rc<Metadata>) {
+        for entry in metadata.entries.iter().flatten() {
+            let (value, location) = match entry.value.as_ref() {
+                MetadataValue::String(val) => val.clone(),
+                MetadataValue::IdentifierString(val) => val.clone(),
+            };
+            if let Some((bundle, key)) = Self::parse_resource_directive(&value) {
+                Self::verify_resource(verifier, &location, &bundle, &key);
+            }
+        }
+    }
+
+    /// Verifies the `@Resource(...)` directives given as attribute
+    /// values throughout an MXML document.
+    pub fn verify_mxml(verifier: &mut Subverifier, mxml: &Rc<Mxml>) {
+        for content in mxml.content.iter() {
+            if let MxmlContent::Element(root) = content.as_ref() {
+                Self::verify_element(verifier, root);
+            }
+        }
+    }
+
+    fn verify_element(verifier: &mut Subverifier, element: &Rc<MxmlElement>) {
+        for attr in element.attributes.iter().filter(|a| !a.xmlns) {
+            if let Some((bundle, key)) = Self::parse_resource_directive(&attr.value.0) {
+                Self::verify_resource(verifier, &attr.value.1, &bundle, &key);
+            }
+        }
+        for child in MxmlStates::children(element).iter() {
+            Self::verify_element(verifier, child);
+        }
+    }
+
+    pub fn verify_bundle(verifier: &mut Subverifier, location: &Location, bundle: &str) -> bool {
+        verifier.referenced_resource_bundles.insert(bundle.to_owned());
+        let registry = verifier.resource_bundles.clone();
+        let mut found = true;
+        for locale in registry.locales.iter() {
+            if registry.bundle(locale, bundle).is_none() {
+                verifier.add_verify_error(location, WhackDiagnosticKind::ResourceBundleNotFound, diagarg![bundle.to_owned(), locale.clone()]);
+                found = false;
+            }
+        }
+        found
+    }
+
+    /// Verifies that a key exists in a bundle for every configured locale.
+    pub fn verify_resource(verifier: &mut Subverifier, location: &Location, bundle: &str, key: &str) {
+        if !Self::verify_bundle(verifier, location, bundle) {
+            return;
+        }
+        let registry = verifier.resource_bundles.clone();
+        for locale in registry.locales.iter() {
+            if !registry.bundle(locale, bundle).unwrap().contains_key(key) {
+                verifier.add_verify_error(location, WhackDiagnosticKind::ResourceKeyNotFound, diagarg![key.to_owned(), bundle.to_owned(), locale.clone()]);
+            }
+        }
+    }
+
+    /// Parses a `@Resource(key='k', bundle='b')` directive
+    /// into its bundle and key.
+    pub fn parse_resource_directive(text: &str) -> Option<(String, String)> {
+        let args = text.trim().strip_prefix("@Resource(")?.strip_suffix(')')?;
+        let mut bundle: Option<String> = None;
+        let mut key: Option<String> = None;
+        for pair in args.split(',') {
+            let (k, v) = pair.split_once('=')?;
+            let v = v.trim();
+            let v = v.strip_prefix(['\'', '"']).and_then(|v| v.strip_suffix(['\'', '"'])).unwrap_or(v);
+            match k.trim() {
+                "bundle" => bundle = Some(v.to_owned()),
+                "key" => key = Some(v.to_owned()),
+                _ => {},
+            }
+        }
+        Some((bundle?, key?))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use crate::ns::*;
+
+/// Traversal of a scope and its enclosing scopes.
+///
+/// ```ignore
+/// let class_scope = verifier.scope().find_scope(|s| s.is::<ClassScope>());
+/// for scope in verifier.scope().ancestors() {
+///     // innermost first
+/// }
+/// ```
+pub trait ScopeChain {
+    /// The scope itself followed by its enclosing scopes, innermost first.
+    fn ancestors(&self) -> ScopeAncestors;
+
+    /// The innermost scope of the chain satisfying a predicate.
+    fn find_scope(&self, predicate: impl Fn(&Entity) -> bool) -> Option<Entity> {
+        self.ancestors().find(|scope| predicate(scope))
+    }
+
+    fn enclosing_class_scope(&self) -> Option<Entity> {
+        self.find_scope(|scope| scope.is::<ClassScope>())
+    }
+
+    fn enclosing_activation(&self) -> Option<Entity> {
+        self.find_scope(|scope| scope.is::<Activation>())
+    }
+
+    /// Namespaces opened by every scope of the chain, innermost first.
+    fn open_namespaces(&self) -> Vec<Entity> {
+        self.ancestors().flat_map(|scope| scope.open_ns_set().iter().collect::<Vec<_>>()).collect()
+    }
+
+    /// Imports of every scope of the chain, innermost first.
+    fn imports(&self) -> Vec<Entity> {
+        self.ancestors().flat_map(|scope| scope.import_list().iter().collect::<Vec<_>>()).collect()
+    }
+}
+
+impl ScopeChain for Entity {
+    fn ancestors(&self) -> ScopeAncestors {
+        ScopeAncestors { next: Some(self.clone()) }
+    }
+}
+
+/// Iterator returned by `ScopeChain::ancestors()`.
+pub struct ScopeAncestors {
+    next: Option<Entity>,
+}
+
+impl Iterator for ScopeAncestors {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        let scope = self.next.take()?;
+        self.next = scope.parent();
+        Some(scope)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,135 @@
+use crate::ns::*;
+
+/// Source position at which `ScopeSnapshot`s are recorded.
+#[derive(Clone, Debug)]
+pub struct ScopeProbe {
+    pub file_path: String,
+    /// Byte offset into the file text.
+    pub offset: usize,
+}
+
+impl ScopeProbe {
+    pub fn contains(&self, location: &Location) -> bool {
+        location.compilation_unit().file_path().map(|p| p == self.file_path).unwrap_or(false)
+            && location.first_offset() <= self.offset && self.offset <= location.last_offset()
+    }
+}
+
+/// An import of a scope and whether the imported package declares the
+/// probed name.
+#[derive(Clone, Debug)]
+pub struct ScopeSnapshotImport {
+    pub name: String,
+    pub declares_name: bool,
+}
+
+/// A scope of the scope chain at the probed position.
+#[derive(Clone, Debug)]
+pub struct ScopeSnapshotScope {
+    /// Such as `activation`, `class` or `package`.
+    pub kind: String,
+    pub open_namespaces: Vec<String>,
+    pub imports: Vec<ScopeSnapshotImport>,
+    /// Whether the scope itself declares the probed name.
+    pub declares_name: bool,
+}
+
+/// The scope chain seen while resolving an unqualified name, innermost
+/// scope first, as recorded for a `ScopeProbe`.
+///
+/// ```ignore
+/// verifier.set_scope_probe(Some(ScopeProbe { file_path, offset }));
+/// verifier.verify_programs(&compiler_options, programs, vec![]);
+/// for snapshot in verifier.scope_snapshots() {
+///     print!("{}", snapshot.format_human());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScopeSnapshot {
+    pub location: Location,
+    pub name: String,
+    pub scopes: Vec<ScopeSnapshotScope>,
+    /// The resolved reference, if any.
+    pub resolved: Option<String>,
+}
+
+impl ScopeSnapshot {
+    pub(crate) fn capture(host: &Database, scope: &Entity, location: &Location, name: &str, resolved: Option<&Entity>) -> Self {
+        let open_ns_set = scope.concat_open_ns_set_of_scope_chain();
+        let mut scopes = vec![];
+        let mut scope = Some(scope.clone());
+        while let Some(scope1) = scope {
+            let open_namespaces = scope1.open_ns_set().iter().map(|ns| ns.to_string()).collect();
+            let imports = scope1.import_list().iter().map(|import| Self::import_snapshot(host, &import, &open_ns_set, name)).collect();
+            let declares_name = !matches!(scope1.properties(host).get_in_ns_set_or_any_public_ns(&open_ns_set, name), Ok(None));
+            scopes.push(ScopeSnapshotScope {
+                kind: Self::scope_kind(&scope1).into(),
+                open_namespaces,
+                imports,
+                declares_name,
+            });
+            scope = scope1.parent();
+        }
+        Self {
+            location: location.clone(),
+            name: name.to_owned(),
+            scopes,
+            resolved: resolved.map(|r| r.to_string()),
+        }
+    }
+
+    fn scope_kind(scope: &Entity) -> &'static str {
+        if scope.is::<Activation>() {
+            "activation"
+        } else if scope.is::<ClassScope>() {
+            "class"
+        } else if scope.is::<EnumScope>() {
+            "enum"
+        } else if scope.is::<InterfaceScope>() {
+            "interface"
+        } else if scope.is::<PackageScope>() {
+            "package"
+        } else if scope.is::<FixtureScope>() {
+            "fixture"
+        } else {
+            "block"
+        }
+    }
+
+    fn import_snapshot(host: &Database, import: &Entity, open_ns_set: &SharedArray<Entity>, name: &str) -> ScopeSnapshotImport {
+        if import.is::<PackageWildcardImport>() || import.is::<PackageRecursiveImport>() {
+            let pckg = import.package();
+            let suffix = if import.is::<PackageWildcardImport>() { "*" } else { "**" };
+            let declares_name = matches!(pckg.properties(host).get_in_ns_set_or_any_public_ns(open_ns_set, name), Ok(Some(_)));
+            return ScopeSnapshotImport {
+                name: format!("{}.{suffix}", pckg.fully_qualified_name_list().join(".")),
+                declares_name,
+            };
+        }
+        let property = import.property();
+        ScopeSnapshotImport {
+            name: property.to_string(),
+            declares_name: property.name().local_name() == name,
+        }
+    }
+
+    pub fn format_human(&self) -> String {
+        let mut r = format!("{}:{}: {}\n", self.location.first_line_number(), self.location.first_column() + 1, self.name);
+        for (i, scope) in self.scopes.iter().enumerate() {
+            let found = if scope.declares_name { " (declares the name)" } else { "" };
+            r.push_str(&format!("  #{i} {}{found}\n", scope.kind));
+            if !scope.open_namespaces.is_empty() {
+                r.push_str(&format!("    open namespaces: {}\n", scope.open_namespaces.join(", ")));
+            }
+            for import in scope.imports.iter() {
+                let found = if import.declares_name { " (declares the name)" } else { "" };
+                r.push_str(&format!("    import {}{found}\n", import.name));
+            }
+        }
+        match self.resolved.as_ref() {
+            Some(resolved) => r.push_str(&format!("  resolved to {resolved}\n")),
+            None => r.push_str("  not resolved\n"),
+        }
+        r
+    }
+}
\ No newline at end of file
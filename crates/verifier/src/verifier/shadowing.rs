@@ -0,0 +1,181 @@
+use crate::ns::*;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ShadowedKind {
+    Parameter,
+    Local,
+    InstanceMember,
+}
+
+impl ShadowedKind {
+    fn description(&self) -> &'static str {
+        match self {
+            Self::Parameter => "a parameter",
+            Self::Local => "an outer local variable",
+            Self::InstanceMember => "an instance member",
+        }
+    }
+}
+
+/// Reports local variable declarations shadowing parameters, locals of
+/// enclosing blocks or functions, or instance members of the enclosing
+/// class. The shadowed definition is attached as related information.
+///
+/// Only members declared by the enclosing class itself are considered.
+pub(crate) struct ShadowingSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    options: ShadowingWarningOptions,
+    /// Names declared by enclosing functions and blocks.
+    frames: Vec<HashMap<String, (Location, ShadowedKind)>>,
+    function_depth: usize,
+    /// Instance members of the enclosing class, if in an instance context.
+    members: Vec<Option<Rc<HashMap<String, Location>>>>,
+}
+
+impl<'a> ShadowingSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], options: &ShadowingWarningOptions) {
+        if !(options.parameters || options.outer_locals || options.instance_members) {
+            return;
+        }
+        let mut subverifier = Self {
+            verifier,
+            options: options.clone(),
+            frames: vec![],
+            function_depth: 0,
+            members: vec![],
+        };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn instance_members(block: &Block) -> HashMap<String, Location> {
+        let mut members = HashMap::new();
+        for drtv in block.directives.iter() {
+            match drtv.as_ref() {
+                Directive::VariableDefinition(defn) => {
+                    if Attribute::find_static(&defn.attributes).is_some() {
+                        continue;
+                    }
+                    for binding in defn.bindings.iter() {
+                        if let Some((name, location)) = binding.destructuring.destructuring.to_identifier_name() {
+                            members.insert(name, location);
+                        }
+                    }
+                },
+                Directive::FunctionDefinition(defn) => {
+                    if Attribute::find_static(&defn.attributes).is_some() {
+                        continue;
+                    }
+                    match &defn.name {
+                        FunctionName::Identifier(name) |
+                        FunctionName::Getter(name) |
+                        FunctionName::Setter(name) => {
+                            members.insert(name.0.clone(), name.1.clone());
+                        },
+                        FunctionName::Constructor(_) => {},
+                    }
+                },
+                _ => {},
+            }
+        }
+        members
+    }
+
+    fn find_shadowed(&self, name: &str) -> Option<(Location, ShadowedKind)> {
+        // Skip the current frame, whose names are redeclarations
+        for frame in self.frames.iter().rev().skip(1) {
+            if let Some((location, kind)) = frame.get(name) {
+                let enabled = match kind {
+                    ShadowedKind::Parameter => self.options.parameters,
+                    _ => self.options.outer_locals,
+                };
+                if enabled {
+                    return Some((location.clone(), *kind));
+                }
+            }
+        }
+        if !self.options.instance_members {
+            return None;
+        }
+        let members = self.members.last()?.as_ref()?;
+        members.get(name).map(|location| (location.clone(), ShadowedKind::InstanceMember))
+    }
+
+    fn declare_local(&mut self, name: String, location: Location) {
+        if let Some((shadowed_location, kind)) = self.find_shadowed(&name) {
+            self.verifier.add_warning(&location, WhackDiagnosticKind::ShadowedDeclaration, diagarg![name.clone(), kind.description().to_owned()]);
+            self.verifier.related_information.push(RelatedInformation {
+                location: location.clone(),
+                related_location: shadowed_location,
+                message: format!("'{name}' is defined here"),
+            });
+        }
+        if let Some(frame) = self.frames.last_mut() {
+            frame.entry(name).or_insert((location, ShadowedKind::Local));
+        }
+    }
+
+    fn with_frame(&mut self, f: impl FnOnce(&mut Self)) {
+        self.frames.push(HashMap::new());
+        f(self);
+        self.frames.pop();
+    }
+}
+
+impl<'a> Visitor for ShadowingSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                let members = Rc::new(Self::instance_members(&defn.block));
+                // Functions nested in a class body leave outer locals behind
+                let frames = std::mem::take(&mut self.frames);
+                let function_depth = std::mem::take(&mut self.function_depth);
+                for drtv in defn.block.directives.iter() {
+                    let is_instance = match drtv.as_ref() {
+                        Directive::FunctionDefinition(fndefn) => Attribute::find_static(&fndefn.attributes).is_none(),
+                        Directive::VariableDefinition(vardefn) => Attribute::find_static(&vardefn.attributes).is_none(),
+                        _ => false,
+                    };
+                    self.members.push(if is_instance { Some(members.clone()) } else { None });
+                    self.visit_directive(drtv);
+                    self.members.pop();
+                }
+                self.frames = frames;
+                self.function_depth = function_depth;
+            },
+            Directive::VariableDefinition(defn) if self.function_depth != 0 => {
+                for binding in defn.bindings.iter() {
+                    if let Some((name, location)) = binding.destructuring.destructuring.to_identifier_name() {
+                        self.declare_local(name, location);
+                    }
+                }
+                walk_directive(self, drtv);
+            },
+            Directive::Block(_) |
+            Directive::ForStatement(_) |
+            Directive::ForInStatement(_) => {
+                self.with_frame(|this| walk_directive(this, drtv));
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        self.function_depth += 1;
+        let mut params = HashMap::new();
+        for param in common.signature.parameters.iter() {
+            if let Some((name, location)) = param.destructuring.destructuring.to_identifier_name() {
+                params.insert(name, (location, ShadowedKind::Parameter));
+            }
+        }
+        self.frames.push(params);
+        // Body locals get a frame apart from the parameters, so that
+        // redeclaring a parameter is reported.
+        self.with_frame(|this| walk_function_common(this, common));
+        self.frames.pop();
+        self.function_depth -= 1;
+    }
+}
\ No newline at end of file
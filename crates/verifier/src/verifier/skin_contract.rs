@@ -0,0 +1,187 @@
+use crate::ns::*;
+
+struct SkinContractClass {
+    name: (String, Location),
+    /// Fully qualified name, such as `com.example.Button`.
+    qualified_name: String,
+    /// `[SkinPart]` members, with whether they are required.
+    skin_parts: Vec<(String, bool)>,
+    /// `[SkinState]` names.
+    skin_states: Vec<String>,
+    /// `[HostComponent]` of a skin class.
+    host_component: Option<String>,
+    /// `[States]` declared by a skin class.
+    states: Vec<String>,
+    /// Names of instance variables and accessors.
+    members: HashSet<String>,
+}
+
+/// Verifies Spark skin contracts: a skin class declaring
+/// `[HostComponent("C")]` must declare every required `[SkinPart]`
+/// of `C` (and of its base classes) and, if it declares `[States]`,
+/// every `[SkinState]` of `C`.
+pub(crate) struct SkinContractSubverifier;
+
+impl SkinContractSubverifier {
+    pub fn verify_programs(verifier: &mut Subverifier, programs: &[Rc<Program>]) {
+        let mut classes = Vec::<(Option<Entity>, SkinContractClass)>::new();
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                let package_name = pckgdef.name.iter().map(|n| n.0.clone()).collect::<Vec<_>>().join(".");
+                Self::collect(verifier, &pckgdef.block.directives, &package_name, &mut classes);
+            }
+            Self::collect(verifier, &program.directives, "", &mut classes);
+        }
+
+        let by_entity = |entity: &Entity| classes.iter().find(|(e, _)| e.as_ref() == Some(entity)).map(|(_, c)| c);
+
+        for (skin_entity, skin) in classes.iter() {
+            let Some(host_name) = skin.host_component.as_ref() else {
+                continue;
+            };
+            let Some((host_entity, host_class)) = classes.iter().find(|(_, c)| &c.qualified_name == host_name) else {
+                continue;
+            };
+
+            // Members of the skin, including these of base classes in sources.
+            let mut members = skin.members.clone();
+            let mut base = skin_entity.as_ref().and_then(|e| e.extends_class(&verifier.host));
+            while let Some(b) = base {
+                if let Some(c) = by_entity(&b) {
+                    members.extend(c.members.iter().cloned());
+                }
+                base = b.extends_class(&verifier.host);
+            }
+
+            // Skin parts and states of the host component and its base classes.
+            let mut chain = vec![host_class];
+            let mut base = host_entity.as_ref().and_then(|e| e.extends_class(&verifier.host));
+            while let Some(b) = base {
+                if let Some(c) = by_entity(&b) {
+                    chain.push(c);
+                }
+                base = b.extends_class(&verifier.host);
+            }
+
+            for c in chain.iter() {
+                for (part, required) in c.skin_parts.iter() {
+                    if *required && !members.contains(part) {
+                        verifier.add_verify_error(&skin.name.1, WhackDiagnosticKind::MissingRequiredSkinPart, diagarg![skin.name.0.clone(), part.clone(), c.name.0.clone()]);
+                    }
+                }
+                if !skin.states.is_empty() {
+                    for state in c.skin_states.iter() {
+                        if !skin.states.contains(state) {
+                            verifier.add_verify_error(&skin.name.1, WhackDiagnosticKind::MissingSkinState, diagarg![skin.name.0.clone(), state.clone(), c.name.0.clone()]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn collect(verifier: &Subverifier, list: &[Rc<Directive>], package_name: &str, classes: &mut Vec<(Option<Entity>, SkinContractClass)>) {
+        for drtv in list.iter() {
+            match drtv.as_ref() {
+                Directive::ClassDefinition(defn) => {
+                    let entity = verifier.host.node_mapping().get(drtv);
+                    classes.push((entity, Self::collect_class(defn, package_name)));
+                },
+                Directive::Block(block) => {
+                    Self::collect(verifier, &block.directives, package_name, classes);
+                },
+                Directive::IncludeDirective(incdrtv) => {
+                    for pckgdef in incdrtv.nested_packages.iter() {
+                        let package_name = pckgdef.name.iter().map(|n| n.0.clone()).collect::<Vec<_>>().join(".");
+                        Self::collect(verifier, &pckgdef.block.directives, &package_name, classes);
+                    }
+                    Self::collect(verifier, &incdrtv.nested_directives, package_name, classes);
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn collect_class(defn: &ClassDefinition, package_name: &str) -> SkinContractClass {
+        let qualified_name = if package_name.is_empty() { defn.name.0.clone() } else { format!("{package_name}.{}", defn.name.0) };
+        let mut class = SkinContractClass {
+            name: defn.name.clone(),
+            qualified_name,
+            skin_parts: vec![],
+            skin_states: vec![],
+            host_component: None,
+            states: vec![],
+            members: HashSet::new(),
+        };
+        for m in Attribute::find_metadata(&defn.attributes).iter() {
+            match m.name.0.as_str() {
+                "SkinState" => class.skin_states.extend(Self::metadata_values(m).into_iter().take(1)),
+                "HostComponent" => class.host_component = Self::metadata_values(m).into_iter().next(),
+                "States" => class.states.extend(Self::metadata_values(m)),
+                _ => {},
+            }
+        }
+        for drtv in defn.block.directives.iter() {
+            match drtv.as_ref() {
+                Directive::VariableDefinition(defn) => {
+                    if Attribute::find_static(&defn.attributes).is_some() {
+                        continue;
+                    }
+                    let skin_part = Attribute::find_metadata(&defn.attributes).into_iter().find(|m| m.name.0 == "SkinPart");
+                    for binding in defn.bindings.iter() {
+                        let Expression::QualifiedIdentifier(id) = binding.destructuring.destructuring.as_ref() else {
+                            continue;
+                        };
+                        let Some(name) = id.to_identifier_name_or_asterisk() else {
+                            continue;
+                        };
+                        if let Some(m) = skin_part.as_ref() {
+                            class.skin_parts.push((name.0.clone(), Self::is_required(m)));
+                        }
+                        class.members.insert(name.0);
+                    }
+                },
+                Directive::FunctionDefinition(defn) => {
+                    if Attribute::find_static(&defn.attributes).is_some() {
+                        continue;
+                    }
+                    match &defn.name {
+                        FunctionName::Getter(name) | FunctionName::Setter(name) => {
+                            if let Some(m) = Attribute::find_metadata(&defn.attributes).into_iter().find(|m| m.name.0 == "SkinPart") {
+                                class.skin_parts.push((name.0.clone(), Self::is_required(&m)));
+                            }
+                            class.members.insert(name.0.clone());
+                        },
+                        _ => {},
+                    }
+                },
+                _ => {},
+            }
+        }
+        class
+    }
+
+    fn is_required(m: &Rc<Metadata>) -> bool {
+        m.entries.as_ref().map(|entries| entries.iter().any(|entry| {
+            entry.key.as_ref().map(|k| k.0 == "required").unwrap_or(false) && Self::entry_value(entry) == "true"
+        })).unwrap_or(false)
+    }
+
+    /// Returns the values of entries without a key, or the `name` entry.
+    fn metadata_values(m: &Rc<Metadata>) -> Vec<String> {
+        let Some(entries) = m.entries.as_ref() else {
+            return vec![];
+        };
+        entries.iter()
+            .filter(|entry| entry.key.as_ref().map(|k| k.0 == "name").unwrap_or(true))
+            .map(|entry| Self::entry_value(entry))
+            .collect()
+    }
+
+    fn entry_value(entry: &Rc<MetadataEntry>) -> String {
+        match entry.value.as_ref() {
+            MetadataValue::String(val) => val.0.clone(),
+            MetadataValue::IdentifierString(val) => val.0.clone(),
+        }
+    }
+}
\ No newline at end of file
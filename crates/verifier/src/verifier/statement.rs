@@ -5,20 +5,40 @@ pub(crate) struct StatementSubverifier;
 impl StatementSubverifier {
     pub fn verify_statements(verifier: &mut Subverifier, list: &[Rc<Directive>]) {
         for stmt in list.iter() {
+            if verifier.cancelled() {
+                return;
+            }
             Self::verify_statement(verifier, stmt);
         }
     }
 
+    /// Verifies a statement, queuing it whole on `DeferredStatementQueue`
+    /// if one of its own expressions defers. Compound statements return
+    /// before verifying their substatements once that happens.
     pub fn verify_statement(verifier: &mut Subverifier, stmt: &Rc<Directive>) {
+        let scope = verifier.scope.clone();
+        let enclosing_deferral = verifier.statement_deferral.take();
+        Self::verify_statement_1(verifier, stmt);
+        if let Some(blocker) = verifier.statement_deferral.take() {
+            verifier.deferred_statements.push(DeferredStatement { stmt: stmt.clone(), scope, blocker });
+        }
+        verifier.statement_deferral = enclosing_deferral;
+    }
+
+    fn verify_statement_1(verifier: &mut Subverifier, stmt: &Rc<Directive>) {
         match stmt.as_ref() {
             Directive::ExpressionStatement(estmt) => {
-                verifier.verify_expression_or_max_cycles_error(&estmt.expression, &Default::default());
+                if !Self::verify_static_assert(verifier, &estmt.expression) {
+                    verifier.verify_expression_or_max_cycles_error(&estmt.expression, &Default::default());
+                }
             },
             Directive::SuperStatement(supstmt) => {
                 Self::verify_super_stmt(verifier, stmt, supstmt)
             },
             Directive::Block(block) => {
-                let scope = verifier.host.node_mapping().get(stmt).unwrap();
+                let Some(scope) = Self::statement_mapping(verifier, stmt) else {
+                    return;
+                };
                 verifier.inherit_and_enter_scope(&scope);
                 Self::verify_statements(verifier, &block.directives);
                 verifier.exit_scope();
@@ -27,7 +47,11 @@ impl StatementSubverifier {
                 Self::verify_statement(verifier, &labstmt.substatement);
             },
             Directive::IfStatement(ifstmt) => {
-                verifier.verify_expression_or_max_cycles_error(&ifstmt.test, &Default::default());
+                let test = verifier.verify_expression_or_max_cycles_error(&ifstmt.test, &Default::default());
+                if verifier.statement_deferred() {
+                    return;
+                }
+                BooleanTest::check(verifier, &ifstmt.test, test.as_ref());
                 Self::verify_statement(verifier, &ifstmt.consequent);
                 if let Some(alt) = ifstmt.alternative.as_ref() {
                     Self::verify_statement(verifier, alt);
@@ -36,50 +60,89 @@ impl StatementSubverifier {
             Directive::SwitchStatement(swstmt) => {
                 let host = verifier.host.clone();
                 let discriminant = verifier.verify_expression_or_max_cycles_error(&swstmt.discriminant, &Default::default());
-                for case in swstmt.cases.iter() {
-                    for label in case.labels.iter() {
-                        match label {
-                            CaseLabel::Case((exp, _)) => {
-                                if let Some(discriminant) = discriminant.as_ref() {
-                                    verifier.imp_coerce_exp_or_max_cycles_error(exp, &discriminant.static_type(&host));
-                                } else {
-                                    verifier.verify_expression_or_max_cycles_error(exp, &Default::default());
+                if verifier.statement_deferred() {
+                    return;
+                }
+                let mut matched_slots: Vec<Entity> = vec![];
+                let mut string_cases: Vec<(String, Location)> = vec![];
+                let mut has_default = false;
+                for label in swstmt.cases.iter().flat_map(|case| case.labels.iter()) {
+                    match label {
+                        CaseLabel::Case((exp, _)) => {
+                            if let Some(discriminant) = discriminant.as_ref() {
+                                let val = verifier.imp_coerce_exp_or_max_cycles_error(exp, &discriminant.static_type(&host));
+                                if let Some(val) = val.as_ref() {
+                                    if val.is::<StaticReferenceValue>() {
+                                        matched_slots.push(val.property());
+                                    }
+                                }
+                                if let Some(val) = val.filter(|v| v.is::<StringConstant>()) {
+                                    string_cases.push((val.string_value(), exp.location()));
                                 }
-                            },
-                            CaseLabel::Default(_) => {},
-                        }
+                            } else {
+                                verifier.verify_expression_or_max_cycles_error(exp, &Default::default());
+                            }
+                        },
+                        CaseLabel::Default(_) => {
+                            has_default = true;
+                        },
                     }
+                }
+                if verifier.statement_deferred() {
+                    return;
+                }
+                for case in swstmt.cases.iter() {
                     Self::verify_statements(verifier, &case.directives);
                 }
+                Self::check_switch_fallthrough(verifier, swstmt);
+                Self::check_string_switch_cases(verifier, &string_cases);
+                if let Some(discriminant) = discriminant.as_ref() {
+                    if !has_default {
+                        Self::check_exhaustive_enum_switch(verifier, &discriminant.static_type(&host), &matched_slots, &swstmt.discriminant.location());
+                    }
+                }
             },
             Directive::SwitchTypeStatement(swstmt) => {
-                verifier.verify_expression_or_max_cycles_error(&swstmt.discriminant, &Default::default());
-                for case in swstmt.cases.iter() {
-                    Self::verify_block(verifier, &case.block);
-                }
+                Self::verify_switch_type_stmt(verifier, swstmt);
             },
             Directive::DoStatement(dostmt) => {
+                // The test is verified first so that a deferring test
+                // does not verify the body twice.
+                let test = verifier.verify_expression_or_max_cycles_error(&dostmt.test, &Default::default());
+                if verifier.statement_deferred() {
+                    return;
+                }
+                BooleanTest::check(verifier, &dostmt.test, test.as_ref());
                 Self::verify_statement(verifier, &dostmt.body);
-                verifier.verify_expression_or_max_cycles_error(&dostmt.test, &Default::default());
             },
             Directive::WhileStatement(wstmt) => {
-                verifier.verify_expression_or_max_cycles_error(&wstmt.test, &Default::default());
+                let test = verifier.verify_expression_or_max_cycles_error(&wstmt.test, &Default::default());
+                if verifier.statement_deferred() {
+                    return;
+                }
+                BooleanTest::check(verifier, &wstmt.test, test.as_ref());
+                Self::check_constant_loop_condition(verifier, test.as_ref(), &wstmt.test.location(), &wstmt.body);
                 Self::verify_statement(verifier, &wstmt.body);
             },
             Directive::ForStatement(forstmt) => {
-                let host = verifier.host.clone();
-                let scope = host.node_mapping().get(&stmt).unwrap();
+                let Some(scope) = Self::statement_mapping(verifier, stmt) else {
+                    return;
+                };
                 verifier.inherit_and_enter_scope(&scope);
                 if let Some(ForInitializer::Expression(init)) = forstmt.init.as_ref() {
                     verifier.verify_expression_or_max_cycles_error(&init, &Default::default());
                 }
                 if let Some(test) = forstmt.test.as_ref() {
-                    verifier.verify_expression_or_max_cycles_error(&test, &Default::default());
+                    let val = verifier.verify_expression_or_max_cycles_error(&test, &Default::default());
+                    BooleanTest::check(verifier, test, val.as_ref());
+                    Self::check_constant_loop_condition(verifier, val.as_ref(), &test.location(), &forstmt.body);
                 }
                 if let Some(update) = forstmt.update.as_ref() {
                     verifier.verify_expression_or_max_cycles_error(&update, &Default::default());
                 }
-                Self::verify_statement(verifier, &forstmt.body);
+                if !verifier.statement_deferred() {
+                    Self::verify_statement(verifier, &forstmt.body);
+                }
                 verifier.exit_scope();
             },
             Directive::ForInStatement(forstmt) => {
@@ -88,6 +151,9 @@ impl StatementSubverifier {
             Directive::WithStatement(wstmt) => {
                 let host = verifier.host.clone();
                 let obj = verifier.verify_expression_or_max_cycles_error(&wstmt.object, &Default::default());
+                if verifier.statement_deferred() {
+                    return;
+                }
                 let scope = host.lazy_node_mapping(stmt, || {
                     if let Some(obj) = obj {
                         host.factory().create_with_scope(&obj)
@@ -119,7 +185,9 @@ impl StatementSubverifier {
                 }
             },
             Directive::ConfigurationDirective(cfgdrtv) => {
-                let cval = verifier.host.node_mapping().get(stmt).unwrap();
+                let Some(cval) = Self::statement_mapping(verifier, stmt) else {
+                    return;
+                };
                 if cval.is::<BooleanConstant>() && cval.boolean_value() {
                     // Do not just resolve the directive; if it is a block,
                     // resolve it without creating a block scope for it.
@@ -155,6 +223,16 @@ impl StatementSubverifier {
         verifier.exit_scope();
     }
 
+    /// Returns the entity mapped to a statement by the directive
+    /// verification, reporting an internal error if there is none.
+    fn statement_mapping(verifier: &mut Subverifier, stmt: &Rc<Directive>) -> Option<Entity> {
+        let r = verifier.host.node_mapping().get(stmt);
+        if r.is_none() {
+            verifier.add_internal_error(&stmt.location(), "statement was not resolved");
+        }
+        r
+    }
+
     fn verify_return_stmt(verifier: &mut Subverifier, _stmt: &Rc<Directive>, retstmt: &ReturnStatement) {
         let host = verifier.host.clone();
         let act = verifier.scope().search_activation();
@@ -202,19 +280,12 @@ impl StatementSubverifier {
 
     fn verify_super_stmt(verifier: &mut Subverifier, _stmt: &Rc<Directive>, supstmt: &SuperStatement) {
         let host = verifier.host.clone();
-        let mut scope = Some(verifier.scope());
-        while let Some(scope1) = scope.as_ref() {
-            if scope1.is::<ClassScope>() {
-                break;
-            }
-            scope = scope1.parent();
-        }
-        if scope.is_none() {
+        let Some(scope) = verifier.scope().enclosing_class_scope() else {
             return;
-        }
-        let scope = scope.unwrap();
+        };
         let class_t = scope.class().extends_class(&host);
         if class_t.is_none() {
+            verifier.add_verify_error(&supstmt.location, WhackDiagnosticKind::SuperStatementRequiresBaseClass, diagarg![]);
             return;
         }
         let class_t = class_t.unwrap();
@@ -240,11 +311,16 @@ impl StatementSubverifier {
 
     fn verify_for_in_stmt(verifier: &mut Subverifier, stmt: &Rc<Directive>, forstmt: &ForInStatement) {
         let host = verifier.host.clone();
-        let scope = host.node_mapping().get(&stmt).unwrap();
+        let Some(scope) = Self::statement_mapping(verifier, stmt) else {
+            return;
+        };
 
         if let ForInBinding::Expression(dest_exp) = &forstmt.left {
             // Resolve object key-values
             let obj = verifier.verify_expression_or_max_cycles_error(&forstmt.right, &Default::default());
+            if verifier.statement_deferred() {
+                return;
+            }
             let mut kv_types = (host.any_type(), host.any_type());
             if let Some(obj) = obj.as_ref() {
                 let kv_types_1 = StatementSubverifier::for_in_kv_types(&host, obj);
@@ -266,6 +342,9 @@ impl StatementSubverifier {
                 mode: VerifyMode::Write,
                 ..default()
             });
+            if verifier.statement_deferred() {
+                return;
+            }
             if let Some(dest) = dest {
                 let dest_t = dest.static_type(&host);
 
@@ -336,6 +415,14 @@ impl StatementSubverifier {
             let num_t = host.number_type().defer()?;
             return Ok(Some((num_t.clone(), num_t)));
         }
+        // Map.<K, V>
+        if let Some((key_t, value_t)) = CollectionTypes::map_key_value_types(host, &t) {
+            return Ok(Some((key_t, value_t)));
+        }
+        // Set.<T>
+        if let Some(elem_t) = CollectionTypes::set_element_type(host, &t) {
+            return Ok(Some((elem_t.clone(), elem_t)));
+        }
         // Dictionary
         if t == host.dictionary_type().defer()? {
             return Ok(Some((host.any_type(), host.any_type())));
@@ -349,7 +436,218 @@ impl StatementSubverifier {
         if t == host.xml_type().defer()? || t == host.xml_list_type().defer()? {
             return Ok(Some((host.number_type().defer()?, host.xml_type())));
         }
+        // Iterable or iterator
+        if let Some(elem_t) = Self::iterator_element_type(host, &t)? {
+            return Ok(Some((host.number_type().defer()?, elem_t)));
+        }
 
         Ok(None)
     }
+
+    /// Verifies a `static_assert(test, "message")` statement, returning
+    /// `false` if the expression is not one. The test must fold into a
+    /// Boolean constant.
+    fn verify_static_assert(verifier: &mut Subverifier, exp: &Rc<Expression>) -> bool {
+        let Expression::Call(call) = exp.as_ref() else {
+            return false;
+        };
+        let Expression::QualifiedIdentifier(id) = call.base.as_ref() else {
+            return false;
+        };
+        if id.attribute || id.qualifier.is_some() || !matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "static_assert") {
+            return false;
+        }
+
+        let message = match call.arguments.get(1).map(|arg| arg.as_ref()) {
+            Some(Expression::StringLiteral(literal)) => Some(literal.value.clone()),
+            _ => None,
+        };
+        if call.arguments.is_empty() || call.arguments.len() > 2 || (call.arguments.len() == 2 && message.is_none()) {
+            verifier.add_verify_error(&call.location, WhackDiagnosticKind::MalformedStaticAssert, diagarg![]);
+            return true;
+        }
+
+        let test = &call.arguments[0];
+        let Some(val) = verifier.verify_expression_or_max_cycles_error(test, &Default::default()) else {
+            return true;
+        };
+        if !val.is::<BooleanConstant>() {
+            verifier.add_verify_error(&test.location(), WhackDiagnosticKind::StaticAssertRequiresConstant, diagarg![]);
+        } else if !val.boolean_value() {
+            let message = message.unwrap_or_else(|| "Static assertion failed.".to_owned());
+            verifier.add_verify_error(&call.location, WhackDiagnosticKind::StaticAssertFailed, diagarg![message]);
+        }
+        true
+    }
+
+    /// Warns on `while` and `for` loops whose test is a constant `true`
+    /// without a way out of the body, or a constant `false`.
+    fn check_constant_loop_condition(verifier: &mut Subverifier, test: Option<&Entity>, location: &Location, body: &Rc<Directive>) {
+        let Some(test) = test.filter(|t| t.is::<BooleanConstant>()) else {
+            return;
+        };
+        if !test.boolean_value() {
+            verifier.add_warning(location, WhackDiagnosticKind::LoopBodyNeverRuns, diagarg![]);
+        } else if !Reachability::may_exit_loop(body) {
+            verifier.add_warning(location, WhackDiagnosticKind::InfiniteLoop, diagarg![]);
+        }
+    }
+
+    /// Warns, if enabled, on non-empty cases whose end is reachable and
+    /// which are not followed by a comment mentioning fallthrough.
+    fn check_switch_fallthrough(verifier: &mut Subverifier, swstmt: &SwitchStatement) {
+        if !verifier.switch_fallthrough_warnings {
+            return;
+        }
+        for (case, next_case) in swstmt.cases.iter().zip(swstmt.cases.iter().skip(1)) {
+            let Some(last) = case.directives.last() else {
+                continue;
+            };
+            if !Reachability::list_completes_normally(&case.directives) {
+                continue;
+            }
+            let Some(next_location) = next_case.labels.first().map(|label| match label {
+                CaseLabel::Case((_, location)) => location.clone(),
+                CaseLabel::Default(location) => location.clone(),
+            }) else {
+                continue;
+            };
+            let (start, end) = (last.location().last_offset(), next_location.first_offset());
+            let annotated = next_location.compilation_unit().comments().iter()
+                .filter(|comment| start <= comment.location().first_offset() && comment.location().last_offset() <= end)
+                .any(|comment| {
+                    let content = comment.content().to_lowercase();
+                    ["fallthrough", "falls through", "fall through"].iter().any(|s| content.contains(s))
+                });
+            if annotated {
+                continue;
+            }
+            verifier.add_warning(&next_location, WhackDiagnosticKind::SwitchCaseFallthrough, diagarg![]);
+        }
+    }
+
+    /// Warns on each string constant case matching an earlier one, which
+    /// is never reached, and, if enabled, on cases differing only in
+    /// letter case. Cases that are not constant are not compared.
+    fn check_string_switch_cases(verifier: &mut Subverifier, cases: &[(String, Location)]) {
+        for (i, (value, location)) in cases.iter().enumerate() {
+            let previous = &cases[..i];
+            if let Some((_, first)) = previous.iter().find(|(v, _)| v == value) {
+                verifier.add_warning(location, WhackDiagnosticKind::DuplicateSwitchCase, diagarg![value.clone()]);
+                verifier.related_information.push(RelatedInformation {
+                    location: location.clone(),
+                    related_location: first.clone(),
+                    message: format!("'{value}' is first matched here"),
+                });
+                continue;
+            }
+            if !verifier.switch_case_letter_case_warnings {
+                continue;
+            }
+            let lowercase = value.to_lowercase();
+            if let Some((other, _)) = previous.iter().find(|(v, _)| v.to_lowercase() == lowercase) {
+                verifier.add_warning(location, WhackDiagnosticKind::SwitchCaseDiffersOnlyInLetterCase, diagarg![value.clone(), other.clone()]);
+            }
+        }
+    }
+
+    fn verify_switch_type_stmt(verifier: &mut Subverifier, swstmt: &SwitchTypeStatement) {
+        let host = verifier.host.clone();
+        let discriminant = verifier.verify_expression_or_max_cycles_error(&swstmt.discriminant, &Default::default());
+        if verifier.statement_deferred() {
+            return;
+        }
+        let mut case_types: Vec<Entity> = vec![];
+        let mut has_default = false;
+        for case in swstmt.cases.iter() {
+            if let Some(parameter) = case.parameter.as_ref() {
+                // A parameter without a type annotation matches any value.
+                let (case_type, location) = if let Some(type_annot) = parameter.type_annotation.as_ref() {
+                    (verifier.verify_type_expression(type_annot).ok().flatten(), type_annot.location())
+                } else {
+                    (Some(host.any_type()), parameter.location.clone())
+                };
+                if let Some(case_type) = case_type {
+                    let covering_type = case_types.iter().find(|t| Self::type_covers(&host, &verifier.type_relations, t, &case_type)).cloned();
+                    if let Some(covering_type) = covering_type {
+                        verifier.add_warning(&location, WhackDiagnosticKind::UnreachableSwitchTypeCase, diagarg![case_type, covering_type]);
+                    } else if has_default {
+                        verifier.add_warning(&location, WhackDiagnosticKind::UnreachableSwitchTypeCase, diagarg![case_type, "default".to_owned()]);
+                    } else {
+                        case_types.push(case_type);
+                    }
+                }
+            } else {
+                has_default = true;
+            }
+            Self::verify_block(verifier, &case.block);
+        }
+
+        // A match without a default case must have a case covering the discriminant type.
+        if let Some(discriminant) = discriminant {
+            let discriminant_type = discriminant.static_type(&host).escape_of_non_nullable();
+            if !has_default && !case_types.iter().any(|t| Self::type_covers(&host, &verifier.type_relations, t, &discriminant_type)) {
+                verifier.add_warning(&swstmt.discriminant.location(), WhackDiagnosticKind::NonExhaustiveSwitchType, diagarg![discriminant_type]);
+            }
+        }
+    }
+
+    /// Whether a `switch type` case of type `case_type` matches every value of type `t`.
+    fn type_covers(host: &Database, relations: &TypeRelationCache, case_type: &Entity, t: &Entity) -> bool {
+        let case_type = case_type.escape_of_non_nullable();
+        case_type == host.any_type() || case_type == *t || relations.is_subtype_of(host, t, &case_type).unwrap_or(false)
+    }
+
+    /// Warns about enum members not covered by a `switch` without a `default` case.
+    fn check_exhaustive_enum_switch(verifier: &mut Subverifier, discriminant_type: &Entity, matched_slots: &[Entity], location: &Location) {
+        let enum_type = discriminant_type.escape_of_nullable_or_non_nullable();
+        if !enum_type.is::<EnumType>() {
+            return;
+        }
+        let mut missing = enum_type.enum_member_slot_mapping().borrow().iter()
+            .filter(|(_, slot)| !matched_slots.contains(slot))
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return;
+        }
+        missing.sort();
+        verifier.add_warning(location, WhackDiagnosticKind::NonExhaustiveEnumSwitch, diagarg![enum_type, missing.join(", ")]);
+    }
+
+    /// Returns the element type of the iterator protocol: a type with
+    /// `hasNext()` and `next()` methods (`IIterator`), or a type with an
+    /// `iterator()` method returning such a type (`IIterable`).
+    fn iterator_element_type(host: &Database, t: &Entity) -> Result<Option<Entity>, DeferError> {
+        let mut iterator_t = t.clone();
+        if let Some(sig) = Self::method_signature(host, t, "iterator")? {
+            iterator_t = sig.result_type().escape_of_non_nullable();
+        }
+        if Self::method_signature(host, &iterator_t, "hasNext")?.is_none() {
+            return Ok(None);
+        }
+        let Some(next_sig) = Self::method_signature(host, &iterator_t, "next")? else {
+            return Ok(None);
+        };
+        Ok(Some(next_sig.result_type()))
+    }
+
+    fn method_signature(host: &Database, t: &Entity, name: &str) -> Result<Option<Entity>, DeferError> {
+        if !(t.is::<ClassType>() || t.is::<InterfaceType>() || t.is::<TypeAfterSubstitution>()) {
+            return Ok(None);
+        }
+        let obj = host.factory().create_value(t);
+        let r = PropertyLookup(host).lookup_in_object(&obj, &SharedArray::new(), None, &PropertyLookupKey::LocalName(name.to_owned()), true);
+        match r {
+            Ok(Some(r)) => {
+                if r.is::<FixtureReferenceValue>() && r.property().is::<MethodSlot>() {
+                    Ok(Some(r.property().signature(host).defer()?))
+                } else {
+                    Ok(None)
+                }
+            },
+            Err(PropertyLookupError::Defer) => Err(DeferError(None)),
+            _ => Ok(None),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,130 @@
+use crate::ns::*;
+
+/// Reports static initializers reading statics of another class whose
+/// initialization cannot be proven to precede them.
+///
+/// A dependency exists from a class to another when a static variable
+/// initializer or a statement of its class block reads a static
+/// variable of the other class. A read is reported when the classes
+/// depend on each other, directly or transitively; reads of base class
+/// statics are not, since base classes initialize first.
+pub(crate) struct StaticInitializationSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Static reads by class, with the read class, location and name.
+    dependencies: HashMap<Entity, Vec<(Entity, Location, String)>>,
+    /// Classes in order of definition.
+    classes: Vec<Entity>,
+}
+
+impl<'a> StaticInitializationSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, dependencies: HashMap::new(), classes: vec![] };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+        let host = subverifier.verifier.host.clone();
+        for class in subverifier.classes.clone() {
+            let Some(reads) = subverifier.dependencies.get(&class).cloned() else {
+                continue;
+            };
+            for (other, location, name) in reads {
+                if subverifier.verifier.type_relations.is_subtype_of(&host, &class, &other).unwrap_or(false) {
+                    continue;
+                }
+                if subverifier.reaches(&other, &class) {
+                    subverifier.verifier.add_warning(&location, WhackDiagnosticKind::StaticInitializationOrderDependency, diagarg![class.clone(), other, name]);
+                }
+            }
+        }
+    }
+
+    fn reaches(&self, from: &Entity, to: &Entity) -> bool {
+        let mut visited = HashSet::<Entity>::new();
+        let mut pending = vec![from.clone()];
+        while let Some(class) = pending.pop() {
+            if class == *to {
+                return true;
+            }
+            if !visited.insert(class.clone()) {
+                continue;
+            }
+            for (other, _, _) in self.dependencies.get(&class).into_iter().flatten() {
+                pending.push(other.clone());
+            }
+        }
+        false
+    }
+
+    fn collect_class(&mut self, drtv: &Rc<Directive>, defn: &ClassDefinition) {
+        let host = self.verifier.host.clone();
+        let Some(class) = host.node_mapping().get(drtv).filter(|c| c.is::<ClassType>()) else {
+            return;
+        };
+        let mut collector = StaticReadCollector { host: host.clone(), class: class.clone(), reads: vec![] };
+        for drtv in defn.block.directives.iter() {
+            match drtv.as_ref() {
+                Directive::VariableDefinition(vardefn) => {
+                    if Attribute::find_static(&vardefn.attributes).is_none() {
+                        continue;
+                    }
+                    for binding in vardefn.bindings.iter() {
+                        if let Some(init) = binding.initializer.as_ref() {
+                            collector.visit_expression(init);
+                        }
+                    }
+                },
+                Directive::FunctionDefinition(_) |
+                Directive::ClassDefinition(_) |
+                Directive::EnumDefinition(_) |
+                Directive::InterfaceDefinition(_) |
+                Directive::TypeDefinition(_) |
+                Directive::NamespaceDefinition(_) => {},
+                _ => {
+                    collector.visit_directive(drtv);
+                },
+            }
+        }
+        self.classes.push(class.clone());
+        self.dependencies.insert(class, collector.reads);
+    }
+}
+
+impl<'a> Visitor for StaticInitializationSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::ClassDefinition(defn) = drtv.as_ref() {
+            self.collect_class(drtv, defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
+
+/// Collects reads of static variables of other classes, outside
+/// nested functions.
+struct StaticReadCollector {
+    host: Rc<Database>,
+    class: Entity,
+    reads: Vec<(Entity, Location, String)>,
+}
+
+impl Visitor for StaticReadCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if !matches!(drtv.as_ref(), Directive::FunctionDefinition(_)) {
+            walk_directive(self, drtv);
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if matches!(exp.as_ref(), Expression::Function(_)) {
+            return;
+        }
+        if matches!(exp.as_ref(), Expression::Member(_) | Expression::QualifiedIdentifier(_)) {
+            let slot = self.host.node_mapping().get(exp).filter(|r| r.is::<StaticReferenceValue>()).map(|r| r.property());
+            if let Some(slot) = slot.filter(|s| s.is::<VariableSlot>()) {
+                if let Some(other) = slot.parent().filter(|p| p.is::<ClassType>() && *p != self.class) {
+                    self.reads.push((other, exp.location(), slot.name().local_name()));
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
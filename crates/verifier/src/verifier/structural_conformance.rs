@@ -0,0 +1,46 @@
+use crate::ns::*;
+
+/// Checks whether classes satisfy interfaces member by member,
+/// regardless of their `implements` clause.
+///
+/// Interfaces without members are satisfied by every class.
+///
+/// ```ignore
+/// if StructuralConformance(&host).structurally_conforms(&class, &itrfc)? {
+///     // class could declare `implements` itrfc
+/// }
+/// ```
+pub struct StructuralConformance<'a>(pub &'a Database);
+
+impl<'a> StructuralConformance<'a> {
+    pub fn structurally_conforms(&self, class: &Entity, interface: &Entity) -> Result<bool, DeferError> {
+        Ok(InterfaceImplement(self.0).verify(class, interface)?.is_empty())
+    }
+
+    /// Interfaces of a list that a class satisfies without
+    /// implementing them, directly or through a base class.
+    pub fn missing_implements(&self, class: &Entity, interfaces: &[Entity]) -> Result<Vec<Entity>, DeferError> {
+        let mut r = vec![];
+        for interface in interfaces.iter() {
+            if class.is_subtype_of(interface, self.0)? {
+                continue;
+            }
+            if self.structurally_conforms(class, interface)? {
+                r.push(interface.clone());
+            }
+        }
+        Ok(r)
+    }
+
+    /// Resolves a public interface by its fully qualified name,
+    /// such as `flash.events.IEventDispatcher`.
+    pub fn resolve_interface(&self, fully_qualified_name: &str) -> Option<Entity> {
+        let host = self.0;
+        let (pckg, name) = match fully_qualified_name.rsplit_once('.') {
+            Some((pckg_name, name)) => (host.factory().create_package(pckg_name.split('.').collect::<Vec<_>>()), name),
+            None => (host.top_level_package(), fully_qualified_name),
+        };
+        let qname = host.factory().create_qname(&pckg.public_ns()?, name.to_owned());
+        pckg.properties(host).get(&qname).filter(|t| t.is::<InterfaceType>())
+    }
+}
\ No newline at end of file
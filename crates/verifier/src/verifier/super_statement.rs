@@ -0,0 +1,114 @@
+use crate::ns::*;
+
+/// Verifies the placement of `super()` statements.
+///
+/// A `super()` statement may only appear in a class constructor, at
+/// most once, and `this` may not be used by the statements preceding
+/// it. A constructor without a `super()` statement calls the base
+/// constructor implicitly at its start.
+pub(crate) struct SuperStatementSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> SuperStatementSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_function_common(&mut self, common: &Rc<FunctionCommon>, is_constructor: bool) {
+        let Some(FunctionBody::Block(block)) = common.body.as_ref() else {
+            return;
+        };
+        let mut collector = SuperStatementCollector { supers: vec![], this_uses: vec![] };
+        collector.visit_block(block);
+        if !is_constructor {
+            for location in collector.supers {
+                self.verifier.add_verify_error(&location, WhackDiagnosticKind::SuperStatementOutsideConstructor, diagarg![]);
+            }
+            return;
+        }
+        for location in collector.supers.iter().skip(1) {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::SuperStatementMustBeCalledOnce, diagarg![]);
+        }
+
+        // Uses of this in the statements before super()
+        let Some(i) = block.directives.iter().position(|d| matches!(d.as_ref(), Directive::SuperStatement(_))) else {
+            return;
+        };
+        let mut collector = SuperStatementCollector { supers: vec![], this_uses: vec![] };
+        for drtv in block.directives[..i].iter() {
+            collector.visit_directive(drtv);
+        }
+        for location in collector.this_uses {
+            self.verifier.add_verify_error(&location, WhackDiagnosticKind::ThisUsedBeforeSuperStatement, diagarg![]);
+        }
+    }
+}
+
+impl<'a> Visitor for SuperStatementSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::ClassDefinition(defn) => {
+                for drtv in defn.block.directives.iter() {
+                    if let Directive::FunctionDefinition(fndefn) = drtv.as_ref() {
+                        let is_constructor = matches!(fndefn.name, FunctionName::Constructor(_));
+                        self.verify_function_common(&fndefn.common, is_constructor);
+                        walk_function_common(self, &fndefn.common);
+                    } else {
+                        self.visit_directive(drtv);
+                    }
+                }
+            },
+            Directive::FunctionDefinition(defn) => {
+                self.verify_function_common(&defn.common, false);
+                walk_directive(self, drtv);
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Function(function) = exp.as_ref() {
+            self.verify_function_common(&function.common, false);
+        }
+        walk_expression(self, exp);
+    }
+}
+
+/// Collects `super()` statements and uses of `this` outside nested functions.
+struct SuperStatementCollector {
+    supers: Vec<Location>,
+    this_uses: Vec<Location>,
+}
+
+impl Visitor for SuperStatementCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(_) => {},
+            Directive::SuperStatement(supstmt) => {
+                self.supers.push(supstmt.location.clone());
+                walk_directive(self, drtv);
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::ThisLiteral(literal) => {
+                self.this_uses.push(literal.location.clone());
+            },
+            _ => {
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use crate::ns::*;
+
+/// Reports self-recursive calls within `[TailCall]` functions that
+/// cannot be converted into a loop by codegen.
+pub(crate) struct TailCallSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> TailCallSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_function_defn(&mut self, defn: &FunctionDefinition) {
+        if !Attribute::find_metadata(&defn.attributes).iter().any(|m| m.name.0 == "TailCall") {
+            return;
+        }
+        let FunctionName::Identifier(name) = &defn.name else {
+            return;
+        };
+        for call in CodegenTailCalls::self_calls(&name.0, &defn.common) {
+            if !call.is_tail {
+                self.verifier.add_warning(&call.call.location(), WhackDiagnosticKind::TailCallNotConverted, diagarg![name.0.clone()]);
+            }
+        }
+    }
+}
+
+impl<'a> Visitor for TailCallSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::FunctionDefinition(defn) = drtv.as_ref() {
+            self.verify_function_defn(defn);
+        }
+        walk_directive(self, drtv);
+    }
+}
\ No newline at end of file
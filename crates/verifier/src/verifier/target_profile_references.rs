@@ -0,0 +1,51 @@
+use crate::ns::*;
+
+/// Reports references to package definitions of packages absent from
+/// `CompilerOptions::target_profile`, as `flash.display.Sprite` or a
+/// name imported through `flash.display.*`. Imports of such packages
+/// are reported by the import directive itself.
+///
+/// Sources within an unavailable package, as library stubs, may refer
+/// to its definitions.
+pub(crate) struct TargetProfileSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    profile: TargetProfile,
+}
+
+impl<'a> TargetProfileSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], profile: TargetProfile) {
+        if profile.unavailable_packages().is_empty() {
+            return;
+        }
+        let mut subverifier = Self { verifier, profile };
+        for program in programs.iter() {
+            for pckgdef in program.packages.iter() {
+                let name = pckgdef.name.iter().map(|name| name.0.as_str()).collect::<Vec<_>>().join(".");
+                if subverifier.profile.is_package_available(&name) {
+                    subverifier.visit_block(&pckgdef.block);
+                }
+            }
+            for drtv in program.directives.iter() {
+                subverifier.visit_directive(drtv);
+            }
+        }
+    }
+
+    fn verify_reference(&mut self, definition: &Entity, location: &Location) {
+        let Some(package) = definition.parent().filter(|p| p.is::<Package>()) else {
+            return;
+        };
+        if !self.profile.is_package_available(&package.fully_qualified_name_list().join(".")) {
+            self.verifier.add_verify_error(location, WhackDiagnosticKind::UnavailableInTargetProfile, diagarg![definition.to_string(), self.profile.name().to_owned()]);
+        }
+    }
+}
+
+impl<'a> Visitor for TargetProfileSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Some(definition) = referenced_entity(&self.verifier.host, exp) {
+            self.verify_reference(&definition, &exp.location());
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use crate::ns::*;
+
+/// Warns on references to block-scoped bindings preceding their
+/// declaration in the same function. Block-scoped bindings are the
+/// variables of blocks and `for` headings, each given a scope of its
+/// own, and constants anywhere within a function. Such references are
+/// valid ActionScript, reading the default value of the binding, and
+/// thus only warned on.
+///
+/// References from nested functions are not reported, as the function
+/// may only be called after the declaration.
+pub(crate) struct TemporalDeadZoneSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Locations of the enclosing functions.
+    functions: Vec<Location>,
+}
+
+impl<'a> TemporalDeadZoneSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, functions: vec![] };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn is_block_scoped(host: &Database, slot: &Entity) -> bool {
+        let Some(parent) = slot.parent() else {
+            return false;
+        };
+        if parent.is::<Activation>() {
+            slot.read_only(host)
+        } else {
+            parent.is::<Scope>() && !parent.is::<FixtureScope>()
+        }
+    }
+
+    fn verify_reference(&mut self, exp: &Rc<Expression>, id: &QualifiedIdentifier) {
+        let Some(r) = self.verifier.host.node_mapping().get(exp) else {
+            return;
+        };
+        if !(r.is::<FixtureReferenceValue>() || r.is::<ScopeReferenceValue>()) {
+            return;
+        }
+        let slot = r.property();
+        if !slot.is::<VariableSlot>() || !Self::is_block_scoped(&self.verifier.host, &slot) {
+            return;
+        }
+        let Some(declaration) = slot.location() else {
+            return;
+        };
+        if !Rc::ptr_eq(&declaration.compilation_unit(), &id.location.compilation_unit())
+        || declaration.first_offset() <= id.location.first_offset() {
+            return;
+        }
+        // Declared by an enclosing function
+        if let Some(function) = self.functions.last() {
+            if declaration.first_offset() < function.first_offset() || declaration.first_offset() > function.last_offset() {
+                return;
+            }
+        }
+        let name = slot.name().local_name();
+        self.verifier.add_warning(&id.location, WhackDiagnosticKind::UseBeforeDeclaration, diagarg![name.clone()]);
+        self.verifier.related_information.push(RelatedInformation {
+            location: id.location.clone(),
+            related_location: declaration,
+            message: format!("'{name}' is declared here"),
+        });
+    }
+}
+
+impl<'a> Visitor for TemporalDeadZoneSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::FunctionDefinition(defn) = drtv.as_ref() {
+            self.functions.push(defn.location.clone());
+            walk_directive(self, drtv);
+            self.functions.pop();
+            return;
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::QualifiedIdentifier(id) if id.qualifier.is_none() && !id.attribute => {
+                self.verify_reference(exp, id);
+                walk_expression(self, exp);
+            },
+            Expression::Function(function) => {
+                self.functions.push(function.location.clone());
+                walk_expression(self, exp);
+                self.functions.pop();
+            },
+            _ => walk_expression(self, exp),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,172 @@
+use crate::ns::*;
+
+/// Reads `[Throws(type="T")]` meta-data from methods.
+///
+/// A method may carry several `[Throws]` entries, one per documented
+/// error type. Type names are either local names resolved from the
+/// method's scope or fully qualified names.
+pub struct ThrowsMetadata;
+
+impl ThrowsMetadata {
+    /// Returns the type names declared by the `[Throws]` meta-data of
+    /// a method slot, in declaration order, for hover and documentation.
+    pub fn declared_types(slot: &Entity) -> Vec<String> {
+        Self::declared_types_in(&slot.metadata().iter().collect::<Vec<_>>())
+    }
+
+    pub(crate) fn declared_types_in(metadata: &[Rc<Metadata>]) -> Vec<String> {
+        let mut types = vec![];
+        for m in metadata.iter().filter(|m| m.name.0 == "Throws") {
+            for entry in m.entries.iter().flatten() {
+                if entry.key.as_ref().map(|(k, _)| k == "type").unwrap_or(false) {
+                    types.push(match entry.value.as_ref() {
+                        MetadataValue::String(val) => val.0.clone(),
+                        MetadataValue::IdentifierString(val) => val.0.clone(),
+                    });
+                }
+            }
+        }
+        types
+    }
+}
+
+/// Verifies `[Throws]` meta-data of methods.
+///
+/// Declared types must be `Error` subtypes. When the respective
+/// warning is enabled, `throw` statements outside `try` statements
+/// with catch clauses whose operand is not covered by a declared type
+/// are reported for methods that declare `[Throws]` meta-data.
+pub(crate) struct ThrowsMetadataSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    warn_undeclared: bool,
+    error_class: Entity,
+    /// Scopes from which type names are resolved.
+    scope_stack: Vec<Entity>,
+}
+
+impl<'a> ThrowsMetadataSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>], warn_undeclared: bool) {
+        let host = verifier.host.clone();
+        let pckg = host.top_level_package();
+        let qname = host.factory().create_qname(&pckg.public_ns().unwrap(), "Error".to_owned());
+        // Without the core library there is nothing to check against
+        let Some(error_class) = pckg.properties(&host).get(&qname).filter(|t| t.is::<ClassType>()) else {
+            return;
+        };
+        let mut subverifier = Self { verifier, warn_undeclared, error_class, scope_stack: vec![] };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn resolve_type(&self, name: &str) -> Option<Entity> {
+        let host = self.verifier.host.clone();
+        if let Some((pckg_name, local_name)) = name.rsplit_once('.') {
+            let pckg = host.factory().create_package(pckg_name.split('.').collect::<Vec<_>>());
+            let qname = host.factory().create_qname(&pckg.public_ns()?, local_name.to_owned());
+            return pckg.properties(&host).get(&qname).filter(|t| t.is_class_type_possibly_after_sub());
+        }
+        let scope = self.scope_stack.last()?;
+        let r = scope.lookup_in_scope_chain(&host, None, &PropertyLookupKey::LocalName(name.to_owned())).ok()??;
+        r.expect_type().ok()
+    }
+
+    fn is_error_type(&self, t: &Entity) -> bool {
+        *t == self.error_class || self.verifier.type_relations.is_subtype_of(&self.verifier.host, t, &self.error_class).unwrap_or(false)
+    }
+
+    fn verify_function_defn(&mut self, drtv: &Rc<Directive>, defn: &FunctionDefinition) {
+        let metadata = Attribute::find_metadata(&defn.attributes);
+        let Some(location) = metadata.iter().find(|m| m.name.0 == "Throws").map(|m| m.location.clone()) else {
+            return;
+        };
+        let mut declared = vec![];
+        for name in ThrowsMetadata::declared_types_in(&metadata) {
+            let Some(t) = self.resolve_type(&name) else {
+                self.verifier.add_verify_error(&location, WhackDiagnosticKind::ThrowsMetadataTypeNotFound, diagarg![name]);
+                continue;
+            };
+            if !self.is_error_type(&t) {
+                self.verifier.add_verify_error(&location, WhackDiagnosticKind::ThrowsMetadataTypeMustBeError, diagarg![t]);
+                continue;
+            }
+            declared.push(t);
+        }
+        if !self.warn_undeclared {
+            return;
+        }
+        let Some(FunctionBody::Block(block)) = defn.common.body.as_ref() else {
+            return;
+        };
+        let mut collector = UncaughtThrowCollector {
+            host: self.verifier.host.clone(),
+            try_depth: 0,
+            throws: vec![],
+            callees: vec![],
+        };
+        collector.visit_block(block);
+        let name = self.verifier.host.node_mapping().get(drtv).map(|slot| slot.name().local_name()).unwrap_or("<anonymous>".into());
+        for (location, exp) in collector.throws {
+            let Some(t) = self.verifier.host.node_mapping().get(&exp).map(|v| v.static_type(&self.verifier.host)) else {
+                continue;
+            };
+            if !self.is_error_type(&t) {
+                continue;
+            }
+            let covered = declared.iter().any(|d| t == *d || self.verifier.type_relations.is_subtype_of(&self.verifier.host, &t, d).unwrap_or(false));
+            if !covered {
+                self.verifier.add_warning(&location, WhackDiagnosticKind::UndeclaredThrownType, diagarg![name.clone(), t]);
+            }
+        }
+    }
+
+    fn visit_scoped_block(&mut self, block: &Rc<Block>) {
+        let scope = self.verifier.host.node_mapping().get(block);
+        if let Some(scope) = scope.as_ref() {
+            self.scope_stack.push(scope.clone());
+        }
+        self.visit_block(block);
+        if scope.is_some() {
+            self.scope_stack.pop();
+        }
+    }
+}
+
+impl<'a> Visitor for ThrowsMetadataSubverifier<'a> {
+    fn visit_program(&mut self, program: &Rc<Program>) {
+        let activation = self.verifier.host.node_mapping().get(program);
+        if let Some(activation) = activation.as_ref() {
+            self.scope_stack.push(activation.clone());
+        }
+        for pckgdef in program.packages.iter() {
+            self.visit_scoped_block(&pckgdef.block);
+        }
+        for drtv in program.directives.iter() {
+            self.visit_directive(drtv);
+        }
+        if activation.is_some() {
+            self.scope_stack.pop();
+        }
+    }
+
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            Directive::FunctionDefinition(defn) => {
+                self.verify_function_defn(drtv, defn);
+                walk_directive(self, drtv);
+            },
+            Directive::ClassDefinition(defn) => {
+                self.visit_scoped_block(&defn.block);
+            },
+            Directive::EnumDefinition(defn) => {
+                self.visit_scoped_block(&defn.block);
+            },
+            Directive::InterfaceDefinition(defn) => {
+                self.visit_scoped_block(&defn.block);
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+}
\ No newline at end of file
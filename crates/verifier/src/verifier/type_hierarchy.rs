@@ -0,0 +1,150 @@
+use crate::ns::*;
+
+/// A type or method found by a `TypeHierarchy` query.
+#[derive(Clone)]
+pub struct HierarchyItem {
+    pub entity: Entity,
+    /// Location of the definition, or `None` for library definitions.
+    pub location: Option<Location>,
+    /// Distance from the queried entity, one for a direct supertype,
+    /// subtype or override.
+    pub depth: usize,
+}
+
+impl HierarchyItem {
+    fn new(entity: &Entity, depth: usize) -> Self {
+        Self { entity: entity.clone(), location: entity.location(), depth }
+    }
+}
+
+/// Queries over the inheritance of verified classes and interfaces, for
+/// type hierarchy requests and the inheritance sections of documentation.
+///
+/// ```ignore
+/// let hierarchy = TypeHierarchy(&host);
+/// for item in hierarchy.overrides(&method) {
+///     // item.location is where the override is defined
+/// }
+/// ```
+///
+/// Subtypes are found through the known subclasses and implementors the
+/// verifier records, so only verified definitions are found. Interfaces
+/// extending an interface are not recorded and are not listed as its
+/// subtypes.
+pub struct TypeHierarchy<'a>(pub &'a Database);
+
+impl<'a> TypeHierarchy<'a> {
+    /// Base classes and implemented interfaces of a class, or extended
+    /// interfaces of an interface, nearest first.
+    pub fn supertypes(&self, t: &Entity) -> Vec<HierarchyItem> {
+        let host = self.0;
+        let mut r: Vec<HierarchyItem> = vec![];
+        let mut pending = vec![(t.clone(), 0)];
+        while !pending.is_empty() {
+            let (t, depth) = pending.remove(0);
+            let mut direct = vec![];
+            if t.is::<ClassType>() {
+                direct.extend(t.extends_class(host));
+                direct.extend(t.implements(host).iter());
+            } else if t.is::<InterfaceType>() {
+                direct.extend(t.extends_interfaces(host).iter());
+            }
+            for supertype in direct {
+                if supertype.is::<UnresolvedEntity>() || r.iter().any(|item| item.entity == supertype) {
+                    continue;
+                }
+                r.push(HierarchyItem::new(&supertype, depth + 1));
+                pending.push((supertype, depth + 1));
+            }
+        }
+        r
+    }
+
+    /// Classes extending a class, directly or not, or classes
+    /// implementing an interface, as by `implementors()`.
+    pub fn subtypes(&self, t: &Entity) -> Vec<HierarchyItem> {
+        if t.is::<InterfaceType>() {
+            return self.implementors(t);
+        }
+        let mut r = vec![];
+        self.add_subclasses(t, 1, &mut r);
+        r
+    }
+
+    /// Classes implementing an interface, including subclasses of those
+    /// declaring it in their `implements` clause.
+    pub fn implementors(&self, interface: &Entity) -> Vec<HierarchyItem> {
+        let mut r: Vec<HierarchyItem> = vec![];
+        for class in interface.known_implementors().iter() {
+            if r.iter().any(|item| item.entity == class) {
+                continue;
+            }
+            r.push(HierarchyItem::new(&class, 1));
+            self.add_subclasses(&class, 2, &mut r);
+        }
+        r
+    }
+
+    fn add_subclasses(&self, class: &Entity, depth: usize, r: &mut Vec<HierarchyItem>) {
+        for subclass in class.known_subclasses().iter() {
+            if r.iter().any(|item| item.entity == subclass) {
+                continue;
+            }
+            r.push(HierarchyItem::new(&subclass, depth));
+            self.add_subclasses(&subclass, depth + 1, r);
+        }
+    }
+
+    /// Methods and accessors overriding a method or accessor, or
+    /// implementing one of an interface.
+    pub fn overrides(&self, method: &Entity) -> Vec<HierarchyItem> {
+        let host = self.0;
+        let Some(parent) = method.parent() else {
+            return vec![];
+        };
+        let is_interface = parent.is::<InterfaceType>();
+        let mut r = vec![];
+        for subtype in self.subtypes(&parent) {
+            let member = subtype.entity.prototype(host).borrow().iter()
+                .find(|(name, member)| {
+                    (is_interface || member.is_overriding()) && name.local_name() == method.name().local_name()
+                        && Self::same_namespace(&name.namespace(), &method.name().namespace(), is_interface)
+                })
+                .map(|(_, member)| member.clone());
+            if let Some(member) = member {
+                r.push(HierarchyItem::new(&member, subtype.depth));
+            }
+        }
+        r
+    }
+
+    /// Method or accessor of a base class that a method or accessor
+    /// overrides.
+    pub fn overridden(&self, method: &Entity) -> Option<Entity> {
+        let host = self.0;
+        if !method.is_overriding() {
+            return None;
+        }
+        let mut class = method.parent()?.extends_class(host);
+        while let Some(base) = class.filter(|c| !c.is::<UnresolvedEntity>()) {
+            let member = base.prototype(host).borrow().iter()
+                .find(|(name, _)| name.local_name() == method.name().local_name() && Self::same_namespace(&name.namespace(), &method.name().namespace(), false))
+                .map(|(_, member)| member.clone());
+            if member.is_some() {
+                return member;
+            }
+            class = base.extends_class(host);
+        }
+        None
+    }
+
+    /// Whether an overriding member's namespace matches the overridden
+    /// one. Each class has its own `protected` namespace, and interface
+    /// members are implemented as public members.
+    fn same_namespace(a: &Entity, b: &Entity, is_interface: bool) -> bool {
+        if is_interface {
+            return a.is_public_ns();
+        }
+        a == b || (matches!(a.system_ns_kind(), Some(SystemNamespaceKind::Protected)) && matches!(b.system_ns_kind(), Some(SystemNamespaceKind::Protected)))
+    }
+}
\ No newline at end of file
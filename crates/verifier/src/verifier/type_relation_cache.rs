@@ -0,0 +1,88 @@
+use crate::ns::*;
+use std::cell::{Cell, RefCell};
+
+/// Memoizes type relationship queries, which are made repeatedly per
+/// expression and walk the whole class hierarchy.
+///
+/// Only answered queries are cached; deferred ones are retried next
+/// time. The verifier clears the cache on every cycle of directive
+/// verification, while definitions may still change.
+#[derive(Default)]
+pub struct TypeRelationCache {
+    subtypes: RefCell<HashMap<(Entity, Entity), bool>>,
+    common_supertypes: RefCell<HashMap<(Entity, Entity), Entity>>,
+    implicit_coercions: RefCell<HashMap<(Entity, Entity), bool>>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+}
+
+impl TypeRelationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `t` is a subtype of `base`.
+    pub fn is_subtype_of(&self, host: &Database, t: &Entity, base: &Entity) -> Result<bool, DeferError> {
+        let key = (t.clone(), base.clone());
+        if let Some(r) = self.subtypes.borrow().get(&key) {
+            self.hits.set(self.hits.get() + 1);
+            return Ok(*r);
+        }
+        self.misses.set(self.misses.get() + 1);
+        let r = t.is_subtype_of(base, host)?;
+        self.subtypes.borrow_mut().insert(key, r);
+        Ok(r)
+    }
+
+    /// The nearest class both types derive from, or `*` if
+    /// either is not a class.
+    pub fn common_supertype(&self, host: &Database, a: &Entity, b: &Entity) -> Result<Entity, DeferError> {
+        if a == b {
+            return Ok(a.clone());
+        }
+        let key = (a.clone(), b.clone());
+        if let Some(r) = self.common_supertypes.borrow().get(&key) {
+            self.hits.set(self.hits.get() + 1);
+            return Ok(r.clone());
+        }
+        self.misses.set(self.misses.get() + 1);
+        let mut r = host.any_type();
+        if a.is::<ClassType>() && b.is::<ClassType>() {
+            let mut ancestor = Some(a.clone());
+            while let Some(c) = ancestor {
+                if c == *b || self.is_subtype_of(host, b, &c)? {
+                    r = c;
+                    break;
+                }
+                ancestor = c.extends_class(host);
+            }
+        }
+        self.common_supertypes.borrow_mut().insert(key, r.clone());
+        Ok(r)
+    }
+
+    /// Whether a value of type `from_type` implicitly converts to `to_type`.
+    pub fn implicitly_coercible(&self, host: &Database, from_type: &Entity, to_type: &Entity) -> Result<bool, DeferError> {
+        let key = (from_type.clone(), to_type.clone());
+        if let Some(r) = self.implicit_coercions.borrow().get(&key) {
+            self.hits.set(self.hits.get() + 1);
+            return Ok(*r);
+        }
+        self.misses.set(self.misses.get() + 1);
+        let value = host.factory().create_value(from_type);
+        let r = ConversionMethods(host).implicit(&value, to_type, false)?.is_some();
+        self.implicit_coercions.borrow_mut().insert(key, r);
+        Ok(r)
+    }
+
+    pub fn invalidate(&self) {
+        self.subtypes.borrow_mut().clear();
+        self.common_supertypes.borrow_mut().clear();
+        self.implicit_coercions.borrow_mut().clear();
+    }
+
+    /// Number of queries answered from and missing the cache.
+    pub fn statistics(&self) -> (usize, usize) {
+        (self.hits.get(), self.misses.get())
+    }
+}
\ No newline at end of file
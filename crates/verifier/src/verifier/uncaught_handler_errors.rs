@@ -0,0 +1,171 @@
+use crate::ns::*;
+
+/// Reports `throw` statements reachable from event handlers without an
+/// enclosing `try` statement with catch clauses.
+///
+/// Handlers are the functions passed to `addEventListener()`, either as
+/// function expressions or as references to methods and package-level
+/// functions. Calls to known functions outside `try` statements are
+/// followed. Handlers given in MXML event attributes are not covered,
+/// since MXML is not yet compiled into ActionScript.
+pub(crate) struct UncaughtHandlerErrorSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+    /// Function definitions by method slot.
+    functions: HashMap<Entity, Rc<FunctionCommon>>,
+    /// Handlers in order of registration, resolved once every function
+    /// definition is collected, since a handler may be registered before
+    /// the definition of its method is visited.
+    handlers: Vec<RegisteredHandler>,
+}
+
+enum RegisteredHandler {
+    Function(String, Rc<FunctionCommon>),
+    Slot(Entity),
+}
+
+impl<'a> UncaughtHandlerErrorSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier, functions: HashMap::new(), handlers: vec![] };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+        let handlers: Vec<(String, Rc<FunctionCommon>)> = std::mem::take(&mut subverifier.handlers).into_iter().filter_map(|handler| match handler {
+            RegisteredHandler::Function(name, common) => Some((name, common)),
+            RegisteredHandler::Slot(slot) => subverifier.functions.get(&slot).map(|common| (slot.name().local_name(), common.clone())),
+        }).collect();
+        let mut reported = HashSet::<(usize, String)>::new();
+        for (name, common) in handlers.iter() {
+            let mut visited = HashSet::<NodeAsKey<Rc<FunctionCommon>>>::new();
+            let mut throws = vec![];
+            subverifier.collect_uncaught_throws(common, &mut visited, &mut throws);
+            for (location, _) in throws {
+                let file_path = location.compilation_unit().file_path().unwrap_or_default();
+                if reported.insert((location.first_offset(), format!("{file_path}:{name}"))) {
+                    subverifier.verifier.add_warning(&location, WhackDiagnosticKind::UncaughtErrorInEventHandler, diagarg![name.clone()]);
+                }
+            }
+        }
+    }
+
+    fn collect_uncaught_throws(&self, common: &Rc<FunctionCommon>, visited: &mut HashSet<NodeAsKey<Rc<FunctionCommon>>>, throws: &mut Vec<(Location, Rc<Expression>)>) {
+        if !visited.insert(NodeAsKey(common.clone())) {
+            return;
+        }
+        let mut collector = UncaughtThrowCollector {
+            host: self.verifier.host.clone(),
+            try_depth: 0,
+            throws: vec![],
+            callees: vec![],
+        };
+        if let Some(FunctionBody::Block(block)) = common.body.as_ref() {
+            collector.visit_block(block);
+        }
+        throws.extend(collector.throws);
+        for callee in collector.callees {
+            if let Some(callee) = self.functions.get(&callee) {
+                self.collect_uncaught_throws(callee, visited, throws);
+            }
+        }
+    }
+
+    fn register_handler(&mut self, handler: &Rc<Expression>) {
+        if let Expression::Function(function) = handler.as_ref() {
+            let name = function.name.as_ref().map(|name| name.0.clone()).unwrap_or("<anonymous>".into());
+            self.handlers.push(RegisteredHandler::Function(name, function.common.clone()));
+            return;
+        }
+        let Some(slot) = self.verifier.host.node_mapping().get(handler).filter(|r| r.is::<FixtureReferenceValue>() || r.is::<ScopeReferenceValue>()).map(|r| r.property()) else {
+            return;
+        };
+        self.handlers.push(RegisteredHandler::Slot(slot));
+    }
+}
+
+impl<'a> Visitor for UncaughtHandlerErrorSubverifier<'a> {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        if let Directive::FunctionDefinition(defn) = drtv.as_ref() {
+            if let Some(slot) = self.verifier.host.node_mapping().get(drtv) {
+                self.functions.insert(slot, defn.common.clone());
+            }
+        }
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            let is_add_event_listener = match call.base.as_ref() {
+                Expression::Member(member) => member.identifier.to_identifier_name_or_asterisk().map(|(name, _)| name == "addEventListener").unwrap_or(false),
+                Expression::QualifiedIdentifier(id) => matches!(&id.id, QualifiedIdentifierIdentifier::Id((name, _)) if name == "addEventListener"),
+                _ => false,
+            };
+            if is_add_event_listener {
+                if let Some(handler) = call.arguments.get(1) {
+                    self.register_handler(handler);
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
+
+/// Collects the `throw` statements of a function body outside `try`
+/// statements with catch clauses, and the functions it calls outside them.
+pub(crate) struct UncaughtThrowCollector {
+    pub host: Rc<Database>,
+    pub try_depth: usize,
+    /// Locations and operands of `throw` statements.
+    pub throws: Vec<(Location, Rc<Expression>)>,
+    pub callees: Vec<Entity>,
+}
+
+impl Visitor for UncaughtThrowCollector {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        match drtv.as_ref() {
+            // Nested functions run at another moment
+            Directive::FunctionDefinition(_) => {},
+            Directive::ThrowStatement(tstmt) => {
+                if self.try_depth == 0 {
+                    self.throws.push((tstmt.location.clone(), tstmt.expression.clone()));
+                }
+                walk_directive(self, drtv);
+            },
+            Directive::TryStatement(trystmt) => {
+                let catches = !trystmt.catch_clauses.is_empty();
+                if catches {
+                    self.try_depth += 1;
+                }
+                self.visit_block(&trystmt.block);
+                if catches {
+                    self.try_depth -= 1;
+                }
+                for catch_clause in trystmt.catch_clauses.iter() {
+                    self.visit_block(&catch_clause.block);
+                }
+                if let Some(finally_clause) = trystmt.finally_clause.as_ref() {
+                    self.visit_block(&finally_clause.block);
+                }
+            },
+            _ => {
+                walk_directive(self, drtv);
+            },
+        }
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        match exp.as_ref() {
+            Expression::Function(_) => {},
+            Expression::Call(call) => {
+                if self.try_depth == 0 {
+                    let callee = self.host.node_mapping().get(&call.base).filter(|r| r.is::<FixtureReferenceValue>() || r.is::<ScopeReferenceValue>()).map(|r| r.property());
+                    if let Some(callee) = callee.filter(|p| p.is::<MethodSlot>()) {
+                        self.callees.push(callee);
+                    }
+                }
+                walk_expression(self, exp);
+            },
+            _ => {
+                walk_expression(self, exp);
+            },
+        }
+    }
+}
\ No newline at end of file
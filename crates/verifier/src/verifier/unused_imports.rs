@@ -0,0 +1,28 @@
+use crate::ns::*;
+
+/// Warns about import directives not used during verification.
+pub(crate) struct UnusedImportSubverifier;
+
+impl UnusedImportSubverifier {
+    pub fn verify_programs(verifier: &mut Subverifier, programs: &[Rc<Program>]) {
+        for program in programs.iter() {
+            for pckgdefn in program.packages.iter() {
+                Self::verify_directives(verifier, &pckgdefn.block.directives);
+            }
+            Self::verify_directives(verifier, &program.directives);
+        }
+    }
+
+    fn verify_directives(verifier: &mut Subverifier, list: &[Rc<Directive>]) {
+        for drtv in ImportOrganizer::unused_imports(&verifier.host, list) {
+            let Directive::ImportDirective(impdrtv) = drtv.as_ref() else {
+                continue;
+            };
+            // Unused aliases are reported as unused definitions
+            if impdrtv.alias.is_some() {
+                continue;
+            }
+            verifier.add_warning(&drtv.location(), WhackDiagnosticKind::UnusedImport, diagarg![ImportOrganizer::import_name(impdrtv)]);
+        }
+    }
+}
\ No newline at end of file
@@ -41,12 +41,35 @@ use crate::ns::*;
 /// ```
 pub struct Verifier {
     verifier: Subverifier,
+    timings: CompilerTimings,
+    event_subscriber: Option<Rc<dyn CompilerEventSubscriber>>,
+    lint_rules: Vec<Rc<dyn LintRule>>,
+    member_generators: Vec<Rc<dyn MemberGenerator>>,
+    bindable: Rc<BindableGenerator>,
+    mixins: Rc<MixinGenerator>,
+    pass_manager: PassManager,
+    /// Time spent in each pass of the last verification.
+    pass_durations: HashMap<VerifierPass, std::time::Duration>,
+    /// Whether the embedded files were given by `set_embedded_files()`,
+    /// in which case they are not read from the file system.
+    embedded_files_given: bool,
+    /// Whether the warning levels were given by `set_warning_config()`.
+    warning_config_given: bool,
+    /// Whether the core library was verified into the database, by an
+    /// earlier verification or among the given programs.
+    core_library_verified: bool,
 }
 
 impl Verifier {
     pub(crate) const MAX_CYCLES: usize = 512;
 
+    /// Occurrences of a diagnostic with the same kind and arguments
+    /// reported per file before further ones are suppressed.
+    pub(crate) const MAX_DUPLICATE_DIAGNOSTICS: usize = 10;
+
     pub fn new(host: &Rc<Database>) -> Self {
+        let bindable = Rc::new(BindableGenerator::default());
+        let mixins = Rc::new(MixinGenerator::default());
         Self {
             verifier: Subverifier {
                 host: host.clone(),
@@ -60,11 +83,53 @@ impl Verifier {
                 class_defn_guard: HashMap::new(),
                 itrfc_defn_guard: HashMap::new(),
                 codegen_class_info: SharedMap::new(),
+                resource_bundles: Rc::new(ResourceBundleRegistry::new()),
+                referenced_resource_bundles: HashSet::new(),
+                embedded_files: Rc::new(EmbeddedFiles::new()),
+                embedded_fonts: vec![],
                 invalidated: false,
                 external: false,
+                cancellation_token: None,
+                suggested_fixes: vec![],
+                related_information: vec![],
+                coercion_audit: None,
+                dynamic_access_audit: None,
+                config_elimination: None,
+                scope_probe: None,
+                scope_snapshots: vec![],
+                diagnostic_occurrences: HashMap::new(),
+                target_profile: TargetProfile::Avm2,
+                type_relations: TypeRelationCache::new(),
+                generated_members: HashMap::new(),
+                generated_method_bodies: vec![],
+                operator_overloading: false,
+                promise_like_type: None,
+                switch_case_letter_case_warnings: false,
+                switch_fallthrough_warnings: false,
+                loop_variable_redeclaration_warnings: false,
+                warning_config: None,
+                operator_overloads: vec![],
+                extension_method_calls: vec![],
+                reflected_definitions: vec![],
+                mxml_state_overrides: HashMap::new(),
+                deferred_statements: vec![],
+                statement_deferral: None,
+                statement_deferral_enabled: true,
+                blocker: None,
                 // deferred_counter: 0,
                 scope: None,
             },
+            timings: CompilerTimings::new(),
+            event_subscriber: None,
+            lint_rules: vec![],
+            member_generators: vec![bindable.clone(), mixins.clone()],
+            bindable,
+            mixins,
+            pass_manager: PassManager::new(),
+            pass_durations: HashMap::new(),
+            embedded_files_given: false,
+            warning_config_given: false,
+            core_library_verified: false,
         }
     }
 
@@ -79,15 +144,284 @@ impl Verifier {
         self.verifier.codegen_class_info.clone()
     }
 
+    /// Resource bundles loaded from the locale source paths.
+    pub fn resource_bundles(&self) -> Rc<ResourceBundleRegistry> {
+        self.verifier.resource_bundles.clone()
+    }
+
+    /// Replaces the resource bundles, which are otherwise loaded from the
+    /// locale source paths by `verify_programs()`.
+    pub fn set_resource_bundles(&mut self, registry: Rc<ResourceBundleRegistry>) {
+        self.verifier.resource_bundles = registry;
+    }
+
+    /// Replaces the files embedded by `[Embed]` meta-data, which are
+    /// otherwise read from the file system by `verify_programs()`.
+    pub fn set_embedded_files(&mut self, files: Rc<EmbeddedFiles>) {
+        self.verifier.embedded_files = files;
+        self.embedded_files_given = true;
+    }
+
+    /// Fonts embedded by `[Embed]` meta-data, with their tags.
+    pub fn embedded_fonts(&self) -> &[EmbeddedFont] {
+        &self.verifier.embedded_fonts
+    }
+
+    /// Replaces the per-directory warning levels, which are otherwise
+    /// loaded from the source path by `verify_programs()`.
+    pub fn set_warning_config(&mut self, config: Rc<WarningConfig>) {
+        self.verifier.warning_config = Some(config);
+        self.warning_config_given = true;
+    }
+
+    /// Names of resource bundles referenced by `[ResourceBundle]`
+    /// meta-data or `@Resource` directives, to be embedded into the output.
+    pub fn referenced_resource_bundles(&self) -> &HashSet<String> {
+        &self.verifier.referenced_resource_bundles
+    }
+
+    /// Lists resource bundles and keys missing per locale.
+    pub fn missing_resource_report(&self) -> MissingResourceReport {
+        MissingResourceReport::new(&self.verifier.resource_bundles)
+    }
+
+    /// Timings of the declaration collection and verification phases,
+    /// including per-file durations.
+    pub fn timings(&self) -> &CompilerTimings {
+        &self.timings
+    }
+
+    pub fn timings_mut(&mut self) -> &mut CompilerTimings {
+        &mut self.timings
+    }
+
+    /// Sets the subscriber receiving the start and end of phases.
+    pub fn set_event_subscriber(&mut self, subscriber: Option<Rc<dyn CompilerEventSubscriber>>) {
+        self.event_subscriber = subscriber;
+    }
+
+    /// Sets the token checked between directives and statements to abort
+    /// an in-flight verification.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.verifier.cancellation_token = token;
+    }
+
+    /// Indicates whether the last verification was cancelled, in which
+    /// case its results are incomplete.
+    pub fn cancelled(&self) -> bool {
+        self.verifier.cancelled()
+    }
+
+    /// Fixes suggested for diagnostics reported so far.
+    pub fn suggested_fixes(&self) -> &[SuggestedFix] {
+        &self.verifier.suggested_fixes
+    }
+
+    /// Secondary locations of diagnostics reported so far.
+    pub fn related_information(&self) -> &[RelatedInformation] {
+        &self.verifier.related_information
+    }
+
+    /// Implicit coercions from `*` and `Object` grouped by file, if
+    /// `compiler_options.coercion_audit` was set.
+    pub fn coercion_audit_report(&self) -> Option<&CoercionAuditReport> {
+        self.verifier.coercion_audit.as_ref()
+    }
+
+    /// Property accesses resolved only at runtime grouped by receiver
+    /// type, if `compiler_options.dynamic_access_audit` was set.
+    pub fn dynamic_access_audit_report(&self) -> Option<&DynamicAccessAuditReport> {
+        self.verifier.dynamic_access_audit.as_ref()
+    }
+
+    /// Regions eliminated by configuration constants grouped by file, if
+    /// `compiler_options.config_elimination_report` was set.
+    pub fn config_elimination_report(&self) -> Option<&ConfigEliminationReport> {
+        self.verifier.config_elimination.as_ref()
+    }
+
+    /// Records the scope chain and name resolution of unqualified
+    /// identifiers containing the given position, for debugging
+    /// references that are not found.
+    pub fn set_scope_probe(&mut self, probe: Option<ScopeProbe>) {
+        self.verifier.scope_probe = probe;
+    }
+
+    /// Snapshots recorded for the scope probe.
+    pub fn scope_snapshots(&self) -> &[ScopeSnapshot] {
+        &self.verifier.scope_snapshots
+    }
+
+    /// Selects the passes run by `verify_programs()`.
+    pub fn set_pass_manager(&mut self, pass_manager: PassManager) {
+        self.pass_manager = pass_manager;
+    }
+
+    pub fn pass_manager(&self) -> &PassManager {
+        &self.pass_manager
+    }
+
+    /// Time spent in a pass of the last verification, or `None` if the
+    /// pass did not complete. The first verification includes parsing
+    /// and verifying the core library.
+    pub fn pass_duration(&self, pass: VerifierPass) -> Option<std::time::Duration> {
+        self.pass_durations.get(&pass).cloned()
+    }
+
+    /// Records the duration of a finished pass and notifies the
+    /// observers of the pass manager.
+    fn finish_pass(&mut self, pass: VerifierPass, start: &mut Stopwatch) {
+        self.pass_durations.insert(pass, start.elapsed());
+        *start = Stopwatch::start();
+        let host = self.verifier.host.clone();
+        self.pass_manager.finish(pass, &host);
+    }
+
+    /// Registers a lint rule to run after programs are verified.
+    pub fn register_lint_rule(&mut self, rule: Rc<dyn LintRule>) {
+        self.lint_rules.push(rule);
+    }
+
+    /// Registers a generator contributing members to classes marked with
+    /// its meta-data, run before method bodies are verified.
+    pub fn register_member_generator(&mut self, generator: Rc<dyn MemberGenerator>) {
+        self.member_generators.push(generator);
+    }
+
+    /// Members contributed by member generators, with the meta-data name
+    /// of their generator, to be lowered by codegen.
+    pub fn generated_members(&self) -> &HashMap<Entity, String> {
+        &self.verifier.generated_members
+    }
+
+    /// Properties marked with `[Bindable]` meta-data, to be lowered by codegen.
+    pub fn bindable_properties(&self) -> Vec<BindableProperty> {
+        self.bindable.properties()
+    }
+
+    /// `IEventDispatcher` implementations injected into `[Bindable]`
+    /// classes, to be generated by codegen.
+    pub fn bindable_event_dispatcher_injections(&self) -> Vec<EventDispatcherInjection> {
+        self.bindable.event_dispatcher_injections()
+    }
+
+    /// Binary expressions resolved to static operator methods.
+    pub fn operator_overloads(&self) -> &[OperatorOverload] {
+        &self.verifier.operator_overloads
+    }
+
+    /// Calls resolved to `[Extension]` static methods, for hovers and codegen.
+    pub fn extension_method_calls(&self) -> &[ExtensionMethodCall] {
+        &self.verifier.extension_method_calls
+    }
+
+    /// Definitions reached through `getDefinitionByName()` with a constant
+    /// name, to be kept by codegen.
+    pub fn reflected_definitions(&self) -> &[ReflectedDefinition] {
+        &self.verifier.reflected_definitions
+    }
+
+    /// View state overrides of each MXML component, keyed by file path,
+    /// to be lowered into the generated component class by codegen.
+    pub fn mxml_state_overrides(&self) -> &HashMap<String, Vec<MxmlStateOverride>> {
+        &self.verifier.mxml_state_overrides
+    }
+
+    /// Members included into classes by `[Mixin]` meta-data, to be
+    /// copied or delegated to by codegen.
+    pub fn mixin_inclusions(&self) -> Vec<MixinInclusion> {
+        self.mixins.inclusions()
+    }
+
+    /// Prepends the core library selected by `compiler_options.core_library`
+    /// to the programs, unless they include one of its files.
+    fn with_core_library(compiler_options: &Rc<CompilerOptions>, programs: Vec<Rc<Program>>) -> Vec<Rc<Program>> {
+        let core_paths = CoreLibrary::sources(&compiler_options.core_library).into_iter().map(|(path, _)| path).collect::<HashSet<_>>();
+        let includes_core = programs.iter().any(|program| program.location.compilation_unit().file_path().is_some_and(|path| core_paths.contains(&path)));
+        if core_paths.is_empty() || includes_core {
+            return programs;
+        }
+        let (_, mut core_programs) = CoreLibrary::parse(compiler_options);
+        core_programs.extend(programs);
+        core_programs
+    }
+
+    /// If `compiler_options.syntax_only` is set, only declaration checks
+    /// are performed and no semantic verification takes place.
+    ///
+    /// The passes that run are selected by `set_pass_manager()`. The core
+    /// library is verified along with the first programs, unless they
+    /// include it.
+    ///
     /// # Panics
     ///
     /// Panics if the verifier is already invalidated before verifying.
-    pub fn verify_programs(&mut self, _compiler_options: &Rc<CompilerOptions>, programs: Vec<Rc<Program>>, _mxml_list: Vec<Rc<Mxml>>) {
+    pub fn verify_programs(&mut self, compiler_options: &Rc<CompilerOptions>, programs: Vec<Rc<Program>>, mxml_list: Vec<Rc<Mxml>>) {
         if self.verifier.invalidated {
             panic!("Verifier already invalidated.");
         }
+        self.verifier.reset_results();
+        self.bindable.clear();
+        self.mixins.clear();
+        self.pass_durations.clear();
+
+        // Perform declaration checks only
+        if compiler_options.syntax_only {
+            let declaration_start = self.start_phase(CompilerPhase::DeclarationCollection);
+            for program in programs.iter() {
+                let start = Stopwatch::start();
+                DeclarationCheckSubverifier::check_program(&mut self.verifier, program);
+                Self::add_file_timing(&mut self.timings, &program.location, start);
+            }
+            self.end_phase(CompilerPhase::DeclarationCollection, declaration_start);
+            return;
+        }
 
         let host = self.verifier.host.clone();
+        let mut pass_start = Stopwatch::start();
+
+        // Verify the core library into the database first, unless the
+        // programs include it
+        let programs = if self.core_library_verified { programs } else { Self::with_core_library(compiler_options, programs) };
+        self.core_library_verified = true;
+
+        self.verifier.target_profile = compiler_options.target_profile;
+        self.verifier.operator_overloading = compiler_options.operator_overloading;
+        self.verifier.promise_like_type = compiler_options.promise_like_type.clone();
+        self.verifier.switch_case_letter_case_warnings = compiler_options.warnings.switch_case_letter_case;
+        self.verifier.switch_fallthrough_warnings = compiler_options.warnings.switch_fallthrough;
+        self.verifier.loop_variable_redeclaration_warnings = compiler_options.warnings.loop_variable_redeclaration;
+
+        self.verifier.coercion_audit = compiler_options.coercion_audit.then(CoercionAuditReport::default);
+        self.verifier.dynamic_access_audit = compiler_options.dynamic_access_audit.then(DynamicAccessAuditReport::default);
+        self.verifier.config_elimination = compiler_options.config_elimination_report.then(ConfigEliminationReport::default);
+
+        // Load resource bundles
+        #[cfg(feature = "fs")]
+        if self.verifier.resource_bundles.is_empty() && !compiler_options.locales.is_empty() {
+            let mut registry = ResourceBundleRegistry::load(&compiler_options.locales, &compiler_options.locale_source_path);
+            if let Some(pseudo_locale) = compiler_options.pseudo_locale.as_ref() {
+                PseudoLocale::add_to(&mut registry, pseudo_locale, &compiler_options.locales[0]);
+            }
+            self.verifier.resource_bundles = Rc::new(registry);
+        }
+
+        // Read embedded files
+        #[cfg(feature = "fs")]
+        if !self.embedded_files_given {
+            self.verifier.embedded_files = Rc::new(EmbeddedFiles::load(&programs, &compiler_options.source_path));
+        }
+        self.verifier.embedded_fonts.clear();
+
+        // Load per-directory warning levels, again on each verification
+        // as the files may have changed
+        if !self.warning_config_given {
+            self.verifier.warning_config = None;
+            #[cfg(feature = "fs")]
+            if compiler_options.warnings.directory_config {
+                self.verifier.warning_config = Some(Rc::new(WarningConfig::load(&compiler_options.source_path)));
+            }
+        }
 
         // Topmost activation before a package
         let top_m = host.factory().create_method_slot(&host.empty_empty_qname(), &host.factory().create_function_type(vec![], host.void_type()));
@@ -98,6 +432,7 @@ impl Verifier {
         self.inherit_and_enter_scope(&top_act);
 
         // Collect package definitions, including these from top-level include directives.
+        let declaration_start = self.start_phase(CompilerPhase::DeclarationCollection);
         let mut packages: Vec<Rc<PackageDefinition>> = vec![];
         for program in programs.iter() {
             packages.extend(Self::collect_package_definitions(program));
@@ -119,6 +454,14 @@ impl Verifier {
             scope.open_ns_set().push(pckg.internal_ns().unwrap());
             host.node_mapping().set(&pckgdef.block, Some(scope));
         }
+        self.end_phase(CompilerPhase::DeclarationCollection, declaration_start);
+        self.finish_pass(VerifierPass::DeclarationCollection, &mut pass_start);
+        if !self.pass_manager.runs(VerifierPass::SignatureResolution) {
+            self.exit_scope();
+            self.verifier.reset_state();
+            return;
+        }
+        let verification_start = self.start_phase(CompilerPhase::Verification);
 
         // @todo Declare packages based in MXML source tree.
 
@@ -131,8 +474,16 @@ impl Verifier {
                 break;
             }
             let mut done_pckgs = Vec::<Rc<PackageDefinition>>::new();
+            if self.verifier.cancelled() {
+                break;
+            }
+            // Definitions may change within the cycle
+            self.verifier.type_relations.invalidate();
             for pckg in rem_pckg_list.iter() {
-                if DirectiveSubverifier::verify_block(&mut self.verifier, &pckg.block).is_ok() {
+                let start = Stopwatch::start();
+                let done = DirectiveSubverifier::verify_block(&mut self.verifier, &pckg.block).is_ok();
+                Self::add_file_timing(&mut self.timings, &pckg.location, start);
+                if done {
                     done_pckgs.push(pckg.clone());
                 }
             }
@@ -147,12 +498,21 @@ impl Verifier {
                 rem_pckg_list.remove(i);
             }
         }
-        if rem_pckg_list.is_empty() {
-            // Verify statements across packages
-            for pckg in packages.iter() {
-                StatementSubverifier::verify_block(&mut self.verifier, &pckg.block);
+        if !self.verifier.cancelled() && !rem_pckg_list.is_empty() {
+            // Give up on the method bodies that never resolved rather than
+            // on their whole package, and retry the enclosing definitions.
+            let mut recovered = false;
+            for pckg in rem_pckg_list.iter() {
+                for drtv in Self::unresolved_bodies(&self.verifier, &pckg.block.directives) {
+                    self.verifier.add_verify_error(&drtv.location(), WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
+                    self.verifier.set_drtv_phase(&drtv, VerifierPhase::Finished);
+                    recovered = true;
+                }
+            }
+            if recovered {
+                self.verifier.type_relations.invalidate();
+                rem_pckg_list.retain(|pckg| DirectiveSubverifier::verify_block(&mut self.verifier, &pckg.block).is_err());
             }
-        } else {
             for pckg in rem_pckg_list.iter() {
                 self.verifier.add_verify_error(&pckg.location, WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
             }
@@ -161,7 +521,7 @@ impl Verifier {
         // Exit the activation before a package
         self.exit_scope();
 
-        // Verify directives and then statements in the top-level of all programs.
+        // Verify directives in the top-level of all programs.
         // Assign activation to program, which includes public and internal
         // namespaces, which will also be opened.
         for program in programs.iter() {
@@ -182,11 +542,21 @@ impl Verifier {
             top_act.import_list().push(host.factory().create_package_wildcard_import(&host.top_level_package(), None));
             host.node_mapping().set(program, Some(top_act.clone()));
 
+            // The frame script runs with the timeline as `this`
+            if let Some(frame_script) = compiler_options.frame_script.as_ref().filter(|f| program.location.compilation_unit().file_path().map(|p| f.is_frame_script(&p)).unwrap_or(false)) {
+                match self.verifier.resolve_qualified_name(&frame_script.timeline_class).filter(|t| t.is::<ClassType>()) {
+                    Some(class) => top_act.set_this(Some(host.factory().create_this_object(&class))),
+                    None => self.verifier.add_verify_error(&program.location, WhackDiagnosticKind::FrameScriptTimelineClassNotFound, diagarg![frame_script.timeline_class.clone()]),
+                }
+            }
+
             // Enter scope
             self.inherit_and_enter_scope(&top_act);
+            let start = Stopwatch::start();
 
             let mut any_defer = false;
             for _ in 0..Verifier::MAX_CYCLES {
+                self.verifier.type_relations.invalidate();
                 any_defer = DirectiveSubverifier::verify_directives(&mut self.verifier, &program.directives).is_err();
                 if !any_defer {
                     break;
@@ -195,9 +565,64 @@ impl Verifier {
             if any_defer {
                 self.verifier.add_verify_error(&program.location, WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
             }
-            StatementSubverifier::verify_statements(&mut self.verifier, &program.directives);
+            Self::add_file_timing(&mut self.timings, &program.location, start);
+
+            self.exit_scope();
+        }
+
+        if self.verifier.cancelled() {
+            self.end_phase(CompilerPhase::Verification, verification_start);
+            self.verifier.reset_state();
+            return;
+        }
+        self.finish_pass(VerifierPass::SignatureResolution, &mut pass_start);
+        if !self.pass_manager.runs(VerifierPass::BodyVerification) {
+            self.end_phase(CompilerPhase::Verification, verification_start);
+            self.verifier.reset_state();
+            return;
+        }
+
+        // Contribute generated members
+        MemberGeneratorRunner::run(&mut self.verifier, &programs, &self.member_generators);
+
+        // Units whose statements are verified, for progress events
+        let solved_pckgs: Vec<Rc<PackageDefinition>> = packages.iter().filter(|p| !rem_pckg_list.iter().any(|r| Rc::ptr_eq(p, r))).cloned().collect();
+        let unit_total = solved_pckgs.len() + programs.iter().filter(|p| host.node_mapping().has(*p)).count();
+        let mut units_completed = 0;
+
+        // Verify statements across packages whose directives were solved
+        if !solved_pckgs.is_empty() {
+            self.inherit_and_enter_scope(&top_act);
+            for pckg in solved_pckgs.iter() {
+                self.emit_unit_started(&pckg.location);
+                let start = Stopwatch::start();
+                self.verify_package_statements(pckg);
+                Self::add_file_timing(&mut self.timings, &pckg.location, start);
+                units_completed += 1;
+                self.emit_unit_verified(&pckg.location, units_completed, unit_total);
+            }
+            self.exit_scope();
+        }
 
+        // Verify statements in the top-level of all programs
+        for program in programs.iter() {
+            let Some(top_act) = host.node_mapping().get(program) else {
+                continue;
+            };
+            self.inherit_and_enter_scope(&top_act);
+            self.emit_unit_started(&program.location);
+            let start = Stopwatch::start();
+            StatementSubverifier::verify_statements(&mut self.verifier, &program.directives);
+            Self::add_file_timing(&mut self.timings, &program.location, start);
             self.exit_scope();
+            units_completed += 1;
+            self.emit_unit_verified(&program.location, units_completed, unit_total);
+        }
+
+        if self.verifier.cancelled() {
+            self.end_phase(CompilerPhase::Verification, verification_start);
+            self.verifier.reset_state();
+            return;
         }
 
         // Handle deferred function commons for lambdas.
@@ -216,13 +641,260 @@ impl Verifier {
             self.verifier.add_verify_error(&loc, WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
         }
 
+        // Verify the bodies of generated methods
+        GeneratedMethodBody::verify_all(&mut self.verifier);
+
+        // Retry expressions deferred by statements
+        DeferredStatementQueue::drain(&mut self.verifier);
+
         for (old, new) in self.verifier.definition_conflicts.clone().iter() {
             self.verifier.finish_definition_conflict(&old, &new);
         }
+        self.finish_pass(VerifierPass::BodyVerification, &mut pass_start);
+        if !self.pass_manager.runs(VerifierPass::FlowAnalysis) {
+            self.end_phase(CompilerPhase::Verification, verification_start);
+            self.verifier.reset_state();
+            return;
+        }
+
+        // Verify values shared with workers
+        WorkerSharingSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify [Inline] methods
+        InlineMetadataSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify [TailCall] functions
+        TailCallSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify skin contracts
+        SkinContractSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify MXML view states, ids, default property children and resources
+        for mxml in mxml_list.iter() {
+            MxmlStatesSubverifier::verify_mxml(&mut self.verifier, mxml);
+            MxmlIdSubverifier::verify_mxml(&mut self.verifier, mxml);
+            MxmlDefaultPropertySubverifier::verify_mxml(&mut self.verifier, mxml);
+            ResourceBundleSubverifier::verify_mxml(&mut self.verifier, mxml);
+        }
+
+        // Report unused imports
+        if compiler_options.warnings.unused {
+            UnusedImportSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Verify super() statements
+        SuperStatementSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify field initialization in constructors
+        ConstructorInitializationSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify the static initialization order
+        StaticInitializationSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Report accessors referring to themselves
+        RecursiveAccessorSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify [Embed] fonts
+        FontEmbedSubverifier::verify_programs(&mut self.verifier, &programs, &compiler_options.source_path);
+
+        // Verify [RemoteClass] classes
+        RemoteClassSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Verify [JsonType] classes
+        if compiler_options.json_type_helpers {
+            JsonTypeSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Verify native extension IDs
+        if let Some(ids) = compiler_options.native_extension_ids.as_ref() {
+            NativeExtensionIdSubverifier::verify_programs(&mut self.verifier, &programs, ids);
+        }
+
+        // Report reassigned parameters
+        if compiler_options.const_parameters {
+            ConstParameterSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Report package-level functions and variables named as types
+        PackageDefinitionSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Enforce module export lists
+        ModuleExportSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Resolve constant names given to getDefinitionByName()
+        ReflectedDefinitionSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Report describeType() calls missing stripped meta-data
+        if compiler_options.warnings.stripped_metadata {
+            MetadataRetentionSubverifier::verify_programs(&mut self.verifier, &programs, compiler_options);
+        }
+
+        // Report references to [Experimental] and [InternalAPI] definitions
+        ApiStabilitySubverifier::verify_programs(&mut self.verifier, &programs, &compiler_options.api_stability);
+
+        // Report references to packages absent from the target profile
+        TargetProfileSubverifier::verify_programs(&mut self.verifier, &programs, compiler_options.target_profile);
+
+        // Report references to APIs later than the target runtime version
+        if let Some(version) = compiler_options.target_runtime_version {
+            ApiVersionSubverifier::verify_programs(&mut self.verifier, &programs, version);
+        }
+
+        // Report block-scoped bindings used before their declaration
+        TemporalDeadZoneSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Report loop variables captured by functions
+        if compiler_options.warnings.loop_variable_capture {
+            LoopCaptureSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Audit lossy numeric arithmetic
+        if compiler_options.warnings.numeric_promotion {
+            NumericPromotionSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Report discarded results of pure methods
+        if compiler_options.warnings.discarded_results {
+            DiscardedResultSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Audit dynamic property accesses
+        if self.verifier.dynamic_access_audit.is_some() {
+            DynamicAccessAuditSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Report code eliminated by configuration constants
+        if self.verifier.config_elimination.is_some() {
+            ConfigEliminationSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Report language features used without their gate
+        if let Some(options) = compiler_options.language_features.as_ref() {
+            LanguageFeatureSubverifier::verify_programs(&mut self.verifier, &programs, options);
+        }
+
+        // Report function declarations nested in conditional statements
+        ConditionalFunctionSubverifier::verify_programs(&mut self.verifier, &programs);
+
+        // Report shadowed declarations
+        ShadowingSubverifier::verify_programs(&mut self.verifier, &programs, &compiler_options.warnings.shadowing);
+
+        // Verify [Throws] meta-data
+        ThrowsMetadataSubverifier::verify_programs(&mut self.verifier, &programs, compiler_options.warnings.undeclared_throws);
+
+        // Report errors uncaught by event handlers
+        if compiler_options.warnings.uncaught_handler_errors {
+            UncaughtHandlerErrorSubverifier::verify_programs(&mut self.verifier, &programs);
+        }
+
+        // Run lint rules
+        if !self.lint_rules.is_empty() {
+            let mut runner = LintRunner::new(&mut self.verifier, compiler_options, &self.lint_rules);
+            for program in programs.iter() {
+                runner.visit_program(program);
+            }
+        }
+        self.finish_pass(VerifierPass::FlowAnalysis, &mut pass_start);
+
+        self.end_phase(CompilerPhase::Verification, verification_start);
 
         self.verifier.reset_state();
     }
 
+    /// Verifies the statements of a package, emitting a `DefinitionVerified`
+    /// event per definition if there is an event subscriber.
+    fn verify_package_statements(&mut self, pckg: &Rc<PackageDefinition>) {
+        let Some(subscriber) = self.event_subscriber.clone() else {
+            StatementSubverifier::verify_block(&mut self.verifier, &pckg.block);
+            return;
+        };
+        let host = self.verifier.host.clone();
+        let scope = host.lazy_node_mapping(&pckg.block, || host.factory().create_scope());
+        self.inherit_and_enter_scope(&scope);
+        for drtv in pckg.block.directives.iter() {
+            if self.verifier.cancelled() {
+                break;
+            }
+            let start = Stopwatch::start();
+            StatementSubverifier::verify_statement(&mut self.verifier, drtv);
+            if let Some(name) = Self::definition_name(&host, drtv) {
+                subscriber.on_event(&CompilerEvent::DefinitionVerified {
+                    name,
+                    file_path: pckg.location.compilation_unit().file_path().unwrap_or_default(),
+                    duration: start.elapsed(),
+                });
+            }
+        }
+        self.exit_scope();
+    }
+
+    fn definition_name(host: &Database, drtv: &Rc<Directive>) -> Option<String> {
+        let definition = match drtv.as_ref() {
+            Directive::ClassDefinition(_) |
+            Directive::InterfaceDefinition(_) |
+            Directive::EnumDefinition(_) |
+            Directive::FunctionDefinition(_) => host.node_mapping().get(drtv),
+            Directive::VariableDefinition(defn) => host.node_mapping().get(&defn.bindings.first()?.destructuring.destructuring),
+            _ => None,
+        };
+        definition.filter(|d| !d.is::<InvalidationEntity>()).map(|d| d.to_string())
+    }
+
+    fn emit_unit_started(&self, location: &Location) {
+        if let Some(subscriber) = self.event_subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::UnitStarted(location.compilation_unit().file_path().unwrap_or_default()));
+        }
+    }
+
+    fn emit_unit_verified(&self, location: &Location, completed: usize, total: usize) {
+        if let Some(subscriber) = self.event_subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::UnitVerified {
+                file_path: location.compilation_unit().file_path().unwrap_or_default(),
+                completed,
+                total,
+            });
+        }
+    }
+
+    fn start_phase(&self, phase: CompilerPhase) -> Stopwatch {
+        if let Some(subscriber) = self.event_subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::PhaseStart(phase));
+        }
+        Stopwatch::start()
+    }
+
+    fn end_phase(&mut self, phase: CompilerPhase, start: Stopwatch) {
+        let duration = start.elapsed();
+        self.timings.add_phase(phase, duration);
+        if let Some(subscriber) = self.event_subscriber.as_ref() {
+            subscriber.on_event(&CompilerEvent::PhaseEnd(phase, duration));
+        }
+    }
+
+    /// Function definitions, at the package level or within a type, whose
+    /// signature is resolved but whose body is still deferred.
+    fn unresolved_bodies(verifier: &Subverifier, directives: &[Rc<Directive>]) -> Vec<Rc<Directive>> {
+        let mut r = vec![];
+        for drtv in directives.iter() {
+            match drtv.as_ref() {
+                Directive::FunctionDefinition(_) => {
+                    if verifier.phase_of_drtv.get(&NodeAsKey(drtv.clone())) == Some(&VerifierPhase::Omega) {
+                        r.push(drtv.clone());
+                    }
+                },
+                Directive::ClassDefinition(defn) => r.extend(Self::unresolved_bodies(verifier, &defn.block.directives)),
+                Directive::EnumDefinition(defn) => r.extend(Self::unresolved_bodies(verifier, &defn.block.directives)),
+                _ => {},
+            }
+        }
+        r
+    }
+
+    fn add_file_timing(timings: &mut CompilerTimings, location: &Location, start: Stopwatch) {
+        let file_path = location.compilation_unit().file_path().unwrap_or("<unknown>".into());
+        timings.add_file(&file_path, start.elapsed());
+    }
+
     /// Verifies an expression. Returns `None` if verification failed.
     ///
     /// # Panics
@@ -232,6 +904,7 @@ impl Verifier {
         if self.verifier.invalidated {
             panic!("Verifier already invalidated.");
         }
+        self.verifier.reset_results();
 
         let v = self.verifier.verify_expression(exp, context);
         if let Ok(v) = v {
@@ -327,10 +1000,68 @@ pub(crate) struct Subverifier {
 
     pub codegen_class_info: SharedMap<Entity, Rc<CodegenClassInfo>>,
 
+    pub resource_bundles: Rc<ResourceBundleRegistry>,
+    pub referenced_resource_bundles: HashSet<String>,
+    pub embedded_files: Rc<EmbeddedFiles>,
+    pub embedded_fonts: Vec<EmbeddedFont>,
+
     invalidated: bool,
     // pub deferred_counter: usize,
     pub scope: Option<Entity>,
     pub external: bool,
+    pub cancellation_token: Option<CancellationToken>,
+    pub suggested_fixes: Vec<SuggestedFix>,
+    pub related_information: Vec<RelatedInformation>,
+    /// Present while `CompilerOptions::coercion_audit` is set.
+    pub coercion_audit: Option<CoercionAuditReport>,
+    /// Present while `CompilerOptions::dynamic_access_audit` is set.
+    pub dynamic_access_audit: Option<DynamicAccessAuditReport>,
+    /// Present while `CompilerOptions::config_elimination_report` is set.
+    pub config_elimination: Option<ConfigEliminationReport>,
+    pub scope_probe: Option<ScopeProbe>,
+    pub scope_snapshots: Vec<ScopeSnapshot>,
+    /// Spans of the occurrences per file path, diagnostic ID and
+    /// arguments, in order of first report.
+    pub diagnostic_occurrences: HashMap<(String, i32, Vec<String>), Vec<(usize, usize)>>,
+    pub target_profile: TargetProfile,
+    pub type_relations: TypeRelationCache,
+    /// Members contributed by member generators, with the meta-data
+    /// name of their generator.
+    pub generated_members: HashMap<Entity, String>,
+    /// Bodies of generated methods, verified after the other bodies.
+    pub generated_method_bodies: Vec<GeneratedMethodBody>,
+    pub operator_overloading: bool,
+    /// Set from `CompilerOptions::promise_like_type`.
+    pub promise_like_type: Option<String>,
+    /// Set from `CompilerWarningOptions::switch_case_letter_case`.
+    pub switch_case_letter_case_warnings: bool,
+    /// Set from `CompilerWarningOptions::switch_fallthrough`.
+    pub switch_fallthrough_warnings: bool,
+    /// Set from `CompilerWarningOptions::loop_variable_redeclaration`.
+    pub loop_variable_redeclaration_warnings: bool,
+    /// Per-directory warning levels.
+    pub warning_config: Option<Rc<WarningConfig>>,
+    /// Binary expressions resolved to operator methods, lowered
+    /// into static calls by codegen.
+    pub operator_overloads: Vec<OperatorOverload>,
+    /// Calls resolved to `[Extension]` methods, lowered into
+    /// static calls receiving the base object.
+    pub extension_method_calls: Vec<ExtensionMethodCall>,
+    /// Definitions named by constant strings given to
+    /// `getDefinitionByName()`.
+    pub reflected_definitions: Vec<ReflectedDefinition>,
+    /// View state overrides of each MXML component, keyed by file path.
+    pub mxml_state_overrides: HashMap<String, Vec<MxmlStateOverride>>,
+    /// Statements deferred during statement verification.
+    pub(crate) deferred_statements: Vec<DeferredStatement>,
+    /// Blocker of the first expression that deferred in the statement
+    /// being verified, if any; see `StatementSubverifier::verify_statement()`.
+    pub(crate) statement_deferral: Option<Option<Entity>>,
+    /// Whether deferring statement expressions queue their statement
+    /// rather than reporting a maximum cycles error.
+    pub(crate) statement_deferral_enabled: bool,
+    /// Entity the last deferral waits for, if known.
+    pub(crate) blocker: Option<Entity>,
 }
 
 impl Subverifier {
@@ -339,6 +1070,29 @@ impl Subverifier {
         &self.host.node_mapping()
     }
 
+    /// Whether the verification was cancelled through the cancellation token.
+    pub fn cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().map(|t| t.is_cancelled()).unwrap_or(false)
+    }
+
+    /// Records a `ScopeSnapshot` if the identifier contains the probed position.
+    pub fn capture_scope_snapshot(&mut self, id: &QualifiedIdentifier, resolved: Option<&Entity>) {
+        let Some(probe) = self.scope_probe.as_ref() else {
+            return;
+        };
+        let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id else {
+            return;
+        };
+        if !probe.contains(&id.location) {
+            return;
+        }
+        let snapshot = ScopeSnapshot::capture(&self.host, &self.scope(), &id.location, name, resolved);
+
+        // An identifier may be verified again after deferring
+        self.scope_snapshots.retain(|s| s.location.first_offset() != id.location.first_offset());
+        self.scope_snapshots.push(snapshot);
+    }
+
     pub fn reset_state(&mut self) {
         self.cached_var_init.clear();
         self.phase_of_entity.clear();
@@ -348,6 +1102,38 @@ impl Subverifier {
         self.function_definition_partials.clear();
         self.class_defn_guard.clear();
         self.itrfc_defn_guard.clear();
+        self.type_relations.invalidate();
+        self.diagnostic_occurrences.clear();
+    }
+
+    /// Clears the results of a previous verification, such as suggested
+    /// fixes, which are otherwise kept until they are read.
+    pub fn reset_results(&mut self) {
+        self.diagnostic_occurrences.clear();
+        self.suggested_fixes.clear();
+        self.related_information.clear();
+        self.scope_snapshots.clear();
+        self.reflected_definitions.clear();
+        self.mxml_state_overrides.clear();
+        self.extension_method_calls.clear();
+    }
+
+    /// Resolves a public package-level definition by its fully qualified
+    /// name, with the local name separated by either `.` or `::`, as in
+    /// `com.example.Main` and `com.example::Main`.
+    pub fn resolve_qualified_name(&self, name: &str) -> Option<Entity> {
+        let host = &self.host;
+        let (pckg_name, local_name) = match name.rsplit_once("::") {
+            Some(split) => split,
+            None => name.rsplit_once('.').unwrap_or(("", name)),
+        };
+        let pckg = if pckg_name.is_empty() {
+            host.top_level_package()
+        } else {
+            host.factory().create_package(pckg_name.split('.').collect::<Vec<_>>())
+        };
+        let qname = host.factory().create_qname(&pckg.public_ns()?, local_name.to_owned());
+        pckg.properties(host).get(&qname)
     }
 
     pub fn lazy_init_drtv_phase(&mut self, drtv: &Rc<Directive>, initial_phase: VerifierPhase) -> VerifierPhase {
@@ -422,16 +1208,79 @@ impl Subverifier {
         if cu.prevent_equal_offset_error(location) {
             return;
         }
-        cu.add_diagnostic(WhackDiagnostic::new_verify_error(location, kind, arguments));
         self.invalidated = true;
+        if !self.count_diagnostic(location, kind, &arguments) {
+            return;
+        }
+        cu.add_diagnostic(WhackDiagnostic::new_verify_error(location, kind, arguments));
     }
 
-    pub fn add_warning(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+    /// Counts an occurrence of a diagnostic with arguments, such as an
+    /// unresolved name used throughout a file, and returns whether it is
+    /// still to be reported. Diagnostics without arguments are not capped,
+    /// and an occurrence reported again at the same span, such as after
+    /// deferring, counts once.
+    fn count_diagnostic(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: &[Rc<dyn DiagnosticArgument>]) -> bool {
+        if arguments.is_empty() {
+            return true;
+        }
         let cu = location.compilation_unit();
-        if cu.prevent_equal_offset_warning(location) {
-            return;
+        let file_path = cu.file_path().unwrap_or_default();
+        let key = (file_path, kind.id(), arguments.iter().map(|a| a.to_string()).collect::<Vec<_>>());
+        let span = (location.first_offset(), location.last_offset());
+        let spans = self.diagnostic_occurrences.entry(key).or_default();
+        let index = match spans.iter().position(|s| *s == span) {
+            Some(index) => index,
+            None => {
+                spans.push(span);
+                if spans.len() == Verifier::MAX_DUPLICATE_DIAGNOSTICS + 1 {
+                    cu.add_diagnostic(WhackDiagnostic::new_warning(location, WhackDiagnosticKind::DuplicateDiagnosticsSuppressed, diagarg![]));
+                }
+                spans.len() - 1
+            },
+        };
+        index < Verifier::MAX_DUPLICATE_DIAGNOSTICS
+    }
+
+    /// Reports an internal compiler error instead of panicking, so that
+    /// the remaining programs are still verified. The message includes a
+    /// backtrace when enabled by `RUST_BACKTRACE`.
+    pub fn add_internal_error(&mut self, location: &Location, message: &str) {
+        let backtrace = std::backtrace::Backtrace::capture();
+        let message = if backtrace.status() == std::backtrace::BacktraceStatus::Captured {
+            format!("{message}.\n{backtrace}")
+        } else {
+            format!("{message}.")
+        };
+        self.add_verify_error(location, WhackDiagnosticKind::InternalCompilerError, diagarg![message]);
+    }
+
+    /// Reports a directive reached in a phase its verification does not
+    /// handle, finishing it so that it is not verified again.
+    pub fn add_unexpected_phase_error(&mut self, drtv: &Rc<Directive>) {
+        self.add_internal_error(&drtv.location(), "unexpected verifier phase");
+        self.set_drtv_phase(drtv, VerifierPhase::Finished);
+    }
+
+    /// Renders a type given to a diagnostic through `EntityDisplay`, with
+    /// fully qualified names.
+    pub fn display_type(&self, t: &Entity) -> String {
+        EntityDisplay::new(&self.host, DisplayVerbosity::FullyQualified).type_name(t)
+    }
+
+    pub fn add_warning(&mut self, location: &Location, kind: WhackDiagnosticKind, arguments: Vec<Rc<dyn DiagnosticArgument>>) {
+        // Allowed warnings are not counted towards the duplicate cap
+        match self.warning_config.as_ref().and_then(|config| config.level(location, kind)) {
+            Some(LintSeverity::Allow) => {},
+            Some(LintSeverity::Error) => self.add_verify_error(location, kind, arguments),
+            _ => {
+                let cu = location.compilation_unit();
+                if cu.prevent_equal_offset_warning(location) || !self.count_diagnostic(location, kind, &arguments) {
+                    return;
+                }
+                cu.add_diagnostic(WhackDiagnostic::new_warning(location, kind, arguments));
+            },
         }
-        cu.add_diagnostic(WhackDiagnostic::new_warning(location, kind, arguments));
     }
 
     pub fn set_scope(&mut self, scope: &Entity) {
@@ -454,17 +1303,45 @@ impl Subverifier {
         self.scope.as_ref().unwrap().clone()
     }
 
+    /// Verifies a statement expression, marking the statement as
+    /// deferred (see `DeferredStatementQueue`) and returning `None` if it
+    /// defers.
     pub fn verify_expression_or_max_cycles_error(&mut self, exp: &Rc<Expression>, context: &VerifierExpressionContext) -> Option<Entity> {
+        self.blocker = None;
         let val = self.verify_expression(exp, context);
         if let Ok(val) = val {
             val
         } else {
-            self.add_verify_error(&exp.location(), WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
-            self.host.node_mapping().set(exp, None);
+            self.defer_statement(exp);
             None
         }
     }
 
+    fn defer_statement(&mut self, exp: &Rc<Expression>) {
+        self.host.node_mapping().set(exp, None);
+        let blocker = self.blocker.take();
+        if !self.statement_deferral_enabled {
+            self.add_verify_error(&exp.location(), WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
+        } else if self.statement_deferral.is_none() {
+            self.statement_deferral = Some(blocker);
+        }
+    }
+
+    /// Whether an expression of the statement being verified deferred.
+    pub fn statement_deferred(&self) -> bool {
+        self.statement_deferral.is_some()
+    }
+
+    /// Returns `entity`, or defers recording `blocker` as the entity
+    /// to wait for if `entity` is unresolved.
+    pub fn defer_on(&mut self, blocker: &Entity, entity: Entity) -> Result<Entity, DeferError> {
+        if entity.is::<UnresolvedEntity>() {
+            self.blocker = Some(blocker.clone());
+            return Err(DeferError(None));
+        }
+        Ok(entity)
+    }
+
     pub fn verify_expression(&mut self, exp: &Rc<Expression>, context: &VerifierExpressionContext) -> Result<Option<Entity>, DeferError> {
         // Cache-result - prevents diagnostic duplication
         if self.host.node_mapping().has(exp) {
@@ -554,7 +1431,8 @@ impl Subverifier {
             Expression::OptionalChainingPlaceholder(_) => {
                 // The optional chaining placeholder is assumed to be already
                 // cached by the optional chaining operation.
-                panic!();
+                self.add_internal_error(&exp.location(), "optional chaining placeholder was not cached");
+                result = None;
             },
             Expression::Binary(e) => {
                 result = ExpSubverifier::verify_binary_exp(self, e)?;
@@ -593,7 +1471,7 @@ impl Subverifier {
                 result = ExpSubverifier::verify_assignment_exp(self, e)?;
             },
             Expression::Function(e) => {
-                result = ExpSubverifier::verify_function_exp(self, e)?;
+                result = ExpSubverifier::verify_function_exp(self, e, context)?;
             },
         }
 
@@ -658,13 +1536,15 @@ impl Subverifier {
         Ok(Some(v))
     }
 
+    /// Implicitly coerces a statement expression to a type, marking the
+    /// statement as deferred and returning `None` if it defers.
     pub fn imp_coerce_exp_or_max_cycles_error(&mut self, exp: &Rc<Expression>, target_type: &Entity) -> Option<Entity> {
+        self.blocker = None;
         let val = self.imp_coerce_exp(exp, target_type);
         if let Ok(val) = val {
             val
         } else {
-            self.add_verify_error(&exp.location(), WhackDiagnosticKind::ReachedMaximumCycles, diagarg![]);
-            self.host.node_mapping().set(exp, None);
+            self.defer_statement(exp);
             None
         }
     }
@@ -688,17 +1568,97 @@ impl Subverifier {
         }
         let v = v.unwrap();
         let got_type = v.static_type(&self.host);
+
+        // Invalidated values and types absorb the coercion, since their
+        // error was already reported.
+        if v.is::<InvalidationEntity>() || got_type.is::<InvalidationEntity>() || target_type.is::<InvalidationEntity>() {
+            self.host.node_mapping().set(exp, Some(v.clone()));
+            return Ok(Some(v));
+        }
+
+        // The result of a void call has no value
+        if got_type == self.host.void_type() && matches!(exp.as_ref(), Expression::Call(_)) {
+            self.add_warning(&exp.location(), WhackDiagnosticKind::VoidResultUsedAsValue, diagarg![]);
+        }
+
+        // Vector types are invariant
+        let got_elem_type = got_type.escape_of_non_nullable().vector_element_type(&self.host)?;
+        let target_elem_type = target_type.escape_of_non_nullable().vector_element_type(&self.host)?;
+        if let (Some(got_elem_type), Some(target_elem_type)) = (got_elem_type, target_elem_type) {
+            if got_elem_type != target_elem_type && target_elem_type != self.host.any_type() {
+                self.add_verify_error(&exp.location(), WhackDiagnosticKind::ImplicitVectorCoercion, diagarg![self.display_type(&got_type), self.display_type(target_type)]);
+                self.host.node_invalidation_mapping().set(exp, Some(()));
+                return Ok(None);
+            }
+        }
+
+        // Tuple types are assignable element-wise
+        let got_type_esc = got_type.escape_of_non_nullable();
+        let target_type_esc = target_type.escape_of_non_nullable();
+        if got_type_esc.is::<TupleType>() && target_type_esc.is::<TupleType>() && got_type_esc != target_type_esc {
+            if self.tuple_elements_assignable(&got_type_esc, &target_type_esc)? {
+                let v = self.host.factory().create_value(target_type);
+                self.host.node_mapping().set(exp, Some(v.clone()));
+                return Ok(Some(v));
+            }
+            self.add_verify_error(&exp.location(), WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![self.display_type(&got_type), self.display_type(target_type)]);
+            self.host.node_invalidation_mapping().set(exp, Some(()));
+            return Ok(None);
+        }
+
+        // Enums do not implicitly convert from their underlying values
+        if target_type.escape_of_nullable_or_non_nullable().is::<EnumType>() {
+            let raw_types = [
+                self.host.number_type().defer()?,
+                self.host.int_type().defer()?,
+                self.host.uint_type().defer()?,
+                self.host.float_type().defer()?,
+                self.host.string_type().defer()?,
+            ];
+            if raw_types.contains(&got_type_esc) {
+                self.add_verify_error(&exp.location(), WhackDiagnosticKind::ImplicitCoercionOfRawValueToEnum, diagarg![self.display_type(&got_type), self.display_type(target_type)]);
+                self.host.node_invalidation_mapping().set(exp, Some(()));
+                return Ok(None);
+            }
+        }
+
         let v = ConversionMethods(&self.host).implicit(&v, target_type, false)?;
         if v.is_none() {
-            self.add_verify_error(&exp.location(), WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![got_type, target_type.clone()]);
+            self.add_verify_error(&exp.location(), WhackDiagnosticKind::ImplicitCoercionToUnrelatedType, diagarg![self.display_type(&got_type), self.display_type(target_type)]);
             self.host.node_invalidation_mapping().set(exp, Some(()));
             return Ok(None);
         }
         let v = v.unwrap();
+
+        // Audit coercions from * and Object
+        if self.coercion_audit.is_some() {
+            let any_type = self.host.any_type();
+            let object_type = self.host.object_type().defer()?;
+            let target_type_esc = target_type.escape_of_nullable_or_non_nullable();
+            let from_dynamic = got_type == any_type || got_type_esc == object_type;
+            if from_dynamic && target_type_esc != any_type && target_type_esc != object_type {
+                self.coercion_audit.as_mut().unwrap().add(&exp.location(), &got_type, target_type);
+            }
+        }
+
         self.host.node_mapping().set(exp, Some(v.clone()));
         Ok(Some(v))
     }
     
+    fn tuple_elements_assignable(&self, from_type: &Entity, to_type: &Entity) -> Result<bool, DeferError> {
+        let from_elems = from_type.element_types();
+        let to_elems = to_type.element_types();
+        if from_elems.length() != to_elems.length() {
+            return Ok(false);
+        }
+        for (from_elem, to_elem) in from_elems.iter().zip(to_elems.iter()) {
+            if from_elem != to_elem && to_elem != self.host.any_type() && !self.type_relations.is_subtype_of(&self.host, &from_elem, &to_elem)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub fn detect_local_capture(&self, reference: &Entity) {
         if reference.is::<ScopeReferenceValue>() {
             let r_act = reference.base().search_activation();
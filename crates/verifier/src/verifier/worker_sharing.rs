@@ -0,0 +1,115 @@
+use crate::ns::*;
+
+/// Verifies values passed to the `flash.system` worker APIs
+/// (`Worker.setSharedProperty()` and `MessageChannel.send()`).
+///
+/// Display objects and functions cannot cross worker boundaries, so passing
+/// one is an error; closures referencing display objects are warned about.
+pub(crate) struct WorkerSharingSubverifier<'a> {
+    verifier: &'a mut Subverifier,
+}
+
+impl<'a> WorkerSharingSubverifier<'a> {
+    pub fn verify_programs(verifier: &'a mut Subverifier, programs: &[Rc<Program>]) {
+        let mut subverifier = Self { verifier };
+        for program in programs.iter() {
+            subverifier.visit_program(program);
+        }
+    }
+
+    fn verify_call(&mut self, call: &CallExpression) {
+        let Expression::Member(member) = call.base.as_ref() else {
+            return;
+        };
+        let Some(name) = member.identifier.to_identifier_name_or_asterisk() else {
+            return;
+        };
+        let host = self.verifier.host.clone();
+        let Some(base_type) = self.static_type(&member.base) else {
+            return;
+        };
+        let value = match name.0.as_str() {
+            "setSharedProperty" if Self::is_class_named(&base_type, &["flash", "system"], "Worker") => call.arguments.get(1),
+            "send" if Self::is_class_named(&base_type, &["flash", "system"], "MessageChannel") => call.arguments.first(),
+            _ => None,
+        };
+        let Some(value) = value else {
+            return;
+        };
+
+        if let Expression::Function(function) = value.as_ref() {
+            let mut captures = DisplayObjectCaptures { verifier: &*self.verifier, found: vec![] };
+            captures.visit_function_common(&function.common);
+            for (name, location) in captures.found {
+                self.verifier.add_warning(&location, WhackDiagnosticKind::WorkerClosureReferencesDisplayObject, diagarg![name]);
+            }
+            self.verifier.add_verify_error(&value.location(), WhackDiagnosticKind::NonShareableWorkerValue, diagarg![host.function_type()]);
+            return;
+        }
+
+        let Some(value_type) = self.static_type(value) else {
+            return;
+        };
+        if value_type == host.function_type() || value_type.is::<FunctionType>() || Self::is_display_object(&host, &value_type) {
+            self.verifier.add_verify_error(&value.location(), WhackDiagnosticKind::NonShareableWorkerValue, diagarg![value_type]);
+        }
+    }
+
+    fn static_type(&self, exp: &Rc<Expression>) -> Option<Entity> {
+        let entity = self.verifier.host.node_mapping().get(exp)?;
+        if entity.is::<Value>() {
+            Some(entity.static_type(&self.verifier.host))
+        } else {
+            None
+        }
+    }
+
+    fn is_display_object(host: &Database, t: &Entity) -> bool {
+        let mut t = Some(t.clone());
+        while let Some(t1) = t {
+            if Self::is_class_named(&t1, &["flash", "display"], "DisplayObject") {
+                return true;
+            }
+            t = t1.extends_class(host);
+        }
+        false
+    }
+
+    fn is_class_named(t: &Entity, package: &[&str], name: &str) -> bool {
+        if !t.is::<ClassType>() || t.name().local_name() != name {
+            return false;
+        }
+        t.parent().map(|p| p.is::<Package>() && p.fully_qualified_name_list() == package).unwrap_or(false)
+    }
+}
+
+impl<'a> Visitor for WorkerSharingSubverifier<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            self.verify_call(call);
+        }
+        walk_expression(self, exp);
+    }
+}
+
+/// Collects identifiers of a closure that refer to display objects.
+struct DisplayObjectCaptures<'a> {
+    verifier: &'a Subverifier,
+    found: Vec<(String, Location)>,
+}
+
+impl<'a> Visitor for DisplayObjectCaptures<'a> {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if let Expression::QualifiedIdentifier(id) = exp.as_ref() {
+            let host = &self.verifier.host;
+            if let Some(entity) = host.node_mapping().get(exp) {
+                if entity.is::<Value>() && WorkerSharingSubverifier::is_display_object(host, &entity.static_type(host)) {
+                    if let Some(name) = id.to_identifier_name_or_asterisk() {
+                        self.found.push(name);
+                    }
+                }
+            }
+        }
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
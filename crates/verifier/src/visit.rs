@@ -0,0 +1,8 @@
+mod visitor;
+pub use visitor::*;
+
+mod mut_visitor;
+pub use mut_visitor::*;
+
+mod node_ids;
+pub use node_ids::*;
\ No newline at end of file
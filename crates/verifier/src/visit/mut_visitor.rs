@@ -0,0 +1,478 @@
+use crate::ns::*;
+
+/// Mutable traversal over programs, directives and expressions.
+///
+/// Overrides may replace the visited node entirely by assigning to it.
+/// Walking into a node shared by other trees clones it first
+/// (as per `Rc::make_mut()`), so node mappings from a previous
+/// verification do not apply to the mutated nodes.
+///
+/// ```ignore
+/// struct RenameIdentifier { from: String, to: String }
+///
+/// impl MutVisitor for RenameIdentifier {
+///     fn visit_qualified_identifier(&mut self, id: &mut QualifiedIdentifier) {
+///         if let QualifiedIdentifierIdentifier::Id((name, _)) = &mut id.id {
+///             if *name == self.from {
+///                 *name = self.to.clone();
+///             }
+///         }
+///         walk_qualified_identifier_mut(self, id);
+///     }
+/// }
+/// ```
+pub trait MutVisitor {
+    fn visit_program(&mut self, program: &mut Rc<Program>) {
+        walk_program_mut(self, program);
+    }
+
+    fn visit_directive(&mut self, drtv: &mut Rc<Directive>) {
+        walk_directive_mut(self, drtv);
+    }
+
+    fn visit_block(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_expression(&mut self, exp: &mut Rc<Expression>) {
+        walk_expression_mut(self, exp);
+    }
+
+    fn visit_qualified_identifier(&mut self, id: &mut QualifiedIdentifier) {
+        walk_qualified_identifier_mut(self, id);
+    }
+
+    fn visit_function_common(&mut self, common: &mut Rc<FunctionCommon>) {
+        walk_function_common_mut(self, common);
+    }
+}
+
+pub fn walk_program_mut<V: MutVisitor + ?Sized>(visitor: &mut V, program: &mut Rc<Program>) {
+    let program = Rc::make_mut(program);
+    for pckgdef in program.packages.iter_mut() {
+        visitor.visit_block(Rc::make_mut(&mut Rc::make_mut(pckgdef).block));
+    }
+    for drtv in program.directives.iter_mut() {
+        visitor.visit_directive(drtv);
+    }
+}
+
+pub fn walk_block_mut<V: MutVisitor + ?Sized>(visitor: &mut V, block: &mut Block) {
+    for drtv in block.directives.iter_mut() {
+        visitor.visit_directive(drtv);
+    }
+}
+
+pub fn walk_directive_mut<V: MutVisitor + ?Sized>(visitor: &mut V, drtv: &mut Rc<Directive>) {
+    match Rc::make_mut(drtv) {
+        Directive::VariableDefinition(defn) => {
+            walk_variable_definition_mut(visitor, defn);
+        },
+        Directive::FunctionDefinition(defn) => {
+            visitor.visit_function_common(&mut defn.common);
+        },
+        Directive::ClassDefinition(defn) => {
+            if let Some(exp) = defn.extends_clause.as_mut() {
+                visitor.visit_expression(exp);
+            }
+            if let Some(list) = defn.implements_clause.as_mut() {
+                for exp in list.iter_mut() {
+                    visitor.visit_expression(exp);
+                }
+            }
+            visitor.visit_block(Rc::make_mut(&mut defn.block));
+        },
+        Directive::EnumDefinition(defn) => {
+            visitor.visit_block(Rc::make_mut(&mut defn.block));
+        },
+        Directive::InterfaceDefinition(defn) => {
+            if let Some(list) = defn.extends_clause.as_mut() {
+                for exp in list.iter_mut() {
+                    visitor.visit_expression(exp);
+                }
+            }
+            visitor.visit_block(Rc::make_mut(&mut defn.block));
+        },
+        Directive::TypeDefinition(defn) => {
+            visitor.visit_expression(&mut defn.right);
+        },
+        Directive::NamespaceDefinition(defn) => {
+            if let Some(exp) = defn.right.as_mut() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Directive::Block(block) => {
+            visitor.visit_block(block);
+        },
+        Directive::ExpressionStatement(estmt) => {
+            visitor.visit_expression(&mut estmt.expression);
+        },
+        Directive::SuperStatement(supstmt) => {
+            for exp in supstmt.arguments.iter_mut() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Directive::LabeledStatement(labstmt) => {
+            visitor.visit_directive(&mut labstmt.substatement);
+        },
+        Directive::IfStatement(ifstmt) => {
+            visitor.visit_expression(&mut ifstmt.test);
+            visitor.visit_directive(&mut ifstmt.consequent);
+            if let Some(alt) = ifstmt.alternative.as_mut() {
+                visitor.visit_directive(alt);
+            }
+        },
+        Directive::SwitchStatement(swstmt) => {
+            visitor.visit_expression(&mut swstmt.discriminant);
+            for case in swstmt.cases.iter_mut() {
+                for label in case.labels.iter_mut() {
+                    if let CaseLabel::Case((exp, _)) = label {
+                        visitor.visit_expression(exp);
+                    }
+                }
+                for drtv in case.directives.iter_mut() {
+                    visitor.visit_directive(drtv);
+                }
+            }
+        },
+        Directive::SwitchTypeStatement(swstmt) => {
+            visitor.visit_expression(&mut swstmt.discriminant);
+            for case in swstmt.cases.iter_mut() {
+                if let Some(parameter) = case.parameter.as_mut() {
+                    visitor.visit_expression(&mut parameter.destructuring);
+                    if let Some(t) = parameter.type_annotation.as_mut() {
+                        visitor.visit_expression(t);
+                    }
+                }
+                visitor.visit_block(Rc::make_mut(&mut case.block));
+            }
+        },
+        Directive::DoStatement(dostmt) => {
+            visitor.visit_directive(&mut dostmt.body);
+            visitor.visit_expression(&mut dostmt.test);
+        },
+        Directive::WhileStatement(wstmt) => {
+            visitor.visit_expression(&mut wstmt.test);
+            visitor.visit_directive(&mut wstmt.body);
+        },
+        Directive::ForStatement(forstmt) => {
+            match forstmt.init.as_mut() {
+                Some(ForInitializer::Expression(exp)) => {
+                    visitor.visit_expression(exp);
+                },
+                Some(ForInitializer::VariableDefinition(defn)) => {
+                    walk_variable_definition_mut(visitor, Rc::make_mut(defn));
+                },
+                None => {},
+            }
+            if let Some(exp) = forstmt.test.as_mut() {
+                visitor.visit_expression(exp);
+            }
+            if let Some(exp) = forstmt.update.as_mut() {
+                visitor.visit_expression(exp);
+            }
+            visitor.visit_directive(&mut forstmt.body);
+        },
+        Directive::ForInStatement(forstmt) => {
+            match &mut forstmt.left {
+                ForInBinding::Expression(exp) => {
+                    visitor.visit_expression(exp);
+                },
+                ForInBinding::VariableDefinition(defn) => {
+                    walk_variable_definition_mut(visitor, Rc::make_mut(defn));
+                },
+            }
+            visitor.visit_expression(&mut forstmt.right);
+            visitor.visit_directive(&mut forstmt.body);
+        },
+        Directive::WithStatement(wstmt) => {
+            visitor.visit_expression(&mut wstmt.object);
+            visitor.visit_directive(&mut wstmt.body);
+        },
+        Directive::TryStatement(trystmt) => {
+            visitor.visit_block(Rc::make_mut(&mut trystmt.block));
+            for catch_clause in trystmt.catch_clauses.iter_mut() {
+                visitor.visit_expression(&mut catch_clause.parameter.destructuring);
+                if let Some(t) = catch_clause.parameter.type_annotation.as_mut() {
+                    visitor.visit_expression(t);
+                }
+                visitor.visit_block(Rc::make_mut(&mut catch_clause.block));
+            }
+            if let Some(finally_clause) = trystmt.finally_clause.as_mut() {
+                visitor.visit_block(Rc::make_mut(&mut finally_clause.block));
+            }
+        },
+        Directive::ReturnStatement(retstmt) => {
+            if let Some(exp) = retstmt.expression.as_mut() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Directive::ThrowStatement(tstmt) => {
+            visitor.visit_expression(&mut tstmt.expression);
+        },
+        Directive::DefaultXmlNamespaceStatement(dxns) => {
+            visitor.visit_expression(&mut dxns.right);
+        },
+        Directive::UseNamespaceDirective(usedrtv) => {
+            visitor.visit_expression(&mut usedrtv.expression);
+        },
+        Directive::IncludeDirective(incdrtv) => {
+            for pckgdef in incdrtv.nested_packages.iter_mut() {
+                visitor.visit_block(Rc::make_mut(&mut Rc::make_mut(pckgdef).block));
+            }
+            for drtv in incdrtv.nested_directives.iter_mut() {
+                visitor.visit_directive(drtv);
+            }
+        },
+        Directive::ConfigurationDirective(cfgdrtv) => {
+            visitor.visit_directive(&mut cfgdrtv.directive);
+        },
+        Directive::DirectiveInjection(inj) => {
+            for drtv in inj.directives.get_mut().iter_mut() {
+                visitor.visit_directive(drtv);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn walk_variable_definition_mut<V: MutVisitor + ?Sized>(visitor: &mut V, defn: &mut VariableDefinition) {
+    for binding in defn.bindings.iter_mut() {
+        let binding = Rc::make_mut(binding);
+        visitor.visit_expression(&mut binding.destructuring.destructuring);
+        if let Some(t) = binding.destructuring.type_annotation.as_mut() {
+            visitor.visit_expression(t);
+        }
+        if let Some(init) = binding.initializer.as_mut() {
+            visitor.visit_expression(init);
+        }
+    }
+}
+
+pub fn walk_function_common_mut<V: MutVisitor + ?Sized>(visitor: &mut V, common: &mut Rc<FunctionCommon>) {
+    let common = Rc::make_mut(common);
+    for param in common.signature.parameters.iter_mut() {
+        let param = Rc::make_mut(param);
+        visitor.visit_expression(&mut param.destructuring.destructuring);
+        if let Some(t) = param.destructuring.type_annotation.as_mut() {
+            visitor.visit_expression(t);
+        }
+        if let Some(exp) = param.default_value.as_mut() {
+            visitor.visit_expression(exp);
+        }
+    }
+    if let Some(t) = common.signature.result_type.as_mut() {
+        visitor.visit_expression(t);
+    }
+    match &mut common.body {
+        Some(FunctionBody::Block(block)) => {
+            visitor.visit_block(Rc::make_mut(block));
+        },
+        Some(FunctionBody::Expression(exp)) => {
+            visitor.visit_expression(exp);
+        },
+        None => {},
+    }
+}
+
+pub fn walk_qualified_identifier_mut<V: MutVisitor + ?Sized>(visitor: &mut V, id: &mut QualifiedIdentifier) {
+    if let Some(qualifier) = id.qualifier.as_mut() {
+        visitor.visit_expression(qualifier);
+    }
+    if let QualifiedIdentifierIdentifier::Brackets(exp) = &mut id.id {
+        visitor.visit_expression(exp);
+    }
+}
+
+pub fn walk_expression_mut<V: MutVisitor + ?Sized>(visitor: &mut V, exp: &mut Rc<Expression>) {
+    match Rc::make_mut(exp) {
+        Expression::QualifiedIdentifier(id) => {
+            visitor.visit_qualified_identifier(id);
+        },
+        Expression::Member(e) => {
+            visitor.visit_expression(&mut e.base);
+            visitor.visit_qualified_identifier(&mut e.identifier);
+        },
+        Expression::ComputedMember(e) => {
+            visitor.visit_expression(&mut e.base);
+            visitor.visit_expression(&mut e.key);
+        },
+        Expression::Paren(e) => {
+            visitor.visit_expression(&mut e.expression);
+        },
+        Expression::Xml(e) => {
+            walk_xml_element_mut(visitor, Rc::make_mut(&mut e.element));
+        },
+        Expression::XmlList(e) => {
+            for content in e.content.iter_mut() {
+                walk_xml_content_mut(visitor, content);
+            }
+        },
+        Expression::ArrayLiteral(e) => {
+            walk_elements_mut(visitor, &mut e.elements);
+        },
+        Expression::VectorLiteral(e) => {
+            visitor.visit_expression(&mut e.element_type);
+            walk_elements_mut(visitor, &mut e.elements);
+        },
+        Expression::ObjectInitializer(e) => {
+            for field in e.fields.iter_mut() {
+                match Rc::make_mut(field) {
+                    InitializerField::Field { name, value, .. } => {
+                        match &mut name.0 {
+                            FieldName::Identifier(id) => {
+                                visitor.visit_qualified_identifier(id);
+                            },
+                            FieldName::Brackets(exp) |
+                            FieldName::NumericLiteral(exp) |
+                            FieldName::StringLiteral(exp) => {
+                                visitor.visit_expression(exp);
+                            },
+                        }
+                        if let Some(value) = value.as_mut() {
+                            visitor.visit_expression(value);
+                        }
+                    },
+                    InitializerField::Rest((exp, _)) => {
+                        visitor.visit_expression(exp);
+                    },
+                }
+            }
+        },
+        Expression::New(e) => {
+            visitor.visit_expression(&mut e.base);
+            if let Some(arguments) = e.arguments.as_mut() {
+                for arg in arguments.iter_mut() {
+                    visitor.visit_expression(arg);
+                }
+            }
+        },
+        Expression::Descendants(e) => {
+            visitor.visit_expression(&mut e.base);
+            visitor.visit_qualified_identifier(&mut e.identifier);
+        },
+        Expression::Filter(e) => {
+            visitor.visit_expression(&mut e.base);
+            visitor.visit_expression(&mut e.test);
+        },
+        Expression::Super(e) => {
+            if let Some(object) = e.object.as_mut() {
+                for exp in object.iter_mut() {
+                    visitor.visit_expression(exp);
+                }
+            }
+        },
+        Expression::Call(e) => {
+            visitor.visit_expression(&mut e.base);
+            for arg in e.arguments.iter_mut() {
+                visitor.visit_expression(arg);
+            }
+        },
+        Expression::WithTypeArguments(e) => {
+            visitor.visit_expression(&mut e.base);
+            for arg in e.arguments.iter_mut() {
+                visitor.visit_expression(arg);
+            }
+        },
+        Expression::Unary(e) => {
+            visitor.visit_expression(&mut e.expression);
+        },
+        Expression::OptionalChaining(e) => {
+            visitor.visit_expression(&mut e.base);
+            visitor.visit_expression(&mut e.expression);
+        },
+        Expression::Binary(e) => {
+            visitor.visit_expression(&mut e.left);
+            visitor.visit_expression(&mut e.right);
+        },
+        Expression::Conditional(e) => {
+            visitor.visit_expression(&mut e.test);
+            visitor.visit_expression(&mut e.consequent);
+            visitor.visit_expression(&mut e.alternative);
+        },
+        Expression::Sequence(e) => {
+            visitor.visit_expression(&mut e.left);
+            visitor.visit_expression(&mut e.right);
+        },
+        Expression::NullableType(e) => {
+            visitor.visit_expression(&mut e.base);
+        },
+        Expression::NonNullableType(e) => {
+            visitor.visit_expression(&mut e.base);
+        },
+        Expression::ArrayType(e) => {
+            visitor.visit_expression(&mut e.expression);
+        },
+        Expression::TupleType(e) => {
+            for exp in e.expressions.iter_mut() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Expression::FunctionType(e) => {
+            for param in e.parameters.iter_mut() {
+                if let Some(t) = Rc::make_mut(param).type_expression.as_mut() {
+                    visitor.visit_expression(t);
+                }
+            }
+            if let Some(t) = e.result_type.as_mut() {
+                visitor.visit_expression(t);
+            }
+        },
+        Expression::Assignment(e) => {
+            visitor.visit_expression(&mut e.left);
+            visitor.visit_expression(&mut e.right);
+        },
+        Expression::Function(e) => {
+            visitor.visit_function_common(&mut e.common);
+        },
+        _ => {},
+    }
+}
+
+fn walk_elements_mut<V: MutVisitor + ?Sized>(visitor: &mut V, elements: &mut [Element]) {
+    for elem in elements.iter_mut() {
+        match elem {
+            Element::Expression(exp) => {
+                visitor.visit_expression(exp);
+            },
+            Element::Rest((exp, _)) => {
+                visitor.visit_expression(exp);
+            },
+            Element::Elision => {},
+        }
+    }
+}
+
+fn walk_xml_element_mut<V: MutVisitor + ?Sized>(visitor: &mut V, elem: &mut XmlElement) {
+    if let XmlTagName::Expression(exp) = &mut elem.name {
+        visitor.visit_expression(exp);
+    }
+    for attr in elem.attributes.iter_mut() {
+        if let XmlAttributeValue::Expression(exp) = &mut Rc::make_mut(attr).value {
+            visitor.visit_expression(exp);
+        }
+    }
+    if let Some(exp) = elem.attribute_expression.as_mut() {
+        visitor.visit_expression(exp);
+    }
+    if let Some(content_list) = elem.content.as_mut() {
+        for content in content_list.iter_mut() {
+            walk_xml_content_mut(visitor, content);
+        }
+    }
+    if let Some(XmlTagName::Expression(exp)) = elem.closing_name.as_mut() {
+        visitor.visit_expression(exp);
+    }
+}
+
+fn walk_xml_content_mut<V: MutVisitor + ?Sized>(visitor: &mut V, content: &mut Rc<XmlContent>) {
+    match Rc::make_mut(content) {
+        XmlContent::Element(elem) => {
+            walk_xml_element_mut(visitor, Rc::make_mut(elem));
+        },
+        XmlContent::Expression(exp) => {
+            visitor.visit_expression(exp);
+        },
+        _ => {},
+    }
+}
\ No newline at end of file
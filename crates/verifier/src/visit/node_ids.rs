@@ -0,0 +1,93 @@
+use crate::ns::*;
+
+/// Identifier of a directive or expression within its program: its
+/// index in a pre-order traversal.
+///
+/// Unlike the `Rc` addresses keying `node_mapping()`, node IDs are the
+/// same for every parse of the same text, so they can be serialized
+/// and matched against a re-parsed program.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct NodeId(pub u32);
+
+#[derive(Clone)]
+pub enum IdentifiedNode {
+    Directive(Rc<Directive>),
+    Expression(Rc<Expression>),
+}
+
+/// Node IDs of a program.
+///
+/// ```ignore
+/// let ids = NodeIds::assign(&program);
+/// let id = ids.id_of_expression(&exp).unwrap();
+/// for (id, entity) in ids.entities(&host) {
+///     // persist (file path, id, entity)
+/// }
+/// ```
+///
+/// The parser does not assign IDs itself yet, so they are computed by a
+/// traversal after parsing; `node_mapping()` remains the source of truth
+/// for the verifier.
+pub struct NodeIds {
+    by_address: HashMap<*const (), NodeId>,
+    nodes: Vec<IdentifiedNode>,
+}
+
+impl NodeIds {
+    pub fn assign(program: &Rc<Program>) -> Self {
+        let mut ids = Self {
+            by_address: HashMap::new(),
+            nodes: vec![],
+        };
+        ids.visit_program(program);
+        ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn id_of_directive(&self, drtv: &Rc<Directive>) -> Option<NodeId> {
+        self.by_address.get(&(Rc::as_ptr(drtv) as *const ())).copied()
+    }
+
+    pub fn id_of_expression(&self, exp: &Rc<Expression>) -> Option<NodeId> {
+        self.by_address.get(&(Rc::as_ptr(exp) as *const ())).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&IdentifiedNode> {
+        self.nodes.get(id.0 as usize)
+    }
+
+    /// Entities assigned by the verifier to the identified nodes, in ID order.
+    pub fn entities(&self, host: &Database) -> Vec<(NodeId, Entity)> {
+        self.nodes.iter().enumerate().filter_map(|(i, node)| {
+            let entity = match node {
+                IdentifiedNode::Directive(drtv) => host.node_mapping().get(drtv),
+                IdentifiedNode::Expression(exp) => host.node_mapping().get(exp),
+            };
+            entity.map(|entity| (NodeId(i as u32), entity))
+        }).collect()
+    }
+
+    fn push(&mut self, address: *const (), node: IdentifiedNode) {
+        self.by_address.insert(address, NodeId(self.nodes.len() as u32));
+        self.nodes.push(node);
+    }
+}
+
+impl Visitor for NodeIds {
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        self.push(Rc::as_ptr(drtv) as *const (), IdentifiedNode::Directive(drtv.clone()));
+        walk_directive(self, drtv);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        self.push(Rc::as_ptr(exp) as *const (), IdentifiedNode::Expression(exp.clone()));
+        walk_expression(self, exp);
+    }
+}
\ No newline at end of file
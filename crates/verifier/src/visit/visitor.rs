@@ -0,0 +1,471 @@
+use crate::ns::*;
+
+/// Read-only traversal over programs, directives and expressions.
+///
+/// Every method walks into the children of the node by default;
+/// an override may call the respective `walk_*` function to
+/// continue the traversal.
+///
+/// ```ignore
+/// struct CallCounter(usize);
+///
+/// impl Visitor for CallCounter {
+///     fn visit_expression(&mut self, exp: &Rc<Expression>) {
+///         if matches!(exp.as_ref(), Expression::Call(_)) {
+///             self.0 += 1;
+///         }
+///         walk_expression(self, exp);
+///     }
+/// }
+/// ```
+pub trait Visitor {
+    fn visit_program(&mut self, program: &Rc<Program>) {
+        walk_program(self, program);
+    }
+
+    fn visit_directive(&mut self, drtv: &Rc<Directive>) {
+        walk_directive(self, drtv);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        walk_expression(self, exp);
+    }
+
+    fn visit_qualified_identifier(&mut self, id: &QualifiedIdentifier) {
+        walk_qualified_identifier(self, id);
+    }
+
+    fn visit_function_common(&mut self, common: &Rc<FunctionCommon>) {
+        walk_function_common(self, common);
+    }
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Rc<Program>) {
+    for pckgdef in program.packages.iter() {
+        visitor.visit_block(&pckgdef.block);
+    }
+    for drtv in program.directives.iter() {
+        visitor.visit_directive(drtv);
+    }
+}
+
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Block) {
+    for drtv in block.directives.iter() {
+        visitor.visit_directive(drtv);
+    }
+}
+
+pub fn walk_directive<V: Visitor + ?Sized>(visitor: &mut V, drtv: &Rc<Directive>) {
+    match drtv.as_ref() {
+        Directive::VariableDefinition(defn) => {
+            walk_variable_definition(visitor, defn);
+        },
+        Directive::FunctionDefinition(defn) => {
+            visitor.visit_function_common(&defn.common);
+        },
+        Directive::ClassDefinition(defn) => {
+            if let Some(exp) = defn.extends_clause.as_ref() {
+                visitor.visit_expression(exp);
+            }
+            if let Some(list) = defn.implements_clause.as_ref() {
+                for exp in list.iter() {
+                    visitor.visit_expression(exp);
+                }
+            }
+            visitor.visit_block(&defn.block);
+        },
+        Directive::EnumDefinition(defn) => {
+            visitor.visit_block(&defn.block);
+        },
+        Directive::InterfaceDefinition(defn) => {
+            if let Some(list) = defn.extends_clause.as_ref() {
+                for exp in list.iter() {
+                    visitor.visit_expression(exp);
+                }
+            }
+            visitor.visit_block(&defn.block);
+        },
+        Directive::TypeDefinition(defn) => {
+            visitor.visit_expression(&defn.right);
+        },
+        Directive::NamespaceDefinition(defn) => {
+            if let Some(exp) = defn.right.as_ref() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Directive::Block(block) => {
+            visitor.visit_block(block);
+        },
+        Directive::ExpressionStatement(estmt) => {
+            visitor.visit_expression(&estmt.expression);
+        },
+        Directive::SuperStatement(supstmt) => {
+            for exp in supstmt.arguments.iter() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Directive::LabeledStatement(labstmt) => {
+            visitor.visit_directive(&labstmt.substatement);
+        },
+        Directive::IfStatement(ifstmt) => {
+            visitor.visit_expression(&ifstmt.test);
+            visitor.visit_directive(&ifstmt.consequent);
+            if let Some(alt) = ifstmt.alternative.as_ref() {
+                visitor.visit_directive(alt);
+            }
+        },
+        Directive::SwitchStatement(swstmt) => {
+            visitor.visit_expression(&swstmt.discriminant);
+            for case in swstmt.cases.iter() {
+                for label in case.labels.iter() {
+                    if let CaseLabel::Case((exp, _)) = label {
+                        visitor.visit_expression(exp);
+                    }
+                }
+                for drtv in case.directives.iter() {
+                    visitor.visit_directive(drtv);
+                }
+            }
+        },
+        Directive::SwitchTypeStatement(swstmt) => {
+            visitor.visit_expression(&swstmt.discriminant);
+            for case in swstmt.cases.iter() {
+                if let Some(parameter) = case.parameter.as_ref() {
+                    visitor.visit_expression(&parameter.destructuring);
+                    if let Some(t) = parameter.type_annotation.as_ref() {
+                        visitor.visit_expression(t);
+                    }
+                }
+                visitor.visit_block(&case.block);
+            }
+        },
+        Directive::DoStatement(dostmt) => {
+            visitor.visit_directive(&dostmt.body);
+            visitor.visit_expression(&dostmt.test);
+        },
+        Directive::WhileStatement(wstmt) => {
+            visitor.visit_expression(&wstmt.test);
+            visitor.visit_directive(&wstmt.body);
+        },
+        Directive::ForStatement(forstmt) => {
+            match forstmt.init.as_ref() {
+                Some(ForInitializer::Expression(exp)) => {
+                    visitor.visit_expression(exp);
+                },
+                Some(ForInitializer::VariableDefinition(defn)) => {
+                    walk_variable_definition(visitor, defn);
+                },
+                None => {},
+            }
+            if let Some(exp) = forstmt.test.as_ref() {
+                visitor.visit_expression(exp);
+            }
+            if let Some(exp) = forstmt.update.as_ref() {
+                visitor.visit_expression(exp);
+            }
+            visitor.visit_directive(&forstmt.body);
+        },
+        Directive::ForInStatement(forstmt) => {
+            match &forstmt.left {
+                ForInBinding::Expression(exp) => {
+                    visitor.visit_expression(exp);
+                },
+                ForInBinding::VariableDefinition(defn) => {
+                    walk_variable_definition(visitor, defn);
+                },
+            }
+            visitor.visit_expression(&forstmt.right);
+            visitor.visit_directive(&forstmt.body);
+        },
+        Directive::WithStatement(wstmt) => {
+            visitor.visit_expression(&wstmt.object);
+            visitor.visit_directive(&wstmt.body);
+        },
+        Directive::TryStatement(trystmt) => {
+            visitor.visit_block(&trystmt.block);
+            for catch_clause in trystmt.catch_clauses.iter() {
+                visitor.visit_expression(&catch_clause.parameter.destructuring);
+                if let Some(t) = catch_clause.parameter.type_annotation.as_ref() {
+                    visitor.visit_expression(t);
+                }
+                visitor.visit_block(&catch_clause.block);
+            }
+            if let Some(finally_clause) = trystmt.finally_clause.as_ref() {
+                visitor.visit_block(&finally_clause.block);
+            }
+        },
+        Directive::ReturnStatement(retstmt) => {
+            if let Some(exp) = retstmt.expression.as_ref() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Directive::ThrowStatement(tstmt) => {
+            visitor.visit_expression(&tstmt.expression);
+        },
+        Directive::DefaultXmlNamespaceStatement(dxns) => {
+            visitor.visit_expression(&dxns.right);
+        },
+        Directive::UseNamespaceDirective(usedrtv) => {
+            visitor.visit_expression(&usedrtv.expression);
+        },
+        Directive::IncludeDirective(incdrtv) => {
+            for pckgdef in incdrtv.nested_packages.iter() {
+                visitor.visit_block(&pckgdef.block);
+            }
+            for drtv in incdrtv.nested_directives.iter() {
+                visitor.visit_directive(drtv);
+            }
+        },
+        Directive::ConfigurationDirective(cfgdrtv) => {
+            visitor.visit_directive(&cfgdrtv.directive);
+        },
+        Directive::DirectiveInjection(inj) => {
+            for drtv in inj.directives.borrow().iter() {
+                visitor.visit_directive(drtv);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn walk_variable_definition<V: Visitor + ?Sized>(visitor: &mut V, defn: &VariableDefinition) {
+    for binding in defn.bindings.iter() {
+        visitor.visit_expression(&binding.destructuring.destructuring);
+        if let Some(t) = binding.destructuring.type_annotation.as_ref() {
+            visitor.visit_expression(t);
+        }
+        if let Some(init) = binding.initializer.as_ref() {
+            visitor.visit_expression(init);
+        }
+    }
+}
+
+pub fn walk_function_common<V: Visitor + ?Sized>(visitor: &mut V, common: &Rc<FunctionCommon>) {
+    for param in common.signature.parameters.iter() {
+        visitor.visit_expression(&param.destructuring.destructuring);
+        if let Some(t) = param.destructuring.type_annotation.as_ref() {
+            visitor.visit_expression(t);
+        }
+        if let Some(exp) = param.default_value.as_ref() {
+            visitor.visit_expression(exp);
+        }
+    }
+    if let Some(t) = common.signature.result_type.as_ref() {
+        visitor.visit_expression(t);
+    }
+    match &common.body {
+        Some(FunctionBody::Block(block)) => {
+            visitor.visit_block(block);
+        },
+        Some(FunctionBody::Expression(exp)) => {
+            visitor.visit_expression(exp);
+        },
+        None => {},
+    }
+}
+
+pub fn walk_qualified_identifier<V: Visitor + ?Sized>(visitor: &mut V, id: &QualifiedIdentifier) {
+    if let Some(qualifier) = id.qualifier.as_ref() {
+        visitor.visit_expression(qualifier);
+    }
+    if let QualifiedIdentifierIdentifier::Brackets(exp) = &id.id {
+        visitor.visit_expression(exp);
+    }
+}
+
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, exp: &Rc<Expression>) {
+    match exp.as_ref() {
+        Expression::QualifiedIdentifier(id) => {
+            visitor.visit_qualified_identifier(id);
+        },
+        Expression::Member(e) => {
+            visitor.visit_expression(&e.base);
+            visitor.visit_qualified_identifier(&e.identifier);
+        },
+        Expression::ComputedMember(e) => {
+            visitor.visit_expression(&e.base);
+            visitor.visit_expression(&e.key);
+        },
+        Expression::Paren(e) => {
+            visitor.visit_expression(&e.expression);
+        },
+        Expression::Xml(e) => {
+            walk_xml_element(visitor, &e.element);
+        },
+        Expression::XmlList(e) => {
+            for content in e.content.iter() {
+                walk_xml_content(visitor, content);
+            }
+        },
+        Expression::ArrayLiteral(e) => {
+            walk_elements(visitor, &e.elements);
+        },
+        Expression::VectorLiteral(e) => {
+            visitor.visit_expression(&e.element_type);
+            walk_elements(visitor, &e.elements);
+        },
+        Expression::ObjectInitializer(e) => {
+            for field in e.fields.iter() {
+                match field.as_ref() {
+                    InitializerField::Field { name, value, .. } => {
+                        match &name.0 {
+                            FieldName::Identifier(id) => {
+                                visitor.visit_qualified_identifier(id);
+                            },
+                            FieldName::Brackets(exp) |
+                            FieldName::NumericLiteral(exp) |
+                            FieldName::StringLiteral(exp) => {
+                                visitor.visit_expression(exp);
+                            },
+                        }
+                        if let Some(value) = value.as_ref() {
+                            visitor.visit_expression(value);
+                        }
+                    },
+                    InitializerField::Rest((exp, _)) => {
+                        visitor.visit_expression(exp);
+                    },
+                }
+            }
+        },
+        Expression::New(e) => {
+            visitor.visit_expression(&e.base);
+            if let Some(arguments) = e.arguments.as_ref() {
+                for arg in arguments.iter() {
+                    visitor.visit_expression(arg);
+                }
+            }
+        },
+        Expression::Descendants(e) => {
+            visitor.visit_expression(&e.base);
+            visitor.visit_qualified_identifier(&e.identifier);
+        },
+        Expression::Filter(e) => {
+            visitor.visit_expression(&e.base);
+            visitor.visit_expression(&e.test);
+        },
+        Expression::Super(e) => {
+            if let Some(object) = e.object.as_ref() {
+                for exp in object.iter() {
+                    visitor.visit_expression(exp);
+                }
+            }
+        },
+        Expression::Call(e) => {
+            visitor.visit_expression(&e.base);
+            for arg in e.arguments.iter() {
+                visitor.visit_expression(arg);
+            }
+        },
+        Expression::WithTypeArguments(e) => {
+            visitor.visit_expression(&e.base);
+            for arg in e.arguments.iter() {
+                visitor.visit_expression(arg);
+            }
+        },
+        Expression::Unary(e) => {
+            visitor.visit_expression(&e.expression);
+        },
+        Expression::OptionalChaining(e) => {
+            visitor.visit_expression(&e.base);
+            visitor.visit_expression(&e.expression);
+        },
+        Expression::Binary(e) => {
+            visitor.visit_expression(&e.left);
+            visitor.visit_expression(&e.right);
+        },
+        Expression::Conditional(e) => {
+            visitor.visit_expression(&e.test);
+            visitor.visit_expression(&e.consequent);
+            visitor.visit_expression(&e.alternative);
+        },
+        Expression::Sequence(e) => {
+            visitor.visit_expression(&e.left);
+            visitor.visit_expression(&e.right);
+        },
+        Expression::NullableType(e) => {
+            visitor.visit_expression(&e.base);
+        },
+        Expression::NonNullableType(e) => {
+            visitor.visit_expression(&e.base);
+        },
+        Expression::ArrayType(e) => {
+            visitor.visit_expression(&e.expression);
+        },
+        Expression::TupleType(e) => {
+            for exp in e.expressions.iter() {
+                visitor.visit_expression(exp);
+            }
+        },
+        Expression::FunctionType(e) => {
+            for param in e.parameters.iter() {
+                if let Some(t) = param.type_expression.as_ref() {
+                    visitor.visit_expression(t);
+                }
+            }
+            if let Some(t) = e.result_type.as_ref() {
+                visitor.visit_expression(t);
+            }
+        },
+        Expression::Assignment(e) => {
+            visitor.visit_expression(&e.left);
+            visitor.visit_expression(&e.right);
+        },
+        Expression::Function(e) => {
+            visitor.visit_function_common(&e.common);
+        },
+        _ => {},
+    }
+}
+
+fn walk_elements<V: Visitor + ?Sized>(visitor: &mut V, elements: &[Element]) {
+    for elem in elements.iter() {
+        match elem {
+            Element::Expression(exp) => {
+                visitor.visit_expression(exp);
+            },
+            Element::Rest((exp, _)) => {
+                visitor.visit_expression(exp);
+            },
+            Element::Elision => {},
+        }
+    }
+}
+
+fn walk_xml_element<V: Visitor + ?Sized>(visitor: &mut V, elem: &XmlElement) {
+    if let XmlTagName::Expression(exp) = &elem.name {
+        visitor.visit_expression(exp);
+    }
+    for attr in elem.attributes.iter() {
+        if let XmlAttributeValue::Expression(exp) = &attr.value {
+            visitor.visit_expression(exp);
+        }
+    }
+    if let Some(exp) = elem.attribute_expression.as_ref() {
+        visitor.visit_expression(exp);
+    }
+    if let Some(content_list) = elem.content.as_ref() {
+        for content in content_list.iter() {
+            walk_xml_content(visitor, content);
+        }
+    }
+    if let Some(XmlTagName::Expression(exp)) = elem.closing_name.as_ref() {
+        visitor.visit_expression(exp);
+    }
+}
+
+fn walk_xml_content<V: Visitor + ?Sized>(visitor: &mut V, content: &Rc<XmlContent>) {
+    match content.as_ref() {
+        XmlContent::Element(elem) => {
+            walk_xml_element(visitor, elem);
+        },
+        XmlContent::Expression(exp) => {
+            visitor.visit_expression(exp);
+        },
+        _ => {},
+    }
+}
\ No newline at end of file
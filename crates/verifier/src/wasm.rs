@@ -0,0 +1,43 @@
+//! JavaScript API for in-browser playgrounds, built for
+//! `wasm32-unknown-unknown` with `--no-default-features --features wasm`.
+//!
+//! ```js
+//! import { verify_source } from "whackengine_verifier";
+//! const diagnostics = JSON.parse(verify_source("Main.as", "trace(x);"));
+//! ```
+
+use crate::ns::*;
+use wasm_bindgen::prelude::*;
+
+/// Parses and verifies a single ActionScript source, returning its
+/// diagnostics as a JSON array of objects with the `severity`, `id`,
+/// `message`, `line` and `column` fields.
+#[wasm_bindgen]
+pub fn verify_source(file_path: &str, text: &str) -> String {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert(file_path, text);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    diagnostics_json(&output)
+}
+
+fn diagnostics_json(output: &CompilationOutput) -> String {
+    let mut list = vec![];
+    for cu in output.compilation_units.iter() {
+        cu.sort_diagnostics();
+        for diagnostic in cu.nested_diagnostics().iter() {
+            let d = WhackDiagnostic(diagnostic);
+            let location = diagnostic.location();
+            list.push(format!(
+                "{{\"severity\":{},\"id\":{},\"message\":{},\"line\":{},\"column\":{}}}",
+                json_string(if diagnostic.is_warning() { "warning" } else { "error" }),
+                d.id(),
+                json_string(&d.format_message_english()),
+                location.first_line_number(),
+                location.first_column() + 1,
+            ));
+        }
+    }
+    format!("[{}]", list.join(","))
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use whackengine_verifier::ns::*;
+
+fn abc_with_pool(constant_pool: AbcConstantPool) -> AbcFile {
+    AbcFile { minor_version: 16, major_version: 46, constant_pool, ..AbcFile::default() }
+}
+
+fn pool_with_namespace(kind: u8) -> AbcConstantPool {
+    AbcConstantPool {
+        strings: vec!["a".to_owned()],
+        namespaces: vec![AbcNamespaceInfo { kind, name: 1 }],
+        multinames: vec![AbcMultiname::QName { attribute: false, ns: 1, name: 1 }],
+        ..AbcConstantPool::default()
+    }
+}
+
+#[test]
+fn shared_entries_are_stored_once() {
+    let files = [abc_with_pool(pool_with_namespace(AbcNamespaceInfo::PACKAGE_NAMESPACE)), abc_with_pool(pool_with_namespace(AbcNamespaceInfo::PACKAGE_NAMESPACE))];
+    let (abc, report) = AbcPoolMerger::merge(&files).unwrap();
+    assert_eq!(abc.constant_pool.strings, vec!["a".to_owned()]);
+    assert_eq!(abc.constant_pool.namespaces.len(), 1);
+    assert_eq!(abc.constant_pool.multinames.len(), 1);
+    assert_eq!(report.entries_before[3], 2);
+    assert_eq!(report.entries_after[3], 1);
+}
+
+#[test]
+fn private_namespaces_are_not_merged() {
+    let files = [abc_with_pool(pool_with_namespace(AbcNamespaceInfo::PRIVATE_NS)), abc_with_pool(pool_with_namespace(AbcNamespaceInfo::PRIVATE_NS))];
+    let (abc, _) = AbcPoolMerger::merge(&files).unwrap();
+    assert_eq!(abc.constant_pool.namespaces.len(), 2);
+    assert_eq!(abc.constant_pool.multinames, vec![
+        AbcMultiname::QName { attribute: false, ns: 1, name: 1 },
+        AbcMultiname::QName { attribute: false, ns: 2, name: 1 },
+    ]);
+}
+
+#[test]
+fn self_referencing_type_name_maps_to_no_name() {
+    let pool = AbcConstantPool {
+        multinames: vec![AbcMultiname::TypeName { name: 1, params: vec![1] }],
+        ..AbcConstantPool::default()
+    };
+    let (abc, _) = AbcPoolMerger::merge(&[abc_with_pool(pool)]).unwrap();
+    assert_eq!(abc.constant_pool.multinames, vec![AbcMultiname::TypeName { name: 0, params: vec![0] }]);
+}
+
+#[test]
+fn merged_file_round_trips() {
+    let files = [abc_with_pool(pool_with_namespace(AbcNamespaceInfo::PACKAGE_NAMESPACE)), abc_with_pool(AbcConstantPool::default())];
+    let (abc, _) = AbcPoolMerger::merge(&files).unwrap();
+    assert_eq!(AbcReader::read(&AbcWriter::write(&abc)).unwrap(), abc);
+}
\ No newline at end of file
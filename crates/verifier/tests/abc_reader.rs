@@ -0,0 +1,72 @@
+use whackengine_verifier::ns::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn read_s32_sign_extends_from_last_byte() {
+    let cases: &[(&[u8], i32)] = &[
+        (&[0x3F], 63),
+        (&[0x7F], -1),
+        (&[0x40], -64),
+        (&[0xFF, 0x00], 127),
+        (&[0x80, 0x40], -8192),
+        (&[0xFF, 0xFF, 0x7F], -1),
+        (&[0xFF, 0xFF, 0xFF, 0x7F], -1),
+        (&[0xFF, 0xFF, 0xFF, 0xFF, 0x07], i32::MAX),
+        (&[0x80, 0x80, 0x80, 0x80, 0x08], i32::MIN),
+    ];
+    for (bytes, expected) in cases.iter() {
+        assert_eq!(AbcReader::new(bytes).read_s32(), Ok(*expected), "{bytes:?}");
+    }
+}
+
+#[test]
+fn read_u32_le_is_fixed_width() {
+    let mut reader = AbcReader::new(&[0x01, 0x02, 0x03, 0x84, 0xFF]);
+    assert_eq!(reader.read_u32_le(), Ok(0x8403_0201));
+    assert_eq!(reader.offset(), 4);
+}
+
+#[test]
+fn huge_counts_fail_without_allocating() {
+    let mut bytes = vec![16, 0, 46, 0];
+    // Integers, unsigned integers, doubles, strings and namespaces.
+    bytes.extend([0, 0, 0, 0, 0]);
+    // One namespace set claiming 2^30 - 1 entries.
+    bytes.extend([2, 0xFF, 0xFF, 0xFF, 0xFF, 0x03]);
+    assert_eq!(AbcReader::read(&bytes), Err(AbcReadError::UnexpectedEof));
+}
+
+#[test]
+fn abc_round_trip() {
+    let abc = AbcReader::read(&abc_bytes()).unwrap();
+    assert_eq!((abc.minor_version, abc.major_version), (16, 46));
+}
+
+#[test]
+fn extract_reads_long_tags_and_do_abc_flags() {
+    let mut do_abc = SwfAbcTag::LAZY_INITIALIZE.to_le_bytes().to_vec();
+    do_abc.extend(b"main\0");
+    do_abc.extend(abc_bytes());
+    let swf = swf(&[(9, vec![0xFF, 0xFF, 0xFF]), (SwfAbcTag::DO_ABC, do_abc), (SwfAbcTag::DO_ABC1, abc_bytes())]);
+
+    let tags = SwfAbcTag::extract(&swf).unwrap();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].flags, SwfAbcTag::LAZY_INITIALIZE);
+    assert_eq!(tags[0].name, "main");
+    assert_eq!(tags[0].bytes, abc_bytes());
+    assert!(tags[1].read_abc().is_ok());
+}
+
+#[test]
+fn extract_rejects_compressed_swf() {
+    assert_eq!(SwfAbcTag::extract(b"CWS\x0a\0\0\0\0"), Err(AbcReadError::UnsupportedSwf));
+}
+
+#[test]
+fn truncated_tag_is_an_error() {
+    let mut swf = swf(&[(SwfAbcTag::DO_ABC1, abc_bytes())]);
+    swf.truncate(swf.len() - 4);
+    assert_eq!(SwfAbcTag::extract(&swf), Err(AbcReadError::UnexpectedEof));
+}
\ No newline at end of file
@@ -0,0 +1,130 @@
+use std::io::{Cursor, Read};
+use whackengine_verifier::ns::*;
+
+/// A 4x4 PNG of half transparent red.
+fn png() -> Vec<u8> {
+    let image = image::RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 128]));
+    let mut r = Cursor::new(vec![]);
+    image.write_to(&mut r, image::ImageFormat::Png).unwrap();
+    r.into_inner()
+}
+
+/// A 32x32 JPEG of noise at the highest quality.
+fn jpeg() -> Vec<u8> {
+    let image = image::RgbImage::from_fn(32, 32, |x, y| image::Rgb([(x * 37 % 256) as u8, (y * 91 % 256) as u8, ((x ^ y) * 53 % 256) as u8]));
+    let mut r = vec![];
+    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut r, 100).encode_image(&image).unwrap();
+    r
+}
+
+fn options(compression: bool, quality: Option<u8>) -> BitmapEmbedOptions {
+    BitmapEmbedOptions { source: "logo.png".into(), compression, quality }
+}
+
+#[test]
+fn png_bitmaps_are_decoded_into_premultiplied_pixels() {
+    let mut assets = CodegenBitmapAssets::new(false);
+    assert!(assets.add("Main.Logo", "logo.png", &options(false, None), &png()));
+    let asset = &assets.assets[0];
+    assert_eq!(asset.tag, BitmapTagKind::DefineBitsLossless2);
+
+    let tag = asset.tag(7);
+    let header = u16::from_le_bytes([tag[0], tag[1]]);
+    assert_eq!(header >> 6, 36);
+    let body = if header & 0x3F == 0x3F { &tag[6..] } else { &tag[2..] };
+    assert_eq!(&body[..7], &[7, 0, 5, 4, 0, 4, 0]);
+    let mut pixels = vec![];
+    flate2::read::ZlibDecoder::new(&body[7..]).read_to_end(&mut pixels).unwrap();
+    assert_eq!(pixels.len(), 4 * 4 * 4);
+    assert_eq!(&pixels[..4], &[128, 128, 0, 0]);
+}
+
+#[test]
+fn identical_bitmaps_share_a_character() {
+    let mut assets = CodegenBitmapAssets::new(false);
+    assets.add("Main.Logo", "logo.png", &options(false, None), &png());
+    assets.add("Other.Logo", "copy/logo.png", &options(false, None), &png());
+    assert_eq!(assets.assets[1].shared_with, Some(0));
+    assert_eq!(assets.assets[1].emitted_size, 0);
+    assert!(assets.format_report().contains("shared with Main.Logo"));
+}
+
+#[test]
+fn compression_recompresses_lossless_bitmaps_into_jpeg() {
+    let mut assets = CodegenBitmapAssets::new(false);
+    assets.add("Main.Logo", "logo.png", &options(true, None), &png());
+    let asset = &assets.assets[0];
+    // The alpha channel goes alongside the JPEG data
+    assert_eq!(asset.tag, BitmapTagKind::DefineBitsJpeg3);
+    assert_eq!(asset.recompressed_quality, Some(CodegenBitmapAssets::DEFAULT_QUALITY));
+    assert!(assets.format_report().contains("recompressed as JPEG"));
+}
+
+#[test]
+fn jpeg_bitmaps_are_recompressed_at_a_lower_quality() {
+    let data = jpeg();
+    let mut assets = CodegenBitmapAssets::new(false);
+    assets.add("Main.Photo", "photo.jpg", &options(false, None), &data);
+    assets.add("Main.Thumbnail", "photo.jpg", &options(false, Some(10)), &data);
+    let (original, recompressed) = (&assets.assets[0], &assets.assets[1]);
+    assert_eq!((original.tag, recompressed.tag), (BitmapTagKind::DefineBitsJpeg2, BitmapTagKind::DefineBitsJpeg2));
+    assert_eq!(original.emitted_size, data.len() + 8);
+    assert_eq!(original.recompressed_quality, None);
+    assert_eq!(recompressed.recompressed_quality, Some(10));
+    assert!(recompressed.emitted_size < original.emitted_size);
+}
+
+#[test]
+fn optimized_png_bitmaps_are_stored_as_is() {
+    let data = png();
+    let mut assets = CodegenBitmapAssets::new(true);
+    assets.add("Main.Logo", "logo.png", &options(false, None), &data);
+    assert_eq!(assets.assets[0].tag, BitmapTagKind::DefineBitsJpeg2);
+    assert_eq!(assets.assets[0].emitted_size, data.len() + 8);
+}
+
+#[test]
+fn bitmap_report_reads_bitmaps_through_the_source_provider() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert_bytes("assets/logo.png", &png());
+    sources.insert("Main.as", r#"
+package {
+    public class Main {
+        [Embed(source="assets/logo.png")]
+        public static const Logo: Class;
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { embedded_bitmap_report: true, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let report = output.artifacts.iter().find(|a| a.path == CodegenBitmapAssets::REPORT_FILE_NAME).expect("missing report");
+    let report = String::from_utf8_lossy(&report.bytes);
+    assert!(report.contains("Main.Logo\n  assets/logo.png (PNG 4x4 with alpha)"), "{report}");
+    assert!(report.contains("as DefineBitsLossless2"), "{report}");
+}
+
+#[test]
+fn bitmap_headers_give_format_dimensions_and_alpha() {
+    assert_eq!(BitmapInfo::read(&png()), Some(BitmapInfo { format: BitmapFormat::Png, width: 4, height: 4, has_alpha: true }));
+    let mut opaque = Cursor::new(vec![]);
+    image::RgbImage::from_pixel(3, 2, image::Rgb([0, 0, 255])).write_to(&mut opaque, image::ImageFormat::Png).unwrap();
+    assert_eq!(BitmapInfo::read(opaque.get_ref()), Some(BitmapInfo { format: BitmapFormat::Png, width: 3, height: 2, has_alpha: false }));
+    assert_eq!(BitmapInfo::read(&jpeg()), Some(BitmapInfo { format: BitmapFormat::Jpeg, width: 32, height: 32, has_alpha: false }));
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(5u16.to_le_bytes());
+    gif.extend(6u16.to_le_bytes());
+    gif.extend([0, 0, 0, 0x21, 0xF9, 4, 1, 0, 0, 0, 0]);
+    assert_eq!(BitmapInfo::read(&gif), Some(BitmapInfo { format: BitmapFormat::Gif, width: 5, height: 6, has_alpha: true }));
+}
+
+#[test]
+fn truncated_or_unknown_bitmaps_have_no_header() {
+    assert_eq!(BitmapInfo::read(&png()[..20]), None);
+    assert_eq!(BitmapInfo::read(&jpeg()[..4]), None);
+    assert_eq!(BitmapInfo::read(b"GIF89a\x05"), None);
+    assert_eq!(BitmapInfo::read(b"BM not supported"), None);
+    // A JPEG segment length pointing past the end
+    assert_eq!(BitmapInfo::read(&[0xFF, 0xD8, 0xFF, 0xE0, 0xFF, 0xFF]), None);
+}
\ No newline at end of file
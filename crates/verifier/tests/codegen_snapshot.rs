@@ -0,0 +1,41 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+const FIXTURE: &str = r#"
+package {
+    public function f(o: Object): void {
+        var {x, y} = o;
+        trace(x, y);
+    }
+}
+"#;
+
+fn snapshot() -> CodegenSnapshot {
+    let host = Rc::new(Database::new(Default::default()));
+    CodegenSnapshot::compile(&host, &Rc::new(CompilerOptions::default()), "Main.as", FIXTURE)
+}
+
+#[test]
+fn snapshots_hold_the_abc_asm_listing_of_the_fixture() {
+    let snapshot = snapshot();
+    assert!(snapshot.listing.starts_with("; Main.as.abc-asm.txt\nmethod#"), "{}", snapshot.listing);
+    assert!(snapshot.listing.contains("destructuring {x, y}\n"), "{}", snapshot.listing);
+    assert!(snapshot.listing.contains("x = getproperty x\n"), "{}", snapshot.listing);
+    snapshot.assert_matches(&snapshot.listing.replace('\n', "\r\n"));
+}
+
+#[test]
+#[should_panic(expected = "codegen output differs from the golden listing")]
+fn snapshots_differing_from_the_golden_listing_panic() {
+    let snapshot = snapshot();
+    snapshot.assert_matches(&snapshot.listing.replace("getproperty x", "getproperty z"));
+}
+
+#[test]
+fn diffs_show_changed_lines_with_context() {
+    assert_eq!(CodegenSnapshot::diff("a\nb\nc\n", "a\nx\nc\n"), "  a\n+ x\n- b\n  c\n");
+    let expected = (0..10).map(|i| format!("{i}\n")).collect::<String>();
+    let actual = expected.replace("9\n", "nine\n");
+    assert_eq!(CodegenSnapshot::diff(&expected, &actual), "  ...\n  6\n  7\n  8\n+ nine\n- 9\n");
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use whackengine_verifier::ns::*;
+
+fn run(sources: &MemorySourceProvider, codemod: Rc<dyn Codemod>) -> CodemodReport {
+    CodemodRunner::run(&Rc::new(CompilerOptions::default()), sources, &[codemod])
+}
+
+#[test]
+fn arrays_of_integers_become_vectors() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public class Main {
+        public var counts:Array /* of int */ = [1, 2];
+        public var ratios:Array /* of int */ = [1, 1.5];
+    }
+}
+"#);
+    let report = run(&sources, builtin_codemod("array-to-vector").unwrap());
+    let text = &report.files["Main.as"];
+    assert!(text.contains("public var counts:Vector.<int> = new <int>[1, 2];"), "{text}");
+    assert!(text.contains("public var ratios:Array /* of int */ = [1, 1.5];"), "{text}");
+}
+
+#[test]
+fn element_types_are_compared_by_qualified_name() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("a/Item.as", "package a { public class Item {} }");
+    sources.insert("b/Item.as", "package b { public class Item {} }");
+    sources.insert("Main.as", r#"
+package {
+    import a.Item;
+    public class Main {
+        public var items:Array /* of b.Item */ = [new Item()];
+    }
+}
+"#);
+    let report = run(&sources, builtin_codemod("array-to-vector").unwrap());
+    assert!(report.files.is_empty(), "{}", report.format_human());
+}
+
+#[test]
+fn deprecated_apis_are_replaced_and_imported() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("a/Old.as", "package a { public class Old { public static function f(): void {} } }");
+    sources.insert("b/New.as", "package b { public class New { public static function g(): void {} } }");
+    sources.insert("Main.as", r#"
+package {
+    import a.Old;
+    public function main(): void {
+        Old.f();
+    }
+}
+"#);
+    let codemod = DeprecatedApiCodemod::parse("# Moved\na.Old.f = b.New.g\n").unwrap();
+    let report = run(&sources, Rc::new(codemod));
+    let text = &report.files["Main.as"];
+    assert!(text.contains("    import b.New;\n    import a.Old;\n"), "{text}");
+    assert!(text.contains("        b.New.g();"), "{text}");
+}
+
+#[test]
+fn metadata_consumed_by_the_verifier_is_supported_for_migration() {
+    assert!(is_supported_metadata("Inline"));
+    assert!(is_supported_metadata("Throws"));
+    assert!(is_supported_metadata("SkinPart"));
+    assert!(!is_supported_metadata("ArrayElementTypes"));
+}
\ No newline at end of file
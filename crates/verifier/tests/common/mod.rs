@@ -0,0 +1,39 @@
+#![allow(dead_code)]
+
+use whackengine_verifier::ns::*;
+use whackengine_verifier::testing::*;
+
+pub fn abc_bytes() -> Vec<u8> {
+    AbcWriter::write(&AbcFile { minor_version: 16, major_version: 46, ..AbcFile::default() })
+}
+
+/// An uncompressed SWF with an empty frame size rectangle followed by
+/// the given tags, each a code and a body, written with long headers.
+pub fn swf(tags: &[(u16, Vec<u8>)]) -> Vec<u8> {
+    let mut body = vec![0u8, 0, 24, 1, 0];
+    for (code, tag) in tags.iter() {
+        body.extend(((code << 6) | 0x3F).to_le_bytes());
+        body.extend((tag.len() as u32).to_le_bytes());
+        body.extend(tag.iter());
+    }
+    body.extend([0u8, 0]);
+    let mut r = b"FWS".to_vec();
+    r.push(10);
+    r.extend(((body.len() + 8) as u32).to_le_bytes());
+    r.extend(body);
+    r
+}
+
+/// Parses `text` as `Main.as`.
+pub fn parse_main(compiler_options: &Rc<CompilerOptions>, text: &str) -> Rc<Program> {
+    let cu = CompilationUnit::new(Some("Main.as".into()), text.to_owned());
+    cu.set_compiler_options(Some(compiler_options.clone()));
+    ParserFacade(&cu, ParserOptions::default()).parse_program()
+}
+
+/// Compiles `text` as `Main.as` along with the core library and asserts
+/// its diagnostics match its `// expect-*` comments.
+pub fn assert_fixture(compiler_options: CompilerOptions, text: &str) {
+    let host = Rc::new(Database::new(Default::default()));
+    VerifierFixture::verify(&host, &Rc::new(compiler_options), "Main.as", text).assert_matches();
+}
\ No newline at end of file
@@ -0,0 +1,596 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+#[test]
+fn legacy_syntax_accepts_trailing_argument_comma() {
+    assert_fixture(CompilerOptions { legacy_syntax: true, ..default() }, r#"
+package {
+    public function f(a: Number, b: Number): void {}
+}
+f(1, 2,);
+// expect-warning: LegacyTrailingComma @ 5:7
+"#);
+}
+
+#[test]
+fn const_parameters_report_reassignment() {
+    assert_fixture(CompilerOptions { const_parameters: true, ..default() }, r#"
+package {
+    public function f(a: Number): void {
+        a = 1; // expect-error: ConstParameterReassignment @ 4:9
+    }
+}
+"#);
+}
+
+#[test]
+fn build_manifest_is_an_artifact() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main {} }");
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { build_manifest: true, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    assert!(!output.invalidated, "{:?}", output.diagnostics());
+    assert!(output.artifacts.iter().any(|a| a.path == BuildManifest::FILE_NAME));
+}
+
+#[test]
+fn timings_cover_every_source_file() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main {} }");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    assert!(output.timings.hot_spots().iter().any(|(path, _)| path == "Main.as"));
+}
+
+#[test]
+fn unmeasured_phases_are_not_reported_as_zero() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main {} }");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    assert!(output.timings.measured(CompilerPhase::Parsing));
+    assert!(!output.timings.measured(CompilerPhase::Codegen));
+    assert!(output.timings.format_human().contains(&format!("  {:<24}{:>12}\n", "codegen", "n/a")), "{}", output.timings.format_human());
+    assert!(output.timings.format_json().contains("\"emit\":null"), "{}", output.timings.format_json());
+}
+
+#[test]
+fn dynamic_access_audit_is_an_artifact() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public function f(o: *): void { trace(o.x); } }");
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { dynamic_access_audit: true, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let report = output.artifacts.iter().find(|a| a.path == DynamicAccessAuditReport::FILE_NAME).expect("missing audit");
+    assert!(String::from_utf8_lossy(&report.bytes).contains("Main.as:1:"));
+}
+
+#[test]
+fn resource_directives_are_verified_and_embed_their_bundles() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("locale/en_US/strings.properties", "greeting=Hello");
+    sources.insert("Main.as", r#"
+package {
+    [Inspectable(defaultValue="@Resource(key='greeting', bundle='strings')")]
+    public class Main {}
+    [Inspectable(defaultValue="@Resource(key='farewell', bundle='strings')")]
+    public class Other {}
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions {
+        locales: vec!["en_US".into()],
+        locale_source_path: vec![format!("locale/{}", ResourceBundleRegistry::LOCALE_TOKEN)],
+        ..default()
+    });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let diagnostics = output.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(diagnostics[0].contains("farewell"));
+    let bundle_class = format!("{}.as", ResourceBundleRegistry::bundle_class_name("en_US", "strings"));
+    assert!(output.artifacts.iter().any(|a| a.path == bundle_class));
+}
+
+#[test]
+fn method_limits_are_estimated_from_the_sources() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public class Main {
+        public function f(a: Number): Number {
+            var b: Number = a;
+            var c: Number = b;
+            try {
+                return f(f(a) + c);
+            } catch (e: Error) {}
+            return 0;
+        }
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions {
+        method_limit_thresholds: Some(MethodLimitThresholds { local_count: 4, ..default() }),
+        ..default()
+    });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let f = output.method_limits["Main.as"].iter().find(|m| m.name == "Main/f").expect("missing Main/f");
+    // `this`, `a`, `b`, `c` and the exception
+    assert_eq!((f.local_count, f.max_scope_depth), (5, 1));
+    assert!(f.max_stack >= 3);
+    assert!(!output.invalidated);
+    let diagnostics = output.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(diagnostics[0].contains("'Main/f' is estimated to use 5 registers, past the threshold of 4"));
+}
+
+#[test]
+fn diagnostic_baseline_reports_only_new_diagnostics() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public function f(): void { trace(undefinedName); } }");
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    assert!(output.invalidated);
+    let baseline = DiagnosticBaseline::from_tsv(&DiagnosticBaseline::collect(&host, &output).to_tsv());
+    assert_eq!(baseline.len(), 1);
+
+    // The known error moves to another line and a new one appears
+    sources.insert("Main.as", "package {\n    public function f(): void { trace(undefinedName); }\n    public function g(): void { trace(otherName); }\n}");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let delta = baseline.compare(&host, &output);
+    assert!(delta.introduces_errors());
+    assert_eq!(delta.introduced.len(), 1, "{}", delta.format_human());
+    assert_eq!(delta.introduced[0].line, 3);
+    assert!(delta.fixed.is_empty());
+}
+
+#[test]
+fn module_pragmas_restrict_references_to_exports() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("com/example/parser/Parser.as", "// @whack-module export Parser, Missing\npackage com.example.parser { public class Parser {} }");
+    sources.insert("com/example/parser/Token.as", "package com.example.parser { public class Token {} }");
+    sources.insert("Main.as", r#"
+package {
+    import com.example.parser.*;
+    public class Main {
+        public var parser: Parser;
+        public var token: Token;
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    let diagnostics = output.diagnostics();
+    assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+    assert!(diagnostics.iter().any(|d| d.contains("Parser.as") && d.contains("'Missing'")), "{diagnostics:?}");
+    assert!(diagnostics.iter().any(|d| d.contains("Main.as") && d.contains("'Token'")), "{diagnostics:?}");
+}
+
+#[test]
+fn corpus_benchmark_counts_only_the_corpus() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main { public function f(): void { trace(1); } } }");
+    let benchmark = bench_sources(&Rc::new(CompilerOptions::default()), &sources);
+    assert!(!benchmark.invalidated);
+    assert_eq!(benchmark.files, 1);
+    assert_eq!(benchmark.statements, 3);
+}
+
+#[test]
+fn content_hashes_are_sha256_digests() {
+    assert_eq!(ContentHash::sha256(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    assert_eq!(ContentHash::sha256(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+}
+
+#[test]
+fn constant_names_given_to_get_definition_by_name_are_resolved() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("flash/utils/getDefinitionByName.as", "package flash.utils { public function getDefinitionByName(name: String): Object { return null; } }");
+    sources.insert("com/example/Main.as", r#"
+package com.example {
+    import flash.utils.getDefinitionByName;
+    public class Main {
+        public function f(): void {
+            getDefinitionByName("com.example.Main");
+            getDefinitionByName("com.example::Main");
+            getDefinitionByName("com.example.Missing");
+        }
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    let diagnostics = output.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(diagnostics[0].contains("com.example.Missing"), "{diagnostics:?}");
+}
+
+#[test]
+fn parse_cache_reuses_files_without_diagnostics() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main {} }");
+    sources.insert("Other.as", "package { public class Other { public function f(): void { trace(u); } } }");
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let cache = ParseCache::new();
+    let output = Compilation::run_with_cache(&Rc::new(Database::new(Default::default())), &compiler_options, &sources, &[], None, &cache);
+    let diagnostics = output.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(cache.hits(), 0);
+
+    // Other.as holds a diagnostic and is parsed again
+    let output = Compilation::run_with_cache(&Rc::new(Database::new(Default::default())), &compiler_options, &sources, &[], None, &cache);
+    assert_eq!(output.diagnostics(), diagnostics);
+    assert_eq!(cache.hits(), CoreLibrary::embedded_sources().len() + 1);
+
+    sources.insert("Main.as", "package { public class Main { public var x: Number; } }");
+    Compilation::run_with_cache(&Rc::new(Database::new(Default::default())), &compiler_options, &sources, &[], None, &cache);
+    assert_eq!(cache.hits(), 2 * CoreLibrary::embedded_sources().len() + 1);
+}
+
+#[test]
+fn mxml_children_are_checked_against_the_default_property() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Item.as", "package { public class Item {} }");
+    sources.insert("Other.as", "package { public class Other {} }");
+    sources.insert("List.as", r#"
+package {
+    [DefaultProperty("items")]
+    public class List {
+        public var items: Vector.<Item>;
+    }
+}
+"#);
+    sources.insert("Main.mxml", r#"<?xml version="1.0"?>
+<local:List xmlns:local="*">
+    <local:Item/>
+    <local:Other/>
+</local:List>
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    let diagnostics = output.diagnostics();
+    let incompatible = diagnostics.iter().filter(|d| d.contains("the element type of the default property 'items'")).collect::<Vec<_>>();
+    assert_eq!(incompatible.len(), 1, "{diagnostics:?}");
+    assert!(incompatible[0].contains("Other"), "{diagnostics:?}");
+}
+
+#[test]
+fn abc_asm_lists_the_methods_of_source_files() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main { public function f(a: Number): Number { return a + 1; } } }");
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("method#0 script initializer"), "{listing}");
+    assert!(listing.contains("method#1 Main/f\n  ; max_stack=2 locals=2"), "{listing}");
+}
+
+#[test]
+fn abc_asm_lists_destructuring_accesses() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public function f(point: Object): void {
+        var {x, y: [a]} = point;
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("4:13  destructuring {x, y: [a]}\n"), "{listing}");
+    assert!(listing.contains("x = getproperty x\n"), "{listing}");
+    assert!(listing.contains("a = getproperty y, getproperty 0\n"), "{listing}");
+}
+
+#[test]
+fn abc_asm_lists_function_expressions_reading_this() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public class Main {
+        public var x: Number = 0;
+        public function f(): Function {
+            return function(): Number { return this.x; };
+        }
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("method#1 Main/f\n  ; max_stack=1 locals=1 scope_depth=0..0\n  6:20  newfunction\n"), "{listing}");
+    assert!(listing.contains("; reads the enclosing this, saved into the activation"), "{listing}");
+}
+
+#[test]
+fn abc_asm_lists_the_states_of_async_functions() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public function f(p: Promise.<Number>): Promise.<*> {
+        trace(await p);
+        trace(await p);
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("3:57  async state machine of 3 states\n"), "{listing}");
+    assert!(listing.contains("5:15  await\n          callproperty then, 2; returnvoid; resumes in state 2\n"), "{listing}");
+}
+
+#[test]
+fn typed_ast_names_referenced_definitions() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public function f(): void {} } f();");
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::TypedAst], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let json = output.artifacts.iter().find(|a| a.path == "Main.as.typed-ast.json").expect("missing typed AST");
+    let json = String::from_utf8_lossy(&json.bytes);
+    assert!(json.contains("\"entity\":\"function f(): void\""), "{json}");
+    assert!(!json.contains("[object"), "{json}");
+}
+
+#[test]
+fn definitions_duplicated_across_source_roots_are_reported() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("lib/com/example/Util.as", "package com.example { public class Util {} }");
+    sources.insert("src/com/example/Util.as", "package com.example { public class Util {} }");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    let expected = "com.example.Util is defined by more than one origin (lib/com/example/Util.as, src/com/example/Util.as); the definition from lib/com/example/Util.as is used.";
+    assert!(output.diagnostics().iter().any(|d| d.contains(expected)), "{:?}", output.diagnostics());
+}
+
+#[test]
+fn crash_reports_name_the_unit_being_verified() {
+    let tracker = CrashTracker::new(None);
+    tracker.on_event(&CompilerEvent::FileEnqueued("A.as".into()));
+    tracker.on_event(&CompilerEvent::FileEnqueued("B.as".into()));
+    tracker.on_event(&CompilerEvent::UnitStarted("A.as".into()));
+    tracker.on_event(&CompilerEvent::UnitVerified { file_path: "A.as".into(), completed: 1, total: 2 });
+    tracker.on_event(&CompilerEvent::UnitStarted("B.as".into()));
+    let report = CrashReport::catch(&tracker, &CompilerOptions::default(), || panic!("verifier bug")).unwrap_err();
+    assert_eq!(report.file_path.as_deref(), Some("B.as"));
+
+    let dir = std::env::temp_dir().join(format!("whack-crash-bundles-{}", std::process::id()));
+    let first = report.write_bundle(&dir).unwrap();
+    let second = report.write_bundle(&dir).unwrap();
+    assert_ne!(first, second);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn warning_levels_are_read_from_the_sources() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("src/legacy/Main.as", "package legacy { public function f(): void { trace(k); const k: Number = 1; } }");
+    sources.insert("src/legacy/whack-warnings.properties", "UseBeforeDeclaration = error");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions { source_path: vec!["src".into()], ..default() }), &sources);
+    let cu = output.compilation_units.iter().find(|cu| cu.file_path().as_deref() == Some("src/legacy/Main.as")).unwrap();
+    let diagnostics = cu.nested_diagnostics();
+    assert!(!diagnostics.is_empty() && diagnostics.iter().all(|d| !d.is_warning()), "{:?}", output.diagnostics());
+}
+
+#[test]
+fn allowed_warnings_do_not_count_towards_the_duplicate_cap() {
+    let mut sources = MemorySourceProvider::new();
+    let uses = "trace(k); ".repeat(12);
+    sources.insert("src/legacy/Main.as", &format!("package legacy {{ public function f(): void {{ {uses}const k: Number = 1; }} }}"));
+    sources.insert("src/legacy/whack-warnings.properties", "UseBeforeDeclaration = allow");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions { source_path: vec!["src".into()], ..default() }), &sources);
+    assert!(!output.invalidated);
+    assert!(output.diagnostics().is_empty(), "{:?}", output.diagnostics());
+}
+
+#[test]
+fn swf_modules_emit_a_load_manifest() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("src/Main.as", "package { import feature.Feature; public class Main { public var f: Feature; } }");
+    sources.insert("src/feature/Feature.as", "package feature { public class Feature {} }");
+    let host = Rc::new(Database::new(Default::default()));
+    let swf_modules = vec![SwfModuleOptions { name: "feature".into(), packages: vec!["feature".into()] }];
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions { source_path: vec!["src".into()], swf_modules, ..default() }), &sources);
+    let manifest = output.artifacts.iter().find(|a| a.path == CodegenModuleSplit::MANIFEST_FILE_NAME).expect("missing load manifest");
+    let manifest = String::from_utf8_lossy(&manifest.bytes);
+    let feature = manifest.find("\"name\":\"feature\"").expect(&manifest);
+    let main = manifest.find("\"name\":\"main\"").expect(&manifest);
+    assert!(feature < main, "{manifest}");
+}
+
+#[test]
+fn workspace_libraries_failing_to_load_are_listed() {
+    let dir = std::env::temp_dir().join(format!("whack-workspace-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("src/Main.as"), "package { public class Main {} }").unwrap();
+    std::fs::write(dir.join("broken.swf"), b"not a swf").unwrap();
+    let dir_path = dir.to_string_lossy().into_owned();
+    let library_path = vec![format!("{dir_path}/broken.swf"), format!("{dir_path}/missing.abc")];
+    let mut session = WorkspaceSession::new(&Rc::new(CompilerOptions::default()));
+    session.add_root(WorkspaceRoot { name: "app".into(), source_path: vec![format!("{dir_path}/src")], library_path: library_path.clone(), ..default() });
+    session.compile("app").unwrap();
+    let paths: Vec<String> = session.library_errors("app").iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(paths, library_path);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn speculative_edits_report_introduced_errors_across_verifications() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main { public function f(): void { Util.g(); } } }");
+    sources.insert("Util.as", "package { public class Util { public static function g(): void {} } }");
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let mut batch = SourceEditBatch::new();
+    batch.push(SourceEdit::new(52, 53, "h"));
+    let mut edits = std::collections::BTreeMap::new();
+    edits.insert("Util.as".to_owned(), batch);
+
+    let speculation = SpeculativeVerification::new();
+    for _ in 0..2 {
+        let delta = speculation.verify(&compiler_options, &sources, &edits, None).unwrap();
+        assert!(delta.introduces_errors(), "{delta:?}");
+        assert!(delta.introduced.iter().all(|d| d.file_path == "Main.as"), "{delta:?}");
+        assert!(delta.resolved.is_empty(), "{delta:?}");
+    }
+
+    let mut overlay = OverlaySourceProvider::new(&sources);
+    overlay.files.insert("New.as".into(), "package { public class New {} }".into());
+    assert_eq!(overlay.source_files(), ["Main.as", "New.as", "Util.as"]);
+}
+
+#[test]
+fn database_snapshots_keep_their_diagnostics_across_generations() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "package { public class Main { public function f(): void { Util.g(); } } }");
+    sources.insert("Util.as", "package { public class Util { public static function g(): void {} } }");
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let mut snapshots = DatabaseSnapshots::new();
+
+    let (generation, host, _) = snapshots.begin();
+    let output = Compilation::run_with_cache(&host, &compiler_options, &sources, &[], None, snapshots.parse_cache());
+    assert!(snapshots.publish(generation, host, output));
+    let first = snapshots.current().unwrap();
+
+    sources.insert("Util.as", "package { public class Util { public static function h(): void {} } }");
+    let (generation, host, _) = snapshots.begin();
+    let output = Compilation::run_with_cache(&host, &compiler_options, &sources, &[], None, snapshots.parse_cache());
+    assert!(snapshots.publish(generation, host, output));
+    let second = snapshots.current().unwrap();
+
+    // Main.as is shared by both generations
+    assert!(snapshots.parse_cache().hits() > 0);
+    assert!(first.diagnostics("Main.as").is_empty());
+    assert!(!second.diagnostics("Main.as").is_empty());
+}
+
+#[test]
+fn abc_asm_lists_inlined_calls() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public class Main {
+        [Inline]
+        public static function square(x: Number): Number {
+            return x * x;
+        }
+        public static function f(): Number {
+            return square(2) + 1;
+        }
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("9:20  inline square\n          ; binds 1 parameters to locals and copies 1 directives\n"), "{listing}");
+}
+
+#[test]
+fn abc_asm_lists_tail_calls_converted_into_loops() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public function sum(n: Number, total: Number): Number {
+        if (n == 0) return total;
+        return sum(n - 1, total + n);
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("5:16  tail call converted into a loop\n          ; rebinds 2 parameters and jumps to the start of sum\n"), "{listing}");
+}
+
+#[test]
+fn abc_asm_lists_the_lowering_of_switches() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public function f(v: int, s: String): void {
+        switch (v) {
+            case 0: trace("zero"); break;
+            case 1: trace("one"); break;
+            case 2: trace("two"); break;
+            case 3: trace("three"); break;
+        }
+        switch (v) {
+            case 0: trace("zero"); break;
+            case 1000: trace("thousand"); break;
+        }
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions { emit: EmitOptions { forms: vec![IntermediateForm::AbcAsm], files: vec![] }, ..default() });
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let listing = output.artifacts.iter().find(|a| a.path == "Main.as.abc-asm.txt").expect("missing listing");
+    let listing = String::from_utf8_lossy(&listing.bytes);
+    assert!(listing.contains("4:9  lookupswitch of 4 entries from 0\n"), "{listing}");
+    assert!(listing.contains("10:9  chained ifstricteq of 2 cases\n"), "{listing}");
+}
+
+#[test]
+fn test_manifest_and_runner_are_artifacts() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("app/MainTest.as", r#"
+package app {
+    public class MainTest {
+        [Before] public function setUp(): void {}
+        [Test] public function adds(): void {}
+        [Test] public function takesArguments(n: Number): void {}
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let test_discovery = Some(TestDiscoveryOptions { runner_class: Some("TestRunner".into()) });
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions { test_discovery, ..default() }), &sources);
+    assert!(output.invalidated);
+    assert!(output.diagnostics().iter().any(|d| d.contains("takesArguments")), "{:?}", output.diagnostics());
+    let manifest = output.artifacts.iter().find(|a| a.path == TestManifest::FILE_NAME).expect("missing manifest");
+    let manifest = String::from_utf8_lossy(&manifest.bytes);
+    assert!(manifest.contains(r#"{"class":"app.MainTest","file":"app/MainTest.as","methods":[{"kind":"Before","name":"setUp","line":4},{"kind":"Test","name":"adds","line":5}]}"#), "{manifest}");
+    let runner = output.artifacts.iter().find(|a| a.path == "TestRunner.as").expect("missing runner");
+    let runner = String::from_utf8_lossy(&runner.bytes);
+    assert!(runner.contains("import app.MainTest;"), "{runner}");
+    assert!(runner.contains("app_MainTest_adds.adds();"), "{runner}");
+}
+
+#[test]
+fn diagnostics_render_types_through_entity_display() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    class A {}
+    class B {}
+    public function f(t: [A, A]): void {
+        var u: [B, B] = t;
+        trace(u);
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    let diagnostics = output.diagnostics();
+    assert!(diagnostics.iter().any(|d| d.contains("Implicit coercion of a value of type [A, A] to an unrelated type [B, B].")), "{diagnostics:?}");
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn provider(name: &str, definitions: &[(&str, &str, &str)]) -> Rc<dyn DefinitionProvider> {
+    let mut provider = MemoryDefinitionProvider::new(name);
+    for (package, name, source) in definitions.iter() {
+        provider.insert(ProvidedDefinition::new(package, name), source);
+    }
+    Rc::new(provider)
+}
+
+const VIBRATION: &str = "package com.example { public class Vibration { public static function vibrate(pattern: Pattern): void {} } }";
+const PATTERN: &str = "package com.example { public class Pattern {} }";
+const UNUSED: &str = "package com.example { public class Unused {} }";
+
+#[test]
+fn referenced_definitions_are_materialized_transitively() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", "import com.example.Vibration;\nVibration.vibrate(null);");
+    let providers = [provider("ane", &[("com.example", "Vibration", VIBRATION), ("com.example", "Pattern", PATTERN), ("com.example", "Unused", UNUSED)])];
+    let output = Compilation::run_with_providers(&Rc::new(Database::new(Default::default())), &Rc::new(CompilerOptions::default()), &sources, &providers);
+    assert!(!output.invalidated);
+    let mut paths: Vec<String> = output.compilation_units.iter().filter_map(|cu| cu.file_path()).filter(|path| path.starts_with("ane:")).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["ane:com.example.Pattern.as".to_owned(), "ane:com.example.Vibration.as".to_owned()]);
+}
+
+#[test]
+fn sources_take_precedence_over_providers() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let program = parse_main(&compiler_options, "package com.example { public class Vibration {} }\nimport com.example.Vibration;\ntrace(Vibration);");
+    let mut materializer = DefinitionMaterializer::new(&[provider("ane", &[("com.example", "Vibration", VIBRATION)])]);
+    let (compilation_units, _) = materializer.materialize_referenced(&compiler_options, &[program]);
+    assert!(compilation_units.is_empty());
+    let conflicts = materializer.conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].definition.fully_qualified_name(), "com.example.Vibration");
+    assert_eq!(conflicts[0].origins, vec!["Main.as".to_owned(), "ane".to_owned()]);
+}
+
+#[test]
+fn earlier_providers_take_precedence_over_later_ones() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let program = parse_main(&compiler_options, "import com.example.Pattern;\ntrace(Pattern);");
+    let mut materializer = DefinitionMaterializer::new(&[
+        provider("ane", &[("com.example", "Pattern", PATTERN)]),
+        provider("idl", &[("com.example", "Pattern", PATTERN)]),
+    ]);
+    let (compilation_units, _) = materializer.materialize_referenced(&compiler_options, &[program]);
+    let paths: Vec<_> = compilation_units.iter().filter_map(|cu| cu.file_path()).collect();
+    assert_eq!(paths, vec!["ane:com.example.Pattern.as".to_owned()]);
+
+    materializer.report_conflicts();
+    let cu = &compilation_units[0];
+    assert_eq!(materializer.conflicts()[0].origins, vec!["ane".to_owned(), "idl".to_owned()]);
+    assert!(cu.nested_diagnostics().iter().any(|d| d.is_warning() && WhackDiagnostic(d).fx_kind_eq(WhackDiagnosticKind::DefinitionConflict)));
+}
\ No newline at end of file
@@ -0,0 +1,94 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn parse(file_path: &str, text: &str) -> Rc<Program> {
+    let cu = CompilationUnit::new(Some(file_path.to_owned()), text.to_owned());
+    ParserFacade(&cu, ParserOptions::default()).parse_program()
+}
+
+fn names(items: &[OutlineItem]) -> Vec<String> {
+    items.iter().map(|item| item.name.clone()).collect()
+}
+
+#[test]
+fn outline_lists_package_and_type_members() {
+    let program = parse("Main.as", "package p { public class C { public function f(): void {} public var x: Number; } }");
+    let outline = DocumentOutline::of(&program);
+    assert_eq!(names(&outline.items), vec!["p"]);
+    let class = &outline.items[0].children[0];
+    assert_eq!((class.name.as_str(), class.kind), ("C", OutlineItemKind::Class));
+    assert_eq!(names(&class.children), vec!["f", "x"]);
+}
+
+#[test]
+fn outline_leaves_out_included_definitions() {
+    let dir = std::env::temp_dir().join(format!("whack-outline-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("helpers.as"), "function helper(): void {}").unwrap();
+    let main = dir.join("Main.as").to_string_lossy().into_owned();
+    let program = parse(&main, "include \"helpers.as\";\nfunction main(): void {}");
+    let outline = DocumentOutline::of(&program);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(names(&outline.items), vec!["main"]);
+    assert!(outline.items.iter().all(|item| item.location.compilation_unit().file_path().as_deref() == Some(main.as_str())));
+}
+
+fn range_texts(text: &str, offset: usize) -> Vec<String> {
+    let program = parse("Main.as", text);
+    SelectionRanges::at(&program, offset).iter().map(|r| text[r.location.first_offset()..r.location.last_offset()].to_owned()).collect()
+}
+
+#[test]
+fn selection_ranges_nest_strictly() {
+    let text = "function f(): void { g(a + b); }";
+    let ranges = range_texts(text, text.find('b').unwrap());
+    assert_eq!(ranges[0], "b");
+    assert!(ranges.contains(&"a + b".to_owned()));
+    assert!(ranges.windows(2).all(|w| w[1].len() > w[0].len() && w[1].contains(w[0].as_str())), "{ranges:?}");
+}
+
+#[test]
+fn selection_ranges_between_statements_select_the_following_one() {
+    let text = "f();g();";
+    let ranges = range_texts(text, 4);
+    assert_eq!(ranges[0], "g");
+    assert!(!ranges.iter().any(|r| r.starts_with('f')), "{ranges:?}");
+    // At the end of the last statement, it remains selected
+    assert!(range_texts(text, text.len()).iter().any(|r| r == "g();"));
+}
+
+#[test]
+fn hover_names_the_target_of_extension_method_calls() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    let (_, mut programs) = CoreLibrary::parse(&compiler_options);
+    let text = r#"
+package util {
+    public class StringUtils {
+        [Extension]
+        public static function trimmed(s: String): String {
+            return s;
+        }
+    }
+}
+package {
+    import util.StringUtils;
+    public function main(): void {
+        var s: String = "a";
+        s.trimmed();
+        var n: Number = 0;
+        n.toFixed(1);
+    }
+}
+"#;
+    let program = parse_main(&compiler_options, text);
+    programs.push(program.clone());
+    verifier.verify_programs(&compiler_options, programs, vec![]);
+    assert!(!verifier.invalidated());
+
+    let hover = |needle: &str| Hover::at(&host, verifier.extension_method_calls(), &program, text.find(needle).unwrap());
+    assert_eq!(hover("trimmed();").as_deref(), Some("(extension) StringUtils.function trimmed(s: String): String"));
+    assert_eq!(hover("n.toFixed").as_deref(), Some("n: Number"));
+}
\ No newline at end of file
@@ -0,0 +1,108 @@
+use whackengine_verifier::ns::*;
+
+/// Builds the flow graph of the body of `function f() { <body> }`.
+fn body_graph(body: &str) -> FlowGraph {
+    let text = format!("function f() {{ {} }}", body);
+    let cu = CompilationUnit::new(None, text);
+    let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+    assert!(!cu.invalidated(), "syntax error in test source");
+    let Directive::FunctionDefinition(defn) = program.directives[0].as_ref() else {
+        panic!("expected a function definition");
+    };
+    let Some(FunctionBody::Block(block)) = defn.common.body.as_ref() else {
+        panic!("expected a block body");
+    };
+    FlowGraph::build(&block.directives)
+}
+
+fn unreachable_calls(graph: &FlowGraph) -> Vec<String> {
+    graph.unreachable_statements().iter().filter_map(|d| {
+        let Directive::ExpressionStatement(stmt) = d.as_ref() else {
+            return None;
+        };
+        let Expression::Call(call) = stmt.expression.as_ref() else {
+            return None;
+        };
+        call.base.to_identifier_name().map(|(name, _)| name)
+    }).collect()
+}
+
+#[test]
+fn return_through_finally_does_not_complete() {
+    assert!(!body_graph("try { return; } finally {}").completes_normally());
+}
+
+#[test]
+fn normal_try_finally_completes() {
+    assert!(body_graph("try { x(); } finally {}").completes_normally());
+}
+
+#[test]
+fn break_through_finally_leaves_loop() {
+    assert!(body_graph("while (c) { try { break; } finally {} }").completes_normally());
+}
+
+#[test]
+fn finally_after_return_is_reachable() {
+    let graph = body_graph("try { return; } finally { a(); } b();");
+    assert_eq!(unreachable_calls(&graph), vec!["b".to_owned()]);
+}
+
+#[test]
+fn thrown_exception_is_caught() {
+    assert!(body_graph("try { throw e; } catch (e) {}").completes_normally());
+}
+
+#[test]
+fn throw_through_finally_does_not_complete() {
+    assert!(!body_graph("try { throw e; } finally {}").completes_normally());
+}
+
+#[test]
+fn case_returning_through_finally_does_not_fall_through() {
+    let graph = body_graph("try { return; } finally { a(); }");
+    assert!(!graph.completes_normally());
+    assert!(unreachable_calls(&graph).is_empty());
+}
+
+#[test]
+fn loops_without_test_are_left_only_by_jumps() {
+    assert!(!body_graph("for (;;) { a(); }").completes_normally());
+    assert!(body_graph("for (;;) { if (c) break; }").completes_normally());
+}
+
+#[test]
+fn labeled_break_leaves_the_outer_loop() {
+    let graph = body_graph("outer: for (;;) { for (;;) { break outer; } b(); } a();");
+    assert!(graph.completes_normally());
+    assert_eq!(unreachable_calls(&graph), vec!["b".to_owned()]);
+}
+
+#[test]
+fn labeled_block_is_left_by_break() {
+    let graph = body_graph("l: { break l; a(); } b();");
+    assert_eq!(unreachable_calls(&graph), vec!["a".to_owned()]);
+}
+
+#[test]
+fn continue_through_finally_stays_in_loop() {
+    let graph = body_graph("for (;;) { try { continue; } finally { a(); } b(); }");
+    assert!(!graph.completes_normally());
+    assert_eq!(unreachable_calls(&graph), vec!["b".to_owned()]);
+}
+
+#[test]
+fn switch_completes_unless_every_path_returns() {
+    assert!(!body_graph("switch (v) { case 1: return; default: return; }").completes_normally());
+    assert!(body_graph("switch (v) { case 1: return; case 2: return; }").completes_normally());
+    assert!(body_graph("switch (v) { case 1: a(); default: break; }").completes_normally());
+}
+
+#[test]
+fn statements_after_returning_branches_are_unreachable() {
+    let graph = body_graph("if (c) { return; } else { throw e; } a();");
+    assert!(!graph.completes_normally());
+    assert_eq!(unreachable_calls(&graph), vec!["a".to_owned()]);
+    let graph = body_graph("do { return; } while (c); b();");
+    assert_eq!(unreachable_calls(&graph), vec!["b".to_owned()]);
+}
\ No newline at end of file
@@ -0,0 +1,204 @@
+use whackengine_verifier::ns::*;
+
+/// A TrueType font named `Test` mapping `A` to a square with a curved
+/// corner and `B` to a composite of `A` moved right by 100 units.
+fn test_font(mac_style: u16) -> Vec<u8> {
+    let be16 = |v: u16| v.to_be_bytes().to_vec();
+
+    let mut head = vec![0u8; 54];
+    head[..4].copy_from_slice(&0x00010000u32.to_be_bytes());
+    head[18..20].copy_from_slice(&be16(1000));
+    head[44..46].copy_from_slice(&be16(mac_style));
+
+    let mut hhea = vec![0u8; 36];
+    hhea[4..6].copy_from_slice(&be16(800));
+    hhea[6..8].copy_from_slice(&be16((-200i16) as u16));
+    hhea[34..36].copy_from_slice(&be16(3));
+
+    let maxp = [0x00005000u32.to_be_bytes().to_vec(), be16(3)].concat();
+    let hmtx = [be16(500), be16(0), be16(600), be16(0), be16(700), be16(0)].concat();
+
+    // Four points, the third of which is a control point
+    let mut square = [be16(1), be16(0), be16(0), be16(500), be16(700), be16(3), be16(0)].concat();
+    square.extend([1u8, 1, 0, 1]);
+    for delta in [0i16, 500, 0, -500, 0, 0, 700, 0] {
+        square.extend(be16(delta as u16));
+    }
+    let composite = [be16((-1i16) as u16), be16(0), be16(0), be16(600), be16(700), be16(3), be16(1), be16(100), be16(0)].concat();
+    let glyf = [square.clone(), composite.clone()].concat();
+    let loca = [be16(0), be16(0), be16(square.len() as u16 / 2), be16(glyf.len() as u16 / 2)].concat();
+
+    // A format 4 subtable with segments for `A`-`B` and the final 0xFFFF
+    let mut cmap = [be16(0), be16(1), be16(3), be16(1), 12u32.to_be_bytes().to_vec()].concat();
+    let subtable = [
+        be16(4), be16(32), be16(0), be16(4), be16(4), be16(1), be16(0),
+        be16(0x42), be16(0xFFFF), be16(0),
+        be16(0x41), be16(0xFFFF),
+        be16(1u16.wrapping_sub(0x41)), be16(1),
+        be16(0), be16(0),
+    ].concat();
+    cmap.extend(subtable);
+
+    let family = "Test".encode_utf16().flat_map(|u| u.to_be_bytes()).collect::<Vec<u8>>();
+    let mut name = [be16(0), be16(1), be16(18), be16(3), be16(1), be16(0x409), be16(1), be16(family.len() as u16), be16(0)].concat();
+    name.extend(family);
+
+    let tables: Vec<(&[u8; 4], Vec<u8>)> = vec![
+        (b"cmap", cmap), (b"glyf", glyf), (b"head", head), (b"hhea", hhea),
+        (b"hmtx", hmtx), (b"loca", loca), (b"maxp", maxp), (b"name", name),
+    ];
+    let mut r = [0x00010000u32.to_be_bytes().to_vec(), be16(tables.len() as u16), be16(0), be16(0), be16(0)].concat();
+    let mut offset = 12 + tables.len() * 16;
+    for (tag, table) in tables.iter() {
+        r.extend(tag.iter());
+        r.extend(0u32.to_be_bytes());
+        r.extend((offset as u32).to_be_bytes());
+        r.extend((table.len() as u32).to_be_bytes());
+        offset += (table.len() + 3) & !3;
+    }
+    for (_, table) in tables.iter() {
+        r.extend(table.iter());
+        r.resize((r.len() + 3) & !3, 0);
+    }
+    r
+}
+
+#[test]
+fn font_file_reads_names_style_and_coverage() {
+    let font = FontFile::parse(&test_font(1)).unwrap();
+    assert_eq!(font.family_name.as_deref(), Some("Test"));
+    assert!(font.bold && !font.italic && !font.cff);
+    assert_eq!(font.glyph_count, 3);
+    assert_eq!(font.coverage, vec![(0x41, 0x42)]);
+    assert_eq!(font.missing_ranges(&[(0x41, 0x43)]), vec![(0x43, 0x43)]);
+}
+
+#[test]
+fn truncated_fonts_are_rejected() {
+    let data = test_font(0);
+    assert_eq!(FontFile::parse(&data[..40]).err(), Some(FontFileError::UnexpectedEof));
+
+    // A table at the end of the address space
+    let mut data = data;
+    data[20..24].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert_eq!(FontFile::parse(&data).err(), Some(FontFileError::UnexpectedEof));
+}
+
+#[test]
+fn composite_glyphs_are_transformed_components() {
+    let data = test_font(0);
+    let outlines = TrueTypeOutlines::parse(&data).unwrap();
+    assert_eq!((outlines.glyph_index('A' as u32).unwrap(), outlines.glyph_index('B' as u32).unwrap()), (1, 2));
+    let square = outlines.contours(1).unwrap();
+    assert_eq!(square.len(), 1);
+    assert_eq!(square[0].iter().filter(|p| !p.on_curve).count(), 1);
+    let moved: Vec<(f64, f64)> = outlines.contours(2).unwrap()[0].iter().map(|p| (p.x, p.y)).collect();
+    assert_eq!(moved, square[0].iter().map(|p| (p.x + 100.0, p.y)).collect::<Vec<_>>());
+    assert_eq!(outlines.advance(2).unwrap(), 700);
+}
+
+#[test]
+fn define_font3_maps_the_covered_code_points() {
+    let data = test_font(0);
+    let font = FontFile::parse(&data).unwrap();
+    let options = FontEmbedOptions { font_name: Some("Test".into()), embed_as_cff: Some("false".into()), ..default() };
+    let tag = CodegenFontEmbed::define_font3(1, &options, &font, &data, &[(0x41, 0x43)]).unwrap();
+
+    // Long record header
+    assert_eq!(u16::from_le_bytes([tag[0], tag[1]]), (CodegenFontEmbed::DEFINE_FONT3 << 6) | 0x3F);
+    let body = &tag[6..];
+    assert_eq!(u32::from_le_bytes([tag[2], tag[3], tag[4], tag[5]]) as usize, body.len());
+    assert_eq!(body[2], 0x84, "layout and wide codes");
+    assert_eq!(&body[5..5 + body[4] as usize], b"Test");
+    let glyphs = &body[5 + body[4] as usize..];
+    assert_eq!(u16::from_le_bytes([glyphs[0], glyphs[1]]), 2);
+    let offsets = &glyphs[2..];
+    let code_table = u16::from_le_bytes([offsets[4], offsets[5]]) as usize;
+    assert_eq!(&offsets[code_table..code_table + 4], &[0x41, 0, 0x42, 0]);
+    // One fill style and no line style
+    let first_shape = u16::from_le_bytes([offsets[0], offsets[1]]) as usize;
+    assert_eq!(offsets[first_shape], 0x10);
+}
+
+#[test]
+fn true_type_fonts_are_read_through_the_source_provider() {
+    let main = |embed_as_cff: &str| format!(r#"
+package {{
+    public class Main {{
+        [Embed(source="assets/Test.ttf", fontName="Test", embedAsCFF="{embed_as_cff}")]
+        public static const TestFont: Class;
+    }}
+}}
+"#);
+    let mut sources = MemorySourceProvider::new();
+    sources.insert_bytes("assets/Test.ttf", &test_font(0));
+    let host = Rc::new(Database::new(Default::default()));
+    let compiler_options = Rc::new(CompilerOptions::default());
+
+    sources.insert("Main.as", &main("false"));
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    assert!(output.diagnostics().is_empty(), "{:?}", output.diagnostics());
+
+    // TrueType outlines cannot be embedded for the Flash Text Engine
+    sources.insert("Main.as", &main("true"));
+    let output = Compilation::run(&host, &compiler_options, &sources);
+    let diagnostics = output.diagnostics();
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert!(diagnostics[0].contains("classic text fields"));
+}
+
+#[test]
+fn missing_embedded_fonts_are_reported() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", r#"
+package {
+    public class Main {
+        [Embed(source="assets/Missing.ttf", fontName="Missing", embedAsCFF="false")]
+        public static const MissingFont: Class;
+    }
+}
+"#);
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    assert!(output.invalidated);
+    assert!(output.diagnostics().iter().any(|d| d.contains("'assets/Missing.ttf' not found")));
+}
+
+#[test]
+fn embedded_fonts_are_not_read_from_the_file_system_when_sources_are_given() {
+    // Relative to `Main.as`, and so to the working directory
+    let font_path = format!("whack-font-embeds-{}.ttf", std::process::id());
+    std::fs::write(&font_path, test_font(0)).unwrap();
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("Main.as", &format!(r#"
+package {{
+    public class Main {{
+        [Embed(source="{font_path}", fontName="OnDisk", embedAsCFF="false")]
+        public static const OnDiskFont: Class;
+    }}
+}}
+"#));
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    std::fs::remove_file(&font_path).unwrap();
+    assert!(output.invalidated);
+    assert!(output.diagnostics().iter().any(|d| d.contains("not found")), "{:?}", output.diagnostics());
+}
+
+#[test]
+fn font_styles_and_coverage_are_queried() {
+    let font = FontFile::parse(&test_font(2)).unwrap();
+    assert!(font.italic && !font.bold);
+    assert!(font.covers('A' as u32) && font.covers('B' as u32));
+    assert!(!font.covers('@' as u32) && !font.covers('C' as u32));
+    assert!(font.missing_ranges(&[(0x41, 0x42)]).is_empty());
+}
+
+#[test]
+fn fonts_without_known_signature_or_cmap_are_rejected() {
+    assert_eq!(FontFile::parse(b"wOFF\0\0\0\0\0\0\0\0").err(), Some(FontFileError::UnsupportedFormat));
+    let mut data = test_font(0);
+    let cmap = data.windows(4).position(|w| w == b"cmap").unwrap();
+    data[cmap + 3] = b'q';
+    assert_eq!(FontFile::parse(&data).err(), Some(FontFileError::MissingTable("cmap")));
+}
\ No newline at end of file
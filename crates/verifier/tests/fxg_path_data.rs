@@ -0,0 +1,44 @@
+use whackengine_verifier::ns::*;
+use FxgPathCommand::*;
+
+#[test]
+fn relative_and_axis_commands_become_absolute() {
+    assert_eq!(FxgPathCommand::parse("M 10 10 h 5 v 5 l-5,0").unwrap(), vec![
+        MoveTo(10.0, 10.0),
+        LineTo(15.0, 10.0),
+        LineTo(15.0, 15.0),
+        LineTo(10.0, 15.0),
+    ]);
+}
+
+#[test]
+fn pairs_after_move_are_line_commands() {
+    assert_eq!(FxgPathCommand::parse("M0 0 1 1 2 2").unwrap(), vec![MoveTo(0.0, 0.0), LineTo(1.0, 1.0), LineTo(2.0, 2.0)]);
+}
+
+#[test]
+fn closed_paths_terminate() {
+    assert_eq!(FxgPathCommand::parse("M0 0L10 0 10 10Z").unwrap(), vec![
+        MoveTo(0.0, 0.0),
+        LineTo(10.0, 0.0),
+        LineTo(10.0, 10.0),
+        Close,
+    ]);
+    assert_eq!(FxgPathCommand::parse("M0 0 L1 1 z m 1 1").unwrap(), vec![MoveTo(0.0, 0.0), LineTo(1.0, 1.0), Close, MoveTo(1.0, 1.0)]);
+}
+
+#[test]
+fn coordinates_after_close_are_rejected() {
+    assert!(FxgPathCommand::parse("M0 0 L1 1 Z 2 2").is_err());
+}
+
+#[test]
+fn exponents_and_signs_split_numbers() {
+    assert_eq!(FxgPathCommand::parse("M1e1-2").unwrap(), vec![MoveTo(10.0, -2.0)]);
+}
+
+#[test]
+fn missing_coordinates_are_rejected() {
+    assert!(FxgPathCommand::parse("M 1").is_err());
+    assert!(FxgPathCommand::parse("X 1 1").is_err());
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use whackengine_verifier::ns::*;
+
+fn rewritten(text: &str) -> (String, Vec<usize>) {
+    let (r, commas) = LegacySyntax::rewrite(text);
+    assert_eq!(r.len(), text.len());
+    (r, commas)
+}
+
+#[test]
+fn trailing_comma_of_argument_list_is_removed() {
+    assert_eq!(rewritten("f(a, b,)"), ("f(a, b )".to_owned(), vec![6]));
+    assert_eq!(rewritten("new Foo(a,\n)").1, vec![9]);
+    assert_eq!(rewritten("Vector.<int>(a,)").1, vec![14]);
+}
+
+#[test]
+fn trailing_comma_of_parameter_list_is_removed() {
+    assert_eq!(rewritten("function g(a,) {}").1, vec![12]);
+    assert_eq!(rewritten("var g = function(a, /* b */) {};").1, vec![18]);
+}
+
+#[test]
+fn literals_keep_their_trailing_commas() {
+    for text in ["var o = {x: 1,};", "var a = [1, 2,];", "if (a,) {}"] {
+        assert_eq!(rewritten(text), (text.to_owned(), vec![]));
+    }
+}
+
+#[test]
+fn strings_regexes_and_xml_are_skipped() {
+    for text in [
+        "f('a,)');",
+        "var r = /a,)/g; f(r);",
+        "var r = /[/,)]/;",
+        "var x = <a b=\"1,)\">c,)<!-- d,) --></a>;",
+        "var l = <>a,)</>;",
+        "var x = <a>{g(1, 2)}</a>;",
+    ] {
+        assert_eq!(rewritten(text), (text.to_owned(), vec![]), "{text}");
+    }
+}
+
+#[test]
+fn division_is_not_a_regex() {
+    assert_eq!(rewritten("f(a / b, c / d,)").1, vec![14]);
+}
+
+#[test]
+fn code_after_xml_is_rewritten() {
+    assert_eq!(rewritten("var x = <a/>; f(x,)").1, vec![17]);
+}
+
+#[test]
+fn nested_argument_lists_are_rewritten() {
+    assert_eq!(rewritten("f(g(a,), b,)").1, vec![5, 10]);
+    assert_eq!(rewritten("f(a, // note\n)").1, vec![3]);
+}
+
+#[test]
+fn comments_are_skipped() {
+    for text in ["// f(a,)\n", "/* f(a,) */"] {
+        assert_eq!(rewritten(text), (text.to_owned(), vec![]), "{text}");
+    }
+}
\ No newline at end of file
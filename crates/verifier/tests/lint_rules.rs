@@ -0,0 +1,77 @@
+mod common;
+use common::*;
+use std::cell::RefCell;
+use whackengine_verifier::ns::*;
+
+/// Reports calls to `eval()` and counts the definitions and statements
+/// of `Main.as` its hooks receive.
+#[derive(Default)]
+struct NoEval {
+    definitions: RefCell<usize>,
+    statements: RefCell<usize>,
+}
+
+impl LintRule for NoEval {
+    fn name(&self) -> String {
+        "no-eval".into()
+    }
+
+    fn check_statement(&self, _context: &mut LintContext, stmt: &Rc<Directive>) {
+        if in_main(&stmt.location()) {
+            *self.statements.borrow_mut() += 1;
+        }
+    }
+
+    fn check_expression(&self, context: &mut LintContext, exp: &Rc<Expression>) {
+        if let Expression::Call(call) = exp.as_ref() {
+            if call.base.to_identifier_name().is_some_and(|(name, _)| name == "eval") {
+                context.report(&exp.location(), "eval() is not allowed");
+            }
+        }
+    }
+
+    fn check_definition(&self, _context: &mut LintContext, defn: &Rc<Directive>) {
+        if in_main(&defn.location()) {
+            *self.definitions.borrow_mut() += 1;
+        }
+    }
+}
+
+fn in_main(location: &Location) -> bool {
+    location.compilation_unit().file_path().is_some_and(|path| path == "Main.as")
+}
+
+const SOURCE: &str = "function eval(s: String): void {}\nfunction f(): void {\n    eval(\"1\");\n}";
+
+/// Verifies `SOURCE` with the `NoEval` rule, returning whether the
+/// verifier was invalidated and the lint diagnostics as
+/// `(is warning, line)`.
+fn lint(compiler_options: CompilerOptions, rule: &Rc<NoEval>) -> (bool, Vec<(bool, usize)>) {
+    let compiler_options = Rc::new(compiler_options);
+    let mut verifier = Verifier::new(&Rc::new(Database::new(Default::default())));
+    verifier.register_lint_rule(rule.clone());
+    let program = parse_main(&compiler_options, SOURCE);
+    verifier.verify_programs(&compiler_options, vec![program.clone()], vec![]);
+    let diagnostics = program.location.compilation_unit().nested_diagnostics().iter()
+        .filter(|d| WhackDiagnostic(d).fx_kind_eq(WhackDiagnosticKind::LintRuleViolation))
+        .map(|d| (d.is_warning(), d.location().first_line_number()))
+        .collect();
+    (verifier.invalidated(), diagnostics)
+}
+
+#[test]
+fn lint_rules_report_at_their_default_severity() {
+    let rule = Rc::new(NoEval::default());
+    assert_eq!(lint(CompilerOptions::default(), &rule), (false, vec![(true, 3)]));
+    assert_eq!(*rule.definitions.borrow(), 2);
+    assert_eq!(*rule.statements.borrow(), 1);
+}
+
+#[test]
+fn lint_severities_override_the_default_severity() {
+    let severities = |severity| CompilerOptions { lint_severities: [("no-eval".to_owned(), severity)].into_iter().collect(), ..default() };
+    assert_eq!(lint(severities(LintSeverity::Error), &Rc::new(NoEval::default())), (true, vec![(false, 3)]));
+    let rule = Rc::new(NoEval::default());
+    assert_eq!(lint(severities(LintSeverity::Allow), &rule), (false, vec![]));
+    assert_eq!(*rule.definitions.borrow(), 0);
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+const FLASH_EVENTS: &str = r#"
+package flash.events {
+    public class Event {}
+    public interface IEventDispatcher {
+        function addEventListener(type: String, listener: Function): void;
+        function dispatchEvent(event: Event): Boolean;
+    }
+    public class EventDispatcher implements IEventDispatcher {
+        public function addEventListener(type: String, listener: Function): void {}
+        public function dispatchEvent(event: Event): Boolean {
+            return true;
+        }
+    }
+}
+"#;
+
+#[test]
+fn bindable_class_is_injected_an_event_dispatcher() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    let (_, mut programs) = CoreLibrary::parse(&compiler_options);
+    programs.push(parse_main(&compiler_options, &format!("{FLASH_EVENTS}{}", r#"
+package {
+    [Bindable]
+    public class Model {
+        public var value: Number;
+        public function dispatchEvent(event: flash.events.Event): Boolean {
+            return false;
+        }
+    }
+}
+"#)));
+    verifier.verify_programs(&compiler_options, programs, vec![]);
+    assert!(!verifier.invalidated());
+
+    let injections = verifier.bindable_event_dispatcher_injections();
+    assert_eq!(injections.len(), 1);
+    assert_eq!(injections[0].class.name().local_name(), "Model");
+    let methods: Vec<String> = injections[0].methods.iter().map(|m| m.name().local_name()).collect();
+    assert_eq!(methods, vec!["addEventListener".to_owned()]);
+}
+
+struct Equatable(&'static str);
+
+impl MemberGenerator for Equatable {
+    fn metadata_name(&self) -> String {
+        "Equatable".into()
+    }
+
+    fn generate(&self, context: &mut MemberGeneratorContext, _metadata: &Rc<Metadata>) {
+        let host = context.host();
+        let other = context.class();
+        context.define_method_with_body("equals", vec![("other", other)], &host.boolean_type(), false, self.0);
+    }
+}
+
+fn verify_equatable(body: &'static str) -> Verifier {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    verifier.register_member_generator(Rc::new(Equatable(body)));
+    let (_, mut programs) = CoreLibrary::parse(&compiler_options);
+    programs.push(parse_main(&compiler_options, r#"
+package {
+    [Equatable]
+    public class Point {
+        public var x: Number;
+    }
+}
+"#));
+    verifier.verify_programs(&compiler_options, programs, vec![]);
+    verifier
+}
+
+#[test]
+fn generated_method_bodies_are_verified_within_their_class() {
+    let verifier = verify_equatable("return this.x == other.x;");
+    assert!(!verifier.invalidated());
+    let names: Vec<String> = verifier.generated_members().keys().map(|m| m.name().local_name()).collect();
+    assert!(names.contains(&"equals".to_owned()));
+
+    let verifier = verify_equatable("return this.y == other.y;");
+    assert!(verifier.invalidated());
+}
+
+#[test]
+fn generated_method_bodies_with_syntax_errors_are_reported() {
+    let verifier = verify_equatable("return this.x ==;");
+    assert!(verifier.invalidated());
+    assert!(AstBuilder::identifier("equals").is_ok());
+    assert!(AstBuilder::identifier("class").is_err());
+}
\ No newline at end of file
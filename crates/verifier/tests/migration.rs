@@ -0,0 +1,61 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn analyze(text: &str) -> Vec<(MigrationIssueKind, usize)> {
+    let program = parse_main(&Rc::new(CompilerOptions::default()), text);
+    MigrationAnalyzer::analyze(&[program]).issues.into_iter()
+        .map(|issue| (issue.kind, issue.location.first_line_number()))
+        .collect()
+}
+
+#[test]
+fn unsupported_constructs_are_reported() {
+    let issues = analyze(r#"
+package {
+    import flash.utils.Proxy;
+    [Frob]
+    public class P extends Proxy {
+        [Inject] public var x;
+        public function f(o) {
+            with (o) {}
+            default xml namespace = "http://example.com";
+        }
+    }
+}
+"#);
+    assert_eq!(issues, vec![
+        (MigrationIssueKind::UnsupportedMetadata("Frob".into()), 4),
+        (MigrationIssueKind::ProxySubclassWithoutOverrides, 5),
+        (MigrationIssueKind::UnsupportedMetadata("Inject".into()), 6),
+        (MigrationIssueKind::WithStatement, 8),
+        (MigrationIssueKind::DefaultXmlNamespaceStatement, 9),
+    ]);
+}
+
+#[test]
+fn supported_constructs_are_not_reported() {
+    let issues = analyze(r#"
+package {
+    import flash.utils.*;
+    [Bindable]
+    [RemoteClass]
+    public class P extends Proxy {
+        override flash_proxy function getProperty(name: *): * {
+            return undefined;
+        }
+    }
+}
+"#);
+    assert_eq!(issues, vec![]);
+}
+
+#[test]
+fn issues_are_counted_per_file() {
+    let first = parse_main(&Rc::new(CompilerOptions::default()), "with (o) {}\nwith (o) {}");
+    let cu = CompilationUnit::new(Some("Other.as".into()), "with (o) {}".into());
+    let second = ParserFacade(&cu, ParserOptions::default()).parse_program();
+    let report = MigrationAnalyzer::analyze(&[second, first]);
+    assert_eq!(report.per_file_counts(), vec![("Main.as".to_owned(), 2), ("Other.as".to_owned(), 1)]);
+    assert!(report.format_human().starts_with("Other.as:1: The with statement is not supported. "), "{}", report.format_human());
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use whackengine_verifier::ns::*;
+
+fn root(text: &str) -> Rc<MxmlElement> {
+    let cu = CompilationUnit::new(Some("Main.mxml".into()), text.into());
+    let mxml = ParserFacade(&cu, ParserOptions::default()).parse_mxml();
+    mxml.content.iter().find_map(|content| match content.as_ref() {
+        MxmlContent::Element(e) => Some(e.clone()),
+        _ => None,
+    }).unwrap()
+}
+
+#[test]
+fn state_overrides_are_lowered_per_state() {
+    let root = root(r#"<?xml version="1.0"?>
+<s:Group xmlns:s="library://ns.adobe.com/flex/spark">
+    <s:states>
+        <s:State name="normal"/>
+        <s:State name="hover" stateGroups="active"/>
+        <s:State name="down" stateGroups="active"/>
+    </s:states>
+    <s:Label text="A" text.active="B"/>
+    <s:Button excludeFrom="normal"/>
+</s:Group>
+"#);
+    let states = MxmlStates::collect(&root);
+    let overrides = states.overrides(&root);
+    let set_properties = overrides.iter().filter_map(|o| match o {
+        MxmlStateOverride::SetProperty { state, name, value, .. } => Some((state.as_str(), name.as_str(), value.as_str())),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(set_properties, [("hover", "text", "B"), ("down", "text", "B")]);
+    let add_items = overrides.iter().filter_map(|o| match o {
+        MxmlStateOverride::AddItems { state, position, .. } => Some((state.as_str(), *position)),
+        _ => None,
+    }).collect::<Vec<_>>();
+    assert_eq!(add_items, [("hover", 2), ("down", 2)]);
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn recovered(text: &str) -> String {
+    let (r, _) = PartialInputRecovery::recover(text);
+    // Original characters keep their offsets
+    assert!(r.len() >= text.len());
+    r
+}
+
+#[test]
+fn dangling_member_access_leaves_the_base() {
+    assert_eq!(PartialInputRecovery::recover("f(obj.);"), ("f(obj );".to_owned(), vec![InputRecovery::DanglingMemberAccess { offset: 5, punctuator: "." }]));
+    assert_eq!(recovered("a?.\n}"), "a  \n}");
+    for text in ["a.b;", "x..y;", "f(...rest);", "v.<int>;", "1.;", "'a.';"] {
+        assert_eq!(PartialInputRecovery::recover(text), (text.to_owned(), vec![]), "{text}");
+    }
+}
+
+#[test]
+fn open_brackets_are_closed_at_the_end() {
+    assert_eq!(recovered("package { class A { function f() { g([1"), "package { class A { function f() { g([1\n])}}}");
+    assert_eq!(recovered("f() // {"), "f() // {");
+    let (_, recoveries) = PartialInputRecovery::recover("{ }\n{ (");
+    assert_eq!(recoveries, vec![
+        InputRecovery::UnclosedBracket { offset: 4, bracket: '{' },
+        InputRecovery::UnclosedBracket { offset: 6, bracket: '(' },
+    ]);
+}
+
+#[test]
+fn dangling_statement_headings_are_completed() {
+    assert_eq!(recovered("{ if ("), "{ if (\nfalse);}");
+    assert_eq!(recovered("{ while (a"), "{ while (a\n);}");
+    assert_eq!(recovered("{ switch (a"), "{ switch (a\n){}}");
+}
+
+#[test]
+fn unterminated_block_comments_are_left_alone() {
+    assert_eq!(PartialInputRecovery::recover("{ /* f("), ("{ /* f(".to_owned(), vec![]));
+}
+
+#[test]
+fn partial_input_is_verified_with_syntax_errors() {
+    assert_fixture(CompilerOptions { verify_with_syntax_errors: true, ..default() }, r#"
+// expect-error: UnclosedBracket @ 7:9
+// expect-error: UnclosedBracket @ 8:23
+// expect-error: UnclosedBracket @ 9:44
+// expect-error: DanglingMemberAccess @ 11:14
+// expect-error: UndefinedProperty @ 10:19
+package {
+    public class Main {
+        public function f(o: Object): void {
+            trace(u);
+            o."#);
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use whackengine_verifier::ns::*;
+
+#[test]
+fn unquoted_values_may_contain_operator_characters() {
+    let query = SemanticQuery::parse("definitions where name $= $internal and package*=app limit 2").unwrap();
+    let values: Vec<&str> = query.conditions.iter().map(|c| c.value.as_str()).collect();
+    assert_eq!(values, ["$internal", "app"]);
+    assert_eq!(query.conditions[0].operator, QueryOperator::EndsWith);
+    assert_eq!(query.limit, Some(2));
+}
+
+#[test]
+fn references_through_the_scope_chain_are_indexed() {
+    let mut sources = MemorySourceProvider::new();
+    sources.insert("app/Main.as", "package app { public class Main { public function run(): void { helper(); } } }");
+    sources.insert("app/helper.as", "package app { public function helper(): void {} }");
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run(&host, &Rc::new(CompilerOptions::default()), &sources);
+    assert!(!output.invalidated, "{:?}", output.diagnostics());
+    let index = SemanticQueryIndex::build(&host, &output);
+    let rows = index.run(&SemanticQuery::parse("references where from = app.Main and to = app.helper").unwrap());
+    assert_eq!(rows.len(), 1);
+}
\ No newline at end of file
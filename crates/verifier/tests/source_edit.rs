@@ -0,0 +1,32 @@
+use whackengine_verifier::ns::*;
+
+fn batch(edits: &[(usize, usize, &str)]) -> SourceEditBatch {
+    let mut batch = SourceEditBatch::new();
+    batch.extend(edits.iter().map(|(start, end, text)| SourceEdit::new(*start, *end, text)));
+    batch
+}
+
+#[test]
+fn edits_apply_against_the_original_text() {
+    let edits = batch(&[(8, 11, "qux"), (0, 3, "a"), (4, 4, "new ")]);
+    assert_eq!(edits.apply("foo bar baz").unwrap(), "a new bar qux");
+}
+
+#[test]
+fn insertions_at_the_same_offset_keep_their_order() {
+    let edits = batch(&[(3, 3, "1"), (3, 3, "2"), (0, 3, "x")]);
+    assert_eq!(edits.apply("abc").unwrap(), "x12");
+}
+
+#[test]
+fn overlapping_edits_are_rejected() {
+    assert!(matches!(batch(&[(0, 4, "a"), (2, 6, "b")]).apply("abcdefg"), Err(SourceEditError::Overlap(_, _))));
+    assert!(matches!(batch(&[(0, 4, "a"), (2, 2, "b")]).apply("abcdefg"), Err(SourceEditError::Overlap(_, _))));
+    assert_eq!(batch(&[(0, 4, "a"), (4, 6, "b")]).apply("abcdefg").unwrap(), "abg");
+}
+
+#[test]
+fn ranges_must_fall_within_the_text_at_character_boundaries() {
+    assert!(matches!(batch(&[(2, 9, "")]).apply("abc"), Err(SourceEditError::InvalidRange(_))));
+    assert!(matches!(batch(&[(1, 2, "")]).apply("\u{e9}t\u{e9}"), Err(SourceEditError::InvalidRange(_))));
+}
\ No newline at end of file
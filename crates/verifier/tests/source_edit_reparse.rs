@@ -0,0 +1,32 @@
+use whackengine_verifier::ns::*;
+
+fn parse(text: &str) -> (Rc<CompilationUnit>, Rc<Program>) {
+    let cu = CompilationUnit::new(None, text.to_owned());
+    let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+    (cu, program)
+}
+
+#[test]
+fn every_node_locates_into_the_edited_unit() {
+    let text = "function f() { a(); }\nfunction g() { b(); }";
+    let (cu, program) = parse(text);
+    let offset = text.find("b()").unwrap();
+    let reparsed = SourceEditReparse::reparse(&cu, &program, &SourceEdit::new(offset, offset, "xy")).unwrap();
+    for drtv in reparsed.program.directives.iter() {
+        assert!(Rc::ptr_eq(&drtv.location().compilation_unit(), &reparsed.compilation_unit));
+    }
+    assert!(reparsed.is_confined());
+    let start = text.find("function g").unwrap();
+    assert_eq!(reparsed.changed_range, Some((start, text.len() + 2)));
+}
+
+#[test]
+fn edit_across_directives_is_not_confined() {
+    let text = "function f() { a(); }\nfunction g() { b(); }";
+    let (cu, program) = parse(text);
+    let start = text.find("a()").unwrap();
+    let end = text.find("b()").unwrap();
+    let reparsed = SourceEditReparse::reparse(&cu, &program, &SourceEdit::new(start, end, "")).unwrap();
+    assert!(!reparsed.is_confined());
+    assert_eq!(reparsed.program.directives.len(), 1);
+}
\ No newline at end of file
@@ -0,0 +1,79 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+const SHAPES: &str = r#"
+package app {
+    public interface IShape {
+        function area(): Number;
+    }
+    public class Square {
+        public function area(): Number { return 1; }
+    }
+    public class Circle implements IShape {
+        public function area(): Number { return 3; }
+    }
+    public class Point {}
+}
+"#;
+
+fn verify_shapes(verifier: &mut Verifier) -> Rc<Program> {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let program = parse_main(&compiler_options, SHAPES);
+    verifier.verify_programs(&compiler_options, vec![program.clone()], vec![]);
+    assert!(!verifier.invalidated());
+    program
+}
+
+fn app_definition(host: &Database, name: &str) -> Entity {
+    let pckg = host.factory().create_package(vec!["app"]);
+    let qname = host.factory().create_qname(&pckg.public_ns().unwrap(), name.to_owned());
+    pckg.properties(host).get(&qname).unwrap()
+}
+
+#[test]
+fn classes_conform_to_interfaces_member_by_member() {
+    let host = Rc::new(Database::new(Default::default()));
+    verify_shapes(&mut Verifier::new(&host));
+    let conformance = StructuralConformance(&host);
+    let shape = conformance.resolve_interface("app.IShape").unwrap();
+    assert!(conformance.resolve_interface("app.Square").is_none());
+
+    for (class, conforms, missing) in [("Square", true, 1), ("Circle", true, 0), ("Point", false, 0)] {
+        let class = app_definition(&host, class);
+        assert_eq!(conformance.structurally_conforms(&class, &shape).unwrap(), conforms);
+        assert_eq!(conformance.missing_implements(&class, &[shape.clone()]).unwrap().len(), missing);
+    }
+}
+
+#[test]
+fn migration_reports_missing_implements_clauses() {
+    let host = Rc::new(Database::new(Default::default()));
+    let program = verify_shapes(&mut Verifier::new(&host));
+    let report = MigrationAnalyzer::missing_implements(&host, &[program], &["app.IShape".into(), "app.Unknown".into()]);
+    let issues: Vec<_> = report.issues.iter().map(|issue| (issue.location.first_line_number(), issue.kind.description())).collect();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].0, 6);
+    assert!(issues[0].1.ends_with("IShape without implementing it"), "{}", issues[0].1);
+}
+
+#[test]
+fn missing_implements_rule_suggests_an_implements_clause() {
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    verifier.register_lint_rule(Rc::new(MissingImplementsRule { interfaces: vec!["app.IShape".into()] }));
+    let program = verify_shapes(&mut verifier);
+
+    let cu = program.location.compilation_unit();
+    let lines: Vec<_> = cu.nested_diagnostics().iter()
+        .filter(|d| WhackDiagnostic(d).fx_kind_eq(WhackDiagnosticKind::LintRuleViolation))
+        .map(|d| (d.is_warning(), d.location().first_line_number()))
+        .collect();
+    assert_eq!(lines, vec![(true, 6)]);
+
+    let fixes = verifier.suggested_fixes();
+    assert_eq!(fixes.len(), 1);
+    let mut batch = SourceEditBatch::new();
+    batch.extend(fixes[0].edits.iter().cloned());
+    assert!(batch.apply(SHAPES).unwrap().contains("public class Square implements IShape {"));
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+use whackengine_verifier::ns::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn inspect_lists_long_tags_and_symbols() {
+    let mut symbol_class = 1u16.to_le_bytes().to_vec();
+    symbol_class.extend(5u16.to_le_bytes());
+    symbol_class.extend(b"com.example.Main\0");
+    let mut binary_data = 5u16.to_le_bytes().to_vec();
+    binary_data.extend([0u8; 300]);
+    let mut do_abc = 0u32.to_le_bytes().to_vec();
+    do_abc.extend(b"frame1\0");
+    do_abc.extend(abc_bytes());
+    let swf = swf(&[(87, binary_data), (76, symbol_class), (SwfAbcTag::DO_ABC, do_abc)]);
+
+    let inspection = SwfInspection::inspect(&swf).unwrap();
+    assert_eq!(inspection.version, 10);
+    assert_eq!(inspection.frame_count, 1);
+    let names: Vec<String> = inspection.tags.iter().map(|t| t.name()).collect();
+    assert_eq!(names, ["DefineBinaryData", "SymbolClass", "DoABC", "End"]);
+    assert_eq!(inspection.tags[0].length, 302);
+    assert_eq!(inspection.tags[0].character_id, Some(5));
+    assert_eq!(inspection.tags[0].offset, 13);
+    assert_eq!(inspection.symbol_classes.get(&5).map(|s| s.as_str()), Some("com.example.Main"));
+    assert_eq!(inspection.abc_blocks.len(), 1);
+    assert_eq!(inspection.abc_blocks[0].name, "frame1");
+}
+
+#[test]
+fn compressed_swfs_are_decompressed_before_extraction() {
+    use std::io::Write;
+    let mut do_abc = 0u32.to_le_bytes().to_vec();
+    do_abc.extend(b"frame1\0");
+    do_abc.extend(abc_bytes());
+    let swf = swf(&[(SwfAbcTag::DO_ABC, do_abc)]);
+    let mut encoder = flate2::write::ZlibEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(&swf[8..]).unwrap();
+    let mut compressed = b"CWS".to_vec();
+    compressed.extend_from_slice(&swf[3..8]);
+    compressed.extend(encoder.finish().unwrap());
+
+    let decompressed = SwfAbcTag::decompress(compressed).unwrap();
+    assert_eq!(decompressed, swf);
+    assert_eq!(SwfAbcTag::extract(&decompressed).unwrap()[0].name, "frame1");
+    assert_eq!(SwfAbcTag::decompress(b"not a swf".to_vec()), Err(AbcReadError::UnsupportedSwf));
+}
+
+/// An uncompressed SWF holding a single tag written with a short header.
+fn swf_with_short_tag(code: u16, body: &[u8]) -> Vec<u8> {
+    let mut r = swf(&[]);
+    let end = r.split_off(r.len() - 2);
+    r.extend(((code << 6) | body.len() as u16).to_le_bytes());
+    r.extend(body);
+    r.extend(end);
+    let length = r.len() as u32;
+    r[4..8].copy_from_slice(&length.to_le_bytes());
+    r
+}
+
+#[test]
+fn short_tag_headers_and_exported_assets_are_read() {
+    let mut export_assets = 1u16.to_le_bytes().to_vec();
+    export_assets.extend(3u16.to_le_bytes());
+    export_assets.extend(b"Logo\0");
+    let inspection = SwfInspection::inspect(&swf_with_short_tag(56, &export_assets)).unwrap();
+    let names: Vec<String> = inspection.tags.iter().map(|t| t.name()).collect();
+    assert_eq!(names, ["ExportAssets", "End"]);
+    assert_eq!((inspection.tags[0].offset, inspection.tags[0].length), (13, 9));
+    assert_eq!(inspection.tags[1].offset, 24);
+    assert_eq!(inspection.exports.get(&3).map(|s| s.as_str()), Some("Logo"));
+    assert!(inspection.format_human().contains("\nExported assets:\n      3  Logo\n"), "{}", inspection.format_human());
+}
+
+#[test]
+fn unknown_tags_are_listed_by_code() {
+    let inspection = SwfInspection::inspect(&swf(&[(200, vec![1, 2, 3])])).unwrap();
+    assert_eq!(inspection.tags[0].name(), "Tag200");
+    assert_eq!(inspection.tags[0].character_id, None);
+}
+
+#[test]
+fn truncated_symbols_are_an_error() {
+    let mut symbol_class = 2u16.to_le_bytes().to_vec();
+    symbol_class.extend(1u16.to_le_bytes());
+    symbol_class.extend(b"Main\0");
+    assert_eq!(SwfInspection::inspect(&swf(&[(76, symbol_class)])).err(), Some(AbcReadError::UnexpectedEof));
+}
+
+#[test]
+fn unterminated_abc_block_names_are_an_error() {
+    let mut do_abc = 0u32.to_le_bytes().to_vec();
+    do_abc.extend(b"main");
+    assert_eq!(SwfAbcTag::extract(&swf(&[(SwfAbcTag::DO_ABC, do_abc)])), Err(AbcReadError::UnexpectedEof));
+}
+
+#[test]
+fn libraries_are_read_from_abc_and_swf_files() {
+    assert_eq!(SwfAbcTag::read_library("lib/a.abc", abc_bytes()).unwrap().len(), 1);
+    let swf = swf(&[(SwfAbcTag::DO_ABC1, abc_bytes()), (SwfAbcTag::DO_ABC1, abc_bytes())]);
+    assert_eq!(SwfAbcTag::read_library("library.swf", swf).unwrap().len(), 2);
+    assert_eq!(SwfAbcTag::read_library("library.swf", b"not a swf".to_vec()).err(), Some(AbcReadError::UnsupportedSwf));
+}
\ No newline at end of file
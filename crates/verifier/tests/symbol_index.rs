@@ -0,0 +1,67 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn symbol(name: &str, package: &str, file_path: &str, is_public: bool) -> IndexedSymbol {
+    IndexedSymbol { kind: IndexedSymbolKind::Class, name: name.to_owned(), package: package.to_owned(), file_path: file_path.to_owned(), line: 1, is_public }
+}
+
+#[test]
+fn internal_top_level_definitions_are_visible_from_their_package() {
+    let mut index = SymbolIndex::new();
+    index.update_file("src/Main.as", vec![symbol("Main", "", "src/Main.as", true)]);
+    index.update_file("src/Helper.as", vec![symbol("Helper", "", "src/Helper.as", false)]);
+    index.update_file("lib/com/example/Helpers.as", vec![symbol("Helpers", "com.example", "lib/com/example/Helpers.as", true)]);
+    let names: Vec<String> = FuzzySymbolSearch(&index).search("Help", Some("src/Main.as"), 10).iter().map(|m| m.symbol.name.clone()).collect();
+    assert_eq!(names, vec!["Helper".to_owned(), "Helpers".to_owned()]);
+}
+
+fn instance(name: u32, flags: u8) -> AbcInstanceInfo {
+    AbcInstanceInfo { name, super_name: 0, flags, protected_ns: None, interfaces: vec![], iinit: 0, traits: vec![] }
+}
+
+fn package_trait(name: u32, data: AbcTraitData) -> AbcTrait {
+    AbcTrait { name, attributes: 0, data, metadata: vec![] }
+}
+
+#[test]
+fn library_definitions_are_indexed_under_the_library_path() {
+    let abc = AbcFile {
+        minor_version: 16,
+        major_version: 46,
+        constant_pool: AbcConstantPool {
+            strings: vec!["com.example".to_owned(), "Widget".to_owned(), "IThing".to_owned(), "helper".to_owned(), "secret".to_owned()],
+            namespaces: vec![
+                AbcNamespaceInfo { kind: AbcNamespaceInfo::PACKAGE_NAMESPACE, name: 1 },
+                AbcNamespaceInfo { kind: AbcNamespaceInfo::PRIVATE_NS, name: 1 },
+            ],
+            multinames: vec![
+                AbcMultiname::QName { attribute: false, ns: 1, name: 2 },
+                AbcMultiname::QName { attribute: false, ns: 1, name: 3 },
+                AbcMultiname::QName { attribute: false, ns: 1, name: 4 },
+                AbcMultiname::QName { attribute: false, ns: 2, name: 5 },
+            ],
+            ..AbcConstantPool::default()
+        },
+        instances: vec![instance(1, AbcInstanceInfo::SEALED), instance(2, AbcInstanceInfo::INTERFACE)],
+        classes: vec![AbcClassInfo { cinit: 0, traits: vec![] }, AbcClassInfo { cinit: 0, traits: vec![] }],
+        scripts: vec![AbcScriptInfo { init: 0, traits: vec![
+            package_trait(1, AbcTraitData::Class { slot_id: 0, class: 0 }),
+            package_trait(3, AbcTraitData::Getter { disp_id: 0, method: 0 }),
+            package_trait(3, AbcTraitData::Setter { disp_id: 0, method: 0 }),
+            package_trait(4, AbcTraitData::Method { disp_id: 0, method: 0 }),
+        ] }],
+        ..AbcFile::default()
+    };
+    let library_swf = swf(&[(SwfAbcTag::DO_ABC1, AbcWriter::write(&abc))]);
+    let abc_files = SwfAbcTag::read_library("library.swf", library_swf).unwrap();
+    let mut index = SymbolIndex::new();
+    index.update_file("libs/widgets.swc", abc_files.iter().flat_map(|abc| SymbolCollector::collect_abc("libs/widgets.swc", abc)).collect());
+    let symbols: Vec<(IndexedSymbolKind, String, usize)> = index.file_symbols("libs/widgets.swc").iter().map(|s| (s.kind, s.fully_qualified_name(), s.line)).collect();
+    assert_eq!(symbols, vec![
+        (IndexedSymbolKind::Class, "com.example.Widget".to_owned(), 0),
+        (IndexedSymbolKind::Interface, "com.example.IThing".to_owned(), 0),
+        (IndexedSymbolKind::Variable, "com.example.helper".to_owned(), 0),
+    ]);
+    assert_eq!(index.find_by_name("Widget")[0].file_path, "libs/widgets.swc");
+}
\ No newline at end of file
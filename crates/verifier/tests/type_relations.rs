@@ -0,0 +1,57 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+/// Verifies `text` and returns the host along with the public
+/// top-level definitions of the given names.
+fn verify_top_level(text: &str, names: &[&str]) -> (Rc<Database>, Vec<Entity>) {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    verifier.verify_programs(&compiler_options, vec![parse_main(&compiler_options, text)], vec![]);
+    assert!(!verifier.invalidated());
+    let pckg = host.top_level_package();
+    let definitions = names.iter().map(|name| {
+        let qname = host.factory().create_qname(&pckg.public_ns().unwrap(), (*name).to_owned());
+        pckg.properties(&host).get(&qname).unwrap()
+    }).collect();
+    (host, definitions)
+}
+
+#[test]
+fn answered_queries_are_served_from_the_cache() {
+    let (host, classes) = verify_top_level("package { public class A {} public class B extends A {} }", &["A", "B"]);
+    let (a, b) = (&classes[0], &classes[1]);
+    let cache = TypeRelationCache::new();
+    assert!(cache.is_subtype_of(&host, b, a).unwrap());
+    assert_eq!(cache.statistics(), (0, 1));
+    assert!(cache.is_subtype_of(&host, b, a).unwrap());
+    assert_eq!(cache.statistics(), (1, 1));
+    assert!(!cache.is_subtype_of(&host, a, b).unwrap());
+    assert_eq!(cache.statistics(), (1, 2));
+
+    cache.invalidate();
+    assert!(cache.is_subtype_of(&host, b, a).unwrap());
+    assert_eq!(cache.statistics(), (1, 3));
+}
+
+#[test]
+fn common_supertypes_and_coercions_are_cached() {
+    let (host, classes) = verify_top_level(
+        "package { public class A {} public class B extends A {} public class C extends A {} public interface I {} }",
+        &["A", "B", "C", "I"]);
+    let (a, b, c, i) = (&classes[0], &classes[1], &classes[2], &classes[3]);
+    let cache = TypeRelationCache::new();
+    assert!(cache.common_supertype(&host, b, c).unwrap() == *a);
+    assert!(cache.common_supertype(&host, b, b).unwrap() == *b);
+    assert!(cache.common_supertype(&host, b, i).unwrap() == host.any_type());
+    let (hits, misses) = cache.statistics();
+    assert!(cache.common_supertype(&host, b, c).unwrap() == *a);
+    assert_eq!(cache.statistics(), (hits + 1, misses));
+
+    assert!(cache.implicitly_coercible(&host, b, a).unwrap());
+    assert!(!cache.implicitly_coercible(&host, a, b).unwrap());
+    let (hits, misses) = cache.statistics();
+    assert!(cache.implicitly_coercible(&host, b, a).unwrap());
+    assert_eq!(cache.statistics(), (hits + 1, misses));
+}
\ No newline at end of file
@@ -0,0 +1,274 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn switch_fallthrough() -> CompilerOptions {
+    let mut warnings = CompilerOptions::default().warnings;
+    warnings.switch_fallthrough = true;
+    CompilerOptions { warnings, ..default() }
+}
+
+#[test]
+fn case_returning_through_finally_does_not_fall_through() {
+    assert_fixture(switch_fallthrough(), r#"
+package {
+    public function f(v: Number): void {
+        switch (v) {
+            case 0:
+                try {
+                    return;
+                } finally {
+                    trace("done");
+                }
+            case 1:
+                trace("one");
+        }
+    }
+}
+"#);
+}
+
+#[test]
+fn case_completing_normally_falls_through() {
+    assert_fixture(switch_fallthrough(), r#"
+package {
+    public function f(v: Number): void {
+        switch (v) {
+            case 0:
+                trace("zero");
+            case 1:
+                // expect-warning: SwitchCaseFallthrough @ 7:13
+                trace("one");
+        }
+    }
+}
+"#);
+}
+
+#[test]
+fn fallthrough_comments_annotate_cases() {
+    assert_fixture(switch_fallthrough(), r#"
+package {
+    public function f(v: Number): void {
+        switch (v) {
+            case 0:
+                trace("zero");
+                // falls through
+            case 1:
+                trace("one");
+                /* FALLTHROUGH */
+            case 2:
+                trace("two");
+        }
+    }
+}
+"#);
+}
+
+#[test]
+fn fallthrough_warnings_are_opt_in() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public function f(v: Number): void {
+        switch (v) {
+            case 0:
+                trace("zero");
+            case 1:
+                trace("one");
+        }
+    }
+}
+"#);
+}
+
+#[test]
+fn numeric_api_codes_are_not_versions() {
+    assert_fixture(CompilerOptions { target_runtime_version: Some(RuntimeVersion::new(10, 0)), ..default() }, r#"
+package {
+    [API("667")]
+    public class Legacy {}
+    [API("11.4")]
+    public class Modern {}
+    public class Main {
+        public function Main() {
+            new Legacy();
+            new Modern(); // expect-error: ApiRequiresRuntimeVersion @ 10:17
+        }
+    }
+}
+"#);
+}
+
+#[test]
+fn duplicate_diagnostics_are_capped_per_span() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public function f(): void {
+        trace(u); // expect-error: UndefinedProperty @ 4:15
+        trace(u); // expect-error: UndefinedProperty @ 5:15
+        trace(u); // expect-error: UndefinedProperty @ 6:15
+        trace(u); // expect-error: UndefinedProperty @ 7:15
+        trace(u); // expect-error: UndefinedProperty @ 8:15
+        trace(u); // expect-error: UndefinedProperty @ 9:15
+        trace(u); // expect-error: UndefinedProperty @ 10:15
+        trace(u); // expect-error: UndefinedProperty @ 11:15
+        trace(u); // expect-error: UndefinedProperty @ 12:15
+        trace(u); // expect-error: UndefinedProperty @ 13:15
+        trace(u); // expect-warning: DuplicateDiagnosticsSuppressed @ 14:15
+    }
+}
+"#);
+}
+
+#[test]
+fn this_in_static_method_and_at_top_level() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public class Main {
+        public static function f(): void {
+            trace(this); // expect-error: ThisUsedInStaticContext @ 5:19
+        }
+    }
+}
+trace(this); // expect-error: UnexpectedThis @ 9:7
+"#);
+}
+
+#[test]
+fn top_level_classes_overload_operators() {
+    let mut compiler_options = CompilerOptions { operator_overloading: true, ..default() };
+    compiler_options.warnings.unused = false;
+    assert_fixture(compiler_options, r#"
+package {
+    public class Vec2 {
+        public static function op_subtract(a: Vec2, b: Vec2): Vec2 {
+            return a;
+        }
+    }
+    public function f(a: Vec2, b: Vec2): Vec2 {
+        return a - b;
+    }
+}
+"#);
+}
+
+#[test]
+fn await_accepts_the_configured_promise_like_type() {
+    let text = r#"
+package {
+    public class Deferred {}
+    public function f(d: Deferred): Promise.<*> {
+        trace(await d);
+    }
+}
+"#;
+    assert_fixture(CompilerOptions { promise_like_type: Some("Deferred".into()), ..default() }, text);
+    assert_fixture(CompilerOptions::default(), &format!("{text}// expect-error: AwaitOperandMustBeAPromise @ 5:15\n"));
+}
+
+#[test]
+fn internal_api_references_are_reported_through_package_names() {
+    assert_fixture(CompilerOptions::default(), r#"
+package mx.core {
+    [InternalAPI] public function helper(): void {}
+}
+package app {
+    import mx.core.*;
+    public function run(): void {
+        helper(); // expect-error: InternalApiReference @ 8:9
+        mx.core.helper(); // expect-error: InternalApiReference @ 9:9
+    }
+}
+"#);
+}
+
+#[test]
+fn collection_literals_coerce_their_elements() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    class A {}
+    public function f(): void {
+        // expect-error: ImplicitCoercionToUnrelatedType @ 6:57
+        var m: Map.<String, A> = [["a", new A()], ["b", 1]];
+        // expect-error: ImplicitCoercionToUnrelatedType @ 8:36
+        var s: Set.<A> = [new A(), "y"];
+        trace(m, s);
+    }
+}
+"#);
+}
+
+#[test]
+fn tuple_rest_patterns_hold_the_remaining_element_types() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    class A {}
+    function triple(): [Number, String, A] {
+        return [1, "a", new A()];
+    }
+    public function f(): void {
+        var [n, ...rest] = triple();
+        var s: String = rest[0];
+        // expect-error: ImplicitCoercionToUnrelatedType @ 11:20
+        var b: A = rest[0];
+        // expect-error: TupleIndexOutOfRange @ 13:22
+        var c = rest[2];
+        trace(n, s, b, c);
+    }
+}
+"#);
+}
+
+#[test]
+fn enums_have_typed_value_of_and_to_string_methods() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    class A {}
+    enum Color {
+        const RED;
+        const GREEN = "green";
+    }
+    public function f(): void {
+        var n: Number = Color.RED.valueOf();
+        var s: String = Color.GREEN.toString();
+        // expect-error: ImplicitCoercionToUnrelatedType @ 13:20
+        var a: A = Color.RED.valueOf();
+        // expect-error: ImplicitCoercionToUnrelatedType @ 15:20
+        var b: A = Color.GREEN.toString();
+        trace(n, s, a, b);
+    }
+}
+"#);
+}
+
+#[test]
+fn references_to_packages_absent_from_the_target_profile_are_reported() {
+    assert_fixture(CompilerOptions { target_profile: TargetProfile::Headless, ..default() }, r#"
+package flash.display {
+    public class Sprite {}
+    public function stage(): Sprite { return null; }
+}
+package app {
+    import flash.display.*; // expect-error: UnavailableInTargetProfile @ 7:5
+    public function run(): void {
+        trace(new Sprite()); // expect-error: UnavailableInTargetProfile @ 9:19
+        flash.display.stage(); // expect-error: UnavailableInTargetProfile @ 10:9
+    }
+}
+"#);
+}
+
+#[test]
+fn static_assertions_are_evaluated_at_compile_time() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public function f(v: Boolean): void {
+        static_assert(1 == 1);
+        static_assert("a" == "b", "Strings differ."); // expect-error: StaticAssertFailed @ 5:9
+        static_assert(false); // expect-error: StaticAssertFailed @ 6:9
+        static_assert(v); // expect-error: StaticAssertRequiresConstant @ 7:23
+        static_assert(true, v); // expect-error: MalformedStaticAssert @ 8:9
+    }
+}
+"#);
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+#[test]
+fn config_elimination_report_is_reset_per_verification() {
+    let compiler_options = Rc::new(CompilerOptions { config_elimination_report: true, ..default() });
+    let host = Rc::new(Database::new(Default::default()));
+    host.config_constants().set("CONFIG::debug".into(), "false".into());
+    let mut verifier = Verifier::new(&host);
+
+    let (_, mut programs) = CoreLibrary::parse(&compiler_options);
+    programs.push(parse_main(&compiler_options, "CONFIG::debug {\n}"));
+    verifier.verify_programs(&compiler_options, programs, vec![]);
+    assert_eq!(verifier.config_elimination_report().map(|r| r.len()), Some(1));
+
+    verifier.verify_programs(&compiler_options, vec![parse_main(&compiler_options, "CONFIG::debug {\n}")], vec![]);
+    assert_eq!(verifier.config_elimination_report().map(|r| r.len()), Some(1));
+}
+
+#[test]
+fn diagnostic_kinds_resolve_past_unused_ids() {
+    assert_eq!(WhackDiagnosticKind::from_id(2230), None);
+    let kind = WhackDiagnosticKind::from_name("BindableEventNotDeclared").unwrap();
+    assert_eq!(WhackDiagnosticKind::from_id(kind.id()), Some(kind));
+    assert!(WhackDiagnosticKind::ALL.windows(2).all(|w| w[0].id() < w[1].id()));
+}
+
+#[test]
+fn core_library_is_verified_once_with_the_first_programs() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    for text in ["trace(\"a\".toUpperCase());", "trace(\"b\".toUpperCase());"] {
+        let program = parse_main(&compiler_options, text);
+        verifier.verify_programs(&compiler_options, vec![program.clone()], vec![]);
+        assert!(!verifier.invalidated(), "{text}");
+        assert!(program.location.compilation_unit().nested_diagnostics().is_empty(), "{text}");
+    }
+}
+
+#[test]
+fn cancelled_verifications_end_the_verification_phase() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let host = Rc::new(Database::new(Default::default()));
+    let mut verifier = Verifier::new(&host);
+    let token = CancellationToken::new();
+    verifier.set_cancellation_token(Some(token.clone()));
+    token.cancel();
+    verifier.verify_programs(&compiler_options, vec![parse_main(&compiler_options, "package { public class Main {} }")], vec![]);
+    assert!(verifier.timings().measured(CompilerPhase::DeclarationCollection));
+    assert!(verifier.timings().measured(CompilerPhase::Verification));
+}
+
+#[test]
+fn core_library_is_parsed_once_per_compiler_options() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let (_, first) = CoreLibrary::parse(&compiler_options);
+    let mut verifier = Verifier::new(&Rc::new(Database::new(Default::default())));
+    verifier.verify_programs(&compiler_options, vec![parse_main(&compiler_options, "trace(1);")], vec![]);
+    assert!(!verifier.invalidated());
+    let (_, second) = CoreLibrary::parse(&compiler_options);
+    assert!(first.iter().zip(second.iter()).all(|(a, b)| Rc::ptr_eq(a, b)));
+    let (_, other) = CoreLibrary::parse(&Rc::new(CompilerOptions::default()));
+    assert!(!Rc::ptr_eq(&first[0], &other[0]));
+}
+
+#[test]
+fn durations_are_recorded_for_the_passes_run() {
+    let compiler_options = Rc::new(CompilerOptions::default());
+    let mut verifier = Verifier::new(&Rc::new(Database::new(Default::default())));
+    verifier.set_pass_manager(PassManager::new().up_to(VerifierPass::SignatureResolution));
+    verifier.verify_programs(&compiler_options, vec![parse_main(&compiler_options, "package { public class Main {} }")], vec![]);
+    assert!(verifier.pass_duration(VerifierPass::DeclarationCollection).is_some());
+    assert!(verifier.pass_duration(VerifierPass::SignatureResolution).is_some());
+    assert_eq!(verifier.pass_duration(VerifierPass::BodyVerification), None);
+}
\ No newline at end of file
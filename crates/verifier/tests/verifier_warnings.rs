@@ -0,0 +1,149 @@
+mod common;
+use common::*;
+use whackengine_verifier::ns::*;
+
+fn warnings(warnings: CompilerWarningOptions) -> CompilerOptions {
+    CompilerOptions { warnings, ..default() }
+}
+
+#[test]
+fn handler_registered_before_its_method_is_followed() {
+    let mut options = CompilerOptions::default().warnings;
+    options.unused = false;
+    options.uncaught_handler_errors = true;
+    assert_fixture(warnings(options), r#"
+package {
+    public class Button {
+        public function Button() {
+            addEventListener("click", onClick);
+        }
+        public function addEventListener(type: String, listener: Function): void {}
+        private function onClick(event: Object): void {
+            throw new Error("click"); // expect-warning: UncaughtErrorInEventHandler @ 9:13
+        }
+    }
+}
+"#);
+}
+
+const LOOP_REDECLARATION: &str = r#"
+package {
+    public function f(): void {
+        for (var i: Number = 0; i < 2; i++) {
+            var i: Number = 1;
+            trace(i);
+        }
+    }
+}
+"#;
+
+#[test]
+fn loop_variable_redeclaration_is_opt_in() {
+    assert_fixture(CompilerOptions::default(), LOOP_REDECLARATION);
+    let text = LOOP_REDECLARATION.replace("var i: Number = 1;", "var i: Number = 1; // expect-warning: LoopVariableRedeclared @ 5:17");
+    let mut options = CompilerOptions::default().warnings;
+    options.loop_variable_redeclaration = true;
+    assert_fixture(warnings(options), &text);
+}
+
+#[test]
+fn use_before_declaration_is_a_warning() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public function f(): void {
+        trace(k); // expect-warning: UseBeforeDeclaration @ 4:15
+        const k: Number = 1;
+    }
+}
+"#);
+}
+
+#[test]
+fn duplicate_switch_case_is_found_among_non_constant_cases() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public function f(s: String, t: String): void {
+        switch (s) {
+            case "a":
+                trace(1);
+                break;
+            case t:
+                trace(2);
+                break;
+            case "a": // expect-warning: DuplicateSwitchCase @ 11:18
+                trace(3);
+                break;
+        }
+    }
+}
+"#);
+}
+
+const PARAMETER_SHADOWING: &str = r#"
+package {
+    public function f(a: Number): void {
+        if (a > 0) {
+            var a: Number = 1;
+            trace(a);
+        }
+    }
+}
+"#;
+
+#[test]
+fn shadowing_is_opt_in() {
+    assert_fixture(CompilerOptions::default(), PARAMETER_SHADOWING);
+    let mut options = CompilerOptions::default().warnings;
+    options.shadowing.parameters = true;
+    let text = PARAMETER_SHADOWING.replace("var a: Number = 1;", "var a: Number = 1; // expect-warning: ShadowedDeclaration @ 5:17");
+    assert_fixture(warnings(options), &text);
+}
+
+const DESCRIBED_CLASS: &str = r#"
+package flash.utils {
+    public function describeType(value: *): XML {
+        return null;
+    }
+}
+package {
+    import flash.utils.describeType;
+    [Event(name="change", type="Object")]
+    public class Model {
+        [Inject]
+        public var service: Object;
+    }
+    public function f(): void {
+        describeType(Model);
+    }
+}
+"#;
+
+#[test]
+fn stripped_metadata_warnings_are_opt_in_and_skip_compiler_metadata() {
+    let mut compiler_options = CompilerOptions::default();
+    compiler_options.warnings.unused = false;
+    assert_fixture(compiler_options.clone(), DESCRIBED_CLASS);
+    compiler_options.warnings.stripped_metadata = true;
+    let text = DESCRIBED_CLASS.replace("describeType(Model);", "describeType(Model); // expect-warning: DescribedMetadataStripped @ 15:22");
+    assert_fixture(compiler_options.clone(), &text);
+    compiler_options.retained_metadata.push("Inject".into());
+    assert_fixture(compiler_options, DESCRIBED_CLASS);
+}
+
+#[test]
+fn comparisons_with_undefined_tell_null_from_undefined() {
+    assert_fixture(CompilerOptions::default(), r#"
+package {
+    public function f(n: Number, o: Object, a: *): void {
+        // expect-warning: ComparisonWithUndefined @ 5:13
+        if (n === undefined) trace(n);
+        // expect-warning: ComparisonWithUndefined @ 7:13
+        if (n != undefined) trace(n);
+        if (o == undefined) trace(o);
+        // expect-warning: ComparisonWithUndefined @ 10:13
+        if (o !== undefined) trace(o);
+        if (a === undefined) trace(a);
+    }
+}
+"#);
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use whackengine_verifier::ns::*;
+
+fn parse(text: &str) -> Rc<Program> {
+    let cu = CompilationUnit::new(None, text.to_owned());
+    let program = ParserFacade(&cu, ParserOptions::default()).parse_program();
+    assert!(!cu.invalidated(), "syntax error in test source");
+    program
+}
+
+struct CallCounter(usize);
+
+impl Visitor for CallCounter {
+    fn visit_expression(&mut self, exp: &Rc<Expression>) {
+        if matches!(exp.as_ref(), Expression::Call(_)) {
+            self.0 += 1;
+        }
+        walk_expression(self, exp);
+    }
+}
+
+#[derive(Default)]
+struct Identifiers(Vec<String>);
+
+impl Visitor for Identifiers {
+    fn visit_qualified_identifier(&mut self, id: &QualifiedIdentifier) {
+        if let QualifiedIdentifierIdentifier::Id((name, _)) = &id.id {
+            self.0.push(name.clone());
+        }
+        walk_qualified_identifier(self, id);
+    }
+}
+
+struct Rename(&'static str, &'static str);
+
+impl MutVisitor for Rename {
+    fn visit_qualified_identifier(&mut self, id: &mut QualifiedIdentifier) {
+        if let QualifiedIdentifierIdentifier::Id((name, _)) = &mut id.id {
+            if *name == self.0 {
+                *name = self.1.to_owned();
+            }
+        }
+        walk_qualified_identifier_mut(self, id);
+    }
+}
+
+fn identifiers(program: &Rc<Program>) -> Vec<String> {
+    let mut names = Identifiers::default();
+    names.visit_program(program);
+    names.0
+}
+
+#[test]
+fn visitor_walks_into_packages_classes_and_function_expressions() {
+    let program = parse("package p { public class C { function m() { a(); var g = function() { a(1, a()); }; } } }\nb();");
+    let mut counter = CallCounter(0);
+    counter.visit_program(&program);
+    assert_eq!(counter.0, 4);
+}
+
+#[test]
+fn mut_visitor_rewrites_a_copy_of_shared_nodes() {
+    let program = parse("function f(x) { trace(a + x); if (x) { a = 1; } }");
+    let mut rewritten = program.clone();
+    Rename("a", "b").visit_program(&mut rewritten);
+    let names = identifiers(&rewritten);
+    assert_eq!(names.iter().filter(|n| *n == "b").count(), 2, "{names:?}");
+    assert!(!names.contains(&"a".to_owned()), "{names:?}");
+    assert_eq!(identifiers(&program).iter().filter(|n| *n == "a").count(), 2);
+}
\ No newline at end of file
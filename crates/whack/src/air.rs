@@ -0,0 +1,14 @@
+mod air_descriptor;
+pub use air_descriptor::*;
+
+mod ane_package;
+pub use ane_package::*;
+
+mod air_packager;
+pub use air_packager::*;
+
+mod zip_writer;
+pub use zip_writer::*;
+
+mod zip_reader;
+pub use zip_reader::*;
\ No newline at end of file
@@ -0,0 +1,124 @@
+use whackengine_verifier::ns::*;
+
+/// Namespace prefix of AIR application descriptors, followed by the AIR version.
+pub const AIR_NAMESPACE_PREFIX: &'static str = "http://ns.adobe.com/air/application/";
+
+/// An AIR application descriptor (`-app.xml`).
+#[derive(Clone, Debug)]
+pub struct AirDescriptor {
+    /// AIR version taken from the root element namespace, such as `(33, 1)`.
+    pub namespace_version: (u32, u32),
+    pub id: Option<String>,
+    pub filename: Option<String>,
+    pub name: Option<String>,
+    /// `versionNumber`, or `version` for AIR versions before 2.5.
+    pub version: Option<String>,
+    /// The `initialWindow.content` element.
+    pub content: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AirDescriptorError {
+    /// The descriptor is not well-formed XML.
+    Malformed,
+    /// The root element is not `application`.
+    InvalidRoot,
+    /// The root namespace is not an AIR application namespace.
+    InvalidNamespace(String),
+    MissingElement(String),
+    /// `initialWindow.content` does not match the build output.
+    ContentMismatch { expected: String, found: String },
+    InvalidVersionNumber(String),
+}
+
+impl AirDescriptorError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Malformed => "Application descriptor is not well-formed XML.".into(),
+            Self::InvalidRoot => "Application descriptor root element must be 'application'.".into(),
+            Self::InvalidNamespace(ns) => format!("Unknown application descriptor namespace '{ns}'."),
+            Self::MissingElement(name) => format!("Application descriptor is missing the '{name}' element."),
+            Self::ContentMismatch { expected, found } => format!("Initial window content '{found}' does not match the build output '{expected}'."),
+            Self::InvalidVersionNumber(v) => format!("Invalid version number '{v}'; expected up to three dot-separated integers."),
+        }
+    }
+}
+
+impl AirDescriptor {
+    /// Parses an application descriptor.
+    pub fn parse(file_path: &str, text: &str) -> Result<AirDescriptor, AirDescriptorError> {
+        let cu = CompilationUnit::new(Some(file_path.to_owned()), text.to_owned());
+        let mxml = ParserFacade(&cu, ParserOptions::default()).parse_mxml();
+        if cu.invalidated() {
+            return Err(AirDescriptorError::Malformed);
+        }
+        let Some(root) = mxml.content.iter().find_map(|c| if let MxmlContent::Element(e) = c.as_ref() { Some(e.clone()) } else { None }) else {
+            return Err(AirDescriptorError::InvalidRoot);
+        };
+        if root.name.name != "application" {
+            return Err(AirDescriptorError::InvalidRoot);
+        }
+        let ns = root.namespace.get(root.name.prefix.as_ref().map(|p| p.as_str()).unwrap_or("")).unwrap_or_default();
+        let Some(namespace_version) = ns.strip_prefix(AIR_NAMESPACE_PREFIX).and_then(Self::parse_namespace_version) else {
+            return Err(AirDescriptorError::InvalidNamespace(ns));
+        };
+
+        let child_text = |element: &Rc<MxmlElement>, name: &str| -> Option<String> {
+            Self::child(element, name).map(|e| e.inner_text().trim().to_owned())
+        };
+
+        let version = if namespace_version >= (2, 5) {
+            child_text(&root, "versionNumber")
+        } else {
+            child_text(&root, "version")
+        };
+
+        Ok(AirDescriptor {
+            namespace_version,
+            id: child_text(&root, "id"),
+            filename: child_text(&root, "filename"),
+            name: child_text(&root, "name"),
+            version,
+            content: Self::child(&root, "initialWindow").and_then(|w| child_text(&w, "content")),
+        })
+    }
+
+    /// Validates the descriptor against the SWF file name produced by the build.
+    pub fn validate(&self, output_swf: &str) -> Vec<AirDescriptorError> {
+        let mut r = vec![];
+        let version_element = if self.namespace_version >= (2, 5) { "versionNumber" } else { "version" };
+        for (name, value) in [("id", &self.id), ("filename", &self.filename), (version_element, &self.version), ("initialWindow.content", &self.content)] {
+            if value.is_none() {
+                r.push(AirDescriptorError::MissingElement(name.into()));
+            }
+        }
+        if self.namespace_version >= (2, 5) {
+            if let Some(v) = self.version.as_ref() {
+                let valid = v.split('.').count() <= 3 && v.split('.').all(|n| n.parse::<u32>().is_ok());
+                if !valid {
+                    r.push(AirDescriptorError::InvalidVersionNumber(v.clone()));
+                }
+            }
+        }
+        if let Some(content) = self.content.as_ref() {
+            if content != output_swf {
+                r.push(AirDescriptorError::ContentMismatch { expected: output_swf.to_owned(), found: content.clone() });
+            }
+        }
+        r
+    }
+
+    fn parse_namespace_version(version: &str) -> Option<(u32, u32)> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse::<u32>().ok()?;
+        let minor = parts.next().map(|m| m.parse::<u32>().ok()).unwrap_or(Some(0))?;
+        Some((major, minor))
+    }
+
+    fn child(element: &Rc<MxmlElement>, name: &str) -> Option<Rc<MxmlElement>> {
+        element.content.as_ref()?.iter().find_map(|c| match c.as_ref() {
+            MxmlContent::Element(e) if e.name.name == name => Some(e.clone()),
+            _ => None,
+        })
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+use crate::air::*;
+
+/// Packages an unsigned AIR bundle (`.airi`).
+///
+/// The `mimetype` entry is written first, followed by the application
+/// descriptor at `META-INF/AIR/application.xml`, the compiled SWF
+/// and the declared assets.
+pub struct AirPackager {
+    pub descriptor_path: PathBuf,
+    pub swf_path: PathBuf,
+    /// Asset files, paired with their path inside the bundle.
+    pub assets: Vec<(String, PathBuf)>,
+}
+
+#[derive(Debug)]
+pub enum AirPackageError {
+    Io(std::io::Error),
+    InvalidDescriptor(Vec<AirDescriptorError>),
+}
+
+impl From<std::io::Error> for AirPackageError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl AirPackager {
+    pub const MIMETYPE: &'static str = "application/vnd.adobe.air-application-installer-package+zip";
+
+    pub fn package(&self, output: &Path) -> Result<(), AirPackageError> {
+        let descriptor_text = std::fs::read_to_string(&self.descriptor_path)?;
+        let descriptor = AirDescriptor::parse(&self.descriptor_path.to_string_lossy(), &descriptor_text)
+            .map_err(|e| AirPackageError::InvalidDescriptor(vec![e]))?;
+        let swf_name = self.swf_path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let errors = descriptor.validate(&swf_name);
+        if !errors.is_empty() {
+            return Err(AirPackageError::InvalidDescriptor(errors));
+        }
+
+        let mut zip = ZipWriter::new();
+        zip.add_file("mimetype", Self::MIMETYPE.as_bytes());
+        zip.add_file("META-INF/AIR/application.xml", descriptor_text.as_bytes());
+        zip.add_file(&swf_name, &std::fs::read(&self.swf_path)?);
+        for (name, path) in self.assets.iter() {
+            zip.add_file(&name.replace('\\', "/"), &std::fs::read(path)?);
+        }
+        std::fs::write(output, zip.finish())?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+use whackengine_verifier::ns::*;
+use super::*;
+
+/// Namespace prefix of native extension descriptors, followed by the AIR version.
+pub const ANE_NAMESPACE_PREFIX: &'static str = "http://ns.adobe.com/air/extension/";
+
+/// A native extension descriptor (`META-INF/ANE/extension.xml`).
+#[derive(Clone, Debug)]
+pub struct AneDescriptor {
+    /// Extension ID given to `ExtensionContext.createExtensionContext()`.
+    pub id: String,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    /// Names of the `platforms.platform` elements, such as `iPhone-ARM`.
+    pub platforms: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AneError {
+    Zip(ZipReadError),
+    /// The package has no `META-INF/ANE/extension.xml`.
+    MissingDescriptor,
+    /// The descriptor is not well-formed XML.
+    Malformed,
+    /// The root element is not `extension` in an extension namespace.
+    InvalidRoot,
+    MissingElement(String),
+    /// The package has no `library.swf`.
+    MissingLibrary,
+    InvalidLibrary(AbcReadError),
+}
+
+impl AneError {
+    pub fn message(&self) -> String {
+        match self {
+            Self::Zip(error) => format!("Native extension is not a valid ZIP archive ({error:?})."),
+            Self::MissingDescriptor => "Native extension is missing 'META-INF/ANE/extension.xml'.".into(),
+            Self::Malformed => "Extension descriptor is not well-formed XML.".into(),
+            Self::InvalidRoot => "Extension descriptor root element must be 'extension'.".into(),
+            Self::MissingElement(name) => format!("Extension descriptor is missing the '{name}' element."),
+            Self::MissingLibrary => "Native extension is missing 'library.swf'.".into(),
+            Self::InvalidLibrary(error) => format!("Native extension 'library.swf' is invalid ({error:?})."),
+        }
+    }
+}
+
+impl AneDescriptor {
+    pub const PATH: &'static str = "META-INF/ANE/extension.xml";
+
+    /// Parses an extension descriptor.
+    pub fn parse(file_path: &str, text: &str) -> Result<AneDescriptor, AneError> {
+        let cu = CompilationUnit::new(Some(file_path.to_owned()), text.to_owned());
+        let mxml = ParserFacade(&cu, ParserOptions::default()).parse_mxml();
+        if cu.invalidated() {
+            return Err(AneError::Malformed);
+        }
+        let Some(root) = mxml.content.iter().find_map(|c| if let MxmlContent::Element(e) = c.as_ref() { Some(e.clone()) } else { None }) else {
+            return Err(AneError::InvalidRoot);
+        };
+        let ns = root.namespace.get(root.name.prefix.as_ref().map(|p| p.as_str()).unwrap_or("")).unwrap_or_default();
+        if root.name.name != "extension" || !ns.starts_with(ANE_NAMESPACE_PREFIX) {
+            return Err(AneError::InvalidRoot);
+        }
+        let child_text = |element: &Rc<MxmlElement>, name: &str| -> Option<String> {
+            Self::children(element, name).first().map(|e| e.inner_text().trim().to_owned())
+        };
+        let platforms = Self::children(&root, "platforms").first()
+            .map(|p| Self::children(p, "platform"))
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|p| p.attributes.iter().find(|a| !a.xmlns && a.name.name == "name").map(|a| a.value.0.clone()))
+            .collect();
+        Ok(AneDescriptor {
+            id: child_text(&root, "id").ok_or(AneError::MissingElement("id".into()))?,
+            version: child_text(&root, "versionNumber"),
+            name: child_text(&root, "name"),
+            platforms,
+        })
+    }
+
+    fn children(element: &Rc<MxmlElement>, name: &str) -> Vec<Rc<MxmlElement>> {
+        element.content.as_ref().map(|content| content.iter().filter_map(|c| match c.as_ref() {
+            MxmlContent::Element(e) if e.name.name == name => Some(e.clone()),
+            _ => None,
+        }).collect()).unwrap_or_default()
+    }
+}
+
+/// A loaded native extension (`.ane`) whose ActionScript library is
+/// supplied to the verifier as `native` declarations.
+///
+/// ```ignore
+/// let ane = Rc::new(AnePackage::load("Vibration.ane", &std::fs::read(path)?)?);
+/// compiler_options.native_extension_ids = Some(vec![ane.descriptor.id.clone()]);
+/// Compilation::run_with_providers(&host, &compiler_options, &sources, &[ane]);
+/// ```
+pub struct AnePackage {
+    pub descriptor: AneDescriptor,
+    library: MemoryDefinitionProvider,
+}
+
+impl AnePackage {
+    pub fn load(file_path: &str, bytes: &[u8]) -> Result<AnePackage, AneError> {
+        let zip = ZipReader::new(bytes).map_err(AneError::Zip)?;
+        let descriptor = zip.read(AneDescriptor::PATH).ok_or(AneError::MissingDescriptor)?.map_err(AneError::Zip)?;
+        let descriptor = AneDescriptor::parse(&format!("{file_path}/{}", AneDescriptor::PATH), &String::from_utf8_lossy(&descriptor))?;
+
+        // The root library.swf is the default platform's; fall back
+        // to that of any platform.
+        let library_entry = zip.entries().iter().find(|e| e.name == "library.swf")
+            .or_else(|| zip.entries().iter().find(|e| e.name.starts_with("META-INF/ANE/") && e.name.ends_with("/library.swf")))
+            .ok_or(AneError::MissingLibrary)?;
+        let swf = SwfAbcTag::decompress(zip.read_entry(library_entry).map_err(AneError::Zip)?).map_err(AneError::InvalidLibrary)?;
+
+        let mut library = MemoryDefinitionProvider::new("ane");
+        for tag in SwfAbcTag::extract(&swf).map_err(AneError::InvalidLibrary)? {
+            let abc = tag.read_abc().map_err(AneError::InvalidLibrary)?;
+            for (definition, source) in AbcStubGenerator::generate(&abc) {
+                library.insert(definition, &source);
+            }
+        }
+        Ok(AnePackage { descriptor, library })
+    }
+}
+
+impl DefinitionProvider for AnePackage {
+    fn name(&self) -> String {
+        "ane".into()
+    }
+
+    fn definitions(&self) -> Vec<ProvidedDefinition> {
+        self.library.definitions()
+    }
+
+    fn materialize(&self, definition: &ProvidedDefinition) -> Option<String> {
+        self.library.materialize(definition)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use flate2::read::DeflateDecoder;
+use std::io::Read;
+
+/// Reads ZIP archives with stored or deflated entries.
+pub struct ZipReader<'a> {
+    bytes: &'a [u8],
+    entries: Vec<ZipReaderEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ZipReaderEntry {
+    pub name: String,
+    /// Compression method; 0 for stored and 8 for deflated entries.
+    pub method: u16,
+    pub compressed_size: u32,
+    pub size: u32,
+    offset: u32,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ZipReadError {
+    /// The end of central directory record is missing or truncated.
+    NotAZip,
+    UnexpectedEof,
+    UnsupportedMethod(u16),
+    /// Deflated data is corrupt or does not have the size given by the
+    /// central directory.
+    InvalidData,
+}
+
+impl<'a> ZipReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ZipReadError> {
+        // The end of central directory record is at least 22 bytes,
+        // followed by a comment of up to 65535 bytes.
+        let min = bytes.len().saturating_sub(22 + 0xFFFF);
+        let eocd = (min..=bytes.len().saturating_sub(22)).rev()
+            .find(|&i| Self::u32_at(bytes, i) == Some(0x06054B50))
+            .ok_or(ZipReadError::NotAZip)?;
+        let count = Self::u16_at(bytes, eocd + 10).ok_or(ZipReadError::NotAZip)?;
+        let mut offset = Self::u32_at(bytes, eocd + 16).ok_or(ZipReadError::NotAZip)? as usize;
+
+        let mut entries = vec![];
+        for _ in 0..count {
+            if Self::u32_at(bytes, offset) != Some(0x02014B50) {
+                return Err(ZipReadError::UnexpectedEof);
+            }
+            let field = |at: usize| Self::u16_at(bytes, offset + at).ok_or(ZipReadError::UnexpectedEof);
+            let method = field(10)?;
+            let compressed_size = Self::u32_at(bytes, offset + 20).ok_or(ZipReadError::UnexpectedEof)?;
+            let size = Self::u32_at(bytes, offset + 24).ok_or(ZipReadError::UnexpectedEof)?;
+            let name_len = field(28)? as usize;
+            let extra_len = field(30)? as usize;
+            let comment_len = field(32)? as usize;
+            let local_offset = Self::u32_at(bytes, offset + 42).ok_or(ZipReadError::UnexpectedEof)?;
+            let name = bytes.get(offset + 46..offset + 46 + name_len).ok_or(ZipReadError::UnexpectedEof)?;
+            entries.push(ZipReaderEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method,
+                compressed_size,
+                size,
+                offset: local_offset,
+            });
+            offset += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Self { bytes, entries })
+    }
+
+    pub fn entries(&self) -> &[ZipReaderEntry] {
+        &self.entries
+    }
+
+    /// Reads the contents of the entry of a given name.
+    pub fn read(&self, name: &str) -> Option<Result<Vec<u8>, ZipReadError>> {
+        let entry = self.entries.iter().find(|e| e.name == name)?;
+        Some(self.read_entry(entry))
+    }
+
+    /// Reads the contents of an entry. The output is bounded by the
+    /// size recorded in the central directory.
+    pub fn read_entry(&self, entry: &ZipReaderEntry) -> Result<Vec<u8>, ZipReadError> {
+        let offset = entry.offset as usize;
+        if Self::u32_at(self.bytes, offset) != Some(0x04034B50) {
+            return Err(ZipReadError::UnexpectedEof);
+        }
+        let name_len = Self::u16_at(self.bytes, offset + 26).ok_or(ZipReadError::UnexpectedEof)? as usize;
+        let extra_len = Self::u16_at(self.bytes, offset + 28).ok_or(ZipReadError::UnexpectedEof)? as usize;
+        let start = offset + 30 + name_len + extra_len;
+        let data = self.bytes.get(start..start + entry.compressed_size as usize).ok_or(ZipReadError::UnexpectedEof)?;
+        let r = match entry.method {
+            0 => data.to_vec(),
+            8 => {
+                // Reading one byte past the size detects longer data
+                // without inflating all of it.
+                let mut r = vec![];
+                DeflateDecoder::new(data).take(entry.size as u64 + 1).read_to_end(&mut r).map_err(|_| ZipReadError::InvalidData)?;
+                r
+            },
+            method => return Err(ZipReadError::UnsupportedMethod(method)),
+        };
+        if r.len() != entry.size as usize {
+            return Err(ZipReadError::InvalidData);
+        }
+        Ok(r)
+    }
+
+    fn u16_at(bytes: &[u8], at: usize) -> Option<u16> {
+        Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> Option<u32> {
+        Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+/// Writes ZIP archives with stored (uncompressed) entries.
+pub struct ZipWriter {
+    bytes: Vec<u8>,
+    entries: Vec<ZipEntry>,
+}
+
+struct ZipEntry {
+    name: String,
+    crc32: u32,
+    size: u32,
+    offset: u32,
+}
+
+impl ZipWriter {
+    /// DOS date of 1980-01-01, used for every entry so that output is reproducible.
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    pub fn new() -> Self {
+        Self { bytes: vec![], entries: vec![] }
+    }
+
+    pub fn add_file(&mut self, name: &str, contents: &[u8]) {
+        let crc32 = Self::crc32(contents);
+        let offset = self.bytes.len() as u32;
+
+        // Local file header
+        self.write_u32(0x04034B50);
+        self.write_u16(10);
+        self.write_u16(0);
+        self.write_u16(0);
+        self.write_u16(0);
+        self.write_u16(Self::DOS_DATE);
+        self.write_u32(crc32);
+        self.write_u32(contents.len() as u32);
+        self.write_u32(contents.len() as u32);
+        self.write_u16(name.len() as u16);
+        self.write_u16(0);
+        self.bytes.extend_from_slice(name.as_bytes());
+        self.bytes.extend_from_slice(contents);
+
+        self.entries.push(ZipEntry { name: name.to_owned(), crc32, size: contents.len() as u32, offset });
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        let directory_offset = self.bytes.len() as u32;
+        let entries = std::mem::take(&mut self.entries);
+        for entry in entries.iter() {
+            // Central directory header
+            self.write_u32(0x02014B50);
+            self.write_u16(20);
+            self.write_u16(10);
+            self.write_u16(0);
+            self.write_u16(0);
+            self.write_u16(0);
+            self.write_u16(Self::DOS_DATE);
+            self.write_u32(entry.crc32);
+            self.write_u32(entry.size);
+            self.write_u32(entry.size);
+            self.write_u16(entry.name.len() as u16);
+            self.write_u16(0);
+            self.write_u16(0);
+            self.write_u16(0);
+            self.write_u16(0);
+            self.write_u32(0);
+            self.write_u32(entry.offset);
+            self.bytes.extend_from_slice(entry.name.as_bytes());
+        }
+        let directory_size = self.bytes.len() as u32 - directory_offset;
+
+        // End of central directory
+        self.write_u32(0x06054B50);
+        self.write_u16(0);
+        self.write_u16(0);
+        self.write_u16(entries.len() as u16);
+        self.write_u16(entries.len() as u16);
+        self.write_u32(directory_size);
+        self.write_u32(directory_offset);
+        self.write_u16(0);
+
+        self.bytes
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = 0xFFFFFFFFu32;
+        for b in bytes.iter() {
+            crc ^= *b as u32;
+            for _ in 0..8 {
+                let mask = (!(crc & 1)).wrapping_add(1);
+                crc = (crc >> 1) ^ (0xEDB88320 & mask);
+            }
+        }
+        !crc
+    }
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod build_target;
+pub use build_target::*;
+
+mod build_server;
+pub use build_server::*;
\ No newline at end of file
@@ -0,0 +1,247 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, Read, Write};
+use std::path::PathBuf;
+use serde_json::{json, Value};
+use whackengine_verifier::ns::*;
+use crate::bsp::*;
+
+/// Build server speaking JSON-RPC with the framing and methods of the
+/// Build Server Protocol, so that IDEs and build orchestrators can list
+/// and compile the packages of a workspace.
+///
+/// Supported requests are `build/initialize`, `build/shutdown`,
+/// `workspace/buildTargets`, `buildTarget/sources`,
+/// `buildTarget/dependencySources` and `buildTarget/compile`. Compiling
+/// publishes diagnostics through `build/publishDiagnostics` notifications.
+pub struct BuildServer {
+    root: PathBuf,
+    targets: Vec<BuildTarget>,
+    compiler_options: Rc<CompilerOptions>,
+    shutdown_requested: bool,
+}
+
+impl BuildServer {
+    pub const VERSION: &'static str = "2.1.0";
+
+    pub fn new(root: PathBuf, compiler_options: &Rc<CompilerOptions>) -> Self {
+        let targets = BuildTarget::discover(&root);
+        Self {
+            root,
+            targets,
+            compiler_options: compiler_options.clone(),
+            shutdown_requested: false,
+        }
+    }
+
+    /// Serves messages until `build/exit` or the end of input.
+    pub fn run(&mut self, mut reader: impl BufRead, mut writer: impl Write) -> std::io::Result<()> {
+        while let Some(message) = Self::read_message(&mut reader)? {
+            let Ok(message) = serde_json::from_str::<Value>(&message) else {
+                Self::write_message(&mut writer, &json!({
+                    "jsonrpc": "2.0",
+                    "id": Value::Null,
+                    "error": { "code": -32700, "message": "Parse error" },
+                }))?;
+                continue;
+            };
+            let method = message["method"].as_str().unwrap_or("").to_owned();
+            if method == "build/exit" {
+                break;
+            }
+            let mut notifications = vec![];
+            let result = self.handle(&method, &message["params"], &mut notifications);
+            for notification in notifications.iter() {
+                Self::write_message(&mut writer, notification)?;
+            }
+            // Notifications have no ID and get no response
+            let Some(id) = message.get("id") else {
+                continue;
+            };
+            let response = match result {
+                Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                None => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32601, "message": format!("Method not found: {method}") },
+                }),
+            };
+            Self::write_message(&mut writer, &response)?;
+        }
+        Ok(())
+    }
+
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown_requested
+    }
+
+    /// Handles a request or notification, returning `None` for an
+    /// unknown method.
+    fn handle(&mut self, method: &str, params: &Value, notifications: &mut Vec<Value>) -> Option<Value> {
+        match method {
+            "build/initialize" => Some(json!({
+                "displayName": "whack",
+                "version": env!("CARGO_PKG_VERSION"),
+                "bspVersion": Self::VERSION,
+                "capabilities": {
+                    "compileProvider": { "languageIds": ["actionscript", "mxml"] },
+                    "dependencySourcesProvider": true,
+                    "canReload": true,
+                },
+            })),
+            "build/initialized" => Some(Value::Null),
+            "build/shutdown" => {
+                self.shutdown_requested = true;
+                Some(Value::Null)
+            },
+            "workspace/reload" => {
+                self.targets = BuildTarget::discover(&self.root);
+                Some(Value::Null)
+            },
+            "workspace/buildTargets" => Some(json!({
+                "targets": self.targets.iter().map(|t| self.target_json(t)).collect::<Vec<_>>(),
+            })),
+            "buildTarget/sources" => Some(json!({
+                "items": self.requested_targets(params).iter().map(|t| json!({
+                    "target": { "uri": t.uri() },
+                    "sources": t.source_paths.iter().map(|p| json!({
+                        "uri": path_to_uri(p),
+                        "kind": 2,
+                        "generated": false,
+                    })).collect::<Vec<_>>(),
+                })).collect::<Vec<_>>(),
+            })),
+            "buildTarget/dependencySources" => Some(json!({
+                "items": self.requested_targets(params).iter().map(|t| json!({
+                    "target": { "uri": t.uri() },
+                    "sources": self.dependency_source_paths(t).iter().map(|p| path_to_uri(p)).collect::<Vec<_>>(),
+                })).collect::<Vec<_>>(),
+            })),
+            "buildTarget/compile" => {
+                let mut invalidated = false;
+                for target in self.requested_targets(params) {
+                    invalidated = self.compile(target, notifications) || invalidated;
+                }
+                let mut result = json!({ "statusCode": if invalidated { 2 } else { 1 } });
+                if let Some(origin_id) = params.get("originId") {
+                    result["originId"] = origin_id.clone();
+                }
+                Some(result)
+            },
+            _ => None,
+        }
+    }
+
+    fn target_json(&self, target: &BuildTarget) -> Value {
+        json!({
+            "id": { "uri": target.uri() },
+            "displayName": target.name,
+            "baseDirectory": path_to_uri(&target.dir),
+            "tags": [],
+            "languageIds": ["actionscript", "mxml"],
+            "dependencies": target.dependencies.iter()
+                .filter_map(|dir| self.targets.iter().find(|t| t.uri() == path_to_uri(dir)))
+                .map(|t| json!({ "uri": t.uri() }))
+                .collect::<Vec<_>>(),
+            "capabilities": { "canCompile": true, "canTest": false, "canRun": false, "canDebug": false },
+        })
+    }
+
+    fn requested_targets(&self, params: &Value) -> Vec<&BuildTarget> {
+        let uris: Vec<&str> = params["targets"].as_array().into_iter().flatten()
+            .filter_map(|t| t["uri"].as_str())
+            .collect();
+        self.targets.iter().filter(|t| uris.contains(&t.uri().as_str())).collect()
+    }
+
+    /// Compiles a target along with its dependencies, adding a
+    /// `build/publishDiagnostics` notification per file of the target.
+    /// Returns whether an error was found.
+    fn compile(&self, target: &BuildTarget, notifications: &mut Vec<Value>) -> bool {
+        let mut source_path: Vec<String> = target.dependency_source_paths(&self.targets).iter().map(|p| p.to_string_lossy().into_owned()).collect();
+        source_path.extend(target.source_paths.iter().map(|p| p.to_string_lossy().into_owned()));
+        let sources = FileSystemSourceProvider { source_path };
+        let host = Rc::new(Database::new(Default::default()));
+        let output = Compilation::run(&host, &self.compiler_options, &sources);
+
+        let target_sources: Vec<String> = target.source_paths.iter().map(|p| path_to_uri(p)).collect();
+        let mut files = BTreeMap::<String, Vec<Value>>::new();
+        for cu in output.compilation_units.iter() {
+            let Some(file_path) = cu.file_path() else {
+                continue;
+            };
+            let uri = path_to_uri(std::path::Path::new(&file_path));
+            if !target_sources.iter().any(|s| uri.starts_with(s.as_str())) {
+                continue;
+            }
+            cu.sort_diagnostics();
+            let text = SourceText::new(&cu.text());
+            let diagnostics = files.entry(uri).or_default();
+            for diagnostic in cu.nested_diagnostics().iter() {
+                let d = WhackDiagnostic(diagnostic);
+                let location = diagnostic.location();
+                // Columns are in UTF-16 code units, as in LSP
+                let (start, end) = if Rc::ptr_eq(&location.compilation_unit(), cu) {
+                    text.range(&location, ColumnEncoding::Utf16)
+                } else {
+                    SourceText::new(&location.compilation_unit().text()).range(&location, ColumnEncoding::Utf16)
+                };
+                diagnostics.push(json!({
+                    "range": {
+                        "start": { "line": start.line, "character": start.column },
+                        "end": { "line": end.line, "character": end.column },
+                    },
+                    "severity": if diagnostic.is_warning() { 2 } else { 1 },
+                    "code": d.id(),
+                    "source": "whack",
+                    "message": d.format_message_english(),
+                }));
+            }
+        }
+        for (uri, diagnostics) in files {
+            notifications.push(json!({
+                "jsonrpc": "2.0",
+                "method": "build/publishDiagnostics",
+                "params": {
+                    "textDocument": { "uri": uri },
+                    "buildTarget": { "uri": target.uri() },
+                    "diagnostics": diagnostics,
+                    "reset": true,
+                },
+            }));
+        }
+        output.invalidated
+    }
+
+    /// Reads a message framed by a `Content-Length` header, returning
+    /// `None` at the end of input.
+    fn read_message(reader: &mut impl BufRead) -> std::io::Result<Option<String>> {
+        let mut content_length: Option<usize> = None;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                if content_length.is_some() {
+                    break;
+                }
+                continue;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("Content-Length") {
+                    content_length = value.trim().parse().ok();
+                }
+            }
+        }
+        let mut body = vec![0u8; content_length.unwrap()];
+        reader.read_exact(&mut body)?;
+        Ok(Some(String::from_utf8_lossy(&body).into_owned()))
+    }
+
+    fn write_message(writer: &mut impl Write, message: &Value) -> std::io::Result<()> {
+        let body = message.to_string();
+        write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+        writer.flush()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+use crate::packagemanager::*;
+
+/// A Whack package exposed as a build target, identified by the URI of
+/// its directory.
+#[derive(Clone)]
+pub struct BuildTarget {
+    pub dir: PathBuf,
+    pub name: String,
+    pub source_paths: Vec<PathBuf>,
+    /// Directories of the local packages this package depends on.
+    pub dependencies: Vec<PathBuf>,
+}
+
+impl BuildTarget {
+    /// Discovers the build targets of the package or workspace at `root`.
+    pub fn discover(root: &Path) -> Vec<BuildTarget> {
+        let Some(manifest) = WhackManifest::read(root) else {
+            return vec![];
+        };
+        let mut r = vec![];
+        if let Some(workspace) = manifest.workspace.as_ref() {
+            for member in workspace.members.iter() {
+                let dir = root.join(member);
+                if let Some(member_manifest) = WhackManifest::read(&dir) {
+                    r.push(Self::from_manifest(&dir, &member_manifest));
+                }
+            }
+        }
+        if manifest.package.is_some() {
+            r.push(Self::from_manifest(root, &manifest));
+        }
+        r
+    }
+
+    fn from_manifest(dir: &Path, manifest: &WhackManifest) -> BuildTarget {
+        let name = manifest.package.as_ref().map(|p| p.name.clone()).unwrap_or_default();
+        let source_paths = manifest.source.iter().flatten()
+            .filter(|s| s.include.unwrap_or(true))
+            .map(|s| dir.join(&s.path))
+            .collect();
+        let dependencies = manifest.dependencies.iter().flatten()
+            .filter_map(|(_, dependency)| match dependency {
+                ManifestDependency::Advanced { path: Some(path), .. } => Some(dir.join(path)),
+                _ => None,
+            })
+            .collect();
+        BuildTarget {
+            dir: dir.to_owned(),
+            name,
+            source_paths,
+            dependencies,
+        }
+    }
+
+    /// Source paths of the local packages this package depends on,
+    /// transitively, looking them up in `targets` first.
+    pub fn dependency_source_paths(&self, targets: &[BuildTarget]) -> Vec<PathBuf> {
+        let mut visited = vec![self.uri()];
+        let mut pending: Vec<PathBuf> = self.dependencies.clone();
+        let mut r = vec![];
+        while let Some(dir) = pending.pop() {
+            let uri = path_to_uri(&dir);
+            if visited.contains(&uri) {
+                continue;
+            }
+            visited.push(uri.clone());
+            // Dependencies outside the workspace are discovered as needed
+            let dependency = match targets.iter().find(|t| t.uri() == uri) {
+                Some(t) => t.clone(),
+                None => match BuildTarget::discover(&dir).pop() {
+                    Some(t) => t,
+                    None => continue,
+                },
+            };
+            r.extend(dependency.source_paths.iter().cloned());
+            pending.extend(dependency.dependencies.iter().cloned());
+        }
+        r
+    }
+
+    pub fn uri(&self) -> String {
+        path_to_uri(&self.dir)
+    }
+}
+
+pub fn path_to_uri(path: &Path) -> String {
+    let path = std::fs::canonicalize(path).unwrap_or(path.to_owned());
+    format!("file://{}", path.to_string_lossy().replace('\\', "/"))
+}
\ No newline at end of file
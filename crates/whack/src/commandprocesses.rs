@@ -1,2 +1,17 @@
 mod check_process;
-pub use check_process::*;
\ No newline at end of file
+pub use check_process::*;
+
+mod explain_process;
+pub use explain_process::*;
+
+mod serve_process;
+pub use serve_process::*;
+
+mod bsp_process;
+pub use bsp_process::*;
+
+mod inspect_process;
+pub use inspect_process::*;
+
+mod codemod_process;
+pub use codemod_process::*;
\ No newline at end of file
@@ -0,0 +1,21 @@
+use crate::bsp::*;
+use whackengine_verifier::ns::*;
+
+/// Runs a build server over the standard input and output for the
+/// package or workspace in the current directory.
+pub fn bsp_process(matches: &clap::ArgMatches) {
+    let compiler_options = Rc::new(CompilerOptions {
+        operator_overloading: matches.get_flag("operator-overloading"),
+        const_parameters: matches.get_flag("const-parameters"),
+        ..default()
+    });
+    let dir = std::env::current_dir().unwrap();
+    let mut server = BuildServer::new(dir, &compiler_options);
+    let stdin = std::io::stdin();
+    if let Err(error) = server.run(stdin.lock(), std::io::stdout()) {
+        eprintln!("Error: {error}");
+    }
+    if !server.shutdown_requested() {
+        std::process::exit(1);
+    }
+}
\ No newline at end of file
@@ -1,40 +1,230 @@
-use crate::packagemanager::*;
+use crate::air::*;
+use crate::bsp::*;
 use colored::*;
+use std::collections::BTreeMap;
+use whackengine_verifier::ns::*;
 
 pub async fn check_process(matches: &clap::ArgMatches) {
     let builtins = matches.get_one::<std::path::PathBuf>("builtins");
     let package = matches.get_one::<String>("package");
+    let timings_format = matches.get_one::<String>("timings");
+    let diagnostic_baseline = matches.get_one::<std::path::PathBuf>("diagnostic-baseline");
+    let update_baseline = matches.get_flag("update-baseline");
+    let mut swf_modules = vec![];
+    for module in matches.get_many::<String>("swf-module").into_iter().flatten() {
+        let parsed = module.split_once('=').map(|(name, packages)| SwfModuleOptions {
+            name: name.trim().to_owned(),
+            packages: packages.split(',').map(|p| p.trim().to_owned()).filter(|p| !p.is_empty()).collect(),
+        });
+        match parsed {
+            Some(options) if !options.name.is_empty() && !options.packages.is_empty() => swf_modules.push(options),
+            _ => {
+                println!("{} Invalid SWF module: {} (expected NAME=PACKAGE,PACKAGE)", "Error:".red(), module);
+                return;
+            },
+        }
+    }
+    let retained_metadata = matches.get_one::<String>("retained-metadata").map(|tags| {
+        tags.split(',').map(|t| t.trim().to_owned()).filter(|t| !t.is_empty()).collect::<Vec<_>>()
+    });
+    let severity = |name: &str| match matches.get_one::<String>(name).map(|s| s.as_str()) {
+        Some("allow") => Some(LintSeverity::Allow),
+        Some("warning") => Some(LintSeverity::Warning),
+        Some("error") => Some(LintSeverity::Error),
+        _ => None,
+    };
+    let mut api_stability = ApiStabilityOptions::default();
+    if let Some(severity) = severity("experimental-api") {
+        api_stability.experimental = severity;
+    }
+    if let Some(severity) = severity("internal-api") {
+        api_stability.internal_api = severity;
+    }
+    let mut warnings = CompilerOptions::default().warnings;
+    warnings.stripped_metadata = matches.get_flag("warn-stripped-metadata");
+    warnings.switch_fallthrough = matches.get_flag("warn-switch-fallthrough");
+    warnings.directory_config = !matches.get_flag("no-warning-config");
+    let frame_script = matches.get_one::<String>("frame-script").map(|file_path| FrameScriptOptions {
+        file_path: file_path.clone(),
+        ..default()
+    });
+    let features = |list: &str| list.split(',').filter_map(|name| LanguageFeature::from_name(name.trim())).collect::<Vec<_>>();
+    let package_features: BTreeMap<String, Vec<LanguageFeature>> = matches.get_many::<String>("package-language-features").into_iter().flatten().filter_map(|entry| {
+        let (package, list) = entry.split_once('=')?;
+        Some((package.trim().to_owned(), features(list)))
+    }).collect();
+    let language_features = (matches.contains_id("language-features") || !package_features.is_empty()).then(|| LanguageFeatureOptions {
+        enabled: matches.get_one::<String>("language-features").map(|list| features(list)).unwrap_or_default(),
+        packages: package_features,
+    });
+    let target_runtime_version = match matches.get_one::<String>("target-runtime-version") {
+        Some(version) => match RuntimeVersion::parse(version) {
+            Some(version) => Some(version),
+            None => {
+                println!("{} Invalid runtime version: {}", "Error:".red(), version);
+                return;
+            },
+        },
+        None => None,
+    };
+    let test_runner = matches.get_one::<String>("test-runner").cloned();
+    let test_discovery = (matches.get_flag("test-manifest") || test_runner.is_some()).then(|| TestDiscoveryOptions { runner_class: test_runner });
+    let emit = EmitOptions {
+        forms: matches.get_many::<String>("emit").into_iter().flatten().filter_map(|form| IntermediateForm::from_name(form)).collect(),
+        files: matches.get_many::<String>("emit-file").into_iter().flatten().cloned().collect(),
+    };
 
     let dir = std::env::current_dir().unwrap();
 
-    // Read lockfile
-    let lockfile_path = dir.join("whack.lock");
-    let mut lockfile: Option<WhackLockfile> = None;
-    if std::fs::exists(&lockfile_path).unwrap() && std::fs::metadata(&lockfile_path).unwrap().is_file() {
-        lockfile = toml::from_str::<WhackLockfile>(&std::fs::read_to_string(&lockfile_path).unwrap()).ok();
+    // Find the package to check
+    let targets = BuildTarget::discover(&dir);
+    if targets.is_empty() {
+        println!("{} {}", "Error:".red(), "Whack manifest not found.");
+        return;
+    }
+    let target = match package {
+        Some(name) => targets.iter().find(|t| &t.name == name),
+        None if targets.len() == 1 => targets.first(),
+        None => None,
+    };
+    let Some(target) = target else {
+        println!("{} {}", "Error:".red(), "Package must be specified.");
+        return;
+    };
+
+    // Check the built-ins first, then each dependency along with the package
+    let core_library = match builtins {
+        Some(builtins) => match read_builtins(builtins) {
+            Some(sources) => CoreLibrarySource::Custom(sources),
+            None => {
+                println!("{} {}", "Error:".red(), "Whack manifest of the built-ins not found.");
+                return;
+            },
+        },
+        None => CoreLibrarySource::Embedded,
+    };
+    let mut native_extensions: Vec<Rc<dyn DefinitionProvider>> = vec![];
+    let mut native_extension_ids = vec![];
+    for path in matches.get_many::<std::path::PathBuf>("native-extension").into_iter().flatten() {
+        let loaded = std::fs::read(path).map_err(|error| error.to_string())
+            .and_then(|bytes| AnePackage::load(&path.to_string_lossy(), &bytes).map_err(|error| error.message()));
+        match loaded {
+            Ok(ane) => {
+                native_extension_ids.push(ane.descriptor.id.clone());
+                native_extensions.push(Rc::new(ane));
+            },
+            Err(message) => {
+                println!("{} {}: {}", "Error:".red(), path.display(), message);
+                return;
+            },
+        }
     }
+    let mut source_path: Vec<String> = target.dependency_source_paths(&targets).iter().map(|p| p.to_string_lossy().into_owned()).collect();
+    source_path.extend(target.source_paths.iter().map(|p| p.to_string_lossy().into_owned()));
 
-    // Process directed acyclic graph
-    let dag = match Dag::retrieve(&dir, &dir, package.cloned(), lockfile.as_mut()).await {
-        Ok(dag) => dag,
-        Err(error) => {
-            match error {
-                DagError::ManifestNotFound => {
-                    println!("{} {}", "Error:".red(), "Whack manifest not found.");
-                },
-                DagError::PackageMustBeSpecified => {
-                    println!("{} {}", "Error:".red(), "Package must be specified.");
+    // Compiler options
+    let mut compiler_options = CompilerOptions {
+        warnings,
+        api_stability,
+        source_path: source_path.clone(),
+        core_library,
+        syntax_only: matches.get_flag("syntax-only"),
+        legacy_syntax: matches.get_flag("legacy-syntax"),
+        swf_modules,
+        frame_script,
+        build_manifest: matches.get_flag("build-manifest"),
+        dynamic_access_audit: matches.get_flag("dynamic-access-audit"),
+        emit,
+        optimize_embedded_bitmaps: matches.get_flag("optimize-embedded-bitmaps"),
+        embedded_bitmap_report: matches.get_flag("embedded-bitmap-report"),
+        test_discovery,
+        json_type_helpers: matches.get_flag("json-type-helpers"),
+        operator_overloading: matches.get_flag("operator-overloading"),
+        const_parameters: matches.get_flag("const-parameters"),
+        promise_like_type: matches.get_one::<String>("promise-like-type").cloned(),
+        language_features,
+        target_runtime_version,
+        target_profile: matches.get_one::<String>("target-profile").and_then(|name| TargetProfile::from_name(name)).unwrap_or_default(),
+        native_extension_ids: (!native_extensions.is_empty()).then_some(native_extension_ids),
+        ..default()
+    };
+    if let Some(tags) = retained_metadata {
+        compiler_options.retained_metadata = tags;
+    }
+    let compiler_options = Rc::new(compiler_options);
+
+    let sources = FileSystemSourceProvider { source_path };
+    let host = Rc::new(Database::new(Default::default()));
+    let output = Compilation::run_with_providers(&host, &compiler_options, &sources, &native_extensions);
+
+    // Report the diagnostics, or only those missing from the baseline
+    let mut failed = output.invalidated;
+    match diagnostic_baseline {
+        Some(path) if update_baseline => {
+            let baseline = DiagnosticBaseline::collect(&host, &output);
+            if let Err(error) = baseline.save(path) {
+                println!("{} Could not write {}: {}", "Error:".red(), path.display(), error);
+                std::process::exit(1);
+            }
+            println!("Recorded {} diagnostics in {}.", baseline.len(), path.display());
+            failed = false;
+        },
+        Some(path) => {
+            let baseline = match DiagnosticBaseline::load(path) {
+                Ok(baseline) => baseline,
+                Err(error) => {
+                    println!("{} Could not read {}: {}", "Error:".red(), path.display(), error);
+                    std::process::exit(1);
                 },
+            };
+            let delta = baseline.compare(&host, &output);
+            print!("{}", delta.format_human());
+            failed = delta.introduces_errors();
+        },
+        None => {
+            for message in output.diagnostics() {
+                println!("{message}");
             }
+        },
+    }
 
-            return;
+    // Write the artifacts
+    let artifacts_dir = target.dir.join(ARTIFACTS_DIR);
+    for artifact in output.artifacts.iter() {
+        let path = artifacts_dir.join(&artifact.path);
+        let written = std::fs::create_dir_all(path.parent().unwrap()).and_then(|_| std::fs::write(&path, &artifact.bytes));
+        if let Err(error) = written {
+            println!("{} Could not write {}: {}", "Error:".red(), path.display(), error);
+        }
+    }
+
+    // Report timings
+    match timings_format.map(|f| f.as_str()) {
+        Some("json") => {
+            println!("{}", output.timings.format_json());
         },
-    };
+        Some(_) => {
+            print!("{}", output.timings.format_human());
+        },
+        None => {},
+    }
 
-    // Check the built-ins first
-    fixme();
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Directory of the checked package receiving the artifacts.
+const ARTIFACTS_DIR: &str = "target";
 
-    // Check each dependency in ascending order for AS3 and MXML errors,
-    // running the build script if required.
-    fixme();
+/// Reads the sources of the Whack package defining the built-ins.
+fn read_builtins(dir: &std::path::Path) -> Option<Vec<(String, String)>> {
+    let target = BuildTarget::discover(dir).pop()?;
+    let sources = FileSystemSourceProvider {
+        source_path: target.source_paths.iter().map(|p| p.to_string_lossy().into_owned()).collect(),
+    };
+    Some(sources.source_files().into_iter().filter_map(|path| {
+        let text = sources.read_to_string(&path)?;
+        Some((path, text))
+    }).collect())
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use colored::*;
+use std::path::PathBuf;
+use whackengine_verifier::ns::*;
+
+/// Applies codemods to the sources under the given directories, writing
+/// the files whose edits verify without errors when `--write` is given.
+pub fn codemod_process(matches: &clap::ArgMatches) {
+    let mut codemods: Vec<Rc<dyn Codemod>> = vec![];
+    for name in matches.get_many::<String>("transform").into_iter().flatten() {
+        match builtin_codemod(name) {
+            Some(codemod) => codemods.push(codemod),
+            None => {
+                println!("{} Unknown transform {}.", "Error:".red(), name);
+                return;
+            },
+        }
+    }
+    if let Some(path) = matches.get_one::<PathBuf>("api-mapping") {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) => {
+                println!("{} Could not read {}: {}", "Error:".red(), path.display(), error);
+                return;
+            },
+        };
+        match DeprecatedApiCodemod::parse(&text) {
+            Ok(codemod) => codemods.push(Rc::new(codemod)),
+            Err(line) => {
+                println!("{} {}:{}: expected 'old = replacement'.", "Error:".red(), path.display(), line);
+                return;
+            },
+        }
+    }
+    if codemods.is_empty() {
+        println!("{} No transform given.", "Error:".red());
+        return;
+    }
+
+    let source_path: Vec<String> = matches.get_many::<PathBuf>("SOURCE_PATH").into_iter().flatten().map(|p| p.to_string_lossy().into_owned()).collect();
+    let compiler_options = Rc::new(CompilerOptions { source_path: source_path.clone(), ..default() });
+    let sources = FileSystemSourceProvider { source_path };
+    let report = CodemodRunner::run(&compiler_options, &sources, &codemods);
+    print!("{}", report.format_human());
+
+    if !matches.get_flag("write") {
+        return;
+    }
+    for (path, text) in report.files.iter() {
+        if let Err(error) = std::fs::write(path, text) {
+            println!("{} Could not write {}: {}", "Error:".red(), path, error);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use colored::*;
+use whackengine_verifier::ns::*;
+
+pub fn explain_process(matches: &clap::ArgMatches) {
+    let code = matches.get_one::<String>("CODE").unwrap();
+    match WhackDiagnosticExplanation::find(code) {
+        Some(explanation) => {
+            print!("{}", explanation.format_human());
+        },
+        None => {
+            println!("{} Unknown diagnostic {}.", "Error:".red(), code);
+        },
+    }
+}
\ No newline at end of file
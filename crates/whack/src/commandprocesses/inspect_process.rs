@@ -0,0 +1,34 @@
+use crate::air::*;
+use colored::*;
+use whackengine_verifier::ns::*;
+
+/// Lists the tags, symbols and ABC scripts of a SWF, or of the
+/// `library.swf` of a SWC.
+pub fn inspect_process(matches: &clap::ArgMatches) {
+    let path = matches.get_one::<std::path::PathBuf>("FILE").unwrap();
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            println!("{} Could not read {}: {}", "Error:".red(), path.display(), error);
+            return;
+        },
+    };
+    let swf = if bytes.starts_with(b"PK") {
+        let library = ZipReader::new(&bytes).ok().and_then(|zip| zip.read("library.swf")).and_then(|r| r.ok());
+        let Some(library) = library else {
+            println!("{} {} contains no library.swf.", "Error:".red(), path.display());
+            return;
+        };
+        library
+    } else {
+        bytes
+    };
+    let Ok(swf) = SwfAbcTag::decompress(swf) else {
+        println!("{} {} is not an uncompressed or zlib-compressed SWF.", "Error:".red(), path.display());
+        return;
+    };
+    match SwfInspection::inspect(&swf) {
+        Ok(inspection) => print!("{}", inspection.format_human()),
+        Err(error) => println!("{} Could not read {}: {:?}", "Error:".red(), path.display(), error),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use whackengine_verifier::ns::*;
+
+/// Serves compile requests over the standard input and output, or over
+/// a local TCP socket when `--listen` is given, with one request and one
+/// response per line (see `CompileServer`).
+pub fn serve_process(matches: &clap::ArgMatches) {
+    let compiler_options = Rc::new(CompilerOptions {
+        operator_overloading: matches.get_flag("operator-overloading"),
+        const_parameters: matches.get_flag("const-parameters"),
+        ..default()
+    });
+    let mut server = CompileServer::new(&compiler_options);
+    if matches.get_flag("events") {
+        server.set_event_subscriber(Some(Rc::new(StderrEventSubscriber)));
+    }
+    let crash_dumps = CrashDumps {
+        dir: matches.get_one::<PathBuf>("crash-dump").cloned(),
+        redact: matches.get_flag("redact-crash-dumps"),
+    };
+
+    let Some(address) = matches.get_one::<String>("listen") else {
+        let stdin = std::io::stdin();
+        serve_connection(&mut server, &crash_dumps, stdin.lock(), std::io::stdout());
+        return;
+    };
+
+    let listener = match std::net::TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("Error: Could not listen on {address}: {error}");
+            return;
+        },
+    };
+
+    // Connections are served one at a time, sharing the same server
+    for stream in listener.incoming().flatten() {
+        let Ok(reader) = stream.try_clone() else {
+            continue;
+        };
+        if !serve_connection(&mut server, &crash_dumps, BufReader::new(reader), stream) {
+            break;
+        }
+    }
+}
+
+struct StderrEventSubscriber;
+
+impl CompilerEventSubscriber for StderrEventSubscriber {
+    fn on_event(&self, event: &CompilerEvent) {
+        eprintln!("{}", event.format_json());
+    }
+}
+
+/// Where reports of internal errors go.
+struct CrashDumps {
+    dir: Option<PathBuf>,
+    redact: bool,
+}
+
+impl CrashDumps {
+    fn write(&self, mut report: CrashReport) {
+        let Some(dir) = self.dir.as_ref() else {
+            eprintln!("Internal error: {}", report.message);
+            eprintln!("Run with --crash-dump <DIR> to write a report to attach to an issue.");
+            return;
+        };
+        if self.redact {
+            report.redact();
+        }
+        match report.write_bundle(dir) {
+            Ok(path) => eprintln!("Internal error: {}. Report written to {}", report.message, path.display()),
+            Err(error) => eprintln!("Internal error: {}. Could not write report: {error}", report.message),
+        }
+    }
+}
+
+/// Serves requests until the end of input, returning `false` on `shutdown`.
+fn serve_connection(server: &mut CompileServer, crash_dumps: &CrashDumps, reader: impl BufRead, mut writer: impl Write) -> bool {
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = server.handle_request(&line) else {
+            return false;
+        };
+        if let Some(report) = server.take_crash_report() {
+            crash_dumps.write(report);
+        }
+        if writeln!(writer, "{response}").and_then(|_| writer.flush()).is_err() {
+            break;
+        }
+    }
+    true
+}
\ No newline at end of file
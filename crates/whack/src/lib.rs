@@ -1,3 +1,7 @@
+pub mod air;
+
+pub mod bsp;
+
 pub mod commandprocesses;
 
 pub mod packagemanager;
\ No newline at end of file
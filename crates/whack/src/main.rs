@@ -1,3 +1,16 @@
+/// Options changing the language accepted by the verifier, shared by
+/// the subcommands that verify sources.
+fn language_option_args() -> Vec<clap::Arg> {
+    vec![
+        clap::arg!(--"operator-overloading")
+            .help("Resolves +, -, *, == and != over class operands to static operator methods such as op_add().")
+            .action(clap::ArgAction::SetTrue),
+        clap::arg!(--"const-parameters")
+            .help("Reports the reassignment of function parameters.")
+            .action(clap::ArgAction::SetTrue),
+    ]
+}
+
 #[tokio::main]
 async fn main() {
     let cmd = clap::Command::new("whack")
@@ -5,13 +18,147 @@ async fn main() {
         .subcommand_required(true)
         .subcommand(
             clap::command!("check")
-                .about("Verifies ActionScript sources for errors and warnings.")
+                .about("Verifies the ActionScript sources of a package and its dependencies for errors and warnings, writing the artifacts to its target directory.")
                 .arg(clap::arg!(--"builtins" <PATH>)
                     .help("Path to the Whack package defining the ActionScript built-ins.")
                     .value_parser(clap::value_parser!(std::path::PathBuf)))
                 .arg(clap::arg!(--"package" <NAME>)
                     .help("For a workspace, specifies the Whack package to operate on.")
                     .alias("p"))
+                .arg(clap::arg!(--"timings" [FORMAT])
+                    .help("Reports time spent per phase and per file, either as \"human\" (default) or \"json\".")
+                    .value_parser(["human", "json"])
+                    .default_missing_value("human"))
+                .arg(clap::arg!(--"diagnostic-baseline" <FILE>)
+                    .help("Reports only the diagnostics missing from the given baseline file, failing on new errors.")
+                    .value_parser(clap::value_parser!(std::path::PathBuf)))
+                .arg(clap::arg!(--"update-baseline")
+                    .help("Writes the diagnostics of this run to the --diagnostic-baseline file instead of comparing against it.")
+                    .requires("diagnostic-baseline")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"syntax-only")
+                    .help("Only parses sources and checks declarations, skipping semantic verification.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"retained-metadata" <TAGS>)
+                    .help("Keeps the given comma-separated meta-data tags in the generated output for describeType(), in place of the default ones."))
+                .arg(clap::arg!(--"warn-stripped-metadata")
+                    .help("Warns on describeType() calls over classes whose meta-data is partly stripped from the generated output.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"warn-switch-fallthrough")
+                    .help("Warns on switch cases falling through into the next one without a fallthrough comment.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"experimental-api" <SEVERITY>)
+                    .help("Severity of references to [Experimental] definitions from outside their owning package, \"warning\" by default.")
+                    .value_parser(["allow", "warning", "error"]))
+                .arg(clap::arg!(--"internal-api" <SEVERITY>)
+                    .help("Severity of references to [InternalAPI] definitions from outside their owning package, \"error\" by default.")
+                    .value_parser(["allow", "warning", "error"]))
+                .arg(clap::arg!(--"no-warning-config")
+                    .help("Ignores the whack-warnings.properties files configuring warning levels per directory of the source path.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"swf-module" <MODULE>)
+                    .help("Builds the given packages into a SWF module loaded at runtime, as NAME=PACKAGE,PACKAGE. May be repeated.")
+                    .action(clap::ArgAction::Append))
+                .arg(clap::arg!(--"frame-script" <FILE>)
+                    .help("Compiles the top-level statements of the given main file as the frame script of a MovieClip timeline."))
+                .arg(clap::arg!(--"emit" <FORM>)
+                    .help("Dumps an intermediate form of the sources as an artifact. May be repeated.")
+                    .value_parser(["ast", "typed-ast", "cfg", "abc-asm"])
+                    .action(clap::ArgAction::Append))
+                .arg(clap::arg!(--"emit-file" <FILE>)
+                    .help("Restricts --emit to the given source file. May be repeated.")
+                    .action(clap::ArgAction::Append))
+                .arg(clap::arg!(--"optimize-embedded-bitmaps")
+                    .help("Stores embedded PNG and GIF bitmaps as is rather than decoded.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"embedded-bitmap-report")
+                    .help("Writes the sizes of the embedded bitmaps to bitmap-assets.txt.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"test-manifest")
+                    .help("Writes the [Test], [Before] and [After] methods of the sources to test-manifest.json, reporting invalid signatures.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"test-runner" <CLASS>)
+                    .help("Generates a top-level class of the given name running the discovered tests, along with the test manifest."))
+                .arg(clap::arg!(--"dynamic-access-audit")
+                    .help("Writes the property accesses resolved only at runtime, grouped by receiver type, to dynamic-access-audit.txt.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"build-manifest")
+                    .help("Writes the content hashes of the build inputs and outputs to whack-build-manifest.json.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"json-type-helpers")
+                    .help("Generates fromJSON() and toJSON() methods for [JsonType] classes.")
+                    .action(clap::ArgAction::SetTrue))
+                .args(language_option_args())
+                .arg(clap::arg!(--"promise-like-type" <CLASS>)
+                    .help("Accepts instances of the given fully qualified class as await operands besides Promise."))
+                .arg(clap::arg!(--"native-extension" <FILE>)
+                    .help("Loads the ActionScript library of a native extension (.ane) and checks extension context IDs against it. May be repeated.")
+                    .value_parser(clap::value_parser!(std::path::PathBuf))
+                    .action(clap::ArgAction::Append))
+                .arg(clap::arg!(--"target-runtime-version" <VERSION>)
+                    .help("Reports references to APIs introduced by runtime versions later than the given one, as MAJOR.MINOR."))
+                .arg(clap::arg!(--"target-profile" <PROFILE>)
+                    .help("Reports references to packages absent from the given runtime, \"avm2\" by default.")
+                    .value_parser(["avm2", "browser-js", "headless"]))
+                .arg(clap::arg!(--"legacy-syntax")
+                    .help("Accepts historical syntax quirks, such as trailing commas in argument lists, with a warning each.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"language-features" [FEATURES])
+                    .help("Gates language extensions, enabling the given comma-separated features for every file. Other files opt in with a // @whack-features comment.")
+                    .default_missing_value(""))
+                .arg(clap::arg!(--"package-language-features" <PACKAGE_FEATURES>)
+                    .help("Enables gated language features for a package and its subpackages, as PACKAGE=FEATURE,FEATURE. May be repeated.")
+                    .action(clap::ArgAction::Append))
+        )
+        .subcommand(
+            clap::command!("serve")
+                .about("Runs a compile server, reading one request per line from the standard input or a local socket.")
+                .arg(clap::arg!(--"listen" <ADDRESS>)
+                    .help("Listens on a local TCP address, such as 127.0.0.1:7070, instead of the standard input."))
+                .arg(clap::arg!(--"events")
+                    .help("Writes compiler progress events to the standard error as JSON lines.")
+                    .action(clap::ArgAction::SetTrue))
+                .arg(clap::arg!(--"crash-dump" <DIR>)
+                    .help("Writes a report bundle to the given directory when the compiler hits an internal error.")
+                    .value_parser(clap::value_parser!(std::path::PathBuf)))
+                .arg(clap::arg!(--"redact-crash-dumps")
+                    .help("Leaves the source file out of crash reports and reduces paths to file names.")
+                    .action(clap::ArgAction::SetTrue))
+                .args(language_option_args())
+        )
+        .subcommand(
+            clap::command!("bsp")
+                .about("Runs a Build Server Protocol server over the standard input and output.")
+                .args(language_option_args())
+        )
+        .subcommand(
+            clap::command!("inspect")
+                .about("Lists the tags, symbols and ABC scripts of a SWF or SWC.")
+                .arg(clap::arg!(<FILE>)
+                    .help("Path to the SWF or SWC.")
+                    .value_parser(clap::value_parser!(std::path::PathBuf)))
+        )
+        .subcommand(
+            clap::command!("codemod")
+                .about("Applies upgrade transforms to sources, keeping the files whose changes verify without errors.")
+                .arg(clap::arg!(<SOURCE_PATH> ...)
+                    .help("Source directories to transform.")
+                    .value_parser(clap::value_parser!(std::path::PathBuf)))
+                .arg(clap::arg!(--"transform" <NAME>)
+                    .help("Applies a built-in transform: array-to-vector or int-division. May be repeated.")
+                    .action(clap::ArgAction::Append))
+                .arg(clap::arg!(--"api-mapping" <FILE>)
+                    .help("Rewrites references to deprecated APIs following a file of 'old = replacement' lines.")
+                    .value_parser(clap::value_parser!(std::path::PathBuf)))
+                .arg(clap::arg!(--"write")
+                    .help("Writes the transformed files instead of only listing the changes.")
+                    .action(clap::ArgAction::SetTrue))
+        )
+        .subcommand(
+            clap::command!("explain")
+                .about("Explains a diagnostic with examples and common fixes.")
+                .arg(clap::arg!(<CODE>)
+                    .help("Diagnostic ID or kind name, such as 2049 or ImplicitCoercionToUnrelatedType."))
         );
 
     let matches = cmd.get_matches();
@@ -20,6 +167,21 @@ async fn main() {
         Some(("check", matches)) => {
             whackengine_whack::commandprocesses::check_process(matches).await;
         },
+        Some(("explain", matches)) => {
+            whackengine_whack::commandprocesses::explain_process(matches);
+        },
+        Some(("bsp", matches)) => {
+            whackengine_whack::commandprocesses::bsp_process(matches);
+        },
+        Some(("codemod", matches)) => {
+            whackengine_whack::commandprocesses::codemod_process(matches);
+        },
+        Some(("inspect", matches)) => {
+            whackengine_whack::commandprocesses::inspect_process(matches);
+        },
+        Some(("serve", matches)) => {
+            whackengine_whack::commandprocesses::serve_process(matches);
+        },
         _ => unreachable!(),
     }
 }
\ No newline at end of file
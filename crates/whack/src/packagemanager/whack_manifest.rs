@@ -15,10 +15,21 @@ pub struct WhackManifest {
     #[serde(rename = "build-script")]
     pub build_script: Option<ManifestBuildScript>,
     pub javascript: Option<Vec<ManifestJscript>>,
+    pub air: Option<ManifestAir>,
     /// Configuration constants.
     pub define: Option<HashMap<String, String>>,
 }
 
+impl WhackManifest {
+    pub const FILE_NAME: &'static str = "whack.toml";
+
+    /// Reads the manifest of the package or workspace at `dir`.
+    pub fn read(dir: &std::path::Path) -> Option<WhackManifest> {
+        let text = std::fs::read_to_string(dir.join(Self::FILE_NAME)).ok()?;
+        toml::from_str(&text).ok()
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct WorkspaceManifest {
     pub members: Vec<String>,
@@ -70,6 +81,15 @@ pub struct ManifestBuildScript {
     pub source: Option<Vec<ManifestSource>>,
 }
 
+/// AIR application settings.
+#[derive(Serialize, Deserialize)]
+pub struct ManifestAir {
+    /// Path to the `-app.xml` application descriptor.
+    pub descriptor: String,
+    /// Asset files packaged alongside the SWF.
+    pub assets: Option<Vec<String>>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ManifestJscript {
     path: String,
@@ -0,0 +1,120 @@
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::Write;
+use whackengine_verifier::ns::*;
+use whackengine_whack::air::*;
+
+/// A ZIP archive with a single deflated entry, recording `size` as its
+/// uncompressed size.
+fn deflated_zip(name: &str, contents: &[u8], size: u32) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+    encoder.write_all(contents).unwrap();
+    let data = encoder.finish().unwrap();
+    let mut r = vec![];
+    r.extend(0x04034B50u32.to_le_bytes());
+    r.extend([20, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    r.extend((data.len() as u32).to_le_bytes());
+    r.extend(size.to_le_bytes());
+    r.extend((name.len() as u16).to_le_bytes());
+    r.extend([0, 0]);
+    r.extend(name.as_bytes());
+    r.extend(data.iter());
+    let directory_offset = r.len() as u32;
+    r.extend(0x02014B50u32.to_le_bytes());
+    r.extend([20, 0, 20, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    r.extend((data.len() as u32).to_le_bytes());
+    r.extend(size.to_le_bytes());
+    r.extend((name.len() as u16).to_le_bytes());
+    r.extend([0u8; 12]);
+    r.extend(0u32.to_le_bytes());
+    r.extend(name.as_bytes());
+    let directory_size = r.len() as u32 - directory_offset;
+    r.extend(0x06054B50u32.to_le_bytes());
+    r.extend([0, 0, 0, 0, 1, 0, 1, 0]);
+    r.extend(directory_size.to_le_bytes());
+    r.extend(directory_offset.to_le_bytes());
+    r.extend([0, 0]);
+    r
+}
+
+#[test]
+fn stored_entries_round_trip() {
+    let mut writer = ZipWriter::new();
+    writer.add_file("a.txt", b"alpha");
+    writer.add_file("dir/b.txt", b"");
+    let bytes = writer.finish();
+    let zip = ZipReader::new(&bytes).unwrap();
+    let names: Vec<&str> = zip.entries().iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["a.txt", "dir/b.txt"]);
+    assert_eq!(zip.read("a.txt"), Some(Ok(b"alpha".to_vec())));
+    assert_eq!(zip.read("dir/b.txt"), Some(Ok(vec![])));
+    assert_eq!(zip.read("c.txt"), None);
+}
+
+#[test]
+fn deflated_entry_is_inflated() {
+    let contents = b"abcabcabcabcabcabcabcabc".repeat(16);
+    let bytes = deflated_zip("a.bin", &contents, contents.len() as u32);
+    assert_eq!(ZipReader::new(&bytes).unwrap().read("a.bin"), Some(Ok(contents)));
+}
+
+#[test]
+fn deflated_entry_larger_than_recorded_is_rejected() {
+    let contents = vec![0u8; 4096];
+    let bytes = deflated_zip("a.bin", &contents, 16);
+    assert_eq!(ZipReader::new(&bytes).unwrap().read("a.bin"), Some(Err(ZipReadError::InvalidData)));
+}
+
+#[test]
+fn huge_recorded_size_does_not_preallocate() {
+    let bytes = deflated_zip("a.bin", b"abc", u32::MAX);
+    assert_eq!(ZipReader::new(&bytes).unwrap().read("a.bin"), Some(Err(ZipReadError::InvalidData)));
+}
+
+#[test]
+fn truncated_archive_is_rejected() {
+    let mut writer = ZipWriter::new();
+    writer.add_file("a.txt", b"alpha");
+    let bytes = writer.finish();
+    assert!(ZipReader::new(&bytes[..bytes.len() - 4]).is_err());
+    assert_eq!(ZipReader::new(b"not a zip").err(), Some(ZipReadError::NotAZip));
+}
+
+fn library_swf() -> Vec<u8> {
+    let abc = AbcWriter::write(&AbcFile { minor_version: 16, major_version: 46, ..AbcFile::default() });
+    let mut doabc = 1u32.to_le_bytes().to_vec();
+    doabc.extend(b"lib\0");
+    doabc.extend(abc);
+    let mut body = vec![0u8, 0, 24, 1, 0];
+    body.extend(((82u16 << 6) | 0x3F).to_le_bytes());
+    body.extend((doabc.len() as u32).to_le_bytes());
+    body.extend(doabc);
+    body.extend([0u8, 0]);
+    let mut r = b"FWS".to_vec();
+    r.push(10);
+    r.extend((body.len() as u32 + 8).to_le_bytes());
+    r.extend(body);
+    r
+}
+
+#[test]
+fn native_extension_loads_descriptor_and_library() {
+    let mut writer = ZipWriter::new();
+    writer.add_file(AneDescriptor::PATH, br#"<extension xmlns="http://ns.adobe.com/air/extension/3.5">
+    <id>com.example.Vibration</id>
+    <versionNumber>1.0.0</versionNumber>
+    <platforms><platform name="iPhone-ARM"/></platforms>
+</extension>"#);
+    writer.add_file("library.swf", &library_swf());
+    let ane = AnePackage::load("Vibration.ane", &writer.finish()).unwrap();
+    assert_eq!(ane.descriptor.id, "com.example.Vibration");
+    assert_eq!(ane.descriptor.version.as_deref(), Some("1.0.0"));
+    assert_eq!(ane.descriptor.platforms, vec!["iPhone-ARM".to_owned()]);
+}
+
+#[test]
+fn native_extension_without_library_is_rejected() {
+    let mut writer = ZipWriter::new();
+    writer.add_file(AneDescriptor::PATH, br#"<extension xmlns="http://ns.adobe.com/air/extension/3.5"><id>a</id></extension>"#);
+    assert_eq!(AnePackage::load("a.ane", &writer.finish()).err(), Some(AneError::MissingLibrary));
+}
\ No newline at end of file